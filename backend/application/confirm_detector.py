@@ -99,6 +99,7 @@ async def detect_confirmation(
             "signal": str,            # "url_change" | "success_text" | "form_gone" |
                                       # "error_detected" | "submitted_ambiguous"
             "screenshot_path": str,   # path to final screenshot
+            "html_path": str,         # path to the final page's HTML
         }
     """
     out_dir = CV_GENERATED_DIR / str(application_id)
@@ -123,6 +124,7 @@ async def detect_confirmation(
         "confirmed": False,
         "signal": "submitted_ambiguous",
         "screenshot_path": "",
+        "html_path": "",
     }
 
     # Run detection for up to timeout_seconds
@@ -220,6 +222,13 @@ async def detect_confirmation(
         log.warning("confirm_detector.screenshot_failed", stage="final", error=str(e))
         result["screenshot_path"] = initial_screenshot
 
+    final_html = out_dir / "confirmation.html"
+    try:
+        final_html.write_text(await page.content(), encoding="utf-8")
+        result["html_path"] = str(final_html)
+    except Exception as e:
+        log.warning("confirm_detector.html_failed", error=str(e))
+
     log.info(
         "confirm_detector.result",
         confirmed=result["confirmed"],
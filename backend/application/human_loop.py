@@ -125,7 +125,9 @@ async def prepare_for_review(
     title = _get_job_title(app)
 
     await _notify_review_ready(application_id, company, title)
-    await _broadcast_sse_review_ready(application_id, company, title, form_url, screenshot_path)
+    await _broadcast_sse_review_ready(
+        application_id, company, title, _get_job_location(app), form_url, screenshot_path
+    )
 
     log.info(
         "human_loop.prepare_complete",
@@ -237,6 +239,12 @@ async def submit_authorized(
                         application_id=application_id,
                     )
 
+                # The form exactly as it is about to be sent, for the desktop
+                # shell's evidence archive
+                out_dir = CV_GENERATED_DIR / str(application_id)
+                out_dir.mkdir(parents=True, exist_ok=True)
+                evidence = await _capture_page(page, out_dir / "submitted_form", application_id)
+
                 # ----------------------------------------------------------
                 # Step 6: Find and click submit button
                 # ----------------------------------------------------------
@@ -301,11 +309,17 @@ async def submit_authorized(
                 # Cancel timeout task
                 _cancel_timeout_task(application_id)
 
+                if confirmation.get("screenshot_path"):
+                    evidence["confirmation_screenshot"] = confirmation["screenshot_path"]
+                if confirmation.get("html_path"):
+                    evidence["confirmation_html"] = confirmation["html_path"]
+
                 final_status = "applied" if confirmation["confirmed"] else "submitted_ambiguous"
                 return {
                     "status": final_status,
                     "signal": confirmation["signal"],
                     "screenshot_path": confirmation.get("screenshot_path", ""),
+                    "evidence": evidence,
                 }
 
             finally:
@@ -320,6 +334,29 @@ async def submit_authorized(
         return {"status": "error", "detail": str(e)}
 
 
+async def _capture_page(page: Any, base: Path, application_id: int) -> dict:
+    """
+    Full-page screenshot and HTML of the page, as <base>.png and <base>.html.
+
+    Returns {"<name>_screenshot": path, "<name>_html": path} for what could be
+    saved, <name> being the file name of `base`.
+    """
+    captured: dict = {}
+    screenshot = base.with_suffix(".png")
+    try:
+        await page.screenshot(path=str(screenshot), full_page=True)
+        captured[f"{base.name}_screenshot"] = str(screenshot)
+    except Exception as e:
+        log.warning("human_loop.capture_failed", kind="screenshot", error=str(e), application_id=application_id)
+    html = base.with_suffix(".html")
+    try:
+        html.write_text(await page.content(), encoding="utf-8")
+        captured[f"{base.name}_html"] = str(html)
+    except Exception as e:
+        log.warning("human_loop.capture_failed", kind="html", error=str(e), application_id=application_id)
+    return captured
+
+
 # ---------------------------------------------------------------------------
 # Session timeout handler
 # ---------------------------------------------------------------------------
@@ -397,6 +434,16 @@ def _get_job_title(app: Application) -> str:
     return app.cv_profile
 
 
+def _get_job_location(app: Application) -> Optional[str]:
+    """The job's location, for the shell's duplicate check (best effort)."""
+    try:
+        if hasattr(app, "job") and app.job:
+            return app.job.location
+    except Exception:
+        pass
+    return None
+
+
 async def _notify_review_ready(
     application_id: int,
     company: str,
@@ -419,6 +466,7 @@ async def _broadcast_sse_review_ready(
     application_id: int,
     company: str,
     title: str,
+    location: Optional[str],
     form_url: str,
     screenshot_path: str,
 ) -> None:
@@ -431,6 +479,7 @@ async def _broadcast_sse_review_ready(
                 "application_id": application_id,
                 "company": company,
                 "title": title,
+                "location": location,
                 "form_url": form_url,
                 "screenshot_path": screenshot_path,
                 "expires_at": (
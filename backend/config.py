@@ -13,7 +13,9 @@ from pydantic_settings import BaseSettings, SettingsConfigDict
 # ---------------------------------------------------------------------------
 
 ROOT_DIR = Path(__file__).parent.parent          # jobbot/
-DATA_DIR = ROOT_DIR / "data"
+# The desktop shell points this at the per-user (or per-profile / portable)
+# data dir; running from source keeps everything under jobbot/data.
+DATA_DIR = Path(os.environ.get("JOBBOT_DATA_DIR") or ROOT_DIR / "data")
 BACKEND_DIR = ROOT_DIR / "backend"
 
 DB_PATH            = DATA_DIR / "jobs.db"
@@ -24,6 +26,19 @@ BROWSER_PROFILES_DIR = DATA_DIR / "browser_profiles"
 LOGS_DIR           = DATA_DIR / "logs"
 BACKUPS_DIR        = DATA_DIR / "backups"
 TEMPLATES_DIR      = BACKEND_DIR / "documents" / "templates"
+# The desktop shell's document library (documents.rs); unset when running
+# from source without it.
+_documents_dir = os.environ.get("JOBBOT_DOCUMENTS_DIR")
+SHELL_DOCUMENTS_DIR = Path(_documents_dir) if _documents_dir else None
+# A webdriver matching the installed Chrome or Edge, kept current by the
+# shell (drivers.rs); unset when there is none.
+_webdriver = os.environ.get("JOBBOT_WEBDRIVER")
+SHELL_WEBDRIVER = Path(_webdriver) if _webdriver else None
+SHELL_WEBDRIVER_BROWSER = os.environ.get("JOBBOT_WEBDRIVER_BROWSER")
+# Per-account browser profiles kept by the shell (browserprofiles.rs); unset
+# when running from source without it.
+_browser_profiles_dir = os.environ.get("JOBBOT_BROWSER_PROFILES_DIR")
+SHELL_BROWSER_PROFILES_DIR = Path(_browser_profiles_dir) if _browser_profiles_dir else None
 
 # Ensure directories exist at import time
 for _d in (DATA_DIR, CV_GENERATED_DIR, CV_SOURCES_DIR, BROWSER_PROFILES_DIR, LOGS_DIR, BACKUPS_DIR):
@@ -7,6 +7,7 @@ from typing import Any, Optional, Sequence
 
 from sqlalchemy import delete, func, select, update
 from sqlalchemy.ext.asyncio import AsyncSession
+from sqlalchemy.orm import selectinload
 
 from backend.config import (
     COMPANY_APPLICATION_RULES_DEFAULT_DAYS,
@@ -168,7 +169,7 @@ async def list_applications(
     limit: int = 50,
     status: Optional[str] = None,
 ) -> tuple[list[Application], Optional[int]]:
-    q = select(Application).order_by(Application.id.desc())
+    q = select(Application).options(selectinload(Application.job)).order_by(Application.id.desc())
     if cursor:
         q = q.where(Application.id < cursor)
     if status:
@@ -193,6 +194,7 @@ async def count_applications_by_status(db: AsyncSession) -> dict[str, int]:
 async def get_pending_reviews(db: AsyncSession) -> list[Application]:
     result = await db.execute(
         select(Application)
+        .options(selectinload(Application.job))
         .where(Application.status == ApplicationStatus.pending_human_review.value)
         .order_by(Application.updated_at.asc())
     )
@@ -469,3 +471,48 @@ async def cleanup_old_records(
     apps_deleted = len(apps_result.fetchall())
 
     return {"jobs_deleted": jobs_deleted, "applications_deleted": apps_deleted}
+
+
+async def list_stale_applications(
+    db: AsyncSession,
+    *,
+    statuses: Sequence[str],
+    older_than_days: int,
+) -> list[Application]:
+    """Applications in one of `statuses` not updated for `older_than_days`, oldest first."""
+    cutoff = _now() - timedelta(days=older_than_days)
+    result = await db.execute(
+        select(Application)
+        .where(Application.status.in_(list(statuses)), Application.updated_at < cutoff)
+        .order_by(Application.updated_at)
+    )
+    return list(result.scalars().all())
+
+
+async def delete_applications(db: AsyncSession, app_ids: Sequence[int]) -> int:
+    """Removes the applications with their events and CV records; their files are the caller's."""
+    if not app_ids:
+        return 0
+    ids = list(app_ids)
+    await db.execute(delete(ApplicationEvent).where(ApplicationEvent.application_id.in_(ids)))
+    await db.execute(delete(CVDocument).where(CVDocument.application_id.in_(ids)))
+    result = await db.execute(
+        delete(Application).where(Application.id.in_(ids)).returning(Application.id)
+    )
+    return len(result.fetchall())
+
+
+async def clear_screenshot_paths(db: AsyncSession, paths: set[str]) -> None:
+    """Forgets form and confirmation screenshots that were deleted."""
+    if not paths:
+        return
+    await db.execute(
+        update(Application)
+        .where(Application.form_screenshot_path.in_(paths))
+        .values(form_screenshot_path=None)
+    )
+    await db.execute(
+        update(Application)
+        .where(Application.confirmation_screenshot_path.in_(paths))
+        .values(confirmation_screenshot_path=None)
+    )
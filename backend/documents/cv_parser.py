@@ -1,7 +1,13 @@
-"""PDF → canonical JSON CV parser using pdfplumber."""
+"""PDF → canonical JSON CV parser using pdfplumber.
+
+The desktop shell has its own reading of the same headers and heuristics
+(src-tauri/src/resume.rs); what it found at upload is kept next to the PDF
+(see parsed_path) and used instead. Changes here belong there too.
+"""
 from __future__ import annotations
 
 import asyncio
+import json
 import re
 from pathlib import Path
 from typing import Optional
@@ -79,6 +85,11 @@ _DATE_RANGE_RE = re.compile(
 # Public API
 # ---------------------------------------------------------------------------
 
+def parsed_path(pdf_path: Path) -> Path:
+    """Where the shell's parse of an uploaded CV is kept."""
+    return Path(pdf_path).with_suffix(".parsed.json")
+
+
 async def parse_cv(pdf_path: Path) -> dict:
     """
     Parse a PDF CV into the canonical JSON structure.
@@ -107,6 +118,16 @@ async def parse_cv(pdf_path: Path) -> dict:
     if not pdf_path.exists():
         raise FileNotFoundError(f"CV PDF not found: {pdf_path}")
 
+    sidecar = parsed_path(pdf_path)
+    if sidecar.exists():
+        try:
+            result = json.loads(sidecar.read_text(encoding="utf-8"))
+        except (OSError, ValueError) as exc:
+            log.warning("cv_parser.parsed_unreadable", path=str(sidecar), error=str(exc))
+        else:
+            log.info("cv_parser.from_shell", path=str(pdf_path))
+            return result
+
     loop = asyncio.get_event_loop()
     result = await loop.run_in_executor(None, _parse_pdf_sync, pdf_path)
 
@@ -0,0 +1,64 @@
+"""Read access to the desktop shell's document library, by document id.
+
+The shell (frontend/src-tauri/src/documents.rs) owns the library: résumés and
+cover letters with their versions, catalogued in ``index.json`` and stored by
+SHA-256 under ``blobs``. The engine only reads it, so an application can name
+the document it was sent with and find the file again later.
+"""
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from typing import Any, Optional
+
+import structlog
+
+from backend.config import SHELL_DOCUMENTS_DIR
+
+log = structlog.get_logger(__name__)
+
+
+def _index() -> list[dict[str, Any]]:
+    if SHELL_DOCUMENTS_DIR is None:
+        return []
+    try:
+        data = json.loads((SHELL_DOCUMENTS_DIR / "index.json").read_text(encoding="utf-8"))
+    except FileNotFoundError:
+        return []
+    except (OSError, ValueError) as exc:
+        log.warning("documents.index_unreadable", error=str(exc))
+        return []
+    return data.get("documents", [])
+
+
+def list_documents(kind: Optional[str] = None, include_retired: bool = False) -> list[dict[str, Any]]:
+    """Documents in the library, optionally of one kind ("resume", "cover_letter")."""
+    return [
+        d for d in _index()
+        if (kind is None or d.get("kind") == kind)
+        and (include_retired or not d.get("retired_at"))
+    ]
+
+
+def get_document(document_id: str) -> Optional[dict[str, Any]]:
+    return next((d for d in _index() if d.get("id") == document_id), None)
+
+
+def resolve(document_id: str, version: Optional[int] = None) -> Optional[Path]:
+    """Path of a document's file: the given version, else the current one.
+
+    Retired documents still resolve, since applications sent with them keep
+    pointing at them.
+    """
+    document = get_document(document_id)
+    if document is None or SHELL_DOCUMENTS_DIR is None:
+        return None
+    versions = document.get("versions", [])
+    if version is None:
+        chosen = versions[-1] if versions else None
+    else:
+        chosen = next((v for v in versions if v.get("version") == version), None)
+    if chosen is None:
+        return None
+    path = SHELL_DOCUMENTS_DIR / "blobs" / chosen["file"]
+    return path if path.is_file() else None
@@ -4,6 +4,7 @@ from __future__ import annotations
 import gzip
 import logging
 import logging.handlers
+import os
 import shutil
 from datetime import datetime, timezone
 from pathlib import Path
@@ -11,18 +12,27 @@ from pathlib import Path
 import structlog
 
 from backend.config import LOGS_DIR, settings
+from backend.security.redaction import RedactingFilter, structlog_processor
 
 
 def setup_logging() -> None:
     date_str = datetime.now(timezone.utc).strftime("%Y-%m-%d")
     log_file = LOGS_DIR / f"jobbot-{date_str}.jsonl"
 
-    # Standard library handler → rotating by day
-    file_handler = logging.FileHandler(str(log_file), encoding="utf-8")
+    # Standard library handler → rotating by day, and by size when the desktop
+    # shell asks for it (it then also enforces the total size of the folder)
+    max_bytes = int(os.environ.get("JOBBOT_LOG_ROTATE_BYTES") or 0)
+    file_handler = logging.handlers.RotatingFileHandler(
+        str(log_file), maxBytes=max_bytes, backupCount=100 if max_bytes else 0, encoding="utf-8"
+    )
     file_handler.setLevel(logging.DEBUG)
 
     console_handler = logging.StreamHandler()
-    console_handler.setLevel(logging.INFO)
+    # The desktop shell's debug mode sets DEBUG and shows this output
+    console_handler.setLevel(logging.DEBUG if settings.debug else logging.INFO)
+
+    for handler in (file_handler, console_handler):
+        handler.addFilter(RedactingFilter())
 
     logging.basicConfig(
         format="%(message)s",
@@ -37,6 +47,7 @@ def setup_logging() -> None:
             structlog.processors.TimeStamper(fmt="iso"),
             structlog.processors.StackInfoRenderer(),
             structlog.processors.format_exc_info,
+            structlog_processor,
             structlog.processors.JSONRenderer(),
         ],
         wrapper_class=structlog.make_filtering_bound_logger(logging.DEBUG),
@@ -35,7 +35,13 @@ from backend.database.crud import (
     list_jobs,
     list_scraper_runs,
 )
+from backend.documents.cv_parser import parsed_path
 from backend.logging_config import setup_logging
+from backend.scrapers.proxy_pool import proxy_pool
+from backend.security.cookie_vault import cookie_vault
+from backend.security.oauth_tokens import PROVIDERS as OAUTH_PROVIDERS, oauth_tokens
+from backend.security.redaction import redactor
+from backend.security.session import SessionTokenMiddleware
 
 log = structlog.get_logger(__name__)
 
@@ -84,6 +90,9 @@ class SSEHub:
 
 
 sse_hub = SSEHub()
+cookie_vault.attach(sse_hub.broadcast)
+proxy_pool.attach(sse_hub.broadcast)
+oauth_tokens.attach(sse_hub.broadcast)
 
 
 # ---------------------------------------------------------------------------
@@ -135,7 +144,9 @@ async def lifespan(app: FastAPI):
     # Start scheduler if setup complete
     if settings.setup_complete:
         from backend.scrapers.scheduler import start_scheduler
-        start_scheduler()
+        async with AsyncSessionLocal() as db:
+            paused = await _automation_paused(db)
+        start_scheduler(paused=paused)
         log.info("scheduler.started")
 
     yield
@@ -155,6 +166,9 @@ app = FastAPI(
     lifespan=lifespan,
 )
 
+# Added first so CORS stays outermost and 401s still carry CORS headers
+app.add_middleware(SessionTokenMiddleware)
+
 app.add_middleware(
     CORSMiddleware,
     allow_origins=["http://localhost:3000"],
@@ -213,13 +227,15 @@ async def get_jobs(
     status: Optional[str] = Query(None),
     cv_profile: Optional[str] = Query(None),
     search: Optional[str] = Query(None),
+    describe: bool = Query(False),
     db: AsyncSession = Depends(get_db),
 ):
+    """`describe` adds each job's description, for the desktop shell's job alerts."""
     jobs, next_cursor = await list_jobs(
         db, cursor=cursor, limit=limit, site=site, status=status, cv_profile=cv_profile, search=search
     )
     return {
-        "items": [_serialize_job(j) for j in jobs],
+        "items": [_serialize_job(j, describe=describe) for j in jobs],
         "next_cursor": next_cursor,
     }
 
@@ -229,6 +245,34 @@ async def get_job_counts(db: AsyncSession = Depends(get_db)):
     return await count_jobs_by_status(db)
 
 
+@app.post("/api/jobs/intake")
+async def intake_job(body: dict, db: AsyncSession = Depends(get_db)):
+    """A posting URL added by hand: the desktop shell's clipboard watcher, or the
+    browser extension, which also knows the title and company as the page shows them.
+    Sent again with what the shell fetched itself, it fills in the stored job."""
+    from backend.scrapers.intake import add_job_from_url, is_described
+    url = str(body.get("url") or "").strip()
+    try:
+        job, is_new = await add_job_from_url(
+            db,
+            url,
+            str(body.get("source") or "manual"),
+            title=body.get("title") or None,
+            company=body.get("company") or None,
+            location=body.get("location") or None,
+            description=body.get("description") or None,
+        )
+    except ValueError as exc:
+        raise HTTPException(status_code=422, detail=str(exc))
+    await sse_hub.broadcast("job_added", {"job_id": job.id, "site": job.site, "new": is_new})
+    return {
+        "job_id": job.id,
+        "new": is_new,
+        "described": is_described(job),
+        "job": _serialize_job(job),
+    }
+
+
 # ---------------------------------------------------------------------------
 # Applications
 # ---------------------------------------------------------------------------
@@ -386,6 +430,51 @@ async def trigger_scraper(site: str):
     return {"status": "triggered", "site": site, "task_id": str(uuid.uuid4())}
 
 
+# ---------------------------------------------------------------------------
+# Automation (scheduled scraper runs)
+# ---------------------------------------------------------------------------
+
+_run_all_task: Optional[asyncio.Task] = None
+
+
+@app.get("/api/automation")
+async def get_automation(db: AsyncSession = Depends(get_db)):
+    return {
+        "paused": await _automation_paused(db),
+        "running": _run_all_task is not None and not _run_all_task.done(),
+    }
+
+
+@app.post("/api/automation/pause")
+async def pause_automation(db: AsyncSession = Depends(get_db)):
+    """Stop scheduled runs until resumed; kept across restarts."""
+    from backend.database.crud import set_setting
+    from backend.scrapers.scheduler import pause_scheduler
+    await set_setting(db, "automation_paused", "true")
+    pause_scheduler()
+    await sse_hub.broadcast("automation_changed", {"paused": True})
+    return {"paused": True}
+
+
+@app.post("/api/automation/resume")
+async def resume_automation(db: AsyncSession = Depends(get_db)):
+    from backend.database.crud import set_setting
+    from backend.scrapers.scheduler import resume_scheduler
+    await set_setting(db, "automation_paused", "false")
+    resume_scheduler()
+    await sse_hub.broadcast("automation_changed", {"paused": False})
+    return {"paused": False}
+
+
+@app.post("/api/automation/run")
+async def run_automation_now():
+    """Run every scraper once now, paused or not. No-op while a run is going."""
+    global _run_all_task
+    if _run_all_task is None or _run_all_task.done():
+        _run_all_task = asyncio.create_task(_run_all_scrapers())
+    return {"status": "triggered"}
+
+
 # ---------------------------------------------------------------------------
 # CV
 # ---------------------------------------------------------------------------
@@ -422,6 +511,7 @@ async def delete_cv_source_endpoint(source_id: int, db: AsyncSession = Depends(g
     await db.commit()
     if file_path.exists():
         file_path.unlink()
+    parsed_path(file_path).unlink(missing_ok=True)
     return {"status": "deleted", "id": source_id}
 
 
@@ -490,13 +580,18 @@ async def complete_setup(db: AsyncSession = Depends(get_db)):
     from backend.database.crud import set_setting
     await set_setting(db, "setup_complete", "true")
     from backend.scrapers.scheduler import start_scheduler
-    start_scheduler()
+    start_scheduler(paused=await _automation_paused(db))
     return {"status": "complete"}
 
 
 @app.post("/api/setup/upload-cv")
 async def upload_cv(request: Request, db: AsyncSession = Depends(get_db)):
-    """Accept CV PDF upload, save to data/cv_sources/, and create a CVSource record."""
+    """Accept CV PDF upload, save to data/cv_sources/, and create a CVSource record.
+
+    The desktop shell reads the CV itself and sends the result as `parsed`,
+    kept next to the PDF for parse_cv to use instead of reading it again.
+    """
+    import json
     import re
     form = await request.form()
     file = form.get("file")
@@ -516,6 +611,14 @@ async def upload_cv(request: Request, db: AsyncSession = Depends(get_db)):
     dest_filename = f"{existing_count + 1}_{slug}.pdf"
     dest = CV_SOURCES_DIR / dest_filename
     dest.write_bytes(content)
+    parsed = form.get("parsed")
+    if isinstance(parsed, str) and parsed:
+        try:
+            canonical = json.loads(parsed)
+        except ValueError:
+            raise HTTPException(status_code=422, detail="parsed is not JSON")
+        if isinstance(canonical, dict):
+            parsed_path(dest).write_text(json.dumps(canonical, ensure_ascii=False), encoding="utf-8")
 
     source = await create_cv_source(
         db, name=name, filename=original_filename, file_path=str(dest)
@@ -570,6 +673,151 @@ async def trigger_backup():
     return {"status": "complete", "path": str(dest)}
 
 
+# ---------------------------------------------------------------------------
+# Retention (desktop shell, see frontend/src-tauri/src/retention.rs)
+# ---------------------------------------------------------------------------
+
+@app.post("/api/retention/purge")
+async def purge_retained(body: dict, db: AsyncSession = Depends(get_db)):
+    """What the shell's retention policy takes from the engine: applications in
+    `statuses` not updated for `applications_days`, with their events, CV records and
+    files, and screenshots older than `screenshots_days` (0 keeps either). Only listed
+    unless `dry_run` is false."""
+    import shutil
+    import time
+    from pathlib import Path as _Path
+    from backend.config import CV_GENERATED_DIR
+    from backend.database.crud import (
+        clear_screenshot_paths,
+        delete_applications,
+        list_stale_applications,
+    )
+    dry_run = body.get("dry_run", True) is not False
+    statuses = [str(s) for s in body.get("statuses") or []]
+    try:
+        applications_days = int(body.get("applications_days") or 0)
+        screenshots_days = int(body.get("screenshots_days") or 0)
+    except (TypeError, ValueError):
+        raise HTTPException(status_code=400, detail="days must be whole numbers")
+
+    stale = []
+    if applications_days > 0 and statuses:
+        stale = await list_stale_applications(
+            db, statuses=statuses, older_than_days=applications_days
+        )
+    stale_ids = {a.id for a in stale}
+    screenshots = []
+    if screenshots_days > 0 and CV_GENERATED_DIR.is_dir():
+        cutoff = time.time() - screenshots_days * 86400
+        for path in sorted(CV_GENERATED_DIR.glob("*/*.png")):
+            # Those of applications going anyway are counted with them
+            if not path.parent.name.isdigit() or int(path.parent.name) in stale_ids:
+                continue
+            stat = path.stat()
+            if stat.st_mtime < cutoff:
+                screenshots.append({
+                    "application_id": int(path.parent.name),
+                    "path": str(path),
+                    "bytes": stat.st_size,
+                    "modified_at": datetime.fromtimestamp(stat.st_mtime, timezone.utc).isoformat(),
+                })
+
+    if not dry_run:
+        removed = {s["path"] for s in screenshots}
+        await delete_applications(db, list(stale_ids))
+        await clear_screenshot_paths(db, removed)
+        await db.commit()
+        for path in removed:
+            _Path(path).unlink(missing_ok=True)
+        for app_id in stale_ids:
+            shutil.rmtree(CV_GENERATED_DIR / str(app_id), ignore_errors=True)
+        log.info("retention.purged", applications=len(stale_ids), screenshots=len(removed))
+
+    return {
+        "dry_run": dry_run,
+        "applications": [
+            {
+                "id": a.id,
+                "company": a.company,
+                "status": a.status,
+                "updated_at": a.updated_at.isoformat() if a.updated_at else None,
+            }
+            for a in stale
+        ],
+        "screenshots": screenshots,
+    }
+
+
+# ---------------------------------------------------------------------------
+# Session cookies (desktop shell vault, see backend/security/cookie_vault.py)
+# ---------------------------------------------------------------------------
+
+@app.get("/api/sessions/{site}")
+async def collect_session(site: str):
+    jar = cookie_vault.collect(site)
+    if jar is None:
+        raise HTTPException(status_code=404, detail="No session waiting for this site")
+    return jar
+
+
+@app.post("/api/sessions/{site}")
+async def deliver_session(site: str, body: dict):
+    cookies = body.get("cookies")
+    if not isinstance(cookies, list):
+        raise HTTPException(status_code=400, detail="cookies must be a list")
+    cookie_vault.deliver(site, cookies)
+    return {"status": "ok"}
+
+
+# ---------------------------------------------------------------------------
+# Proxy pool (desktop shell, see backend/scrapers/proxy_pool.py)
+# ---------------------------------------------------------------------------
+
+@app.post("/api/proxy-pool/{site}")
+async def assign_proxy(site: str, body: dict):
+    proxy = body.get("proxy")
+    if proxy is not None and not (
+        isinstance(proxy, dict) and isinstance(proxy.get("id"), str) and isinstance(proxy.get("server"), str)
+    ):
+        raise HTTPException(status_code=400, detail="proxy must be null or have an id and server")
+    proxy_pool.assign(site, proxy)
+    return {"status": "ok"}
+
+
+@app.post("/api/browser-profiles/{site}/release")
+async def release_browser_profile(site: str):
+    """Closes the site's browser so the shell can reset or wipe its profile."""
+    from backend.scrapers.browser_pool import browser_pool
+    await browser_pool.close_context(site)
+    return {"status": "ok"}
+
+
+# ---------------------------------------------------------------------------
+# Sign-in tokens (desktop shell, see backend/security/oauth_tokens.py)
+# ---------------------------------------------------------------------------
+
+@app.post("/api/oauth/{provider}")
+async def deliver_oauth_token(provider: str, body: dict):
+    if provider not in OAUTH_PROVIDERS:
+        raise HTTPException(status_code=404, detail="Unknown provider")
+    access_token = body.get("access_token")
+    expires_at = body.get("expires_at")
+    if not isinstance(access_token, str) or not access_token:
+        raise HTTPException(status_code=400, detail="access_token is required")
+    if expires_at is not None and not isinstance(expires_at, (int, float)):
+        raise HTTPException(status_code=400, detail="expires_at must be a timestamp")
+    oauth_tokens.deliver(provider, access_token, expires_at)
+    return {"status": "ok"}
+
+
+@app.delete("/api/oauth/{provider}")
+async def clear_oauth_token(provider: str):
+    if provider not in OAUTH_PROVIDERS:
+        raise HTTPException(status_code=404, detail="Unknown provider")
+    oauth_tokens.clear(provider)
+    return {"status": "ok"}
+
+
 # ---------------------------------------------------------------------------
 # Notifications
 # ---------------------------------------------------------------------------
@@ -594,6 +842,34 @@ async def _run_scraper(site: str) -> None:
         await sse_hub.broadcast("scraper_error", {"site": site, "error": str(exc)})
 
 
+async def _run_all_scrapers() -> None:
+    # run_progress / run_finished: the desktop shell's progress contract
+    run_id = str(uuid.uuid4())
+
+    async def on_progress(done: int, total: int) -> None:
+        await sse_hub.broadcast("run_progress", {
+            "run_id": run_id, "label": "Scraping", "current": done, "total": total,
+        })
+
+    try:
+        from backend.scrapers.scheduler import run_all_scrapers
+        results = await run_all_scrapers(on_progress)
+        await sse_hub.broadcast("run_finished", {
+            "run_id": run_id,
+            "label": "Scraping",
+            "succeeded": sum(1 for r in results if r.get("status") != "failed"),
+            "total": len(results),
+        })
+        await sse_hub.broadcast("scraper_finished", {"site": "all"})
+    except Exception as exc:
+        log.error("scraper.run_all_failed", error=str(exc))
+        await sse_hub.broadcast("scraper_error", {"site": "all", "error": str(exc)})
+
+
+async def _automation_paused(db: AsyncSession) -> bool:
+    return await get_setting(db, "automation_paused", "false") == "true"
+
+
 async def _generate_cv_task(application_id: int, task_id: str) -> None:
     try:
         await sse_hub.broadcast("cv_generation_started", {
@@ -620,6 +896,9 @@ async def _generate_cv_task(application_id: int, task_id: str) -> None:
 
             # Parse and store canonical JSON before adapt_cv runs
             canonical = await parse_cv(cv_path)
+            redactor.add_names([canonical.get("name")])
+            if canonical.get("name"):
+                await sse_hub.broadcast("redaction_names", {"names": [canonical["name"]]})
             app.cv_canonical_json = canonical
             await db.flush()
             await db.commit()
@@ -647,6 +926,8 @@ async def _submit_application(application_id: int) -> None:
         await sse_hub.broadcast("application_submitted", {
             "application_id": application_id,
             "status": result.get("status"),
+            # Screenshot and HTML paths, kept by the desktop shell's evidence archive
+            "evidence": result.get("evidence", {}),
         })
     except Exception as exc:
         log.error("application.submit_failed", application_id=application_id, error=str(exc))
@@ -656,8 +937,8 @@ async def _submit_application(application_id: int) -> None:
 # Serializers
 # ---------------------------------------------------------------------------
 
-def _serialize_job(j) -> dict:
-    return {
+def _serialize_job(j, describe: bool = False) -> dict:
+    serialized = {
         "id": j.id,
         "site": j.site,
         "title": j.title,
@@ -671,6 +952,9 @@ def _serialize_job(j) -> dict:
         "posted_at": j.posted_at.isoformat() if j.posted_at else None,
         "scraped_at": j.scraped_at.isoformat() if j.scraped_at else None,
     }
+    if describe:
+        serialized["description"] = j.description
+    return serialized
 
 
 def _serialize_application(a) -> dict:
@@ -680,6 +964,12 @@ def _serialize_application(a) -> dict:
         "status": a.status,
         "cv_profile": a.cv_profile,
         "company": a.company,
+        # The job's board, location and salary; only when the query loaded the job with it
+        "site": a.__dict__["job"].site if a.__dict__.get("job") is not None else None,
+        "location": a.__dict__["job"].location if a.__dict__.get("job") is not None else None,
+        "salary_raw": a.__dict__["job"].salary_raw if a.__dict__.get("job") is not None else None,
+        # For the desktop app's full-text search (search.rs)
+        "description": a.__dict__["job"].description if a.__dict__.get("job") is not None else None,
         "quality_score": a.quality_score,
         "authorized_by_human": a.authorized_by_human,
         "authorized_at": a.authorized_at.isoformat() if a.authorized_at else None,
@@ -36,7 +36,9 @@ except ImportError:
             "Run: pip install patchright"
         ) from exc
 
-from backend.config import BROWSER_PROFILES_DIR, COOKIE_TTL, settings
+from backend.config import BROWSER_PROFILES_DIR, COOKIE_TTL, SHELL_BROWSER_PROFILES_DIR, settings
+from backend.scrapers.proxy_pool import proxy_pool
+from backend.security.cookie_vault import cookie_vault
 
 log = structlog.get_logger(__name__)
 
@@ -73,15 +75,68 @@ USER_AGENTS: list[str] = [
 ]
 
 
+LAUNCH_ARGS: list[str] = [
+    "--no-sandbox",
+    "--disable-setuid-sandbox",
+    "--disable-blink-features=AutomationControlled",
+    "--disable-dev-shm-usage",
+    "--disable-infobars",
+    "--window-size=1366,768",
+    "--disable-extensions",
+    "--disable-gpu",
+    "--no-first-run",
+    "--no-default-browser-check",
+]
+
+
+# Where anti-bot services send a browser they want to check
+CAPTCHA_MARKERS: tuple[str, ...] = (
+    "captcha",
+    "/cdn-cgi/challenge-platform",
+    "challenges.cloudflare.com",
+    "/sorry/index",
+)
+
+
+def _outcome(status: int, url: str) -> str:
+    """How a page load went, as the shell's proxy pool counts it."""
+    lowered = url.lower()
+    if any(marker in lowered for marker in CAPTCHA_MARKERS):
+        return "captcha"
+    if status in (403, 429):
+        return "ban"
+    return "ok"
+
+
+def _managed_profile(site: str) -> Optional[Path]:
+    """The desktop shell's active profile directory for *site*, if it keeps
+    one (frontend/src-tauri/src/browserprofiles.rs)."""
+    if SHELL_BROWSER_PROFILES_DIR is None:
+        return None
+    try:
+        catalog = json.loads(
+            (SHELL_BROWSER_PROFILES_DIR / "profiles.json").read_text(encoding="utf-8")
+        )
+    except (OSError, ValueError):
+        return None
+    for profile in catalog.get("profiles", []):
+        if profile.get("site") == site and profile.get("active"):
+            return SHELL_BROWSER_PROFILES_DIR / profile["id"]
+    return None
+
+
 class BrowserPool:
     """Singleton-style pool that manages a single Chromium instance with
     per-site browser contexts.  Cookies are persisted to disk and reloaded
-    on subsequent runs (respecting COOKIE_TTL per site).
+    on subsequent runs (respecting COOKIE_TTL per site).  A site with a
+    profile managed by the desktop shell gets a persistent context in that
+    profile's directory instead, which keeps its own cookies and storage.
     """
 
     _playwright: Optional[Any] = None          # AsyncPlaywright instance
     _browser: Optional[Any] = None             # Browser instance
     _contexts: dict[str, Any] = {}             # site → BrowserContext
+    _persistent: set[str] = set()              # sites in a managed profile
     _lock: asyncio.Lock = asyncio.Lock()
 
     # ------------------------------------------------------------------
@@ -94,13 +149,8 @@ class BrowserPool:
             if site in self._contexts:
                 return self._contexts[site]
 
-            await self._ensure_browser()
-
             import random
-            user_agent = random.choice(USER_AGENTS)
-
-            context: Any = await self._browser.new_context(  # type: ignore[union-attr]
-                user_agent=user_agent,
+            options: dict[str, Any] = dict(
                 locale="es-ES",
                 timezone_id="Europe/Madrid",
                 geolocation={"latitude": 40.4168, "longitude": -3.7038},
@@ -112,6 +162,32 @@ class BrowserPool:
                 },
             )
 
+            proxy = await proxy_pool.for_site(site)
+            if proxy is not None:
+                options["proxy"] = proxy
+
+            profile_dir = _managed_profile(site)
+            if profile_dir is not None:
+                await self._ensure_playwright()
+                profile_dir.mkdir(parents=True, exist_ok=True)
+                # The same account should look like the same browser each time
+                user_agent = random.Random(profile_dir.name).choice(USER_AGENTS)
+                context: Any = await self._playwright.chromium.launch_persistent_context(  # type: ignore[union-attr]
+                    str(profile_dir),
+                    headless=True,
+                    args=LAUNCH_ARGS,
+                    user_agent=user_agent,
+                    **options,
+                )
+                self._persistent.add(site)
+                log.debug("browser_pool.managed_profile", site=site, profile=profile_dir.name)
+            else:
+                await self._ensure_browser()
+                context = await self._browser.new_context(  # type: ignore[union-attr]
+                    user_agent=random.choice(USER_AGENTS),
+                    **options,
+                )
+
             # Inject stealth JS to mask automation signals
             await context.add_init_script("""
                 Object.defineProperty(navigator, 'webdriver', {get: () => undefined});
@@ -120,8 +196,15 @@ class BrowserPool:
                 window.chrome = { runtime: {} };
             """)
 
-            # Load saved cookies if not expired
-            cookies_loaded = await self._load_cookies(site, context)
+            if proxy is not None:
+                async def on_response(response: Any) -> None:
+                    if response.request.resource_type == "document":
+                        await proxy_pool.report(site, _outcome(response.status, response.url))
+
+                context.on("response", on_response)
+
+            # Load saved cookies if not expired; a managed profile has its own
+            cookies_loaded = site in self._persistent or await self._load_cookies(site, context)
             if cookies_loaded:
                 log.debug("browser_pool.cookies_loaded", site=site)
             else:
@@ -132,7 +215,19 @@ class BrowserPool:
             return context
 
     async def save_cookies(self, site: str, context: Any) -> None:
-        """Persist cookies for *site* to disk."""
+        """Persist cookies for *site* to the shell's vault, or to disk."""
+        if site in self._persistent:
+            return
+        if cookie_vault.enabled:
+            try:
+                cookies = await context.cookies()
+                expires_at = time.time() + COOKIE_TTL.get(site, 24) * 3600
+                await cookie_vault.save(site, cookies, expires_at)
+                log.debug("browser_pool.cookies_saved", site=site, count=len(cookies), vault=True)
+            except Exception as exc:
+                log.warning("browser_pool.cookies_save_error", site=site, error=str(exc))
+            return
+
         cookies_dir = BROWSER_PROFILES_DIR / site
         cookies_dir.mkdir(parents=True, exist_ok=True)
         cookies_file = cookies_dir / "cookies.json"
@@ -152,6 +247,7 @@ class BrowserPool:
         """Close and remove the context for *site*."""
         async with self._lock:
             ctx = self._contexts.pop(site, None)
+            self._persistent.discard(site)
             if ctx:
                 try:
                     await ctx.close()
@@ -170,6 +266,7 @@ class BrowserPool:
                 except Exception:
                     pass
             self._contexts.clear()
+            self._persistent.clear()
 
             if self._browser:
                 try:
@@ -193,27 +290,20 @@ class BrowserPool:
     # Internal helpers
     # ------------------------------------------------------------------
 
-    async def _ensure_browser(self) -> None:
-        """Start Playwright + launch Chromium if not already running."""
+    async def _ensure_playwright(self) -> None:
+        """Start Playwright if not already running."""
         if self._playwright is None:
             self._playwright = await async_playwright().start()
             log.debug("browser_pool.playwright_started", engine="patchright" if _PATCHRIGHT_AVAILABLE else "playwright")
 
+    async def _ensure_browser(self) -> None:
+        """Start Playwright + launch Chromium if not already running."""
+        await self._ensure_playwright()
+
         if self._browser is None:
-            self._browser = await self._playwright.chromium.launch(
+            self._browser = await self._playwright.chromium.launch(  # type: ignore[union-attr]
                 headless=True,
-                args=[
-                    "--no-sandbox",
-                    "--disable-setuid-sandbox",
-                    "--disable-blink-features=AutomationControlled",
-                    "--disable-dev-shm-usage",
-                    "--disable-infobars",
-                    "--window-size=1366,768",
-                    "--disable-extensions",
-                    "--disable-gpu",
-                    "--no-first-run",
-                    "--no-default-browser-check",
-                ],
+                args=LAUNCH_ARGS,
             )
             log.debug("browser_pool.browser_launched")
 
@@ -222,6 +312,13 @@ class BrowserPool:
 
         Returns True if cookies were loaded, False otherwise.
         """
+        if cookie_vault.enabled:
+            # The shell drops expired jars itself
+            cookies = await cookie_vault.load(site)
+            if cookies:
+                await context.add_cookies(cookies)
+            return bool(cookies)
+
         cookies_file = BROWSER_PROFILES_DIR / site / "cookies.json"
         if not cookies_file.exists():
             return False
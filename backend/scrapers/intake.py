@@ -0,0 +1,117 @@
+"""Jobs added by hand from a posting URL (the desktop shell's clipboard watcher
+and the browser extension).
+
+The job is stored like a scraped one, so qualification and the rest of the
+pipeline treat it the same. Unless the caller already knows the title, the
+page is fetched once for its title, company and summary where it offers them
+(Open Graph tags or <title>); a page that can't be fetched still leaves the
+URL to be looked at, and the details can be sent again later (the shell
+fetches the posting itself when a site turns this fetch away): they fill in
+what the stored job is missing.
+"""
+from __future__ import annotations
+
+import hashlib
+import html
+import re
+from typing import Optional
+from urllib.parse import urlparse
+
+import httpx
+import structlog
+from sqlalchemy.ext.asyncio import AsyncSession
+
+from backend.database.crud import upsert_job
+from backend.database.models import Job, JobStatus
+
+log = structlog.get_logger(__name__)
+
+_FETCH_TIMEOUT = 10.0
+_MAX_TITLE = 512
+_MAX_COMPANY = 256
+_MAX_LOCATION = 256
+_META = r'<meta[^>]+(?:property|name)=["\']{name}["\'][^>]+content=["\']([^"\']*)["\']'
+
+
+def site_for(url: str) -> str:
+    """The board a URL belongs to: "www.infojobs.net" is "infojobs"."""
+    host = (urlparse(url).hostname or "").lower()
+    labels = [part for part in host.split(".") if part not in ("www", "es", "m", "jobs", "careers")]
+    if len(labels) >= 2:
+        return labels[-2]
+    return labels[0] if labels else "manual"
+
+
+def _meta(page: str, name: str) -> Optional[str]:
+    match = re.search(_META.format(name=re.escape(name)), page, re.IGNORECASE)
+    return html.unescape(match.group(1)).strip() if match else None
+
+
+async def _describe(url: str) -> tuple[Optional[str], Optional[str], Optional[str]]:
+    """Title, company and summary of the posting, as far as the page says."""
+    try:
+        async with httpx.AsyncClient(timeout=_FETCH_TIMEOUT, follow_redirects=True) as client:
+            response = await client.get(url, headers={"User-Agent": "Mozilla/5.0"})
+            response.raise_for_status()
+            page = response.text
+    except Exception as exc:
+        log.info("intake.fetch_failed", url=url, error=str(exc))
+        return None, None, None
+    title = _meta(page, "og:title")
+    if not title:
+        match = re.search(r"<title[^>]*>(.*?)</title>", page, re.IGNORECASE | re.DOTALL)
+        title = html.unescape(match.group(1)).strip() if match else None
+    company = _meta(page, "og:site_name")
+    summary = _meta(page, "og:description") or _meta(page, "description")
+    return title, company, summary
+
+
+async def add_job_from_url(
+    db: AsyncSession,
+    url: str,
+    source: str,
+    title: Optional[str] = None,
+    company: Optional[str] = None,
+    location: Optional[str] = None,
+    description: Optional[str] = None,
+) -> tuple[Job, bool]:
+    """Stores the posting at `url` as a scraped job, or fills in what an earlier
+    intake of it lacked. Returns (job, is_new)."""
+    parsed = urlparse(url)
+    if parsed.scheme not in ("http", "https") or not parsed.hostname:
+        raise ValueError("not a web address")
+    site = site_for(url)
+    external_id = hashlib.sha256(url.encode()).hexdigest()[:32]
+    if not title:
+        title, found_company, summary = await _describe(url)
+        company = company or found_company
+        description = description or summary
+    job, is_new = await upsert_job(
+        db,
+        site=site,
+        external_id=external_id,
+        url=url,
+        title=(title or url)[:_MAX_TITLE],
+        company=(company or "")[:_MAX_COMPANY],
+        location=location[:_MAX_LOCATION] if location else None,
+        description=description,
+        status=JobStatus.scraped.value,
+        raw_data={"intake": source},
+    )
+    if not is_new:
+        if title and job.title == job.url:
+            job.title = title[:_MAX_TITLE]
+        if company and not job.company:
+            job.company = company[:_MAX_COMPANY]
+        if location and not job.location:
+            job.location = location[:_MAX_LOCATION]
+        if description and not job.description:
+            job.description = description
+    await db.commit()
+    log.info("intake.job_added", site=site, job_id=job.id, new=is_new, source=source)
+    return job, is_new
+
+
+def is_described(job: Job) -> bool:
+    """Whether the job has the posting's text to qualify on."""
+    return bool(job.description)
@@ -0,0 +1,85 @@
+"""Per-site proxies handed out by the desktop shell's proxy pool.
+
+With JOBBOT_PROXY_POOL=1 (set by the shell) browser_pool asks for a site's
+proxy before opening its browser context: this broadcasts `proxy_requested`
+and waits briefly for the shell to answer with POST /api/proxy-pool/{site}
+(null when the site should go direct). The shell posts again whenever it
+moves a site to another proxy; the new one applies from the site's next
+context. What each page load ran into is reported back as `proxy_outcome`
+(ok, ban or captcha) so the shell can keep rates per proxy. Events carry
+the site and proxy id only, never addresses or credentials. Running from
+source without the variable uses no proxy pool.
+"""
+from __future__ import annotations
+
+import asyncio
+import os
+from typing import Any, Awaitable, Callable, Optional
+
+import structlog
+
+log = structlog.get_logger(__name__)
+
+POOL_ENV = "JOBBOT_PROXY_POOL"
+# The shell answers from memory; anything slower means it isn't there
+REQUEST_TIMEOUT = 5.0
+OUTCOMES = ("ok", "ban", "captcha")
+
+Broadcast = Callable[[str, dict], Awaitable[None]]
+
+
+class ProxyPool:
+    def __init__(self, enabled: Optional[bool] = None) -> None:
+        self.enabled = os.environ.get(POOL_ENV) == "1" if enabled is None else enabled
+        self._broadcast: Optional[Broadcast] = None
+        self._assigned: dict[str, Optional[dict]] = {}
+        self._waiting: dict[str, asyncio.Future] = {}
+
+    def attach(self, broadcast: Broadcast) -> None:
+        """Wired to the SSE hub by main.py, which imports this module."""
+        self._broadcast = broadcast
+
+    async def for_site(self, site: str) -> Optional[dict[str, Any]]:
+        """Playwright's ``proxy`` option for *site*, or None to go direct."""
+        if not self.enabled or not self._broadcast:
+            return None
+        if site not in self._assigned:
+            future = self._waiting.get(site)
+            if future is None or future.done():
+                future = asyncio.get_running_loop().create_future()
+                self._waiting[site] = future
+            await self._broadcast("proxy_requested", {"site": site})
+            try:
+                await asyncio.wait_for(asyncio.shield(future), REQUEST_TIMEOUT)
+            except asyncio.TimeoutError:
+                log.warning("proxy_pool.no_answer", site=site)
+                return None
+            finally:
+                if self._waiting.get(site) is future:
+                    del self._waiting[site]
+        proxy = self._assigned.get(site)
+        if proxy is None:
+            return None
+        return {k: proxy[k] for k in ("server", "username", "password") if proxy.get(k)}
+
+    def assign(self, site: str, proxy: Optional[dict]) -> None:
+        """The shell's answer to `proxy_requested`, or a rotation."""
+        previous = self._assigned.get(site)
+        self._assigned[site] = proxy
+        if (previous or {}).get("id") != (proxy or {}).get("id"):
+            log.info("proxy_pool.assigned", site=site, proxy=(proxy or {}).get("id"))
+        future = self._waiting.get(site)
+        if future is not None and not future.done():
+            future.set_result(None)
+
+    async def report(self, site: str, outcome: str) -> None:
+        """Counts a page load against the proxy *site* is using."""
+        proxy = self._assigned.get(site)
+        if proxy is None or outcome not in OUTCOMES or not self._broadcast:
+            return
+        await self._broadcast(
+            "proxy_outcome", {"site": site, "proxy_id": proxy["id"], "outcome": outcome}
+        )
+
+
+proxy_pool = ProxyPool()
@@ -2,7 +2,7 @@
 from __future__ import annotations
 
 import asyncio
-from typing import Optional, TYPE_CHECKING
+from typing import Awaitable, Callable, Optional, TYPE_CHECKING
 
 import structlog
 
@@ -86,11 +86,12 @@ def get_scheduler() -> Optional[object]:
     return _scheduler
 
 
-def start_scheduler() -> None:
+def start_scheduler(paused: bool = False) -> None:
     """Create and start the APScheduler AsyncIOScheduler.
 
     Uses a SQLAlchemyJobStore backed by the same jobs.db SQLite database so
-    that scheduled job state survives restarts.
+    that scheduled job state survives restarts. With ``paused`` the jobs are
+    registered but nothing fires until resume_scheduler().
     """
     global _scheduler
 
@@ -146,9 +147,22 @@ def start_scheduler() -> None:
             job_id=job_id,
         )
 
-    scheduler.start()
+    scheduler.start(paused=paused)
     _scheduler = scheduler
-    log.info("scheduler.started", total_jobs=len(SCRAPER_SCHEDULE))
+    log.info("scheduler.started", total_jobs=len(SCRAPER_SCHEDULE), paused=paused)
+
+
+def pause_scheduler() -> None:
+    """Stop scheduled runs from firing; a run already in progress finishes."""
+    if _scheduler is not None:
+        _scheduler.pause()  # type: ignore[union-attr]
+        log.info("scheduler.paused")
+
+
+def resume_scheduler() -> None:
+    if _scheduler is not None:
+        _scheduler.resume()  # type: ignore[union-attr]
+        log.info("scheduler.resumed")
 
 
 def stop_scheduler() -> None:
@@ -168,6 +182,25 @@ def stop_scheduler() -> None:
 # Manual trigger
 # ---------------------------------------------------------------------------
 
+async def run_all_scrapers(
+    on_progress: Optional[Callable[[int, int], Awaitable[None]]] = None,
+) -> list[dict]:
+    """Run every scheduled scraper once, one after another ("Run now").
+
+    ``on_progress(done, total)`` is awaited before the first scraper and after
+    each one.
+    """
+    sites = list(SCRAPER_SCHEDULE)
+    results: list[dict] = []
+    if on_progress:
+        await on_progress(0, len(sites))
+    for site in sites:
+        results.append(await run_scraper_by_name(site))
+        if on_progress:
+            await on_progress(len(results), len(sites))
+    return results
+
+
 async def run_scraper_by_name(site: str) -> dict:
     """Instantiate and run a scraper by its site name.
 
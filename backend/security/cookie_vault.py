@@ -0,0 +1,73 @@
+"""Session cookies kept by the desktop shell instead of in plain files.
+
+With JOBBOT_COOKIE_VAULT=1 (set by the shell) browser_pool stops writing
+browser_profiles/<site>/cookies.json. Saving a jar parks it here and
+broadcasts `session_saved`; the shell collects it with
+GET /api/sessions/{site}, seals it with its keychain key and tracks its
+expiry. Loading broadcasts `session_requested` and waits briefly for the
+shell to answer with POST /api/sessions/{site}. Events carry the site and
+expiry only, never cookie values. Running from source without the variable
+keeps the cookies.json files.
+"""
+from __future__ import annotations
+
+import asyncio
+import os
+from typing import Awaitable, Callable, Optional
+
+import structlog
+
+log = structlog.get_logger(__name__)
+
+VAULT_ENV = "JOBBOT_COOKIE_VAULT"
+# The shell answers from a local file; anything slower means it isn't there
+REQUEST_TIMEOUT = 5.0
+
+Broadcast = Callable[[str, dict], Awaitable[None]]
+
+
+class CookieVault:
+    def __init__(self, enabled: Optional[bool] = None) -> None:
+        self.enabled = os.environ.get(VAULT_ENV) == "1" if enabled is None else enabled
+        self._broadcast: Optional[Broadcast] = None
+        self._outbox: dict[str, dict] = {}
+        self._waiting: dict[str, asyncio.Future] = {}
+
+    def attach(self, broadcast: Broadcast) -> None:
+        """Wired to the SSE hub by main.py, which imports this module."""
+        self._broadcast = broadcast
+
+    async def save(self, site: str, cookies: list[dict], expires_at: float) -> None:
+        self._outbox[site] = {"cookies": cookies, "expires_at": expires_at}
+        if self._broadcast:
+            await self._broadcast("session_saved", {"site": site, "expires_at": expires_at})
+
+    def collect(self, site: str) -> Optional[dict]:
+        """Hands a parked jar to the shell once."""
+        return self._outbox.pop(site, None)
+
+    async def load(self, site: str) -> list[dict]:
+        if not self._broadcast:
+            return []
+        future = self._waiting.get(site)
+        if future is None or future.done():
+            future = asyncio.get_running_loop().create_future()
+            self._waiting[site] = future
+        await self._broadcast("session_requested", {"site": site})
+        try:
+            return await asyncio.wait_for(asyncio.shield(future), REQUEST_TIMEOUT)
+        except asyncio.TimeoutError:
+            log.warning("cookie_vault.no_answer", site=site)
+            return []
+        finally:
+            if self._waiting.get(site) is future:
+                del self._waiting[site]
+
+    def deliver(self, site: str, cookies: list[dict]) -> None:
+        """The shell's answer to `session_requested`; empty when it has none."""
+        future = self._waiting.get(site)
+        if future is not None and not future.done():
+            future.set_result(cookies)
+
+
+cookie_vault = CookieVault()
@@ -0,0 +1,82 @@
+"""LinkedIn and Google access tokens handed over by the desktop shell.
+
+The shell runs the sign-in in the system browser (providers block it inside
+the webview) and keeps the tokens in the keychain. It posts the access token
+alone to POST /api/oauth/{provider} when the engine connects and after each
+sign-in or refresh, and DELETE /api/oauth/{provider} on sign-out. A caller
+needing a token it doesn't have, or one that has expired, gets it with
+`get`: that broadcasts `oauth_required` and waits briefly for the shell,
+which refreshes it or asks the user to sign in. Tokens are kept in memory
+only and never appear in events or logs.
+"""
+from __future__ import annotations
+
+import asyncio
+import time
+from typing import Awaitable, Callable, Optional
+
+import structlog
+
+log = structlog.get_logger(__name__)
+
+PROVIDERS = ("linkedin", "google")
+# A refresh is a round trip to the provider
+REQUEST_TIMEOUT = 15.0
+# Treated as expired this long before the provider says
+EXPIRY_MARGIN = 60.0
+
+Broadcast = Callable[[str, dict], Awaitable[None]]
+
+
+class OAuthTokens:
+    def __init__(self) -> None:
+        self._broadcast: Optional[Broadcast] = None
+        self._tokens: dict[str, dict] = {}
+        self._waiting: dict[str, asyncio.Future] = {}
+
+    def attach(self, broadcast: Broadcast) -> None:
+        """Wired to the SSE hub by main.py, which imports this module."""
+        self._broadcast = broadcast
+
+    def _usable(self, provider: str) -> Optional[str]:
+        token = self._tokens.get(provider)
+        if token is None:
+            return None
+        expires_at = token.get("expires_at")
+        if expires_at is not None and expires_at - EXPIRY_MARGIN <= time.time():
+            return None
+        return token["access_token"]
+
+    async def get(self, provider: str) -> Optional[str]:
+        """An access token for *provider*, or None if the user isn't signed in."""
+        token = self._usable(provider)
+        if token is not None or not self._broadcast:
+            return token
+        future = self._waiting.get(provider)
+        if future is None or future.done():
+            future = asyncio.get_running_loop().create_future()
+            self._waiting[provider] = future
+        await self._broadcast("oauth_required", {"provider": provider})
+        try:
+            await asyncio.wait_for(asyncio.shield(future), REQUEST_TIMEOUT)
+        except asyncio.TimeoutError:
+            log.warning("oauth.no_token", provider=provider)
+            return None
+        finally:
+            if self._waiting.get(provider) is future:
+                del self._waiting[provider]
+        return self._usable(provider)
+
+    def deliver(self, provider: str, access_token: str, expires_at: Optional[float]) -> None:
+        self._tokens[provider] = {"access_token": access_token, "expires_at": expires_at}
+        log.info("oauth.token_received", provider=provider)
+        future = self._waiting.get(provider)
+        if future is not None and not future.done():
+            future.set_result(None)
+
+    def clear(self, provider: str) -> None:
+        if self._tokens.pop(provider, None) is not None:
+            log.info("oauth.signed_out", provider=provider)
+
+
+oauth_tokens = OAuthTokens()
@@ -0,0 +1,132 @@
+"""Strips personal data and secrets from everything written to log files.
+
+Built-in patterns cover email addresses, phone numbers, bearer tokens,
+`key=value` secrets, JWTs and long hex strings after a secret-sounding key
+(API keys, the shell's session token; hashes are kept). Nine digits on their
+own are left too, being as often a pid or a size as a phone number. On top
+of that the desktop shell passes, as JSON lists:
+  JOBBOT_REDACT_PATTERNS — extra regular expressions from the user's settings
+  JOBBOT_REDACT_NAMES    — names to hide (the user's own, the profile name)
+The name on a parsed CV is added at runtime, and sent to the shell in a
+`redaction_names` event for its own logs. The same rules live in the shell's
+redact.rs; keep the two in step.
+"""
+from __future__ import annotations
+
+import json
+import logging
+import os
+import re
+from typing import Any
+
+PATTERNS_ENV = "JOBBOT_REDACT_PATTERNS"
+NAMES_ENV = "JOBBOT_REDACT_NAMES"
+
+# Order matters: secrets in key=value form before the generic shapes
+BUILTIN: list[tuple[re.Pattern[str], str]] = [
+    (re.compile(r"(?i)\bbearer\s+[A-Za-z0-9._~+/=\-]+"), "Bearer [token]"),
+    (
+        re.compile(
+            r"(?i)\b(token|password|passwd|secret|api[_\-]?key|cookie)"
+            r"([\"']?\s*[:=]\s*[\"']?)[^\s\"'&,;]+"
+        ),
+        r"\1\2[token]",
+    ),
+    (re.compile(r"\beyJ[\w\-]+\.[\w\-]+\.[\w\-]+"), "[token]"),
+    (
+        re.compile(
+            r"(?i)\b([\w\-]*(?:key|token|secret|session|signature|auth)[\w\-]*[\"']?"
+            r"(?:\s*[:=]\s*|\s+)[\"']?)[0-9a-f]{32,}\b"
+        ),
+        r"\1[token]",
+    ),
+    (re.compile(r"[a-zA-Z0-9._%+\-]+@[a-zA-Z0-9.\-]+\.[a-zA-Z]{2,}"), "[email]"),
+    (re.compile(r"\+\d{1,3}[\s\-]?\d{3}[\s\-]?\d{3}[\s\-]?\d{3}\b"), "[phone]"),
+    (re.compile(r"\b\d{3}[\s\-]\d{3}[\s\-]\d{3}\b"), "[phone]"),
+    (
+        re.compile(r"(?i)\b(phone|tel|telephone|mobile|m[oó]vil|tel[eé]fono|whatsapp)(\W{1,4})\d{9}\b"),
+        r"\1\2[phone]",
+    ),
+]
+
+# Shorter name parts hit too many ordinary words
+MIN_NAME_PART = 3
+
+
+def _load_json_list(env: str) -> list[str]:
+    raw = os.environ.get(env, "")
+    if not raw:
+        return []
+    try:
+        value = json.loads(raw)
+    except ValueError:
+        return []
+    return [v for v in value if isinstance(v, str) and v.strip()] if isinstance(value, list) else []
+
+
+class Redactor:
+    def __init__(self, patterns: list[str] | None = None, names: list[str] | None = None) -> None:
+        self.custom: list[re.Pattern[str]] = []
+        for pattern in patterns or []:
+            try:
+                self.custom.append(re.compile(pattern))
+            except re.error:
+                # The shell validates patterns; a bad one is skipped, not fatal
+                continue
+        self.names: list[re.Pattern[str]] = []
+        self.add_names(names or [])
+
+    @classmethod
+    def from_env(cls) -> "Redactor":
+        return cls(_load_json_list(PATTERNS_ENV), _load_json_list(NAMES_ENV))
+
+    def add_names(self, names: list[str | None]) -> None:
+        """Full names first, then each part, longest first."""
+        known = {p.pattern for p in self.names}
+        candidates: list[str] = []
+        for name in names:
+            if not name or not name.strip():
+                continue
+            candidates.append(name.strip())
+            candidates.extend(p for p in name.split() if len(p) >= MIN_NAME_PART)
+        for candidate in sorted(set(candidates), key=len, reverse=True):
+            pattern = r"(?i)\b" + re.escape(candidate) + r"\b"
+            if pattern not in known:
+                self.names.append(re.compile(pattern))
+                known.add(pattern)
+        self.names.sort(key=lambda p: len(p.pattern), reverse=True)
+
+    def redact(self, text: str) -> str:
+        for pattern, replacement in BUILTIN:
+            text = pattern.sub(replacement, text)
+        for pattern in self.custom:
+            text = pattern.sub("[redacted]", text)
+        for pattern in self.names:
+            text = pattern.sub("[name]", text)
+        return text
+
+    def redact_value(self, value: Any) -> Any:
+        if isinstance(value, str):
+            return self.redact(value)
+        if isinstance(value, dict):
+            return {k: self.redact_value(v) for k, v in value.items()}
+        if isinstance(value, (list, tuple)):
+            return type(value)(self.redact_value(v) for v in value)
+        return value
+
+
+redactor = Redactor.from_env()
+
+
+def structlog_processor(_logger: Any, _method: str, event_dict: dict) -> dict:
+    """structlog processor; runs just before rendering."""
+    return redactor.redact_value(event_dict)
+
+
+class RedactingFilter(logging.Filter):
+    """For stdlib records (uvicorn, APScheduler...) that bypass structlog."""
+
+    def filter(self, record: logging.LogRecord) -> bool:
+        record.msg = redactor.redact(record.getMessage())
+        record.args = ()
+        return True
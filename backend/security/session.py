@@ -0,0 +1,53 @@
+"""Per-launch shared secret between the desktop shell and this backend.
+
+The shell generates a random token at startup and passes it in
+JOBBOT_SESSION_TOKEN; when set, every HTTP and WebSocket request must carry it
+(`X-JobBot-Token` header, or `?token=` where the client can't set headers, e.g.
+EventSource and <img>). Other local processes can then no longer drive the
+engine through 127.0.0.1. Running from source without the variable leaves the
+API open as before.
+"""
+from __future__ import annotations
+
+import hmac
+import os
+
+from starlette.responses import JSONResponse
+from starlette.types import ASGIApp, Receive, Scope, Send
+
+TOKEN_ENV = "JOBBOT_SESSION_TOKEN"
+TOKEN_HEADER = b"x-jobbot-token"
+
+
+class SessionTokenMiddleware:
+    def __init__(self, app: ASGIApp, token: str | None = None) -> None:
+        self.app = app
+        self.token = token if token is not None else os.environ.get(TOKEN_ENV, "")
+
+    def _supplied(self, scope: Scope) -> str:
+        for name, value in scope.get("headers", []):
+            if name == TOKEN_HEADER:
+                return value.decode("latin-1")
+        query = scope.get("query_string", b"").decode("latin-1")
+        for part in query.split("&"):
+            key, _, value = part.partition("=")
+            if key == "token":
+                return value
+        return ""
+
+    async def __call__(self, scope: Scope, receive: Receive, send: Send) -> None:
+        if not self.token or scope["type"] not in ("http", "websocket"):
+            await self.app(scope, receive, send)
+            return
+        # CORS preflights never carry custom headers
+        if scope["type"] == "http" and scope["method"] == "OPTIONS":
+            await self.app(scope, receive, send)
+            return
+        if hmac.compare_digest(self._supplied(scope), self.token):
+            await self.app(scope, receive, send)
+            return
+        if scope["type"] == "websocket":
+            await send({"type": "websocket.close", "code": 4401})
+            return
+        response = JSONResponse({"detail": "invalid session token"}, status_code=401)
+        await response(scope, receive, send)
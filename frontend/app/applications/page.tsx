@@ -2,23 +2,83 @@
 import { useEffect, useState, useCallback } from "react"
 import { motion, AnimatePresence } from "motion/react"
 import { useRouter } from "next/navigation"
-import { XCircle, Clock } from "lucide-react"
-import { api, createSSEConnection } from "@/lib/api"
+import { XCircle, Clock, CloudOff, StickyNote } from "lucide-react"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { api, createSSEConnection, isQueued } from "@/lib/api"
 import { playError, playSuccess } from "@/lib/sounds"
 import type { Application } from "@/lib/types"
 import { Button } from "@/components/ui/button"
 import { formatDate, cvProfileColor, cn } from "@/lib/utils"
 import { toast } from "@/lib/toast"
 
-const COLUMNS: { id: string; label: string; accent: string }[] = [
-  { id: "pending_human_review", label: "Pending Review",  accent: "border-amber-400/40" },
-  { id: "cv_generating",        label: "CV Generating",   accent: "border-[#007AFF]/40" },
-  { id: "applied",              label: "Applied",         accent: "border-[#34C759]/40" },
-  { id: "interview",            label: "Interview",       accent: "border-purple-400/40" },
-  { id: "offered",              label: "Offered",         accent: "border-[#34C759]/40" },
-  { id: "rejected",             label: "Rejected",        accent: "border-[#FF3B30]/40" },
+interface Stage {
+  id: string
+  label: string
+  statuses: string[]
+}
+
+// The shell's board (pipeline.rs): stages, and cards in board order
+interface PipelineCard {
+  application_id: number
+  company: string
+  role: string | null
+  status: string
+  cv_profile: string | null
+  url: string | null
+  created_at: string | null
+  updated_at: string
+  authorized_at: string | null
+  stage: string | null
+  notes: string
+  syncing: boolean
+}
+
+interface Pipeline {
+  stages: Stage[]
+  cards: PipelineCard[]
+}
+
+// A card as the board shows it, from the engine or the shell
+type BoardApp = Application & { notes?: string; syncing?: boolean }
+
+const COLUMNS: Stage[] = [
+  { id: "pending_human_review", label: "Pending Review", statuses: ["pending_human_review"] },
+  { id: "cv_generating",        label: "CV Generating",  statuses: ["cv_generating"] },
+  { id: "applied",              label: "Applied",        statuses: ["applied", "submitted_ambiguous"] },
+  { id: "interview",            label: "Interview",      statuses: ["interview_scheduled", "interviewed"] },
+  { id: "offered",              label: "Offered",        statuses: ["offered"] },
+  { id: "rejected",             label: "Rejected",       statuses: ["rejected"] },
 ]
 
+const ACCENTS: Record<string, string> = {
+  pending_human_review: "border-amber-400/40",
+  cv_generating:        "border-[#007AFF]/40",
+  applied:              "border-[#34C759]/40",
+  interview:            "border-purple-400/40",
+  offered:              "border-[#34C759]/40",
+  rejected:             "border-[#FF3B30]/40",
+}
+
+function fromCard(c: PipelineCard): BoardApp {
+  return {
+    id: c.application_id,
+    job_id: 0,
+    status: c.status as Application["status"],
+    cv_profile: c.cv_profile ?? "",
+    company: c.company,
+    quality_score: null,
+    authorized_by_human: c.authorized_at !== null,
+    authorized_at: c.authorized_at,
+    form_screenshot_path: null,
+    form_url: c.url,
+    created_at: c.created_at ?? c.updated_at,
+    updated_at: c.updated_at,
+    notes: c.notes,
+    syncing: c.syncing,
+  }
+}
+
 // Statuses user can transition to from a given status
 const STATUS_TRANSITIONS: Record<string, { value: string; label: string }[]> = {
   applied: [
@@ -67,14 +127,21 @@ function AppCard({
   onReject,
   onClick,
   onStatusChange,
+  onNotes,
+  onDragStart,
+  onDrop,
 }: {
-  app: Application
+  app: BoardApp
   onReject?: () => void
   onClick: () => void
   onStatusChange?: (newStatus: string) => void
+  onNotes?: (notes: string) => void
+  onDragStart?: () => void
+  onDrop?: () => void
 }) {
   const isPending = app.status === "pending_human_review"
   const transitions = STATUS_TRANSITIONS[app.status] ?? []
+  const [editingNotes, setEditingNotes] = useState(false)
 
   return (
     <motion.div
@@ -91,6 +158,10 @@ function AppCard({
         isPending && "border-amber-400/30 bg-amber-400/5"
       )}
       onClick={onClick}
+      draggable={!!onDragStart}
+      onDragStart={onDragStart}
+      onDragOver={onDrop && (e => e.preventDefault())}
+      onDrop={onDrop && (e => { e.preventDefault(); e.stopPropagation(); onDrop() })}
     >
       <div className="flex items-start justify-between gap-2">
         <div className="flex-1 min-w-0">
@@ -105,11 +176,41 @@ function AppCard({
             <QualityBadge score={app.quality_score} />
           </div>
         </div>
-        <div className="text-right shrink-0">
+        <div className="text-right shrink-0 flex items-center gap-1">
+          {app.syncing && (
+            <span title="Waiting for the engine">
+              <CloudOff className="h-3 w-3 text-[#8E8E93]" />
+            </span>
+          )}
           <span className="text-[11px] text-[#8E8E93]">{formatDate(app.updated_at)}</span>
         </div>
       </div>
 
+      {onNotes && (
+        <div className="mt-2" onClick={e => e.stopPropagation()}>
+          {editingNotes ? (
+            <textarea
+              autoFocus
+              defaultValue={app.notes}
+              rows={3}
+              onBlur={e => {
+                setEditingNotes(false)
+                if (e.target.value !== app.notes) onNotes(e.target.value)
+              }}
+              className="w-full bg-white/5 border border-white/10 rounded-lg px-2 py-1.5 text-xs text-white outline-none focus:border-[#007AFF]"
+            />
+          ) : (
+            <button
+              onClick={() => setEditingNotes(true)}
+              className="flex items-start gap-1 text-left text-[11px] text-[#8E8E93] hover:text-white"
+            >
+              <StickyNote className="h-3 w-3 shrink-0 mt-0.5" />
+              <span className="line-clamp-2">{app.notes || "Add a note"}</span>
+            </button>
+          )}
+        </div>
+      )}
+
       {isPending && (
         <div className="flex gap-1.5 mt-2.5 pt-2.5 border-t border-white/5">
           {onReject && (
@@ -169,14 +270,21 @@ function KanbanColumn({
   onReject,
   onCardClick,
   onStatusChange,
+  onNotes,
+  onDragStart,
+  onDrop,
 }: {
   columnId: string
   label: string
   accent: string
-  apps: Application[]
+  apps: BoardApp[]
   onReject: (id: number) => void
   onCardClick: (app: Application) => void
   onStatusChange: (id: number, status: string) => void
+  // Only with the shell's board
+  onNotes?: (id: number, notes: string) => void
+  onDragStart?: (id: number) => void
+  onDrop?: (index: number) => void
 }) {
   return (
     <div
@@ -185,6 +293,8 @@ function KanbanColumn({
         "bg-white/[0.03] border rounded-2xl overflow-hidden",
         accent
       )}
+      onDragOver={onDrop && (e => e.preventDefault())}
+      onDrop={onDrop && (e => { e.preventDefault(); onDrop(apps.length) })}
     >
       {/* Column Header */}
       <div className="px-3 py-2.5 border-b border-white/5 flex items-center justify-between">
@@ -202,13 +312,16 @@ function KanbanColumn({
           </div>
         ) : (
           <AnimatePresence>
-            {apps.map(app => (
+            {apps.map((app, index) => (
               <AppCard
                 key={app.id}
                 app={app}
                 onReject={columnId === "pending_human_review" ? () => onReject(app.id) : undefined}
                 onClick={() => onCardClick(app)}
                 onStatusChange={(s) => onStatusChange(app.id, s)}
+                onNotes={onNotes && (notes => onNotes(app.id, notes))}
+                onDragStart={onDragStart && (() => onDragStart(app.id))}
+                onDrop={onDrop && (() => onDrop(index))}
               />
             ))}
           </AnimatePresence>
@@ -219,12 +332,31 @@ function KanbanColumn({
 }
 
 export default function ApplicationsPage() {
-  const [applications, setApplications] = useState<Application[]>([])
+  const [applications, setApplications] = useState<BoardApp[]>([])
+  const [stages, setStages] = useState<Stage[]>(COLUMNS)
   const [loading, setLoading] = useState(true)
   const [actionInProgress, setActionInProgress] = useState<number | null>(null)
+  const [dragging, setDragging] = useState<number | null>(null)
+  // In the desktop app the board lives in the shell and works offline
+  const [tauri, setTauri] = useState(false)
   const router = useRouter()
 
+  const showPipeline = (p: Pipeline) => {
+    setStages(p.stages)
+    setApplications(p.cards.map(fromCard))
+  }
+
   const fetchApplications = useCallback(async () => {
+    if ("__TAURI_INTERNALS__" in window) {
+      try {
+        showPipeline(await invoke<Pipeline>("get_pipeline"))
+      } catch (e) {
+        toast.error(String(e))
+      } finally {
+        setLoading(false)
+      }
+      return
+    }
     try {
       // Fetch all relevant statuses (interview column covers two statuses)
       const fetchStatuses = [
@@ -251,6 +383,15 @@ export default function ApplicationsPage() {
 
   useEffect(() => {
     fetchApplications()
+    if ("__TAURI_INTERNALS__" in window) {
+      setTauri(true)
+      const unlisten = ["pipeline-changed", "history-changed", "offline-queue-changed"].map(e =>
+        listen(e, fetchApplications)
+      )
+      return () => {
+        unlisten.forEach(u => u.then(f => f()))
+      }
+    }
     const closeSSE = createSSEConnection((event) => {
       if (
         event === "review_ready" ||
@@ -301,12 +442,22 @@ export default function ApplicationsPage() {
   }
 
   const handleStatusChange = async (id: number, newStatus: string) => {
+    if (tauri) {
+      try {
+        showPipeline(await invoke<Pipeline>("set_pipeline_status", { applicationId: id, status: newStatus }))
+        playSuccess()
+      } catch (e) {
+        playError()
+        toast.error(String(e))
+      }
+      return
+    }
     // Optimistic update
     setApplications(prev =>
       prev.map(a => a.id === id ? { ...a, status: newStatus as Application["status"] } : a)
     )
     try {
-      await api.updateApplicationStatus(id, newStatus)
+      if (isQueued(await api.updateApplicationStatus(id, newStatus))) return
       playSuccess()
       toast.success("Status updated")
     } catch {
@@ -316,16 +467,28 @@ export default function ApplicationsPage() {
     }
   }
 
-  const getColumnApps = (columnId: string) => {
-    if (columnId === "applied") {
-      return applications.filter(a => a.status === "applied" || a.status === "submitted_ambiguous")
+  const handleNotes = async (id: number, notes: string) => {
+    try {
+      showPipeline(await invoke<Pipeline>("set_pipeline_notes", { applicationId: id, notes }))
+    } catch (e) {
+      toast.error(String(e))
     }
-    if (columnId === "interview") {
-      return applications.filter(a => a.status === "interview_scheduled" || a.status === "interviewed")
+  }
+
+  const handleDrop = async (stage: string, index: number) => {
+    if (dragging === null) return
+    const id = dragging
+    setDragging(null)
+    try {
+      showPipeline(await invoke<Pipeline>("move_pipeline_card", { applicationId: id, stage, index }))
+    } catch (e) {
+      playError()
+      toast.error(String(e))
     }
-    return applications.filter(a => a.status === columnId)
   }
 
+  const getColumnApps = (stage: Stage) => applications.filter(a => stage.statuses.includes(a.status))
+
   const totalPending = applications.filter(a => a.status === "pending_human_review").length
 
   return (
@@ -352,22 +515,25 @@ export default function ApplicationsPage() {
       {/* Kanban Board */}
       {loading ? (
         <div className="flex gap-3 overflow-x-auto pb-4">
-          {COLUMNS.map(col => (
+          {stages.map(col => (
             <div key={col.id} className="min-w-[240px] max-w-[300px] bg-white/5 rounded-2xl h-64 animate-pulse flex-shrink-0" />
           ))}
         </div>
       ) : (
         <div className="flex gap-3 overflow-x-auto pb-4">
-          {COLUMNS.map(col => (
+          {stages.map(col => (
             <KanbanColumn
               key={col.id}
               columnId={col.id}
               label={col.label}
-              accent={col.accent}
-              apps={getColumnApps(col.id)}
+              accent={ACCENTS[col.id] ?? "border-white/10"}
+              apps={getColumnApps(col)}
               onReject={handleReject}
               onCardClick={handleCardClick}
               onStatusChange={handleStatusChange}
+              onNotes={tauri ? handleNotes : undefined}
+              onDragStart={tauri ? setDragging : undefined}
+              onDrop={tauri ? index => handleDrop(col.id, index) : undefined}
             />
           ))}
         </div>
@@ -5,15 +5,15 @@ import {
   Upload, FileText, CheckCircle2, AlertCircle,
   Settings as SettingsIcon, X, Cpu, Trash2
 } from "lucide-react"
-import { api } from "@/lib/api"
+import { invoke } from "@tauri-apps/api/core"
+import { api, authHeaders, BASE } from "@/lib/api"
 import type { CVSource } from "@/lib/types"
 import { Card } from "@/components/ui/card"
 import { Button } from "@/components/ui/button"
 import { cvProfileLabel, cvProfileColor, cn } from "@/lib/utils"
+import { DocumentLibrary } from "@/components/DocumentLibrary"
 import Link from "next/link"
 
-const BASE = "http://localhost:8000"
-
 const PROFILES = [
   {
     id: "cashier",
@@ -114,6 +114,25 @@ function GenerateModal({ profile, onClose }: GenerateModalProps) {
   )
 }
 
+// What the desktop shell read from the CV (resume.rs), in the engine's shape
+interface ParsedCV {
+  name: string
+  experience: unknown[]
+  education: unknown[]
+  skills: string[]
+}
+
+// Under the desktop app the CV is read before uploading; a file it can't read
+// is still uploaded, for the engine to try.
+async function parseInShell(file: File): Promise<ParsedCV | null> {
+  if (!("__TAURI_INTERNALS__" in window)) return null
+  try {
+    return await invoke<ParsedCV>("parse_resume", new Uint8Array(await file.arrayBuffer()))
+  } catch {
+    return null
+  }
+}
+
 interface UploadZoneProps {
   onUpload: (source: CVSource) => void
 }
@@ -124,6 +143,7 @@ function UploadZone({ onUpload }: UploadZoneProps) {
   const [uploadDone, setUploadDone] = useState(false)
   const [uploadedName, setUploadedName] = useState<string | null>(null)
   const [uploadError, setUploadError] = useState<string | null>(null)
+  const [parsed, setParsed] = useState<ParsedCV | null>(null)
   const [cvName, setCvName] = useState("")
   const inputRef = useRef<HTMLInputElement>(null)
 
@@ -135,17 +155,21 @@ function UploadZone({ onUpload }: UploadZoneProps) {
     setUploading(true)
     setUploadError(null)
     try {
+      const cv = await parseInShell(file)
       const formData = new FormData()
       formData.append("file", file)
       if (cvName.trim()) formData.append("name", cvName.trim())
+      if (cv) formData.append("parsed", JSON.stringify(cv))
       const res = await fetch(`${BASE}/api/setup/upload-cv`, {
         method: "POST",
+        headers: authHeaders(),
         body: formData,
       })
       if (!res.ok) throw new Error(`Upload failed: ${res.statusText}`)
       const data = await res.json()
       setUploadDone(true)
       setUploadedName(data.name ?? file.name)
+      setParsed(cv)
       onUpload({ id: data.id, name: data.name, filename: file.name, uploaded_at: new Date().toISOString() })
     } catch (e) {
       setUploadError(e instanceof Error ? e.message : "Upload failed")
@@ -164,6 +188,7 @@ function UploadZone({ onUpload }: UploadZoneProps) {
   const reset = () => {
     setUploadDone(false)
     setUploadedName(null)
+    setParsed(null)
     setCvName("")
   }
 
@@ -210,6 +235,12 @@ function UploadZone({ onUpload }: UploadZoneProps) {
             <CheckCircle2 className="h-10 w-10 text-[#34C759]" />
             <p className="text-sm font-semibold text-[#34C759]">{uploadedName}</p>
             <p className="text-xs text-[#34C759]/70">uploaded successfully</p>
+            {parsed && (
+              <p className="text-xs text-[#8E8E93]">
+                {parsed.name && `${parsed.name} · `}
+                {parsed.experience.length} roles · {parsed.education.length} studies · {parsed.skills.length} skills
+              </p>
+            )}
             <button
               onClick={e => { e.stopPropagation(); reset() }}
               className="text-xs text-[#8E8E93] hover:text-white mt-1 underline"
@@ -383,6 +414,9 @@ export default function CVProfilesPage() {
         </motion.div>
       )}
 
+      {/* Document Library (desktop app) */}
+      <DocumentLibrary />
+
       {/* Profile Grid */}
       <div>
         <h2 className="text-base font-semibold text-white mb-3">Perfiles</h2>
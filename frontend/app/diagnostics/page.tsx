@@ -0,0 +1,302 @@
+"use client"
+import { Fragment, useEffect, useState } from "react"
+import Link from "next/link"
+import { Activity, Gauge, HeartPulse, Monitor, ScrollText } from "lucide-react"
+import { invoke } from "@tauri-apps/api/core"
+import { Card } from "@/components/ui/card"
+
+interface Metrics {
+  uptime_secs: number
+  startup: {
+    tray_ms: number | null
+    backend_ready_ms: number | null
+    first_paint_ms: number | null
+  }
+  engine_starts: number
+  engine_ready_ms: number | null
+  shell_memory_bytes: number | null
+  backend_memory_bytes: number | null
+  events_total: number
+  events_per_minute: number
+}
+
+interface HealthReport {
+  status: "ok" | "degraded" | "down"
+  checked_at: string
+  engine: { running: boolean; pid: number | null; responding: boolean; starts: number }
+  events: { connected: boolean; last_heartbeat_at: string | null; stale: boolean }
+  disk: { free_bytes: number | null; total_bytes: number | null; low: boolean }
+  update: { state: string; version?: string; error?: string; percent?: number | null }
+  notification_permission: "granted" | "denied" | "unknown"
+  network: { online: boolean; latency_ms: number | null; via_proxy: boolean; error: string | null }
+  // Linux only
+  display: {
+    session: "wayland" | "x11" | "other"
+    desktop: string | null
+    compat_mode: boolean
+    xwayland: boolean
+    tray_host: boolean
+    global_shortcuts: boolean
+    window_positioning: boolean
+  } | null
+}
+
+interface SystemInfo {
+  os: string
+  os_version: string | null
+  arch: string
+  cpu: string | null
+  cpu_cores: number
+  memory_total_bytes: number | null
+  memory_available_bytes: number | null
+  disk_free_bytes: number | null
+  disk_total_bytes: number | null
+  webview_version: string | null
+  displays: { name: string | null; width: number; height: number; scale_factor: number; primary: boolean }[]
+}
+
+interface CommandStats {
+  command: string
+  count: number
+  errors: number
+  mean_ms: number
+  p50_ms: number
+  p95_ms: number
+  max_ms: number
+}
+
+interface AreaLevel {
+  area: string
+  level: string | null
+  effective: string
+}
+
+const REFRESH_MS = 5_000
+// The health check reaches out to the network, so less often
+const HEALTH_REFRESH_MS = 30_000
+const STATUS_COLOR = { ok: "text-[#34C759]", degraded: "text-[#FF9F0A]", down: "text-[#FF3B30]" }
+const LEVELS = ["error", "warn", "info", "debug", "trace"]
+
+const ms = (v: number | null) => (v === null ? "—" : `${(v / 1000).toFixed(2)} s`)
+const mb = (v: number | null) => (v === null ? "—" : `${(v / 1048576).toFixed(0)} MB`)
+const gb = (v: number | null) => (v === null ? "—" : `${(v / 1024 ** 3).toFixed(1)} GB`)
+
+function Row({ label, value }: { label: string; value: string }) {
+  return (
+    <div className="flex items-center justify-between py-1.5">
+      <span className="text-sm text-[#8E8E93]">{label}</span>
+      <span className="text-sm text-white font-mono">{value}</span>
+    </div>
+  )
+}
+
+// Desktop app only: numbers the shell keeps about itself and the engine.
+export default function DiagnosticsPage() {
+  const [metrics, setMetrics] = useState<Metrics | null>(null)
+  const [levels, setLevels] = useState<AreaLevel[]>([])
+  const [system, setSystem] = useState<SystemInfo | null>(null)
+  const [health, setHealth] = useState<HealthReport | null>(null)
+  const [commands, setCommands] = useState<CommandStats[]>([])
+  const [isTauriApp, setIsTauriApp] = useState(true)
+
+  const loadLevels = () =>
+    invoke<AreaLevel[]>("get_log_levels").then(setLevels).catch(() => {})
+
+  const setLevel = async (area: string, level: string) => {
+    await invoke("set_log_level", { area, level: level || null }).catch(() => {})
+    loadLevels()
+  }
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) {
+      setIsTauriApp(false)
+      return
+    }
+    const load = () => {
+      invoke<Metrics>("get_metrics").then(setMetrics).catch(() => {})
+      invoke<CommandStats[]>("get_ipc_stats").then(setCommands).catch(() => {})
+    }
+    load()
+    loadLevels()
+    invoke<SystemInfo>("get_system_info").then(setSystem).catch(() => {})
+    const loadHealth = () => invoke<HealthReport>("get_health_report").then(setHealth).catch(() => {})
+    loadHealth()
+    const timer = setInterval(load, REFRESH_MS)
+    const healthTimer = setInterval(loadHealth, HEALTH_REFRESH_MS)
+    return () => {
+      clearInterval(timer)
+      clearInterval(healthTimer)
+    }
+  }, [])
+
+  return (
+    <div className="max-w-3xl mx-auto space-y-5 pb-24">
+      <div>
+        <h1 className="text-2xl font-bold text-white">Diagnostics</h1>
+        <p className="text-sm text-[#8E8E93] mt-0.5">
+          <Link href="/settings" className="text-[#007AFF]">Settings</Link> → Diagnostics
+        </p>
+      </div>
+      {!isTauriApp && (
+        <p className="text-sm text-[#8E8E93]">Only available in the desktop app.</p>
+      )}
+      {health && (
+        <Card>
+          <div className="flex items-center gap-2 mb-3">
+            <HeartPulse className={`h-4 w-4 ${STATUS_COLOR[health.status]}`} />
+            <h2 className="text-base font-semibold text-white">Health</h2>
+            <span className={`text-xs font-medium ml-auto ${STATUS_COLOR[health.status]}`}>
+              {health.status === "ok" ? "All good" : health.status === "degraded" ? "Needs attention" : "Engine down"}
+            </span>
+          </div>
+          <Row
+            label="Engine"
+            value={
+              health.engine.responding
+                ? `responding (pid ${health.engine.pid ?? "?"})`
+                : health.engine.running
+                  ? "running, not responding"
+                  : "stopped"
+            }
+          />
+          <Row
+            label="Event stream"
+            value={`${health.events.connected ? "connected" : "disconnected"}${
+              health.events.last_heartbeat_at
+                ? `, last heartbeat ${new Date(health.events.last_heartbeat_at).toLocaleTimeString()}`
+                : ""
+            }`}
+          />
+          <Row
+            label="Disk free"
+            value={`${gb(health.disk.free_bytes)}${health.disk.low ? " (low)" : ""}`}
+          />
+          <Row
+            label="Updates"
+            value={
+              health.update.state === "failed"
+                ? `failed: ${health.update.error}`
+                : health.update.state.replace("_", " ")
+            }
+          />
+          <Row label="Notifications" value={health.notification_permission} />
+          <Row
+            label="Network"
+            value={
+              health.network.online
+                ? `online, ${health.network.latency_ms} ms${health.network.via_proxy ? " via proxy" : ""}`
+                : "offline"
+            }
+          />
+          {health.display && (
+            <>
+              <Row
+                label="Display"
+                value={`${health.display.session}${health.display.desktop ? ` (${health.display.desktop})` : ""}${
+                  health.display.xwayland ? ", through XWayland" : ""
+                }`}
+              />
+              <Row label="Tray icon" value={health.display.tray_host ? "shown" : "no tray host"} />
+              <Row label="Global shortcuts" value={health.display.global_shortcuts ? "available" : "not offered by the desktop"} />
+              <Row label="Window placement" value={health.display.window_positioning ? "available" : "left to the compositor"} />
+            </>
+          )}
+        </Card>
+      )}
+      {metrics && (
+        <Card>
+          <div className="flex items-center gap-2 mb-3">
+            <Activity className="h-4 w-4 text-[#007AFF]" />
+            <h2 className="text-base font-semibold text-white">Performance</h2>
+          </div>
+          <Row label="Tray ready" value={ms(metrics.startup.tray_ms)} />
+          <Row label="Engine ready" value={ms(metrics.startup.backend_ready_ms)} />
+          <Row label="First paint" value={ms(metrics.startup.first_paint_ms)} />
+          <div className="border-t border-white/5 my-2" />
+          <Row label="Engine starts" value={String(metrics.engine_starts)} />
+          <Row label="Last engine start to ready" value={ms(metrics.engine_ready_ms)} />
+          <Row label="App memory" value={mb(metrics.shell_memory_bytes)} />
+          <Row label="Engine memory" value={mb(metrics.backend_memory_bytes)} />
+          <Row label="Engine events (last minute / total)" value={`${metrics.events_per_minute} / ${metrics.events_total}`} />
+          <Row label="Uptime" value={`${Math.floor(metrics.uptime_secs / 60)} min`} />
+        </Card>
+      )}
+      {commands.length > 0 && (
+        <Card>
+          <div className="flex items-center gap-2 mb-3">
+            <Gauge className="h-4 w-4 text-[#007AFF]" />
+            <h2 className="text-base font-semibold text-white">Slowest commands</h2>
+            <button
+              onClick={() => invoke("reset_ipc_stats").then(() => setCommands([])).catch(() => {})}
+              className="text-xs text-[#007AFF] ml-auto"
+            >
+              Reset
+            </button>
+          </div>
+          <div className="grid grid-cols-[1fr_repeat(4,auto)] gap-x-4 gap-y-1 text-sm">
+            <span className="text-xs text-[#8E8E93]">Command</span>
+            <span className="text-xs text-[#8E8E93] text-right">Calls</span>
+            <span className="text-xs text-[#8E8E93] text-right">Errors</span>
+            <span className="text-xs text-[#8E8E93] text-right">p50</span>
+            <span className="text-xs text-[#8E8E93] text-right">p95</span>
+            {commands.slice(0, 15).map(c => (
+              <Fragment key={c.command}>
+                <span className="text-white font-mono truncate">{c.command}</span>
+                <span className="text-[#8E8E93] font-mono text-right">{c.count}</span>
+                <span className="text-[#8E8E93] font-mono text-right">{c.errors}</span>
+                <span className="text-white font-mono text-right">{Math.round(c.p50_ms)} ms</span>
+                <span className="text-white font-mono text-right">{Math.round(c.p95_ms)} ms</span>
+              </Fragment>
+            ))}
+          </div>
+        </Card>
+      )}
+      {system && (
+        <Card>
+          <div className="flex items-center gap-2 mb-3">
+            <Monitor className="h-4 w-4 text-[#007AFF]" />
+            <h2 className="text-base font-semibold text-white">System</h2>
+          </div>
+          <Row label="OS" value={`${system.os_version ?? system.os} (${system.arch})`} />
+          <Row label="CPU" value={`${system.cpu ?? "—"}, ${system.cpu_cores} cores`} />
+          <Row
+            label="Memory (available / total)"
+            value={`${gb(system.memory_available_bytes)} / ${gb(system.memory_total_bytes)}`}
+          />
+          <Row label="Disk free on the data volume" value={`${gb(system.disk_free_bytes)} / ${gb(system.disk_total_bytes)}`} />
+          <Row label="Webview" value={system.webview_version ?? "—"} />
+          {system.displays.map((d, i) => (
+            <Row
+              key={i}
+              label={`Display ${i + 1}${d.primary ? " (primary)" : ""}`}
+              value={`${d.width}×${d.height} at ${Math.round(d.scale_factor * 100)} %`}
+            />
+          ))}
+        </Card>
+      )}
+      {levels.length > 0 && (
+        <Card>
+          <div className="flex items-center gap-2 mb-3">
+            <ScrollText className="h-4 w-4 text-[#007AFF]" />
+            <h2 className="text-base font-semibold text-white">Log levels</h2>
+          </div>
+          {levels.map(({ area, level, effective }) => (
+            <div key={area} className="flex items-center justify-between py-1.5">
+              <span className="text-sm text-[#8E8E93]">{area}</span>
+              <select
+                value={level ?? ""}
+                onChange={(e) => setLevel(area, e.target.value)}
+                className="appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-sm text-white outline-none"
+              >
+                <option value="">{level ? "Default" : `Default (${effective})`}</option>
+                {LEVELS.map((l) => (
+                  <option key={l} value={l}>{l}</option>
+                ))}
+              </select>
+            </div>
+          ))}
+        </Card>
+      )}
+    </div>
+  )
+}
@@ -0,0 +1,1444 @@
+"use client"
+import { useEffect, useState, useCallback, useRef } from "react"
+import { motion, AnimatePresence } from "motion/react"
+import {
+  Search, ChevronDown, Filter, History, ExternalLink, Download,
+  CalendarPlus, CalendarDays, Trash2, FileText, Upload, Tags
+} from "lucide-react"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { toast } from "@/lib/toast"
+import { Card } from "@/components/ui/card"
+import { Button } from "@/components/ui/button"
+import { StatusBadge } from "@/components/ui/badge"
+import { formatDate, cn } from "@/lib/utils"
+
+const PAGE = 30
+
+interface HistoryEntry {
+  id: number
+  company: string
+  role: string | null
+  url: string | null
+  status: string
+  cv_profile: string | null
+  created_at: string | null
+  updated_at: string | null
+  authorized_at: string | null
+  submitted_at: string | null
+  recorded_at: string
+  site: string | null
+  // "linkedin", "indeed" or "csv" for rows read from an export
+  imported_from: string | null
+  // Tag ids (tags.rs)
+  tags: number[]
+  // Where a search matched (search.rs)
+  snippet?: { text: string; hit: boolean }[]
+}
+
+interface Tag {
+  id: number
+  name: string
+  color: string
+  count: number
+}
+
+function TagChip({ tag, dim, onClick }: { tag: Tag; dim?: boolean; onClick?: () => void }) {
+  return (
+    <span
+      onClick={onClick}
+      className={cn(
+        "inline-flex items-center rounded-full px-2 py-0.5 text-[11px] font-medium",
+        onClick && "cursor-pointer",
+        dim && "opacity-40"
+      )}
+      style={{ backgroundColor: `${tag.color}33`, color: tag.color }}
+    >
+      {tag.name}
+    </span>
+  )
+}
+
+// Which tags the application has; a click puts one on or takes it off.
+function TagSection({ entry, tags }: { entry: HistoryEntry; tags: Tag[] }) {
+  const [name, setName] = useState("")
+
+  const set = (ids: number[]) =>
+    invoke("set_application_tags", { applicationId: entry.id, tagIds: ids }).catch(e => toast.error(String(e)))
+
+  const toggle = (id: number) =>
+    set(entry.tags.includes(id) ? entry.tags.filter(t => t !== id) : [...entry.tags, id])
+
+  const add = async () => {
+    if (!name.trim()) return
+    try {
+      const tag = await invoke<Tag>("create_tag", { name, color: null })
+      setName("")
+      await set([...entry.tags, tag.id])
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  return (
+    <div className="space-y-1 sm:col-span-2" onClick={e => e.stopPropagation()}>
+      <p className="text-[#8E8E93] font-medium">Tags</p>
+      <div className="flex flex-wrap items-center gap-1.5">
+        {tags.map(tag => (
+          <TagChip key={tag.id} tag={tag} dim={!entry.tags.includes(tag.id)} onClick={() => toggle(tag.id)} />
+        ))}
+        <input
+          type="text"
+          value={name}
+          onChange={e => setName(e.target.value)}
+          onKeyDown={e => e.key === "Enter" && add()}
+          placeholder="New tag"
+          className="w-24 bg-transparent text-[11px] text-white placeholder:text-[#8E8E93] outline-none"
+        />
+      </div>
+    </div>
+  )
+}
+
+// Renaming, recoloring and deleting tags.
+function TagsForm({ tags, onClose }: { tags: Tag[]; onClose: () => void }) {
+  const update = (id: number, change: { name?: string; color?: string }) =>
+    invoke("update_tag", { id, name: change.name ?? null, color: change.color ?? null })
+      .catch(e => toast.error(String(e)))
+
+  const remove = (id: number) =>
+    invoke("delete_tag", { id }).catch(e => toast.error(String(e)))
+
+  return (
+    <motion.div
+      initial={{ opacity: 0 }}
+      animate={{ opacity: 1 }}
+      exit={{ opacity: 0 }}
+      className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50 flex items-center justify-center p-4"
+      onClick={onClose}
+    >
+      <motion.div
+        initial={{ scale: 0.95, y: 10 }}
+        animate={{ scale: 1, y: 0 }}
+        exit={{ scale: 0.95, y: 10 }}
+        transition={{ type: "spring", stiffness: 300, damping: 25 }}
+        className="bg-[#2C2C2E] border border-white/10 rounded-2xl p-5 w-full max-w-sm"
+        onClick={e => e.stopPropagation()}
+      >
+        <h3 className="text-base font-semibold text-white mb-4">Tags</h3>
+        {tags.length === 0 && (
+          <p className="text-xs text-[#8E8E93]">None yet. Add one from an application&apos;s details.</p>
+        )}
+        <div className="space-y-2 max-h-80 overflow-y-auto">
+          {tags.map(tag => (
+            <div key={tag.id} className="flex items-center gap-2">
+              <input
+                type="color"
+                value={tag.color}
+                onChange={e => update(tag.id, { color: e.target.value })}
+                className="h-6 w-6 shrink-0 bg-transparent border-0 cursor-pointer"
+              />
+              <input
+                type="text"
+                defaultValue={tag.name}
+                onBlur={e => e.target.value !== tag.name && update(tag.id, { name: e.target.value })}
+                className="flex-1 bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-sm text-white outline-none"
+              />
+              <span className="text-[11px] text-[#8E8E93] w-8 text-right">{tag.count}</span>
+              <button className="text-[#8E8E93] hover:text-[#FF3B30]" onClick={() => remove(tag.id)}>
+                <Trash2 className="h-3.5 w-3.5" />
+              </button>
+            </div>
+          ))}
+        </div>
+        <div className="flex mt-4">
+          <Button variant="outline" className="flex-1" onClick={onClose}>
+            Done
+          </Button>
+        </div>
+      </motion.div>
+    </motion.div>
+  )
+}
+
+interface ImportSummary {
+  source: string
+  rows: number
+  imported: number
+  duplicates: number
+  skipped: number
+  errors: string[]
+}
+
+interface HistoryDetail extends HistoryEntry {
+  status_changes: { status: string; at: string }[]
+  artifacts: { kind: "form_screenshot" | "form"; location: string; recorded_at: string }[]
+}
+
+const ARTIFACT_LABELS: Record<HistoryDetail["artifacts"][number]["kind"], string> = {
+  form_screenshot: "Form screenshot",
+  form: "Application form",
+}
+
+interface CalendarEvent {
+  id: number
+  application_id: number | null
+  kind: "interview" | "follow_up"
+  starts_at: string
+  // Set as a wall-clock time in timezone (tz.rs), or here when null
+  local_time: string | null
+  timezone: string | null
+  duration_minutes: number
+  location: string | null
+  notes: string | null
+  // Follow-ups only
+  notified_at: string | null
+  done_at: string | null
+}
+
+// Quick follow-ups, in days (reminders.rs)
+const NUDGES = [3, 7, 14]
+
+const KIND_LABELS: Record<CalendarEvent["kind"], string> = {
+  interview: "Interview",
+  follow_up: "Follow-up reminder",
+}
+
+interface Evidence {
+  id: number
+  application_id: number
+  kind: "submitted_form_screenshot" | "submitted_form_html" | "confirmation_screenshot" | "confirmation_html"
+  sha256: string
+  size: number
+  media_type: string
+  captured_at: string
+}
+
+const EVIDENCE_LABELS: Record<Evidence["kind"], string> = {
+  submitted_form_screenshot: "Form as submitted",
+  submitted_form_html: "Form as submitted (HTML)",
+  confirmation_screenshot: "Confirmation page",
+  confirmation_html: "Confirmation page (HTML)",
+}
+
+// Screenshots and pages kept as proof of what was sent (evidence.rs).
+function EvidenceSection({ applicationId }: { applicationId: number }) {
+  const [items, setItems] = useState<Evidence[]>([])
+  const [preview, setPreview] = useState<{ id: number; src: string } | null>(null)
+
+  const load = useCallback(() => {
+    invoke<Evidence[]>("list_evidence", { applicationId }).then(setItems).catch(() => {})
+  }, [applicationId])
+
+  useEffect(() => {
+    load()
+    const unlisten = listen("evidence-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [load])
+
+  const show = async (e: Evidence) => {
+    if (preview?.id === e.id) {
+      setPreview(null)
+      return
+    }
+    try {
+      const data = await invoke<{ media_type: string; base64: string }>("read_evidence", { id: e.id })
+      setPreview({ id: e.id, src: `data:${data.media_type};base64,${data.base64}` })
+    } catch (err) {
+      toast.error(String(err))
+    }
+  }
+
+  return (
+    <div className="space-y-1 sm:col-span-2" onClick={e => e.stopPropagation()}>
+      <p className="text-[#8E8E93] font-medium">Proof of submission</p>
+      {items.length === 0 && <p className="text-[#8E8E93]">None kept.</p>}
+      {items.map(e => (
+        <div key={e.id} className="flex items-center gap-2">
+          <span className="text-white flex-1 truncate" title={e.sha256}>
+            {EVIDENCE_LABELS[e.kind] ?? e.kind}
+            <span className="text-[#8E8E93]">
+              {" "}· {new Date(e.captured_at).toLocaleString()} · {Math.ceil(e.size / 1024)} KB
+            </span>
+          </span>
+          {e.media_type.startsWith("image/") && (
+            <button className="text-[#007AFF]" onClick={() => show(e)}>
+              {preview?.id === e.id ? "Hide" : "View"}
+            </button>
+          )}
+          <button
+            className="text-[#007AFF] inline-flex items-center gap-1"
+            onClick={() => invoke("open_evidence", { id: e.id }).catch(err => toast.error(String(err)))}
+          >
+            <ExternalLink className="h-3 w-3" />
+            Open
+          </button>
+        </div>
+      ))}
+      {preview && (
+        // eslint-disable-next-line @next/next/no-img-element
+        <img src={preview.src} alt="" className="mt-2 max-h-96 rounded-lg border border-white/10" />
+      )}
+    </div>
+  )
+}
+
+// Interviews and reminders for one application, each exportable as .ics.
+function CalendarSection({ applicationId }: { applicationId: number }) {
+  const [events, setEvents] = useState<CalendarEvent[]>([])
+  const [adding, setAdding] = useState(false)
+  const [form, setForm] = useState({
+    kind: "interview" as CalendarEvent["kind"],
+    starts_at: "",
+    timezone: "",
+    duration_minutes: "60",
+    location: "",
+    notes: "",
+  })
+  const [saving, setSaving] = useState(false)
+  const [zones, setZones] = useState<{ local: string; zones: string[] }>({ local: "", zones: [] })
+
+  useEffect(() => {
+    invoke<{ local: string; zones: string[] }>("list_timezones")
+      .then(z => {
+        setZones(z)
+        setForm(f => ({ ...f, timezone: f.timezone || z.local }))
+      })
+      .catch(() => {})
+  }, [])
+
+  const load = useCallback(() => {
+    invoke<CalendarEvent[]>("list_calendar_events", { applicationId })
+      .then(setEvents)
+      .catch(() => {})
+  }, [applicationId])
+
+  useEffect(() => {
+    load()
+    const unlisten = listen("reminders-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [load])
+
+  const nudge = async (days: number) => {
+    try {
+      await invoke("add_reminder", { applicationId, days, notes: null })
+      toast.success(`Reminder set for ${days} days from now`)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const snooze = (id: number) =>
+    invoke("snooze_reminder", { id, minutes: 24 * 60 }).catch(e => toast.error(String(e)))
+
+  const complete = (id: number) =>
+    invoke("complete_reminder", { id }).catch(e => toast.error(String(e)))
+
+  const save = async () => {
+    if (!form.starts_at) return
+    setSaving(true)
+    try {
+      await invoke("save_calendar_event", {
+        id: null,
+        event: {
+          application_id: applicationId,
+          kind: form.kind,
+          // datetime-local is the wall-clock time, in the zone picked
+          local_time: form.starts_at.length === 16 ? `${form.starts_at}:00` : form.starts_at,
+          timezone: form.timezone || null,
+          duration_minutes: Number(form.duration_minutes) || 60,
+          location: form.location || null,
+          notes: form.notes || null,
+        },
+      })
+      setAdding(false)
+      setForm(f => ({ ...f, starts_at: "", location: "", notes: "" }))
+      load()
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  const exportEvent = async (id: number) => {
+    try {
+      const path = await invoke<string | null>("export_calendar_event", { id })
+      if (path) toast.success(`Saved ${path}`)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const remove = async (id: number) => {
+    await invoke("delete_calendar_event", { id }).catch(e => toast.error(String(e)))
+    load()
+  }
+
+  return (
+    <div className="space-y-1 sm:col-span-2" onClick={e => e.stopPropagation()}>
+      <div className="flex items-center justify-between">
+        <p className="text-[#8E8E93] font-medium">Interviews and reminders</p>
+        <button className="text-[#007AFF] inline-flex items-center gap-1" onClick={() => setAdding(a => !a)}>
+          <CalendarPlus className="h-3 w-3" />
+          Add
+        </button>
+      </div>
+      <div className="flex items-center gap-2 text-[#8E8E93]">
+        Remind me in
+        {NUDGES.map(days => (
+          <button key={days} className="text-[#007AFF]" onClick={() => nudge(days)}>
+            {days} days
+          </button>
+        ))}
+      </div>
+      {events.length === 0 && !adding && <p className="text-[#8E8E93]">None.</p>}
+      {events.map(ev => (
+        <div key={ev.id} className="flex items-center gap-2 text-white">
+          <span className={cn("flex-1 min-w-0 truncate", ev.done_at && "line-through text-[#8E8E93]")}>
+            {KIND_LABELS[ev.kind]} · {new Date(ev.starts_at).toLocaleString()}
+            {ev.timezone && ev.local_time && ev.timezone !== zones.local && (
+              <span className="text-[#8E8E93]">
+                {" "}({ev.local_time.slice(11, 16)} {ev.timezone.replace(/_/g, " ")})
+              </span>
+            )}
+            {ev.location && <span className="text-[#8E8E93]"> · {ev.location}</span>}
+            {ev.notified_at && !ev.done_at && <span className="text-amber-400"> · due</span>}
+          </span>
+          {ev.kind === "follow_up" && !ev.done_at && (
+            <>
+              <button className="text-[#007AFF]" onClick={() => snooze(ev.id)}>Snooze a day</button>
+              <button className="text-[#34C759]" onClick={() => complete(ev.id)}>Done</button>
+            </>
+          )}
+          <button className="text-[#007AFF]" onClick={() => exportEvent(ev.id)}>.ics</button>
+          <button className="text-[#8E8E93] hover:text-[#FF3B30]" onClick={() => remove(ev.id)}>
+            <Trash2 className="h-3 w-3" />
+          </button>
+        </div>
+      ))}
+      {adding && (
+        <div className="grid grid-cols-2 gap-2 pt-1">
+          <div className="relative">
+            <select
+              value={form.kind}
+              onChange={e => setForm(f => ({ ...f, kind: e.target.value as CalendarEvent["kind"] }))}
+              className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 pr-7 text-xs text-white outline-none"
+            >
+              <option value="interview" className="bg-[#2C2C2E]">Interview</option>
+              <option value="follow_up" className="bg-[#2C2C2E]">Follow-up reminder</option>
+            </select>
+            <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+          </div>
+          <input
+            type="datetime-local"
+            value={form.starts_at}
+            onChange={e => setForm(f => ({ ...f, starts_at: e.target.value }))}
+            className="bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-xs text-white outline-none"
+          />
+          <div className="relative col-span-2">
+            <select
+              value={form.timezone}
+              onChange={e => setForm(f => ({ ...f, timezone: e.target.value }))}
+              title="The time above is in this zone: the recruiter's, for a remote role"
+              className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 pr-7 text-xs text-white outline-none"
+            >
+              {zones.zones.map(z => (
+                <option key={z} value={z} className="bg-[#2C2C2E]">
+                  {z.replace(/_/g, " ")}{z === zones.local ? " (here)" : ""}
+                </option>
+              ))}
+            </select>
+            <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+          </div>
+          <input
+            type="number"
+            value={form.duration_minutes}
+            onChange={e => setForm(f => ({ ...f, duration_minutes: e.target.value }))}
+            placeholder="Minutes"
+            className="bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-xs text-white outline-none"
+          />
+          <input
+            type="text"
+            value={form.location}
+            onChange={e => setForm(f => ({ ...f, location: e.target.value }))}
+            placeholder="Place or video link"
+            className="bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-xs text-white placeholder:text-[#8E8E93] outline-none"
+          />
+          <input
+            type="text"
+            value={form.notes}
+            onChange={e => setForm(f => ({ ...f, notes: e.target.value }))}
+            placeholder="Notes"
+            className="col-span-2 bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-xs text-white placeholder:text-[#8E8E93] outline-none"
+          />
+          <Button className="col-span-2" loading={saving} disabled={!form.starts_at} onClick={save}>
+            Save
+          </Button>
+        </div>
+      )}
+    </div>
+  )
+}
+
+type ExportColumn =
+  | "id" | "company" | "role" | "url" | "status" | "cv_profile"
+  | "created_at" | "updated_at" | "authorized_at" | "submitted_at"
+
+const EXPORT_COLUMNS: { id: ExportColumn; label: string }[] = [
+  { id: "id", label: "ID" },
+  { id: "company", label: "Company" },
+  { id: "role", label: "Role" },
+  { id: "url", label: "Link" },
+  { id: "status", label: "Status" },
+  { id: "cv_profile", label: "CV profile" },
+  { id: "created_at", label: "Created" },
+  { id: "updated_at", label: "Updated" },
+  { id: "authorized_at", label: "Authorized" },
+  { id: "submitted_at", label: "Submitted" },
+]
+
+function ExportForm({
+  range,
+  onClose,
+}: {
+  range: { since: string; until: string }
+  onClose: () => void
+}) {
+  const [format, setFormat] = useState<"csv" | "xlsx">("xlsx")
+  const [columns, setColumns] = useState<ExportColumn[]>(["company", "role", "status", "created_at", "submitted_at", "url"])
+  const [since, setSince] = useState(range.since)
+  const [until, setUntil] = useState(range.until)
+  const [exporting, setExporting] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  // Kept in the table's order
+  const toggle = (id: ExportColumn) =>
+    setColumns(c => (c.includes(id) ? c.filter(x => x !== id) : EXPORT_COLUMNS.map(x => x.id).filter(x => x === id || c.includes(x))))
+
+  const handleExport = async () => {
+    setExporting(true)
+    try {
+      const path = await invoke<string | null>("export_applications", {
+        format,
+        columns,
+        since: since || null,
+        until: until || null,
+      })
+      if (path) {
+        toast.success(`Exported to ${path}`)
+        onClose()
+      }
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setExporting(false)
+    }
+  }
+
+  return (
+    <motion.div
+      initial={{ opacity: 0 }}
+      animate={{ opacity: 1 }}
+      exit={{ opacity: 0 }}
+      className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50 flex items-center justify-center p-4"
+      onClick={onClose}
+    >
+      <motion.div
+        initial={{ scale: 0.95, y: 10 }}
+        animate={{ scale: 1, y: 0 }}
+        exit={{ scale: 0.95, y: 10 }}
+        transition={{ type: "spring", stiffness: 300, damping: 25 }}
+        className="bg-[#2C2C2E] border border-white/10 rounded-2xl p-5 w-full max-w-md"
+        onClick={e => e.stopPropagation()}
+      >
+        <h3 className="text-base font-semibold text-white mb-4">Export Applications</h3>
+
+        <div className="space-y-3">
+          <div className="space-y-1.5">
+            <label className="text-xs text-[#8E8E93] font-medium">Format</label>
+            <div className="relative">
+              <select
+                value={format}
+                onChange={e => setFormat(e.target.value as "csv" | "xlsx")}
+                className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-2 pr-7 text-sm text-white outline-none"
+              >
+                <option value="xlsx" className="bg-[#2C2C2E]">Excel (.xlsx)</option>
+                <option value="csv" className="bg-[#2C2C2E]">CSV</option>
+              </select>
+              <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+            </div>
+          </div>
+
+          <div className="space-y-1.5">
+            <label className="text-xs text-[#8E8E93] font-medium">Columns</label>
+            <div className="flex flex-wrap gap-1.5">
+              {EXPORT_COLUMNS.map(c => (
+                <button
+                  key={c.id}
+                  onClick={() => toggle(c.id)}
+                  className={cn(
+                    "px-2.5 py-1 rounded-full text-xs transition-colors",
+                    columns.includes(c.id) ? "bg-[#007AFF] text-white" : "bg-white/5 text-[#8E8E93]"
+                  )}
+                >
+                  {c.label}
+                </button>
+              ))}
+            </div>
+          </div>
+
+          <div className="grid grid-cols-2 gap-2">
+            <div className="space-y-1.5">
+              <label className="text-xs text-[#8E8E93] font-medium">Created from</label>
+              <input
+                type="date"
+                value={since}
+                onChange={e => setSince(e.target.value)}
+                className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white outline-none"
+              />
+            </div>
+            <div className="space-y-1.5">
+              <label className="text-xs text-[#8E8E93] font-medium">Until</label>
+              <input
+                type="date"
+                value={until}
+                onChange={e => setUntil(e.target.value)}
+                className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white outline-none"
+              />
+            </div>
+          </div>
+        </div>
+
+        {error && (
+          <p className="text-xs text-[#FF3B30] mt-3">{error}</p>
+        )}
+
+        <div className="flex gap-2 mt-4">
+          <Button variant="outline" className="flex-1" onClick={onClose}>
+            Cancel
+          </Button>
+          <Button className="flex-1" loading={exporting} disabled={columns.length === 0} onClick={handleExport}>
+            Export…
+          </Button>
+        </div>
+      </motion.div>
+    </motion.div>
+  )
+}
+
+// Before templates moved into the shell, the form kept its last letter here
+const LETTER_KEY = "coverLetter"
+
+const DEFAULT_LETTER = `Dear hiring team,
+
+I am writing to apply for the {role} position at {company}.
+
+Kind regards,
+{name}`
+
+interface LetterTemplate {
+  id: string
+  name: string
+  created_at: string
+  // Oldest first; the last is current
+  versions: { version: number; body: string; saved_at: string }[]
+}
+
+interface TemplateStore {
+  templates: LetterTemplate[]
+  sender: { name: string; details: string }
+}
+
+interface Rendered {
+  text: string
+  missing: string[]
+  unknown: string[]
+}
+
+const current = (template: LetterTemplate) => template.versions[template.versions.length - 1]
+
+// Templates and the sender are kept by the shell (templates.rs), which also
+// fills in the placeholders, for the preview and the PDF (pdf.rs).
+function CoverLetterForm({ entry, onClose }: { entry: HistoryEntry; onClose: () => void }) {
+  const [letter, setLetter] = useState({ sender_name: "", sender_details: "", body: DEFAULT_LETTER })
+  const [templates, setTemplates] = useState<LetterTemplate[]>([])
+  const [templateId, setTemplateId] = useState<string | null>(null)
+  const [templateName, setTemplateName] = useState("")
+  const [preview, setPreview] = useState<Rendered | null>(null)
+  const [recipient, setRecipient] = useState("")
+  const [saving, setSaving] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const template = templates.find(t => t.id === templateId) ?? null
+
+  const pick = (t: LetterTemplate | null) => {
+    setTemplateId(t?.id ?? null)
+    setTemplateName(t?.name ?? "")
+    if (t) setLetter(l => ({ ...l, body: current(t).body }))
+  }
+
+  useEffect(() => {
+    invoke<TemplateStore>("list_templates")
+      .then(async store => {
+        // Brought over once from the webview's storage
+        const saved = localStorage.getItem(LETTER_KEY)
+        if (saved && store.templates.length === 0) {
+          const old = JSON.parse(saved)
+          const sender = { name: old.sender_name ?? "", details: old.sender_details ?? "" }
+          await invoke("save_letter_sender", { sender })
+          if (old.body) {
+            await invoke("save_template", { id: null, name: "My letter", body: old.body })
+          }
+          localStorage.removeItem(LETTER_KEY)
+          store = await invoke<TemplateStore>("list_templates")
+        }
+        setTemplates(store.templates)
+        setLetter(l => ({ ...l, sender_name: store.sender.name, sender_details: store.sender.details }))
+        if (store.templates.length > 0) pick(store.templates[0])
+      })
+      .catch(e => setError(String(e)))
+  }, [])
+
+  useEffect(() => {
+    const timer = setTimeout(() => {
+      invoke<Rendered>("render_template", {
+        body: letter.body,
+        applicationId: entry.id,
+        recipient: recipient || null,
+      })
+        .then(setPreview)
+        .catch(() => setPreview(null))
+    }, 300)
+    return () => clearTimeout(timer)
+  }, [letter.body, letter.sender_name, recipient, entry.id])
+
+  const saveTemplate = async (asNew: boolean) => {
+    const name = templateName.trim() || "Untitled"
+    try {
+      const saved = await invoke<LetterTemplate>("save_template", {
+        id: asNew ? null : templateId,
+        name,
+        body: letter.body,
+      })
+      setTemplates(ts => [...ts.filter(t => t.id !== saved.id), saved])
+      pick(saved)
+      toast.success(`Saved “${saved.name}”, version ${current(saved).version}`)
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const restore = async (version: number) => {
+    if (!template) return
+    try {
+      const saved = await invoke<LetterTemplate>("restore_template_version", { id: template.id, version })
+      setTemplates(ts => ts.map(t => (t.id === saved.id ? saved : t)))
+      pick(saved)
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const removeTemplate = async () => {
+    if (!template) return
+    try {
+      await invoke("delete_template", { id: template.id })
+      setTemplates(ts => ts.filter(t => t.id !== template.id))
+      pick(null)
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const exportTemplates = async () => {
+    try {
+      const path = await invoke<string | null>("export_templates")
+      if (path) toast.success(`Saved ${path}`)
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const importTemplates = async () => {
+    try {
+      const count = await invoke<number | null>("import_templates")
+      if (count === null) return
+      const store = await invoke<TemplateStore>("list_templates")
+      setTemplates(store.templates)
+      toast.success(count === 1 ? "1 template imported" : `${count} templates imported`)
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const handleSave = async () => {
+    setSaving(true)
+    try {
+      await invoke("save_letter_sender", {
+        sender: { name: letter.sender_name, details: letter.sender_details },
+      })
+      const path = await invoke<string | null>("export_cover_letter_pdf", {
+        letter: { ...letter, application_id: entry.id, recipient: recipient || null },
+      })
+      if (path) {
+        toast.success(`Saved ${path}`)
+        onClose()
+      }
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  return (
+    <motion.div
+      initial={{ opacity: 0 }}
+      animate={{ opacity: 1 }}
+      exit={{ opacity: 0 }}
+      className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50 flex items-center justify-center p-4"
+      onClick={onClose}
+    >
+      <motion.div
+        initial={{ scale: 0.95, y: 10 }}
+        animate={{ scale: 1, y: 0 }}
+        exit={{ scale: 0.95, y: 10 }}
+        transition={{ type: "spring", stiffness: 300, damping: 25 }}
+        className="bg-[#2C2C2E] border border-white/10 rounded-2xl p-5 w-full max-w-lg"
+        onClick={e => e.stopPropagation()}
+      >
+        <h3 className="text-base font-semibold text-white mb-4">
+          Cover Letter for {entry.company || `#${entry.id}`}
+        </h3>
+
+        <div className="space-y-3">
+          <div className="grid grid-cols-2 gap-2">
+            <input
+              type="text"
+              value={letter.sender_name}
+              onChange={e => setLetter(l => ({ ...l, sender_name: e.target.value }))}
+              placeholder="Your name"
+              className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+            />
+            <input
+              type="text"
+              value={recipient}
+              onChange={e => setRecipient(e.target.value)}
+              placeholder="Addressed to (optional)"
+              className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+            />
+          </div>
+          <textarea
+            value={letter.sender_details}
+            onChange={e => setLetter(l => ({ ...l, sender_details: e.target.value }))}
+            placeholder="Address, email, phone — one per line"
+            rows={2}
+            className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none resize-none"
+          />
+          <div className="grid grid-cols-2 gap-2">
+            <div className="relative">
+              <select
+                value={templateId ?? ""}
+                onChange={e => pick(templates.find(t => t.id === e.target.value) ?? null)}
+                className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-2 pr-7 text-sm text-white outline-none"
+              >
+                <option value="" className="bg-[#2C2C2E]">No template</option>
+                {templates.map(t => (
+                  <option key={t.id} value={t.id} className="bg-[#2C2C2E]">{t.name}</option>
+                ))}
+              </select>
+              <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+            </div>
+            <input
+              type="text"
+              value={templateName}
+              onChange={e => setTemplateName(e.target.value)}
+              placeholder="Template name"
+              className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+            />
+          </div>
+          <textarea
+            value={letter.body}
+            onChange={e => setLetter(l => ({ ...l, body: e.target.value }))}
+            rows={10}
+            className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white outline-none resize-y"
+          />
+          <div className="flex flex-wrap items-center gap-3 text-xs">
+            {template && (
+              <button className="text-[#007AFF]" onClick={() => saveTemplate(false)}>Save template</button>
+            )}
+            <button className="text-[#007AFF]" onClick={() => saveTemplate(true)}>Save as new template</button>
+            {template && template.versions.length > 1 && (
+              <select
+                value=""
+                onChange={e => e.target.value && restore(Number(e.target.value))}
+                className="bg-transparent text-[#007AFF] outline-none"
+              >
+                <option value="" className="bg-[#2C2C2E]">Version {current(template).version}</option>
+                {template.versions.slice(0, -1).reverse().map(v => (
+                  <option key={v.version} value={v.version} className="bg-[#2C2C2E]">
+                    Restore version {v.version} · {formatDate(v.saved_at)}
+                  </option>
+                ))}
+              </select>
+            )}
+            {template && (
+              <button className="text-[#8E8E93] hover:text-[#FF3B30]" onClick={removeTemplate}>Delete</button>
+            )}
+            <span className="flex-1" />
+            <button className="text-[#007AFF]" onClick={importTemplates}>Import…</button>
+            <button className="text-[#007AFF]" onClick={exportTemplates}>Export…</button>
+          </div>
+          <p className="text-xs text-[#8E8E93]">
+            {"{company}"}, {"{role}"}, {"{name}"}, {"{date}"}, {"{recipient}"}, {"{location}"} and {"{url}"} are filled in. Leave a blank line between paragraphs.
+          </p>
+          {preview && (
+            <div className="max-h-40 overflow-y-auto rounded-xl bg-black/20 px-3 py-2 text-xs text-white/80 whitespace-pre-wrap">
+              {preview.text}
+              {(preview.missing.length > 0 || preview.unknown.length > 0) && (
+                <p className="mt-2 text-amber-400">
+                  {preview.missing.length > 0 && `Nothing to fill in for ${preview.missing.map(k => `{${k}}`).join(", ")}. `}
+                  {preview.unknown.length > 0 && `Not a placeholder: ${preview.unknown.map(k => `{${k}}`).join(", ")}.`}
+                </p>
+              )}
+            </div>
+          )}
+        </div>
+
+        {error && (
+          <p className="text-xs text-[#FF3B30] mt-3">{error}</p>
+        )}
+
+        <div className="flex gap-2 mt-4">
+          <Button variant="outline" className="flex-1" onClick={onClose}>
+            Cancel
+          </Button>
+          <Button
+            className="flex-1"
+            loading={saving}
+            disabled={!letter.sender_name.trim() || !letter.body.trim()}
+            onClick={handleSave}
+          >
+            Save PDF…
+          </Button>
+        </div>
+      </motion.div>
+    </motion.div>
+  )
+}
+
+// Time on the search (activity.rs), by week
+interface WeekActivity {
+  week_of: string
+  focus_seconds: number
+  run_seconds: number
+  runs: number
+  submitted: number
+  days: { date: string; focus_seconds: number; run_seconds: number; runs: number }[]
+}
+
+interface DigestSettings {
+  enabled: boolean
+  time: string
+  include_activity: boolean
+}
+
+const hoursMinutes = (seconds: number) =>
+  `${Math.floor(seconds / 3600)} h ${String(Math.floor(seconds / 60) % 60).padStart(2, "0")} min`
+
+// Any day in the week will do; the report runs Monday to Sunday.
+function ReportForm({ onClose }: { onClose: () => void }) {
+  const [weekOf, setWeekOf] = useState(() => new Date().toLocaleDateString("sv"))
+  const [saving, setSaving] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+  const [activity, setActivity] = useState<WeekActivity | null>(null)
+  const [digest, setDigest] = useState<DigestSettings | null>(null)
+
+  useEffect(() => {
+    invoke<WeekActivity[]>("activity_report", { weeks: 1, weekOf: weekOf || null })
+      .then(weeks => setActivity(weeks[0] ?? null))
+      .catch(() => setActivity(null))
+  }, [weekOf])
+
+  useEffect(() => {
+    invoke<DigestSettings>("get_digest_settings").then(setDigest).catch(() => {})
+  }, [])
+
+  const setIncludeActivity = (include: boolean) => {
+    if (!digest) return
+    invoke("set_digest_settings", { enabled: digest.enabled, time: digest.time, includeActivity: include })
+      .then(() => setDigest({ ...digest, include_activity: include }))
+      .catch(e => toast.error(String(e)))
+  }
+
+  const handleSave = async () => {
+    setSaving(true)
+    try {
+      const path = await invoke<string | null>("export_weekly_report_pdf", { weekOf: weekOf || null })
+      if (path) {
+        toast.success(`Saved ${path}`)
+        onClose()
+      }
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  return (
+    <motion.div
+      initial={{ opacity: 0 }}
+      animate={{ opacity: 1 }}
+      exit={{ opacity: 0 }}
+      className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50 flex items-center justify-center p-4"
+      onClick={onClose}
+    >
+      <motion.div
+        initial={{ scale: 0.95, y: 10 }}
+        animate={{ scale: 1, y: 0 }}
+        exit={{ scale: 0.95, y: 10 }}
+        transition={{ type: "spring", stiffness: 300, damping: 25 }}
+        className="bg-[#2C2C2E] border border-white/10 rounded-2xl p-5 w-full max-w-sm"
+        onClick={e => e.stopPropagation()}
+      >
+        <h3 className="text-base font-semibold text-white mb-4">Weekly Report</h3>
+
+        <div className="space-y-1.5">
+          <label className="text-xs text-[#8E8E93] font-medium">Week of</label>
+          <input
+            type="date"
+            value={weekOf}
+            onChange={e => setWeekOf(e.target.value)}
+            className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white outline-none"
+          />
+        </div>
+
+        {activity && (
+          <div className="mt-4 bg-white/5 rounded-xl p-3 space-y-1">
+            <p className="text-xs font-medium text-white">Time spent</p>
+            <p className="text-xs text-[#8E8E93]">
+              {hoursMinutes(activity.focus_seconds)} in JobBot · {activity.runs} runs ({hoursMinutes(activity.run_seconds)})
+              · {activity.submitted} applications sent
+            </p>
+          </div>
+        )}
+
+        {digest && (
+          <label className="flex items-center gap-2 mt-3 text-xs text-[#8E8E93]">
+            <input
+              type="checkbox"
+              checked={digest.include_activity}
+              onChange={e => setIncludeActivity(e.target.checked)}
+            />
+            Add the day&apos;s time to the daily digest
+          </label>
+        )}
+
+        {error && (
+          <p className="text-xs text-[#FF3B30] mt-3">{error}</p>
+        )}
+
+        <div className="flex gap-2 mt-4">
+          <Button variant="outline" className="flex-1" onClick={onClose}>
+            Cancel
+          </Button>
+          <Button className="flex-1" loading={saving} onClick={handleSave}>
+            Save PDF…
+          </Button>
+        </div>
+      </motion.div>
+    </motion.div>
+  )
+}
+
+function HistoryRow({ entry, tags, open, onToggle }: {
+  entry: HistoryEntry
+  tags: Tag[]
+  open: boolean
+  onToggle: () => void
+}) {
+  const [detail, setDetail] = useState<HistoryDetail | null>(null)
+  const [showLetter, setShowLetter] = useState(false)
+
+  useEffect(() => {
+    if (!open) return
+    invoke<HistoryDetail | null>("get_history_entry", { id: entry.id })
+      .then(setDetail)
+      .catch(() => {})
+  }, [open, entry.id, entry.recorded_at])
+
+  return (
+    <motion.div
+      layout
+      initial={{ opacity: 0, y: 8 }}
+      animate={{ opacity: 1, y: 0 }}
+      exit={{ opacity: 0, y: -4 }}
+      transition={{ type: "spring", stiffness: 200, damping: 22 }}
+    >
+      <Card onClick={onToggle}>
+        <div className="flex items-start gap-4">
+          <div className="flex-1 min-w-0">
+            <p className="text-sm font-bold text-white">{entry.company || `#${entry.id}`}</p>
+            {entry.role && <p className="text-xs text-white/70 mt-0.5">{entry.role}</p>}
+            {entry.imported_from && (
+              <p className="text-[11px] text-[#8E8E93] mt-0.5">Imported from {entry.imported_from}</p>
+            )}
+            {entry.snippet && entry.snippet.length > 0 && (
+              <p className="text-[11px] text-[#8E8E93] mt-1 line-clamp-2">
+                {entry.snippet.map((s, i) =>
+                  s.hit ? <mark key={i} className="bg-[#FFCC00]/30 text-white rounded-sm">{s.text}</mark> : <span key={i}>{s.text}</span>
+                )}
+              </p>
+            )}
+            {entry.tags.length > 0 && (
+              <div className="flex flex-wrap gap-1 mt-1">
+                {tags.filter(t => entry.tags.includes(t.id)).map(t => <TagChip key={t.id} tag={t} />)}
+              </div>
+            )}
+          </div>
+          <span className="hidden sm:block text-[11px] text-[#8E8E93] shrink-0">
+            {formatDate(entry.created_at)}
+          </span>
+          {entry.status && <StatusBadge status={entry.status} />}
+        </div>
+
+        <AnimatePresence>
+          {open && detail && (
+            <motion.div
+              initial={{ opacity: 0, height: 0 }}
+              animate={{ opacity: 1, height: "auto" }}
+              exit={{ opacity: 0, height: 0 }}
+              className="overflow-hidden"
+            >
+              <div className="mt-3 pt-3 border-t border-white/5 grid sm:grid-cols-2 gap-4 text-xs">
+                <div className="space-y-1">
+                  <p className="text-[#8E8E93] font-medium">Status</p>
+                  {detail.status_changes.length === 0 && <p className="text-[#8E8E93]">No changes recorded.</p>}
+                  {detail.status_changes.map((c, i) => (
+                    <p key={i} className="text-white">
+                      {c.status.replace(/_/g, " ")}
+                      <span className="text-[#8E8E93]"> · {new Date(c.at).toLocaleString()}</span>
+                    </p>
+                  ))}
+                  {detail.submitted_at && (
+                    <p className="text-[#8E8E93]">Submitted {new Date(detail.submitted_at).toLocaleString()}</p>
+                  )}
+                </div>
+                <div className="space-y-1">
+                  <p className="text-[#8E8E93] font-medium">Files and links</p>
+                  {detail.artifacts.length === 0 && <p className="text-[#8E8E93]">None recorded.</p>}
+                  {detail.artifacts.map(a => (
+                    <p key={`${a.kind}:${a.location}`} className="text-white truncate" title={a.location}>
+                      {ARTIFACT_LABELS[a.kind] ?? a.kind}
+                      {a.location.startsWith("http") ? (
+                        <a
+                          href={a.location}
+                          target="_blank"
+                          rel="noreferrer"
+                          onClick={e => e.stopPropagation()}
+                          className="inline-flex items-center gap-1 text-[#007AFF] ml-2"
+                        >
+                          <ExternalLink className="h-3 w-3" />
+                          Open
+                        </a>
+                      ) : (
+                        <span className="text-[#8E8E93] ml-2">{a.location}</span>
+                      )}
+                    </p>
+                  ))}
+                </div>
+                <TagSection entry={entry} tags={tags} />
+                <CalendarSection applicationId={entry.id} />
+                <EvidenceSection applicationId={entry.id} />
+                <div className="sm:col-span-2">
+                  <button
+                    className="text-[#007AFF] inline-flex items-center gap-1"
+                    onClick={e => {
+                      e.stopPropagation()
+                      setShowLetter(true)
+                    }}
+                  >
+                    <FileText className="h-3 w-3" />
+                    Cover letter PDF
+                  </button>
+                </div>
+              </div>
+            </motion.div>
+          )}
+        </AnimatePresence>
+      </Card>
+      <div onClick={e => e.stopPropagation()}>
+        <AnimatePresence>
+          {showLetter && <CoverLetterForm entry={entry} onClose={() => setShowLetter(false)} />}
+        </AnimatePresence>
+      </div>
+    </motion.div>
+  )
+}
+
+// The shell's own record (history.rs), readable while the engine is down or
+// after its database was reset.
+export default function HistoryPage() {
+  const [isTauriApp, setIsTauriApp] = useState(true)
+  const [items, setItems] = useState<HistoryEntry[]>([])
+  const [total, setTotal] = useState(0)
+  const [statuses, setStatuses] = useState<string[]>([])
+  const [tags, setTags] = useState<Tag[]>([])
+  const [filters, setFilters] = useState({ status: "", tag: "", since: "", until: "" })
+  const [searchInput, setSearchInput] = useState("")
+  const [search, setSearch] = useState("")
+  const [loading, setLoading] = useState(true)
+  const [loadingMore, setLoadingMore] = useState(false)
+  const [openId, setOpenId] = useState<number | null>(null)
+  const [showExport, setShowExport] = useState(false)
+  const [showReport, setShowReport] = useState(false)
+  const [showTags, setShowTags] = useState(false)
+  const [importing, setImporting] = useState(false)
+  const itemsLengthRef = useRef(0)
+  itemsLengthRef.current = items.length
+
+  useEffect(() => {
+    const t = setTimeout(() => setSearch(searchInput), 300)
+    return () => clearTimeout(t)
+  }, [searchInput])
+
+  const fetchPage = useCallback(
+    async (reset: boolean) => {
+      try {
+        const query = {
+          status: filters.status || null,
+          tag: filters.tag ? Number(filters.tag) : null,
+          since: filters.since || null,
+          until: filters.until || null,
+          offset: reset ? 0 : itemsLengthRef.current,
+          limit: PAGE,
+        }
+        // Words go to the full-text index, best matches first
+        const page = search.trim()
+          ? await invoke<{ items: HistoryEntry[]; total: number }>("search_applications", {
+              query: search,
+              filters: query,
+            })
+          : await invoke<{ items: HistoryEntry[]; total: number }>("query_history", { query })
+        setItems(prev => (reset ? page.items : [...prev, ...page.items]))
+        setTotal(page.total)
+      } catch (e) {
+        toast.error(String(e))
+      } finally {
+        setLoading(false)
+        setLoadingMore(false)
+      }
+    },
+    [filters, search]
+  )
+
+  useEffect(() => {
+    const tauri = "__TAURI_INTERNALS__" in window
+    setIsTauriApp(tauri)
+    if (!tauri) return
+    fetchPage(true)
+    invoke<string[]>("history_statuses").then(setStatuses).catch(() => {})
+    const unlisten = listen("history-changed", () => fetchPage(true))
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [fetchPage])
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => invoke<Tag[]>("list_tags").then(setTags).catch(() => {})
+    load()
+    const unlisten = listen("tags-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const importHistory = async () => {
+    setImporting(true)
+    try {
+      const summary = await invoke<ImportSummary | null>("import_application_history", { path: null })
+      if (!summary) return
+      const extra = [
+        summary.duplicates > 0 && `${summary.duplicates} already here`,
+        summary.skipped > 0 && `${summary.skipped} unreadable`,
+      ].filter(Boolean)
+      toast.success(`Imported ${summary.imported} of ${summary.rows}${extra.length ? ` (${extra.join(", ")})` : ""}`)
+      if (summary.errors.length > 0) toast.error(summary.errors[0])
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setImporting(false)
+    }
+  }
+
+  const exportCalendar = async () => {
+    try {
+      const path = await invoke<string | null>("export_calendar")
+      if (path) toast.success(`Saved ${path}`)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  if (!isTauriApp) {
+    return (
+      <div className="text-center py-20">
+        <History className="h-10 w-10 text-[#8E8E93] mx-auto mb-3" />
+        <p className="text-[#8E8E93]">The application history is kept by the desktop app.</p>
+      </div>
+    )
+  }
+
+  return (
+    <div className="max-w-4xl mx-auto space-y-5">
+      <div className="flex items-center justify-between">
+        <div>
+          <h1 className="text-2xl font-bold text-white">History</h1>
+          <p className="text-sm text-[#8E8E93] mt-0.5">
+            {total} applications, kept on this computer
+          </p>
+        </div>
+        <div className="flex gap-2">
+          <Button variant="outline" onClick={() => setShowTags(true)}>
+            <Tags className="h-4 w-4" />
+            Tags
+          </Button>
+          <Button variant="outline" loading={importing} onClick={importHistory}>
+            <Upload className="h-4 w-4" />
+            Import
+          </Button>
+          <Button variant="outline" onClick={exportCalendar}>
+            <CalendarDays className="h-4 w-4" />
+            Export calendar
+          </Button>
+          <Button variant="outline" onClick={() => setShowReport(true)}>
+            <FileText className="h-4 w-4" />
+            Weekly report
+          </Button>
+          <Button variant="outline" onClick={() => setShowExport(true)}>
+            <Download className="h-4 w-4" />
+            Export
+          </Button>
+        </div>
+      </div>
+
+      {/* Filter Bar */}
+      <div className="bg-white/5 border border-white/10 rounded-2xl p-3 flex flex-wrap gap-2 items-center">
+        <Filter className="h-4 w-4 text-[#8E8E93] shrink-0" />
+
+        <div className="flex items-center gap-1.5 flex-1 min-w-48 bg-white/5 rounded-xl px-3 py-1.5">
+          <Search className="h-3.5 w-3.5 text-[#8E8E93]" />
+          <input
+            type="text"
+            placeholder="Search companies, roles, descriptions and notes..."
+            value={searchInput}
+            onChange={e => setSearchInput(e.target.value)}
+            className="flex-1 bg-transparent text-sm text-white placeholder:text-[#8E8E93] outline-none"
+          />
+        </div>
+
+        <div className="relative">
+          <select
+            value={filters.status}
+            onChange={e => setFilters(f => ({ ...f, status: e.target.value }))}
+            className="appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 pr-7 text-sm text-white outline-none cursor-pointer"
+          >
+            <option value="">All statuses</option>
+            {statuses.map(s => (
+              <option key={s} value={s} className="bg-[#2C2C2E]">{s.replace(/_/g, " ")}</option>
+            ))}
+          </select>
+          <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+        </div>
+
+        {tags.length > 0 && (
+          <div className="relative">
+            <select
+              value={filters.tag}
+              onChange={e => setFilters(f => ({ ...f, tag: e.target.value }))}
+              className="appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 pr-7 text-sm text-white outline-none cursor-pointer"
+            >
+              <option value="">All tags</option>
+              {tags.map(t => (
+                <option key={t.id} value={t.id} className="bg-[#2C2C2E]">{t.name}</option>
+              ))}
+            </select>
+            <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+          </div>
+        )}
+
+        <input
+          type="date"
+          value={filters.since}
+          onChange={e => setFilters(f => ({ ...f, since: e.target.value }))}
+          title="Created from"
+          className="bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-sm text-white outline-none"
+        />
+        <input
+          type="date"
+          value={filters.until}
+          onChange={e => setFilters(f => ({ ...f, until: e.target.value }))}
+          title="Created until"
+          className="bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-sm text-white outline-none"
+        />
+      </div>
+
+      {loading ? (
+        <p className="text-sm text-[#8E8E93] text-center py-10">Loading…</p>
+      ) : items.length === 0 ? (
+        <div className="text-center py-20">
+          <History className="h-10 w-10 text-[#8E8E93] mx-auto mb-3" />
+          <p className="text-[#8E8E93]">No applications recorded.</p>
+        </div>
+      ) : (
+        <div className="space-y-2">
+          <AnimatePresence mode="popLayout">
+            {items.map(entry => (
+              <HistoryRow
+                key={entry.id}
+                entry={entry}
+                tags={tags}
+                open={openId === entry.id}
+                onToggle={() => setOpenId(id => (id === entry.id ? null : entry.id))}
+              />
+            ))}
+          </AnimatePresence>
+        </div>
+      )}
+
+      {items.length < total && !loading && (
+        <div className="flex justify-center pt-2">
+          <Button
+            variant="outline"
+            loading={loadingMore}
+            onClick={() => {
+              setLoadingMore(true)
+              fetchPage(false)
+            }}
+          >
+            <ChevronDown className="h-4 w-4" />
+            Load more
+          </Button>
+        </div>
+      )}
+
+      <AnimatePresence>
+        {showExport && (
+          <ExportForm
+            range={{ since: filters.since, until: filters.until }}
+            onClose={() => setShowExport(false)}
+          />
+        )}
+        {showReport && <ReportForm onClose={() => setShowReport(false)} />}
+        {showTags && <TagsForm tags={tags} onClose={() => setShowTags(false)} />}
+      </AnimatePresence>
+    </div>
+  )
+}
@@ -3,8 +3,10 @@ import { useEffect, useState, useCallback, useRef } from "react"
 import { motion, AnimatePresence } from "motion/react"
 import {
   Search, MapPin, DollarSign, ExternalLink, Briefcase,
-  ChevronDown, Filter
+  ChevronDown, Filter, Bell, Trash2
 } from "lucide-react"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
 import { api } from "@/lib/api"
 import { toast } from "@/lib/toast"
 import type { Job } from "@/lib/types"
@@ -18,6 +20,276 @@ const SITES    = ["", "infojobs", "linkedin", "indeed", "trabajos", "jobtoday"]
 const STATUSES = ["", "scraped", "qualified", "cv_generating", "cv_ready", "applied", "rejected"]
 const PROFILES = ["", "cashier", "stocker", "logistics", "frontend_dev", "fullstack_dev"]
 
+interface Salary {
+  period: string
+  currency: string
+  annual_min: number | null
+  annual_max: number | null
+  converted: { currency: string; annual_min: number | null; annual_max: number | null } | null
+}
+
+// The posting's salary a year, in euros where it's in another currency
+// (salary.rs); only in the desktop app, and only where it isn't yearly already.
+function AnnualSalary({ raw }: { raw: string }) {
+  const [salary, setSalary] = useState<Salary | null>(null)
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    invoke<Salary | null>("normalize_salary", { text: raw, currency: "EUR" })
+      .then(setSalary)
+      .catch(() => {})
+  }, [raw])
+
+  if (!salary) return null
+  const figures = salary.converted ?? salary
+  const currency = salary.converted?.currency ?? salary.currency
+  if (salary.period === "year" && !salary.converted) return null
+  const format = (n: number) =>
+    new Intl.NumberFormat(undefined, { style: "currency", currency, notation: "compact", maximumFractionDigits: 1 }).format(n)
+  const range = [figures.annual_min, figures.annual_max]
+    .filter((n): n is number => n !== null)
+    .filter((n, i, all) => all.indexOf(n) === i)
+    .map(format)
+    .join("–")
+  return <span className="text-[11px] text-[#8E8E93]">≈ {range} a year</span>
+}
+
+interface SavedSearch {
+  id: string
+  name: string
+  keywords: string[]
+  excluded: string[]
+  location: string | null
+  // A year, in currency
+  min_salary: number | null
+  currency: string
+  include_unknown_salary: boolean
+  enabled: boolean
+}
+
+interface AlertMatch {
+  search_id: string
+  search_name: string
+  job_id: number | null
+  url: string
+  title: string
+  company: string
+  location: string | null
+  matched_at: string
+}
+
+interface AlertStore {
+  searches: SavedSearch[]
+  matches: AlertMatch[]
+}
+
+const EMPTY_SEARCH: SavedSearch = {
+  id: "",
+  name: "",
+  keywords: [],
+  excluded: [],
+  location: null,
+  min_salary: null,
+  currency: "EUR",
+  include_unknown_salary: true,
+  enabled: true,
+}
+
+const splitWords = (text: string) => text.split(",").map(w => w.trim()).filter(Boolean)
+
+// Saved searches the desktop app checks new jobs against and notifies on
+// (alerts.rs), with what they matched lately.
+function JobAlerts({ onClose }: { onClose: () => void }) {
+  const [store, setStore] = useState<AlertStore>({ searches: [], matches: [] })
+  const [draft, setDraft] = useState<SavedSearch | null>(null)
+  const [keywords, setKeywords] = useState("")
+  const [excluded, setExcluded] = useState("")
+
+  useEffect(() => {
+    const load = () => invoke<AlertStore>("list_job_alerts").then(setStore).catch(() => {})
+    load()
+    const unlisten = listen("job-alerts-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const edit = (search: SavedSearch) => {
+    setDraft(search)
+    setKeywords(search.keywords.join(", "))
+    setExcluded(search.excluded.join(", "))
+  }
+
+  const save = (search: SavedSearch) =>
+    invoke("save_job_alert", { search })
+      .then(() => setDraft(null))
+      .catch(e => toast.error(String(e)))
+
+  const remove = (id: string) =>
+    invoke("delete_job_alert", { id }).catch(e => toast.error(String(e)))
+
+  const input = "bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+
+  return (
+    <motion.div
+      initial={{ opacity: 0 }}
+      animate={{ opacity: 1 }}
+      exit={{ opacity: 0 }}
+      className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50 flex items-center justify-center p-4"
+      onClick={onClose}
+    >
+      <motion.div
+        initial={{ scale: 0.95, y: 10 }}
+        animate={{ scale: 1, y: 0 }}
+        exit={{ scale: 0.95, y: 10 }}
+        transition={{ type: "spring", stiffness: 300, damping: 25 }}
+        className="bg-[#2C2C2E] border border-white/10 rounded-2xl p-5 w-full max-w-lg max-h-[85vh] overflow-y-auto"
+        onClick={e => e.stopPropagation()}
+      >
+        <h3 className="text-base font-semibold text-white mb-1">Job alerts</h3>
+        <p className="text-xs text-[#8E8E93] mb-4">
+          New jobs matching a saved search are notified as they come in, during a run too.
+        </p>
+
+        {draft ? (
+          <div className="space-y-2">
+            <input
+              className={cn(input, "w-full")}
+              placeholder="Name"
+              value={draft.name}
+              onChange={e => setDraft({ ...draft, name: e.target.value })}
+            />
+            <input
+              className={cn(input, "w-full")}
+              placeholder="Words, comma separated (all must appear)"
+              value={keywords}
+              onChange={e => setKeywords(e.target.value)}
+            />
+            <input
+              className={cn(input, "w-full")}
+              placeholder="Leave out jobs with these words"
+              value={excluded}
+              onChange={e => setExcluded(e.target.value)}
+            />
+            <input
+              className={cn(input, "w-full")}
+              placeholder="Location (part of it, e.g. Madrid)"
+              value={draft.location ?? ""}
+              onChange={e => setDraft({ ...draft, location: e.target.value || null })}
+            />
+            <div className="flex gap-2">
+              <input
+                type="number"
+                min={0}
+                step={1000}
+                className={cn(input, "flex-1")}
+                placeholder="Minimum salary a year"
+                value={draft.min_salary ?? ""}
+                onChange={e => setDraft({ ...draft, min_salary: e.target.value ? Number(e.target.value) : null })}
+              />
+              <input
+                className={cn(input, "w-20 uppercase")}
+                maxLength={3}
+                value={draft.currency}
+                onChange={e => setDraft({ ...draft, currency: e.target.value })}
+              />
+            </div>
+            {draft.min_salary !== null && (
+              <label className="flex items-center gap-2 text-xs text-[#8E8E93]">
+                <input
+                  type="checkbox"
+                  checked={draft.include_unknown_salary}
+                  onChange={e => setDraft({ ...draft, include_unknown_salary: e.target.checked })}
+                />
+                Include jobs that don&apos;t say the salary
+              </label>
+            )}
+            <div className="flex gap-2 pt-2">
+              <Button variant="outline" className="flex-1" onClick={() => setDraft(null)}>
+                Cancel
+              </Button>
+              <Button
+                className="flex-1"
+                onClick={() => save({ ...draft, keywords: splitWords(keywords), excluded: splitWords(excluded) })}
+              >
+                Save
+              </Button>
+            </div>
+          </div>
+        ) : (
+          <>
+            {store.searches.length === 0 && (
+              <p className="text-xs text-[#8E8E93]">No saved searches yet.</p>
+            )}
+            <div className="space-y-2">
+              {store.searches.map(search => (
+                <div key={search.id} className="flex items-center gap-2 bg-white/5 rounded-xl px-3 py-2">
+                  <input
+                    type="checkbox"
+                    checked={search.enabled}
+                    onChange={e => save({ ...search, enabled: e.target.checked })}
+                  />
+                  <button className="flex-1 text-left min-w-0" onClick={() => edit(search)}>
+                    <p className="text-sm text-white truncate">{search.name}</p>
+                    <p className="text-[11px] text-[#8E8E93] truncate">
+                      {[
+                        search.keywords.join(", "),
+                        search.location,
+                        search.min_salary !== null && `≥ ${search.min_salary.toLocaleString()} ${search.currency}`,
+                      ].filter(Boolean).join(" · ")}
+                    </p>
+                  </button>
+                  <button className="text-[#8E8E93] hover:text-[#FF3B30]" onClick={() => remove(search.id)}>
+                    <Trash2 className="h-3.5 w-3.5" />
+                  </button>
+                </div>
+              ))}
+            </div>
+            <div className="flex gap-2 mt-3">
+              <Button variant="outline" className="flex-1" onClick={() => edit(EMPTY_SEARCH)}>
+                New search
+              </Button>
+              <Button variant="outline" className="flex-1" onClick={onClose}>
+                Done
+              </Button>
+            </div>
+
+            {store.matches.length > 0 && (
+              <div className="mt-5">
+                <div className="flex items-center justify-between mb-2">
+                  <p className="text-xs font-medium text-white">Recent matches</p>
+                  <button
+                    className="text-[11px] text-[#8E8E93] hover:text-white"
+                    onClick={() => invoke("clear_job_alert_matches").catch(e => toast.error(String(e)))}
+                  >
+                    Clear
+                  </button>
+                </div>
+                <div className="space-y-1.5">
+                  {store.matches.map(m => (
+                    <a
+                      key={`${m.search_id}-${m.job_id ?? m.url}`}
+                      href={m.url}
+                      target="_blank"
+                      rel="noopener noreferrer"
+                      className="block bg-white/5 rounded-xl px-3 py-2 hover:bg-white/10"
+                    >
+                      <p className="text-sm text-white truncate">{m.company} — {m.title}</p>
+                      <p className="text-[11px] text-[#8E8E93] truncate">
+                        {[m.search_name, m.location, formatDate(m.matched_at)].filter(Boolean).join(" · ")}
+                      </p>
+                    </a>
+                  ))}
+                </div>
+              </div>
+            )}
+          </>
+        )}
+      </motion.div>
+    </motion.div>
+  )
+}
+
 function JobSkeleton() {
   return (
     <div className="animate-pulse bg-white/5 border border-white/10 rounded-2xl p-4 space-y-3">
@@ -99,6 +371,7 @@ function JobRow({
                 {job.salary_raw}
               </span>
             )}
+            {job.salary_raw && <AnnualSalary raw={job.salary_raw} />}
             {job.cv_profile && <ProfilePill profile={job.cv_profile} />}
           </div>
         </div>
@@ -151,6 +424,8 @@ export default function JobsPage() {
   const [loadingMore, setLoadingMore] = useState(false)
   const [focusedIndex, setFocusedIndex] = useState(0)
   const [totalCount, setTotalCount] = useState(0)
+  const [showAlerts, setShowAlerts] = useState(false)
+  const [isDesktop, setIsDesktop] = useState(false)
 
   const [filters, setFilters] = useState({ site: "", status: "", cv_profile: "" })
   const [searchInput, setSearchInput] = useState("")  // raw input — instant
@@ -221,6 +496,10 @@ export default function JobsPage() {
     }
   }
 
+  useEffect(() => {
+    setIsDesktop("__TAURI_INTERNALS__" in window)
+  }, [])
+
   useEffect(() => {
     api.getJobCounts().then(c => {
       setTotalCount(Object.values(c).reduce((a, b) => a + b, 0))
@@ -248,8 +527,18 @@ export default function JobsPage() {
           <h1 className="text-2xl font-bold text-white">Jobs</h1>
           <p className="text-sm text-[#8E8E93] mt-0.5">{totalCount} total scraped</p>
         </div>
+        {isDesktop && (
+          <Button variant="outline" size="sm" onClick={() => setShowAlerts(true)}>
+            <Bell className="h-3.5 w-3.5" />
+            Alerts
+          </Button>
+        )}
       </div>
 
+      <AnimatePresence>
+        {showAlerts && <JobAlerts onClose={() => setShowAlerts(false)} />}
+      </AnimatePresence>
+
       {/* Filter Bar */}
       <div className="bg-white/5 border border-white/10 rounded-2xl p-3 flex flex-wrap gap-2 items-center">
         <Filter className="h-4 w-4 text-[#8E8E93] shrink-0" />
@@ -5,6 +5,13 @@ import { Sidebar } from "@/components/Sidebar"
 import { CommandPalette } from "@/components/CommandPalette"
 import { Toaster } from "@/components/ui/toast"
 import { SetupGuard } from "@/components/SetupGuard"
+import { ActivityReporter } from "@/components/ActivityReporter"
+import { FirstPaintReporter } from "@/components/FirstPaintReporter"
+import { IpcMonitor } from "@/components/IpcMonitor"
+import { DebugOverlay } from "@/components/DebugOverlay"
+import { RouteListener } from "@/components/RouteListener"
+import { OfflineQueue } from "@/components/OfflineQueue"
+import { ClipboardIntake } from "@/components/ClipboardIntake"
 
 const geist = Geist({ subsets: ["latin"], variable: "--font-geist" })
 const geistMono = Geist_Mono({ subsets: ["latin"], variable: "--font-geist-mono" })
@@ -19,9 +26,16 @@ export default function RootLayout({ children }: { children: React.ReactNode })
     <html lang="es">
       <body className={`${geist.variable} ${geistMono.variable} antialiased`} style={{ background: "var(--bg)", color: "var(--fg)" }}>
         <SetupGuard />
+        <ActivityReporter />
+        <FirstPaintReporter />
+        <IpcMonitor />
+        <DebugOverlay />
+        <RouteListener />
         <Sidebar />
         <CommandPalette />
         <Toaster />
+        <OfflineQueue />
+        <ClipboardIntake />
         <main className="ml-16 min-h-screen p-6">
           {children}
         </main>
@@ -0,0 +1,167 @@
+"use client"
+import { useEffect, useState } from "react"
+import { invoke } from "@tauri-apps/api/core"
+import { CheckCircle2, XCircle } from "lucide-react"
+import { Button } from "@/components/ui/button"
+
+type Step = "welcome" | "notifications" | "autostart" | "credentials" | "done"
+
+interface OnboardingState {
+  step: Step
+  completed: boolean
+  checks: {
+    notification_permission: "granted" | "denied" | "unknown"
+    autostart_enabled: boolean
+    backend_healthy: boolean
+    credentials_added: boolean
+    portable: boolean
+  }
+}
+
+interface SystemInfo {
+  os_version: string | null
+  memory_total_bytes: number | null
+  disk_free_bytes: number | null
+}
+
+// What the engine and its browser need to run comfortably
+const MIN_MEMORY_BYTES = 4 * 1024 ** 3
+const MIN_FREE_DISK_BYTES = 2 * 1024 ** 3
+
+const gb = (v: number) => `${(v / 1024 ** 3).toFixed(1)} GB`
+
+const inputCls = "w-full rounded-lg bg-white/5 border border-white/10 px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none focus:border-[#007AFF]/60"
+
+function Check({ label, ok }: { label: string; ok: boolean }) {
+  return (
+    <div className="flex items-center gap-2 text-sm">
+      {ok ? <CheckCircle2 className="h-4 w-4 text-[#34C759]" /> : <XCircle className="h-4 w-4 text-[#8E8E93]" />}
+      <span className={ok ? "text-white" : "text-[#8E8E93]"}>{label}</span>
+    </div>
+  )
+}
+
+// Opened by the shell on first launch, in its own window, before the main
+// window is shown. Progress lives in the shell's settings, so closing this
+// halfway resumes at the same step next launch.
+export default function OnboardingPage() {
+  const [state, setState] = useState<OnboardingState | null>(null)
+  const [busy, setBusy] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+  const [username, setUsername] = useState("")
+  const [password, setPassword] = useState("")
+  const [system, setSystem] = useState<SystemInfo | null>(null)
+
+  const refresh = () =>
+    invoke<OnboardingState>("get_onboarding")
+      .then(setState)
+      .catch(e => setError(String(e)))
+
+  useEffect(() => {
+    refresh()
+    invoke<SystemInfo>("get_system_info").then(setSystem).catch(() => {})
+  }, [])
+
+  const run = async (fn: () => Promise<unknown>) => {
+    setBusy(true)
+    setError(null)
+    try {
+      await fn()
+      await refresh()
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const next = () =>
+    run(() => invoke<OnboardingState>("advance_onboarding", { from: state?.step }).then(setState))
+
+  const checks = state?.checks
+
+  return (
+    <div className="fixed inset-0 z-50 flex flex-col p-6" style={{ background: "var(--bg)" }}>
+      <h1 className="text-lg font-semibold text-white">Welcome to JobBot</h1>
+      <div className="flex-1 overflow-y-auto mt-4 space-y-4">
+        {!state ? (
+          <p className="text-sm text-[#8E8E93]">Loading…</p>
+        ) : state.step === "welcome" ? (
+          <>
+            <p className="text-sm text-white/90">
+              A few quick checks before you start. You can change all of this later in Settings.
+            </p>
+            <div className="rounded-xl border border-white/10 p-4 space-y-2">
+              <Check label="Engine running" ok={checks!.backend_healthy} />
+              <Check label="Notifications allowed" ok={checks!.notification_permission === "granted"} />
+              {!checks!.portable && <Check label="Starts on login" ok={checks!.autostart_enabled} />}
+              <Check label="InfoJobs login saved" ok={checks!.credentials_added} />
+              {system?.memory_total_bytes != null && (
+                <Check
+                  label={`Memory: ${gb(system.memory_total_bytes)}${system.memory_total_bytes < MIN_MEMORY_BYTES ? " (4 GB or more recommended)" : ""}`}
+                  ok={system.memory_total_bytes >= MIN_MEMORY_BYTES}
+                />
+              )}
+              {system?.disk_free_bytes != null && (
+                <Check
+                  label={`Free disk space: ${gb(system.disk_free_bytes)}${system.disk_free_bytes < MIN_FREE_DISK_BYTES ? " (2 GB or more recommended)" : ""}`}
+                  ok={system.disk_free_bytes >= MIN_FREE_DISK_BYTES}
+                />
+              )}
+            </div>
+            {system?.os_version && <p className="text-xs text-[#8E8E93]">{system.os_version}</p>}
+          </>
+        ) : state.step === "notifications" ? (
+          <>
+            <p className="text-sm text-white/90">
+              JobBot notifies you when new matches arrive and when an application needs your review.
+            </p>
+            <Check label="Notifications allowed" ok={checks!.notification_permission === "granted"} />
+            {checks!.notification_permission !== "granted" && (
+              <Button variant="outline" loading={busy} onClick={() => run(() => invoke("request_notification_permission"))}>
+                Allow notifications
+              </Button>
+            )}
+          </>
+        ) : state.step === "autostart" ? (
+          <>
+            <p className="text-sm text-white/90">
+              Start JobBot in the tray when you log in, so searches keep running on schedule.
+            </p>
+            <Check label="Starts on login" ok={checks!.autostart_enabled} />
+            <Button
+              variant="outline"
+              loading={busy}
+              onClick={() => run(() => invoke("set_autolaunch", { enabled: !checks!.autostart_enabled }))}
+            >
+              {checks!.autostart_enabled ? "Don't start on login" : "Start on login"}
+            </Button>
+          </>
+        ) : (
+          <>
+            <p className="text-sm text-white/90">
+              Optionally save your InfoJobs login. It is kept in the system keychain, never in a file.
+            </p>
+            <Check label="InfoJobs login saved" ok={checks!.credentials_added} />
+            <input className={inputCls} placeholder="Email" value={username} onChange={e => setUsername(e.target.value)} />
+            <input className={inputCls} type="password" placeholder="Password" value={password} onChange={e => setPassword(e.target.value)} />
+            <Button
+              variant="outline"
+              loading={busy}
+              disabled={!username || !password}
+              onClick={() => run(() => invoke("save_credentials", { username, password }).then(() => setPassword("")))}
+            >
+              Save login
+            </Button>
+          </>
+        )}
+        {error && <p className="text-sm text-[#FF3B30]">{error}</p>}
+      </div>
+      <div className="flex justify-end gap-2 mt-4">
+        <Button onClick={next} loading={busy} disabled={!state}>
+          {state?.step === "credentials" ? "Finish" : "Continue"}
+        </Button>
+      </div>
+    </div>
+  )
+}
@@ -10,6 +10,7 @@ import type { SystemHealth, ScraperStatus, Application } from "@/lib/types"
 import { Card, CardHeader, CardTitle } from "@/components/ui/card"
 import { Button } from "@/components/ui/button"
 import { HealthDot } from "@/components/ui/health-dot"
+import { ActivityStats } from "@/components/ActivityStats"
 import { formatDate } from "@/lib/utils"
 import Link from "next/link"
 
@@ -412,6 +413,8 @@ export default function DashboardPage() {
         </div>
       )}
 
+      <ActivityStats />
+
       {/* Setup Incomplete Warning */}
       {health && !health.setup_complete && (
         <motion.div
@@ -0,0 +1,80 @@
+"use client"
+import { useEffect, useState } from "react"
+import { invoke } from "@tauri-apps/api/core"
+import { getCurrentWindow } from "@tauri-apps/api/window"
+import { Button } from "@/components/ui/button"
+
+interface ReleaseNotes {
+  version: string
+  current_version: string
+  date: string | null
+  notes: string | null
+  critical: boolean
+}
+
+// Opened by the shell (tray "Update ready" item) in its own small window.
+// Covers the app chrome from the root layout so only the notes are shown.
+export default function ReleaseNotesPage() {
+  const [release, setRelease] = useState<ReleaseNotes | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [installing, setInstalling] = useState(false)
+
+  useEffect(() => {
+    invoke<ReleaseNotes>("get_release_notes")
+      .then(setRelease)
+      .catch(e => setError(String(e)))
+  }, [])
+
+  const install = async () => {
+    setInstalling(true)
+    try {
+      await invoke("install_update") // restarts the app on success
+    } catch (e) {
+      setError(String(e))
+      setInstalling(false)
+    }
+  }
+
+  // Postpones the reminder only; the update stays downloaded
+  const snooze = async (duration: "day" | "week") => {
+    await invoke("snooze_update", { duration }).catch(() => {})
+    getCurrentWindow().close()
+  }
+
+  return (
+    <div className="fixed inset-0 z-50 flex flex-col p-6" style={{ background: "var(--bg)" }}>
+      <h1 className="text-lg font-semibold text-white">
+        {release ? `JobBot ${release.version}` : "What's new"}
+      </h1>
+      {release?.critical && (
+        <p className="text-xs text-[#FF3B30] mt-1">This update contains a critical fix.</p>
+      )}
+      {release && (
+        <p className="text-xs text-[#8E8E93] mt-1">
+          You have {release.current_version}
+          {release.date ? ` · released ${release.date.slice(0, 10)}` : ""}
+        </p>
+      )}
+      <div className="flex-1 overflow-y-auto mt-4 rounded-xl border border-white/10 p-4">
+        {error ? (
+          <p className="text-sm text-[#FF3B30]">{error}</p>
+        ) : (
+          <pre className="whitespace-pre-wrap font-sans text-sm text-white/90">
+            {release ? release.notes ?? "No release notes were published for this version." : "Loading…"}
+          </pre>
+        )}
+      </div>
+      <div className="flex justify-end gap-2 mt-4">
+        <Button variant="ghost" onClick={() => snooze("day")}>
+          Tomorrow
+        </Button>
+        <Button variant="ghost" onClick={() => snooze("week")}>
+          Next week
+        </Button>
+        <Button onClick={install} loading={installing} disabled={!release}>
+          Restart & update
+        </Button>
+      </div>
+    </div>
+  )
+}
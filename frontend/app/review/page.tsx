@@ -7,7 +7,9 @@ import {
   CheckCircle2, XCircle, ArrowLeft, AlertTriangle,
   ExternalLink, Clock, Image as ImageIcon
 } from "lucide-react"
-import { api } from "@/lib/api"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { api, BASE, withToken } from "@/lib/api"
 import { playSuccess, playError } from "@/lib/sounds"
 import type { Application } from "@/lib/types"
 import { Button } from "@/components/ui/button"
@@ -16,8 +18,6 @@ import { StatusBadge, ProfilePill } from "@/components/ui/badge"
 import { Card } from "@/components/ui/card"
 import { formatDate, cn } from "@/lib/utils"
 
-const BASE = "http://localhost:8000"
-
 function QualityRing({ score }: { score: number | null }) {
   if (score === null) return <span className="text-[#8E8E93] text-2xl font-bold">—</span>
   const color =
@@ -113,6 +113,65 @@ function SessionCountdown({ authorizedAt }: { authorizedAt: string | null }) {
   )
 }
 
+interface DuplicateFlag {
+  matches: {
+    application_id: number
+    company: string
+    role: string | null
+    status: string
+    applied_at: string | null
+  }[]
+}
+
+// Earlier applications to the same job, as the desktop shell found them (duplicates.rs).
+function DuplicateWarning({ applicationId }: { applicationId: number }) {
+  const [flag, setFlag] = useState<DuplicateFlag | null>(null)
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () =>
+      invoke<DuplicateFlag | null>("get_duplicate_flag", { applicationId }).then(setFlag).catch(() => {})
+    load()
+    const unlisten = listen<{ application_id: number }>("duplicate-flagged", e => {
+      if (e.payload.application_id === applicationId) load()
+    })
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [applicationId])
+
+  if (!flag || flag.matches.length === 0) return null
+
+  const dismiss = () => {
+    invoke("dismiss_duplicate_flag", { applicationId })
+      .then(() => setFlag(null))
+      .catch(() => toast.error("Failed to dismiss the warning"))
+  }
+
+  return (
+    <motion.div
+      initial={{ opacity: 0, y: -6 }}
+      animate={{ opacity: 1, y: 0 }}
+      className="bg-amber-400/10 border border-amber-400/20 rounded-xl px-3 py-2 flex items-start gap-2"
+    >
+      <AlertTriangle className="h-4 w-4 text-amber-400 shrink-0 mt-0.5" />
+      <div className="flex-1 text-sm text-amber-400">
+        <p>Looks like you already applied here:</p>
+        <ul className="mt-1 space-y-0.5">
+          {flag.matches.map(m => (
+            <li key={m.application_id} className="text-xs">
+              {m.company}{m.role && ` — ${m.role}`} · {m.applied_at ? formatDate(m.applied_at) : "date unknown"} · {m.status.replace(/_/g, " ")}
+            </li>
+          ))}
+        </ul>
+      </div>
+      <Button size="sm" variant="ghost" onClick={dismiss}>
+        Dismiss
+      </Button>
+    </motion.div>
+  )
+}
+
 type ExtendedApplication = Application & {
   title?: string
   cv_adapted_json?: Record<string, unknown>
@@ -178,9 +237,10 @@ function ReviewContent() {
       setTimeout(() => setFlashColor(null), 600)
       setSuccessVisible(true)
       setTimeout(() => router.push("/applications"), 2000)
-    } catch {
+    } catch (e) {
       playError()
-      toast.error("Failed to authorize application")
+      // The shell says why when a quota holds it back
+      toast.error(typeof e === "string" ? e : "Failed to authorize application")
     } finally {
       setAuthorizing(false)
     }
@@ -237,7 +297,7 @@ function ReviewContent() {
   }
 
   const screenshotUrl = app.form_screenshot_path
-    ? `${BASE}/api/screenshots/${encodeURIComponent(app.form_screenshot_path)}`
+    ? withToken(`${BASE}/api/screenshots/${encodeURIComponent(app.form_screenshot_path)}`)
     : null
 
   return (
@@ -318,6 +378,7 @@ function ReviewContent() {
       </div>
 
       <SessionCountdown authorizedAt={app.authorized_at} />
+      <DuplicateWarning applicationId={app.id} />
 
       <div className="grid grid-cols-1 lg:grid-cols-2 gap-4">
         <div className="lg:sticky lg:top-4 self-start">
@@ -4,18 +4,21 @@ import { motion, AnimatePresence } from "motion/react"
 import {
   Cpu, Globe, Clock, Trash2, Building2, XCircle,
   Plus, Save, CheckCircle2, AlertTriangle, Volume2,
-  Database, FileText, ChevronDown, Power
+  Database, FileText, ChevronDown, Power, CalendarClock, Play,
+  UserRound, RotateCcw, Network, Shuffle, Mail, KeyRound, Webhook, Gauge,
+  Download
 } from "lucide-react"
+import Link from "next/link"
 import { invoke } from "@tauri-apps/api/core"
-import { api } from "@/lib/api"
+import { listen } from "@tauri-apps/api/event"
+import { api, authHeaders, BASE, isQueued } from "@/lib/api"
 import { playSuccess, playError } from "@/lib/sounds"
+import { toast } from "@/lib/toast"
 import type { CompanySource } from "@/lib/types"
 import { Card, CardHeader, CardTitle } from "@/components/ui/card"
 import { Button } from "@/components/ui/button"
 import { cn } from "@/lib/utils"
 
-const BASE = "http://localhost:8000"
-
 const SCRAPER_TYPES = [
   "career_page",
   "greenhouse",
@@ -25,6 +28,391 @@ const SCRAPER_TYPES = [
   "workday",
 ]
 
+type LoginItemMethod = "auto" | "launch_agent" | "app_service" | "run_key" | "all_users"
+
+const LOGIN_ITEM_LABELS: Record<LoginItemMethod, string> = {
+  auto: "Automatic",
+  app_service: "Login item",
+  launch_agent: "Launch agent",
+  run_key: "This user",
+  all_users: "All users (needs administrator)",
+}
+
+interface LoginItemInfo {
+  enabled: boolean
+  method: LoginItemMethod
+  effective: LoginItemMethod
+  // Empty when there's no choice on this OS
+  methods: LoginItemMethod[]
+  requires_approval: boolean
+}
+
+interface DisplayCompat {
+  session: "wayland" | "x11" | "other"
+  desktop: string | null
+  compat_mode: boolean
+  xwayland: boolean
+  tray_host: boolean
+  global_shortcuts: boolean
+  window_positioning: boolean
+}
+
+interface LanguageInfo {
+  preference: string | null
+  effective: string
+  available: { code: string; name: string }[]
+}
+
+interface SyncInfo {
+  folder: string | null
+  status:
+    | { state: "off" }
+    | { state: "synced"; at: string }
+    | { state: "conflict"; fields: string[] }
+    | { state: "failed"; error: string }
+}
+
+interface SessionInfo {
+  site: string
+  saved_at: number
+  expires_at: number
+}
+
+interface AppLockInfo {
+  enabled: boolean
+  idle_minutes: number
+  available: boolean
+  locked: boolean
+}
+
+interface CrashReport {
+  id: string
+  at: string
+  kind: "panic" | "signal" | "exception"
+  message: string
+  location: string | null
+  backtrace: string | null
+  app_version: string
+  engine_version: string | null
+  os: string
+  log_tail: string[]
+  minidump: string | null
+}
+
+interface ErrorReportingStatus {
+  enabled: boolean
+  dsn: string
+  available: boolean
+}
+
+interface TelemetryStatus {
+  enabled: boolean
+  sending: boolean
+  last_sent_at: string | null
+  pending: {
+    schema: number
+    day: string
+    app_version: string
+    os: string
+    channel: string
+    counters: Record<string, number>
+  }[]
+}
+
+interface LogUsage {
+  shell_bytes: number
+  engine_bytes: number
+  budget_bytes: number
+  max_age_days: number
+}
+
+interface RedactionSettings {
+  patterns: string[]
+  names: string[]
+}
+
+interface ClipboardWatch {
+  watch_job_urls: boolean
+  job_domains: string[]
+}
+
+interface BrowserExtension {
+  settings: { enabled: boolean; chrome_extension_ids: string[] }
+  registered: string[]
+  firefox_extension_id: string
+}
+
+type ScheduleTiming =
+  | { kind: "cron"; expression: string }
+  | { kind: "every"; hours: number; from_hour: number; to_hour: number; weekdays_only: boolean }
+
+type ScheduleTarget = { kind: "all_scrapers" } | { kind: "scraper"; site: string }
+
+interface ScheduleInput {
+  name: string
+  timing: ScheduleTiming
+  target: ScheduleTarget
+  enabled: boolean
+  catch_up: boolean
+}
+
+interface ScheduleInfo extends ScheduleInput {
+  id: string
+  created_at: string
+  handled_until: string | null
+  last_run: string | null
+  last_error: string | null
+  next_run: string | null
+}
+
+interface DriverStatus {
+  browser: "chrome" | "edge"
+  browser_version: string | null
+  driver_version: string | null
+  up_to_date: boolean
+  path: string | null
+}
+
+interface BrowserProfileStatus {
+  id: string
+  site: string
+  account: string
+  active: boolean
+  created_at: string
+  reset_at: string | null
+  size_bytes: number
+  in_use: boolean
+}
+
+interface PoolProxyStatus {
+  id: string
+  label: string
+  scheme: "http" | "https" | "socks5"
+  host: string
+  port: number
+  username: string | null
+  enabled: boolean
+  healthy: boolean | null
+  latency_ms: number | null
+  checked_at: string | null
+  last_error: string | null
+  requests: number
+  bans: number
+  captchas: number
+  rested_until: string | null
+  has_password: boolean
+  ban_rate: number
+  captcha_rate: number
+  sites: string[]
+}
+
+interface NewPoolProxy {
+  label: string
+  scheme: PoolProxyStatus["scheme"]
+  host: string
+  port: string
+  username: string
+  password: string
+}
+
+interface MailSettings {
+  enabled: boolean
+  host: string
+  port: number
+  username: string
+  folder: string
+  interval_minutes: number
+  update_status: boolean
+}
+
+interface MailMatch {
+  message_id: string
+  received_at: string | null
+  from: string
+  subject: string
+  kind: "confirmation" | "interview" | "offer" | "rejection" | null
+  application_id: number | null
+  company: string | null
+  status: string | null
+}
+
+interface MailWatch {
+  settings: MailSettings
+  has_password: boolean
+  last_check: string | null
+  last_error: string | null
+  recent: MailMatch[]
+}
+
+const MAIL_KIND_LABELS: Record<NonNullable<MailMatch["kind"]>, string> = {
+  confirmation: "Received",
+  interview: "Interview",
+  offer: "Offer",
+  rejection: "Rejected",
+}
+
+interface OAuthStatus {
+  provider: "linkedin" | "google"
+  client_id: string
+  has_client_secret: boolean
+  callback_port: number
+  connected: boolean
+  expires_at: string | null
+  signing_in: boolean
+}
+
+const OAUTH_LABELS: Record<OAuthStatus["provider"], string> = {
+  linkedin: "LinkedIn",
+  google: "Google",
+}
+
+interface WebhookStatus {
+  id: string
+  url: string
+  events: WebhookEvent[]
+  enabled: boolean
+  has_secret: boolean
+}
+
+interface WebhookDelivery {
+  id: string
+  webhook_id: string
+  event: string
+  created_at: string
+  state: "pending" | "delivered" | "failed"
+  attempts: number
+  next_attempt_at: string | null
+  response_status: number | null
+  last_error: string | null
+}
+
+interface QuotaSettings {
+  enabled: boolean
+  daily_per_board: number
+  boards: Record<string, number>
+  min_delay_minutes: number
+}
+
+interface QuotaStatus {
+  settings: QuotaSettings
+  boards: { site: string; used: number; limit: number | null; remaining: number | null }[]
+  last_sent_at: string | null
+  next_allowed_at: string | null
+  resets_at: string
+}
+
+// What a retention run removes, or would (retention.rs)
+interface RetentionReport {
+  dry_run: boolean
+  applications: { id: number; company: string; role: string | null; status: string; updated_at: string | null }[]
+  screenshots: { application_id: number; source: "engine" | "evidence"; path: string | null; bytes: number; taken_at: string | null }[]
+  bytes: number
+  engine_reachable: boolean
+}
+
+const RETENTION_STATUSES = ["rejected", "withdrawn", "expired"]
+
+// When automation may run (workhours.rs)
+interface WorkingHours {
+  settings: {
+    enabled: boolean
+    from_hour: number
+    to_hour: number
+    // 1 = Monday ... 7 = Sunday
+    days: number[]
+    override_until: string | null
+  }
+  state: "open" | "closed" | "overridden"
+  next_change: string | null
+}
+
+const WEEKDAYS = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+
+interface DataDirInfo {
+  path: string | null
+  default_path: string | null
+  custom: boolean
+  locked_by_env: boolean
+  log_path: string | null
+}
+
+type DataMoveProgress =
+  | { stage: "copying" | "verifying"; done: number; total: number }
+  | { stage: "switching" | "cleaning" }
+  | { stage: "done"; path: string }
+
+// The shell's updater (updater.rs), as its update-status event carries it
+type UpdateStatus =
+  | { state: "idle" | "checking" | "up_to_date" | "verifying" | "installing" }
+  | { state: "downloading"; downloaded: number; total: number | null; percent: number | null }
+  | { state: "ready"; version: string }
+  | { state: "failed"; error: string }
+
+interface BackupSettings {
+  enabled: boolean
+  interval_hours: number
+  keep: number
+  folder: string | null
+  last_backup_at: string | null
+}
+
+interface BackupInfo {
+  path: string
+  name: string
+  size: number
+  created_at: string | null
+  automatic: boolean
+}
+
+interface AppBackups {
+  settings: BackupSettings
+  folder: string | null
+  backups: BackupInfo[]
+}
+
+interface BackupSummary {
+  path: string
+  created_at: string
+  app_version: string
+  files: number
+  size: number
+  skipped: number
+}
+
+type WebhookEvent = "application.submitted" | "application.status_changed" | "run.finished"
+
+const WEBHOOK_EVENT_LABELS: Record<WebhookEvent, string> = {
+  "application.submitted": "Submitted",
+  "application.status_changed": "Status changes",
+  "run.finished": "Runs finished",
+}
+
+const DELIVERY_COLORS: Record<WebhookDelivery["state"], string> = {
+  pending: "text-amber-400",
+  delivered: "text-[#34C759]",
+  failed: "text-[#FF3B30]",
+}
+
+const BROWSER_LABELS: Record<DriverStatus["browser"], string> = {
+  chrome: "Google Chrome",
+  edge: "Microsoft Edge",
+}
+
+function describeTiming(timing: ScheduleTiming) {
+  if (timing.kind === "cron") return `cron ${timing.expression}`
+  const days = timing.weekdays_only ? "weekdays" : "every day"
+  return `Every ${timing.hours}h, ${timing.from_hour}:00–${timing.to_hour}:00, ${days}`
+}
+
+type ResetScope = "config" | "cache" | "database" | "credentials"
+
+const RESET_SCOPES: { id: ResetScope; label: string }[] = [
+  { id: "config", label: "Desktop settings" },
+  { id: "cache", label: "Cache and data packs" },
+  { id: "database", label: "Jobs, applications and CVs" },
+  { id: "credentials", label: "Saved passwords" },
+]
+
 const CV_PROFILES = ["cashier", "stocker", "logistics", "frontend_dev", "fullstack_dev"]
 
 function SectionHeader({ icon, title }: { icon: React.ReactNode; title: string }) {
@@ -235,6 +623,216 @@ function AddSourceForm({
   )
 }
 
+function AddScheduleForm({
+  sites,
+  onClose,
+  onAdd,
+}: {
+  sites: string[]
+  onClose: () => void
+  onAdd: (schedule: ScheduleInput) => Promise<void>
+}) {
+  const [form, setForm] = useState({
+    name: "",
+    kind: "every" as ScheduleTiming["kind"],
+    expression: "0 9 * * 1-5",
+    hours: "2",
+    from_hour: "9",
+    to_hour: "17",
+    weekdays_only: true,
+    site: "",
+    catch_up: true,
+  })
+  const [preview, setPreview] = useState<string[]>([])
+  const [previewError, setPreviewError] = useState<string | null>(null)
+  const [saving, setSaving] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const timing: ScheduleTiming =
+    form.kind === "cron"
+      ? { kind: "cron", expression: form.expression }
+      : {
+          kind: "every",
+          hours: Number(form.hours),
+          from_hour: Number(form.from_hour),
+          to_hour: Number(form.to_hour),
+          weekdays_only: form.weekdays_only,
+        }
+  const timingKey = JSON.stringify(timing)
+
+  useEffect(() => {
+    invoke<string[]>("preview_schedule", { timing: JSON.parse(timingKey) })
+      .then(runs => {
+        setPreview(runs)
+        setPreviewError(null)
+      })
+      .catch(e => {
+        setPreview([])
+        setPreviewError(String(e))
+      })
+  }, [timingKey])
+
+  const handleSubmit = async () => {
+    if (!form.name) {
+      setError("A name is required")
+      return
+    }
+    setSaving(true)
+    try {
+      await onAdd({
+        name: form.name,
+        timing,
+        target: form.site ? { kind: "scraper", site: form.site } : { kind: "all_scrapers" },
+        enabled: true,
+        catch_up: form.catch_up,
+      })
+      onClose()
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  return (
+    <motion.div
+      initial={{ opacity: 0 }}
+      animate={{ opacity: 1 }}
+      exit={{ opacity: 0 }}
+      className="fixed inset-0 bg-black/60 backdrop-blur-sm z-50 flex items-center justify-center p-4"
+      onClick={onClose}
+    >
+      <motion.div
+        initial={{ scale: 0.95, y: 10 }}
+        animate={{ scale: 1, y: 0 }}
+        exit={{ scale: 0.95, y: 10 }}
+        transition={{ type: "spring", stiffness: 300, damping: 25 }}
+        className="bg-[#2C2C2E] border border-white/10 rounded-2xl p-5 w-full max-w-md"
+        onClick={e => e.stopPropagation()}
+      >
+        <h3 className="text-base font-semibold text-white mb-4">Add Schedule</h3>
+
+        <div className="space-y-3">
+          <SettingInput
+            label="Name"
+            value={form.name}
+            onChange={v => setForm(f => ({ ...f, name: v }))}
+            placeholder="Working hours"
+          />
+
+          {/* Timing */}
+          <div className="space-y-1.5">
+            <label className="text-xs text-[#8E8E93] font-medium">Runs</label>
+            <div className="relative">
+              <select
+                value={form.kind}
+                onChange={e => setForm(f => ({ ...f, kind: e.target.value as ScheduleTiming["kind"] }))}
+                className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-2 pr-7 text-sm text-white outline-none"
+              >
+                <option value="every" className="bg-[#2C2C2E]">Every few hours</option>
+                <option value="cron" className="bg-[#2C2C2E]">Cron expression</option>
+              </select>
+              <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+            </div>
+          </div>
+
+          {form.kind === "cron" ? (
+            <SettingInput
+              label="Expression (minute hour day month weekday)"
+              value={form.expression}
+              onChange={v => setForm(f => ({ ...f, expression: v }))}
+              placeholder="0 9 * * 1-5"
+            />
+          ) : (
+            <>
+              <div className="grid grid-cols-3 gap-2">
+                <SettingInput
+                  label="Every (hours)"
+                  value={form.hours}
+                  onChange={v => setForm(f => ({ ...f, hours: v }))}
+                  type="number"
+                />
+                <SettingInput
+                  label="From"
+                  value={form.from_hour}
+                  onChange={v => setForm(f => ({ ...f, from_hour: v }))}
+                  type="number"
+                />
+                <SettingInput
+                  label="To"
+                  value={form.to_hour}
+                  onChange={v => setForm(f => ({ ...f, to_hour: v }))}
+                  type="number"
+                />
+              </div>
+              <Toggle
+                label="Weekdays only"
+                checked={form.weekdays_only}
+                onChange={v => setForm(f => ({ ...f, weekdays_only: v }))}
+              />
+            </>
+          )}
+
+          {/* Target */}
+          <div className="space-y-1.5">
+            <label className="text-xs text-[#8E8E93] font-medium">Scrapers</label>
+            <div className="relative">
+              <select
+                value={form.site}
+                onChange={e => setForm(f => ({ ...f, site: e.target.value }))}
+                className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-2 pr-7 text-sm text-white outline-none"
+              >
+                <option value="" className="bg-[#2C2C2E]">All scrapers</option>
+                {sites.map(site => (
+                  <option key={site} value={site} className="bg-[#2C2C2E]">
+                    {site}
+                  </option>
+                ))}
+              </select>
+              <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+            </div>
+          </div>
+
+          <Toggle
+            label="Catch up on missed runs"
+            checked={form.catch_up}
+            onChange={v => setForm(f => ({ ...f, catch_up: v }))}
+          />
+          <p className="text-xs text-[#8E8E93]">
+            Runs missed while the computer slept or currobot was closed are made up with one run.
+          </p>
+
+          {previewError ? (
+            <p className="text-xs text-[#FF9500]">{previewError}</p>
+          ) : (
+            preview.length > 0 && (
+              <div className="text-xs text-[#8E8E93] space-y-0.5">
+                <p>Next runs:</p>
+                {preview.map(at => (
+                  <p key={at} className="text-white">{new Date(at).toLocaleString()}</p>
+                ))}
+              </div>
+            )
+          )}
+        </div>
+
+        {error && (
+          <p className="text-xs text-[#FF3B30] mt-3">{error}</p>
+        )}
+
+        <div className="flex gap-2 mt-4">
+          <Button variant="outline" className="flex-1" onClick={onClose}>
+            Cancel
+          </Button>
+          <Button className="flex-1" loading={saving} onClick={handleSubmit}>
+            Add Schedule
+          </Button>
+        </div>
+      </motion.div>
+    </motion.div>
+  )
+}
+
 export default function SettingsPage() {
   const [settings, setSettings] = useState<Record<string, string>>({})
   const [sources, setSources] = useState<CompanySource[]>([])
@@ -242,74 +840,1022 @@ export default function SettingsPage() {
   const [saved, setSaved] = useState(false)
   const [loading, setLoading] = useState(true)
   const [showAddSource, setShowAddSource] = useState(false)
+  const [schedules, setSchedules] = useState<ScheduleInfo[]>([])
+  const [scraperSites, setScraperSites] = useState<string[]>([])
+  const [showAddSchedule, setShowAddSchedule] = useState(false)
+  const [drivers, setDrivers] = useState<DriverStatus[]>([])
+  const [checkingDrivers, setCheckingDrivers] = useState(false)
+  const [browserProfiles, setBrowserProfiles] = useState<BrowserProfileStatus[]>([])
+  const [newProfile, setNewProfile] = useState<{ site: string; account: string } | null>(null)
+  const [proxyPool, setProxyPool] = useState<PoolProxyStatus[]>([])
+  const [newPoolProxy, setNewPoolProxy] = useState<NewPoolProxy | null>(null)
+  const [checkingPool, setCheckingPool] = useState(false)
+  const [mailWatch, setMailWatch] = useState<MailWatch | null>(null)
+  const [mailForm, setMailForm] = useState<(MailSettings & { password: string }) | null>(null)
+  const [checkingMail, setCheckingMail] = useState(false)
+  const [oauth, setOAuth] = useState<OAuthStatus[]>([])
+  const [oauthForm, setOAuthForm] = useState<{
+    provider: OAuthStatus["provider"]
+    client_id: string
+    client_secret: string
+    callback_port: string
+  } | null>(null)
+  const [webhooks, setWebhooks] = useState<WebhookStatus[]>([])
+  const [deliveries, setDeliveries] = useState<WebhookDelivery[]>([])
+  const [webhookForm, setWebhookForm] = useState<{
+    id: string | null
+    url: string
+    events: WebhookEvent[]
+    enabled: boolean
+    secret: string
+    has_secret: boolean
+  } | null>(null)
   const [testingConnection, setTestingConnection] = useState(false)
   const [connectionResult, setConnectionResult] = useState<string | null>(null)
   const [backupStatus, setBackupStatus] = useState<"idle" | "running" | "done" | "coming_soon">("idle")
+  const [quota, setQuota] = useState<QuotaStatus | null>(null)
+  const [evidenceForm, setEvidenceForm] = useState<{ retention_days: string; max_mb: string } | null>(null)
+  const [retentionForm, setRetentionForm] = useState<{
+    enabled: boolean
+    applications_months: string
+    statuses: string[]
+    screenshots_days: string
+    last_run_at: string | null
+  } | null>(null)
+  const [retentionPreview, setRetentionPreview] = useState<RetentionReport | null>(null)
+  const [pruning, setPruning] = useState(false)
+  const [workingHours, setWorkingHours] = useState<WorkingHours | null>(null)
+  const [hoursForm, setHoursForm] = useState<{ from_hour: string; to_hour: string; days: number[] } | null>(null)
+  const [quotaForm, setQuotaForm] = useState<{ daily_per_board: string; min_delay_minutes: string; boards: string } | null>(null)
+  const [appBackups, setAppBackups] = useState<AppBackups | null>(null)
+  const [backupForm, setBackupForm] = useState<{ interval_hours: string; keep: string; folder: string } | null>(null)
+  const [backingUp, setBackingUp] = useState(false)
+  const [exportingData, setExportingData] = useState(false)
+  const [exportLogs, setExportLogs] = useState(false)
+  const [restoreCandidate, setRestoreCandidate] = useState<BackupSummary | null>(null)
+  const [restoring, setRestoring] = useState(false)
   const [isTauriApp, setIsTauriApp] = useState(false)
   const [autolaunchOn, setAutolaunchOn] = useState(false)
+  const [tray, setTray] = useState<{ available: boolean; background_without_tray: boolean } | null>(null)
+  const [keepAwake, setKeepAwake] = useState(true)
+  const [encryptHistory, setEncryptHistory] = useState(false)
+  const [encryptingHistory, setEncryptingHistory] = useState(false)
+  const [dataDir, setDataDir] = useState<DataDirInfo | null>(null)
+  const [dataMove, setDataMove] = useState({ path: "", deleteOld: true })
+  const [dataMoveProgress, setDataMoveProgress] = useState<DataMoveProgress | null>(null)
+  const [movingData, setMovingData] = useState(false)
+  const [updateStatus, setUpdateStatus] = useState<UpdateStatus>({ state: "idle" })
+  const [previousVersion, setPreviousVersion] = useState<string | null>(null)
+  const [rollingBack, setRollingBack] = useState(false)
+  const [clipWatch, setClipWatch] = useState<{ enabled: boolean; domains: string } | null>(null)
+  const [extension, setExtension] = useState<BrowserExtension | null>(null)
+  const [extensionIds, setExtensionIds] = useState("")
+  const [loginItem, setLoginItem] = useState<LoginItemInfo | null>(null)
+  const [menuBar, setMenuBar] = useState<{ supported: boolean; menu_bar_only: boolean } | null>(null)
+  const [display, setDisplay] = useState<DisplayCompat | null>(null)
+  // What this run started with; the mode only changes on restart
+  const [startedCompat, setStartedCompat] = useState(false)
+  const [resetScopes, setResetScopes] = useState<ResetScope[]>([])
+  const [language, setLanguage] = useState<LanguageInfo | null>(null)
+  const [sync, setSync] = useState<SyncInfo | null>(null)
+  const [syncForm, setSyncForm] = useState({ folder: "", passphrase: "" })
+  const [syncBusy, setSyncBusy] = useState(false)
+  const [resetting, setResetting] = useState(false)
+  const [appLock, setAppLock] = useState<AppLockInfo | null>(null)
+  const [sessions, setSessions] = useState<SessionInfo[]>([])
+  const [redaction, setRedaction] = useState<{ patterns: string; names: string } | null>(null)
+  const [redactionPreview, setRedactionPreview] = useState({ text: "", result: "" })
+  const [logUsage, setLogUsage] = useState<LogUsage | null>(null)
+  const [crashReports, setCrashReports] = useState<CrashReport[]>([])
+  const [telemetry, setTelemetry] = useState<TelemetryStatus | null>(null)
+  const [showTelemetryPreview, setShowTelemetryPreview] = useState(false)
+  const [errorReporting, setErrorReporting] = useState<ErrorReportingStatus | null>(null)
+  const [sentryDsn, setSentryDsn] = useState("")
+  const [openCrash, setOpenCrash] = useState<string | null>(null)
+  const [logLimits, setLogLimits] = useState({ budget_mb: "", max_age_days: "" })
 
   useEffect(() => {
     const tauri = "__TAURI_INTERNALS__" in window
     setIsTauriApp(tauri)
     if (tauri) {
       invoke<boolean>("get_autolaunch_enabled").then(setAutolaunchOn).catch(() => {})
+      invoke<{ available: boolean; background_without_tray: boolean }>("get_tray_status").then(setTray).catch(() => {})
+      invoke<{ supported: boolean; menu_bar_only: boolean }>("get_menu_bar_status").then(setMenuBar).catch(() => {})
+      invoke<boolean>("get_keep_awake").then(setKeepAwake).catch(() => {})
+      invoke<boolean>("get_history_encryption").then(setEncryptHistory).catch(() => {})
+      invoke<DataDirInfo>("get_data_dir").then(setDataDir).catch(() => {})
+      invoke<BrowserExtension>("get_browser_extension")
+        .then(x => {
+          setExtension(x)
+          setExtensionIds(x.settings.chrome_extension_ids.join("\n"))
+        })
+        .catch(() => {})
+      invoke<ClipboardWatch>("get_clipboard_watch")
+        .then(c => setClipWatch({ enabled: c.watch_job_urls, domains: c.job_domains.join("\n") }))
+        .catch(() => {})
+      invoke<DisplayCompat | null>("get_display_compat")
+        .then(d => {
+          setDisplay(d)
+          setStartedCompat(d?.compat_mode ?? false)
+        })
+        .catch(() => {})
+      invoke<LoginItemInfo>("get_login_item").then(setLoginItem).catch(() => {})
+      invoke<LanguageInfo>("get_language").then(setLanguage).catch(() => {})
+      invoke<SyncInfo>("get_sync_status").then(setSync).catch(() => {})
+      invoke<AppLockInfo>("get_app_lock").then(setAppLock).catch(() => {})
+      invoke<SessionInfo[]>("list_sessions").then(setSessions).catch(() => {})
+      invoke<CrashReport[]>("list_crash_reports").then(setCrashReports).catch(() => {})
+      invoke<TelemetryStatus>("get_telemetry").then(setTelemetry).catch(() => {})
+      invoke<ErrorReportingStatus>("get_error_reporting")
+        .then(r => {
+          setErrorReporting(r)
+          setSentryDsn(r.dsn)
+        })
+        .catch(() => {})
+      invoke<LogUsage>("get_log_usage")
+        .then(u => {
+          setLogUsage(u)
+          setLogLimits({ budget_mb: String(u.budget_bytes / 1048576), max_age_days: String(u.max_age_days) })
+        })
+        .catch(() => {})
+      invoke<RedactionSettings>("get_redaction")
+        .then(r => setRedaction({ patterns: r.patterns.join("\n"), names: r.names.join("\n") }))
+        .catch(() => {})
     }
   }, [])
 
-  const handleAutolaunchToggle = async (enabled: boolean) => {
-    setAutolaunchOn(enabled)
-    try {
-      await invoke("set_autolaunch", { enabled })
-    } catch {
-      setAutolaunchOn(!enabled) // revert on error
+  // The scheduler updates last run and next run as it goes
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => invoke<ScheduleInfo[]>("list_schedules").then(setSchedules).catch(() => {})
+    load()
+    api.getScraperStatus().then(r => setScraperSites(r.scrapers.map(s => s.site))).catch(() => {})
+    const unlisten = listen("schedules-changed", load)
+    return () => {
+      unlisten.then(f => f())
     }
-  }
+  }, [])
 
-  // Derived settings with defaults
-  const ollamaHost = settings.ollama_host ?? "http://localhost:11434"
-  const ollamaModel = settings.ollama_model ?? "llama3"
-  const jobsRetentionDays = settings.jobs_retention_days ?? "30"
-  const appsRetentionDays = settings.apps_retention_days ?? "90"
-  const soundEnabled = settings.sound_enabled !== "false"
-  const lastBackup = settings.last_backup_at ?? null
+  // Copy, verify, switch and clean up, reported by the shell (datadir.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const unlisten = listen<DataMoveProgress>("data-move-progress", e => setDataMoveProgress(e.payload))
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
 
-  const isRemoteOllama =
-    !ollamaHost.includes("localhost") && !ollamaHost.includes("127.0.0.1")
+  // Checked for and downloaded in the background; the latest status is kept
+  // by the shell for a page opened mid-download
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    invoke<UpdateStatus>("get_update_status").then(setUpdateStatus).catch(() => {})
+    invoke<string | null>("get_previous_version").then(setPreviousVersion).catch(() => {})
+    const unlisten = listen<UpdateStatus>("update-status", e => setUpdateStatus(e.payload))
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
 
-  const fetchAll = useCallback(async () => {
+  // Installed by the shell in the background (drivers.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => invoke<DriverStatus[]>("get_browser_drivers").then(setDrivers).catch(() => {})
+    load()
+    const unlisten = listen("drivers-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const handleCheckDrivers = async () => {
+    setCheckingDrivers(true)
     try {
-      const [s, c] = await Promise.allSettled([
-        api.getSettings(),
-        api.getCompanySources(),
-      ])
-      if (s.status === "fulfilled") setSettings(s.value)
-      if (c.status === "fulfilled") setSources(c.value.items)
+      setDrivers(await invoke<DriverStatus[]>("update_browser_drivers"))
+    } catch (e) {
+      toast.error(String(e))
     } finally {
-      setLoading(false)
+      setCheckingDrivers(false)
     }
-  }, [])
+  }
 
-  useEffect(() => { fetchAll() }, [fetchAll])
+  // Kept by the shell per site account (browserprofiles.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () =>
+      invoke<BrowserProfileStatus[]>("list_browser_profiles").then(setBrowserProfiles).catch(() => {})
+    load()
+    const unlisten = listen("browser-profiles-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
 
-  const updateSetting = (key: string, value: string) => {
-    setSettings(s => ({ ...s, [key]: value }))
+  const handleCreateBrowserProfile = async () => {
+    if (!newProfile?.site) return
+    try {
+      await invoke("create_browser_profile", newProfile)
+      setNewProfile(null)
+    } catch (e) {
+      toast.error(String(e))
+    }
   }
 
-  const handleSave = async () => {
-    setSaving(true)
+  const handleBrowserProfile = async (
+    command: "set_active_browser_profile" | "reset_browser_profile" | "wipe_browser_profile",
+    profile: BrowserProfileStatus
+  ) => {
+    const name = `${profile.site}${profile.account ? ` (${profile.account})` : ""}`
+    if (command === "reset_browser_profile" && !confirm(`Sign out of ${name} and clear its cookies and storage?`)) return
+    if (command === "wipe_browser_profile" && !confirm(`Delete the ${name} browser profile?`)) return
     try {
-      await api.updateSettings(settings)
-      playSuccess()
-      setSaved(true)
-      setTimeout(() => setSaved(false), 2500)
-    } catch {
-      playError()
-    } finally {
-      setSaving(false)
+      await invoke(command, { id: profile.id })
+    } catch (e) {
+      toast.error(String(e))
     }
   }
 
-  const testConnection = async () => {
+  // Proxies the engine scrapes through, checked and rotated by the shell (proxypool.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => invoke<PoolProxyStatus[]>("list_proxy_pool").then(setProxyPool).catch(() => {})
+    load()
+    const unlisten = listen("proxy-pool-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const handleAddPoolProxy = async () => {
+    if (!newPoolProxy) return
+    try {
+      await invoke("add_pool_proxy", {
+        proxy: {
+          ...newPoolProxy,
+          port: Number(newPoolProxy.port),
+          username: newPoolProxy.username || null,
+          password: newPoolProxy.password || null,
+        },
+      })
+      setNewPoolProxy(null)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const handleCheckPool = async () => {
+    setCheckingPool(true)
+    try {
+      setProxyPool(await invoke<PoolProxyStatus[]>("check_proxy_pool"))
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setCheckingPool(false)
+    }
+  }
+
+  // Replies from employers, read from a mail folder by the shell (mailwatch.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => invoke<MailWatch>("get_mail_watch").then(setMailWatch).catch(() => {})
+    load()
+    const unlisten = listen("mail-watch-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const saveMailWatch = async (mail: MailSettings, password: string | null) => {
+    try {
+      await invoke("set_mail_watch", { mail, password })
+      setMailForm(null)
+      setMailWatch(await invoke<MailWatch>("get_mail_watch"))
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const handleCheckMail = async () => {
+    setCheckingMail(true)
+    try {
+      const found = await invoke<number>("check_mail_now")
+      toast.info(found === 1 ? "1 new reply" : `${found} new replies`)
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setCheckingMail(false)
+    }
+  }
+
+  // Sign-ins that run in the system browser and come back to the shell (oauth.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => invoke<OAuthStatus[]>("get_oauth_status").then(setOAuth).catch(() => {})
+    load()
+    const unlisten = listen("oauth-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const handleSaveOAuthClient = async () => {
+    if (!oauthForm) return
+    try {
+      await invoke("set_oauth_client", {
+        provider: oauthForm.provider,
+        clientId: oauthForm.client_id,
+        clientSecret: oauthForm.client_secret || null,
+        callbackPort: Number(oauthForm.callback_port) || 0,
+      })
+      setOAuthForm(null)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const handleOAuthSignIn = async (provider: OAuthStatus["provider"]) => {
+    try {
+      await invoke("sign_in_oauth", { provider })
+      toast.success(`Signed in to ${OAUTH_LABELS[provider]}`)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  // Proof of submission kept by the shell (evidence.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    invoke<{ retention_days: number; max_mb: number }>("get_evidence_settings")
+      .then(e => setEvidenceForm({ retention_days: String(e.retention_days), max_mb: String(e.max_mb) }))
+      .catch(() => {})
+  }, [])
+
+  const saveEvidenceSettings = async () => {
+    if (!evidenceForm) return
+    try {
+      await invoke("set_evidence_settings", {
+        retentionDays: Number(evidenceForm.retention_days) || 0,
+        maxMb: Number(evidenceForm.max_mb) || 0,
+      })
+      toast.success("Evidence retention saved")
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  // Removing old applications and screenshots on a schedule (retention.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    invoke<{
+      enabled: boolean
+      applications_months: number
+      statuses: string[]
+      screenshots_days: number
+      last_run_at: string | null
+    }>("get_retention_settings")
+      .then(r => setRetentionForm({
+        enabled: r.enabled,
+        applications_months: String(r.applications_months),
+        statuses: r.statuses,
+        screenshots_days: String(r.screenshots_days),
+        last_run_at: r.last_run_at,
+      }))
+      .catch(() => {})
+  }, [])
+
+  const saveRetentionSettings = async () => {
+    if (!retentionForm) return
+    try {
+      await invoke("set_retention_settings", {
+        enabled: retentionForm.enabled,
+        applicationsMonths: Number(retentionForm.applications_months) || 0,
+        statuses: retentionForm.statuses,
+        screenshotsDays: Number(retentionForm.screenshots_days) || 0,
+      })
+      setRetentionPreview(null)
+      toast.success("Data retention saved")
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const previewRetention = async () => {
+    setPruning(true)
+    try {
+      setRetentionPreview(await invoke<RetentionReport>("preview_retention"))
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setPruning(false)
+    }
+  }
+
+  const runRetention = async () => {
+    if (!confirm("Remove these applications and screenshots for good?")) return
+    setPruning(true)
+    try {
+      const report = await invoke<RetentionReport>("run_retention_now")
+      setRetentionPreview(null)
+      setRetentionForm(f => f && { ...f, last_run_at: new Date().toISOString() })
+      toast.success(`Removed ${report.applications.length} applications and ${report.screenshots.length} screenshots`)
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setPruning(false)
+    }
+  }
+
+  // Daily caps and pacing for submissions (quotas.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => {
+      invoke<QuotaStatus>("get_quota")
+        .then(q => {
+          setQuota(q)
+          setQuotaForm(f => f ?? {
+            daily_per_board: String(q.settings.daily_per_board),
+            min_delay_minutes: String(q.settings.min_delay_minutes),
+            boards: Object.entries(q.settings.boards).map(([site, n]) => `${site}=${n}`).join(", "),
+          })
+        })
+        .catch(() => {})
+    }
+    load()
+    const unlisten = listen("quota-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const saveQuota = async (enabled: boolean) => {
+    if (!quotaForm) return
+    // "infojobs=5, linkedin=10"
+    const boards: Record<string, number> = {}
+    for (const part of quotaForm.boards.split(",")) {
+      const [site, n] = part.split("=").map(x => x.trim())
+      if (site && n) boards[site] = Number(n)
+    }
+    try {
+      await invoke("set_quota_settings", {
+        quota: {
+          enabled,
+          daily_per_board: Number(quotaForm.daily_per_board) || 0,
+          boards,
+          min_delay_minutes: Number(quotaForm.min_delay_minutes) || 0,
+        },
+      })
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  // Working hours for automation (workhours.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => {
+      invoke<WorkingHours>("get_working_hours")
+        .then(w => {
+          setWorkingHours(w)
+          setHoursForm(f => f ?? {
+            from_hour: String(w.settings.from_hour),
+            to_hour: String(w.settings.to_hour),
+            days: w.settings.days,
+          })
+        })
+        .catch(() => {})
+    }
+    load()
+    const unlisten = listen("working-hours-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const saveWorkingHours = async (enabled: boolean) => {
+    if (!hoursForm) return
+    try {
+      setWorkingHours(await invoke<WorkingHours>("set_working_hours", {
+        enabled,
+        fromHour: Number(hoursForm.from_hour) || 0,
+        toHour: Number(hoursForm.to_hour) || 0,
+        days: hoursForm.days,
+      }))
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const overrideWorkingHours = async (active: boolean) => {
+    try {
+      await invoke("override_working_hours", { active })
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  // Whole-app archives (backup.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => {
+      invoke<AppBackups>("get_backups")
+        .then(b => {
+          setAppBackups(b)
+          setBackupForm({
+            interval_hours: String(b.settings.interval_hours),
+            keep: String(b.settings.keep),
+            folder: b.settings.folder ?? "",
+          })
+        })
+        .catch(() => {})
+    }
+    load()
+    const unlisten = listen("backups-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const saveBackupSettings = async (enabled: boolean) => {
+    if (!backupForm) return
+    try {
+      await invoke("set_backup_settings", {
+        enabled,
+        intervalHours: Number(backupForm.interval_hours),
+        keep: Number(backupForm.keep),
+        folder: backupForm.folder || null,
+      })
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const handleCreateBackup = async () => {
+    setBackingUp(true)
+    try {
+      const b = await invoke<BackupInfo>("create_backup")
+      toast.success(`Backed up to ${b.name}`)
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setBackingUp(false)
+    }
+  }
+
+  // Everything about the user in a readable archive (dataexport.rs)
+  const handleExportData = async () => {
+    setExportingData(true)
+    try {
+      const done = await invoke<{ path: string; files: number; size: number; skipped: number } | null>(
+        "export_personal_data",
+        { includeLogs: exportLogs },
+      )
+      if (done) {
+        toast.success(`Saved ${done.files} files (${(done.size / 1e6).toFixed(1)} MB) to ${done.path}`)
+        if (done.skipped > 0) toast.error(`${done.skipped} files couldn't be read; see the archive's manifest`)
+      }
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setExportingData(false)
+    }
+  }
+
+  // Shows what's in the backup before anything is replaced; None picks a file
+  const handleInspectBackup = async (path: string | null) => {
+    try {
+      const summary = await invoke<BackupSummary | null>("inspect_backup", { path })
+      if (summary) setRestoreCandidate(summary)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const handleRestoreBackup = async () => {
+    if (!restoreCandidate) return
+    setRestoring(true)
+    try {
+      // The app relaunches once it's done
+      await invoke("restore_backup", { path: restoreCandidate.path })
+    } catch (e) {
+      toast.error(String(e))
+      setRestoring(false)
+    }
+  }
+
+  // Signed POSTs the shell sends on engine events (webhooks.rs)
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => {
+      invoke<WebhookStatus[]>("list_webhooks").then(setWebhooks).catch(() => {})
+      invoke<WebhookDelivery[]>("list_webhook_deliveries", { webhookId: null }).then(setDeliveries).catch(() => {})
+    }
+    load()
+    const unlisten = listen("webhooks-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const handleSaveWebhook = async () => {
+    if (!webhookForm) return
+    try {
+      await invoke("save_webhook", {
+        id: webhookForm.id,
+        url: webhookForm.url,
+        events: webhookForm.events,
+        enabled: webhookForm.enabled,
+        // Left blank keeps the saved one
+        secret: webhookForm.secret || (webhookForm.has_secret ? null : ""),
+      })
+      setWebhookForm(null)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const handleTestWebhook = async (id: string) => {
+    try {
+      const delivery = await invoke<WebhookDelivery>("test_webhook", { id })
+      if (delivery.state === "delivered") toast.success(`Delivered (HTTP ${delivery.response_status})`)
+      else toast.error(delivery.last_error ?? "Not delivered")
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const handleAutolaunchToggle = async (enabled: boolean) => {
+    setAutolaunchOn(enabled)
+    try {
+      await invoke("set_autolaunch", { enabled })
+    } catch {
+      setAutolaunchOn(!enabled) // revert on error
+    }
+    invoke<LoginItemInfo>("get_login_item").then(setLoginItem).catch(() => {})
+  }
+
+  const handleLoginItemMethod = async (method: LoginItemMethod) => {
+    try {
+      await invoke("set_login_item_method", { method })
+    } catch (e) {
+      toast.error(String(e))
+    }
+    invoke<LoginItemInfo>("get_login_item")
+      .then(info => {
+        setLoginItem(info)
+        setAutolaunchOn(info.enabled)
+      })
+      .catch(() => {})
+  }
+
+  const handleBackgroundToggle = async (enabled: boolean) => {
+    if (!tray) return
+    setTray({ ...tray, background_without_tray: enabled })
+    try {
+      await invoke("set_background_without_tray", { enabled })
+    } catch {
+      setTray({ ...tray, background_without_tray: !enabled })
+    }
+  }
+
+  const handleKeepAwakeToggle = async (enabled: boolean) => {
+    setKeepAwake(enabled)
+    try {
+      await invoke("set_keep_awake", { enabled })
+    } catch {
+      setKeepAwake(!enabled)
+    }
+  }
+
+  const handleEncryptHistoryToggle = async (enabled: boolean) => {
+    if (encryptingHistory) return
+    setEncryptingHistory(true)
+    try {
+      await invoke("set_history_encryption", { enabled })
+      setEncryptHistory(enabled)
+    } catch (e) {
+      alert(`The history could not be ${enabled ? "encrypted" : "decrypted"}: ${e}`)
+    } finally {
+      setEncryptingHistory(false)
+    }
+  }
+
+  const handleMoveData = async (path: string) => {
+    if (!path.trim()) return
+    setMovingData(true)
+    setDataMoveProgress(null)
+    try {
+      await invoke("move_data_dir", { path: path.trim(), deleteOld: dataMove.deleteOld })
+      setDataMove(m => ({ ...m, path: "" }))
+    } catch (e) {
+      alert(`${e}`)
+    } finally {
+      setMovingData(false)
+      invoke<DataDirInfo>("get_data_dir").then(setDataDir).catch(() => {})
+    }
+  }
+
+  const dataMoveLine = (p: DataMoveProgress) => {
+    switch (p.stage) {
+      case "copying":
+        return `Copying ${p.done} of ${p.total} files…`
+      case "verifying":
+        return `Checking ${p.done} of ${p.total} files…`
+      case "switching":
+        return "Switching over…"
+      case "cleaning":
+        return "Deleting the old copy…"
+      case "done":
+        return `Moved to ${p.path}`
+    }
+  }
+
+  // Progress arrives as update-status events
+  const handleCheckUpdate = async () => {
+    try {
+      await invoke("check_for_update")
+    } catch (e) {
+      setUpdateStatus({ state: "failed", error: String(e) })
+    }
+  }
+
+  const handleInstallUpdate = async () => {
+    try {
+      await invoke("install_update") // restarts the app on success
+    } catch (e) {
+      setUpdateStatus({ state: "failed", error: String(e) })
+    }
+  }
+
+  // Reinstalls the release that ran before the last update and restarts
+  const handleRollback = async () => {
+    if (!previousVersion) return
+    if (!confirm(`Go back to version ${previousVersion}? The app restarts to install it.`)) return
+    setRollingBack(true)
+    try {
+      await invoke("rollback_update")
+    } catch (e) {
+      setUpdateStatus({ state: "failed", error: String(e) })
+      setRollingBack(false)
+    }
+  }
+
+  const updateLine = (u: UpdateStatus) => {
+    switch (u.state) {
+      case "idle":
+        return "Checked in the background every few hours."
+      case "checking":
+        return "Checking for updates…"
+      case "up_to_date":
+        return "You have the latest version."
+      case "downloading":
+        return u.percent === null
+          ? `Downloading… ${(u.downloaded / 1048576).toFixed(1)} MB`
+          : `Downloading… ${u.percent}%`
+      case "verifying":
+        return "Checking the download's signature…"
+      case "ready":
+        return `Version ${u.version} is ready; it's installed when the app restarts.`
+      case "installing":
+        return "Installing…"
+      case "failed":
+        return `The update failed: ${u.error}`
+    }
+  }
+
+  // One site per line
+  const saveClipWatch = async (next: { enabled: boolean; domains: string }) => {
+    const previous = clipWatch
+    setClipWatch(next)
+    try {
+      const saved = await invoke<ClipboardWatch>("set_clipboard_watch", {
+        enabled: next.enabled,
+        domains: next.domains.split("\n").map(l => l.trim()).filter(Boolean),
+      })
+      setClipWatch({ enabled: saved.watch_job_urls, domains: saved.job_domains.join("\n") })
+    } catch (e) {
+      setClipWatch(previous)
+      toast.error(String(e))
+    }
+  }
+
+  // One Chrome extension ID per line
+  const saveExtension = async (enabled: boolean) => {
+    try {
+      const saved = await invoke<BrowserExtension>("set_browser_extension", {
+        enabled,
+        chromeExtensionIds: extensionIds.split("\n").map(l => l.trim()).filter(Boolean),
+      })
+      setExtension(saved)
+      setExtensionIds(saved.settings.chrome_extension_ids.join("\n"))
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const handleWaylandCompatToggle = async (enabled: boolean) => {
+    if (!display) return
+    setDisplay({ ...display, compat_mode: enabled })
+    try {
+      await invoke("set_wayland_compat", { enabled })
+    } catch {
+      setDisplay({ ...display, compat_mode: !enabled })
+    }
+  }
+
+  const handleMenuBarToggle = async (enabled: boolean) => {
+    if (!menuBar) return
+    setMenuBar({ ...menuBar, menu_bar_only: enabled })
+    try {
+      await invoke("set_menu_bar_only", { enabled })
+    } catch {
+      setMenuBar({ ...menuBar, menu_bar_only: !enabled })
+    }
+  }
+
+  // "" = follow the system language
+  const handleLanguageChange = async (code: string) => {
+    try {
+      await invoke("set_language", { language: code || null })
+      setLanguage(await invoke<LanguageInfo>("get_language"))
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const runSync = async (command: string, args?: Record<string, unknown>) => {
+    setSyncBusy(true)
+    try {
+      await invoke(command, args)
+      setSyncForm(f => ({ ...f, passphrase: "" }))
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setSync(await invoke<SyncInfo>("get_sync_status").catch(() => null))
+      setSyncBusy(false)
+    }
+  }
+
+  // Asks for Touch ID / Windows Hello when switching the lock on or off
+  const updateAppLock = async (lock: { enabled: boolean; idle_minutes: number }) => {
+    try {
+      await invoke("set_app_lock", { lock })
+    } catch (e) {
+      toast.error(String(e))
+    }
+    setAppLock(await invoke<AppLockInfo>("get_app_lock").catch(() => null))
+  }
+
+  // One pattern / name per line
+  const saveRedaction = async () => {
+    if (!redaction) return
+    const lines = (s: string) => s.split("\n").map(l => l.trim()).filter(Boolean)
+    try {
+      await invoke("set_redaction", {
+        redaction: { patterns: lines(redaction.patterns), names: lines(redaction.names) },
+      })
+      toast.success("Redaction rules saved")
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const previewRedaction = async (text: string) => {
+    const result = text ? await invoke<string>("preview_redaction", { text }).catch(() => "") : ""
+    setRedactionPreview({ text, result })
+  }
+
+  // Cleared from the clipboard by the shell after the returned delay
+  const copyCredential = async (credential: "infojobs_password" | "proxy_password") => {
+    try {
+      const seconds = await invoke<number>("copy_credential", { credential })
+      toast.success(`Copied. The clipboard is cleared in ${seconds} s.`)
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const forgetSession = async (site: string) => {
+    try {
+      await invoke("forget_session", { site })
+    } catch (e) {
+      toast.error(String(e))
+    }
+    setSessions(await invoke<SessionInfo[]>("list_sessions").catch(() => []))
+  }
+
+  const saveLogLimits = async () => {
+    try {
+      await invoke("set_log_retention", {
+        budgetMb: Number(logLimits.budget_mb),
+        maxAgeDays: Number(logLimits.max_age_days),
+      })
+      toast.success("Log limits saved")
+    } catch (e) {
+      toast.error(String(e))
+    }
+    setLogUsage(await invoke<LogUsage>("get_log_usage").catch(() => null))
+  }
+
+  const toggleTelemetry = async (enabled: boolean) => {
+    try {
+      await invoke("set_telemetry", { enabled })
+    } catch (e) {
+      toast.error(String(e))
+    }
+    setTelemetry(await invoke<TelemetryStatus>("get_telemetry").catch(() => null))
+  }
+
+  const saveErrorReporting = async (enabled: boolean) => {
+    try {
+      await invoke("set_error_reporting", { enabled, dsn: sentryDsn })
+    } catch (e) {
+      toast.error(String(e))
+    }
+    setErrorReporting(await invoke<ErrorReportingStatus>("get_error_reporting").catch(() => null))
+  }
+
+  const testErrorReporting = async () => {
+    try {
+      await invoke("test_error_reporting", { dsn: sentryDsn })
+      toast.success("Test event sent")
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  // Opens a prefilled GitHub issue; nothing is sent until it's submitted there
+  const sendCrashReport = (id: string) =>
+    invoke("send_crash_report", { id }).catch(e => toast.error(String(e)))
+
+  const deleteCrashReport = async (id: string) => {
+    try {
+      await invoke("delete_crash_report", { id })
+    } catch (e) {
+      toast.error(String(e))
+    }
+    setCrashReports(await invoke<CrashReport[]>("list_crash_reports").catch(() => []))
+  }
+
+  const toggleResetScope = (scope: ResetScope, on: boolean) => {
+    setResetScopes(prev => on ? [...prev, scope] : prev.filter(s => s !== scope))
+  }
+
+  const handleReset = async () => {
+    if (!confirm("This permanently deletes the selected data and restarts currobot. Continue?")) return
+    setResetting(true)
+    try {
+      await invoke("reset_app_data", { scopes: resetScopes }) // restarts the app on success
+    } catch (e) {
+      alert(`Reset failed: ${e}`)
+      setResetting(false)
+    }
+  }
+
+  // Derived settings with defaults
+  const ollamaHost = settings.ollama_host ?? "http://localhost:11434"
+  const ollamaModel = settings.ollama_model ?? "llama3"
+  const jobsRetentionDays = settings.jobs_retention_days ?? "30"
+  const appsRetentionDays = settings.apps_retention_days ?? "90"
+  const soundEnabled = settings.sound_enabled !== "false"
+  const lastBackup = settings.last_backup_at ?? null
+
+  const isRemoteOllama =
+    !ollamaHost.includes("localhost") && !ollamaHost.includes("127.0.0.1")
+
+  const fetchAll = useCallback(async () => {
+    try {
+      const [s, c] = await Promise.allSettled([
+        api.getSettings(),
+        api.getCompanySources(),
+      ])
+      if (s.status === "fulfilled") setSettings(s.value)
+      if (c.status === "fulfilled") setSources(c.value.items)
+    } finally {
+      setLoading(false)
+    }
+  }, [])
+
+  useEffect(() => { fetchAll() }, [fetchAll])
+
+  const updateSetting = (key: string, value: string) => {
+    setSettings(s => ({ ...s, [key]: value }))
+  }
+
+  const handleSave = async () => {
+    setSaving(true)
+    try {
+      await api.updateSettings(settings)
+      playSuccess()
+      setSaved(true)
+      setTimeout(() => setSaved(false), 2500)
+    } catch {
+      playError()
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  const testConnection = async () => {
     setTestingConnection(true)
     setConnectionResult(null)
     try {
@@ -325,7 +1871,7 @@ export default function SettingsPage() {
   const runBackup = async () => {
     setBackupStatus("running")
     try {
-      const res = await fetch(`${BASE}/api/backup`, { method: "POST" })
+      const res = await fetch(`${BASE}/api/backup`, { method: "POST", headers: authHeaders() })
       if (res.ok) {
         updateSetting("last_backup_at", new Date().toISOString())
         setBackupStatus("done")
@@ -341,8 +1887,35 @@ export default function SettingsPage() {
     }
   }
 
+  const handleAddSchedule = async (schedule: ScheduleInput) => {
+    await invoke("create_schedule", { schedule })
+  }
+
+  const toggleSchedule = async (s: ScheduleInfo, enabled: boolean) => {
+    const { name, timing, target, catch_up } = s
+    try {
+      await invoke("update_schedule", { id: s.id, schedule: { name, timing, target, catch_up, enabled } })
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const runScheduleNow = async (id: string) => {
+    try {
+      await invoke("run_schedule_now", { id })
+      toast.success("Run started")
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const deleteSchedule = (id: string) => {
+    invoke("delete_schedule", { id }).catch(e => toast.error(String(e)))
+  }
+
   const handleAddSource = async (data: Partial<CompanySource>) => {
     const added = await api.addCompanySource(data)
+    if (isQueued(added)) return
     setSources(s => [...s, added])
   }
 
@@ -403,45 +1976,868 @@ export default function SettingsPage() {
             placeholder="llama3"
           />
 
-          <div className="flex items-center gap-2">
+          <div className="flex items-center gap-2">
+            <Button
+              size="sm"
+              variant="outline"
+              loading={testingConnection}
+              onClick={testConnection}
+            >
+              Test Connection
+            </Button>
+            {connectionResult === "ok" && (
+              <span className="flex items-center gap-1 text-xs text-[#34C759]">
+                <CheckCircle2 className="h-3.5 w-3.5" />
+                Connected
+              </span>
+            )}
+            {connectionResult === "error" && (
+              <span className="flex items-center gap-1 text-xs text-[#FF3B30]">
+                <XCircle className="h-3.5 w-3.5" />
+                Failed to connect
+              </span>
+            )}
+          </div>
+        </div>
+      </Card>
+
+      {/* Scraping Section */}
+      <Card>
+        <SectionHeader icon={<Globe className="h-4 w-4" />} title="Scraping" />
+        <div className="space-y-3">
+          <p className="text-xs text-[#8E8E93]">
+            JobBot applies rate limiting between requests to avoid triggering anti-bot protections.
+            Aggressive scraping may result in IP bans on job platforms.
+          </p>
+          <p className="text-xs text-[#8E8E93]">
+            Default delay: <span className="text-white font-medium">2–6 seconds</span> between page
+            requests. Adjust in the backend configuration if needed.
+          </p>
+        </div>
+      </Card>
+
+      {/* Schedules */}
+      {isTauriApp && (
+        <Card>
+          <div className="flex items-center justify-between mb-3">
+            <SectionHeader icon={<CalendarClock className="h-4 w-4" />} title="Schedules" />
+            <Button size="sm" onClick={() => setShowAddSchedule(true)}>
+              <Plus className="h-3.5 w-3.5" />
+              Add Schedule
+            </Button>
+          </div>
+          <p className="text-xs text-[#8E8E93] mb-3">
+            Extra scraper runs at times you choose, alongside the engine's own schedule. Nothing runs
+            while automation is paused.
+          </p>
+          {schedules.length === 0 ? (
+            <p className="text-sm text-[#8E8E93] text-center py-4">No schedules yet.</p>
+          ) : (
+            <div className="space-y-2">
+              {schedules.map(s => (
+                <div key={s.id} className="flex items-center gap-3 bg-white/5 rounded-xl px-3 py-2.5">
+                  <div className="flex-1 min-w-0">
+                    <p className="text-sm text-white truncate">{s.name}</p>
+                    <p className="text-xs text-[#8E8E93] truncate">
+                      {describeTiming(s.timing)} · {s.target.kind === "scraper" ? s.target.site : "all scrapers"}
+                    </p>
+                    <p className="text-xs text-[#8E8E93]">
+                      {s.next_run ? `Next ${new Date(s.next_run).toLocaleString()}` : "Off"}
+                      {s.last_run && ` · last ${new Date(s.last_run).toLocaleString()}`}
+                    </p>
+                    {s.last_error && (
+                      <p className="text-xs text-[#FF9500] truncate">{s.last_error}</p>
+                    )}
+                  </div>
+                  <button
+                    onClick={() => runScheduleNow(s.id)}
+                    className="text-[#8E8E93] hover:text-white transition-colors"
+                    title="Run now"
+                  >
+                    <Play className="h-4 w-4" />
+                  </button>
+                  <button
+                    onClick={() => toggleSchedule(s, !s.enabled)}
+                    className={cn(
+                      "relative w-8 h-4 rounded-full transition-colors shrink-0",
+                      s.enabled ? "bg-[#007AFF]" : "bg-white/10"
+                    )}
+                  >
+                    <motion.span
+                      layout
+                      className="absolute top-0.5 left-0.5 w-3 h-3 bg-white rounded-full shadow-sm"
+                      animate={{ x: s.enabled ? 16 : 0 }}
+                      transition={{ type: "spring", stiffness: 400, damping: 30 }}
+                    />
+                  </button>
+                  <button
+                    onClick={() => deleteSchedule(s.id)}
+                    className="text-[#8E8E93] hover:text-[#FF3B30] transition-colors"
+                  >
+                    <Trash2 className="h-4 w-4" />
+                  </button>
+                </div>
+              ))}
+            </div>
+          )}
+        </Card>
+      )}
+
+      {/* Quotas */}
+      {isTauriApp && quota && quotaForm && (
+        <Card>
+          <SectionHeader icon={<Gauge className="h-4 w-4" />} title="Quotas" />
+          <div className="space-y-3">
+            <p className="text-xs text-[#8E8E93]">
+              Caps on how many applications go out a day on each board, and how far apart. Authorizing
+              waits until they allow it, and scheduled runs of a board that's used up are skipped.
+            </p>
+            <Toggle label="Enforce quotas" checked={quota.settings.enabled} onChange={saveQuota} />
+            <div className="grid grid-cols-3 gap-2">
+              <SettingInput
+                label="Per board a day (0 = no cap)"
+                type="number"
+                value={quotaForm.daily_per_board}
+                onChange={v => setQuotaForm(f => f && { ...f, daily_per_board: v })}
+              />
+              <SettingInput
+                label="Minutes between"
+                type="number"
+                value={quotaForm.min_delay_minutes}
+                onChange={v => setQuotaForm(f => f && { ...f, min_delay_minutes: v })}
+              />
+              <SettingInput
+                label="Board caps"
+                value={quotaForm.boards}
+                onChange={v => setQuotaForm(f => f && { ...f, boards: v })}
+                placeholder="infojobs=5, linkedin=10"
+              />
+            </div>
+            <div className="flex items-center justify-between gap-2">
+              <p className="text-xs text-[#8E8E93]">
+                {quota.next_allowed_at
+                  ? `Next application at ${new Date(quota.next_allowed_at).toLocaleTimeString()}`
+                  : `Resets ${new Date(quota.resets_at).toLocaleString()}`}
+              </p>
+              <Button size="sm" variant="outline" onClick={() => saveQuota(quota.settings.enabled)}>
+                Save
+              </Button>
+            </div>
+            {quota.boards.length > 0 && (
+              <div className="border-t border-white/5 pt-2 space-y-1">
+                {quota.boards.map(b => (
+                  <div key={b.site} className="flex items-center justify-between text-xs">
+                    <span className="text-white">{b.site || "unknown board"}</span>
+                    <span className={cn(b.remaining === 0 ? "text-[#FF9500]" : "text-[#8E8E93]")}>
+                      {b.limit === null ? `${b.used} today` : `${b.used} of ${b.limit} today`}
+                    </span>
+                  </div>
+                ))}
+              </div>
+            )}
+          </div>
+        </Card>
+      )}
+
+      {/* Working Hours */}
+      {isTauriApp && workingHours && hoursForm && (
+        <Card>
+          <SectionHeader icon={<Clock className="h-4 w-4" />} title="Working Hours" />
+          <div className="space-y-3">
+            <p className="text-xs text-[#8E8E93]">
+              Automation only runs in these hours, in local time. Outside them the engine is paused and
+              scheduled runs are skipped; it resumes when they start again.
+            </p>
+            <Toggle label="Keep to working hours" checked={workingHours.settings.enabled} onChange={saveWorkingHours} />
+            <div className="grid grid-cols-2 gap-2">
+              <SettingInput
+                label="From (hour)"
+                type="number"
+                value={hoursForm.from_hour}
+                onChange={v => setHoursForm(f => f && { ...f, from_hour: v })}
+              />
+              <SettingInput
+                label="Until (hour, 24 = midnight)"
+                type="number"
+                value={hoursForm.to_hour}
+                onChange={v => setHoursForm(f => f && { ...f, to_hour: v })}
+              />
+            </div>
+            <div className="flex items-center gap-3 text-xs text-white">
+              {WEEKDAYS.map((label, i) => (
+                <label key={label} className="flex items-center gap-1">
+                  <input
+                    type="checkbox"
+                    checked={hoursForm.days.includes(i + 1)}
+                    onChange={e => setHoursForm(f => f && {
+                      ...f,
+                      days: e.target.checked
+                        ? [...f.days, i + 1].sort()
+                        : f.days.filter(d => d !== i + 1),
+                    })}
+                  />
+                  {label}
+                </label>
+              ))}
+            </div>
+            <div className="flex items-center justify-between gap-2">
+              <p className={cn("text-xs", workingHours.state === "closed" ? "text-[#FF9500]" : "text-[#8E8E93]")}>
+                {workingHours.state === "closed" && "Outside working hours"}
+                {workingHours.state === "overridden" && "Outside working hours, running anyway"}
+                {workingHours.state === "open" && workingHours.settings.enabled && "Within working hours"}
+                {workingHours.next_change && ` · changes ${new Date(workingHours.next_change).toLocaleString()}`}
+              </p>
+              <div className="flex gap-2">
+                {workingHours.state === "closed" && (
+                  <Button size="sm" variant="outline" onClick={() => overrideWorkingHours(true)}>
+                    Run anyway
+                  </Button>
+                )}
+                {workingHours.state === "overridden" && (
+                  <Button size="sm" variant="outline" onClick={() => overrideWorkingHours(false)}>
+                    Keep to hours
+                  </Button>
+                )}
+                <Button size="sm" variant="outline" onClick={() => saveWorkingHours(workingHours.settings.enabled)}>
+                  Save
+                </Button>
+              </div>
+            </div>
+          </div>
+        </Card>
+      )}
+
+      {/* Browser Drivers */}
+      {isTauriApp && (
+        <Card>
+          <div className="flex items-center justify-between mb-3">
+            <SectionHeader icon={<Globe className="h-4 w-4" />} title="Browser Drivers" />
+            <Button size="sm" variant="outline" loading={checkingDrivers} onClick={handleCheckDrivers}>
+              Check now
+            </Button>
+          </div>
+          <div className="space-y-2">
+            {drivers.map(d => (
+              <div key={d.browser} className="flex items-center justify-between text-sm">
+                <div>
+                  <p className="text-white">{BROWSER_LABELS[d.browser]}</p>
+                  <p className="text-xs text-[#8E8E93]">
+                    {d.browser_version ? `Version ${d.browser_version}` : "Not installed"}
+                    {d.driver_version && ` · driver ${d.driver_version}`}
+                  </p>
+                </div>
+                {d.browser_version && (
+                  d.up_to_date ? (
+                    <CheckCircle2 className="h-4 w-4 text-[#34C759]" />
+                  ) : (
+                    <span className="text-xs text-[#FF9500]">Driver needed</span>
+                  )
+                )}
+              </div>
+            ))}
+          </div>
+          <p className="text-xs text-[#8E8E93] mt-3">
+            The driver matching your browser is downloaded, checked and kept up to date as the browser updates.
+          </p>
+        </Card>
+      )}
+
+      {/* Browser Profiles */}
+      {isTauriApp && (
+        <Card>
+          <div className="flex items-center justify-between mb-3">
+            <SectionHeader icon={<UserRound className="h-4 w-4" />} title="Browser Profiles" />
             <Button
               size="sm"
               variant="outline"
-              loading={testingConnection}
-              onClick={testConnection}
+              onClick={() => setNewProfile({ site: scraperSites[0] ?? "", account: "" })}
             >
-              Test Connection
+              <Plus className="h-3.5 w-3.5" />
+              Add
             </Button>
-            {connectionResult === "ok" && (
-              <span className="flex items-center gap-1 text-xs text-[#34C759]">
-                <CheckCircle2 className="h-3.5 w-3.5" />
-                Connected
-              </span>
-            )}
-            {connectionResult === "error" && (
-              <span className="flex items-center gap-1 text-xs text-[#FF3B30]">
-                <XCircle className="h-3.5 w-3.5" />
-                Failed to connect
-              </span>
-            )}
           </div>
-        </div>
-      </Card>
+          {newProfile && (
+            <div className="flex items-center gap-2 mb-3">
+              <div className="relative">
+                <select
+                  value={newProfile.site}
+                  onChange={e => setNewProfile(p => p && { ...p, site: e.target.value })}
+                  className="appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-2 pr-7 text-sm text-white outline-none"
+                >
+                  {scraperSites.map(site => (
+                    <option key={site} value={site} className="bg-[#2C2C2E]">{site}</option>
+                  ))}
+                </select>
+                <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+              </div>
+              <input
+                value={newProfile.account}
+                onChange={e => setNewProfile(p => p && { ...p, account: e.target.value })}
+                placeholder="Account, e.g. your email"
+                className="flex-1 bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <Button size="sm" variant="outline" onClick={() => setNewProfile(null)}>
+                Cancel
+              </Button>
+              <Button size="sm" disabled={!newProfile.site} onClick={handleCreateBrowserProfile}>
+                Create
+              </Button>
+            </div>
+          )}
+          <div className="space-y-2">
+            {browserProfiles.map(p => (
+              <div key={p.id} className="flex items-center justify-between text-sm">
+                <div className="min-w-0">
+                  <p className="text-white truncate">
+                    {p.site}
+                    {p.account && <span className="text-[#8E8E93]"> · {p.account}</span>}
+                  </p>
+                  <p className="text-xs text-[#8E8E93]">
+                    {(p.size_bytes / 1048576).toFixed(1)} MB
+                    {p.in_use && " · open now"}
+                    {p.reset_at && ` · reset ${new Date(p.reset_at).toLocaleDateString()}`}
+                  </p>
+                </div>
+                <div className="flex items-center gap-1 shrink-0">
+                  {p.active ? (
+                    <span className="text-xs text-[#34C759] mr-1">In use for {p.site}</span>
+                  ) : (
+                    <Button size="sm" variant="outline" onClick={() => handleBrowserProfile("set_active_browser_profile", p)}>
+                      Use
+                    </Button>
+                  )}
+                  <button
+                    onClick={() => handleBrowserProfile("reset_browser_profile", p)}
+                    className="text-[#8E8E93] hover:text-white p-1.5 rounded-lg hover:bg-white/5"
+                    title="Reset"
+                  >
+                    <RotateCcw className="h-3.5 w-3.5" />
+                  </button>
+                  <button
+                    onClick={() => handleBrowserProfile("wipe_browser_profile", p)}
+                    className="text-[#8E8E93] hover:text-[#FF3B30] p-1.5 rounded-lg hover:bg-white/5"
+                    title="Delete"
+                  >
+                    <Trash2 className="h-3.5 w-3.5" />
+                  </button>
+                </div>
+              </div>
+            ))}
+          </div>
+          <p className="text-xs text-[#8E8E93] mt-3">
+            Each job-site account gets its own browser profile, so the engine's cookies and sign-ins stay out of your everyday browser.
+          </p>
+        </Card>
+      )}
 
-      {/* Scraping Section */}
-      <Card>
-        <SectionHeader icon={<Globe className="h-4 w-4" />} title="Scraping" />
-        <div className="space-y-3">
-          <p className="text-xs text-[#8E8E93]">
-            JobBot applies rate limiting between requests to avoid triggering anti-bot protections.
-            Aggressive scraping may result in IP bans on job platforms.
+      {/* Proxy Pool */}
+      {isTauriApp && (
+        <Card>
+          <div className="flex items-center justify-between mb-3">
+            <SectionHeader icon={<Network className="h-4 w-4" />} title="Proxy Pool" />
+            <div className="flex items-center gap-2">
+              {proxyPool.length > 1 && (
+                <Button
+                  size="sm"
+                  variant="outline"
+                  onClick={() => invoke("rotate_proxy_pool", { site: null }).catch(e => toast.error(String(e)))}
+                >
+                  <Shuffle className="h-3.5 w-3.5" />
+                  Rotate
+                </Button>
+              )}
+              {proxyPool.length > 0 && (
+                <Button size="sm" variant="outline" loading={checkingPool} onClick={handleCheckPool}>
+                  Check now
+                </Button>
+              )}
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={() => setNewPoolProxy({ label: "", scheme: "http", host: "", port: "", username: "", password: "" })}
+              >
+                <Plus className="h-3.5 w-3.5" />
+                Add
+              </Button>
+            </div>
+          </div>
+          {newPoolProxy && (
+            <div className="grid grid-cols-2 gap-2 mb-3">
+              <div className="relative">
+                <select
+                  value={newPoolProxy.scheme}
+                  onChange={e => setNewPoolProxy(p => p && { ...p, scheme: e.target.value as NewPoolProxy["scheme"] })}
+                  className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-2 pr-7 text-sm text-white outline-none"
+                >
+                  <option value="http" className="bg-[#2C2C2E]">HTTP</option>
+                  <option value="https" className="bg-[#2C2C2E]">HTTPS</option>
+                  <option value="socks5" className="bg-[#2C2C2E]">SOCKS5</option>
+                </select>
+                <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+              </div>
+              <input
+                value={newPoolProxy.label}
+                onChange={e => setNewPoolProxy(p => p && { ...p, label: e.target.value })}
+                placeholder="Name (optional)"
+                className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <input
+                value={newPoolProxy.host}
+                onChange={e => setNewPoolProxy(p => p && { ...p, host: e.target.value })}
+                placeholder="Host"
+                className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <input
+                value={newPoolProxy.port}
+                onChange={e => setNewPoolProxy(p => p && { ...p, port: e.target.value.replace(/\D/g, "") })}
+                placeholder="Port"
+                inputMode="numeric"
+                className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <input
+                value={newPoolProxy.username}
+                onChange={e => setNewPoolProxy(p => p && { ...p, username: e.target.value })}
+                placeholder="Username (optional)"
+                className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <input
+                type="password"
+                value={newPoolProxy.password}
+                onChange={e => setNewPoolProxy(p => p && { ...p, password: e.target.value })}
+                placeholder="Password"
+                className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <Button size="sm" variant="outline" onClick={() => setNewPoolProxy(null)}>
+                Cancel
+              </Button>
+              <Button size="sm" disabled={!newPoolProxy.host || !newPoolProxy.port} onClick={handleAddPoolProxy}>
+                Add proxy
+              </Button>
+            </div>
+          )}
+          <div className="space-y-2">
+            {proxyPool.map(p => {
+              const rested = p.rested_until && new Date(p.rested_until) > new Date()
+              return (
+                <div key={p.id} className={cn("flex items-center justify-between text-sm", !p.enabled && "opacity-50")}>
+                  <div className="min-w-0">
+                    <p className="text-white truncate">
+                      {p.label}
+                      <span className="text-[#8E8E93]"> · {p.scheme}://{p.host}:{p.port}</span>
+                    </p>
+                    <p className="text-xs text-[#8E8E93] truncate">
+                      {p.healthy === null
+                        ? "Not checked yet"
+                        : p.healthy
+                          ? `Healthy · ${p.latency_ms} ms`
+                          : `Unreachable${p.last_error ? ` · ${p.last_error}` : ""}`}
+                      {p.requests > 0 &&
+                        ` · ${p.requests} pages, ${Math.round(p.ban_rate * 100)}% banned, ${Math.round(p.captcha_rate * 100)}% captcha`}
+                      {rested && ` · resting until ${new Date(p.rested_until!).toLocaleTimeString()}`}
+                      {p.sites.length > 0 && ` · ${p.sites.join(", ")}`}
+                    </p>
+                  </div>
+                  <div className="flex items-center gap-2 shrink-0">
+                    {p.healthy === false || rested ? (
+                      <AlertTriangle className="h-4 w-4 text-[#FF9500]" />
+                    ) : p.healthy ? (
+                      <CheckCircle2 className="h-4 w-4 text-[#34C759]" />
+                    ) : null}
+                    <Toggle
+                      label=""
+                      checked={p.enabled}
+                      onChange={enabled =>
+                        invoke("set_pool_proxy_enabled", { id: p.id, enabled }).catch(e => toast.error(String(e)))
+                      }
+                    />
+                    <button
+                      onClick={() => {
+                        if (!confirm(`Remove ${p.label} from the pool?`)) return
+                        invoke("remove_pool_proxy", { id: p.id }).catch(e => toast.error(String(e)))
+                      }}
+                      className="text-[#8E8E93] hover:text-[#FF3B30] p-1.5 rounded-lg hover:bg-white/5"
+                      title="Remove"
+                    >
+                      <Trash2 className="h-3.5 w-3.5" />
+                    </button>
+                  </div>
+                </div>
+              )
+            })}
+          </div>
+          <p className="text-xs text-[#8E8E93] mt-3">
+            Job sites are spread across these proxies and moved to another every few hours. Proxies that stop answering or keep
+            getting banned or shown captchas are rested for an hour. Passwords are kept in your system keychain.
           </p>
-          <p className="text-xs text-[#8E8E93]">
-            Default delay: <span className="text-white font-medium">2–6 seconds</span> between page
-            requests. Adjust in the backend configuration if needed.
+        </Card>
+      )}
+
+      {/* Email */}
+      {isTauriApp && mailWatch && (
+        <Card>
+          <div className="flex items-center justify-between mb-3">
+            <SectionHeader icon={<Mail className="h-4 w-4" />} title="Email Replies" />
+            <div className="flex items-center gap-2">
+              {mailWatch.settings.enabled && (
+                <Button size="sm" variant="outline" loading={checkingMail} onClick={handleCheckMail}>
+                  Check now
+                </Button>
+              )}
+              {!mailForm && (
+                <Button
+                  size="sm"
+                  variant="outline"
+                  onClick={() => setMailForm({ ...mailWatch.settings, password: "" })}
+                >
+                  Set up
+                </Button>
+              )}
+            </div>
+          </div>
+          {mailForm ? (
+            <div className="space-y-3 mb-3">
+              <div className="grid grid-cols-2 gap-3">
+                <SettingInput
+                  label="IMAP server"
+                  value={mailForm.host}
+                  onChange={v => setMailForm(f => f && { ...f, host: v })}
+                  placeholder="imap.gmail.com"
+                />
+                <SettingInput
+                  label="Port"
+                  value={String(mailForm.port || "")}
+                  onChange={v => setMailForm(f => f && { ...f, port: Number(v.replace(/\D/g, "")) })}
+                  placeholder="993"
+                />
+                <SettingInput
+                  label="User"
+                  value={mailForm.username}
+                  onChange={v => setMailForm(f => f && { ...f, username: v })}
+                />
+                <SettingInput
+                  label="Password"
+                  type="password"
+                  value={mailForm.password}
+                  onChange={v => setMailForm(f => f && { ...f, password: v })}
+                  placeholder={mailWatch.has_password ? "Saved" : "App password"}
+                />
+                <SettingInput
+                  label="Folder"
+                  value={mailForm.folder}
+                  onChange={v => setMailForm(f => f && { ...f, folder: v })}
+                  placeholder="currobot"
+                />
+                <SettingInput
+                  label="Check every (minutes)"
+                  type="number"
+                  value={String(mailForm.interval_minutes)}
+                  onChange={v => setMailForm(f => f && { ...f, interval_minutes: Number(v) })}
+                  placeholder="15"
+                />
+              </div>
+              <Toggle
+                label="Watch this folder"
+                checked={mailForm.enabled}
+                onChange={enabled => setMailForm(f => f && { ...f, enabled })}
+              />
+              <Toggle
+                label="Update application status from replies"
+                checked={mailForm.update_status}
+                onChange={update_status => setMailForm(f => f && { ...f, update_status })}
+              />
+              <div className="flex justify-end gap-2">
+                <Button size="sm" variant="outline" onClick={() => setMailForm(null)}>
+                  Cancel
+                </Button>
+                <Button
+                  size="sm"
+                  onClick={() => {
+                    const { password, ...mail } = mailForm
+                    saveMailWatch(mail, password || null)
+                  }}
+                >
+                  Save
+                </Button>
+              </div>
+            </div>
+          ) : (
+            <p className="text-sm text-white mb-2">
+              {mailWatch.settings.enabled
+                ? `Watching “${mailWatch.settings.folder}” on ${mailWatch.settings.host}`
+                : "Not watching any mailbox"}
+              {mailWatch.last_check && (
+                <span className="text-xs text-[#8E8E93]">
+                  {" "}· checked {new Date(mailWatch.last_check).toLocaleTimeString()}
+                </span>
+              )}
+            </p>
+          )}
+          {mailWatch.last_error && (
+            <p className="text-xs text-[#FF9500] mb-2">{mailWatch.last_error}</p>
+          )}
+          <div className="space-y-2">
+            {mailWatch.recent.slice(0, 8).map(m => (
+              <div key={m.message_id} className="flex items-center justify-between gap-3 text-sm">
+                <div className="min-w-0">
+                  <p className="text-white truncate">{m.subject || "(no subject)"}</p>
+                  <p className="text-xs text-[#8E8E93] truncate">
+                    {m.company ?? m.from}
+                    {m.received_at && ` · ${new Date(m.received_at).toLocaleDateString()}`}
+                    {m.status && ` · marked ${m.status.replace(/_/g, " ")}`}
+                  </p>
+                </div>
+                {m.kind && (
+                  <span className="shrink-0 text-xs px-2 py-0.5 rounded-full bg-white/5 text-[#8E8E93]">
+                    {MAIL_KIND_LABELS[m.kind]}
+                  </span>
+                )}
+              </div>
+            ))}
+          </div>
+          <p className="text-xs text-[#8E8E93] mt-3">
+            Point a mail rule at a folder of its own for replies from employers. New messages there are matched to the
+            applications you sent and you get a notification; they are never marked as read. The password is kept in your
+            system keychain.
           </p>
-        </div>
-      </Card>
+        </Card>
+      )}
+
+      {/* Accounts */}
+      {isTauriApp && oauth.length > 0 && (
+        <Card>
+          <SectionHeader icon={<KeyRound className="h-4 w-4" />} title="Accounts" />
+          <div className="space-y-3">
+            {oauth.map(o => (
+              <div key={o.provider}>
+                <div className="flex items-center justify-between text-sm">
+                  <div className="min-w-0">
+                    <p className="text-white">{OAUTH_LABELS[o.provider]}</p>
+                    <p className="text-xs text-[#8E8E93] truncate">
+                      {o.signing_in
+                        ? "Waiting for the browser…"
+                        : o.connected
+                          ? `Signed in${o.expires_at ? ` · token renews ${new Date(o.expires_at).toLocaleString()}` : ""}`
+                          : o.client_id
+                            ? "Not signed in"
+                            : "No app set up"}
+                    </p>
+                  </div>
+                  <div className="flex items-center gap-2 shrink-0">
+                    <Button
+                      size="sm"
+                      variant="outline"
+                      onClick={() =>
+                        setOAuthForm({
+                          provider: o.provider,
+                          client_id: o.client_id,
+                          client_secret: "",
+                          callback_port: String(o.callback_port),
+                        })
+                      }
+                    >
+                      Set up
+                    </Button>
+                    {o.signing_in ? (
+                      <Button size="sm" variant="outline" onClick={() => invoke("cancel_oauth_sign_in")}>
+                        Cancel
+                      </Button>
+                    ) : o.connected ? (
+                      <Button
+                        size="sm"
+                        variant="outline"
+                        onClick={() =>
+                          invoke("sign_out_oauth", { provider: o.provider }).catch(e => toast.error(String(e)))
+                        }
+                      >
+                        Sign out
+                      </Button>
+                    ) : (
+                      <Button size="sm" disabled={!o.client_id} onClick={() => handleOAuthSignIn(o.provider)}>
+                        Sign in
+                      </Button>
+                    )}
+                  </div>
+                </div>
+                {oauthForm?.provider === o.provider && (
+                  <div className="grid grid-cols-3 gap-2 mt-2">
+                    <SettingInput
+                      label="Client ID"
+                      value={oauthForm.client_id}
+                      onChange={v => setOAuthForm(f => f && { ...f, client_id: v })}
+                    />
+                    <SettingInput
+                      label="Client secret"
+                      type="password"
+                      value={oauthForm.client_secret}
+                      onChange={v => setOAuthForm(f => f && { ...f, client_secret: v })}
+                      placeholder={o.has_client_secret ? "Saved" : "Optional"}
+                    />
+                    <SettingInput
+                      label="Callback port"
+                      value={oauthForm.callback_port}
+                      onChange={v => setOAuthForm(f => f && { ...f, callback_port: v.replace(/\D/g, "") })}
+                      placeholder="0 = any free port"
+                    />
+                    <div className="col-span-3 flex justify-end gap-2">
+                      <Button size="sm" variant="outline" onClick={() => setOAuthForm(null)}>
+                        Cancel
+                      </Button>
+                      <Button size="sm" onClick={handleSaveOAuthClient}>
+                        Save
+                      </Button>
+                    </div>
+                  </div>
+                )}
+              </div>
+            ))}
+          </div>
+          <p className="text-xs text-[#8E8E93] mt-3">
+            Sign-in opens in your browser, since LinkedIn and Google block it inside the app. Register{" "}
+            <code>http://127.0.0.1:47823/callback</code> (or your callback port) as the redirect URL of your app. Tokens are
+            kept in your system keychain.
+          </p>
+        </Card>
+      )}
+
+      {/* Webhooks */}
+      {isTauriApp && (
+        <Card>
+          <SectionHeader icon={<Webhook className="h-4 w-4" />} title="Webhooks" />
+          <div className="space-y-3">
+            {webhooks.map(w => (
+              <div key={w.id} className="flex items-center justify-between gap-2 text-sm">
+                <div className="min-w-0">
+                  <p className={cn("truncate", w.enabled ? "text-white" : "text-[#8E8E93]")}>{w.url}</p>
+                  <p className="text-xs text-[#8E8E93] truncate">
+                    {w.events.map(e => WEBHOOK_EVENT_LABELS[e]).join(", ")}
+                    {w.has_secret ? " · signed" : " · unsigned"}
+                    {!w.enabled && " · off"}
+                  </p>
+                </div>
+                <div className="flex items-center gap-2 shrink-0">
+                  <Button size="sm" variant="outline" onClick={() => handleTestWebhook(w.id)}>
+                    Test
+                  </Button>
+                  <Button
+                    size="sm"
+                    variant="outline"
+                    onClick={() => setWebhookForm({ ...w, secret: "" })}
+                  >
+                    Edit
+                  </Button>
+                  <button
+                    className="text-[#8E8E93] hover:text-[#FF3B30]"
+                    onClick={() => invoke("remove_webhook", { id: w.id }).catch(e => toast.error(String(e)))}
+                  >
+                    <Trash2 className="h-4 w-4" />
+                  </button>
+                </div>
+              </div>
+            ))}
+            {webhookForm ? (
+              <div className="space-y-2">
+                <div className="grid grid-cols-2 gap-2">
+                  <SettingInput
+                    label="URL"
+                    value={webhookForm.url}
+                    onChange={v => setWebhookForm(f => f && { ...f, url: v })}
+                    placeholder="https://hooks.zapier.com/…"
+                  />
+                  <SettingInput
+                    label="Signing secret"
+                    type="password"
+                    value={webhookForm.secret}
+                    onChange={v => setWebhookForm(f => f && { ...f, secret: v })}
+                    placeholder={webhookForm.has_secret ? "Saved" : "Optional"}
+                  />
+                </div>
+                <div className="flex flex-wrap gap-1.5">
+                  {(Object.keys(WEBHOOK_EVENT_LABELS) as WebhookEvent[]).map(e => (
+                    <button
+                      key={e}
+                      onClick={() =>
+                        setWebhookForm(f => f && {
+                          ...f,
+                          events: f.events.includes(e) ? f.events.filter(x => x !== e) : [...f.events, e],
+                        })
+                      }
+                      className={cn(
+                        "px-2.5 py-1 rounded-full text-xs transition-colors",
+                        webhookForm.events.includes(e) ? "bg-[#007AFF] text-white" : "bg-white/5 text-[#8E8E93]"
+                      )}
+                    >
+                      {WEBHOOK_EVENT_LABELS[e]}
+                    </button>
+                  ))}
+                </div>
+                <Toggle
+                  label="Enabled"
+                  checked={webhookForm.enabled}
+                  onChange={v => setWebhookForm(f => f && { ...f, enabled: v })}
+                />
+                <div className="flex justify-end gap-2">
+                  <Button size="sm" variant="outline" onClick={() => setWebhookForm(null)}>
+                    Cancel
+                  </Button>
+                  <Button size="sm" disabled={!webhookForm.url || webhookForm.events.length === 0} onClick={handleSaveWebhook}>
+                    Save
+                  </Button>
+                </div>
+              </div>
+            ) : (
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={() =>
+                  setWebhookForm({
+                    id: null,
+                    url: "",
+                    events: ["application.submitted", "application.status_changed"],
+                    enabled: true,
+                    secret: "",
+                    has_secret: false,
+                  })
+                }
+              >
+                <Plus className="h-3.5 w-3.5" />
+                Add webhook
+              </Button>
+            )}
+            {deliveries.length > 0 && (
+              <div className="border-t border-white/5 pt-2 space-y-1 max-h-48 overflow-y-auto">
+                <p className="text-xs text-[#8E8E93] font-medium">Recent deliveries</p>
+                {deliveries.slice(0, 20).map(d => (
+                  <div key={d.id} className="flex items-center gap-2 text-xs">
+                    <span className={cn("w-16 shrink-0", DELIVERY_COLORS[d.state])}>{d.state}</span>
+                    <span className="text-white truncate flex-1">
+                      {d.event} · {new Date(d.created_at).toLocaleString()}
+                    </span>
+                    <span className="text-[#8E8E93] truncate max-w-[40%]">
+                      {d.response_status ? `HTTP ${d.response_status}` : d.last_error}
+                      {d.attempts > 1 && ` · ${d.attempts} tries`}
+                    </span>
+                    {d.state === "failed" && (
+                      <button
+                        className="text-[#007AFF] shrink-0"
+                        onClick={() =>
+                          invoke("redeliver_webhook", { deliveryId: d.id }).catch(e => toast.error(String(e)))
+                        }
+                      >
+                        Retry
+                      </button>
+                    )}
+                  </div>
+                ))}
+              </div>
+            )}
+          </div>
+          <p className="text-xs text-[#8E8E93] mt-3">
+            Each event is POSTed as JSON. With a secret, <code>X-Currobot-Signature</code> is
+            <code> sha256=</code> HMAC of <code>timestamp.body</code>, with the timestamp in{" "}
+            <code>X-Currobot-Timestamp</code>. Failed deliveries are retried for about an hour.
+          </p>
+        </Card>
+      )}
 
       {/* Data Retention */}
       <Card>
@@ -462,6 +2858,115 @@ export default function SettingsPage() {
             placeholder="90"
           />
         </div>
+        {isTauriApp && evidenceForm && (
+          <div className="mt-4 pt-3 border-t border-white/5 space-y-2">
+            <p className="text-xs text-[#8E8E93]">
+              Screenshots and pages kept as proof of each submission. 0 keeps them regardless.
+            </p>
+            <div className="grid grid-cols-2 gap-4">
+              <SettingInput
+                label="Keep proof for (days)"
+                type="number"
+                value={evidenceForm.retention_days}
+                onChange={v => setEvidenceForm(f => f && { ...f, retention_days: v })}
+              />
+              <SettingInput
+                label="Proof archive limit (MB)"
+                type="number"
+                value={evidenceForm.max_mb}
+                onChange={v => setEvidenceForm(f => f && { ...f, max_mb: v })}
+              />
+            </div>
+            <div className="flex justify-end">
+              <Button size="sm" variant="outline" onClick={saveEvidenceSettings}>
+                Save
+              </Button>
+            </div>
+          </div>
+        )}
+        {isTauriApp && retentionForm && (
+          <div className="mt-4 pt-3 border-t border-white/5 space-y-3">
+            <p className="text-xs text-[#8E8E93]">
+              Removes applications that went nowhere and old screenshots, in the engine and the history, at most once a day.
+              0 keeps them regardless.
+              {retentionForm.last_run_at && ` Last run ${new Date(retentionForm.last_run_at).toLocaleString()}.`}
+            </p>
+            <Toggle
+              label="Remove old data automatically"
+              checked={retentionForm.enabled}
+              onChange={v => setRetentionForm(f => f && { ...f, enabled: v })}
+            />
+            <div className="grid grid-cols-2 gap-4">
+              <SettingInput
+                label="Remove applications after (months)"
+                type="number"
+                value={retentionForm.applications_months}
+                onChange={v => setRetentionForm(f => f && { ...f, applications_months: v })}
+              />
+              <SettingInput
+                label="Remove screenshots after (days)"
+                type="number"
+                value={retentionForm.screenshots_days}
+                onChange={v => setRetentionForm(f => f && { ...f, screenshots_days: v })}
+              />
+            </div>
+            <div className="flex items-center gap-4 text-sm text-white">
+              <span className="text-xs text-[#8E8E93]">Applications that are</span>
+              {RETENTION_STATUSES.map(status => (
+                <label key={status} className="flex items-center gap-1.5 capitalize">
+                  <input
+                    type="checkbox"
+                    checked={retentionForm.statuses.includes(status)}
+                    onChange={e => setRetentionForm(f => f && {
+                      ...f,
+                      statuses: e.target.checked
+                        ? [...f.statuses, status]
+                        : f.statuses.filter(s => s !== status),
+                    })}
+                  />
+                  {status}
+                </label>
+              ))}
+            </div>
+            {retentionPreview && (
+              <div className="rounded-lg bg-white/5 p-3 text-xs text-[#8E8E93] space-y-1">
+                {!retentionPreview.engine_reachable && (
+                  <p className="text-amber-400">
+                    The engine isn&apos;t running: only the history is listed, and nothing can be removed until it is.
+                  </p>
+                )}
+                <p className="text-white">
+                  {retentionPreview.applications.length} applications and {retentionPreview.screenshots.length} screenshots
+                  ({(retentionPreview.bytes / 1024 / 1024).toFixed(1)} MB) would go.
+                </p>
+                <ul className="max-h-40 overflow-y-auto space-y-0.5">
+                  {retentionPreview.applications.map(a => (
+                    <li key={a.id}>
+                      {a.company}{a.role ? ` — ${a.role}` : ""} · {a.status}
+                      {a.updated_at && ` · ${new Date(a.updated_at).toLocaleDateString()}`}
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            )}
+            <div className="flex justify-end gap-2">
+              <Button size="sm" variant="outline" onClick={previewRetention} disabled={pruning}>
+                Preview
+              </Button>
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={runRetention}
+                disabled={pruning || !retentionPreview?.engine_reachable}
+              >
+                Remove now
+              </Button>
+              <Button size="sm" variant="outline" onClick={saveRetentionSettings}>
+                Save
+              </Button>
+            </div>
+          </div>
+        )}
       </Card>
 
       {/* Sound */}
@@ -556,47 +3061,782 @@ export default function SettingsPage() {
           <p className="text-xs text-[#8E8E93] mt-2">
             You can also toggle this from the tray icon menu.
           </p>
-        </Card>
-      )}
-
-      {/* Backup */}
-      <Card>
-        <SectionHeader icon={<Database className="h-4 w-4" />} title="Backup" />
-        <div className="flex items-center justify-between">
-          <div>
-            <p className="text-sm text-white">Database Backup</p>
-            <p className="text-xs text-[#8E8E93] mt-0.5">
-              Stored in <code className="text-[#007AFF]">data/backups/</code> directory
+          {loginItem && loginItem.methods.length > 1 && (
+            <div className="flex items-center justify-between mt-3">
+              <span className="text-sm text-white">Register as</span>
+              <div className="relative">
+                <select
+                  value={loginItem.method}
+                  onChange={e => handleLoginItemMethod(e.target.value as LoginItemMethod)}
+                  className="appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 pr-7 text-sm text-white outline-none"
+                >
+                  {loginItem.methods.map(m => (
+                    <option key={m} value={m} className="bg-[#2C2C2E]">
+                      {LOGIN_ITEM_LABELS[m]}
+                    </option>
+                  ))}
+                </select>
+                <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+              </div>
+            </div>
+          )}
+          {loginItem?.requires_approval && (
+            <p className="text-xs text-[#FF9500] mt-2">
+              macOS is waiting for you to allow currobot under Login Items.{" "}
+              <button className="text-[#007AFF]" onClick={() => invoke("open_login_items_settings")}>
+                Open System Settings
+              </button>
             </p>
-            {lastBackup && (
-              <p className="text-xs text-[#8E8E93] mt-0.5">
-                Last backup: {new Date(lastBackup).toLocaleString("es-ES")}
+          )}
+          {tray && !tray.available && (
+            <div className="border-t border-white/5 mt-4 pt-4">
+              <p className="text-xs text-[#FF9500] mb-2">
+                Your desktop doesn&apos;t show tray icons (on GNOME, install the AppIndicator extension), so closing the window quits.
               </p>
-            )}
+              <Toggle
+                label="Keep running when the window is closed"
+                checked={tray.background_without_tray}
+                onChange={handleBackgroundToggle}
+              />
+              <p className="text-xs text-[#8E8E93] mt-2">
+                Open currobot again from your applications to bring the window back.
+              </p>
+            </div>
+          )}
+          {display?.session === "wayland" && (
+            <div className="border-t border-white/5 mt-4 pt-4">
+              <Toggle
+                label="Wayland compatibility mode"
+                checked={display.compat_mode}
+                onChange={handleWaylandCompatToggle}
+              />
+              <p className="text-xs text-[#8E8E93] mt-2">
+                Runs currobot through XWayland, where its windows are placed as on X11. Applies the next time currobot starts.
+              </p>
+              {display.compat_mode !== startedCompat && (
+                <p className="text-xs text-[#FF9500] mt-2">Restart currobot to apply.</p>
+              )}
+            </div>
+          )}
+          <div className="border-t border-white/5 mt-4 pt-4">
+            <Toggle
+              label="Keep the computer awake during runs"
+              checked={keepAwake}
+              onChange={handleKeepAwakeToggle}
+            />
+            <p className="text-xs text-[#8E8E93] mt-2">
+              Holds off sleep until a run has finished. The screen can still turn off.
+            </p>
+          </div>
+          <div className="border-t border-white/5 mt-4 pt-4">
+            <Toggle
+              label="Encrypt the application history"
+              checked={encryptHistory}
+              onChange={handleEncryptHistoryToggle}
+            />
+            <p className="text-xs text-[#8E8E93] mt-2">
+              Keeps the history on this computer encrypted, with a key in the system keychain. The engine&apos;s own database is not encrypted.
+            </p>
+          </div>
+          {dataDir && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-3">
+              <div>
+                <p className="text-sm text-white">Data folder</p>
+                <p className="text-xs text-[#8E8E93] mt-0.5 break-all">{dataDir.path}</p>
+              </div>
+              {dataDir.locked_by_env ? (
+                <p className="text-xs text-[#8E8E93]">Set by JOBBOT_DATA_DIR, so it can&apos;t be moved from here.</p>
+              ) : (
+                <>
+                  <SettingInput
+                    label="Move to (an empty folder)"
+                    value={dataMove.path}
+                    onChange={v => setDataMove(m => ({ ...m, path: v }))}
+                    placeholder="/Volumes/Data/currobot"
+                  />
+                  <Toggle
+                    label="Delete the old copy afterwards"
+                    checked={dataMove.deleteOld}
+                    onChange={v => setDataMove(m => ({ ...m, deleteOld: v }))}
+                  />
+                  <div className="flex gap-2">
+                    <Button
+                      size="sm"
+                      variant="outline"
+                      loading={movingData}
+                      disabled={!dataMove.path.trim()}
+                      onClick={() => handleMoveData(dataMove.path)}
+                    >
+                      Move
+                    </Button>
+                    {dataDir.custom && dataDir.default_path && (
+                      <Button
+                        size="sm"
+                        variant="ghost"
+                        disabled={movingData}
+                        onClick={() => handleMoveData(dataDir.default_path!)}
+                      >
+                        Move back to the default
+                      </Button>
+                    )}
+                  </div>
+                  {dataMoveProgress && <p className="text-xs text-[#8E8E93]">{dataMoveLine(dataMoveProgress)}</p>}
+                </>
+              )}
+              <p className="text-xs text-[#8E8E93]">
+                The engine is stopped while the files are copied and checked. The app&apos;s own logs don&apos;t move
+                {dataDir.log_path ? <>; they stay in <span className="break-all">{dataDir.log_path}</span></> : null}.
+              </p>
+            </div>
+          )}
+          {extension && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-2">
+              <Toggle
+                label="Connect the browser extension"
+                checked={extension.settings.enabled}
+                onChange={saveExtension}
+              />
+              <p className="text-xs text-[#8E8E93]">
+                Lets the currobot extension add the posting you&apos;re viewing and show what became of your
+                application for it. Works while currobot is running.
+              </p>
+              {extension.settings.enabled && (
+                <>
+                  <p className="text-xs text-[#8E8E93]">
+                    {extension.registered.length > 0
+                      ? `Set up for ${extension.registered.join(", ")}.`
+                      : "No supported browser was found."}{" "}
+                    Firefox recognises the extension by itself; for Chrome, Chromium or Edge, paste its ID from the
+                    browser&apos;s extensions page.
+                  </p>
+                  <textarea
+                    value={extensionIds}
+                    onChange={e => setExtensionIds(e.target.value)}
+                    placeholder="abcdefghijklmnopabcdefghijklmnop"
+                    rows={2}
+                    className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm font-mono text-white outline-none focus:border-[#007AFF] placeholder:text-[#8E8E93]"
+                  />
+                  <Button size="sm" variant="outline" onClick={() => saveExtension(true)}>
+                    Save extension IDs
+                  </Button>
+                </>
+              )}
+            </div>
+          )}
+          {clipWatch && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-2">
+              <Toggle
+                label="Offer to add copied job links"
+                checked={clipWatch.enabled}
+                onChange={enabled => saveClipWatch({ ...clipWatch, enabled })}
+              />
+              <p className="text-xs text-[#8E8E93]">
+                When you copy a link to a posting on one of these sites, currobot asks whether to add it to your
+                jobs. Nothing else on the clipboard is kept.
+              </p>
+              {clipWatch.enabled && (
+                <>
+                  <textarea
+                    value={clipWatch.domains}
+                    onChange={e => setClipWatch({ ...clipWatch, domains: e.target.value })}
+                    placeholder="infojobs.net"
+                    rows={4}
+                    className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm font-mono text-white outline-none focus:border-[#007AFF] placeholder:text-[#8E8E93]"
+                  />
+                  <Button size="sm" variant="outline" onClick={() => saveClipWatch(clipWatch)}>
+                    Save sites
+                  </Button>
+                </>
+              )}
+            </div>
+          )}
+          {menuBar?.supported && (
+            <div className="border-t border-white/5 mt-4 pt-4">
+              <Toggle
+                label="Menu bar only"
+                checked={menuBar.menu_bar_only}
+                onChange={handleMenuBarToggle}
+              />
+              <p className="text-xs text-[#8E8E93] mt-2">
+                Hides the Dock icon while the window is closed. It comes back whenever the window is open.
+              </p>
+            </div>
+          )}
+          <Button
+            size="sm"
+            variant="ghost"
+            className="mt-2 -ml-3"
+            onClick={() => invoke("restart_onboarding").catch(e => toast.error(String(e)))}
+          >
+            Run the welcome guide again
+          </Button>
+          <Link href="/diagnostics" className="block text-xs text-[#007AFF] mt-1">
+            Diagnostics
+          </Link>
+          {language && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-1.5">
+              <label className="text-xs text-[#8E8E93] font-medium">Tray & notification language</label>
+              <div className="relative">
+                <select
+                  value={language.preference ?? ""}
+                  onChange={e => handleLanguageChange(e.target.value)}
+                  className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-2 pr-7 text-sm text-white outline-none"
+                >
+                  <option value="" className="bg-[#2C2C2E]">System default</option>
+                  {language.available.map(l => (
+                    <option key={l.code} value={l.code} className="bg-[#2C2C2E]">
+                      {l.name}
+                    </option>
+                  ))}
+                </select>
+                <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+              </div>
+            </div>
+          )}
+          {appLock?.available && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-3">
+              <Toggle
+                label="Lock with Touch ID / Windows Hello"
+                checked={appLock.enabled}
+                onChange={enabled => updateAppLock({ enabled, idle_minutes: appLock.idle_minutes })}
+              />
+              {appLock.enabled && (
+                <div className="flex items-center justify-between">
+                  <span className="text-sm text-white">Lock after inactivity</span>
+                  <div className="relative">
+                    <select
+                      value={appLock.idle_minutes}
+                      onChange={e => updateAppLock({ enabled: true, idle_minutes: Number(e.target.value) })}
+                      className="appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-1.5 pr-7 text-sm text-white outline-none"
+                    >
+                      {[1, 5, 15, 30, 60].map(m => (
+                        <option key={m} value={m} className="bg-[#2C2C2E]">
+                          {m} min
+                        </option>
+                      ))}
+                    </select>
+                    <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+                  </div>
+                </div>
+              )}
+              <p className="text-xs text-[#8E8E93]">
+                Opening the window asks your computer to confirm it's you.
+              </p>
+            </div>
+          )}
+          {sync && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-3">
+              <div>
+                <p className="text-sm text-white">Sync settings</p>
+                <p className="text-xs text-[#8E8E93] mt-0.5">
+                  Keeps desktop settings in step across machines through a folder you already sync
+                  (Dropbox, OneDrive…). The copy there is encrypted with your passphrase.
+                </p>
+              </div>
+              {sync.folder ? (
+                <>
+                  <p className="text-xs text-[#8E8E93] break-all">{sync.folder}</p>
+                  {sync.status.state === "synced" && (
+                    <p className="text-xs text-[#34C759]">Synced {new Date(sync.status.at).toLocaleString()}</p>
+                  )}
+                  {sync.status.state === "failed" && (
+                    <p className="text-xs text-[#FF3B30]">{sync.status.error}</p>
+                  )}
+                  {sync.status.state === "conflict" && (
+                    <div className="space-y-2">
+                      <p className="text-xs text-[#FF9500]">
+                        Changed differently on another machine: {sync.status.fields.join(", ")}
+                      </p>
+                      <div className="flex gap-2">
+                        <Button size="sm" variant="outline" loading={syncBusy} onClick={() => runSync("resolve_sync_conflict", { keep: "local" })}>
+                          Keep this machine's
+                        </Button>
+                        <Button size="sm" variant="outline" loading={syncBusy} onClick={() => runSync("resolve_sync_conflict", { keep: "remote" })}>
+                          Keep the other's
+                        </Button>
+                      </div>
+                    </div>
+                  )}
+                  <div className="flex gap-2">
+                    <Button size="sm" variant="outline" loading={syncBusy} onClick={() => runSync("sync_now")}>
+                      Sync now
+                    </Button>
+                    <Button size="sm" variant="ghost" disabled={syncBusy} onClick={() => runSync("disable_sync")}>
+                      Turn off
+                    </Button>
+                  </div>
+                </>
+              ) : (
+                <>
+                  <SettingInput
+                    label="Folder"
+                    value={syncForm.folder}
+                    onChange={v => setSyncForm(f => ({ ...f, folder: v }))}
+                    placeholder="/Users/me/Dropbox/JobBot"
+                  />
+                  <SettingInput
+                    label="Passphrase (the same on every machine)"
+                    type="password"
+                    value={syncForm.passphrase}
+                    onChange={v => setSyncForm(f => ({ ...f, passphrase: v }))}
+                  />
+                  <Button
+                    size="sm"
+                    variant="outline"
+                    loading={syncBusy}
+                    disabled={!syncForm.folder || !syncForm.passphrase}
+                    onClick={() => runSync("enable_sync", syncForm)}
+                  >
+                    Turn on sync
+                  </Button>
+                </>
+              )}
+            </div>
+          )}
+          <div className="border-t border-white/5 mt-4 pt-4">
+            <p className="text-sm text-white">Saved logins</p>
+            <p className="text-xs text-[#8E8E93] mt-0.5 mb-3">
+              For signing in by hand when automatic login fails. Copies are kept out of clipboard
+              history where your system allows it and cleared after a short while.
+            </p>
+            <div className="flex gap-2">
+              <Button size="sm" variant="outline" onClick={() => copyCredential("infojobs_password")}>
+                Copy InfoJobs password
+              </Button>
+              <Button size="sm" variant="outline" onClick={() => copyCredential("proxy_password")}>
+                Copy proxy password
+              </Button>
+            </div>
           </div>
-          <div className="flex flex-col items-end gap-1">
+          {sessions.length > 0 && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-2">
+              <div>
+                <p className="text-sm text-white">Signed-in job sites</p>
+                <p className="text-xs text-[#8E8E93] mt-0.5">
+                  Kept encrypted so restarts don't sign you out. Forgetting one takes effect the next
+                  time the engine starts.
+                </p>
+              </div>
+              {sessions.map(s => (
+                <div key={s.site} className="flex items-center justify-between">
+                  <div>
+                    <p className="text-sm text-white">{s.site}</p>
+                    <p className="text-xs text-[#8E8E93]">
+                      Until {new Date(s.expires_at * 1000).toLocaleString()}
+                    </p>
+                  </div>
+                  <Button size="sm" variant="ghost" onClick={() => forgetSession(s.site)}>
+                    Forget
+                  </Button>
+                </div>
+              ))}
+            </div>
+          )}
+          {redaction && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-3">
+              <div>
+                <p className="text-sm text-white">Hide personal data in logs</p>
+                <p className="text-xs text-[#8E8E93] mt-0.5">
+                  Emails, phone numbers and tokens are always removed. Add your name and any other
+                  patterns (regular expressions), one per line.
+                </p>
+              </div>
+              <textarea
+                value={redaction.names}
+                onChange={e => setRedaction({ ...redaction, names: e.target.value })}
+                placeholder="Your full name"
+                rows={2}
+                className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white outline-none focus:border-[#007AFF] placeholder:text-[#8E8E93]"
+              />
+              <textarea
+                value={redaction.patterns}
+                onChange={e => setRedaction({ ...redaction, patterns: e.target.value })}
+                placeholder="NIE-[A-Z0-9]+"
+                rows={2}
+                className="w-full bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm font-mono text-white outline-none focus:border-[#007AFF] placeholder:text-[#8E8E93]"
+              />
+              <SettingInput
+                label="Try a line (uses the saved rules)"
+                value={redactionPreview.text}
+                onChange={previewRedaction}
+              />
+              {redactionPreview.result && (
+                <p className="text-xs font-mono text-[#8E8E93] break-all">{redactionPreview.result}</p>
+              )}
+              <Button size="sm" variant="outline" onClick={saveRedaction}>
+                Save rules
+              </Button>
+            </div>
+          )}
+          {telemetry && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-2">
+              <Toggle
+                label="Share anonymous usage counts"
+                checked={telemetry.enabled}
+                onChange={toggleTelemetry}
+              />
+              <p className="text-xs text-[#8E8E93]">
+                Once a day: how often the app was started, crashed and used a few features, with the
+                app version and OS. No identifiers, jobs or personal data. Turning this off deletes
+                anything not yet sent.
+                {telemetry.enabled && !telemetry.sending && " Sending is currently paused."}
+              </p>
+              {telemetry.enabled && (
+                <>
+                  <Button
+                    size="sm"
+                    variant="ghost"
+                    className="-ml-3"
+                    onClick={() => setShowTelemetryPreview(!showTelemetryPreview)}
+                  >
+                    {showTelemetryPreview ? "Hide" : "Show"} exactly what is sent
+                  </Button>
+                  {showTelemetryPreview && (
+                    <pre className="text-[11px] font-mono text-[#8E8E93] whitespace-pre-wrap break-all max-h-64 overflow-auto">
+                      {telemetry.pending.length > 0
+                        ? JSON.stringify(telemetry.pending, null, 2)
+                        : "Nothing counted yet."}
+                    </pre>
+                  )}
+                </>
+              )}
+            </div>
+          )}
+          {errorReporting?.available && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-2">
+              <Toggle
+                label="Send errors to our Sentry project"
+                checked={errorReporting.enabled}
+                onChange={saveErrorReporting}
+              />
+              <p className="text-xs text-[#8E8E93]">
+                Crash reports and failed actions go to the Sentry DSN below, with personal details
+                removed the same way as in the logs.
+              </p>
+              <SettingInput
+                label="Sentry DSN"
+                value={sentryDsn}
+                onChange={setSentryDsn}
+                placeholder="https://key@o0.ingest.sentry.io/0"
+              />
+              <div className="flex gap-2">
+                <Button size="sm" variant="outline" onClick={() => saveErrorReporting(errorReporting.enabled)}>
+                  Save DSN
+                </Button>
+                <Button size="sm" variant="ghost" onClick={testErrorReporting} disabled={!sentryDsn}>
+                  Send test event
+                </Button>
+              </div>
+            </div>
+          )}
+          {crashReports.length > 0 && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-2">
+              <div>
+                <p className="text-sm text-white">Crash reports</p>
+                <p className="text-xs text-[#8E8E93] mt-0.5">
+                  Sending one opens a GitHub issue in your browser with the error and versions filled
+                  in, for you to review before submitting. Recent log lines stay on this computer.
+                </p>
+              </div>
+              {crashReports.map(r => (
+                <div key={r.id} className="bg-white/5 rounded-xl px-3 py-2 space-y-2">
+                  <div className="flex items-center justify-between gap-2">
+                    <button
+                      className="min-w-0 text-left"
+                      onClick={() => setOpenCrash(openCrash === r.id ? null : r.id)}
+                    >
+                      <p className="text-sm text-white truncate">{r.message}</p>
+                      <p className="text-xs text-[#8E8E93]">
+                        {new Date(r.at).toLocaleString()} · {r.kind} · app {r.app_version}
+                      </p>
+                    </button>
+                    <div className="flex gap-1 shrink-0">
+                      <Button size="sm" variant="outline" onClick={() => sendCrashReport(r.id)}>
+                        Send
+                      </Button>
+                      <Button size="sm" variant="ghost" onClick={() => deleteCrashReport(r.id)}>
+                        Delete
+                      </Button>
+                    </div>
+                  </div>
+                  {openCrash === r.id && (
+                    <pre className="text-[11px] font-mono text-[#8E8E93] whitespace-pre-wrap break-all max-h-64 overflow-auto">
+                      {[
+                        r.location && `at ${r.location}`,
+                        `engine ${r.engine_version ?? "unknown"} · ${r.os}`,
+                        r.minidump && `minidump: ${r.minidump}`,
+                        r.backtrace,
+                        r.log_tail.length > 0 && `--- last log lines ---\n${r.log_tail.join("\n")}`,
+                      ].filter(Boolean).join("\n\n")}
+                    </pre>
+                  )}
+                </div>
+              ))}
+            </div>
+          )}
+          {logUsage && (
+            <div className="border-t border-white/5 mt-4 pt-4 space-y-3">
+              <div>
+                <p className="text-sm text-white">Log files</p>
+                <p className="text-xs text-[#8E8E93] mt-0.5">
+                  Using {((logUsage.shell_bytes + logUsage.engine_bytes) / 1048576).toFixed(1)} MB
+                  of {(logUsage.budget_bytes / 1048576).toFixed(0)} MB (app{" "}
+                  {(logUsage.shell_bytes / 1048576).toFixed(1)} MB, engine{" "}
+                  {(logUsage.engine_bytes / 1048576).toFixed(1)} MB). The oldest files are deleted first.
+                </p>
+              </div>
+              <div className="grid grid-cols-2 gap-3">
+                <SettingInput
+                  label="Keep at most (MB)"
+                  type="number"
+                  value={logLimits.budget_mb}
+                  onChange={v => setLogLimits(l => ({ ...l, budget_mb: v }))}
+                  placeholder="50"
+                />
+                <SettingInput
+                  label="Keep for (days)"
+                  type="number"
+                  value={logLimits.max_age_days}
+                  onChange={v => setLogLimits(l => ({ ...l, max_age_days: v }))}
+                  placeholder="30"
+                />
+              </div>
+              <Button size="sm" variant="outline" onClick={saveLogLimits}>
+                Save limits
+              </Button>
+            </div>
+          )}
+          <div className="border-t border-white/5 mt-4 pt-4">
+            <p className="text-sm text-white">Reset app data</p>
+            <p className="text-xs text-[#8E8E93] mt-0.5 mb-3">
+              Deletes the selected data for this profile and restarts the app.
+            </p>
+            <div className="space-y-3">
+              {RESET_SCOPES.map(({ id, label }) => (
+                <Toggle
+                  key={id}
+                  label={label}
+                  checked={resetScopes.includes(id)}
+                  onChange={on => toggleResetScope(id, on)}
+                />
+              ))}
+            </div>
             <Button
               size="sm"
-              variant="outline"
-              loading={backupStatus === "running"}
-              onClick={runBackup}
+              variant="destructive"
+              className="mt-4"
+              disabled={resetScopes.length === 0}
+              loading={resetting}
+              onClick={handleReset}
             >
-              Run Backup Now
+              <Trash2 className="h-3.5 w-3.5" />
+              Reset Selected
             </Button>
-            {backupStatus === "done" && (
-              <span className="text-xs text-[#34C759] flex items-center gap-1">
-                <CheckCircle2 className="h-3 w-3" />
-                Backup complete
-              </span>
+          </div>
+        </Card>
+      )}
+
+      {/* Updates */}
+      {isTauriApp && (
+        <Card>
+          <SectionHeader icon={<Download className="h-4 w-4" />} title="Updates" />
+          <div className="space-y-3">
+            <p className={cn("text-xs", updateStatus.state === "failed" ? "text-[#FF3B30]" : "text-[#8E8E93]")}>
+              {updateLine(updateStatus)}
+            </p>
+            {updateStatus.state === "downloading" && (
+              <div className="h-1.5 bg-white/5 rounded-full overflow-hidden">
+                <motion.div
+                  className="h-full rounded-full bg-[#007AFF]"
+                  initial={{ width: 0 }}
+                  animate={{ width: `${updateStatus.percent ?? 0}%` }}
+                  transition={{ type: "spring", stiffness: 100, damping: 20 }}
+                />
+              </div>
             )}
-            {backupStatus === "coming_soon" && (
-              <span className="text-xs text-amber-400">
-                Coming soon
-              </span>
+            <div className="flex gap-2">
+              {updateStatus.state === "ready" ? (
+                <Button size="sm" onClick={handleInstallUpdate}>
+                  Restart to install
+                </Button>
+              ) : (
+                <Button
+                  size="sm"
+                  variant="outline"
+                  loading={["checking", "downloading", "verifying"].includes(updateStatus.state)}
+                  disabled={updateStatus.state === "installing"}
+                  onClick={handleCheckUpdate}
+                >
+                  {updateStatus.state === "failed" ? "Try again" : "Check for updates"}
+                </Button>
+              )}
+              {previousVersion && (
+                <Button
+                  size="sm"
+                  variant="ghost"
+                  loading={rollingBack}
+                  disabled={updateStatus.state === "installing"}
+                  onClick={handleRollback}
+                >
+                  <RotateCcw className="h-3.5 w-3.5" />
+                  Roll back to {previousVersion}
+                </Button>
+              )}
+            </div>
+          </div>
+        </Card>
+      )}
+
+      {/* Backup */}
+      {isTauriApp && appBackups && backupForm ? (
+        <Card>
+          <SectionHeader icon={<Database className="h-4 w-4" />} title="Backup" />
+          <div className="space-y-3">
+            <p className="text-xs text-[#8E8E93]">
+              Settings, history, documents and every profile in one archive in{" "}
+              <code className="text-[#007AFF]">{appBackups.folder}</code>. Passwords stay in the
+              system keychain; use the encrypted export to move those.
+            </p>
+            <Toggle
+              label="Back up automatically"
+              checked={appBackups.settings.enabled}
+              onChange={saveBackupSettings}
+            />
+            <div className="grid grid-cols-3 gap-2">
+              <SettingInput
+                label="Every (hours)"
+                type="number"
+                value={backupForm.interval_hours}
+                onChange={v => setBackupForm(f => f && { ...f, interval_hours: v })}
+              />
+              <SettingInput
+                label="Keep (scheduled)"
+                type="number"
+                value={backupForm.keep}
+                onChange={v => setBackupForm(f => f && { ...f, keep: v })}
+              />
+              <SettingInput
+                label="Folder"
+                value={backupForm.folder}
+                onChange={v => setBackupForm(f => f && { ...f, folder: v })}
+                placeholder="Default"
+              />
+            </div>
+            <div className="flex items-center justify-between gap-2">
+              <p className="text-xs text-[#8E8E93]">
+                {appBackups.settings.last_backup_at
+                  ? `Last backup: ${new Date(appBackups.settings.last_backup_at).toLocaleString()}`
+                  : "No backup yet"}
+              </p>
+              <div className="flex gap-2">
+                <Button size="sm" variant="outline" onClick={() => saveBackupSettings(appBackups.settings.enabled)}>
+                  Save
+                </Button>
+                <Button size="sm" variant="outline" onClick={() => handleInspectBackup(null)}>
+                  Restore from file…
+                </Button>
+                <Button size="sm" loading={backingUp} onClick={handleCreateBackup}>
+                  Back up now
+                </Button>
+              </div>
+            </div>
+            {restoreCandidate && (
+              <div className="rounded-xl border border-amber-400/30 bg-amber-400/5 p-3 space-y-2">
+                <p className="text-sm text-white">
+                  Restore the backup from {new Date(restoreCandidate.created_at).toLocaleString()}?
+                </p>
+                <p className="text-xs text-[#8E8E93]">
+                  {restoreCandidate.files} files, {(restoreCandidate.size / 1e6).toFixed(1)} MB, made by
+                  version {restoreCandidate.app_version}
+                  {restoreCandidate.skipped > 0 && ` · ${restoreCandidate.skipped} files were missing when it was made`}.
+                  The engine stops, everything you have now is replaced (and kept aside in{" "}
+                  <code>.before-restore</code>), and the app restarts.
+                </p>
+                <div className="flex justify-end gap-2">
+                  <Button size="sm" variant="outline" disabled={restoring} onClick={() => setRestoreCandidate(null)}>
+                    Cancel
+                  </Button>
+                  <Button size="sm" loading={restoring} onClick={handleRestoreBackup}>
+                    Restore
+                  </Button>
+                </div>
+              </div>
             )}
+            {appBackups.backups.length > 0 && (
+              <div className="border-t border-white/5 pt-2 space-y-1 max-h-48 overflow-y-auto">
+                {appBackups.backups.map(b => (
+                  <div key={b.path} className="flex items-center gap-2 text-xs">
+                    <span className="text-white truncate flex-1">
+                      {b.created_at ? new Date(b.created_at).toLocaleString() : b.name}
+                      {b.automatic && <span className="text-[#8E8E93]"> · scheduled</span>}
+                    </span>
+                    <span className="text-[#8E8E93]">{(b.size / 1e6).toFixed(1)} MB</span>
+                    <button
+                      className="text-[#007AFF] hover:underline"
+                      onClick={() => handleInspectBackup(b.path)}
+                    >
+                      Restore
+                    </button>
+                  </div>
+                ))}
+              </div>
+            )}
+            <div className="border-t border-white/5 pt-3 space-y-2">
+              <p className="text-xs text-[#8E8E93]">
+                Everything the app knows about you, to keep or take elsewhere: your applications and jobs as JSON,
+                your documents, CVs and proof of submission, and your settings without passwords.
+              </p>
+              <div className="flex items-center justify-between gap-2">
+                <label className="flex items-center gap-1.5 text-xs text-white">
+                  <input type="checkbox" checked={exportLogs} onChange={e => setExportLogs(e.target.checked)} />
+                  Include logs
+                </label>
+                <Button size="sm" variant="outline" loading={exportingData} onClick={handleExportData}>
+                  Export my data…
+                </Button>
+              </div>
+            </div>
           </div>
-        </div>
-      </Card>
+        </Card>
+      ) : (
+        <Card>
+          <SectionHeader icon={<Database className="h-4 w-4" />} title="Backup" />
+          <div className="flex items-center justify-between">
+            <div>
+              <p className="text-sm text-white">Database Backup</p>
+              <p className="text-xs text-[#8E8E93] mt-0.5">
+                Stored in <code className="text-[#007AFF]">data/backups/</code> directory
+              </p>
+              {lastBackup && (
+                <p className="text-xs text-[#8E8E93] mt-0.5">
+                  Last backup: {new Date(lastBackup).toLocaleString("es-ES")}
+                </p>
+              )}
+            </div>
+            <div className="flex flex-col items-end gap-1">
+              <Button
+                size="sm"
+                variant="outline"
+                loading={backupStatus === "running"}
+                onClick={runBackup}
+              >
+                Run Backup Now
+              </Button>
+              {backupStatus === "done" && (
+                <span className="text-xs text-[#34C759] flex items-center gap-1">
+                  <CheckCircle2 className="h-3 w-3" />
+                  Backup complete
+                </span>
+              )}
+              {backupStatus === "coming_soon" && (
+                <span className="text-xs text-amber-400">
+                  Coming soon
+                </span>
+              )}
+            </div>
+          </div>
+        </Card>
+      )}
 
       {/* Logs */}
       <Card>
@@ -640,6 +3880,13 @@ export default function SettingsPage() {
             onAdd={handleAddSource}
           />
         )}
+        {showAddSchedule && (
+          <AddScheduleForm
+            sites={scraperSites}
+            onClose={() => setShowAddSchedule(false)}
+            onAdd={handleAddSchedule}
+          />
+        )}
       </AnimatePresence>
     </div>
   )
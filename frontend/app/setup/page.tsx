@@ -7,14 +7,12 @@ import {
   Cpu, HardDrive, Download, Upload, FileText, Bot, AlertTriangle, Power
 } from "lucide-react"
 import { invoke } from "@tauri-apps/api/core"
-import { api } from "@/lib/api"
+import { api, authHeaders, BASE } from "@/lib/api"
 import type { SetupStatus, SystemHealth } from "@/lib/types"
 import { Button } from "@/components/ui/button"
 import { cn } from "@/lib/utils"
 import { toast } from "@/lib/toast"
 
-const BASE = "http://localhost:8000"
-
 const STEPS = [
   { id: 1, label: "System Check", icon: <Cpu className="h-4 w-4" /> },
   { id: 2, label: "RAM & Model",  icon: <HardDrive className="h-4 w-4" /> },
@@ -294,7 +292,7 @@ function Step3({
     try {
       await fetch(`${BASE}/api/setup/pull-model`, {
         method: "POST",
-        headers: { "Content-Type": "application/json" },
+        headers: { "Content-Type": "application/json", ...authHeaders() },
         body: JSON.stringify({ model: modelName }),
       })
       // Simulate progress bar over 30s
@@ -414,7 +412,7 @@ function Step4({ onUploaded }: { onUploaded: () => void }) {
     try {
       const fd = new FormData()
       fd.append("file", file)
-      const res = await fetch(`${BASE}/api/setup/upload-cv`, { method: "POST", body: fd })
+      const res = await fetch(`${BASE}/api/setup/upload-cv`, { method: "POST", headers: authHeaders(), body: fd })
       if (!res.ok) throw new Error(`Upload failed: ${res.statusText}`)
       setDone(true)
       setFilename(file.name)
@@ -0,0 +1,108 @@
+"use client"
+import { useEffect, useState } from "react"
+import { invoke } from "@tauri-apps/api/core"
+import { getCurrentWindow } from "@tauri-apps/api/window"
+import { Button } from "@/components/ui/button"
+import { cn } from "@/lib/utils"
+
+interface WipePlan {
+  folders: string[]
+  credentials: string[]
+}
+
+// Opened by the shell (tray "Uninstall JobBot..." item) in its own small window.
+// Wiping is opt-in; the list shows exactly what the shell will delete.
+export default function UninstallPage() {
+  const [plan, setPlan] = useState<WipePlan | null>(null)
+  const [wipe, setWipe] = useState(false)
+  const [confirmed, setConfirmed] = useState(false)
+  const [busy, setBusy] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    invoke<WipePlan>("get_wipe_plan")
+      .then(setPlan)
+      .catch(e => setError(String(e)))
+  }, [])
+
+  const uninstall = async () => {
+    setBusy(true)
+    setError(null)
+    try {
+      await invoke("cleanup_for_uninstall", { wipe })
+      await invoke("quit_for_uninstall")
+    } catch (e) {
+      setError(String(e))
+      setBusy(false)
+    }
+  }
+
+  const empty = plan && plan.folders.length === 0 && plan.credentials.length === 0
+
+  return (
+    <div className="fixed inset-0 z-50 flex flex-col p-6" style={{ background: "var(--bg)" }}>
+      <h1 className="text-lg font-semibold text-white">Uninstall JobBot</h1>
+      <p className="text-xs text-[#8E8E93] mt-1">
+        Start on login is turned off and the app quits. Then remove it as usual: on macOS move
+        JobBot.app to the Trash, on Windows use Add/Remove Programs.
+      </p>
+
+      <label className="flex items-center gap-2 mt-5 text-sm text-white">
+        <input
+          type="checkbox"
+          checked={wipe}
+          onChange={e => { setWipe(e.target.checked); setConfirmed(false) }}
+        />
+        Also delete all my data from this computer
+      </label>
+
+      <div
+        className={cn(
+          "flex-1 overflow-y-auto mt-3 rounded-xl border p-4 text-xs space-y-3",
+          wipe ? "border-[#FF3B30]/40" : "border-white/10 opacity-50"
+        )}
+      >
+        {!plan && !error && <p className="text-[#8E8E93]">Loading…</p>}
+        {empty && <p className="text-[#8E8E93]">Nothing to delete.</p>}
+        {plan && plan.folders.length > 0 && (
+          <div>
+            <p className="text-white font-medium mb-1">Folders (every profile, logs and caches)</p>
+            {plan.folders.map(f => (
+              <p key={f} className="font-mono text-[#8E8E93] break-all">{f}</p>
+            ))}
+          </div>
+        )}
+        {plan && plan.credentials.length > 0 && (
+          <div>
+            <p className="text-white font-medium mb-1">Passwords and keys in the system keychain</p>
+            {plan.credentials.map(c => (
+              <p key={c} className="font-mono text-[#8E8E93] break-all">{c}</p>
+            ))}
+          </div>
+        )}
+      </div>
+
+      {wipe && (
+        <label className="flex items-center gap-2 mt-3 text-xs text-[#FF3B30]">
+          <input type="checkbox" checked={confirmed} onChange={e => setConfirmed(e.target.checked)} />
+          I understand this cannot be undone
+        </label>
+      )}
+      {error && <p className="text-xs text-[#FF3B30] mt-3">{error}</p>}
+
+      <div className="flex justify-end gap-2 mt-4">
+        <Button variant="ghost" disabled={busy} onClick={() => getCurrentWindow().close()}>
+          Cancel
+        </Button>
+        <Button
+          variant={wipe ? "destructive" : "default"}
+          loading={busy}
+          disabled={wipe && (!plan || !confirmed)}
+          onClick={uninstall}
+        >
+          {wipe ? "Delete everything and quit" : "Uninstall"}
+        </Button>
+      </div>
+    </div>
+  )
+}
@@ -0,0 +1,25 @@
+"use client"
+import { useEffect } from "react"
+import { invoke } from "@tauri-apps/api/core"
+
+const REPORT_EVERY_MS = 30_000
+
+// Tells the shell the user is still here, so the optional app lock (see
+// Settings → Desktop) only kicks in after real inactivity.
+export function ActivityReporter() {
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    let last = 0
+    const report = () => {
+      const now = Date.now()
+      if (now - last < REPORT_EVERY_MS) return
+      last = now
+      invoke("app_lock_activity").catch(() => {})
+    }
+    const events = ["pointerdown", "pointermove", "keydown", "wheel"] as const
+    events.forEach(e => window.addEventListener(e, report, { passive: true }))
+    return () => events.forEach(e => window.removeEventListener(e, report))
+  }, [])
+
+  return null
+}
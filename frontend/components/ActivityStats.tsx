@@ -0,0 +1,155 @@
+"use client"
+import { useEffect, useState } from "react"
+import { motion } from "motion/react"
+import { BarChart3 } from "lucide-react"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { Card, CardHeader, CardTitle } from "@/components/ui/card"
+import { cn } from "@/lib/utils"
+
+type Range = "week" | "month" | "quarter" | "year"
+type GroupBy = "day" | "week" | "month"
+
+interface Stats {
+  buckets: { start: string; applications: number; responses: number }[]
+  totals: {
+    applications: number
+    responded: number
+    successes: number
+    offers: number
+    rejections: number
+    response_rate: number
+    success_rate: number
+    median_response_hours: number | null
+  }
+  boards: {
+    site: string
+    applications: number
+    response_rate: number
+    success_rate: number
+    median_response_hours: number | null
+  }[]
+}
+
+const RANGES: { id: Range; label: string; groupBy: GroupBy }[] = [
+  { id: "week", label: "7 days", groupBy: "day" },
+  { id: "month", label: "30 days", groupBy: "day" },
+  { id: "quarter", label: "90 days", groupBy: "week" },
+  { id: "year", label: "Year", groupBy: "month" },
+]
+
+const percent = (rate: number) => `${Math.round(rate * 100)}%`
+
+function duration(hours: number | null) {
+  if (hours === null) return "—"
+  return hours < 48 ? `${Math.round(hours)} h` : `${Math.round(hours / 24)} d`
+}
+
+// Rollups the desktop shell computes over its application history (stats.rs).
+export function ActivityStats() {
+  const [range, setRange] = useState<Range>("month")
+  const [stats, setStats] = useState<Stats | null>(null)
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const groupBy = RANGES.find(r => r.id === range)!.groupBy
+    const load = () => invoke<Stats>("get_stats", { range, groupBy }).then(setStats).catch(() => {})
+    load()
+    const unlisten = listen("history-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [range])
+
+  if (!stats) return null
+  const peak = Math.max(1, ...stats.buckets.map(b => Math.max(b.applications, b.responses)))
+  const t = stats.totals
+
+  return (
+    <Card>
+      <CardHeader className="flex items-center justify-between">
+        <CardTitle className="flex items-center gap-2">
+          <BarChart3 className="h-4 w-4 text-[#007AFF]" />
+          Activity
+        </CardTitle>
+        <div className="flex gap-1">
+          {RANGES.map(r => (
+            <button
+              key={r.id}
+              onClick={() => setRange(r.id)}
+              className={cn(
+                "text-xs px-2 py-1 rounded-lg",
+                range === r.id ? "bg-white/10 text-white" : "text-[var(--fg-secondary)] hover:text-white"
+              )}
+            >
+              {r.label}
+            </button>
+          ))}
+        </div>
+      </CardHeader>
+      <div className="flex items-end gap-0.5 h-24 mb-1">
+        {stats.buckets.map((b, i) => (
+          <div
+            key={b.start}
+            className="flex-1 flex items-end gap-px h-full"
+            title={`${b.start}: ${b.applications} sent, ${b.responses} replies`}
+          >
+            <motion.div
+              className="flex-1 bg-[#007AFF] rounded-t-sm"
+              initial={{ height: 0 }}
+              animate={{ height: `${(b.applications / peak) * 100}%` }}
+              transition={{ delay: i * 0.01, type: "spring", stiffness: 120, damping: 20 }}
+            />
+            <motion.div
+              className="flex-1 bg-[#34C759] rounded-t-sm"
+              initial={{ height: 0 }}
+              animate={{ height: `${(b.responses / peak) * 100}%` }}
+              transition={{ delay: i * 0.01, type: "spring", stiffness: 120, damping: 20 }}
+            />
+          </div>
+        ))}
+      </div>
+      <div className="flex gap-3 text-[11px] text-[var(--fg-secondary)] mb-3">
+        <span className="flex items-center gap-1"><span className="w-2 h-2 rounded-sm bg-[#007AFF]" />Sent</span>
+        <span className="flex items-center gap-1"><span className="w-2 h-2 rounded-sm bg-[#34C759]" />Replies</span>
+      </div>
+      <div className="grid grid-cols-4 gap-2 text-center mb-3">
+        {[
+          { label: "Sent", value: String(t.applications) },
+          { label: "Response rate", value: percent(t.response_rate) },
+          { label: "Interviews+", value: percent(t.success_rate) },
+          { label: "Time to reply", value: duration(t.median_response_hours) },
+        ].map(s => (
+          <div key={s.label}>
+            <p className="text-lg font-semibold text-[var(--fg)]">{s.value}</p>
+            <p className="text-[11px] text-[var(--fg-secondary)]">{s.label}</p>
+          </div>
+        ))}
+      </div>
+      {stats.boards.length > 0 && (
+        <table className="w-full text-xs">
+          <thead>
+            <tr className="text-[var(--fg-secondary)] text-left">
+              <th className="font-medium py-1">Board</th>
+              <th className="font-medium py-1 text-right">Sent</th>
+              <th className="font-medium py-1 text-right">Replied</th>
+              <th className="font-medium py-1 text-right">Interviews+</th>
+              <th className="font-medium py-1 text-right">Reply time</th>
+            </tr>
+          </thead>
+          <tbody>
+            {stats.boards.map(b => (
+              <tr key={b.site} className="text-[var(--fg)] border-t border-white/5">
+                <td className="py-1">{b.site}</td>
+                <td className="py-1 text-right">{b.applications}</td>
+                <td className="py-1 text-right">{percent(b.response_rate)}</td>
+                <td className="py-1 text-right">{percent(b.success_rate)}</td>
+                <td className="py-1 text-right">{duration(b.median_response_hours)}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+    </Card>
+  )
+}
@@ -0,0 +1,78 @@
+"use client"
+import { useEffect, useState } from "react"
+import { motion, AnimatePresence } from "motion/react"
+import { ClipboardCheck, X } from "lucide-react"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { Button } from "@/components/ui/button"
+import { toast } from "@/lib/toast"
+
+type Intake = { outcome: "added"; job_id: number; new: boolean } | { outcome: "queued" }
+
+// A job link the desktop shell saw copied (clipwatch.rs), with Add and
+// Dismiss. Hidden while there's none.
+export function ClipboardIntake() {
+  const [url, setUrl] = useState<string | null>(null)
+  const [adding, setAdding] = useState(false)
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    invoke<string | null>("get_clipboard_job").then(setUrl).catch(() => {})
+    const unlisten = listen<string | null>("clipboard-job", e => setUrl(e.payload))
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const add = async () => {
+    if (!url) return
+    setAdding(true)
+    try {
+      const intake = await invoke<Intake>("accept_clipboard_job", { url })
+      if (intake.outcome === "queued") toast.info("Added once the engine is back")
+      else toast.success(intake.new ? "Job added" : "This job was already in your list")
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setAdding(false)
+    }
+  }
+
+  return (
+    <AnimatePresence>
+      {url && (
+        <motion.div
+          initial={{ opacity: 0, y: -8 }}
+          animate={{ opacity: 1, y: 0 }}
+          exit={{ opacity: 0, y: -8 }}
+          className="fixed top-4 right-4 z-[90] w-80 bg-[#1C1C1E] border border-white/10 rounded-2xl p-3 shadow-xl"
+        >
+          <div className="flex items-start gap-2">
+            <ClipboardCheck size={16} className="text-[#007AFF] mt-0.5 shrink-0" />
+            <div className="min-w-0 flex-1">
+              <p className="text-sm font-semibold text-white">Add to JobBot?</p>
+              <p className="text-xs text-[#8E8E93] truncate" title={url}>
+                {url}
+              </p>
+            </div>
+            <button
+              onClick={() => invoke("dismiss_clipboard_job").catch(() => {})}
+              className="text-[#8E8E93] hover:text-white"
+              aria-label="Dismiss"
+            >
+              <X size={14} />
+            </button>
+          </div>
+          <div className="flex justify-end gap-2 mt-3">
+            <Button size="sm" variant="outline" onClick={() => invoke("dismiss_clipboard_job").catch(() => {})}>
+              Dismiss
+            </Button>
+            <Button size="sm" loading={adding} onClick={add}>
+              Add
+            </Button>
+          </div>
+        </motion.div>
+      )}
+    </AnimatePresence>
+  )
+}
@@ -0,0 +1,59 @@
+"use client"
+import { useEffect, useState } from "react"
+import { usePathname } from "next/navigation"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { getCurrentWindow } from "@tauri-apps/api/window"
+
+interface Metrics {
+  uptime_secs: number
+  engine_starts: number
+  backend_memory_bytes: number | null
+  events_per_minute: number
+}
+
+const REFRESH_MS = 2_000
+
+// Shown while the shell's debug mode is on: which window and page this is,
+// and a few live numbers. Secondary windows can't read the metrics and show
+// only the first two.
+export function DebugOverlay() {
+  const pathname = usePathname()
+  const [on, setOn] = useState(false)
+  const [label, setLabel] = useState("")
+  const [metrics, setMetrics] = useState<Metrics | null>(null)
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    setLabel(getCurrentWindow().label)
+    invoke<boolean>("get_debug_mode").then(setOn).catch(() => {})
+    const unlisten = listen<boolean>("debug-mode-changed", e => setOn(e.payload))
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  useEffect(() => {
+    if (!on) return
+    const load = () => invoke<Metrics>("get_metrics").then(setMetrics).catch(() => {})
+    load()
+    const timer = setInterval(load, REFRESH_MS)
+    return () => clearInterval(timer)
+  }, [on])
+
+  if (!on) return null
+  return (
+    <div className="fixed bottom-2 right-2 z-[100] pointer-events-none rounded-lg bg-black/80 border border-amber-400/40 px-2.5 py-1.5 text-[10px] font-mono text-amber-200 space-y-0.5">
+      <div>
+        debug · {label} · {pathname}
+      </div>
+      {metrics && (
+        <div>
+          up {Math.floor(metrics.uptime_secs / 60)}m · engine starts {metrics.engine_starts} · engine{" "}
+          {metrics.backend_memory_bytes === null ? "—" : `${Math.round(metrics.backend_memory_bytes / 1048576)} MB`} ·{" "}
+          {metrics.events_per_minute} ev/min
+        </div>
+      )}
+    </div>
+  )
+}
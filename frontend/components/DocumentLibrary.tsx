@@ -0,0 +1,235 @@
+"use client"
+import { useEffect, useState, useCallback } from "react"
+import { motion, AnimatePresence } from "motion/react"
+import { FileText, Plus, Archive, ArchiveRestore, History, ChevronDown } from "lucide-react"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { toast } from "@/lib/toast"
+import { Button } from "@/components/ui/button"
+import { cn } from "@/lib/utils"
+
+type DocumentKind = "resume" | "cover_letter"
+
+interface DocumentVersion {
+  version: number
+  sha256: string
+  file_name: string
+  size: number
+  added_at: string
+}
+
+interface LibraryDocument {
+  id: string
+  kind: DocumentKind
+  name: string
+  tags: string[]
+  target_role: string | null
+  created_at: string
+  retired_at: string | null
+  versions: DocumentVersion[]
+}
+
+const KIND_LABELS: Record<DocumentKind, string> = {
+  resume: "CV",
+  cover_letter: "Carta de presentación",
+}
+
+// The desktop shell's library (documents.rs): versioned CVs and cover letters
+// the engine can refer to by id. Only in the desktop app.
+export function DocumentLibrary() {
+  const [isTauriApp, setIsTauriApp] = useState(false)
+  const [documents, setDocuments] = useState<LibraryDocument[]>([])
+  const [showRetired, setShowRetired] = useState(false)
+  const [form, setForm] = useState<{ kind: DocumentKind; name: string; target_role: string; tags: string } | null>(null)
+  const [adding, setAdding] = useState(false)
+
+  const load = useCallback(() => {
+    invoke<LibraryDocument[]>("list_documents", { includeRetired: showRetired })
+      .then(setDocuments)
+      .catch(() => {})
+  }, [showRetired])
+
+  useEffect(() => {
+    const tauri = "__TAURI_INTERNALS__" in window
+    setIsTauriApp(tauri)
+    if (!tauri) return
+    load()
+    const unlisten = listen("documents-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [load])
+
+  if (!isTauriApp) return null
+
+  const add = async () => {
+    if (!form?.name.trim()) return
+    setAdding(true)
+    try {
+      const added = await invoke<{ document: LibraryDocument; duplicate: boolean } | null>("add_document", {
+        path: null,
+        documentId: null,
+        metadata: {
+          kind: form.kind,
+          name: form.name,
+          target_role: form.target_role || null,
+          tags: form.tags.split(","),
+        },
+      })
+      if (!added) return
+      if (added.duplicate) toast.info(`Ya está en la biblioteca como «${added.document.name}»`)
+      setForm(null)
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setAdding(false)
+    }
+  }
+
+  const addVersion = async (doc: LibraryDocument) => {
+    try {
+      const added = await invoke<{ document: LibraryDocument; duplicate: boolean } | null>("add_document", {
+        path: null,
+        documentId: doc.id,
+        metadata: null,
+      })
+      if (added?.duplicate) toast.info("Es el mismo archivo que la versión actual")
+    } catch (e) {
+      toast.error(String(e))
+    }
+  }
+
+  const retire = (doc: LibraryDocument, retired: boolean) => {
+    invoke("retire_document", { id: doc.id, retired }).catch(e => toast.error(String(e)))
+  }
+
+  return (
+    <div>
+      <div className="flex items-center justify-between mb-3">
+        <div className="flex items-center gap-2">
+          <FileText className="h-4 w-4 text-[#007AFF]" />
+          <h2 className="text-base font-semibold text-white">Biblioteca de documentos</h2>
+          <span className="text-xs text-[#8E8E93]">({documents.length})</span>
+        </div>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={() => setShowRetired(v => !v)}
+            className={cn("text-xs", showRetired ? "text-[#007AFF]" : "text-[#8E8E93]")}
+          >
+            {showRetired ? "Ocultar retirados" : "Mostrar retirados"}
+          </button>
+          <Button size="sm" onClick={() => setForm({ kind: "resume", name: "", target_role: "", tags: "" })}>
+            <Plus className="h-3.5 w-3.5" />
+            Añadir
+          </Button>
+        </div>
+      </div>
+
+      <AnimatePresence>
+        {form && (
+          <motion.div
+            initial={{ opacity: 0, height: 0 }}
+            animate={{ opacity: 1, height: "auto" }}
+            exit={{ opacity: 0, height: 0 }}
+            className="overflow-hidden"
+          >
+            <div className="grid grid-cols-2 gap-2 bg-white/5 border border-white/10 rounded-xl p-3 mb-3">
+              <div className="relative">
+                <select
+                  value={form.kind}
+                  onChange={e => setForm(f => f && { ...f, kind: e.target.value as DocumentKind })}
+                  className="w-full appearance-none bg-white/5 border border-white/10 rounded-xl px-3 py-2 pr-7 text-sm text-white outline-none"
+                >
+                  <option value="resume" className="bg-[#2C2C2E]">CV</option>
+                  <option value="cover_letter" className="bg-[#2C2C2E]">Carta de presentación</option>
+                </select>
+                <ChevronDown className="h-3 w-3 text-[#8E8E93] absolute right-2 top-1/2 -translate-y-1/2 pointer-events-none" />
+              </div>
+              <input
+                value={form.name}
+                onChange={e => setForm(f => f && { ...f, name: e.target.value })}
+                placeholder="Nombre"
+                className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <input
+                value={form.target_role}
+                onChange={e => setForm(f => f && { ...f, target_role: e.target.value })}
+                placeholder="Puesto objetivo (opcional)"
+                className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <input
+                value={form.tags}
+                onChange={e => setForm(f => f && { ...f, tags: e.target.value })}
+                placeholder="Etiquetas, separadas por comas"
+                className="bg-white/5 border border-white/10 rounded-xl px-3 py-2 text-sm text-white placeholder:text-[#8E8E93] outline-none"
+              />
+              <Button variant="outline" onClick={() => setForm(null)}>
+                Cancelar
+              </Button>
+              <Button loading={adding} disabled={!form.name.trim()} onClick={add}>
+                Elegir archivo…
+              </Button>
+            </div>
+          </motion.div>
+        )}
+      </AnimatePresence>
+
+      {documents.length === 0 ? (
+        <p className="text-xs text-[#8E8E93] px-1">
+          Guarda aquí tus CV y cartas de presentación. Cada archivo nuevo de un documento se conserva como una versión.
+        </p>
+      ) : (
+        <div className="space-y-2">
+          {documents.map(doc => {
+            const current = doc.versions[doc.versions.length - 1]
+            return (
+              <div
+                key={doc.id}
+                className={cn(
+                  "flex items-center justify-between bg-white/5 border border-white/10 rounded-xl px-4 py-3",
+                  doc.retired_at && "opacity-50"
+                )}
+              >
+                <div className="min-w-0">
+                  <p className="text-sm font-medium text-white truncate">
+                    {doc.name}
+                    <span className="text-xs text-[#8E8E93] font-normal"> · {KIND_LABELS[doc.kind]}</span>
+                  </p>
+                  <p className="text-xs text-[#8E8E93] truncate">
+                    {current?.file_name} · v{current?.version}
+                    {doc.target_role && ` · ${doc.target_role}`}
+                  </p>
+                  {doc.tags.length > 0 && (
+                    <div className="flex flex-wrap gap-1 mt-1">
+                      {doc.tags.map(tag => (
+                        <span key={tag} className="text-[11px] text-[#8E8E93] bg-white/5 px-2 py-0.5 rounded-full">
+                          {tag}
+                        </span>
+                      ))}
+                    </div>
+                  )}
+                </div>
+                <div className="flex items-center gap-1 shrink-0">
+                  <button
+                    onClick={() => addVersion(doc)}
+                    className="text-[#8E8E93] hover:text-white p-1.5 rounded-lg hover:bg-white/5"
+                    title="Nueva versión"
+                  >
+                    <History className="h-3.5 w-3.5" />
+                  </button>
+                  <button
+                    onClick={() => retire(doc, !doc.retired_at)}
+                    className="text-[#8E8E93] hover:text-white p-1.5 rounded-lg hover:bg-white/5"
+                    title={doc.retired_at ? "Recuperar" : "Retirar"}
+                  >
+                    {doc.retired_at ? <ArchiveRestore className="h-3.5 w-3.5" /> : <Archive className="h-3.5 w-3.5" />}
+                  </button>
+                </div>
+              </div>
+            )
+          })}
+        </div>
+      )}
+    </div>
+  )
+}
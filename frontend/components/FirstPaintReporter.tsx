@@ -0,0 +1,17 @@
+"use client"
+import { useEffect } from "react"
+import { invoke } from "@tauri-apps/api/core"
+
+// Tells the shell when the first frame is on screen, for the startup timings
+// on the diagnostics page. Only the main window's report is kept.
+export function FirstPaintReporter() {
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const frame = requestAnimationFrame(() => {
+      invoke("mark_first_paint").catch(() => {})
+    })
+    return () => cancelAnimationFrame(frame)
+  }, [])
+
+  return null
+}
@@ -0,0 +1,63 @@
+"use client"
+import { useEffect } from "react"
+
+type InvokeFn = (cmd: string, args?: unknown, options?: unknown) => Promise<unknown>
+
+interface Call {
+  command: string
+  ms: number
+  error: string | null
+}
+
+const RECORD = "record_ipc_calls"
+const FLUSH_MS = 5_000
+const MAX_BATCH = 50
+
+// Times every command call as this page waits for it and passes the results
+// to the shell in batches, for the logs, the per-command latency table on the
+// diagnostics page and (when switched on) error reporting.
+export function IpcMonitor() {
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const internals = (window as unknown as { __TAURI_INTERNALS__: { invoke: InvokeFn } })
+      .__TAURI_INTERNALS__
+    const original = internals.invoke
+    let pending: Call[] = []
+
+    const flush = () => {
+      if (pending.length === 0) return
+      const calls = pending
+      pending = []
+      original(RECORD, { calls }).catch(() => {})
+    }
+    const note = (call: Call) => {
+      pending.push(call)
+      if (pending.length >= MAX_BATCH) flush()
+    }
+
+    internals.invoke = (cmd, args, options) => {
+      if (cmd === RECORD) return original(cmd, args, options)
+      const started = performance.now()
+      return original(cmd, args, options).then(
+        value => {
+          note({ command: cmd, ms: performance.now() - started, error: null })
+          return value
+        },
+        e => {
+          note({ command: cmd, ms: performance.now() - started, error: String(e) })
+          throw e
+        }
+      )
+    }
+    const timer = setInterval(flush, FLUSH_MS)
+    window.addEventListener("pagehide", flush)
+    return () => {
+      flush()
+      clearInterval(timer)
+      window.removeEventListener("pagehide", flush)
+      internals.invoke = original
+    }
+  }, [])
+
+  return null
+}
@@ -0,0 +1,157 @@
+"use client"
+import { useEffect, useState } from "react"
+import { motion, AnimatePresence } from "motion/react"
+import { CloudOff, AlertTriangle, X, RefreshCw } from "lucide-react"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { toast } from "@/lib/toast"
+import { cn } from "@/lib/utils"
+
+interface QueuedAction {
+  id: string
+  label: string
+  method: string
+  path: string
+  queued_at: string
+  attempts: number
+  last_error: string | null
+}
+
+interface Conflict {
+  action: QueuedAction
+  status: number
+  detail: string
+  at: string
+}
+
+interface Queue {
+  actions: QueuedAction[]
+  conflicts: Conflict[]
+}
+
+// Changes the desktop shell is holding until the engine is back, and the
+// ones it refused on replay (offlinequeue.rs). Hidden while there are none.
+export function OfflineQueue() {
+  const [queue, setQueue] = useState<Queue>({ actions: [], conflicts: [] })
+  const [open, setOpen] = useState(false)
+  const [replaying, setReplaying] = useState(false)
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const load = () => invoke<Queue>("get_offline_queue").then(setQueue).catch(() => {})
+    load()
+    const unlisten = listen("offline-queue-changed", load)
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [])
+
+  const pending = queue.actions.length
+  const conflicts = queue.conflicts.length
+  if (pending === 0 && conflicts === 0) return null
+
+  const replay = async () => {
+    setReplaying(true)
+    try {
+      const report = await invoke<{ sent: number; conflicts: number; remaining: number }>("replay_offline_queue")
+      if (report.remaining > 0) toast.info("The engine still isn't answering")
+    } catch (e) {
+      toast.error(String(e))
+    } finally {
+      setReplaying(false)
+    }
+  }
+
+  return (
+    <div className="fixed bottom-4 left-20 z-[90]">
+      <AnimatePresence>
+        {open && (
+          <motion.div
+            initial={{ opacity: 0, y: 8 }}
+            animate={{ opacity: 1, y: 0 }}
+            exit={{ opacity: 0, y: 8 }}
+            className="mb-2 w-80 max-h-96 overflow-y-auto bg-[#1C1C1E] border border-white/10 rounded-2xl p-3 shadow-xl space-y-3"
+          >
+            {pending > 0 && (
+              <div>
+                <div className="flex items-center justify-between mb-1.5">
+                  <p className="text-xs font-semibold text-white">Waiting for the engine</p>
+                  <button
+                    onClick={replay}
+                    disabled={replaying}
+                    className="text-[#8E8E93] hover:text-white p-1 rounded-lg hover:bg-white/5"
+                    title="Send now"
+                  >
+                    <RefreshCw className={cn("h-3.5 w-3.5", replaying && "animate-spin")} />
+                  </button>
+                </div>
+                {queue.actions.map(a => (
+                  <div key={a.id} className="flex items-start justify-between gap-2 py-1">
+                    <div className="min-w-0">
+                      <p className="text-xs text-white truncate">{a.label}</p>
+                      <p className="text-[11px] text-[#8E8E93]">
+                        {new Date(a.queued_at).toLocaleTimeString()}
+                        {a.last_error && ` · ${a.last_error}`}
+                      </p>
+                    </div>
+                    <button
+                      onClick={() => invoke("discard_offline_action", { id: a.id }).catch(e => toast.error(String(e)))}
+                      className="text-[#8E8E93] hover:text-white p-0.5"
+                      title="Discard"
+                    >
+                      <X className="h-3 w-3" />
+                    </button>
+                  </div>
+                ))}
+              </div>
+            )}
+            {conflicts > 0 && (
+              <div>
+                <div className="flex items-center justify-between mb-1.5">
+                  <p className="text-xs font-semibold text-[#FF9500]">Not applied</p>
+                  <button
+                    onClick={() => invoke("dismiss_offline_conflict", { id: null }).catch(e => toast.error(String(e)))}
+                    className="text-[11px] text-[#8E8E93] hover:text-white"
+                  >
+                    Dismiss all
+                  </button>
+                </div>
+                {queue.conflicts.map(c => (
+                  <div key={c.action.id} className="flex items-start justify-between gap-2 py-1">
+                    <div className="min-w-0">
+                      <p className="text-xs text-white truncate">{c.action.label}</p>
+                      <p className="text-[11px] text-[#8E8E93]">{c.detail}</p>
+                    </div>
+                    <button
+                      onClick={() =>
+                        invoke("dismiss_offline_conflict", { id: c.action.id }).catch(e => toast.error(String(e)))
+                      }
+                      className="text-[#8E8E93] hover:text-white p-0.5"
+                      title="Dismiss"
+                    >
+                      <X className="h-3 w-3" />
+                    </button>
+                  </div>
+                ))}
+              </div>
+            )}
+          </motion.div>
+        )}
+      </AnimatePresence>
+      <button
+        onClick={() => setOpen(v => !v)}
+        className={cn(
+          "flex items-center gap-2 px-3 py-1.5 rounded-full text-xs font-medium border shadow-lg",
+          conflicts > 0
+            ? "bg-[#FF9500]/10 border-[#FF9500]/30 text-[#FF9500]"
+            : "bg-white/5 border-white/10 text-[#8E8E93]"
+        )}
+      >
+        {conflicts > 0 ? <AlertTriangle className="h-3.5 w-3.5" /> : <CloudOff className="h-3.5 w-3.5" />}
+        {pending > 0 && `${pending} waiting`}
+        {pending > 0 && conflicts > 0 && " · "}
+        {conflicts > 0 && `${conflicts} not applied`}
+      </button>
+    </div>
+  )
+}
@@ -0,0 +1,29 @@
+"use client"
+import { useEffect } from "react"
+import { useRouter } from "next/navigation"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+
+// Menus outside the window (the macOS Dock menu's recent applications) and
+// clicked notifications ask the main window to show a page. One asked for
+// before this listened, while the window was loading, is picked up on mount.
+export function RouteListener() {
+  const router = useRouter()
+
+  useEffect(() => {
+    if (!("__TAURI_INTERNALS__" in window)) return
+    const takePending = () => invoke<string | null>("take_pending_route").catch(() => null)
+    takePending().then(route => {
+      if (route) router.push(route)
+    })
+    const unlisten = listen<string>("open-route", e => {
+      router.push(e.payload)
+      takePending()
+    })
+    return () => {
+      unlisten.then(f => f())
+    }
+  }, [router])
+
+  return null
+}
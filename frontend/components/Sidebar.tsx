@@ -2,7 +2,7 @@
 import { useEffect, useState } from "react"
 import Link from "next/link"
 import { usePathname } from "next/navigation"
-import { LayoutGrid, Briefcase, Kanban, FileText, Settings, Bot } from "lucide-react"
+import { LayoutGrid, Briefcase, Kanban, FileText, Settings, Bot, History } from "lucide-react"
 import { cn } from "@/lib/utils"
 import { motion } from "motion/react"
 import { api, createSSEConnection } from "@/lib/api"
@@ -12,6 +12,8 @@ const nav = [
   { href: "/",             icon: LayoutGrid, label: "Dashboard" },
   { href: "/jobs",         icon: Briefcase,  label: "Jobs" },
   { href: "/applications", icon: Kanban,     label: "Applications" },
+  // The shell's own record, so only in the desktop app
+  { href: "/history",      icon: History,    label: "History", desktop: true },
   { href: "/cv",           icon: FileText,   label: "CV Profiles" },
   { href: "/settings",     icon: Settings,   label: "Settings" },
 ]
@@ -19,8 +21,10 @@ const nav = [
 export function Sidebar() {
   const pathname = usePathname()
   const [pendingCount, setPendingCount] = useState(0)
+  const [isTauriApp, setIsTauriApp] = useState(false)
 
   useEffect(() => {
+    setIsTauriApp("__TAURI_INTERNALS__" in window)
     const handleFirstInteraction = () => {
       unlockAudio()
       document.removeEventListener("pointerdown", handleFirstInteraction)
@@ -55,7 +59,7 @@ export function Sidebar() {
           <Bot className="h-4 w-4 text-white" />
         </div>
       </div>
-      {nav.map(({ href, icon: Icon, label }) => {
+      {nav.filter(item => isTauriApp || !item.desktop).map(({ href, icon: Icon, label }) => {
         const active = pathname === href
         return (
           <Link key={href} href={href} title={label} className="relative">
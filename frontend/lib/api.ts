@@ -1,3 +1,6 @@
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+import { toast } from "./toast"
 import type {
   SystemHealth,
   Job,
@@ -9,17 +12,62 @@ import type {
   CVSource,
 } from "./types"
 
-const BASE = "http://localhost:8000"
+// The desktop shell injects the backend URL when JOBBOT_BACKEND_PORT is set
+export const BASE: string =
+  (typeof window !== "undefined" &&
+    (window as { __JOBBOT_BACKEND_URL__?: string }).__JOBBOT_BACKEND_URL__) ||
+  "http://localhost:8000"
+
+// Per-launch secret from the desktop shell; the backend rejects requests
+// without it. Absent when running against a backend started from source.
+const TOKEN: string | undefined =
+  typeof window !== "undefined"
+    ? (window as { __JOBBOT_BACKEND_TOKEN__?: string }).__JOBBOT_BACKEND_TOKEN__
+    : undefined
+
+export const authHeaders = (): Record<string, string> =>
+  TOKEN ? { "X-JobBot-Token": TOKEN } : {}
+
+// For URLs used where headers can't be set (EventSource, <img src>)
+export function withToken(url: string): string {
+  if (!TOKEN) return url
+  return `${url}${url.includes("?") ? "&" : "?"}token=${encodeURIComponent(TOKEN)}`
+}
 
 async function request<T>(path: string, options?: RequestInit): Promise<T> {
   const res = await fetch(`${BASE}${path}`, {
-    headers: { "Content-Type": "application/json", ...options?.headers },
     ...options,
+    headers: { "Content-Type": "application/json", ...authHeaders(), ...options?.headers },
   })
   if (!res.ok) throw new Error(`${res.status} ${res.statusText}: ${path}`)
   return res.json()
 }
 
+// What a change resolves to when the engine was unreachable and the desktop
+// shell is holding it to send once it's back (offlinequeue.rs)
+export type Queued = { queued: true }
+
+export const isQueued = (result: unknown): result is Queued =>
+  typeof result === "object" && result !== null && (result as Queued).queued === true
+
+// For the changes the shell accepts in its queue (QUEUEABLE in offlinequeue.rs)
+async function queueable<T>(label: string, path: string, options: RequestInit): Promise<T | Queued> {
+  try {
+    return await request<T>(path, options)
+  } catch (e) {
+    // fetch only rejects with a TypeError when nothing answered at all
+    if (!(e instanceof TypeError) || typeof window === "undefined" || !("__TAURI_INTERNALS__" in window)) throw e
+    await invoke("queue_offline_action", {
+      label,
+      method: options.method,
+      path,
+      body: typeof options.body === "string" ? JSON.parse(options.body) : null,
+    })
+    toast.info(`${label}: queued until the engine is back`)
+    return { queued: true }
+  }
+}
+
 export const api = {
   getHealth: () => request<SystemHealth>("/api/health"),
   getJobs: (params?: { cursor?: number; limit?: number; site?: string; status?: string; cv_profile?: string; search?: string }) => {
@@ -41,15 +89,25 @@ export const api = {
   },
   getApplicationCounts: () => request<Record<string, number>>("/api/applications/counts"),
   getPendingReviews: () => request<{ items: Application[]; count: number }>("/api/applications/pending-reviews"),
-  authorizeApplication: (id: number) => request<{ status: string }>(`/api/applications/${id}/authorize`, { method: "POST" }),
-  rejectApplication: (id: number) => request<{ status: string }>(`/api/applications/${id}/reject`, { method: "POST" }),
+  // Through the desktop shell, which holds it back while a daily quota or the minimum delay applies
+  authorizeApplication: (id: number) =>
+    typeof window !== "undefined" && "__TAURI_INTERNALS__" in window
+      ? invoke("authorize_application", { id }).then(() => ({ status: "authorized" }))
+      : request<{ status: string }>(`/api/applications/${id}/authorize`, { method: "POST" }),
+  rejectApplication: (id: number) =>
+    queueable<{ status: string }>(`Reject application #${id}`, `/api/applications/${id}/reject`, { method: "POST" }),
   getScraperStatus: () => request<{ scrapers: ScraperStatus[] }>("/api/scrapers/status"),
-  triggerScraper: (site: string) => request<{ status: string; task_id: string }>(`/api/scrapers/${site}/trigger`, { method: "POST" }),
+  triggerScraper: (site: string) =>
+    queueable<{ status: string; task_id: string }>(`Run ${site}`, `/api/scrapers/${site}/trigger`, { method: "POST" }),
   generateCV: (applicationId: number) => request<{ status: string; task_id: string }>(`/api/cv/generate/${applicationId}`, { method: "POST" }),
   getSettings: () => request<Record<string, string>>("/api/settings"),
   updateSettings: (data: Record<string, string>) => request<{ status: string }>("/api/settings", { method: "POST", body: JSON.stringify(data) }),
   getCompanySources: () => request<{ items: CompanySource[] }>("/api/company-sources"),
-  addCompanySource: (data: Partial<CompanySource>) => request<CompanySource>("/api/company-sources", { method: "POST", body: JSON.stringify(data) }),
+  addCompanySource: (data: Partial<CompanySource>) =>
+    queueable<CompanySource>(`Add ${data.company_name ?? "company source"}`, "/api/company-sources", {
+      method: "POST",
+      body: JSON.stringify(data),
+    }),
   listCVSources: () => request<CVSource[]>("/api/cv/sources"),
   deleteCVSource: (id: number) => request<{ status: string; id: number }>(`/api/cv/sources/${id}`, { method: "DELETE" }),
   getSetupStatus: () => request<SetupStatus>("/api/setup/status"),
@@ -58,16 +116,17 @@ export const api = {
   checkOllama: () => request<{ installed: boolean; running: boolean }>("/api/setup/ollama-check"),
   startOllama: () => request<{ status: string }>("/api/setup/start-ollama", { method: "POST" }),
   updateApplicationStatus: (id: number, status: string) =>
-    request<{ status: string; application_id: number }>(`/api/applications/${id}/status`, {
-      method: "PATCH",
-      body: JSON.stringify({ status }),
-    }),
+    queueable<{ status: string; application_id: number }>(
+      `Mark application #${id} as ${status}`,
+      `/api/applications/${id}/status`,
+      { method: "PATCH", body: JSON.stringify({ status }) }
+    ),
 }
 
 export function createSSEConnection(
   onEvent: (event: string, data: unknown) => void
 ): () => void {
-  const es = new EventSource(`${BASE}/api/events`)
+  let es = new EventSource(withToken(`${BASE}/api/events`))
   const handler = (e: MessageEvent) => {
     try { onEvent(e.type, JSON.parse(e.data)) } catch { onEvent(e.type, e.data) }
   }
@@ -82,6 +141,20 @@ export function createSSEConnection(
     "application_rejected",
     "application_status_updated",
   ]
-  events.forEach(evt => es.addEventListener(evt, handler as EventListener))
-  return () => es.close()
+  const subscribe = () => events.forEach(evt => es.addEventListener(evt, handler as EventListener))
+  subscribe()
+  // After the computer sleeps the stream can be open but dead; the desktop
+  // shell says when it woke
+  const unlisten =
+    typeof window !== "undefined" && "__TAURI_INTERNALS__" in window
+      ? listen("system-resumed", () => {
+          es.close()
+          es = new EventSource(withToken(`${BASE}/api/events`))
+          subscribe()
+        })
+      : null
+  return () => {
+    es.close()
+    unlisten?.then(f => f())
+  }
 }
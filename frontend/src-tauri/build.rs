@@ -1,3 +1,38 @@
+use sha2::{Digest, Sha256};
+use std::{env, fs};
+
 fn main() {
+    embed_sidecar_checksum();
+    link_openssl_deps();
     tauri_build::build()
 }
+
+// The shell re-hashes the bundled backend before every launch and compares it
+// with this (see sidecar::verified_binary).
+fn embed_sidecar_checksum() {
+    let target = env::var("TARGET").unwrap();
+    let suffix = if target.contains("windows") {
+        ".exe"
+    } else {
+        ""
+    };
+    let path = format!("binaries/jobbot-backend-{target}{suffix}");
+    println!("cargo:rerun-if-changed={path}");
+    if let Ok(bytes) = fs::read(&path) {
+        let hex: String = Sha256::digest(&bytes)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        println!("cargo:rustc-env=JOBBOT_SIDECAR_SHA256={hex}");
+    }
+}
+
+// SQLCipher links the static OpenSSL from vcpkg on Windows (release.yml),
+// which needs these system libraries.
+fn link_openssl_deps() {
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows") {
+        for lib in ["crypt32", "user32", "advapi32", "ws2_32"] {
+            println!("cargo:rustc-link-lib={lib}");
+        }
+    }
+}
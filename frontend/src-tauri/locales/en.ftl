@@ -0,0 +1,245 @@
+# Strings shown by the desktop shell itself: tray, window titles and
+# notifications. The webview has its own copy.
+
+## Tray
+
+tray-open = Open JobBot
+tray-start-on-login = Start on Login
+tray-start-on-login-checked = ✓  Start on Login
+tray-profile = Profile
+tray-uninstall = Uninstall JobBot...
+tray-quit = Quit
+tray-debug-mode = Debug Mode
+tray-developer = Developer
+tray-dev-logs = Open Logs Folder
+tray-dev-restart-engine = Restart Engine
+tray-dev-reload = Reload Window
+tray-dev-devtools = Open Web Inspector
+tray-update-ready = Update ready — see what's new...
+automation-pause = Pause Automation
+automation-run-now = Run Now
+automation-recent = Recent Applications
+automation-resume = Resume Automation
+app-menu-settings = Settings…
+app-menu-edit = Edit
+app-menu-window = Window
+app-menu-credits = Local-first job application automation.
+jumplist-dashboard = Open Dashboard
+jumplist-new-application = New Application
+
+## Windows
+
+window-onboarding = Welcome to JobBot
+window-release-notes = What's new in JobBot
+window-uninstall = Uninstall JobBot
+
+## Notifications
+
+uninstall-body = Autolaunch disabled. macOS: move JobBot.app to Trash. Windows: use Add/Remove Programs.
+
+app-lock-reason = unlock JobBot
+
+digest-title = JobBot — Daily summary
+digest-body = Today: { $submitted ->
+        [one] { $submitted } application
+       *[other] { $submitted } applications
+    } submitted, { $review ->
+        [one] { $review } needs
+       *[other] { $review } need
+    } review, { $interviews ->
+        [one] { $interviews } interview
+       *[other] { $interviews } interviews
+    } scheduled
+digest-activity = Time on the search: { $focus } in JobBot, { $runs ->
+        [one] { $runs } run
+       *[other] { $runs } runs
+    } ({ $run_time })
+activity-duration = { $hours } h { $minutes } min
+
+submitted-title = Application Submitted
+submitted-body = ✓ Application #{ $id } submitted
+submitted-summary-title = Applications Submitted
+submitted-summary-body = { $count } more applications submitted in the last { $minutes } minutes
+
+review-ready-title = Review Required
+review-ready-summary-body = { $count } more applications are waiting for your review
+
+review-expiring-title = Session Expiring
+review-expiring-body = Form session expires in { $minutes } minutes. Review now.
+review-expiring-summary-title = Sessions Expiring
+review-expiring-summary-body = { $count } more review sessions are about to expire
+
+scraper-error-title = Scraper Error
+scraper-error-summary-title = Scraper Errors
+scraper-error-summary-body = { $count } more scraper errors in the last { $minutes } minutes
+
+job-alert-title = Job alert: { $search }
+job-alert-summary-title = Job Alerts
+job-alert-summary-body = { $count } more jobs matched your alerts in the last { $minutes } minutes
+
+progress-default-label = Running
+progress-status = { $label } { $current }/{ $total }…
+progress-finished = { $label } finished: { $succeeded }/{ $total } done
+progress-finished-with-failures = { $label } finished: { $succeeded }/{ $total } done, { $failed } failed
+keep-awake-reason = Automation run in progress
+
+engine-updated-title = Engine updated
+engine-updated-body = JobBot engine { $version } is now running.
+engine-integrity-title = Engine not started
+engine-integrity-body = The JobBot engine failed its integrity check and was not started. Reinstall JobBot to repair it.
+engine-update-integrity-body = The downloaded engine { $version } failed its integrity check; using the built-in one instead.
+
+update-critical-title = Important update
+update-critical-body = JobBot { $version } contains a critical fix. Please restart to apply it.
+update-ready-title = Update ready
+update-ready-body = JobBot { $version } has been downloaded. Restart to apply.
+
+settings-title = Settings
+settings-invalid-digest-time = Daily digest time "{ $time }" was invalid and has been reset to 20:00.
+settings-invalid-proxy = The manual proxy had no host or port; using the system proxy instead.
+settings-invalid-mail = Watching the mailbox needs a server, port and user; it has been turned off.
+settings-invalid-redaction-pattern = The log redaction pattern "{ $pattern }" is not a valid regular expression and was dropped.
+settings-corrupt = Your settings could not be read and were reset. The old file was kept as { $path }.
+
+sync-conflict-title = Settings sync conflict
+sync-conflict-body = { $count ->
+        [one] A setting was
+       *[other] { $count } settings were
+    } changed differently on another machine. Choose which to keep in Settings.
+
+crash-title = JobBot closed unexpectedly
+crash-body = { $count ->
+        [one] A crash report was
+       *[other] { $count } crash reports were
+    } saved. Settings → Desktop → Crash reports has the details.
+
+## Import summary
+
+import-secrets-skipped = Saved passwords were skipped (no passphrase given).
+import-secret-not-encrypted = Could not encrypt { $name }: { $error }
+import-secret-not-stored = Could not store { $name } in the keychain: { $error }
+import-engine-settings-failed = Engine settings were not imported: { $error }
+import-company-source-failed = A company source was not imported: { $error }
+
+## Spreadsheet export
+
+export-applications-title = Export applications
+export-sheet-name = Applications
+export-column-id = ID
+export-column-company = Company
+export-column-role = Role
+export-column-url = Link
+export-column-status = Status
+export-column-cv-profile = CV profile
+export-column-created-at = Created
+export-column-updated-at = Updated
+export-column-authorized-at = Authorized
+export-column-submitted-at = Submitted
+
+## Calendar
+
+calendar-name = currobot interviews
+calendar-export-title = Export to calendar
+calendar-interview = Interview
+calendar-interview-with = Interview with { $company }
+calendar-follow-up = Follow up on an application
+calendar-follow-up-with = Follow up with { $company }
+
+## Documents
+
+documents-add-title = Add a résumé or cover letter
+documents-filter = Documents
+
+## PDF documents
+
+pdf-save-title = Save PDF
+pdf-date-long = %B %-d, %Y
+pdf-date-short = %b %-d
+pdf-page = { $page } / { $pages }
+pdf-letter-title = Cover letter
+pdf-letter-title-for = Cover letter for { $company }
+pdf-letter-subject = Application for { $role }
+templates-export-title = Export cover letter templates
+templates-import-title = Import cover letter templates
+pdf-report-title = Weekly application report
+pdf-report-week = { $from } – { $to }
+pdf-report-generated = Generated on { $date }
+pdf-report-summary = Summary
+pdf-report-added = Applications added: { $count }
+pdf-report-submitted = Applications submitted: { $count }
+pdf-report-changes = Status changes: { $count }
+pdf-report-events = Interviews and reminders: { $count }
+pdf-report-applications = Applications this week
+pdf-report-status-changes = Status changes
+pdf-report-calendar = Interviews and reminders
+pdf-report-nothing = Nothing this week.
+pdf-column-date = Date
+pdf-column-when = When
+pdf-column-event = Event
+pdf-report-activity = Time spent
+pdf-report-focus = In JobBot: { $time }
+pdf-report-runs = Automated runs: { $count } ({ $time })
+pdf-column-focus = In JobBot
+pdf-column-runs = Runs
+pdf-column-run-time = Run time
+
+## Offline queue
+
+offline-conflicts-title = Some changes couldn't be applied
+offline-conflicts-body = { $count } changes made while the engine was offline were refused. Open currobot to review them.
+
+## Mail
+
+mail-confirmation-title = { $company } received your application
+mail-interview-title = Interview request from { $company }
+mail-offer-title = Offer from { $company }
+mail-rejection-title = { $company } replied: not moving forward
+
+## Sign-in
+
+oauth-page-done = You're signed in to currobot
+oauth-page-failed = The sign-in didn't go through
+oauth-page-close = You can close this tab and go back to the app.
+oauth-required-title = Sign in to { $provider }
+oauth-required-body = The engine needs you to sign in again. Open Settings to do it.
+
+## History import
+
+history-import-title = Import applications from LinkedIn or Indeed
+history-import-filter = Data export (ZIP or CSV)
+
+## Duplicates
+
+duplicate-title = You may have applied to { $company } already
+duplicate-body = A similar application from { $date } is { $status }. Check it before submitting.
+
+## Reminders
+
+reminder-body = Time to check in on this application.
+reminder-body-role = Time to check in on your application for { $role }.
+reminder-interview-body = Starts at { $time }.
+reminder-interview-body-zone = Starts at { $time } your time, { $their_time } in { $zone }.
+
+## Backups
+
+backup-restore-title = Choose a backup to restore
+backup-filter = currobot backup (ZIP)
+data-export-title = Save all your data
+data-export-filter = currobot data export (ZIP)
+backup-failed-title = Scheduled backup failed
+
+## Quotas
+
+quota-tooltip = { $remaining } of { $limit } applications left today
+quota-tooltip-wait = Next application at { $time }
+working-hours-tooltip = Outside working hours
+working-hours-tooltip-override = Outside working hours, running anyway
+tray-working-hours-override = Run outside working hours
+tray-working-hours-end = Keep to working hours
+working-hours-refusal = it's outside the working hours
+working-hours-refusal-until = it's outside the working hours; they start again { $start }
+working-hours-start = %a %H:%M
+
+## Clipboard watcher
+
+clipboard-job-title = Add to JobBot?
@@ -0,0 +1,245 @@
+# Textos que muestra la propia aplicación de escritorio: bandeja, títulos de
+# ventana y notificaciones. La interfaz web tiene los suyos.
+
+## Bandeja
+
+tray-open = Abrir JobBot
+tray-start-on-login = Abrir al iniciar sesión
+tray-start-on-login-checked = ✓  Abrir al iniciar sesión
+tray-profile = Perfil
+tray-uninstall = Desinstalar JobBot...
+tray-quit = Salir
+tray-debug-mode = Modo depuración
+tray-developer = Desarrollo
+tray-dev-logs = Abrir carpeta de registros
+tray-dev-restart-engine = Reiniciar motor
+tray-dev-reload = Recargar ventana
+tray-dev-devtools = Abrir inspector web
+tray-update-ready = Actualización lista — ver novedades...
+automation-pause = Pausar automatización
+automation-run-now = Ejecutar ahora
+automation-recent = Candidaturas recientes
+automation-resume = Reanudar automatización
+app-menu-settings = Ajustes…
+app-menu-edit = Edición
+app-menu-window = Ventana
+app-menu-credits = Automatización local de candidaturas de empleo.
+jumplist-dashboard = Abrir panel
+jumplist-new-application = Nueva candidatura
+
+## Ventanas
+
+window-onboarding = Te damos la bienvenida a JobBot
+window-release-notes = Novedades de JobBot
+window-uninstall = Desinstalar JobBot
+
+## Notificaciones
+
+uninstall-body = Inicio automático desactivado. macOS: mueve JobBot.app a la Papelera. Windows: usa Agregar o quitar programas.
+
+app-lock-reason = desbloquear JobBot
+
+digest-title = JobBot — Resumen del día
+digest-body = Hoy: { $submitted ->
+        [one] { $submitted } candidatura enviada
+       *[other] { $submitted } candidaturas enviadas
+    }, { $review ->
+        [one] { $review } pendiente
+       *[other] { $review } pendientes
+    } de revisión, { $interviews ->
+        [one] { $interviews } entrevista programada
+       *[other] { $interviews } entrevistas programadas
+    }
+digest-activity = Tiempo dedicado a la búsqueda: { $focus } en JobBot, { $runs ->
+        [one] { $runs } ejecución
+       *[other] { $runs } ejecuciones
+    } ({ $run_time })
+activity-duration = { $hours } h { $minutes } min
+
+submitted-title = Candidatura enviada
+submitted-body = ✓ Candidatura #{ $id } enviada
+submitted-summary-title = Candidaturas enviadas
+submitted-summary-body = { $count } candidaturas más enviadas en los últimos { $minutes } minutos
+
+review-ready-title = Revisión pendiente
+review-ready-summary-body = { $count } candidaturas más esperan tu revisión
+
+review-expiring-title = La sesión va a caducar
+review-expiring-body = La sesión del formulario caduca en { $minutes } minutos. Revísala ahora.
+review-expiring-summary-title = Sesiones a punto de caducar
+review-expiring-summary-body = { $count } sesiones de revisión más están a punto de caducar
+
+scraper-error-title = Error del scraper
+scraper-error-summary-title = Errores del scraper
+scraper-error-summary-body = { $count } errores más del scraper en los últimos { $minutes } minutos
+
+job-alert-title = Alerta de empleo: { $search }
+job-alert-summary-title = Alertas de empleo
+job-alert-summary-body = { $count } ofertas más coinciden con tus alertas en los últimos { $minutes } minutos
+
+progress-default-label = En curso
+progress-status = { $label } { $current }/{ $total }…
+progress-finished = { $label } terminado: { $succeeded }/{ $total } completados
+progress-finished-with-failures = { $label } terminado: { $succeeded }/{ $total } completados, { $failed } con error
+keep-awake-reason = Automatización en curso
+
+engine-updated-title = Motor actualizado
+engine-updated-body = El motor de JobBot { $version } ya está en marcha.
+engine-integrity-title = Motor no iniciado
+engine-integrity-body = El motor de JobBot no superó la comprobación de integridad y no se ha iniciado. Reinstala JobBot para repararlo.
+engine-update-integrity-body = El motor descargado { $version } no superó la comprobación de integridad; se usa el incluido con la aplicación.
+
+update-critical-title = Actualización importante
+update-critical-body = JobBot { $version } incluye una corrección crítica. Reinicia para aplicarla.
+update-ready-title = Actualización lista
+update-ready-body = JobBot { $version } se ha descargado. Reinicia para aplicarla.
+
+settings-title = Ajustes
+settings-invalid-digest-time = La hora del resumen diario "{ $time }" no era válida y se ha restablecido a las 20:00.
+settings-invalid-proxy = El proxy manual no tenía host o puerto; se usa el proxy del sistema.
+settings-invalid-mail = Vigilar el correo necesita servidor, puerto y usuario; se ha desactivado.
+settings-invalid-redaction-pattern = El patrón de redacción de registros "{ $pattern }" no es una expresión regular válida y se ha descartado.
+settings-corrupt = No se pudieron leer tus ajustes y se han restablecido. El archivo anterior se guardó como { $path }.
+
+sync-conflict-title = Conflicto al sincronizar ajustes
+sync-conflict-body = { $count ->
+        [one] Un ajuste se cambió
+       *[other] { $count } ajustes se cambiaron
+    } de otra forma en otro equipo. Elige cuál conservar en Ajustes.
+
+crash-title = JobBot se cerró inesperadamente
+crash-body = { $count ->
+        [one] Se guardó un informe de fallo.
+       *[other] Se guardaron { $count } informes de fallo.
+    } Los detalles están en Ajustes → Escritorio → Informes de fallos.
+
+## Resumen de importación
+
+import-secrets-skipped = Se omitieron las contraseñas guardadas (no se indicó frase de acceso).
+import-secret-not-encrypted = No se pudo cifrar { $name }: { $error }
+import-secret-not-stored = No se pudo guardar { $name } en el llavero: { $error }
+import-engine-settings-failed = No se importaron los ajustes del motor: { $error }
+import-company-source-failed = No se importó una fuente de empresas: { $error }
+
+## Exportación a hoja de cálculo
+
+export-applications-title = Exportar candidaturas
+export-sheet-name = Candidaturas
+export-column-id = ID
+export-column-company = Empresa
+export-column-role = Puesto
+export-column-url = Enlace
+export-column-status = Estado
+export-column-cv-profile = Perfil de CV
+export-column-created-at = Creada
+export-column-updated-at = Actualizada
+export-column-authorized-at = Autorizada
+export-column-submitted-at = Enviada
+
+## Calendario
+
+calendar-name = Entrevistas de currobot
+calendar-export-title = Exportar al calendario
+calendar-interview = Entrevista
+calendar-interview-with = Entrevista con { $company }
+calendar-follow-up = Hacer seguimiento de una candidatura
+calendar-follow-up-with = Hacer seguimiento con { $company }
+
+## Documentos
+
+documents-add-title = Añadir un currículum o una carta de presentación
+documents-filter = Documentos
+
+## Documentos PDF
+
+pdf-save-title = Guardar PDF
+pdf-date-long = %-d de %B de %Y
+pdf-date-short = %-d %b
+pdf-page = { $page } / { $pages }
+pdf-letter-title = Carta de presentación
+pdf-letter-title-for = Carta de presentación para { $company }
+pdf-letter-subject = Candidatura para { $role }
+templates-export-title = Exportar plantillas de cartas de presentación
+templates-import-title = Importar plantillas de cartas de presentación
+pdf-report-title = Informe semanal de candidaturas
+pdf-report-week = { $from } – { $to }
+pdf-report-generated = Generado el { $date }
+pdf-report-summary = Resumen
+pdf-report-added = Candidaturas añadidas: { $count }
+pdf-report-submitted = Candidaturas enviadas: { $count }
+pdf-report-changes = Cambios de estado: { $count }
+pdf-report-events = Entrevistas y recordatorios: { $count }
+pdf-report-applications = Candidaturas de esta semana
+pdf-report-status-changes = Cambios de estado
+pdf-report-calendar = Entrevistas y recordatorios
+pdf-report-nothing = Nada esta semana.
+pdf-column-date = Fecha
+pdf-column-when = Cuándo
+pdf-column-event = Evento
+pdf-report-activity = Tiempo dedicado
+pdf-report-focus = En JobBot: { $time }
+pdf-report-runs = Ejecuciones automáticas: { $count } ({ $time })
+pdf-column-focus = En JobBot
+pdf-column-runs = Ejecuciones
+pdf-column-run-time = Duración
+
+## Cola sin conexión
+
+offline-conflicts-title = Algunos cambios no se pudieron aplicar
+offline-conflicts-body = El motor rechazó { $count } cambios hechos mientras estaba desconectado. Abre currobot para revisarlos.
+
+## Correo
+
+mail-confirmation-title = { $company } ha recibido tu candidatura
+mail-interview-title = { $company } quiere hacerte una entrevista
+mail-offer-title = Oferta de { $company }
+mail-rejection-title = { $company } ha respondido: no sigues en el proceso
+
+## Inicio de sesión
+
+oauth-page-done = Has iniciado sesión en currobot
+oauth-page-failed = No se pudo iniciar sesión
+oauth-page-close = Puedes cerrar esta pestaña y volver a la aplicación.
+oauth-required-title = Inicia sesión en { $provider }
+oauth-required-body = El motor necesita que vuelvas a iniciar sesión. Abre Ajustes para hacerlo.
+
+## Importar historial
+
+history-import-title = Importar candidaturas de LinkedIn o Indeed
+history-import-filter = Exportación de datos (ZIP o CSV)
+
+## Duplicados
+
+duplicate-title = Puede que ya te hayas inscrito en { $company }
+duplicate-body = Hay una candidatura parecida del { $date } en estado { $status }. Revísala antes de enviar.
+
+## Recordatorios
+
+reminder-body = Es momento de preguntar por esta candidatura.
+reminder-body-role = Es momento de preguntar por tu candidatura a { $role }.
+reminder-interview-body = Empieza a las { $time }.
+reminder-interview-body-zone = Empieza a las { $time } en tu hora, las { $their_time } en { $zone }.
+
+## Copias de seguridad
+
+backup-restore-title = Elige una copia de seguridad para restaurar
+backup-filter = Copia de seguridad de currobot (ZIP)
+data-export-title = Guardar todos tus datos
+data-export-filter = Exportación de datos de currobot (ZIP)
+backup-failed-title = Falló la copia de seguridad programada
+
+## Cupos
+
+quota-tooltip = Quedan { $remaining } de { $limit } candidaturas hoy
+quota-tooltip-wait = Próxima candidatura a las { $time }
+working-hours-tooltip = Fuera del horario de trabajo
+working-hours-tooltip-override = Fuera del horario de trabajo, en marcha igualmente
+tray-working-hours-override = Ejecutar fuera del horario
+tray-working-hours-end = Respetar el horario de trabajo
+working-hours-refusal = está fuera del horario de trabajo
+working-hours-refusal-until = está fuera del horario de trabajo; vuelve a empezar el { $start }
+working-hours-start = %A a las %H:%M
+
+## Clipboard watcher
+
+clipboard-job-title = ¿Añadir a JobBot?
@@ -0,0 +1,421 @@
+use chrono::{DateTime, Days, Local, NaiveDate, Utc, Weekday};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::{collections::BTreeMap, sync::Mutex, thread, time::Duration};
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::{events::BackendEvent, history, i18n::t_args};
+
+// Time spent on the job search, for people who have to account for it (to
+// keep unemployment benefits, say): how long JobBot's window was in front,
+// and how long each engine run took, from its first progress to its end.
+// Kept in the history database's `activity` table. A stretch in front is
+// written as it goes, every TICK, so quitting or a crash loses at most that;
+// a longer gap between ticks means the machine slept, and a new stretch
+// starts after it. Days and weeks (Monday to Sunday) are local, a stretch
+// counting on the day it began; the weekly report (pdf.rs) has them, and so
+// can the daily digest.
+const TICK: Duration = Duration::from_secs(60);
+const SLEPT: chrono::Duration = chrono::Duration::minutes(3);
+// Glances shorter than this don't count
+const MIN_FOCUS_SECONDS: i64 = 5;
+const MAX_WEEKS: u32 = 52;
+
+struct Stretch {
+    // Its row, once written
+    id: Option<i64>,
+    started_at: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+// A run under way
+struct Run {
+    label: Option<String>,
+    // Its first progress
+    started_at: DateTime<Utc>,
+}
+
+static FOCUS: Mutex<Option<Stretch>> = Mutex::new(None);
+// By run id
+static RUNS: Mutex<BTreeMap<String, Run>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Serialize)]
+pub struct DayActivity {
+    pub date: NaiveDate,
+    pub focus_seconds: i64,
+    pub run_seconds: i64,
+    pub runs: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WeekActivity {
+    // The Monday
+    pub week_of: NaiveDate,
+    pub focus_seconds: i64,
+    pub run_seconds: i64,
+    pub runs: u32,
+    // Applications sent that week, from the history
+    pub submitted: u32,
+    // Monday to Sunday
+    pub days: Vec<DayActivity>,
+}
+
+// "2 h 05 min", for the digest and the report.
+pub fn duration(seconds: i64) -> String {
+    let minutes = seconds.max(0) / 60;
+    t_args(
+        "activity-duration",
+        &[
+            ("hours", (minutes / 60).into()),
+            ("minutes", format!("{:02}", minutes % 60).into()),
+        ],
+    )
+}
+
+// Writes the stretch up to `until`: its row made the first time it's long
+// enough to count, then moved on.
+fn write(db: &Connection, stretch: &mut Stretch, until: DateTime<Utc>) -> Result<(), String> {
+    let seconds = (until - stretch.started_at).num_seconds();
+    match stretch.id {
+        Some(id) => db
+            .execute(
+                "UPDATE activity SET ended_at = ?2, seconds = ?3 WHERE id = ?1",
+                params![id, until.to_rfc3339(), seconds],
+            )
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        None if seconds < MIN_FOCUS_SECONDS => Ok(()),
+        None => {
+            db.execute(
+                "INSERT INTO activity (kind, started_at, ended_at, seconds)
+                 VALUES ('focus', ?1, ?2, ?3)",
+                params![stretch.started_at.to_rfc3339(), until.to_rfc3339(), seconds],
+            )
+            .map_err(|e| e.to_string())?;
+            stretch.id = Some(db.last_insert_rowid());
+            Ok(())
+        }
+    }
+}
+
+fn focus(app: &AppHandle, focused: bool, now: DateTime<Utc>) -> Result<(), String> {
+    let mut current = FOCUS.lock().unwrap();
+    match (focused, current.take()) {
+        (true, stretch) => {
+            *current = Some(stretch.unwrap_or(Stretch {
+                id: None,
+                started_at: now,
+                last_seen: now,
+            }));
+            Ok(())
+        }
+        (false, Some(mut stretch)) => {
+            // Not past the machine going to sleep, if it did
+            let until = if now - stretch.last_seen > SLEPT {
+                stretch.last_seen
+            } else {
+                now
+            };
+            write(&history::open(app)?, &mut stretch, until)
+        }
+        (false, None) => Ok(()),
+    }
+}
+
+// From the main window's focus changes; written off the event loop.
+pub fn on_focus(app: &AppHandle, focused: bool) {
+    let app = app.clone();
+    let now = Utc::now();
+    thread::spawn(move || {
+        if let Err(e) = focus(&app, focused, now) {
+            warn!("activity not recorded: {e}");
+        }
+    });
+}
+
+// The stretch under way, on quitting.
+pub fn on_exit(app: &AppHandle) {
+    if let Err(e) = focus(app, false, Utc::now()) {
+        warn!("activity not recorded: {e}");
+    }
+}
+
+fn tick(app: &AppHandle) -> Result<(), String> {
+    let mut current = FOCUS.lock().unwrap();
+    let Some(stretch) = current.as_mut() else {
+        return Ok(());
+    };
+    let db = history::open(app)?;
+    let now = Utc::now();
+    if now - stretch.last_seen > SLEPT {
+        // Asleep since the last tick: that stretch ended there
+        let last_seen = stretch.last_seen;
+        write(&db, stretch, last_seen)?;
+        *stretch = Stretch {
+            id: None,
+            started_at: now,
+            last_seen: now,
+        };
+        return Ok(());
+    }
+    stretch.last_seen = now;
+    write(&db, stretch, now)
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        if let Err(e) = tick(&app) {
+            warn!("activity not recorded: {e}");
+        }
+    });
+}
+
+fn record_run(
+    app: &AppHandle,
+    label: Option<String>,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+) -> Result<(), String> {
+    history::open(app)?
+        .execute(
+            "INSERT INTO activity (kind, label, started_at, ended_at, seconds)
+             VALUES ('run', ?1, ?2, ?3, ?4)",
+            params![
+                label,
+                started_at.to_rfc3339(),
+                ended_at.to_rfc3339(),
+                (ended_at - started_at).num_seconds()
+            ],
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+// A run is timed from its first progress to its end; one whose start the
+// shell didn't see isn't counted.
+pub fn on_event(app: &AppHandle, event: &BackendEvent) {
+    let Some(run_id) = event.data["run_id"].as_str() else {
+        return;
+    };
+    let now = Utc::now();
+    let label = event.data["label"].as_str().map(String::from);
+    match event.event.as_str() {
+        "run_progress" => {
+            RUNS.lock()
+                .unwrap()
+                .entry(run_id.to_string())
+                .or_insert(Run {
+                    label,
+                    started_at: now,
+                });
+        }
+        "run_finished" => {
+            let Some(run) = RUNS.lock().unwrap().remove(run_id) else {
+                return;
+            };
+            let app = app.clone();
+            thread::spawn(move || {
+                if let Err(e) = record_run(&app, label.or(run.label), run.started_at, now) {
+                    warn!("run time not recorded: {e}");
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+// The week from `monday`, totalled by day.
+pub fn week(db: &Connection, monday: NaiveDate) -> Result<WeekActivity, String> {
+    let in_week = |date: NaiveDate| date >= monday && date < monday + Days::new(7);
+    // A day either side, for the difference to local time
+    let bound = |date: NaiveDate| date.and_time(Default::default()).and_utc().to_rfc3339();
+    let mut statement = db
+        .prepare(
+            "SELECT kind, started_at, seconds FROM activity
+             WHERE started_at >= ?1 AND started_at < ?2",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map(
+            [bound(monday - Days::new(1)), bound(monday + Days::new(8))],
+            |r| {
+                Ok((
+                    r.get::<_, String>(0)?,
+                    history::local(&Some(r.get(1)?)),
+                    r.get::<_, i64>(2)?,
+                ))
+            },
+        )
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    let mut days: Vec<DayActivity> = (0..7)
+        .map(|i| DayActivity {
+            date: monday + Days::new(i),
+            focus_seconds: 0,
+            run_seconds: 0,
+            runs: 0,
+        })
+        .collect();
+    for (kind, started_at, seconds) in rows {
+        let Some(date) = started_at.map(|t| t.date()).filter(|d| in_week(*d)) else {
+            continue;
+        };
+        let day = &mut days[(date - monday).num_days() as usize];
+        if kind == "run" {
+            day.run_seconds += seconds;
+            day.runs += 1;
+        } else {
+            day.focus_seconds += seconds;
+        }
+    }
+    let mut statement = db
+        .prepare("SELECT submitted_at FROM applications WHERE submitted_at IS NOT NULL")
+        .map_err(|e| e.to_string())?;
+    let submitted = statement
+        .query_map([], |r| r.get::<_, Option<String>>(0))
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?
+        .iter()
+        .filter_map(history::local)
+        .filter(|t| in_week(t.date()))
+        .count() as u32;
+    Ok(WeekActivity {
+        week_of: monday,
+        focus_seconds: days.iter().map(|d| d.focus_seconds).sum(),
+        run_seconds: days.iter().map(|d| d.run_seconds).sum(),
+        runs: days.iter().map(|d| d.runs).sum(),
+        submitted,
+        days,
+    })
+}
+
+// Today's, for the digest.
+pub fn today(app: &AppHandle) -> Result<DayActivity, String> {
+    let today = Local::now().date_naive();
+    let monday = today.week(Weekday::Mon).first_day();
+    week(&history::open(app)?, monday)?
+        .days
+        .into_iter()
+        .find(|d| d.date == today)
+        .ok_or_else(|| "today isn't in this week".into())
+}
+
+// `weeks` weeks, newest first, the newest being the one with `week_of`
+// (YYYY-MM-DD) in it, this week by default.
+#[tauri::command]
+pub async fn activity_report(
+    app: AppHandle,
+    weeks: Option<u32>,
+    week_of: Option<String>,
+) -> Result<Vec<WeekActivity>, String> {
+    let day = match week_of.as_deref().filter(|d| !d.is_empty()) {
+        Some(day) => NaiveDate::parse_from_str(day, "%Y-%m-%d").map_err(|e| e.to_string())?,
+        None => Local::now().date_naive(),
+    };
+    let monday = day.week(Weekday::Mon).first_day();
+    let weeks = weeks.unwrap_or(4).clamp(1, MAX_WEEKS);
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = history::open(&app)?;
+        (0..weeks)
+            .map(|i| week(&db, monday - Days::new(7 * u64::from(i))))
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        text.parse().unwrap()
+    }
+
+    fn db() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        db
+    }
+
+    fn rows(db: &Connection) -> Vec<(String, i64)> {
+        db.prepare("SELECT ended_at, seconds FROM activity ORDER BY id")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn hours_and_minutes() {
+        let text = duration(2 * 3600 + 5 * 60 + 59);
+        assert!(text.contains('2') && text.contains("05"), "{text}");
+        assert!(duration(-30).contains("00"));
+    }
+
+    #[test]
+    fn stretches_written_once_long_enough() {
+        let db = db();
+        let mut stretch = Stretch {
+            id: None,
+            started_at: at("2026-10-14T10:00:00Z"),
+            last_seen: at("2026-10-14T10:00:00Z"),
+        };
+        write(&db, &mut stretch, at("2026-10-14T10:00:03Z")).unwrap();
+        assert!(rows(&db).is_empty());
+        write(&db, &mut stretch, at("2026-10-14T10:01:00Z")).unwrap();
+        write(&db, &mut stretch, at("2026-10-14T10:02:30Z")).unwrap();
+        assert!(stretch.id.is_some());
+        assert_eq!(rows(&db), [("2026-10-14T10:02:30+00:00".to_string(), 150)]);
+    }
+
+    #[test]
+    fn weeks_by_day() {
+        let db = db();
+        for (kind, started_at, seconds) in [
+            ("focus", "2026-10-12T09:00:00Z", 600),
+            ("focus", "2026-10-12T15:00:00Z", 300),
+            ("run", "2026-10-14T12:00:00Z", 1200),
+            ("run", "2026-10-18T12:00:00Z", 60),
+            // The weeks either side
+            ("focus", "2026-10-10T12:00:00Z", 999),
+            ("run", "2026-10-20T12:00:00Z", 999),
+        ] {
+            db.execute(
+                "INSERT INTO activity (kind, started_at, ended_at, seconds)
+                 VALUES (?1, ?2, ?2, ?3)",
+                params![kind, started_at, seconds],
+            )
+            .unwrap();
+        }
+        for submitted in ["2026-10-13T12:00:00Z", "2026-10-19T12:00:00Z"] {
+            db.execute(
+                "INSERT INTO applications (company, status, submitted_at, recorded_at)
+                 VALUES ('Acme', 'applied', ?1, '')",
+                [submitted],
+            )
+            .unwrap();
+        }
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let week = week(&db, monday).unwrap();
+        assert_eq!(week.days.len(), 7);
+        assert_eq!(
+            week.days[6].date,
+            NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+        );
+        assert_eq!(
+            (
+                week.focus_seconds,
+                week.run_seconds,
+                week.runs,
+                week.submitted
+            ),
+            (900, 1260, 2, 1)
+        );
+        assert_eq!(week.days[0].focus_seconds, 900);
+        assert_eq!((week.days[2].run_seconds, week.days[2].runs), (1200, 1));
+    }
+}
@@ -0,0 +1,622 @@
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Condvar, Mutex},
+    thread,
+    time::Duration,
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{debug, info, warn};
+
+use crate::{
+    backend,
+    dispatcher::{self, Category},
+    duplicates,
+    events::BackendEvent,
+    i18n::t_args,
+    paths,
+    salary::{self, DEFAULT_CURRENCY},
+};
+
+// Job alerts: saved searches (words, a place, a salary floor) the shell
+// checks new jobs against itself, and notifies on a match. Jobs come from
+// the engine, looked for whenever it announces some (a job added, a scraper
+// done, a run's progress), so alerts go out while a run is still going, and
+// every CHECK_INTERVAL in case an announcement was missed; and from the
+// clipboard watcher while the engine is down, from the posting the shell
+// fetched. Searches, the newest job already looked at and the latest matches
+// are kept in `job-alerts.json` in the profile's data folder. Words and
+// places match without regard to case or accents.
+pub const ALERTS_CHANGED: &str = "job-alerts-changed";
+const FILE: &str = "job-alerts.json";
+const PAGE: u32 = 50;
+// Further back than this a check stops, after a long time away
+const MAX_PAGES: u32 = 10;
+const MAX_SEARCHES: usize = 50;
+const MAX_MATCHES: usize = 200;
+// Engine events that can mean new jobs
+const JOB_EVENTS: &[&str] = &[
+    "job_added",
+    "run_progress",
+    "scraper_finished",
+    "run_finished",
+];
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+// Announcements in a burst are taken as one
+const SETTLE: Duration = Duration::from_secs(5);
+
+static LOCK: Mutex<()> = Mutex::new(());
+static WAKE: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedSearch {
+    pub id: String,
+    pub name: String,
+    // All of them in the title, company or description
+    pub keywords: Vec<String>,
+    // None of them
+    pub excluded: Vec<String>,
+    // Part of the job's location ("Madrid", "remoto")
+    pub location: Option<String>,
+    // A year, in `currency`, against the top of the posting's range
+    pub min_salary: Option<f64>,
+    pub currency: String,
+    // Whether a posting without a salary (most of them) can match a floor
+    pub include_unknown_salary: bool,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Default for SavedSearch {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            keywords: Vec::new(),
+            excluded: Vec::new(),
+            location: None,
+            min_salary: None,
+            currency: DEFAULT_CURRENCY.into(),
+            include_unknown_salary: true,
+            enabled: true,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AlertMatch {
+    pub search_id: String,
+    pub search_name: String,
+    // None for a posting the engine hasn't got yet
+    pub job_id: Option<i64>,
+    pub url: String,
+    pub title: String,
+    pub company: String,
+    pub location: Option<String>,
+    pub salary_raw: Option<String>,
+    pub matched_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertStore {
+    pub searches: Vec<SavedSearch>,
+    // Newest first
+    pub matches: Vec<AlertMatch>,
+    // The engine's newest job already looked at; none until the first check,
+    // which only notes it, so the jobs already there don't all alert at once
+    pub last_job_id: Option<i64>,
+}
+
+// A job as the engine lists it, or as the shell fetched it.
+#[derive(Debug)]
+pub struct Listing {
+    pub job_id: Option<i64>,
+    pub url: String,
+    pub title: String,
+    pub company: String,
+    pub location: Option<String>,
+    pub salary_raw: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Listing {
+    // From the engine's job listing or an intake body.
+    pub fn from_json(item: &Value) -> Self {
+        let text = |key: &str| {
+            item[key]
+                .as_str()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        Self {
+            job_id: item["id"].as_i64(),
+            url: text("url").unwrap_or_default(),
+            title: text("title").unwrap_or_default(),
+            company: text("company").unwrap_or_default(),
+            location: text("location"),
+            salary_raw: text("salary_raw"),
+            description: text("description"),
+        }
+    }
+}
+
+fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join(FILE))
+}
+
+fn load(path: &Path) -> AlertStore {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+// Written beside and renamed over, so a crash can't leave half a file.
+fn save(path: &Path, store: &AlertStore) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, json).map_err(|e| e.to_string())?;
+    fs::rename(temp, path).map_err(|e| e.to_string())
+}
+
+fn change<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut AlertStore) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = LOCK.lock().unwrap();
+    let path = path(app)?;
+    let mut store = load(&path);
+    let result = f(&mut store)?;
+    save(&path, &store)?;
+    let _ = app.emit(ALERTS_CHANGED, ());
+    Ok(result)
+}
+
+fn new_id() -> String {
+    format!("alert-{}", Local::now().format("%Y%m%d-%H%M%S%3f"))
+}
+
+// Trimmed, without blanks or repeats, whatever the case.
+fn words(list: Vec<String>) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for word in list {
+        let word = word.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !word.is_empty() && !words.iter().any(|w| w.eq_ignore_ascii_case(&word)) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+// The top of the posting's yearly range in `currency`, where it can be told.
+fn yearly(salary_raw: &str, currency: &str) -> Option<f64> {
+    let salary = salary::normalize(salary_raw, Some(currency))?;
+    match salary.converted {
+        Some(converted) => converted.annual_max.or(converted.annual_min),
+        None if salary.currency == currency => salary.annual_max.or(salary.annual_min),
+        None => None,
+    }
+}
+
+pub fn matches(search: &SavedSearch, listing: &Listing) -> bool {
+    let text = duplicates::normalize(&format!(
+        "{} {} {}",
+        listing.title,
+        listing.company,
+        listing.description.as_deref().unwrap_or_default()
+    ));
+    // Whole words or phrases: " java " isn't in " javascript "
+    let has = |word: &str| text.contains(&duplicates::normalize(word));
+    if !search.keywords.iter().all(|w| has(w)) || search.excluded.iter().any(|w| has(w)) {
+        return false;
+    }
+    if let Some(place) = &search.location {
+        let Some(location) = &listing.location else {
+            return false;
+        };
+        if !duplicates::normalize(location).contains(&duplicates::normalize(place)) {
+            return false;
+        }
+    }
+    if let Some(floor) = search.min_salary {
+        let figure = listing
+            .salary_raw
+            .as_deref()
+            .and_then(|raw| yearly(raw, &search.currency));
+        return figure.map_or(search.include_unknown_salary, |f| f >= floor);
+    }
+    true
+}
+
+// Notes and notifies the searches `listing` matches that haven't had it yet.
+fn evaluate(app: &AppHandle, store: &mut AlertStore, listing: &Listing) -> usize {
+    let found = note(store, listing);
+    for matched in &found {
+        notify(app, matched);
+    }
+    found.len()
+}
+
+// The searches `listing` matches that haven't had it yet, by job or by link,
+// added to the store's matches.
+fn note(store: &mut AlertStore, listing: &Listing) -> Vec<AlertMatch> {
+    let mut found = Vec::new();
+    for search in store.searches.iter().filter(|s| s.enabled) {
+        let seen = store.matches.iter().any(|m| {
+            m.search_id == search.id
+                && ((listing.job_id.is_some() && m.job_id == listing.job_id)
+                    || (!listing.url.is_empty() && m.url == listing.url))
+        });
+        if seen || !matches(search, listing) {
+            continue;
+        }
+        let matched = AlertMatch {
+            search_id: search.id.clone(),
+            search_name: search.name.clone(),
+            job_id: listing.job_id,
+            url: listing.url.clone(),
+            title: listing.title.clone(),
+            company: listing.company.clone(),
+            location: listing.location.clone(),
+            salary_raw: listing.salary_raw.clone(),
+            matched_at: Utc::now(),
+        };
+        store.matches.insert(0, matched.clone());
+        found.push(matched);
+    }
+    store.matches.truncate(MAX_MATCHES);
+    found
+}
+
+fn notify(app: &AppHandle, matched: &AlertMatch) {
+    let body = match &matched.location {
+        Some(location) => format!("{} — {} ({location})", matched.company, matched.title),
+        None => format!("{} — {}", matched.company, matched.title),
+    };
+    dispatcher::submit(
+        app,
+        Category::JobAlert,
+        &t_args(
+            "job-alert-title",
+            &[("search", matched.search_name.clone().into())],
+        ),
+        &body,
+        "/jobs",
+    );
+}
+
+// The engine's jobs newer than the last looked at, newest first.
+fn fetch_new(last: i64) -> Result<(Vec<Listing>, Option<i64>), String> {
+    let mut listings = Vec::new();
+    let mut newest = None;
+    let mut cursor: Option<i64> = None;
+    for _ in 0..MAX_PAGES {
+        let query = match cursor {
+            Some(cursor) => format!("/api/jobs?limit={PAGE}&describe=true&cursor={cursor}"),
+            None => format!("/api/jobs?limit={PAGE}&describe=true"),
+        };
+        let page = backend::get_json(&query)?;
+        for item in page["items"].as_array().into_iter().flatten() {
+            let listing = Listing::from_json(item);
+            let Some(id) = listing.job_id else { continue };
+            newest = newest.max(Some(id));
+            if id <= last {
+                return Ok((listings, newest));
+            }
+            listings.push(listing);
+        }
+        match page["next_cursor"].as_i64() {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok((listings, newest))
+}
+
+fn check(app: &AppHandle) -> Result<(), String> {
+    let path = path(app)?;
+    let (searching, last) = {
+        let store = load(&path);
+        (store.searches.iter().any(|s| s.enabled), store.last_job_id)
+    };
+    let Some(last) = last else {
+        // The first look only notes where the engine's jobs are up to
+        let page = backend::get_json("/api/jobs?limit=1")?;
+        let newest = page["items"][0]["id"].as_i64().unwrap_or_default();
+        return change(app, |store| {
+            store.last_job_id = Some(newest);
+            Ok(())
+        });
+    };
+    if !searching {
+        // Nothing to match; jobs from now on are the new ones
+        let page = backend::get_json("/api/jobs?limit=1")?;
+        if let Some(newest) = page["items"][0]["id"].as_i64().filter(|&id| id > last) {
+            change(app, |store| {
+                store.last_job_id = Some(newest);
+                Ok(())
+            })?;
+        }
+        return Ok(());
+    }
+    let (listings, newest) = fetch_new(last)?;
+    if listings.is_empty() {
+        return Ok(());
+    }
+    let found = change(app, |store| {
+        // Oldest first, so the notifications come in the order the jobs did
+        let found = listings
+            .iter()
+            .rev()
+            .map(|listing| evaluate(app, store, listing))
+            .sum::<usize>();
+        store.last_job_id = newest.max(store.last_job_id);
+        Ok(found)
+    })?;
+    debug!(jobs = listings.len(), found, "job alerts checked");
+    Ok(())
+}
+
+fn wake() {
+    let (pending, signal) = &WAKE;
+    *pending.lock().unwrap() = true;
+    signal.notify_one();
+}
+
+pub fn on_event(_app: &AppHandle, event: &BackendEvent) {
+    if JOB_EVENTS.contains(&event.event.as_str()) {
+        wake();
+    }
+}
+
+// A posting the clipboard watcher queued for the engine while it was down,
+// as the shell fetched it. Once the engine has it, it's the same job by its
+// link, and doesn't alert again.
+pub fn on_queued(app: &AppHandle, body: &Value) {
+    let listing = Listing::from_json(body);
+    if listing.title.is_empty() && listing.description.is_none() {
+        return;
+    }
+    let result = change(app, |store| Ok(evaluate(app, store, &listing)));
+    if let Err(e) = result {
+        warn!("queued posting not checked against job alerts: {e}");
+    }
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        let (pending, signal) = &WAKE;
+        let guard = pending.lock().unwrap();
+        let (mut guard, _) = signal
+            .wait_timeout_while(guard, CHECK_INTERVAL, |pending| !*pending)
+            .unwrap();
+        *guard = false;
+        drop(guard);
+        thread::sleep(SETTLE);
+        *pending.lock().unwrap() = false;
+        if let Err(e) = check(&app) {
+            // Mostly the engine not being up yet
+            debug!("job alerts not checked: {e}");
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn list_job_alerts(app: AppHandle) -> Result<AlertStore, String> {
+    tauri::async_runtime::spawn_blocking(move || Ok(load(&path(&app)?)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Adds a search, or with an `id` already saved replaces it.
+#[tauri::command]
+pub fn save_job_alert(app: AppHandle, search: SavedSearch) -> Result<SavedSearch, String> {
+    let name = search.name.trim().to_string();
+    let keywords = words(search.keywords);
+    let location = search
+        .location
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    if name.is_empty() {
+        return Err("a job alert needs a name".into());
+    }
+    if keywords.is_empty() && location.is_none() && search.min_salary.is_none() {
+        return Err("add some words, a place or a salary to look for".into());
+    }
+    if search
+        .min_salary
+        .is_some_and(|s| !s.is_finite() || s <= 0.0)
+    {
+        return Err("the salary has to be a yearly amount above zero".into());
+    }
+    let currency = search.currency.trim().to_uppercase();
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("{currency} is not a currency code like EUR"));
+    }
+    let saved = change(&app, |store| {
+        let existing = store.searches.iter().position(|s| s.id == search.id);
+        let saved = SavedSearch {
+            id: if existing.is_some() {
+                search.id
+            } else {
+                new_id()
+            },
+            name,
+            keywords,
+            excluded: words(search.excluded),
+            location,
+            min_salary: search.min_salary,
+            currency,
+            include_unknown_salary: search.include_unknown_salary,
+            enabled: search.enabled,
+            created_at: existing.map_or_else(Utc::now, |i| store.searches[i].created_at),
+        };
+        match existing {
+            Some(i) => store.searches[i] = saved.clone(),
+            None if store.searches.len() >= MAX_SEARCHES => {
+                return Err(format!("there can be at most {MAX_SEARCHES} job alerts"));
+            }
+            None => store.searches.push(saved.clone()),
+        }
+        Ok(saved)
+    })?;
+    info!(id = saved.id, "job alert saved");
+    Ok(saved)
+}
+
+// Its matches go too.
+#[tauri::command]
+pub fn delete_job_alert(app: AppHandle, id: String) -> Result<(), String> {
+    change(&app, |store| {
+        let before = store.searches.len();
+        store.searches.retain(|s| s.id != id);
+        if store.searches.len() == before {
+            return Err(format!("no job alert {id}"));
+        }
+        store.matches.retain(|m| m.search_id != id);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn clear_job_alert_matches(app: AppHandle) -> Result<(), String> {
+    change(&app, |store| {
+        store.matches.clear();
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn search(id: &str, keywords: &[&str]) -> SavedSearch {
+        SavedSearch {
+            id: id.into(),
+            name: id.to_uppercase(),
+            keywords: keywords.iter().map(|w| w.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn listing(job_id: Option<i64>, url: &str, title: &str) -> Listing {
+        Listing {
+            job_id,
+            url: url.into(),
+            title: title.into(),
+            company: "Acme".into(),
+            location: Some("Málaga, Andalucía".into()),
+            salary_raw: None,
+            description: Some("Equipo pequeño, trabajo en remoto parcial".into()),
+        }
+    }
+
+    #[test]
+    fn words_without_blanks_or_repeats() {
+        let list = ["  rust ", "", "Rust", "data   engineer", "go"].map(String::from);
+        assert_eq!(words(list.to_vec()), ["rust", "data engineer", "go"]);
+    }
+
+    #[test]
+    fn listings_from_the_engine_or_an_intake() {
+        let item = json!({ "id": 7, "url": "https://a.example/7", "title": " Cajero ",
+            "company": "Acme", "location": "", "salary_raw": "hasta 30.000 €" });
+        let listing = Listing::from_json(&item);
+        assert_eq!(listing.job_id, Some(7));
+        assert_eq!(listing.title, "Cajero");
+        assert_eq!(listing.location, None);
+        assert_eq!(listing.salary_raw.as_deref(), Some("hasta 30.000 €"));
+        let intake = Listing::from_json(&json!({ "url": "https://a.example/8" }));
+        assert_eq!((intake.job_id, intake.title.as_str()), (None, ""));
+    }
+
+    #[test]
+    fn words_and_places() {
+        let job = listing(Some(1), "", "Desarrollador Java Senior");
+        assert!(matches(&search("a", &["java", "REMOTO"]), &job));
+        // Whole words only
+        assert!(!matches(&search("a", &["jav"]), &job));
+        assert!(matches(&search("a", &["trabajo en remoto"]), &job));
+        let mut excluded = search("a", &["java"]);
+        excluded.excluded = vec!["senior".into()];
+        assert!(!matches(&excluded, &job));
+        let mut place = search("a", &[]);
+        place.location = Some("malaga".into());
+        assert!(matches(&place, &job));
+        place.location = Some("Madrid".into());
+        assert!(!matches(&place, &job));
+        let mut nowhere = listing(Some(2), "", "Java");
+        nowhere.location = None;
+        place.location = Some("malaga".into());
+        assert!(!matches(&place, &nowhere));
+    }
+
+    #[test]
+    fn salary_floors() {
+        let mut floor = search("a", &[]);
+        floor.min_salary = Some(28_000.0);
+        let mut job = listing(Some(1), "", "Cajero");
+        job.salary_raw = Some("hasta 30.000 €".into());
+        assert!(matches(&floor, &job));
+        floor.min_salary = Some(32_000.0);
+        assert!(!matches(&floor, &job));
+        // Unknown: none given, or in a currency there's no rate for
+        for salary in [None, Some("£55–65k".to_string())] {
+            job.salary_raw = salary;
+            floor.include_unknown_salary = true;
+            assert!(matches(&floor, &job));
+            floor.include_unknown_salary = false;
+            assert!(!matches(&floor, &job));
+        }
+    }
+
+    #[test]
+    fn each_job_matches_a_search_once() {
+        let mut disabled = search("off", &["java"]);
+        disabled.enabled = false;
+        let mut store = AlertStore {
+            searches: vec![
+                search("java", &["java"]),
+                search("rust", &["rust"]),
+                disabled,
+            ],
+            ..Default::default()
+        };
+        let found = note(&mut store, &listing(Some(1), "https://a.example/1", "Java"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].search_name, "JAVA");
+        // The same job again, by id or, before the engine has it, by link
+        assert!(note(&mut store, &listing(Some(1), "", "Java")).is_empty());
+        assert!(note(&mut store, &listing(None, "https://a.example/1", "Java")).is_empty());
+        note(
+            &mut store,
+            &listing(Some(2), "https://a.example/2", "Java y Rust"),
+        );
+        let ids: Vec<Option<i64>> = store.matches.iter().map(|m| m.job_id).collect();
+        assert_eq!(ids, [Some(2), Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn matches_are_capped() {
+        let mut store = AlertStore {
+            searches: vec![search("any", &[])],
+            ..Default::default()
+        };
+        for id in 0..MAX_MATCHES as i64 + 3 {
+            note(&mut store, &listing(Some(id), "", "Job"));
+        }
+        assert_eq!(store.matches.len(), MAX_MATCHES);
+        assert_eq!(store.matches[0].job_id, Some(MAX_MATCHES as i64 + 2));
+    }
+}
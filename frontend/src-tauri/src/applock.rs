@@ -0,0 +1,290 @@
+use serde::Serialize;
+use std::{
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+
+use crate::{
+    i18n::t,
+    onboarding::ONBOARDING_WINDOW,
+    settings::{self, LockSettings},
+    uninstall::UNINSTALL_WINDOW,
+};
+
+// Optional lock for shared machines: with `lock.enabled`, showing the main
+// window first asks the OS to confirm it's the user (Touch ID or the account
+// password on macOS, Windows Hello or PIN on Windows), and the window hides
+// itself again after `lock.idle_minutes` without input. The webview reports
+// input through `app_lock_activity`. The tray's other ways in (the uninstall
+// and onboarding windows, switching profiles) ask first too, through `gate`,
+// and locking hides those windows with the main one. Linux has no equivalent
+// API to call, so the lock isn't offered there.
+pub const APP_LOCK_EVENT: &str = "app-lock";
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+pub struct AppLockState {
+    // Starts locked so the first show after launch asks too
+    locked: Mutex<bool>,
+    last_activity: Mutex<Instant>,
+    // One OS prompt at a time, however often the tray is clicked
+    prompting: Mutex<bool>,
+}
+
+impl Default for AppLockState {
+    fn default() -> Self {
+        Self {
+            locked: Mutex::new(true),
+            last_activity: Mutex::new(Instant::now()),
+            prompting: Mutex::new(false),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppLockInfo {
+    #[serde(flatten)]
+    pub settings: LockSettings,
+    pub available: bool,
+    pub locked: bool,
+}
+
+pub fn is_locked(app: &AppHandle) -> bool {
+    settings::get(app).lock.enabled && *app.state::<AppLockState>().locked.lock().unwrap()
+}
+
+fn touch(app: &AppHandle) {
+    *app.state::<AppLockState>().last_activity.lock().unwrap() = Instant::now();
+}
+
+fn set_locked(app: &AppHandle, locked: bool) {
+    *app.state::<AppLockState>().locked.lock().unwrap() = locked;
+    let _ = app.emit(APP_LOCK_EVENT, locked);
+}
+
+pub fn lock(app: &AppHandle) {
+    set_locked(app, true);
+    if let Some(w) = app.get_webview_window("main") {
+        let _ = w.hide();
+        crate::menubar::on_hidden(app);
+    }
+    for label in [ONBOARDING_WINDOW, UNINSTALL_WINDOW] {
+        if let Some(w) = app.get_webview_window(label) {
+            let _ = w.hide();
+        }
+    }
+}
+
+// Asks the OS off the calling thread (the prompt blocks), then shows the
+// window on success. Cancelling leaves it hidden.
+pub fn unlock_then_show(app: &AppHandle) {
+    unlock_then(app, crate::show_window);
+}
+
+// For a way into the app other than the main window: false while it's
+// locked, when the user is asked to unlock and `then` runs once they have.
+pub fn gate(app: &AppHandle, then: impl FnOnce(&AppHandle) + Send + 'static) -> bool {
+    if !is_locked(app) {
+        return true;
+    }
+    unlock_then(app, then);
+    false
+}
+
+fn unlock_then(app: &AppHandle, then: impl FnOnce(&AppHandle) + Send + 'static) {
+    let state = app.state::<AppLockState>();
+    {
+        let mut prompting = state.prompting.lock().unwrap();
+        if *prompting {
+            return;
+        }
+        *prompting = true;
+    }
+    let app = app.clone();
+    thread::spawn(move || {
+        let result = platform::authenticate(&t("app-lock-reason"));
+        *app.state::<AppLockState>().prompting.lock().unwrap() = false;
+        match result {
+            Ok(true) => {
+                touch(&app);
+                set_locked(&app, false);
+                let handle = app.clone();
+                let _ = app.run_on_main_thread(move || then(&handle));
+            }
+            Ok(false) => {}
+            Err(e) => warn!("app unlock failed: {e}"),
+        }
+    });
+}
+
+// Sleep that the monotonic clock missed still counts as time away.
+pub fn on_wake(app: &AppHandle, missed: Duration) {
+    let state = app.state::<AppLockState>();
+    let mut last = state.last_activity.lock().unwrap();
+    if let Some(earlier) = last.checked_sub(missed) {
+        *last = earlier;
+    }
+}
+
+// Locks once the user has been away for the configured time, whether or not
+// the window is on screen.
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+        let lock_settings = settings::get(&app).lock;
+        if !lock_settings.enabled || is_locked(&app) {
+            continue;
+        }
+        let idle = app
+            .state::<AppLockState>()
+            .last_activity
+            .lock()
+            .unwrap()
+            .elapsed();
+        if idle >= Duration::from_secs(u64::from(lock_settings.idle_minutes) * 60) {
+            lock(&app);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_app_lock(app: AppHandle) -> AppLockInfo {
+    AppLockInfo {
+        settings: settings::get(&app).lock,
+        available: platform::available(),
+        locked: is_locked(&app),
+    }
+}
+
+// Turning the lock on (or off) needs the same confirmation as unlocking, so
+// nobody sitting at an unlocked window can change it, and a user can't lock
+// themselves out with an authenticator that doesn't work.
+#[tauri::command]
+pub async fn set_app_lock(app: AppHandle, lock: LockSettings) -> Result<(), String> {
+    if lock.idle_minutes == 0 {
+        return Err("the idle timeout must be at least a minute".into());
+    }
+    let current = settings::get(&app).lock;
+    if lock.enabled != current.enabled {
+        if !platform::available() {
+            return Err(
+                "this computer has no Touch ID, Windows Hello or password prompt the app can use"
+                    .into(),
+            );
+        }
+        let confirmed =
+            tauri::async_runtime::spawn_blocking(|| platform::authenticate(&t("app-lock-reason")))
+                .await
+                .map_err(|e| e.to_string())??;
+        if !confirmed {
+            return Err("authentication was cancelled".into());
+        }
+    }
+    settings::update(&app, |s| s.lock = lock)?;
+    touch(&app);
+    set_locked(&app, false);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn lock_app_now(app: AppHandle) -> Result<(), String> {
+    if !settings::get(&app).lock.enabled {
+        return Err("the app lock is off".into());
+    }
+    lock(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn app_lock_activity(app: AppHandle) {
+    touch(&app);
+}
+
+// LocalAuthentication has no Rust bindings in our tree; the few calls needed
+// go through the Objective-C runtime directly.
+#[cfg(target_os = "macos")]
+mod platform {
+    use block2::RcBlock;
+    use objc2::{
+        msg_send,
+        rc::Retained,
+        runtime::{AnyClass, AnyObject, Bool},
+    };
+    use objc2_foundation::NSString;
+    use std::{ptr, sync::mpsc};
+
+    #[link(name = "LocalAuthentication", kind = "framework")]
+    extern "C" {}
+
+    // LAPolicyDeviceOwnerAuthentication: biometrics, watch or account password
+    const POLICY: isize = 2;
+
+    fn context() -> Option<Retained<AnyObject>> {
+        let class = AnyClass::get(c"LAContext")?;
+        unsafe { msg_send![class, new] }
+    }
+
+    pub fn available() -> bool {
+        let Some(ctx) = context() else {
+            return false;
+        };
+        let ok: Bool = unsafe {
+            msg_send![&*ctx, canEvaluatePolicy: POLICY, error: ptr::null_mut::<*mut AnyObject>()]
+        };
+        ok.as_bool()
+    }
+
+    pub fn authenticate(reason: &str) -> Result<bool, String> {
+        let ctx = context().ok_or("LocalAuthentication is unavailable")?;
+        let (tx, rx) = mpsc::channel();
+        let reply = RcBlock::new(move |success: Bool, _error: *mut AnyObject| {
+            let _ = tx.send(success.as_bool());
+        });
+        let reason = NSString::from_str(reason);
+        unsafe {
+            let _: () = msg_send![
+                &*ctx,
+                evaluatePolicy: POLICY,
+                localizedReason: &*reason,
+                reply: &*reply
+            ];
+        }
+        rx.recv().map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use windows::{
+        core::HSTRING,
+        Security::Credentials::UI::{
+            UserConsentVerificationResult, UserConsentVerifier, UserConsentVerifierAvailability,
+        },
+    };
+
+    pub fn available() -> bool {
+        UserConsentVerifier::CheckAvailabilityAsync()
+            .and_then(|op| op.get())
+            .is_ok_and(|a| a == UserConsentVerifierAvailability::Available)
+    }
+
+    pub fn authenticate(reason: &str) -> Result<bool, String> {
+        let result = UserConsentVerifier::RequestVerificationAsync(&HSTRING::from(reason))
+            .and_then(|op| op.get())
+            .map_err(|e| e.to_string())?;
+        Ok(result == UserConsentVerificationResult::Verified)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    pub fn available() -> bool {
+        false
+    }
+
+    pub fn authenticate(_reason: &str) -> Result<bool, String> {
+        Err("the app lock is not supported on Linux".into())
+    }
+}
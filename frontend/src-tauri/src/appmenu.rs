@@ -0,0 +1,92 @@
+use tauri::AppHandle;
+
+use crate::automation;
+
+// The application menu on macOS: About with the version and credits,
+// Settings… (⌘,) opening the settings page, and the standard Edit and Window
+// menus, whose items are what give the webview copy, paste and undo. Other
+// platforms have no app menu; the tray stands in. Rebuilt with the tray menu
+// so the labels follow the language, and clicks reach `build_tray`'s handler.
+pub fn refresh(app: &AppHandle) {
+    platform::set(app);
+}
+
+// The items added in `platform::build`.
+pub fn on_menu_event(app: &AppHandle, id: &str) {
+    if id == "app:settings" {
+        automation::open_route(app, "/settings");
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use tauri::{
+        menu::{AboutMetadataBuilder, Menu, MenuItemBuilder, SubmenuBuilder},
+        AppHandle, Wry,
+    };
+    use tracing::warn;
+
+    use crate::i18n::t;
+
+    fn build(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+        let info = app.package_info();
+        let about = AboutMetadataBuilder::new()
+            .name(Some(info.name.clone()))
+            .version(Some(info.version.to_string()))
+            .credits(Some(t("app-menu-credits")))
+            .build();
+        let settings = MenuItemBuilder::with_id("app:settings", t("app-menu-settings"))
+            .accelerator("Cmd+,")
+            .build(app)?;
+        // AppKit titles the first menu with the app's name whatever it's given
+        let application = SubmenuBuilder::new(app, &info.name)
+            .about(Some(about))
+            .separator()
+            .item(&settings)
+            .separator()
+            .services()
+            .separator()
+            .hide()
+            .hide_others()
+            .show_all()
+            .separator()
+            .quit()
+            .build()?;
+        let edit = SubmenuBuilder::new(app, t("app-menu-edit"))
+            .undo()
+            .redo()
+            .separator()
+            .cut()
+            .copy()
+            .paste()
+            .select_all()
+            .build()?;
+        let window = SubmenuBuilder::new(app, t("app-menu-window"))
+            .minimize()
+            .maximize()
+            .separator()
+            .close_window()
+            .build()?;
+        // Lists the open windows under it
+        window.set_as_windows_menu_for_nsapp()?;
+        Menu::with_items(app, &[&application, &edit, &window])
+    }
+
+    pub fn set(app: &AppHandle) {
+        match build(app) {
+            Ok(menu) => {
+                if let Err(e) = app.set_menu(menu) {
+                    warn!("app menu not set: {e}");
+                }
+            }
+            Err(e) => warn!("app menu not built: {e}"),
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use tauri::AppHandle;
+
+    pub fn set(_app: &AppHandle) {}
+}
@@ -0,0 +1,309 @@
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use tauri::AppHandle;
+use tracing::error;
+
+use crate::paths;
+
+// Append-only record of what the app did on the user's behalf, one JSON entry
+// per line in the profile's audit.log. Each entry carries an HMAC over the
+// previous entry's and its own content, keyed by a secret in the OS keychain,
+// so editing, removing or reordering earlier lines breaks the chain from that
+// point and can't be papered over without the key. The app never rewrites the file;
+// only a full wipe on uninstall removes it.
+pub const KEYCHAIN_SERVICE: &str = "com.currobot.app.audit";
+pub const KEYCHAIN_ACCOUNT: &str = "chain-key";
+const LOG_FILE: &str = "audit.log";
+const EXPORT_FORMAT: &str = "jobbot-audit";
+const EXPORT_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    ApplicationSubmitted,
+    // A stored secret was read and handed to something; never the value
+    CredentialUsed,
+    // Dotted paths of what changed; never the values
+    SettingsChanged,
+    DataReset,
+    BackupRestored,
+    UninstallCleanup,
+    // Counts of what the retention policy removed
+    DataPruned,
+    // Everything about the user, saved to a file of their choosing
+    DataExported,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entry {
+    pub seq: u64,
+    // RFC 3339, UTC
+    pub at: String,
+    pub action: Action,
+    pub detail: Value,
+    pub mac: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditLog {
+    // Newest first
+    pub entries: Vec<Entry>,
+    pub total: usize,
+    pub intact: bool,
+    // First entry that fails verification
+    pub broken_at: Option<u64>,
+}
+
+// Path, sequence number and MAC of the last entry written, so appending
+// doesn't re-read the file every time.
+static LAST: Mutex<Option<(PathBuf, u64, String)>> = Mutex::new(None);
+static KEY: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+
+fn log_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::config_dir(app)?.join(LOG_FILE))
+}
+
+fn key() -> Result<Vec<u8>, String> {
+    let mut cached = KEY.lock().unwrap();
+    if let Some(key) = cached.as_ref() {
+        return Ok(key.clone());
+    }
+    let entry =
+        keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT).map_err(|e| e.to_string())?;
+    let key = match entry.get_password() {
+        Ok(encoded) => STANDARD.decode(encoded).map_err(|e| e.to_string())?,
+        Err(keyring::Error::NoEntry) => {
+            let mut key = vec![0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            entry
+                .set_password(&STANDARD.encode(&key))
+                .map_err(|e| e.to_string())?;
+            key
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    *cached = Some(key.clone());
+    Ok(key)
+}
+
+fn sign(key: &[u8], prev: &str, seq: u64, at: &str, action: Action, detail: &Value) -> String {
+    let body = serde_json::to_string(&(seq, at, action, detail)).unwrap_or_default();
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any size");
+    mac.update(prev.as_bytes());
+    mac.update(b"\n");
+    mac.update(body.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+// Every line, including ones that no longer parse (reported as broken).
+fn read_lines(path: &Path) -> Vec<Result<Entry, String>> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).map_err(|e| e.to_string()))
+        .collect()
+}
+
+// Sequence number of the first entry that doesn't verify, if any.
+fn verify(key: &[u8], lines: &[Result<Entry, String>]) -> Option<u64> {
+    let mut prev = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        let expected_seq = i as u64 + 1;
+        let Ok(entry) = line else {
+            return Some(expected_seq);
+        };
+        let mac = sign(
+            key,
+            &prev,
+            entry.seq,
+            &entry.at,
+            entry.action,
+            &entry.detail,
+        );
+        if entry.seq != expected_seq || entry.mac != mac {
+            return Some(expected_seq);
+        }
+        prev = mac;
+    }
+    None
+}
+
+fn append(app: &AppHandle, action: Action, detail: Value) -> Result<(), String> {
+    let path = log_path(app)?;
+    let key = key()?;
+    let mut last = LAST.lock().unwrap();
+    let (seq, prev) = match last.as_ref() {
+        Some((p, seq, mac)) if *p == path => (*seq, mac.clone()),
+        _ => read_lines(&path)
+            .into_iter()
+            .rev()
+            .find_map(Result::ok)
+            .map_or((0, String::new()), |e| (e.seq, e.mac)),
+    };
+    let seq = seq + 1;
+    let at = Utc::now().to_rfc3339();
+    let mac = sign(&key, &prev, seq, &at, action, &detail);
+    let entry = Entry {
+        seq,
+        at,
+        action,
+        detail,
+        mac,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| e.to_string())?;
+    let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+    writeln!(file, "{line}").map_err(|e| e.to_string())?;
+    *last = Some((path, entry.seq, entry.mac));
+    Ok(())
+}
+
+// Failing to audit never blocks the action itself; it's reported instead.
+pub fn record(app: &AppHandle, action: Action, detail: Value) {
+    if let Err(e) = append(app, action, detail) {
+        error!("audit entry not recorded: {e}");
+    }
+}
+
+fn load(app: &AppHandle) -> Result<(Vec<Entry>, Option<u64>), String> {
+    let lines = read_lines(&log_path(app)?);
+    let broken_at = verify(&key()?, &lines);
+    Ok((
+        lines.into_iter().filter_map(Result::ok).collect(),
+        broken_at,
+    ))
+}
+
+#[tauri::command]
+pub async fn get_audit_log(app: AppHandle, limit: Option<usize>) -> Result<AuditLog, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (entries, broken_at) = load(&app)?;
+        let total = entries.len();
+        Ok(AuditLog {
+            entries: entries
+                .into_iter()
+                .rev()
+                .take(limit.unwrap_or(200))
+                .collect(),
+            total,
+            intact: broken_at.is_none(),
+            broken_at,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// The whole chain with its verification result, as one JSON file.
+#[tauri::command]
+pub async fn export_audit_log(app: AppHandle, path: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (entries, broken_at) = load(&app)?;
+        let export = json!({
+            "format": EXPORT_FORMAT,
+            "version": EXPORT_VERSION,
+            "app_version": app.package_info().version.to_string(),
+            "exported_at": Utc::now().to_rfc3339(),
+            "intact": broken_at.is_none(),
+            "broken_at": broken_at,
+            "entries": entries,
+        });
+        let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+        fs::write(&path, json).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test key";
+
+    fn chain(details: &[Value]) -> Vec<Result<Entry, String>> {
+        let mut prev = String::new();
+        let mut entries = Vec::new();
+        for (i, detail) in details.iter().enumerate() {
+            let seq = i as u64 + 1;
+            let at = format!("2026-10-0{seq}T09:00:00Z");
+            let mac = sign(KEY, &prev, seq, &at, Action::SettingsChanged, detail);
+            prev = mac.clone();
+            entries.push(Ok(Entry {
+                seq,
+                at,
+                action: Action::SettingsChanged,
+                detail: detail.clone(),
+                mac,
+            }));
+        }
+        entries
+    }
+
+    fn details() -> Vec<Value> {
+        (1..=3)
+            .map(|i| json!({ "changed": [format!("field{i}")] }))
+            .collect()
+    }
+
+    #[test]
+    fn intact_chain() {
+        assert_eq!(verify(KEY, &chain(&details())), None);
+        assert_eq!(verify(KEY, &[]), None);
+    }
+
+    #[test]
+    fn edits_are_found() {
+        let mut lines = chain(&details());
+        if let Ok(entry) = &mut lines[1] {
+            entry.detail = json!({ "changed": [] });
+        }
+        assert_eq!(verify(KEY, &lines), Some(2));
+        assert_eq!(verify(b"other key", &chain(&details())), Some(1));
+    }
+
+    #[test]
+    fn removed_and_unreadable_lines_are_found() {
+        let mut lines = chain(&details());
+        let _ = lines.remove(1);
+        assert_eq!(verify(KEY, &lines), Some(2));
+
+        let mut lines = chain(&details());
+        lines[2] = Err("expected value".into());
+        assert_eq!(verify(KEY, &lines), Some(3));
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        let path = std::env::temp_dir().join(format!("jobbot-audit-{}.log", std::process::id()));
+        let entry = chain(&details()).remove(0).unwrap();
+        let line = serde_json::to_string(&entry).unwrap();
+        fs::write(&path, format!("{line}\n\n{{broken\n")).unwrap();
+        let lines = read_lines(&path);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].is_ok() && lines[1].is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
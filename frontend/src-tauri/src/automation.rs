@@ -0,0 +1,95 @@
+use serde_json::json;
+use std::{sync::Mutex, thread};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{backend, quotas, schedule::Target, scope, workhours};
+
+// Pausing and running the scheduled scrapers (backend /api/automation) and
+// jumping to a page, for the menus outside the window: the macOS Dock menu
+// (`dock`) and the Windows jump list (`jumplist`). The engine keeps the
+// paused state across restarts and announces changes as `automation_changed`.
+const OPEN_ROUTE: &str = "open-route";
+
+// Kept until the page takes it, as one asked for while the window is still
+// loading (a launch from a notification, say) comes before anyone listens.
+static PENDING_ROUTE: Mutex<Option<String>> = Mutex::new(None);
+
+// Blocking.
+pub fn pause(paused: bool) -> Result<(), String> {
+    let path = if paused {
+        "/api/automation/pause"
+    } else {
+        "/api/automation/resume"
+    };
+    backend::post_json(path, &json!({})).map(|_| ())
+}
+
+// In the background, as menu handlers run on the main thread.
+pub fn set_paused(paused: bool) {
+    thread::spawn(move || match pause(paused) {
+        Ok(()) => info!(paused, "automation"),
+        Err(e) => warn!(paused, "automation not changed: {e}"),
+    });
+}
+
+// Every scraper once, paused or not, unless it's outside the working hours
+// (`workhours`) or none of what it finds could be sent today (`quotas`).
+pub fn run_now(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let refusal =
+            workhours::refusal(&app).or_else(|| quotas::run_refusal(&app, &Target::AllScrapers));
+        if let Some(reason) = refusal {
+            info!("run now skipped: {reason}");
+            return;
+        }
+        if let Err(e) = backend::post_json("/api/automation/run", &json!({})) {
+            warn!("run now failed: {e}");
+        }
+    });
+}
+
+// The main window listens for OPEN_ROUTE (components/RouteListener.tsx).
+pub fn open_route(app: &AppHandle, route: &str) {
+    *PENDING_ROUTE.lock().unwrap() = Some(route.to_string());
+    crate::show_window(app);
+    let _ = app.emit_to(scope::MAIN_WINDOW, OPEN_ROUTE, route);
+}
+
+#[tauri::command]
+pub fn take_pending_route() -> Option<String> {
+    PENDING_ROUTE.lock().unwrap().take()
+}
+
+pub fn open_application(app: &AppHandle, id: i64) {
+    open_route(app, &format!("/review?id={id}"));
+}
+
+// Blocking, None while the engine doesn't answer.
+pub fn paused() -> Option<bool> {
+    backend::get_json("/api/automation")
+        .ok()
+        .and_then(|v| v["paused"].as_bool())
+}
+
+// Blocking: id and company of the latest `count` applications, newest first.
+#[cfg(any(target_os = "macos", windows))]
+pub fn recent(count: usize) -> Vec<(i64, String)> {
+    backend::get_json(&format!("/api/applications?limit={count}"))
+        .ok()
+        .and_then(|v| v["items"].as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| {
+            let id = item["id"].as_i64()?;
+            let company = item["company"].as_str().unwrap_or_default();
+            let label = if company.is_empty() {
+                format!("#{id}")
+            } else {
+                company.to_string()
+            };
+            Some((id, label))
+        })
+        .collect()
+}
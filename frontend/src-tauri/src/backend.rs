@@ -0,0 +1,110 @@
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::Value;
+use std::{sync::OnceLock, time::Duration};
+
+use crate::overrides;
+
+// Per-launch secret the backend requires on every request
+// (backend/security/session.py), so other local processes can't drive it.
+pub const TOKEN_ENV: &str = "JOBBOT_SESSION_TOKEN";
+pub const TOKEN_HEADER: &str = "X-JobBot-Token";
+
+static TOKEN: OnceLock<String> = OnceLock::new();
+
+pub fn token() -> &'static str {
+    TOKEN.get_or_init(|| {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    })
+}
+
+fn builder() -> reqwest::blocking::ClientBuilder {
+    let mut headers = HeaderMap::new();
+    // Hex only, always a valid header value
+    headers.insert(TOKEN_HEADER, HeaderValue::from_static(token()));
+    reqwest::blocking::Client::builder()
+        .no_proxy()
+        .default_headers(headers)
+}
+
+// The sidecar binds to 127.0.0.1 (see backend/config.py: host/port); the port
+// follows JOBBOT_BACKEND_PORT.
+fn client() -> Result<reqwest::blocking::Client, String> {
+    builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+// Long-lived response for the SSE stream: no total timeout, the backend sends
+// a heartbeat comment every 25s and closes the socket when it exits.
+pub fn open_stream(path: &str) -> Result<reqwest::blocking::Response, String> {
+    builder()
+        .timeout(None)
+        .build()
+        .map_err(|e| e.to_string())?
+        .get(format!("{}{path}", overrides::backend_url()))
+        .header("Accept", "text/event-stream")
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())
+}
+
+// Blocking GET against the local backend. Call from a worker thread, never
+// from the main (event loop) thread.
+pub fn get_json(path: &str) -> Result<Value, String> {
+    client()?
+        .get(format!("{}{path}", overrides::backend_url()))
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json()
+        .map_err(|e| e.to_string())
+}
+
+pub fn post_json(path: &str, body: &Value) -> Result<Value, String> {
+    client()?
+        .post(format!("{}{path}", overrides::backend_url()))
+        .json(body)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json()
+        .map_err(|e| e.to_string())
+}
+
+// Why a request didn't go through: the backend never answered, or it did
+// and refused with a status and its `detail`.
+#[derive(Debug)]
+pub enum Failure {
+    Unreachable(String),
+    Refused(u16, String),
+}
+
+// Any method, keeping the backend's refusals apart from not reaching it.
+pub fn send_json(method: &str, path: &str, body: Option<&Value>) -> Result<Value, Failure> {
+    let method = reqwest::Method::from_bytes(method.as_bytes())
+        .map_err(|e| Failure::Unreachable(e.to_string()))?;
+    let mut request = client()
+        .map_err(Failure::Unreachable)?
+        .request(method, format!("{}{path}", overrides::backend_url()));
+    if let Some(body) = body {
+        request = request.json(body);
+    }
+    let response = request
+        .send()
+        .map_err(|e| Failure::Unreachable(e.to_string()))?;
+    let status = response.status();
+    let value: Value = response.json().unwrap_or(Value::Null);
+    if status.is_success() {
+        return Ok(value);
+    }
+    let detail = match &value["detail"] {
+        Value::String(s) => s.clone(),
+        Value::Null => status.to_string(),
+        other => other.to_string(),
+    };
+    Err(Failure::Refused(status.as_u16(), detail))
+}
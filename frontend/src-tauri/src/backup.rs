@@ -0,0 +1,866 @@
+use chrono::{DateTime, Duration as Span, Local, Utc};
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Component, Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::Duration,
+};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_dialog::DialogExt;
+use tracing::{info, warn};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{
+    audit::{self, Action},
+    history, historycrypt,
+    i18n::t,
+    notifications, paths, reset, settings,
+};
+
+// Everything the app keeps, for every profile, in one ZIP: the config folder
+// (settings, profile registry, proxy pool, webhooks) and the data folder
+// (the engine's database and CVs, the application history, documents and
+// browser profiles), under config/ and data/ with a manifest of every file's
+// SHA-256. What is downloaded again on demand, caches, and what only
+// means something on this machine are left out, and so is the audit log,
+// which is only ever appended to. SQLite databases are copied with VACUUM
+// INTO so a backup taken while the engine runs is consistent. Backups are
+// made by hand or every `interval_hours`, the newest `keep` scheduled ones
+// kept. A restore checks the whole archive into a staging folder beside each
+// of the two first, then stops the engine and swaps the folders' contents by
+// renaming, putting everything back if a step fails; what it replaced is kept
+// in .before-restore until the next restore, and the app relaunches.
+// Passwords stay in the OS keychain and aren't in backups (export.rs moves
+// those).
+pub const BACKUPS_CHANGED: &str = "backups-changed";
+const FORMAT: &str = "currobot-backup";
+const FORMAT_VERSION: u32 = 1;
+const MANIFEST: &str = "manifest.json";
+const PREFIX: &str = "currobot-backup-";
+const AUTO_SUFFIX: &str = "-auto";
+const STAGING: &str = ".restore-staging";
+const PREVIOUS: &str = ".before-restore";
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+// Top-level entries: downloaded or rebuilt on demand, or tied to this machine
+const LEFT_OUT: &[&str] = &[
+    "backend",
+    "drivers",
+    "datapacks",
+    "quarantine",
+    "backups",
+    "data-location.json",
+    "wayland.json",
+    STAGING,
+    PREVIOUS,
+];
+// Browser and Python caches, and the engine's own rolling copies of its
+// database, at any depth
+const SKIPPED_DIRS: &[&str] = &[
+    "backups",
+    "Cache",
+    "Code Cache",
+    "GPUCache",
+    "ShaderCache",
+    "GrShaderCache",
+    "DawnCache",
+    "CacheStorage",
+    "__pycache__",
+];
+// Never rolled back by a restore (audit.rs)
+const KEPT_FILES: &[&str] = &["audit.log"];
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+// One backup or restore at a time
+static BUSY: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManifestFile {
+    // "config/..." or "data/...", with forward slashes
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub app_version: String,
+    // "config", and "data" unless the platform keeps both in one folder
+    pub roots: Vec<String>,
+    pub files: Vec<ManifestFile>,
+    // Files that couldn't be read, with why
+    #[serde(default)]
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub name: String,
+    pub size: u64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub automatic: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupStatus {
+    pub settings: settings::BackupSettings,
+    pub folder: Option<PathBuf>,
+    // Newest first
+    pub backups: Vec<BackupInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupSummary {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub app_version: String,
+    pub files: usize,
+    pub size: u64,
+    pub skipped: usize,
+}
+
+struct Root {
+    name: &'static str,
+    dir: PathBuf,
+}
+
+fn roots(app: &AppHandle) -> Result<Vec<Root>, String> {
+    let config = paths::base_config_dir(app)?;
+    let data = paths::base_data_dir(app)?;
+    let mut roots = vec![Root {
+        name: "config",
+        dir: config.clone(),
+    }];
+    if data != config {
+        roots.push(Root {
+            name: "data",
+            dir: data,
+        });
+    }
+    Ok(roots)
+}
+
+fn folder(app: &AppHandle) -> Result<PathBuf, String> {
+    match settings::get(app).backup.folder {
+        Some(folder) => Ok(folder),
+        None => Ok(paths::base_data_dir(app)?.join("backups")),
+    }
+}
+
+fn is_transient(name: &str) -> bool {
+    [".partial", "-wal", "-shm", "-journal"]
+        .iter()
+        .any(|s| name.ends_with(s))
+        || name.starts_with("Singleton")
+        || name == "LOCK"
+}
+
+// Files under `root` to back up, relative to it.
+fn walk(root: &Path, skip: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("{}: {e}", dir.display())),
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let top = dir == root;
+            if (top && LEFT_OUT.contains(&name.as_str())) || path == skip {
+                continue;
+            }
+            let Ok(kind) = entry.file_type() else {
+                continue;
+            };
+            if kind.is_dir() && !SKIPPED_DIRS.contains(&name.as_str()) {
+                stack.push(path);
+            } else if kind.is_file() && !is_transient(&name) && !KEPT_FILES.contains(&name.as_str())
+            {
+                files.push(path.strip_prefix(root).unwrap().to_path_buf());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn is_sqlite(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .is_ok_and(|_| header == SQLITE_HEADER)
+}
+
+// A consistent copy of a database that may be in use.
+fn snapshot(source: &Path, scratch: &Path) -> Result<PathBuf, String> {
+    let target = scratch.join(".snapshot.db");
+    let _ = fs::remove_file(&target);
+    let db = Connection::open_with_flags(source, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| e.to_string())?;
+    db.busy_timeout(Duration::from_secs(5))
+        .map_err(|e| e.to_string())?;
+    db.execute("VACUUM INTO ?1", [target.to_string_lossy()])
+        .map_err(|e| e.to_string())?;
+    Ok(target)
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// Copies `from` into `to` and hashes it on the way.
+fn copy_hashed(from: &mut impl Read, to: &mut impl Write) -> std::io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let read = from.read(&mut buffer)?;
+        if read == 0 {
+            return Ok((size, hex(&hasher.finalize())));
+        }
+        hasher.update(&buffer[..read]);
+        to.write_all(&buffer[..read])?;
+        size += read as u64;
+    }
+}
+
+fn add(
+    zip: &mut ZipWriter<File>,
+    entry: &str,
+    source: &Path,
+    scratch: &Path,
+) -> Result<ManifestFile, String> {
+    // An encrypted history goes in decrypted: the key stays in this
+    // machine's keychain, and the restored file is encrypted again on use
+    let encrypted = source.file_name().is_some_and(|n| n == history::FILE)
+        && historycrypt::is_encrypted(source);
+    let copy = (is_sqlite(source) || encrypted)
+        .then(|| {
+            if encrypted {
+                let target = scratch.join(".snapshot.db");
+                historycrypt::decrypted_copy(source, &target).map(|_| target)
+            } else {
+                snapshot(source, scratch)
+            }
+        })
+        .and_then(|s| {
+            s.inspect_err(|e| warn!(file = entry, "database copied as is: {e}"))
+                .ok()
+        });
+    let mut file = File::open(copy.as_deref().unwrap_or(source)).map_err(|e| e.to_string())?;
+    let large = file.metadata().map_err(|e| e.to_string())?.len() >= u64::from(u32::MAX);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(large);
+    zip.start_file(entry, options).map_err(|e| e.to_string())?;
+    let (size, sha256) = copy_hashed(&mut file, zip).map_err(|e| e.to_string())?;
+    if let Some(copy) = copy {
+        let _ = fs::remove_file(copy);
+    }
+    Ok(ManifestFile {
+        path: entry.to_string(),
+        size,
+        sha256,
+    })
+}
+
+fn info_for(path: PathBuf) -> Option<BackupInfo> {
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    if !name.starts_with(PREFIX) || !name.ends_with(".zip") {
+        return None;
+    }
+    let meta = fs::metadata(&path).ok()?;
+    Some(BackupInfo {
+        size: meta.len(),
+        created_at: meta.modified().ok().map(DateTime::<Utc>::from),
+        automatic: name.ends_with(&format!("{AUTO_SUFFIX}.zip")),
+        name,
+        path,
+    })
+}
+
+fn list(app: &AppHandle) -> Vec<BackupInfo> {
+    let mut backups: Vec<BackupInfo> = folder(app)
+        .ok()
+        .and_then(|f| fs::read_dir(f).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| info_for(e.path()))
+        .collect();
+    // The name carries the time
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    backups
+}
+
+fn prune(app: &AppHandle) {
+    let keep = settings::get(app).backup.keep.max(1) as usize;
+    for old in list(app).into_iter().filter(|b| b.automatic).skip(keep) {
+        match fs::remove_file(&old.path) {
+            Ok(()) => info!(name = old.name, "old backup removed"),
+            Err(e) => warn!(name = old.name, "old backup not removed: {e}"),
+        }
+    }
+}
+
+pub fn create(app: &AppHandle, automatic: bool) -> Result<BackupInfo, String> {
+    let _busy = BUSY
+        .try_lock()
+        .map_err(|_| "a backup or restore is already running")?;
+    let folder = folder(app)?;
+    fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+    let name = format!(
+        "{PREFIX}{}{}.zip",
+        Local::now().format("%Y%m%d-%H%M%S"),
+        if automatic { AUTO_SUFFIX } else { "" }
+    );
+    let partial = folder.join(format!("{name}.partial"));
+    let mut zip = ZipWriter::new(File::create(&partial).map_err(|e| e.to_string())?);
+    let roots = roots(app)?;
+    let mut manifest = Manifest {
+        format: FORMAT.into(),
+        format_version: FORMAT_VERSION,
+        created_at: Utc::now(),
+        app_version: app.package_info().version.to_string(),
+        roots: roots.iter().map(|r| r.name.to_string()).collect(),
+        files: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let written = (|| {
+        for root in &roots {
+            for rel in walk(&root.dir, &folder)? {
+                let entry = format!("{}/{}", root.name, rel.to_string_lossy().replace('\\', "/"));
+                match add(&mut zip, &entry, &root.dir.join(&rel), &folder) {
+                    Ok(file) => manifest.files.push(file),
+                    Err(e) => {
+                        warn!(file = entry, "left out of the backup: {e}");
+                        manifest.skipped.push(format!("{entry}: {e}"));
+                    }
+                }
+            }
+        }
+        zip.start_file(MANIFEST, SimpleFileOptions::default())
+            .map_err(|e| e.to_string())?;
+        let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+        zip.write_all(&json).map_err(|e| e.to_string())?;
+        zip.finish().map_err(|e| e.to_string())?;
+        Ok::<_, String>(())
+    })();
+    if let Err(e) = written {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    let path = folder.join(&name);
+    fs::rename(&partial, &path).map_err(|e| e.to_string())?;
+    info!(
+        name,
+        files = manifest.files.len(),
+        skipped = manifest.skipped.len(),
+        "backup made"
+    );
+    let now = manifest.created_at;
+    let _ = settings::update(app, |s| s.backup.last_backup_at = Some(now));
+    if automatic {
+        prune(app);
+    }
+    let _ = app.emit(BACKUPS_CHANGED, ());
+    info_for(path).ok_or_else(|| "the backup disappeared".into())
+}
+
+fn read_manifest(archive: &mut ZipArchive<File>) -> Result<Manifest, String> {
+    let mut file = archive
+        .by_name(MANIFEST)
+        .map_err(|_| "not a currobot backup")?;
+    let mut json = String::new();
+    file.read_to_string(&mut json).map_err(|e| e.to_string())?;
+    let manifest: Manifest = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    if manifest.format != FORMAT {
+        return Err("not a currobot backup".into());
+    }
+    if manifest.format_version > FORMAT_VERSION {
+        return Err("this backup is from a newer version of the app; update it first".into());
+    }
+    Ok(manifest)
+}
+
+// Where a file of the archive goes, if it's somewhere it may go.
+fn destination(path: &str, roots: &[Root]) -> Result<PathBuf, String> {
+    let (root, rel) = path.split_once('/').unwrap_or((path, ""));
+    let root = roots
+        .iter()
+        .find(|r| r.name == root)
+        .ok_or_else(|| format!("{path}: unknown folder"))?;
+    let rel = Path::new(rel);
+    let top = rel.components().next();
+    let safe = rel.components().all(|c| matches!(c, Component::Normal(_)))
+        && top.is_some_and(|c| !LEFT_OUT.contains(&c.as_os_str().to_string_lossy().as_ref()));
+    if !safe {
+        return Err(format!("{path}: not a path a backup may write"));
+    }
+    Ok(root.dir.join(STAGING).join(rel))
+}
+
+// Unpacks every file into the staging folders, checking it against the
+// manifest.
+fn extract(
+    archive: &mut ZipArchive<File>,
+    manifest: &Manifest,
+    roots: &[Root],
+) -> Result<(), String> {
+    for entry in &manifest.files {
+        let target = destination(&entry.path, roots)?;
+        let mut file = archive
+            .by_name(&entry.path)
+            .map_err(|_| format!("{}: missing from the archive", entry.path))?;
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let mut out = File::create(&target).map_err(|e| e.to_string())?;
+        let (size, sha256) =
+            copy_hashed(&mut file, &mut out).map_err(|e| format!("{}: {e}", entry.path))?;
+        if size != entry.size || sha256 != entry.sha256 {
+            return Err(format!("{}: damaged", entry.path));
+        }
+    }
+    Ok(())
+}
+
+// Windows holds on to a folder for a moment after the process using it exits.
+fn rename(from: &Path, to: &Path) -> Result<(), String> {
+    let mut tries = 0;
+    loop {
+        match fs::rename(from, to) {
+            Ok(()) => return Ok(()),
+            Err(_) if tries < 10 => {
+                tries += 1;
+                thread::sleep(Duration::from_millis(500));
+            }
+            Err(e) => return Err(format!("{}: {e}", from.display())),
+        }
+    }
+}
+
+fn names(dir: &Path) -> Result<Vec<String>, String> {
+    Ok(fs::read_dir(dir)
+        .map_err(|e| format!("{}: {e}", dir.display()))?
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect())
+}
+
+fn swap_root(root: &Root, moves: &mut Vec<(PathBuf, PathBuf)>) -> Result<(), String> {
+    let staging = root.dir.join(STAGING);
+    let previous = root.dir.join(PREVIOUS);
+    reset::remove_dir(&previous)?;
+    fs::create_dir_all(&previous).map_err(|e| e.to_string())?;
+    let mut step = |from: PathBuf, to: PathBuf| {
+        rename(&from, &to)?;
+        moves.push((from, to));
+        Ok::<_, String>(())
+    };
+    for name in names(&root.dir)? {
+        if !LEFT_OUT.contains(&name.as_str()) && !KEPT_FILES.contains(&name.as_str()) {
+            step(root.dir.join(&name), previous.join(&name))?;
+        }
+    }
+    for name in names(&staging)? {
+        step(staging.join(&name), root.dir.join(&name))?;
+    }
+    // Profiles' audit logs go back where they were
+    for rel in kept_files(&previous) {
+        if let Some(dir) = root.dir.join(&rel).parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        step(previous.join(&rel), root.dir.join(&rel))?;
+    }
+    Ok(())
+}
+
+// KEPT_FILES anywhere under `root`, relative to it.
+fn kept_files(root: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.file_type().is_ok_and(|k| k.is_dir()) {
+                stack.push(path);
+            } else if KEPT_FILES.contains(&name.as_str()) {
+                found.push(path.strip_prefix(root).unwrap().to_path_buf());
+            }
+        }
+    }
+    found
+}
+
+// Puts the staged files in place, or everything back as it was.
+fn swap(roots: &[Root]) -> Result<(), String> {
+    let mut moves = Vec::new();
+    let result = roots
+        .iter()
+        .try_for_each(|root| swap_root(root, &mut moves));
+    if result.is_err() {
+        for (from, to) in moves.iter().rev() {
+            if let Err(e) = fs::rename(to, from) {
+                warn!("restore not undone for {}: {e}", to.display());
+            }
+        }
+    }
+    result
+}
+
+fn inspect(path: &Path) -> Result<BackupSummary, String> {
+    let mut archive =
+        ZipArchive::new(File::open(path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    let manifest = read_manifest(&mut archive)?;
+    Ok(BackupSummary {
+        path: path.to_path_buf(),
+        created_at: manifest.created_at,
+        app_version: manifest.app_version,
+        files: manifest.files.len(),
+        size: manifest.files.iter().map(|f| f.size).sum(),
+        skipped: manifest.skipped.len(),
+    })
+}
+
+fn restore(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let _busy = BUSY
+        .try_lock()
+        .map_err(|_| "a backup or restore is already running")?;
+    let mut archive =
+        ZipArchive::new(File::open(path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    let manifest = read_manifest(&mut archive)?;
+    let roots = roots(app)?;
+    let here: Vec<&str> = roots.iter().map(|r| r.name).collect();
+    if manifest.roots != here {
+        return Err(
+            "this backup comes from a system that keeps settings and data differently".into(),
+        );
+    }
+    let clean = || {
+        for root in &roots {
+            let _ = reset::remove_dir(&root.dir.join(STAGING));
+        }
+    };
+    clean();
+    if let Err(e) = extract(&mut archive, &manifest, &roots) {
+        clean();
+        return Err(format!("the backup can't be restored: {e}"));
+    }
+    audit::record(
+        app,
+        Action::BackupRestored,
+        json!({ "created_at": manifest.created_at, "files": manifest.files.len() }),
+    );
+    crate::kill_backend(app);
+    if let Err(e) = swap(&roots) {
+        clean();
+        let _ = crate::restart_backend(app);
+        return Err(format!("restore failed, nothing was changed: {e}"));
+    }
+    clean();
+    info!(
+        created_at = %manifest.created_at,
+        files = manifest.files.len(),
+        "backup restored"
+    );
+    app.restart();
+}
+
+fn is_due(cfg: &settings::BackupSettings, now: DateTime<Utc>) -> bool {
+    cfg.enabled
+        && cfg
+            .last_backup_at
+            .is_none_or(|last| now - last >= Span::hours(i64::from(cfg.interval_hours.max(1))))
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+        if !is_due(&settings::get(&app).backup, Utc::now()) {
+            continue;
+        }
+        if let Err(e) = create(&app, true) {
+            warn!("scheduled backup failed: {e}");
+            notifications::notify_route(&app, &t("backup-failed-title"), &e, "/settings");
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_backups(app: AppHandle) -> BackupStatus {
+    BackupStatus {
+        settings: settings::get(&app).backup,
+        folder: folder(&app).ok(),
+        backups: list(&app),
+    }
+}
+
+#[tauri::command]
+pub fn set_backup_settings(
+    app: AppHandle,
+    enabled: bool,
+    interval_hours: u32,
+    keep: u32,
+    folder: Option<String>,
+) -> Result<(), String> {
+    if interval_hours == 0 || keep == 0 {
+        return Err("the interval and the number kept must be at least 1".into());
+    }
+    let folder = folder
+        .map(|f| PathBuf::from(f.trim()))
+        .filter(|f| !f.as_os_str().is_empty());
+    if folder.as_ref().is_some_and(|f| !f.is_absolute()) {
+        return Err("choose an absolute path".into());
+    }
+    settings::update(&app, |s| {
+        s.backup.enabled = enabled;
+        s.backup.interval_hours = interval_hours;
+        s.backup.keep = keep;
+        s.backup.folder = folder;
+    })?;
+    let _ = app.emit(BACKUPS_CHANGED, ());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn create_backup(app: AppHandle) -> Result<BackupInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || create(&app, false))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// What a backup holds, for the confirmation before restoring it. `path`
+// None asks for the file.
+#[tauri::command]
+pub async fn inspect_backup(
+    app: AppHandle,
+    path: Option<String>,
+) -> Result<Option<BackupSummary>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let mut dialog = app
+                    .dialog()
+                    .file()
+                    .set_title(t("backup-restore-title"))
+                    .add_filter(t("backup-filter"), &["zip"]);
+                if let Ok(folder) = folder(&app) {
+                    dialog = dialog.set_directory(folder);
+                }
+                let Some(chosen) = dialog.blocking_pick_file() else {
+                    return Ok(None);
+                };
+                chosen.into_path().map_err(|e| e.to_string())?
+            }
+        };
+        inspect(&path).map(Some)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Relaunches the app once the backup is in place.
+#[tauri::command]
+pub async fn restore_backup(app: AppHandle, path: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || restore(&app, Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jobbot-backup-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn put(root: &Path, rel: &str, text: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, text).unwrap();
+    }
+
+    fn manifest(files: Vec<ManifestFile>) -> Manifest {
+        Manifest {
+            format: FORMAT.into(),
+            format_version: FORMAT_VERSION,
+            created_at: Utc::now(),
+            app_version: "1.0.0".into(),
+            roots: vec!["config".into()],
+            files,
+            skipped: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn transient_and_machine_files_are_left_out() {
+        let root = temp("walk");
+        put(&root, "settings.json", "{}");
+        put(&root, "profiles/default/history.db-wal", "");
+        put(&root, "profiles/default/notes.txt", "notes");
+        put(&root, "profiles/default/audit.log", "");
+        put(&root, "browsers/work/Cache/data_0", "");
+        put(&root, "browsers/work/SingletonLock", "");
+        put(&root, "drivers/chromedriver", "");
+        put(&root, "backups/currobot-backup-1.zip", "");
+        let files: Vec<String> = walk(&root, &root.join("elsewhere"))
+            .unwrap()
+            .iter()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(files, ["profiles/default/notes.txt", "settings.json"]);
+        assert!(walk(&root.join("missing"), &root).unwrap().is_empty());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn archive_paths_stay_in_their_folder() {
+        let roots = [Root {
+            name: "config",
+            dir: PathBuf::from("base"),
+        }];
+        assert_eq!(
+            destination("config/profiles/a.json", &roots),
+            Ok(Path::new("base").join(STAGING).join("profiles/a.json"))
+        );
+        assert!(destination("data/history.db", &roots).is_err());
+        assert!(destination("config/../outside", &roots).is_err());
+        assert!(destination("config/drivers/chromedriver", &roots).is_err());
+        assert!(destination("config", &roots).is_err());
+    }
+
+    #[test]
+    fn hashes_what_it_copies() {
+        let mut out = Vec::new();
+        let (size, sha256) = copy_hashed(&mut &b"abc"[..], &mut out).unwrap();
+        assert_eq!(out, b"abc");
+        assert_eq!(size, 3);
+        assert_eq!(
+            sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn due_by_interval() {
+        let now = Utc::now();
+        let mut cfg = settings::BackupSettings::default();
+        assert!(!is_due(&cfg, now));
+        cfg.enabled = true;
+        assert!(is_due(&cfg, now));
+        cfg.last_backup_at = Some(now - Span::hours(23));
+        assert!(!is_due(&cfg, now));
+        cfg.last_backup_at = Some(now - Span::hours(24));
+        assert!(is_due(&cfg, now));
+    }
+
+    #[test]
+    fn restores_and_keeps_the_audit_log() {
+        let source = temp("source");
+        put(&source, "settings.json", "{\"theme\":\"dark\"}");
+        put(&source, "profiles/default/notes.txt", "notes");
+        let scratch = temp("scratch");
+        let archive = scratch.join("backup.zip");
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        let mut files = Vec::new();
+        for rel in walk(&source, &scratch).unwrap() {
+            let entry = format!("config/{}", rel.to_string_lossy().replace('\\', "/"));
+            files.push(add(&mut zip, &entry, &source.join(&rel), &scratch).unwrap());
+        }
+        zip.start_file(MANIFEST, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&serde_json::to_vec(&manifest(files)).unwrap())
+            .unwrap();
+        zip.finish().unwrap();
+
+        let target = temp("target");
+        put(&target, "settings.json", "{}");
+        put(&target, "stale.json", "{}");
+        put(&target, "profiles/default/audit.log", "kept");
+        put(&target, "drivers/chromedriver", "driver");
+        let roots = [Root {
+            name: "config",
+            dir: target.clone(),
+        }];
+        let mut archive = ZipArchive::new(File::open(&archive).unwrap()).unwrap();
+        let manifest = read_manifest(&mut archive).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        extract(&mut archive, &manifest, &roots).unwrap();
+        swap(&roots).unwrap();
+
+        let read = |rel: &str| fs::read_to_string(target.join(rel)).ok();
+        assert_eq!(
+            read("settings.json").as_deref(),
+            Some("{\"theme\":\"dark\"}")
+        );
+        assert_eq!(read("profiles/default/notes.txt").as_deref(), Some("notes"));
+        assert_eq!(read("profiles/default/audit.log").as_deref(), Some("kept"));
+        assert_eq!(read("drivers/chromedriver").as_deref(), Some("driver"));
+        assert_eq!(read("stale.json"), None);
+        assert!(target.join(PREVIOUS).join("stale.json").exists());
+        for dir in [source, scratch, target] {
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn damaged_or_foreign_archives() {
+        let dir = temp("damaged");
+        let path = dir.join("backup.zip");
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file("config/settings.json", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"{}").unwrap();
+        zip.start_file(MANIFEST, SimpleFileOptions::default())
+            .unwrap();
+        let files = vec![ManifestFile {
+            path: "config/settings.json".into(),
+            size: 2,
+            sha256: "0".repeat(64),
+        }];
+        zip.write_all(&serde_json::to_vec(&manifest(files)).unwrap())
+            .unwrap();
+        zip.finish().unwrap();
+        let roots = [Root {
+            name: "config",
+            dir: dir.clone(),
+        }];
+        let mut archive = ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let manifest = read_manifest(&mut archive).unwrap();
+        assert_eq!(
+            extract(&mut archive, &manifest, &roots),
+            Err("config/settings.json: damaged".into())
+        );
+
+        let foreign = dir.join("other.zip");
+        let mut zip = ZipWriter::new(File::create(&foreign).unwrap());
+        zip.start_file("readme.txt", SimpleFileOptions::default())
+            .unwrap();
+        zip.finish().unwrap();
+        let mut archive = ZipArchive::new(File::open(&foreign).unwrap()).unwrap();
+        assert_eq!(
+            read_manifest(&mut archive).err().as_deref(),
+            Some("not a currobot backup")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
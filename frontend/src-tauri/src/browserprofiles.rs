@@ -0,0 +1,348 @@
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    audit::{self, Action},
+    backend,
+    reset::remove_dir,
+};
+
+// Browser profiles the engine automates job sites in, one per site account,
+// in `browser-profiles` in the profile's data folder, so its cookies and
+// local storage never mix with the user's own browser. Each profile is a
+// Chromium user data folder named by its id; `profiles.json` is the
+// catalogue, and the engine reads it too (backend/scrapers/browser_pool.py)
+// to launch a site in its active profile, finding the folder through
+// BROWSER_PROFILES_ENV.
+pub const BROWSER_PROFILES_ENV: &str = "JOBBOT_BROWSER_PROFILES_DIR";
+pub const BROWSER_PROFILES_CHANGED: &str = "browser-profiles-changed";
+const CATALOG: &str = "profiles.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BrowserProfile {
+    pub id: String,
+    // As the engine names it, e.g. "linkedin"
+    pub site: String,
+    // Which of the user's accounts on the site; only a label
+    pub account: String,
+    // The one the engine uses for the site
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub reset_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Catalog {
+    profiles: Vec<BrowserProfile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrowserProfileStatus {
+    #[serde(flatten)]
+    pub profile: BrowserProfile,
+    pub size_bytes: u64,
+    // A browser has the folder open
+    pub in_use: bool,
+}
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+pub fn root(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::paths::data_dir(app)?.join("browser-profiles"))
+}
+
+fn load(root: &Path) -> Catalog {
+    fs::read_to_string(root.join(CATALOG))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+// Written beside and renamed over, so the engine never reads half a file.
+fn save(root: &Path, catalog: &Catalog) -> Result<(), String> {
+    fs::create_dir_all(root).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(catalog).map_err(|e| e.to_string())?;
+    let temp = root.join(format!("{CATALOG}.tmp"));
+    fs::write(&temp, json).map_err(|e| e.to_string())?;
+    fs::rename(temp, root.join(CATALOG)).map_err(|e| e.to_string())
+}
+
+fn change<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&Path, &mut Catalog) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = LOCK.lock().unwrap();
+    let root = root(app)?;
+    let mut catalog = load(&root);
+    let result = f(&root, &mut catalog)?;
+    save(&root, &catalog)?;
+    let _ = app.emit(BROWSER_PROFILES_CHANGED, ());
+    Ok(result)
+}
+
+fn new_id(site: &str) -> String {
+    format!("{site}-{}", Local::now().format("%Y%m%d-%H%M%S%3f"))
+}
+
+fn size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => size(&entry.path()),
+            Ok(t) if t.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+// Asks the engine to close the site's browser, then checks nothing else has
+// the folder open. Reset and wipe would otherwise pull files from under it.
+fn release(profile: &BrowserProfile, dir: &Path) -> Result<(), String> {
+    if let Err(e) = backend::post_json(
+        &format!("/api/browser-profiles/{}/release", profile.site),
+        &json!({}),
+    ) {
+        warn!(site = profile.site, error = %e, "could not ask the engine to release a browser profile");
+    }
+    if platform::in_use(dir) {
+        return Err(format!(
+            "the {} profile is open in a browser; stop the engine's session and try again",
+            profile.site
+        ));
+    }
+    Ok(())
+}
+
+fn find<'a>(catalog: &'a mut Catalog, id: &str) -> Result<&'a mut BrowserProfile, String> {
+    catalog
+        .profiles
+        .iter_mut()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("no browser profile {id}"))
+}
+
+#[tauri::command]
+pub fn list_browser_profiles(app: AppHandle) -> Result<Vec<BrowserProfileStatus>, String> {
+    let root = root(&app)?;
+    Ok(load(&root)
+        .profiles
+        .into_iter()
+        .map(|profile| {
+            let dir = root.join(&profile.id);
+            BrowserProfileStatus {
+                size_bytes: size(&dir),
+                in_use: platform::in_use(&dir),
+                profile,
+            }
+        })
+        .collect())
+}
+
+// The site's first profile becomes its active one.
+#[tauri::command]
+pub fn create_browser_profile(
+    app: AppHandle,
+    site: String,
+    account: String,
+) -> Result<BrowserProfile, String> {
+    let site = site.trim().to_lowercase();
+    if site.is_empty() || !site.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err("a site is a name like \"linkedin\"".into());
+    }
+    let account = account.trim().to_string();
+    change(&app, |root, catalog| {
+        let profile = BrowserProfile {
+            id: new_id(&site),
+            active: !catalog.profiles.iter().any(|p| p.site == site),
+            site,
+            account,
+            created_at: Utc::now(),
+            reset_at: None,
+        };
+        fs::create_dir_all(root.join(&profile.id)).map_err(|e| e.to_string())?;
+        info!(id = profile.id, "browser profile created");
+        catalog.profiles.push(profile.clone());
+        Ok(profile)
+    })
+}
+
+// Takes effect the next time the engine opens the site.
+#[tauri::command]
+pub fn set_active_browser_profile(app: AppHandle, id: String) -> Result<BrowserProfile, String> {
+    change(&app, |_, catalog| {
+        let site = find(catalog, &id)?.site.clone();
+        for profile in catalog.profiles.iter_mut().filter(|p| p.site == site) {
+            profile.active = profile.id == id;
+        }
+        Ok(find(catalog, &id)?.clone())
+    })
+}
+
+// Empties the folder, signing the account out; the profile stays.
+#[tauri::command]
+pub async fn reset_browser_profile(app: AppHandle, id: String) -> Result<BrowserProfile, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        change(&app, |root, catalog| {
+            let profile = find(catalog, &id)?;
+            let dir = root.join(&profile.id);
+            release(profile, &dir)?;
+            remove_dir(&dir)?;
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            profile.reset_at = Some(Utc::now());
+            audit::record(
+                &app,
+                Action::DataReset,
+                json!({ "browser_profile": profile.id, "site": profile.site }),
+            );
+            info!(id = profile.id, "browser profile reset");
+            Ok(profile.clone())
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Removes the folder and the profile. If it was the site's active one, the
+// site's next profile takes over, or without one the engine goes back to its
+// own sessions for the site.
+#[tauri::command]
+pub async fn wipe_browser_profile(app: AppHandle, id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        change(&app, |root, catalog| {
+            let profile = find(catalog, &id)?.clone();
+            let dir = root.join(&profile.id);
+            release(&profile, &dir)?;
+            remove_dir(&dir)?;
+            catalog.profiles.retain(|p| p.id != id);
+            if profile.active {
+                if let Some(next) = catalog.profiles.iter_mut().find(|p| p.site == profile.site) {
+                    next.active = true;
+                }
+            }
+            audit::record(
+                &app,
+                Action::DataReset,
+                json!({ "browser_profile": profile.id, "site": profile.site, "wiped": true }),
+            );
+            info!(id = profile.id, "browser profile wiped");
+            Ok(())
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Chromium marks the folder it has open with a SingletonLock symlink whose
+// target ends in its pid; a crash leaves it behind, so the pid is checked.
+#[cfg(unix)]
+mod platform {
+    use std::{fs, path::Path};
+
+    pub fn in_use(dir: &Path) -> bool {
+        let Ok(target) = fs::read_link(dir.join("SingletonLock")) else {
+            return false;
+        };
+        let Some(pid) = target
+            .to_string_lossy()
+            .rsplit('-')
+            .next()
+            .and_then(|p| p.parse::<libc::pid_t>().ok())
+        else {
+            return false;
+        };
+        // Signal 0 only checks the process exists; EPERM means it does
+        let alive = unsafe { libc::kill(pid, 0) } == 0;
+        alive || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+}
+
+// Chromium holds `lockfile` open without sharing delete while it runs, so
+// removing it only fails then; a stale one from a crash just goes.
+#[cfg(windows)]
+mod platform {
+    use std::{fs, path::Path};
+
+    pub fn in_use(dir: &Path) -> bool {
+        let lock = dir.join("lockfile");
+        lock.exists() && fs::remove_file(lock).is_err()
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod platform {
+    use std::path::Path;
+
+    pub fn in_use(_dir: &Path) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str) -> BrowserProfile {
+        BrowserProfile {
+            id: id.into(),
+            site: "linkedin".into(),
+            account: "work".into(),
+            active: true,
+            created_at: Utc::now(),
+            reset_at: None,
+        }
+    }
+
+    #[test]
+    fn catalog_round_trip() {
+        let root = std::env::temp_dir().join(format!("jobbot-browsers-{}", std::process::id()));
+        assert!(load(&root).profiles.is_empty());
+        let mut catalog = Catalog {
+            profiles: vec![profile("linkedin-1"), profile("linkedin-2")],
+        };
+        save(&root, &catalog).unwrap();
+        assert_eq!(load(&root).profiles.len(), 2);
+
+        find(&mut catalog, "linkedin-2").unwrap().active = false;
+        assert!(!catalog.profiles[1].active);
+        assert!(find(&mut catalog, "indeed-1").is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn folder_sizes() {
+        let root = std::env::temp_dir().join(format!("jobbot-browser-size-{}", std::process::id()));
+        fs::create_dir_all(root.join("Default/Cache")).unwrap();
+        fs::write(root.join("Local State"), [0u8; 100]).unwrap();
+        fs::write(root.join("Default/Cache/data_0"), [0u8; 50]).unwrap();
+        assert_eq!(size(&root), 150);
+        assert_eq!(size(&root.join("missing")), 0);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn open_while_its_browser_runs() {
+        let root = std::env::temp_dir().join(format!("jobbot-browser-lock-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        assert!(!platform::in_use(&root));
+        let lock = root.join("SingletonLock");
+        std::os::unix::fs::symlink(format!("host-{}", std::process::id()), &lock).unwrap();
+        assert!(platform::in_use(&root));
+        // Left by a crashed browser
+        fs::remove_file(&lock).unwrap();
+        std::os::unix::fs::symlink("host-2147483646", &lock).unwrap();
+        assert!(!platform::in_use(&root));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,598 @@
+use chrono::{DateTime, Duration, Local, NaiveDateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+use tracing::{info, warn};
+
+use crate::{
+    history,
+    i18n::{t, t_args},
+    reminders, tz,
+};
+
+// Interviews and follow-up reminders, kept in the history database beside
+// the application they're about, and handed to calendar apps as iCalendar
+// (.ics) files: one event, or the whole calendar. Each event keeps its UID
+// and counts its edits, so importing a newer export updates the event
+// instead of adding a second one. An interview can be set in a time zone,
+// the recruiter's, and keeps its wall-clock time there; reminders set for
+// "9:00" keep it wherever the user is (tz.rs).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Interview,
+    FollowUp,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Interview => "interview",
+            Kind::FollowUp => "follow_up",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        if s == "interview" {
+            Kind::Interview
+        } else {
+            Kind::FollowUp
+        }
+    }
+}
+
+// Either `starts_at`, or `local_time` in `timezone` (here when there's none).
+#[derive(Debug, Deserialize)]
+pub struct CalendarEventInput {
+    pub application_id: Option<i64>,
+    pub kind: Kind,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub local_time: Option<NaiveDateTime>,
+    pub timezone: Option<String>,
+    pub duration_minutes: u32,
+    pub location: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarEvent {
+    pub id: i64,
+    pub application_id: Option<i64>,
+    pub kind: Kind,
+    pub starts_at: DateTime<Utc>,
+    // What it was set as, when it was set as a wall-clock time
+    pub local_time: Option<NaiveDateTime>,
+    pub timezone: Option<String>,
+    pub duration_minutes: u32,
+    pub location: Option<String>,
+    pub notes: Option<String>,
+    pub sequence: u32,
+    pub created_at: String,
+    pub updated_at: String,
+    // When the reminder went off, and when the user was done with a follow-up
+    pub notified_at: Option<String>,
+    pub done_at: Option<String>,
+    // From the application, when there is one
+    pub company: Option<String>,
+    pub role: Option<String>,
+    pub url: Option<String>,
+}
+
+const SELECT: &str = "
+SELECT e.*, a.company, a.role, a.url
+FROM calendar_events e LEFT JOIN applications a ON a.id = e.application_id";
+
+fn event(r: &Row) -> rusqlite::Result<CalendarEvent> {
+    let starts_at: String = r.get("starts_at")?;
+    Ok(CalendarEvent {
+        id: r.get("id")?,
+        application_id: r.get("application_id")?,
+        kind: Kind::parse(&r.get::<_, String>("kind")?),
+        starts_at: DateTime::parse_from_rfc3339(&starts_at)
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_default(),
+        local_time: r
+            .get::<_, Option<String>>("local_time")?
+            .and_then(|t| t.parse().ok()),
+        timezone: r.get("timezone")?,
+        duration_minutes: r.get("duration_minutes")?,
+        location: r.get("location")?,
+        notes: r.get("notes")?,
+        sequence: r.get("sequence")?,
+        created_at: r.get("created_at")?,
+        updated_at: r.get("updated_at")?,
+        notified_at: r.get("notified_at")?,
+        done_at: r.get("done_at")?,
+        company: r.get("company")?,
+        role: r.get("role")?,
+        url: r.get("url")?,
+    })
+}
+
+pub fn list(db: &Connection, application_id: Option<i64>) -> Result<Vec<CalendarEvent>, String> {
+    let mut statement = db
+        .prepare(&format!(
+            "{SELECT} WHERE ?1 IS NULL OR e.application_id = ?1 ORDER BY e.starts_at"
+        ))
+        .map_err(|e| e.to_string())?;
+    let events = statement
+        .query_map([application_id], event)
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string());
+    events
+}
+
+pub fn get(db: &Connection, id: i64) -> Result<CalendarEvent, String> {
+    db.query_row(&format!("{SELECT} WHERE e.id = ?1"), [id], event)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("no calendar event {id}"))
+}
+
+fn validate(input: &CalendarEventInput) -> Result<(), String> {
+    if input.duration_minutes == 0 || input.duration_minutes > 24 * 60 {
+        return Err("the duration must be between 1 minute and a day".into());
+    }
+    if let Some(zone) = &input.timezone {
+        if !tz::known(zone) {
+            return Err(format!("unknown time zone {zone}"));
+        }
+    }
+    Ok(())
+}
+
+pub fn format_local(wall: NaiveDateTime) -> String {
+    wall.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+// The instant, and the wall-clock time and zone to keep with it.
+fn when(input: &CalendarEventInput) -> Result<(DateTime<Utc>, Option<NaiveDateTime>), String> {
+    let zone = input.timezone.clone().unwrap_or_else(tz::local_zone);
+    match (input.local_time, input.starts_at) {
+        (Some(wall), _) => Ok((tz::resolve(&zone, wall)?, Some(wall))),
+        (None, Some(at)) if input.timezone.is_some() => {
+            Ok((at, Some(tz::zoned(&zone, at)?.local_time)))
+        }
+        (None, Some(at)) => Ok((at, None)),
+        (None, None) => Err("a start time is needed".into()),
+    }
+}
+
+// Works starts_at out again from the wall-clock time of the events that have
+// one, as the offset may have changed since: a tz update, or, for those kept
+// in the user's time, a move to another zone. How many moved.
+pub fn reschedule(db: &Connection) -> Result<usize, String> {
+    let here = tz::local_zone();
+    let mut statement = db
+        .prepare(
+            "SELECT id, starts_at, local_time, timezone FROM calendar_events
+             WHERE local_time IS NOT NULL AND done_at IS NULL",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map([], |r| {
+            Ok((
+                r.get::<_, i64>(0)?,
+                r.get::<_, String>(1)?,
+                r.get::<_, String>(2)?,
+                r.get::<_, Option<String>>(3)?,
+            ))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    let mut moved = 0;
+    for (id, starts_at, local_time, zone) in rows {
+        let Ok(wall) = local_time.parse::<NaiveDateTime>() else {
+            continue;
+        };
+        let at = match tz::resolve(zone.as_deref().unwrap_or(&here), wall) {
+            Ok(at) => at,
+            Err(e) => {
+                warn!(id, "calendar event not rescheduled: {e}");
+                continue;
+            }
+        };
+        let unchanged = DateTime::parse_from_rfc3339(&starts_at).is_ok_and(|t| t == at);
+        if !unchanged {
+            db.execute(
+                "UPDATE calendar_events SET starts_at = ?2, sequence = sequence + 1 WHERE id = ?1",
+                params![id, at.to_rfc3339()],
+            )
+            .map_err(|e| e.to_string())?;
+            moved += 1;
+        }
+    }
+    Ok(moved)
+}
+
+fn clean(text: &Option<String>) -> Option<String> {
+    text.as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+// iCalendar (RFC 5545) text.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// Lines longer than 75 bytes continue on the next, after a space.
+fn fold(line: &str, out: &mut String) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn stamp(t: DateTime<Utc>) -> String {
+    t.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+pub fn summary(e: &CalendarEvent) -> String {
+    let company = e.company.clone().filter(|c| !c.is_empty());
+    match (e.kind, company) {
+        (Kind::Interview, Some(company)) => {
+            t_args("calendar-interview-with", &[("company", company.into())])
+        }
+        (Kind::Interview, None) => t("calendar-interview"),
+        (Kind::FollowUp, Some(company)) => {
+            t_args("calendar-follow-up-with", &[("company", company.into())])
+        }
+        (Kind::FollowUp, None) => t("calendar-follow-up"),
+    }
+}
+
+fn vevent(e: &CalendarEvent, app_id: &str, out: &mut String) {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:currobot-event-{}@{app_id}", e.id),
+        format!("DTSTAMP:{}", stamp(Utc::now())),
+        format!("SEQUENCE:{}", e.sequence),
+        format!("DTSTART:{}", stamp(e.starts_at)),
+        format!(
+            "DTEND:{}",
+            stamp(e.starts_at + Duration::minutes(e.duration_minutes.into()))
+        ),
+        format!("SUMMARY:{}", escape(&summary(e))),
+    ];
+    if let Some(location) = &e.location {
+        lines.push(format!("LOCATION:{}", escape(location)));
+    }
+    let description: Vec<&str> = [e.role.as_deref(), e.notes.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .collect();
+    if !description.is_empty() {
+        lines.push(format!("DESCRIPTION:{}", escape(&description.join("\n\n"))));
+    }
+    if let Some(url) = e.url.as_deref().filter(|u| u.starts_with("http")) {
+        lines.push(format!("URL:{url}"));
+    }
+    // An hour's warning for an interview, none needed before a reminder
+    let before = match e.kind {
+        Kind::Interview => "-PT1H",
+        Kind::FollowUp => "PT0M",
+    };
+    lines.extend([
+        "BEGIN:VALARM".to_string(),
+        "ACTION:DISPLAY".to_string(),
+        format!("DESCRIPTION:{}", escape(&summary(e))),
+        format!("TRIGGER:{before}"),
+        "END:VALARM".to_string(),
+        "END:VEVENT".to_string(),
+    ]);
+    for line in lines {
+        fold(&line, out);
+    }
+}
+
+fn ics(events: &[CalendarEvent], app_id: &str) -> String {
+    let mut out = String::new();
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//currobot//Application calendar//EN",
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+    ] {
+        fold(line, &mut out);
+    }
+    fold(
+        &format!("X-WR-CALNAME:{}", escape(&t("calendar-name"))),
+        &mut out,
+    );
+    for e in events {
+        vevent(e, app_id, &mut out);
+    }
+    fold("END:VCALENDAR", &mut out);
+    out
+}
+
+fn save(app: &AppHandle, file_name: String, content: String) -> Result<Option<String>, String> {
+    let Some(chosen) = app
+        .dialog()
+        .file()
+        .set_title(t("calendar-export-title"))
+        .set_file_name(file_name)
+        .add_filter("iCalendar", &["ics"])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let path = chosen.into_path().map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(Some(path.display().to_string()))
+}
+
+// All of them, or an application's.
+#[tauri::command]
+pub async fn list_calendar_events(
+    app: AppHandle,
+    application_id: Option<i64>,
+) -> Result<Vec<CalendarEvent>, String> {
+    tauri::async_runtime::spawn_blocking(move || list(&history::open(&app)?, application_id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Adds the event, or with `id` replaces it.
+#[tauri::command]
+pub async fn save_calendar_event(
+    app: AppHandle,
+    id: Option<i64>,
+    mut event: CalendarEventInput,
+) -> Result<CalendarEvent, String> {
+    event.timezone = clean(&event.timezone);
+    validate(&event)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = history::open(&app)?;
+        let now = Utc::now().to_rfc3339();
+        let (starts_at, local_time) = when(&event)?;
+        let (starts_at, local_time) = (starts_at.to_rfc3339(), local_time.map(format_local));
+        let (location, notes) = (clean(&event.location), clean(&event.notes));
+        let id = match id {
+            Some(id) => {
+                let changed = db
+                    .execute(
+                        "UPDATE calendar_events SET application_id = ?2, kind = ?3,
+                            starts_at = ?4, duration_minutes = ?5, location = ?6, notes = ?7,
+                            updated_at = ?8, sequence = sequence + 1,
+                            notified_at = CASE WHEN starts_at = ?4 THEN notified_at END,
+                            local_time = ?9, timezone = ?10
+                         WHERE id = ?1",
+                        params![
+                            id,
+                            event.application_id,
+                            event.kind.as_str(),
+                            starts_at,
+                            event.duration_minutes,
+                            location,
+                            notes,
+                            now,
+                            local_time,
+                            event.timezone,
+                        ],
+                    )
+                    .map_err(|e| e.to_string())?;
+                if changed == 0 {
+                    return Err(format!("no calendar event {id}"));
+                }
+                id
+            }
+            None => {
+                db.execute(
+                    "INSERT INTO calendar_events (application_id, kind, starts_at,
+                        duration_minutes, location, notes, created_at, updated_at,
+                        local_time, timezone)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7, ?8, ?9)",
+                    params![
+                        event.application_id,
+                        event.kind.as_str(),
+                        starts_at,
+                        event.duration_minutes,
+                        location,
+                        notes,
+                        now,
+                        local_time,
+                        event.timezone,
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+                db.last_insert_rowid()
+            }
+        };
+        reminders::wake();
+        get(&db, id)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn delete_calendar_event(app: AppHandle, id: i64) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        history::open(&app)?
+            .execute("DELETE FROM calendar_events WHERE id = ?1", [id])
+            .map_err(|e| e.to_string())?;
+        reminders::wake();
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// One event as an .ics file; the path written, or None when cancelled.
+#[tauri::command]
+pub async fn export_calendar_event(app: AppHandle, id: i64) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let event = get(&history::open(&app)?, id)?;
+        let name = format!(
+            "{}-{}.ics",
+            event.kind.as_str().replace('_', "-"),
+            event.starts_at.with_timezone(&Local).format("%Y-%m-%d")
+        );
+        let content = ics(&[event], &app.config().identifier);
+        save(&app, name, content)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Every interview and reminder in one calendar.
+#[tauri::command]
+pub async fn export_calendar(app: AppHandle) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let events = list(&history::open(&app)?, None)?;
+        let content = ics(&events, &app.config().identifier);
+        let written = save(&app, "currobot-calendar.ics".into(), content)?;
+        if written.is_some() {
+            info!(count = events.len(), "calendar exported");
+        }
+        Ok(written)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(duration_minutes: u32, timezone: Option<&str>) -> CalendarEventInput {
+        CalendarEventInput {
+            application_id: None,
+            kind: Kind::Interview,
+            starts_at: None,
+            local_time: Some("2026-07-01T10:00:00".parse().unwrap()),
+            timezone: timezone.map(str::to_string),
+            duration_minutes,
+            location: None,
+            notes: None,
+        }
+    }
+
+    fn event(location: Option<&str>) -> CalendarEvent {
+        CalendarEvent {
+            id: 7,
+            application_id: Some(3),
+            kind: Kind::Interview,
+            starts_at: "2026-07-01T08:00:00Z".parse().unwrap(),
+            local_time: None,
+            timezone: None,
+            duration_minutes: 45,
+            location: location.map(str::to_string),
+            notes: Some("Bring the portfolio".into()),
+            sequence: 2,
+            created_at: String::new(),
+            updated_at: String::new(),
+            notified_at: None,
+            done_at: None,
+            company: Some("Acme".into()),
+            role: Some("Engineer".into()),
+            url: Some("https://acme.example/jobs/1".into()),
+        }
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        assert_eq!(
+            escape("Room 4; floor 2, left\\right\r\nthen"),
+            "Room 4\\; floor 2\\, left\\\\right\\nthen"
+        );
+    }
+
+    #[test]
+    fn long_lines_fold_between_characters() {
+        let mut out = String::new();
+        fold(&format!("DESCRIPTION:{}", "ñ".repeat(60)), &mut out);
+        let lines: Vec<&str> = out.split_terminator("\r\n").collect();
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| l.len() <= 75));
+        assert!(lines[1..].iter().all(|l| l.starts_with(' ')));
+        let joined: String = lines
+            .iter()
+            .map(|l| l.strip_prefix(' ').unwrap_or(l))
+            .collect();
+        assert_eq!(joined, format!("DESCRIPTION:{}", "ñ".repeat(60)));
+    }
+
+    #[test]
+    fn events_in_the_file() {
+        let text = ics(&[event(Some("Calle Mayor, 1"))], "com.currobot.app");
+        assert!(text.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(text.ends_with("END:VCALENDAR\r\n"));
+        for line in [
+            "UID:currobot-event-7@com.currobot.app",
+            "SEQUENCE:2",
+            "DTSTART:20260701T080000Z",
+            "DTEND:20260701T084500Z",
+            "LOCATION:Calle Mayor\\, 1",
+            "DESCRIPTION:Engineer\\n\\nBring the portfolio",
+            "URL:https://acme.example/jobs/1",
+            "TRIGGER:-PT1H",
+        ] {
+            assert!(text.contains(&format!("\r\n{line}\r\n")), "{line}");
+        }
+        assert!(!ics(&[event(None)], "id").contains("LOCATION:"));
+    }
+
+    #[test]
+    fn durations_and_zones_are_checked() {
+        assert!(validate(&input(45, None)).is_ok());
+        assert!(validate(&input(0, None)).is_err());
+        assert!(validate(&input(24 * 60 + 1, None)).is_err());
+        assert!(validate(&input(45, Some("Europe/Madrid"))).is_ok());
+        assert!(validate(&input(45, Some("../etc/passwd"))).is_err());
+    }
+
+    #[test]
+    fn wall_clock_times_in_their_zone() {
+        let (at, wall) = when(&input(45, Some("Europe/Madrid"))).unwrap();
+        assert_eq!(at.to_rfc3339(), "2026-07-01T08:00:00+00:00");
+        assert_eq!(wall, input(45, None).local_time);
+
+        let mut fixed = input(45, None);
+        fixed.local_time = None;
+        assert!(when(&fixed).is_err());
+        fixed.starts_at = Some(at);
+        assert_eq!(when(&fixed).unwrap(), (at, None));
+        fixed.timezone = Some("America/New_York".into());
+        let (_, wall) = when(&fixed).unwrap();
+        assert_eq!(
+            wall.map(format_local).as_deref(),
+            Some("2026-07-01T04:00:00")
+        );
+    }
+
+    #[test]
+    fn reschedule_follows_the_wall_clock() {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        db.execute(
+            "INSERT INTO calendar_events (kind, starts_at, duration_minutes, created_at,
+             updated_at, local_time, timezone)
+             VALUES ('interview', '2026-07-01T09:00:00+00:00', 45, '', '', ?1, 'Europe/Madrid')",
+            ["2026-07-01T10:00:00"],
+        )
+        .unwrap();
+        assert_eq!(reschedule(&db).unwrap(), 1);
+        let e = get(&db, 1).unwrap();
+        assert_eq!(e.starts_at.to_rfc3339(), "2026-07-01T08:00:00+00:00");
+        assert_eq!(e.sequence, 1);
+        assert_eq!(reschedule(&db).unwrap(), 0);
+    }
+}
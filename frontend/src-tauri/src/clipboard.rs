@@ -0,0 +1,256 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::{thread, time::Duration};
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::{
+    audit::{self, Action},
+    proxy, settings,
+    telemetry::{self, Counter},
+};
+
+// Copies a stored login to the clipboard for signing in by hand when the
+// automated login fails. The copy is marked so clipboard history skips it
+// where the OS lets us say so (Windows history and cloud clipboard, macOS
+// managers that honour the concealed type), and it's cleared
+// after `clipboard.clear_after_secs` unless something else was copied since.
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Credential {
+    InfojobsPassword,
+    ProxyPassword,
+}
+
+fn keychain_entry(app: &AppHandle, credential: Credential) -> Result<(String, String), String> {
+    match credential {
+        Credential::InfojobsPassword => Ok(("jobbot".into(), "infojobs/password".into())),
+        Credential::ProxyPassword => settings::get(app)
+            .proxy
+            .username
+            .map(|u| (proxy::KEYCHAIN_SERVICE.to_string(), u))
+            .ok_or_else(|| "no proxy login is saved".to_string()),
+    }
+}
+
+// Returns how many seconds until the clipboard is cleared.
+#[tauri::command]
+pub async fn copy_credential(app: AppHandle, credential: Credential) -> Result<u32, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (service, account) = keychain_entry(&app, credential)?;
+        let secret = keyring::Entry::new(&service, &account)
+            .and_then(|e| e.get_password())
+            .map_err(|e| match e {
+                keyring::Error::NoEntry => "nothing is saved for this login".to_string(),
+                e => e.to_string(),
+            })?;
+        let marker = platform::write_secret(&secret)?;
+        audit::record(
+            &app,
+            Action::CredentialUsed,
+            json!({ "service": service, "account": account, "for": "clipboard" }),
+        );
+        telemetry::record(&app, Counter::CredentialCopied);
+        let clear_after = settings::get(&app).clipboard.clear_after_secs;
+        thread::spawn(move || {
+            thread::sleep(Duration::from_secs(clear_after.into()));
+            if let Err(e) = platform::clear_if_unchanged(marker) {
+                warn!("clipboard not cleared: {e}");
+            }
+        });
+        Ok(clear_after)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Each platform's `write_secret` returns whatever `clear_if_unchanged` needs
+// to tell whether the user has copied something else in the meantime; here,
+// the pasteboard's change count.
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2_app_kit::{NSPasteboard, NSPasteboardTypeString};
+    use objc2_foundation::NSString;
+
+    // http://nspasteboard.org: managers such as Maccy, Alfred and Raycast
+    // leave items carrying this type out of their history
+    const CONCEALED_TYPE: &str = "org.nspasteboard.ConcealedType";
+
+    pub fn write_secret(text: &str) -> Result<isize, String> {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        let written = pasteboard
+            .setString_forType(&NSString::from_str(text), unsafe { NSPasteboardTypeString });
+        if !written {
+            return Err("the clipboard refused the text".into());
+        }
+        pasteboard.setString_forType(&NSString::from_str(""), &NSString::from_str(CONCEALED_TYPE));
+        Ok(pasteboard.changeCount())
+    }
+
+    pub fn clear_if_unchanged(change_count: isize) -> Result<(), String> {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        if pasteboard.changeCount() == change_count {
+            pasteboard.clearContents();
+        }
+        Ok(())
+    }
+}
+
+// Windows keeps copies out of clipboard history and cloud sync when these
+// extra formats accompany the text; `write_secret` returns the clipboard
+// sequence number for the same check as on macOS.
+#[cfg(windows)]
+mod platform {
+    use windows::{
+        core::w,
+        Win32::{
+            Foundation::{HANDLE, HGLOBAL},
+            System::{
+                DataExchange::{
+                    CloseClipboard, EmptyClipboard, GetClipboardSequenceNumber, OpenClipboard,
+                    RegisterClipboardFormatW, SetClipboardData,
+                },
+                Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
+                Ole::CF_UNICODETEXT,
+            },
+        },
+    };
+
+    fn global(bytes: &[u8]) -> Result<HGLOBAL, String> {
+        unsafe {
+            let handle = GlobalAlloc(GMEM_MOVEABLE, bytes.len()).map_err(|e| e.to_string())?;
+            let ptr = GlobalLock(handle) as *mut u8;
+            if ptr.is_null() {
+                return Err("could not lock clipboard memory".into());
+            }
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len());
+            // Reports an "error" once the lock count reaches zero
+            let _ = GlobalUnlock(handle);
+            Ok(handle)
+        }
+    }
+
+    fn set(format: u32, bytes: &[u8]) -> Result<(), String> {
+        let handle = global(bytes)?;
+        unsafe { SetClipboardData(format, Some(HANDLE(handle.0))) }
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn fill(text: &str) -> Result<(), String> {
+        unsafe { EmptyClipboard() }.map_err(|e| e.to_string())?;
+        let wide: Vec<u8> = text
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        set(u32::from(CF_UNICODETEXT.0), &wide)?;
+        let off = 0u32.to_le_bytes();
+        for (name, value) in [
+            (
+                w!("ExcludeClipboardContentFromMonitorProcessing"),
+                &[0u8][..],
+            ),
+            (w!("CanIncludeInClipboardHistory"), &off[..]),
+            (w!("CanUploadToCloudClipboard"), &off[..]),
+        ] {
+            let format = unsafe { RegisterClipboardFormatW(name) };
+            if format != 0 {
+                set(format, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write_secret(text: &str) -> Result<u32, String> {
+        unsafe { OpenClipboard(None) }.map_err(|e| e.to_string())?;
+        let result = fill(text);
+        let _ = unsafe { CloseClipboard() };
+        result?;
+        Ok(unsafe { GetClipboardSequenceNumber() })
+    }
+
+    pub fn clear_if_unchanged(sequence: u32) -> Result<(), String> {
+        if unsafe { GetClipboardSequenceNumber() } != sequence {
+            return Ok(());
+        }
+        unsafe { OpenClipboard(None) }.map_err(|e| e.to_string())?;
+        let result = unsafe { EmptyClipboard() }.map_err(|e| e.to_string());
+        let _ = unsafe { CloseClipboard() };
+        result
+    }
+}
+
+// No clipboard API without a window toolkit; wl-copy (Wayland) and xclip (X11)
+// do the work. Neither can offer the extra type history managers look for, so
+// only the timed clear applies here. There's no change counter either, so the
+// clear compares against the copied text.
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    fn wayland() -> bool {
+        std::env::var_os("WAYLAND_DISPLAY").is_some()
+    }
+
+    fn pipe(program: &str, args: &[&str], input: &str) -> Result<(), String> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("{program}: {e}"))?;
+        child
+            .stdin
+            .take()
+            .ok_or("no stdin")?
+            .write_all(input.as_bytes())
+            .map_err(|e| e.to_string())?;
+        // wl-copy and xclip fork to keep serving the selection; this only
+        // waits for the parent
+        let status = child.wait().map_err(|e| e.to_string())?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("{program} exited with {status}"))
+        }
+    }
+
+    pub fn write_secret(text: &str) -> Result<String, String> {
+        if wayland() {
+            pipe("wl-copy", &[], text)?;
+        } else {
+            pipe("xclip", &["-selection", "clipboard"], text)?;
+        }
+        Ok(text.to_string())
+    }
+
+    pub fn clear_if_unchanged(text: String) -> Result<(), String> {
+        let current = if wayland() {
+            Command::new("wl-paste").args(["--no-newline"]).output()
+        } else {
+            Command::new("xclip")
+                .args(["-selection", "clipboard", "-o"])
+                .output()
+        }
+        .map_err(|e| e.to_string())?;
+        if current.stdout != text.as_bytes() {
+            return Ok(());
+        }
+        if wayland() {
+            Command::new("wl-copy").arg("--clear").status()
+        } else {
+            Command::new("xclip")
+                .args(["-selection", "clipboard", "/dev/null"])
+                .status()
+        }
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+}
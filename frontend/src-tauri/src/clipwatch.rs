@@ -0,0 +1,410 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{sync::Mutex, thread, time::Duration};
+use tauri::{AppHandle, Emitter, Url};
+use tracing::{info, warn};
+
+use crate::{
+    alerts,
+    backend::{self, Failure},
+    i18n::t,
+    jobfetch, notifications, offlinequeue,
+    settings::{self, ClipboardSettings},
+};
+
+// Opt-in: watches the clipboard for a copied job posting, a link on one of
+// `clipboard.job_domains` (or a subdomain), and offers to add it. The offer is
+// a notification, whose click brings up the app, and the prompt there
+// (`components/ClipboardIntake.tsx`) with Add and Dismiss; Add sends the URL
+// to the engine's intake, or queues it while the engine is down. Only the
+// change counter is polled where the OS keeps one; Linux has to read the text.
+// Copies marked as secret (our own credential copies among them) are skipped.
+pub const CLIPBOARD_JOB: &str = "clipboard-job";
+#[cfg(not(target_os = "linux"))]
+const POLL: Duration = Duration::from_secs(1);
+#[cfg(target_os = "linux")]
+const POLL: Duration = Duration::from_secs(2);
+const IDLE: Duration = Duration::from_secs(5);
+const INTAKE: &str = "/api/jobs/intake";
+
+// The URL on offer, and the last one offered, so a copy isn't offered twice
+static PENDING: Mutex<Option<String>> = Mutex::new(None);
+static OFFERED: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Intake {
+    Added { job_id: i64, new: bool },
+    Queued,
+}
+
+// The URL in the copied text if it's a posting on one of `domains`.
+fn job_url(text: &str, domains: &[String]) -> Option<String> {
+    let text = text.trim();
+    if text.len() > 2048 || text.contains(char::is_whitespace) {
+        return None;
+    }
+    let url = Url::parse(text).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_lowercase();
+    domains
+        .iter()
+        .map(|d| d.trim().trim_start_matches("www.").to_lowercase())
+        .any(|d| !d.is_empty() && (host == d || host.ends_with(&format!(".{d}"))))
+        .then(|| url.to_string())
+}
+
+fn offer(app: &AppHandle, url: String) {
+    {
+        let mut offered = OFFERED.lock().unwrap();
+        if offered.as_deref() == Some(url.as_str()) {
+            return;
+        }
+        *offered = Some(url.clone());
+    }
+    info!("job URL copied");
+    *PENDING.lock().unwrap() = Some(url.clone());
+    let _ = app.emit(CLIPBOARD_JOB, Some(&url));
+    notifications::notify_route(app, &t("clipboard-job-title"), &url, "/jobs");
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || {
+        let mut watcher: Option<platform::Watcher> = None;
+        loop {
+            let cfg = settings::get(&app).clipboard;
+            if !cfg.watch_job_urls {
+                // What was copied while off isn't offered once it's on
+                watcher = None;
+                thread::sleep(IDLE);
+                continue;
+            }
+            let watcher = watcher.get_or_insert_with(platform::Watcher::new);
+            if let Some(url) = watcher
+                .poll()
+                .and_then(|text| job_url(&text, &cfg.job_domains))
+            {
+                offer(&app, url);
+            }
+            thread::sleep(POLL);
+        }
+    });
+}
+
+fn clear(app: &AppHandle) {
+    *PENDING.lock().unwrap() = None;
+    let _ = app.emit(CLIPBOARD_JOB, None::<String>);
+}
+
+#[tauri::command]
+pub fn get_clipboard_job() -> Option<String> {
+    PENDING.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn dismiss_clipboard_job(app: AppHandle) {
+    clear(&app);
+}
+
+// Hands a posting to the engine's intake, or to the offline queue while it's
+// down; `body` is the intake's, with at least `url` and `source`. Where the
+// engine got no text out of the page, or isn't there to try, the shell fetches
+// the posting itself (`jobfetch`) and sends what it found along.
+pub fn send_to_intake(app: &AppHandle, mut body: Value) -> Result<Intake, String> {
+    match backend::send_json("POST", INTAKE, Some(&body)) {
+        Ok(added) => {
+            if !added["described"].as_bool().unwrap_or(true) && describe(&mut body) {
+                if let Err(e) = backend::send_json("POST", INTAKE, Some(&body)) {
+                    warn!("couldn't send the fetched posting: {e:?}");
+                }
+            }
+            Ok(Intake::Added {
+                job_id: added["job_id"].as_i64().unwrap_or_default(),
+                new: added["new"].as_bool().unwrap_or(true),
+            })
+        }
+        Err(Failure::Unreachable(_)) => {
+            describe(&mut body);
+            alerts::on_queued(app, &body);
+            let label = format!("Add {}", body["url"].as_str().unwrap_or_default());
+            offlinequeue::queue(app, label, "POST", INTAKE.into(), Some(body))?;
+            Ok(Intake::Queued)
+        }
+        Err(Failure::Refused(_, detail)) => Err(detail),
+    }
+}
+
+// Fills in `body` from the posting's page, keeping what the caller knew.
+// False when the page had no text to add.
+fn describe(body: &mut Value) -> bool {
+    if body["description"].as_str().is_some_and(|d| !d.is_empty()) {
+        return false;
+    }
+    match jobfetch::fetch(body["url"].as_str().unwrap_or_default()) {
+        Ok(posting) => fill(body, posting),
+        Err(e) => {
+            warn!("couldn't fetch the posting: {e}");
+            false
+        }
+    }
+}
+
+fn fill(body: &mut Value, posting: jobfetch::Posting) -> bool {
+    let Some(description) = posting.description else {
+        return false;
+    };
+    for (key, value) in [
+        ("title", posting.title),
+        ("company", posting.company),
+        ("location", posting.location),
+    ] {
+        if body[key].as_str().is_none_or(str::is_empty) {
+            if let Some(value) = value {
+                body[key] = json!(value);
+            }
+        }
+    }
+    body["description"] = json!(description);
+    true
+}
+
+#[tauri::command]
+pub async fn accept_clipboard_job(app: AppHandle, url: String) -> Result<Intake, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let intake = send_to_intake(&app, json!({ "url": url, "source": "clipboard" }))?;
+        clear(&app);
+        Ok(intake)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_clipboard_watch(app: AppHandle) -> ClipboardSettings {
+    settings::get(&app).clipboard
+}
+
+#[tauri::command]
+pub fn set_clipboard_watch(
+    app: AppHandle,
+    enabled: bool,
+    domains: Vec<String>,
+) -> Result<ClipboardSettings, String> {
+    let domains: Vec<String> = domains
+        .iter()
+        .map(|d| d.trim().to_lowercase())
+        .filter(|d| !d.is_empty())
+        .collect();
+    if enabled && domains.is_empty() {
+        return Err("add at least one job site".into());
+    }
+    settings::update(&app, |s| {
+        s.clipboard.watch_job_urls = enabled;
+        s.clipboard.job_domains = domains;
+    })?;
+    if !enabled {
+        clear(&app);
+    }
+    Ok(settings::get(&app).clipboard)
+}
+
+// Each `Watcher` returns the copied text once per copy, from the second poll
+// on: what was on the clipboard when it started isn't news.
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2_app_kit::{NSPasteboard, NSPasteboardTypeString};
+    use objc2_foundation::NSString;
+
+    // As `clipboard` marks its copies, and password managers theirs
+    const CONCEALED_TYPE: &str = "org.nspasteboard.ConcealedType";
+
+    pub struct Watcher {
+        change_count: isize,
+    }
+
+    impl Watcher {
+        pub fn new() -> Self {
+            Self {
+                change_count: NSPasteboard::generalPasteboard().changeCount(),
+            }
+        }
+
+        pub fn poll(&mut self) -> Option<String> {
+            let pasteboard = NSPasteboard::generalPasteboard();
+            let count = pasteboard.changeCount();
+            if count == self.change_count {
+                return None;
+            }
+            self.change_count = count;
+            if pasteboard
+                .stringForType(&NSString::from_str(CONCEALED_TYPE))
+                .is_some()
+            {
+                return None;
+            }
+            pasteboard
+                .stringForType(unsafe { NSPasteboardTypeString })
+                .map(|s| s.to_string())
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use windows::{
+        core::w,
+        Win32::{
+            Foundation::HGLOBAL,
+            System::{
+                DataExchange::{
+                    CloseClipboard, GetClipboardData, GetClipboardSequenceNumber,
+                    IsClipboardFormatAvailable, OpenClipboard, RegisterClipboardFormatW,
+                },
+                Memory::{GlobalLock, GlobalUnlock},
+                Ole::CF_UNICODETEXT,
+            },
+        },
+    };
+
+    pub struct Watcher {
+        sequence: u32,
+    }
+
+    // The text, unless the copy asks monitors to leave it alone
+    fn read() -> Option<String> {
+        unsafe {
+            let secret =
+                RegisterClipboardFormatW(w!("ExcludeClipboardContentFromMonitorProcessing"));
+            if secret != 0 && IsClipboardFormatAvailable(secret).is_ok() {
+                return None;
+            }
+            let handle = GetClipboardData(u32::from(CF_UNICODETEXT.0)).ok()?;
+            let global = HGLOBAL(handle.0);
+            let ptr = GlobalLock(global) as *const u16;
+            if ptr.is_null() {
+                return None;
+            }
+            let mut len = 0;
+            while *ptr.add(len) != 0 {
+                len += 1;
+            }
+            let text = String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len));
+            let _ = GlobalUnlock(global);
+            Some(text)
+        }
+    }
+
+    impl Watcher {
+        pub fn new() -> Self {
+            Self {
+                sequence: unsafe { GetClipboardSequenceNumber() },
+            }
+        }
+
+        pub fn poll(&mut self) -> Option<String> {
+            let sequence = unsafe { GetClipboardSequenceNumber() };
+            if sequence == self.sequence {
+                return None;
+            }
+            // Another program holding the clipboard; tried again next time
+            unsafe { OpenClipboard(None) }.ok()?;
+            self.sequence = sequence;
+            let text = read();
+            let _ = unsafe { CloseClipboard() };
+            text
+        }
+    }
+}
+
+// wl-paste (Wayland) or xclip (X11), as in `clipboard`; with no counter, the
+// text is compared with the last read.
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::process::{Command, Stdio};
+
+    pub struct Watcher {
+        last: Option<Vec<u8>>,
+    }
+
+    fn read() -> Option<Vec<u8>> {
+        let output = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            Command::new("wl-paste")
+                .args(["--no-newline", "--type", "text"])
+                .stderr(Stdio::null())
+                .output()
+        } else {
+            Command::new("xclip")
+                .args(["-selection", "clipboard", "-o"])
+                .stderr(Stdio::null())
+                .output()
+        }
+        .ok()?;
+        output.status.success().then_some(output.stdout)
+    }
+
+    impl Watcher {
+        pub fn new() -> Self {
+            Self { last: read() }
+        }
+
+        pub fn poll(&mut self) -> Option<String> {
+            let text = read()?;
+            if self.last.as_ref() == Some(&text) {
+                return None;
+            }
+            self.last = Some(text.clone());
+            String::from_utf8(text).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_links_on_listed_boards() {
+        let domains = [
+            "www.infojobs.net".to_string(),
+            "linkedin.com".into(),
+            " ".into(),
+        ];
+        assert_eq!(
+            job_url(
+                " https://www.infojobs.net/madrid/cajero/of-i123\n",
+                &domains
+            )
+            .as_deref(),
+            Some("https://www.infojobs.net/madrid/cajero/of-i123")
+        );
+        assert!(job_url("https://es.LinkedIn.com/jobs/view/1", &domains).is_some());
+        assert_eq!(job_url("https://notlinkedin.com/jobs/1", &domains), None);
+        assert_eq!(job_url("ftp://linkedin.com/jobs/1", &domains), None);
+        assert_eq!(job_url("see https://linkedin.com/jobs/1", &domains), None);
+        assert_eq!(job_url("linkedin.com/jobs/1", &domains), None);
+        let long = format!("https://linkedin.com/{}", "a".repeat(2048));
+        assert_eq!(job_url(&long, &domains), None);
+    }
+
+    #[test]
+    fn fetched_posting_fills_the_gaps() {
+        let mut body = json!({ "url": "https://acme.example/jobs/1", "title": "Cajero" });
+        let posting = jobfetch::Posting {
+            title: Some("Cajero/a".into()),
+            company: Some("Acme".into()),
+            description: Some("Atención al cliente".into()),
+            ..Default::default()
+        };
+        assert!(fill(&mut body, posting));
+        assert_eq!(body["title"], "Cajero");
+        assert_eq!(body["company"], "Acme");
+        assert_eq!(body["location"], Value::Null);
+        assert_eq!(body["description"], "Atención al cliente");
+
+        let mut body = json!({ "url": "https://acme.example/jobs/1" });
+        assert!(!fill(&mut body, jobfetch::Posting::default()));
+        assert_eq!(body, json!({ "url": "https://acme.example/jobs/1" }));
+    }
+}
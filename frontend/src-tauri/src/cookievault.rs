@@ -0,0 +1,190 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::Mutex, thread};
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::{
+    audit::{self, Action},
+    backend,
+    events::BackendEvent,
+    paths, vault,
+};
+
+// Job-site session cookies, kept for the engine so a restart doesn't sign the
+// user out of every board. After a login the engine parks the jar and says so
+// (`session_saved`); this collects it, seals it with the vault key and stores
+// it in sessions.json next to settings.json until it expires. When the engine
+// opens a site it asks (`session_requested`) and gets the jar back, or an
+// empty one. See backend/security/cookie_vault.py for the other half.
+pub const VAULT_ENV: &str = "JOBBOT_COOKIE_VAULT";
+const FILE: &str = "sessions.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct StoredJar {
+    // Unix seconds
+    saved_at: i64,
+    expires_at: i64,
+    // The engine's cookie list as JSON, sealed by `vault`
+    cookies: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionInfo {
+    pub site: String,
+    pub saved_at: i64,
+    pub expires_at: i64,
+}
+
+// sessions.json is read-modify-written from event threads
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::config_dir(app)?.join(FILE))
+}
+
+// Expired jars are left out, and dropped from the file on the next write.
+fn load(app: &AppHandle) -> BTreeMap<String, StoredJar> {
+    let now = Utc::now().timestamp();
+    path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str::<BTreeMap<String, StoredJar>>(&s).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, jar)| jar.expires_at > now)
+        .collect()
+}
+
+fn save(app: &AppHandle, jars: &BTreeMap<String, StoredJar>) -> Result<(), String> {
+    let path = path(app)?;
+    let tmp = path.with_extension("tmp");
+    let json = serde_json::to_vec_pretty(jars).map_err(|e| e.to_string())?;
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+// Site names go into request paths; the engine's are all like "indeed_es".
+fn valid_site(site: &str) -> bool {
+    !site.is_empty()
+        && site
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn store(app: &AppHandle, site: &str) -> Result<(), String> {
+    let jar = backend::get_json(&format!("/api/sessions/{site}"))?;
+    let expires_at = jar["expires_at"]
+        .as_f64()
+        .ok_or("the engine sent a session without an expiry")? as i64;
+    let cookies = serde_json::to_string(&jar["cookies"]).map_err(|e| e.to_string())?;
+    let stored = StoredJar {
+        saved_at: Utc::now().timestamp(),
+        expires_at,
+        cookies: vault::seal(&cookies)?,
+    };
+    let _guard = FILE_LOCK.lock().unwrap();
+    let mut jars = load(app);
+    jars.insert(site.to_string(), stored);
+    save(app, &jars)
+}
+
+// Always answers, so the engine isn't left waiting out its timeout.
+fn serve(app: &AppHandle, site: &str) -> Result<(), String> {
+    let stored = {
+        let _guard = FILE_LOCK.lock().unwrap();
+        load(app).remove(site)
+    };
+    let cookies = match stored.map(|jar| vault::open(&jar.cookies)) {
+        Some(Ok(plain)) => serde_json::from_str(&plain).unwrap_or(Value::Array(Vec::new())),
+        Some(Err(e)) => {
+            warn!("session for {site} unreadable: {e}");
+            Value::Array(Vec::new())
+        }
+        None => Value::Array(Vec::new()),
+    };
+    if cookies.as_array().is_some_and(|c| !c.is_empty()) {
+        audit::record(
+            app,
+            Action::CredentialUsed,
+            json!({ "session": site, "for": "engine" }),
+        );
+    }
+    backend::post_json(
+        &format!("/api/sessions/{site}"),
+        &json!({ "cookies": cookies }),
+    )
+    .map(|_| ())
+}
+
+// Called for every engine event; answers off the event stream's thread.
+pub fn on_event(app: &AppHandle, event: &BackendEvent) {
+    let handler: fn(&AppHandle, &str) -> Result<(), String> = match event.event.as_str() {
+        "session_saved" => store,
+        "session_requested" => serve,
+        _ => return,
+    };
+    let Some(site) = event.data["site"].as_str().filter(|s| valid_site(s)) else {
+        return;
+    };
+    let (app, site, name) = (app.clone(), site.to_string(), event.event.clone());
+    thread::spawn(move || {
+        if let Err(e) = handler(&app, &site) {
+            warn!("{name} for {site} failed: {e}");
+        }
+    });
+}
+
+pub fn backend_env() -> (&'static str, &'static str) {
+    (VAULT_ENV, "1")
+}
+
+// Reset → database or saved passwords: every site signs in again.
+pub fn wipe(app: &AppHandle) -> Result<(), String> {
+    let _guard = FILE_LOCK.lock().unwrap();
+    match fs::remove_file(path(app)?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+// Sites and expiry only; cookie values never leave this module but to the engine.
+#[tauri::command]
+pub fn list_sessions(app: AppHandle) -> Vec<SessionInfo> {
+    load(&app)
+        .into_iter()
+        .map(|(site, jar)| SessionInfo {
+            site,
+            saved_at: jar.saved_at,
+            expires_at: jar.expires_at,
+        })
+        .collect()
+}
+
+// The engine keeps the open browser context until it restarts, so this takes
+// effect on the next restart.
+#[tauri::command]
+pub fn forget_session(app: AppHandle, site: String) -> Result<(), String> {
+    let _guard = FILE_LOCK.lock().unwrap();
+    let mut jars = load(&app);
+    if jars.remove(&site).is_none() {
+        return Err(format!("no saved session for {site}"));
+    }
+    save(&app, &jars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn site_names() {
+        assert!(valid_site("indeed_es"));
+        assert!(valid_site("infojobs"));
+        assert!(!valid_site(""));
+        assert!(!valid_site("../settings"));
+        assert!(!valid_site("Indeed"));
+        assert!(!valid_site("indeed es"));
+    }
+}
@@ -0,0 +1,535 @@
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    backtrace::Backtrace,
+    fs,
+    panic::{self, PanicHookInfo},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    thread,
+};
+use tauri::{AppHandle, Url};
+use tracing::warn;
+
+use crate::{
+    errorreport,
+    i18n::{t, t_args},
+    logging, notifications, paths, redact,
+    telemetry::{self, Counter},
+};
+
+// Crash reports for the shell itself, in <log dir>/crashes/<id>.json. A
+// panic is written up by the hook as it happens. A native crash (a signal on
+// macOS and Linux, an unhandled exception on Windows) can only leave a small
+// marker, plus a minidump on Windows; the report is put together from it on
+// the next launch, with the end of the previous run's shell.log. Reports
+// stay until the user deletes them; new ones are announced once at launch.
+const DIR: &str = "crashes";
+const MARKER: &str = "native.marker";
+const MINIDUMP: &str = "native.dmp";
+const LOG_TAIL: usize = 50;
+const ISSUES_URL: &str = "https://github.com/IFAKA/currobot/issues/new";
+// GitHub rejects longer new-issue URLs
+const MAX_ISSUE_BODY: usize = 6000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CrashReport {
+    // File stem, e.g. crash-20260301-101500123
+    pub id: String,
+    // RFC 3339
+    pub at: String,
+    // "panic", "signal" or "exception"
+    pub kind: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: Option<String>,
+    pub app_version: String,
+    pub engine_version: Option<String>,
+    pub os: String,
+    // JSON log lines, oldest first
+    pub log_tail: Vec<String>,
+    // File name of the Windows minidump beside the report
+    pub minidump: Option<String>,
+    // Announced at a launch already
+    #[serde(default)]
+    pub announced: bool,
+}
+
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+static APP_VERSION: OnceLock<String> = OnceLock::new();
+static ENGINE_VERSION: Mutex<Option<String>> = Mutex::new(None);
+
+fn new_id() -> String {
+    format!("crash-{}", Local::now().format("%Y%m%d-%H%M%S%3f"))
+}
+
+fn os() -> String {
+    format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+fn write(dir: &Path, report: &CrashReport) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(report).map_err(|e| e.to_string())?;
+    fs::write(dir.join(format!("{}.json", report.id)), json).map_err(|e| e.to_string())
+}
+
+fn panic_report(info: &PanicHookInfo) -> CrashReport {
+    let message = info
+        .payload_as_str()
+        .unwrap_or("panic with a non-string payload")
+        .to_string();
+    CrashReport {
+        id: new_id(),
+        at: Utc::now().to_rfc3339(),
+        kind: "panic".into(),
+        message,
+        location: info.location().map(|l| l.to_string()),
+        thread: thread::current().name().map(str::to_string),
+        backtrace: Some(Backtrace::force_capture().to_string()),
+        app_version: APP_VERSION.get().cloned().unwrap_or_default(),
+        // Not waiting on a lock from inside a panic
+        engine_version: ENGINE_VERSION.try_lock().ok().and_then(|v| v.clone()),
+        os: os(),
+        log_tail: logging::tail(LOG_TAIL),
+        minidump: None,
+        announced: false,
+    }
+}
+
+// Right after `logging::init`. Panics before `attach` only reach the default
+// hook, as there's nowhere to write yet. Nothing here logs or redacts: either
+// could be what panicked. Reports are redacted when they're read.
+pub fn init() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Some(dir) = CRASH_DIR.get() {
+            let _ = write(dir, &panic_report(info));
+        }
+        previous(info);
+    }));
+}
+
+// The end of shell.log as the crashed run left it.
+fn file_tail(app: &AppHandle) -> Vec<String> {
+    let Some(text) = paths::log_dir(app)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(logging::LOG_FILE)).ok())
+    else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(LOG_TAIL)..]
+        .iter()
+        .map(|l| l.to_string())
+        .collect()
+}
+
+// Turns what a native crash left behind into a report.
+fn collect_native(app: &AppHandle, dir: &Path) {
+    let marker = dir.join(MARKER);
+    let Ok(content) = fs::read_to_string(&marker) else {
+        return;
+    };
+    let at = fs::metadata(&marker)
+        .and_then(|m| m.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+    let id = new_id();
+    let minidump = fs::rename(dir.join(MINIDUMP), dir.join(format!("{id}.dmp")))
+        .is_ok()
+        .then(|| format!("{id}.dmp"));
+    let (kind, message) = native::describe(content.trim());
+    let report = CrashReport {
+        id,
+        at: at.to_rfc3339(),
+        kind: kind.into(),
+        message,
+        location: None,
+        thread: None,
+        backtrace: None,
+        app_version: app.package_info().version.to_string(),
+        engine_version: None,
+        os: os(),
+        log_tail: file_tail(app),
+        minidump,
+        announced: false,
+    };
+    match write(dir, &report) {
+        Ok(()) => {
+            let _ = fs::remove_file(&marker);
+        }
+        Err(e) => warn!("crash report not written: {e}"),
+    }
+}
+
+// Before `logging::attach`, so shell.log still ends where the last run did.
+pub fn attach(app: &AppHandle) {
+    let _ = APP_VERSION.set(app.package_info().version.to_string());
+    let dir = match paths::log_dir(app) {
+        Ok(dir) => dir.join(DIR),
+        Err(e) => {
+            warn!("crash reports unavailable: {e}");
+            return;
+        }
+    };
+    if let Err(e) = fs::create_dir_all(&dir) {
+        warn!("crash reports unavailable: {e}");
+        return;
+    }
+    collect_native(app, &dir);
+    native::install(&dir.join(MARKER), &dir.join(MINIDUMP));
+    let _ = CRASH_DIR.set(dir);
+}
+
+// Recorded with panics; set each time the engine is started.
+pub fn note_engine_version(version: String) {
+    *ENGINE_VERSION.lock().unwrap() = Some(version);
+}
+
+fn load_all() -> Vec<CrashReport> {
+    let Some(entries) = CRASH_DIR.get().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<CrashReport> = entries
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+        .filter_map(|e| serde_json::from_slice(&fs::read(e.path()).ok()?).ok())
+        .collect();
+    reports.sort_by(|a, b| b.at.cmp(&a.at));
+    reports
+}
+
+fn redacted(app: &AppHandle, mut report: CrashReport) -> CrashReport {
+    report.message = redact::redact(app, &report.message);
+    report.backtrace = report.backtrace.map(|b| redact::redact(app, &b));
+    report.log_tail = report
+        .log_tail
+        .iter()
+        .map(|l| redact::redact(app, l))
+        .collect();
+    report
+}
+
+// Once per launch, for reports not mentioned before.
+pub fn announce(app: &AppHandle) {
+    let Some(dir) = CRASH_DIR.get() else {
+        return;
+    };
+    let mut fresh = 0;
+    for mut report in load_all().into_iter().filter(|r| !r.announced) {
+        report.announced = true;
+        let _ = write(dir, &report);
+        errorreport::crash(app, &redacted(app, report));
+        fresh += 1;
+    }
+    telemetry::record_n(app, Counter::Crash, fresh);
+    if fresh > 0 {
+        notifications::notify_route(
+            app,
+            &t("crash-title"),
+            &t_args("crash-body", &[("count", fresh.into())]),
+            // Settings → Desktop → Crash reports
+            "/settings",
+        );
+    }
+}
+
+fn issue_url(report: &CrashReport) -> Result<Url, String> {
+    let mut body = format!(
+        "**What happened:** {}\n\n- Kind: {}\n- App: {}\n- Engine: {}\n- OS: {}\n- At: {}\n",
+        report.message,
+        report.kind,
+        report.app_version,
+        report.engine_version.as_deref().unwrap_or("unknown"),
+        report.os,
+        report.at,
+    );
+    if let Some(location) = &report.location {
+        body.push_str(&format!("- Location: {location}\n"));
+    }
+    if let Some(backtrace) = &report.backtrace {
+        body.push_str(&format!("\n```\n{backtrace}\n```\n"));
+    }
+    if body.len() > MAX_ISSUE_BODY {
+        let mut cut = MAX_ISSUE_BODY;
+        while !body.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        body.truncate(cut);
+        body.push_str("\n```\n(cut short)\n");
+    }
+    body.push_str(&format!(
+        "\nThe full report ({}.json) is in the app's crashes folder.\n",
+        report.id
+    ));
+    let title = format!("Crash: {}", report.message.lines().next().unwrap_or(""));
+    Url::parse_with_params(ISSUES_URL, [("title", title), ("body", body)])
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_crash_reports(app: AppHandle) -> Vec<CrashReport> {
+    load_all().into_iter().map(|r| redacted(&app, r)).collect()
+}
+
+// Opens a prefilled GitHub issue in the browser; nothing is sent until the
+// user submits it there. The log tail stays out of the issue.
+#[tauri::command]
+#[allow(deprecated)]
+pub fn send_crash_report(app: AppHandle, id: String) -> Result<(), String> {
+    use tauri_plugin_shell::ShellExt;
+    let report = load_all()
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| format!("no crash report {id}"))?;
+    let url = issue_url(&redacted(&app, report))?;
+    app.shell()
+        .open(url.as_str(), None)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_crash_report(id: String) -> Result<(), String> {
+    let dir = CRASH_DIR.get().ok_or("crash reports are unavailable")?;
+    let report = load_all()
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| format!("no crash report {id}"))?;
+    if let Some(dump) = &report.minidump {
+        let _ = fs::remove_file(dir.join(dump));
+    }
+    fs::remove_file(dir.join(format!("{id}.json"))).map_err(|e| e.to_string())
+}
+
+// The handler writes "signal <n>" to the marker with nothing but open(2) and
+// write(2), then puts back the handler it replaced (Rust's stack overflow
+// report, or the default) so the crash carries on as it would have.
+#[cfg(unix)]
+mod native {
+    use std::{ffi::CString, mem, os::unix::ffi::OsStrExt, path::Path, ptr, sync::OnceLock};
+
+    const SIGNALS: &[(libc::c_int, &str)] = &[
+        (libc::SIGSEGV, "SIGSEGV"),
+        (libc::SIGBUS, "SIGBUS"),
+        (libc::SIGILL, "SIGILL"),
+        (libc::SIGFPE, "SIGFPE"),
+        (libc::SIGABRT, "SIGABRT"),
+    ];
+
+    static MARKER: OnceLock<CString> = OnceLock::new();
+    static PREVIOUS: OnceLock<Vec<(libc::c_int, libc::sigaction)>> = OnceLock::new();
+
+    extern "C" fn on_signal(signal: libc::c_int, _: *mut libc::siginfo_t, _: *mut libc::c_void) {
+        if let Some(path) = MARKER.get() {
+            let mut line = *b"signal 00\n";
+            line[7] = b'0' + (signal / 10 % 10) as u8;
+            line[8] = b'0' + (signal % 10) as u8;
+            unsafe {
+                let fd = libc::open(
+                    path.as_ptr(),
+                    libc::O_WRONLY | libc::O_CREAT | libc::O_TRUNC,
+                    0o600 as libc::c_uint,
+                );
+                if fd >= 0 {
+                    libc::write(fd, line.as_ptr().cast(), line.len());
+                    libc::close(fd);
+                }
+            }
+        }
+        // A fault repeats on return and reaches the restored handler; abort()
+        // raises again itself
+        if let Some((_, previous)) = PREVIOUS
+            .get()
+            .and_then(|p| p.iter().find(|(s, _)| *s == signal))
+        {
+            unsafe { libc::sigaction(signal, previous, ptr::null_mut()) };
+        }
+    }
+
+    pub fn install(marker: &Path, _minidump: &Path) {
+        let Ok(path) = CString::new(marker.as_os_str().as_bytes()) else {
+            return;
+        };
+        if MARKER.set(path).is_err() {
+            return;
+        }
+        let mut previous = Vec::new();
+        for &(signal, _) in SIGNALS {
+            unsafe {
+                let mut action: libc::sigaction = mem::zeroed();
+                action.sa_sigaction = on_signal as *const () as usize;
+                action.sa_flags = libc::SA_SIGINFO | libc::SA_ONSTACK;
+                libc::sigemptyset(&mut action.sa_mask);
+                let mut old: libc::sigaction = mem::zeroed();
+                if libc::sigaction(signal, &action, &mut old) == 0 {
+                    previous.push((signal, old));
+                }
+            }
+        }
+        let _ = PREVIOUS.set(previous);
+    }
+
+    pub fn describe(marker: &str) -> (&'static str, String) {
+        let name = marker
+            .strip_prefix("signal ")
+            .and_then(|n| n.parse::<libc::c_int>().ok())
+            .and_then(|n| SIGNALS.iter().find(|(s, _)| *s == n))
+            .map_or("an unknown signal", |(_, name)| *name);
+        ("signal", format!("terminated by {name}"))
+    }
+}
+
+// An unhandled exception filter writes a minidump and "exception <code>" to
+// the marker. Rust's own stack overflow handling is a vectored handler and
+// runs first, unaffected.
+#[cfg(windows)]
+mod native {
+    use std::{os::windows::ffi::OsStrExt, path::Path, sync::OnceLock};
+    use windows::{
+        core::PCWSTR,
+        Win32::{
+            Foundation::{CloseHandle, GENERIC_WRITE, HANDLE},
+            Storage::FileSystem::{
+                CreateFileW, WriteFile, CREATE_ALWAYS, FILE_ATTRIBUTE_NORMAL, FILE_SHARE_NONE,
+            },
+            System::{
+                Diagnostics::Debug::{
+                    MiniDumpNormal, MiniDumpWithThreadInfo, MiniDumpWriteDump,
+                    SetUnhandledExceptionFilter, EXCEPTION_CONTINUE_SEARCH, EXCEPTION_POINTERS,
+                    MINIDUMP_EXCEPTION_INFORMATION,
+                },
+                Threading::{GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId},
+            },
+        },
+    };
+
+    // Wide, NUL-terminated: marker, minidump
+    static PATHS: OnceLock<(Vec<u16>, Vec<u16>)> = OnceLock::new();
+
+    fn wide(path: &Path) -> Vec<u16> {
+        path.as_os_str().encode_wide().chain(Some(0)).collect()
+    }
+
+    unsafe fn create(path: &[u16]) -> Option<HANDLE> {
+        unsafe {
+            CreateFileW(
+                PCWSTR(path.as_ptr()),
+                GENERIC_WRITE.0,
+                FILE_SHARE_NONE,
+                None,
+                CREATE_ALWAYS,
+                FILE_ATTRIBUTE_NORMAL,
+                None,
+            )
+        }
+        .ok()
+    }
+
+    unsafe extern "system" fn on_exception(info: *const EXCEPTION_POINTERS) -> i32 {
+        let Some((marker, minidump)) = PATHS.get() else {
+            return EXCEPTION_CONTINUE_SEARCH;
+        };
+        unsafe {
+            if let Some(file) = create(minidump) {
+                let exception = MINIDUMP_EXCEPTION_INFORMATION {
+                    ThreadId: GetCurrentThreadId(),
+                    ExceptionPointers: info as *mut _,
+                    ClientPointers: false.into(),
+                };
+                let _ = MiniDumpWriteDump(
+                    GetCurrentProcess(),
+                    GetCurrentProcessId(),
+                    file,
+                    MiniDumpNormal | MiniDumpWithThreadInfo,
+                    Some(&exception),
+                    None,
+                    None,
+                );
+                let _ = CloseHandle(file);
+            }
+            if let Some(file) = create(marker) {
+                // No formatting machinery; the heap may be what broke
+                let code = (*(*info).ExceptionRecord).ExceptionCode.0 as u32;
+                let mut line = *b"exception 0x00000000\n";
+                for (i, byte) in line[12..20].iter_mut().enumerate() {
+                    *byte = b"0123456789ABCDEF"[(code >> (28 - 4 * i)) as usize & 0xf];
+                }
+                let _ = WriteFile(file, Some(&line), None, None);
+                let _ = CloseHandle(file);
+            }
+        }
+        EXCEPTION_CONTINUE_SEARCH
+    }
+
+    pub fn install(marker: &Path, minidump: &Path) {
+        if PATHS.set((wide(marker), wide(minidump))).is_ok() {
+            unsafe { SetUnhandledExceptionFilter(Some(on_exception)) };
+        }
+    }
+
+    pub fn describe(marker: &str) -> (&'static str, String) {
+        let code = marker.strip_prefix("exception ").unwrap_or("unknown");
+        ("exception", format!("unhandled exception {code}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(backtrace: String) -> CrashReport {
+        CrashReport {
+            id: "crash-20261015-090000000".into(),
+            at: "2026-10-15T09:00:00+00:00".into(),
+            kind: "panic".into(),
+            message: "index out of bounds\nsecond line".into(),
+            location: Some("src/tray.rs:10:5".into()),
+            thread: Some("main".into()),
+            backtrace: Some(backtrace),
+            app_version: "1.4.0".into(),
+            engine_version: None,
+            os: os(),
+            log_tail: Vec::new(),
+            minidump: None,
+            announced: false,
+        }
+    }
+
+    fn param(url: &Url, name: &str) -> String {
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+            .unwrap()
+    }
+
+    #[test]
+    fn issue_from_a_report() {
+        let url = issue_url(&report("0: main".into())).unwrap();
+        assert!(url.as_str().starts_with(ISSUES_URL));
+        assert_eq!(param(&url, "title"), "Crash: index out of bounds");
+        let body = param(&url, "body");
+        assert!(body.contains("- Engine: unknown\n"));
+        assert!(body.contains("- Location: src/tray.rs:10:5\n"));
+        assert!(body.ends_with("(crash-20261015-090000000.json) is in the app's crashes folder.\n"));
+    }
+
+    #[test]
+    fn long_backtraces_are_cut() {
+        let url = issue_url(&report("ñ".repeat(MAX_ISSUE_BODY))).unwrap();
+        let body = param(&url, "body");
+        assert!(body.contains("(cut short)"));
+        assert!(body.contains("crashes folder"));
+        assert!(body.len() < MAX_ISSUE_BODY + 200);
+    }
+
+    #[test]
+    fn reports_are_written_by_id() {
+        let dir = std::env::temp_dir().join(format!("jobbot-crashes-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        write(&dir, &report("0: main".into())).unwrap();
+        let path = dir.join("crash-20261015-090000000.json");
+        let read: CrashReport = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(read.message, "index out of bounds\nsecond line");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
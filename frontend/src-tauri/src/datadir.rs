@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, RwLock},
+    thread::{self, ThreadId},
+};
+use tauri::{AppHandle, Emitter};
+use tracing::warn;
+
+use crate::{overrides, paths};
+
+// Users with small system drives (or who want their data in a synced folder)
+// can move the whole data dir: engine database and logs, CVs, the history,
+// documents, downloaded backends. The shell's own logs stay in the OS's log
+// folder. The choice lives in <config>/data-location.json, outside the data
+// dir itself. A move copies everything, verifies it, switches over and only
+// then (optionally) deletes the old copy. Meanwhile the engine is stopped and
+// the shell's stores are refused (see `check`), so nothing lands in the old
+// copy after it's been copied.
+pub const DATA_MOVE_PROGRESS: &str = "data-move-progress";
+
+static CUSTOM: RwLock<Option<PathBuf>> = RwLock::new(None);
+// The thread moving the data, while it copies and switches
+static MOVER: Mutex<Option<ThreadId>> = Mutex::new(None);
+
+// Clears MOVER however the move ends.
+struct Moving;
+
+impl Moving {
+    fn start() -> Result<Self, String> {
+        let mut mover = MOVER.lock().unwrap();
+        if mover.is_some() {
+            return Err("the data folder is already being moved".into());
+        }
+        *mover = Some(thread::current().id());
+        Ok(Moving)
+    }
+}
+
+impl Drop for Moving {
+    fn drop(&mut self) {
+        *MOVER.lock().unwrap() = None;
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Location {
+    path: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum MoveProgress {
+    Copying { done: usize, total: usize },
+    Verifying { done: usize, total: usize },
+    Switching,
+    Cleaning,
+    Done { path: PathBuf },
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataDirInfo {
+    pub path: Option<PathBuf>,
+    pub default_path: Option<PathBuf>,
+    pub custom: bool,
+    // JOBBOT_DATA_DIR wins and can't be changed from the app
+    pub locked_by_env: bool,
+    // The shell's logs, which don't move
+    pub log_path: Option<PathBuf>,
+}
+
+fn location_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::base_config_dir(app)?.join("data-location.json"))
+}
+
+pub fn custom() -> Option<PathBuf> {
+    CUSTOM.read().unwrap().clone()
+}
+
+// For paths::base_data_dir: while a move runs, only the thread moving may
+// use the data folder. Others fail rather than wait; a store written from the
+// main thread would otherwise hang the window for the whole copy.
+pub fn check() -> Result<(), String> {
+    match *MOVER.lock().unwrap() {
+        Some(mover) if mover != thread::current().id() => {
+            Err("the data folder is being moved".into())
+        }
+        _ => Ok(()),
+    }
+}
+
+// Must run before anything resolves data paths.
+pub fn init(app: &AppHandle) {
+    let location: Location = location_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    *CUSTOM.write().unwrap() = location.path;
+}
+
+fn save_location(app: &AppHandle, path: Option<PathBuf>) -> Result<(), String> {
+    let file = location_path(app)?;
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&Location { path: path.clone() })
+        .map_err(|e| e.to_string())?;
+    let tmp = file.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &file).map_err(|e| e.to_string())?;
+    *CUSTOM.write().unwrap() = path;
+    Ok(())
+}
+
+// Relative paths of every file under `root`.
+fn list_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let kind = entry.file_type()?;
+            if kind.is_dir() {
+                stack.push(entry.path());
+            } else if kind.is_file() {
+                files.push(entry.path().strip_prefix(root).unwrap().to_path_buf());
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn file_hash(path: &Path) -> io::Result<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+fn copy_and_verify(app: &AppHandle, from: &Path, to: &Path) -> Result<(), String> {
+    let files = list_files(from).map_err(|e| e.to_string())?;
+    let total = files.len();
+    for (i, rel) in files.iter().enumerate() {
+        let target = to.join(rel);
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::copy(from.join(rel), &target).map_err(|e| format!("{}: {e}", rel.display()))?;
+        let _ = app.emit(
+            DATA_MOVE_PROGRESS,
+            MoveProgress::Copying { done: i + 1, total },
+        );
+    }
+    for (i, rel) in files.iter().enumerate() {
+        let same = file_hash(&from.join(rel)).map_err(|e| e.to_string())?
+            == file_hash(&to.join(rel)).map_err(|e| e.to_string())?;
+        if !same {
+            return Err(format!("{} differs after copying", rel.display()));
+        }
+        let _ = app.emit(
+            DATA_MOVE_PROGRESS,
+            MoveProgress::Verifying { done: i + 1, total },
+        );
+    }
+    Ok(())
+}
+
+fn move_to(app: &AppHandle, target: PathBuf, delete_old: bool) -> Result<(), String> {
+    if overrides::get().data_dir.is_some() {
+        return Err("the data directory is set by JOBBOT_DATA_DIR".into());
+    }
+    if !target.is_absolute() {
+        return Err("choose an absolute path".into());
+    }
+    let current = paths::base_data_dir(app)?;
+    if target == current {
+        return Ok(());
+    }
+    if target.starts_with(&current) || current.starts_with(&target) {
+        return Err("the new location can't be inside the current one (or vice versa)".into());
+    }
+    let occupied = fs::read_dir(&target).is_ok_and(|mut d| d.next().is_some());
+    if occupied {
+        return Err("the new location must be an empty folder".into());
+    }
+
+    // Nothing may write while the files are copied
+    let moving = Moving::start()?;
+    crate::kill_backend(app);
+    let copied = if current.exists() {
+        copy_and_verify(app, &current, &target)
+    } else {
+        fs::create_dir_all(&target).map_err(|e| e.to_string())
+    };
+    // Moving back to the default location clears the override
+    let switched = copied.and_then(|_| {
+        let _ = app.emit(DATA_MOVE_PROGRESS, MoveProgress::Switching);
+        let chosen = (target != paths::default_data_dir(app)?).then(|| target.clone());
+        save_location(app, chosen)
+    });
+    drop(moving);
+    if let Err(e) = switched {
+        let _ = fs::remove_dir_all(&target);
+        let _ = crate::restart_backend(app);
+        return Err(format!("data move failed, nothing was changed: {e}"));
+    }
+    crate::restart_backend(app)?;
+
+    if delete_old && current.exists() {
+        let _ = app.emit(DATA_MOVE_PROGRESS, MoveProgress::Cleaning);
+        if let Err(e) = fs::remove_dir_all(&current) {
+            warn!("old data dir not removed: {e}");
+        }
+    }
+    let _ = app.emit(DATA_MOVE_PROGRESS, MoveProgress::Done { path: target });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_data_dir(app: AppHandle) -> DataDirInfo {
+    DataDirInfo {
+        path: paths::base_data_dir(&app).ok(),
+        default_path: paths::default_data_dir(&app).ok(),
+        custom: custom().is_some(),
+        locked_by_env: overrides::get().data_dir.is_some(),
+        log_path: paths::log_dir(&app).ok(),
+    }
+}
+
+#[tauri::command]
+pub async fn move_data_dir(app: AppHandle, path: String, delete_old: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || move_to(&app, PathBuf::from(path), delete_old))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_and_hashes_nested_files() {
+        let root = std::env::temp_dir().join(format!("jobbot-datadir-{}", std::process::id()));
+        fs::create_dir_all(root.join("profiles/work")).unwrap();
+        fs::write(root.join("jobs.db"), b"jobs").unwrap();
+        fs::write(root.join("profiles/work/settings.json"), b"{}").unwrap();
+        fs::write(root.join("profiles/copy.json"), b"{}").unwrap();
+
+        let mut files = list_files(&root).unwrap();
+        files.sort();
+        let expected: Vec<PathBuf> = [
+            "jobs.db",
+            "profiles/copy.json",
+            "profiles/work/settings.json",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        assert_eq!(files, expected);
+
+        let hash = |p: &str| file_hash(&root.join(p)).unwrap();
+        assert_eq!(
+            hash("profiles/copy.json"),
+            hash("profiles/work/settings.json")
+        );
+        assert_ne!(hash("jobs.db"), hash("profiles/copy.json"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn only_the_mover_uses_the_folder() {
+        let moving = Moving::start().unwrap();
+        assert!(Moving::start().is_err());
+        assert!(check().is_ok());
+        assert!(thread::spawn(check).join().unwrap().is_err());
+        drop(moving);
+        assert!(thread::spawn(check).join().unwrap().is_ok());
+    }
+}
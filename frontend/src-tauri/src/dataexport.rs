@@ -0,0 +1,500 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Local, Utc};
+use rusqlite::{types::ValueRef, Connection, OpenFlags};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+use tracing::{info, warn};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    audit::{self, Action},
+    documents, evidence, history,
+    i18n::t,
+    paths, profiles, settings,
+};
+
+// Everything the app knows about the user, for taking it elsewhere or
+// answering a request for it: every table of the application history and of
+// the engine's database as JSON (an array of rows each), the document
+// library, the CVs the engine was given and made, the proof kept of each
+// submission, the shell's settings and its other stores, and the logs if
+// asked for, in one ZIP with a manifest. It's the active profile's. Passwords,
+// tokens and session cookies are left where they are (the OS keychain, the
+// cookie vault), and so is what only concerns this machine. Unlike a backup
+// (backup.rs), it's made to be read, not restored.
+const FORMAT: &str = "currobot-data-export";
+const FORMAT_VERSION: u32 = 1;
+const MANIFEST: &str = "manifest.json";
+// The engine's database and files in its data folder (backend/config.py)
+const ENGINE_DB: &str = "jobs.db";
+const ENGINE_FILES: &[&str] = &["cv_master.pdf", "cv_sources", "cv_generated"];
+// The shell's JSON stores with the user's own data, in the profile's data
+// and config folders
+const DATA_STORES: &[&str] = &[
+    "templates.json",
+    "job-alerts.json",
+    "offline-queue.json",
+    "webhook-deliveries.json",
+];
+const CONFIG_STORES: &[&str] = &["webhooks.json", "schedules.json", "proxy-pool.json"];
+const AUDIT_LOG: &str = "audit.log";
+// Said in the manifest, so nobody goes looking for them
+const LEFT_OUT: &[&str] = &[
+    "passwords, tokens and API keys, kept in the OS keychain",
+    "session cookies for job sites, kept in the cookie vault",
+    "browser profiles used to apply",
+    "the error reporting address",
+];
+
+#[derive(Debug, Serialize)]
+struct ManifestFile {
+    path: String,
+    size: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct Manifest {
+    format: &'static str,
+    format_version: u32,
+    created_at: DateTime<Utc>,
+    app_version: String,
+    profile: String,
+    includes_logs: bool,
+    files: Vec<ManifestFile>,
+    left_out: Vec<&'static str>,
+    // Files that couldn't be read, with why
+    skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataExport {
+    pub path: String,
+    pub files: usize,
+    pub size: u64,
+    pub skipped: usize,
+}
+
+struct Archive {
+    zip: ZipWriter<File>,
+    manifest: Manifest,
+}
+
+impl Archive {
+    fn write(&mut self, entry: &str, bytes: &[u8]) -> Result<(), String> {
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(bytes.len() as u64 >= u64::from(u32::MAX));
+        self.zip
+            .start_file(entry, options)
+            .and_then(|_| Ok(self.zip.write_all(bytes)?))
+            .map_err(|e| e.to_string())?;
+        self.manifest.files.push(ManifestFile {
+            path: entry.to_string(),
+            size: bytes.len() as u64,
+        });
+        Ok(())
+    }
+
+    fn json(&mut self, entry: &str, value: &Value) -> Result<(), String> {
+        let bytes = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+        self.write(entry, &bytes)
+    }
+
+    fn skip(&mut self, entry: &str, error: impl std::fmt::Display) {
+        warn!(file = entry, "left out of the data export: {error}");
+        self.manifest.skipped.push(format!("{entry}: {error}"));
+    }
+
+    fn file(&mut self, entry: &str, source: &Path) -> Result<(), String> {
+        let mut file = File::open(source).map_err(|e| e.to_string())?;
+        let size = file.metadata().map_err(|e| e.to_string())?.len();
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(size >= u64::from(u32::MAX));
+        self.zip
+            .start_file(entry, options)
+            .map_err(|e| e.to_string())?;
+        let size = io::copy(&mut file, &mut self.zip).map_err(|e| e.to_string())?;
+        self.manifest.files.push(ManifestFile {
+            path: entry.to_string(),
+            size,
+        });
+        Ok(())
+    }
+
+    // Every file under `source`, as `entry`/...; nothing if it isn't there.
+    fn tree(&mut self, entry: &str, source: &Path) {
+        if source.is_file() {
+            if let Err(e) = self.file(entry, source) {
+                self.skip(entry, e);
+            }
+            return;
+        }
+        let Ok(children) = fs::read_dir(source) else {
+            return;
+        };
+        let mut children: Vec<_> = children.flatten().map(|c| c.path()).collect();
+        children.sort();
+        for child in children {
+            let name = child.file_name().unwrap_or_default().to_string_lossy();
+            if name.ends_with(".partial") || name.starts_with('.') {
+                continue;
+            }
+            self.tree(&format!("{entry}/{name}"), &child);
+        }
+    }
+
+    // Each table of `db` as `entry`/<table>.json. Full-text indexes are
+    // left out; they only repeat what the tables have.
+    fn database(&mut self, entry: &str, db: &Connection) -> Result<(), String> {
+        let mut statement = db
+            .prepare(
+                "SELECT name, sql FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+            )
+            .map_err(|e| e.to_string())?;
+        let tables = statement
+            .query_map([], |r| {
+                Ok((r.get::<_, String>(0)?, r.get::<_, Option<String>>(1)?))
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| e.to_string())?;
+        let indexes: Vec<&str> = tables
+            .iter()
+            .filter(|(_, sql)| {
+                sql.as_deref()
+                    .is_some_and(|s| s.starts_with("CREATE VIRTUAL TABLE"))
+            })
+            .map(|(name, _)| name.as_str())
+            .collect();
+        for (table, _) in &tables {
+            if indexes
+                .iter()
+                .any(|index| table == index || table.starts_with(&format!("{index}_")))
+            {
+                continue;
+            }
+            let path = format!("{entry}/{table}.json");
+            match rows(db, table) {
+                Ok(rows) => self.json(&path, &Value::Array(rows))?,
+                Err(e) => self.skip(&path, e),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn cell(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(n) => n.into(),
+        ValueRef::Real(n) => n.into(),
+        ValueRef::Text(text) => String::from_utf8_lossy(text).into(),
+        ValueRef::Blob(bytes) => json!({ "base64": STANDARD.encode(bytes) }),
+    }
+}
+
+fn rows(db: &Connection, table: &str) -> Result<Vec<Value>, String> {
+    let mut statement = db
+        .prepare(&format!("SELECT * FROM \"{}\"", table.replace('"', "\"\"")))
+        .map_err(|e| e.to_string())?;
+    let columns: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    statement
+        .query_map([], |r| {
+            let mut row = Map::new();
+            for (i, column) in columns.iter().enumerate() {
+                row.insert(column.clone(), cell(r.get_ref(i)?));
+            }
+            Ok(Value::Object(row))
+        })
+        .and_then(|rows| rows.collect())
+        .map_err(|e| e.to_string())
+}
+
+// The shell's settings without what could sign in anywhere.
+fn shell_settings(app: &AppHandle) -> Result<Value, String> {
+    let mut settings = settings::get(app);
+    settings.secrets.clear();
+    settings.error_reporting.dsn.clear();
+    settings::to_value(&settings)
+}
+
+fn write(app: &AppHandle, archive: &mut Archive, include_logs: bool) -> Result<(), String> {
+    let data = paths::data_dir(app)?;
+    let config = paths::config_dir(app)?;
+    let engine = paths::engine_dir(app)?;
+
+    archive.database("database/history", &history::open(app)?)?;
+    let engine_db = engine.join(ENGINE_DB);
+    if engine_db.is_file() {
+        let db = Connection::open_with_flags(&engine_db, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| e.to_string())?;
+        db.busy_timeout(Duration::from_secs(5))
+            .map_err(|e| e.to_string())?;
+        archive.database("database/engine", &db)?;
+    }
+
+    archive.tree("files/documents", &documents::root(app)?);
+    for name in ENGINE_FILES {
+        archive.tree(&format!("files/engine/{name}"), &engine.join(name));
+    }
+    archive.tree("files/evidence", &evidence::root(app)?);
+
+    archive.json("settings/settings.json", &shell_settings(app)?)?;
+    for (dir, name) in DATA_STORES
+        .iter()
+        .map(|n| (&data, n))
+        .chain(CONFIG_STORES.iter().map(|n| (&config, n)))
+    {
+        archive.tree(&format!("settings/{name}"), &dir.join(name));
+    }
+
+    if include_logs {
+        archive.tree("logs/shell", &paths::log_dir(app)?);
+        archive.tree("logs/engine", &engine.join("logs"));
+        archive.tree(&format!("logs/{AUDIT_LOG}"), &config.join(AUDIT_LOG));
+    }
+    Ok(())
+}
+
+fn export(app: &AppHandle, target: &Path, include_logs: bool) -> Result<DataExport, String> {
+    let partial = target.with_extension("zip.partial");
+    let mut archive = Archive {
+        zip: ZipWriter::new(File::create(&partial).map_err(|e| e.to_string())?),
+        manifest: Manifest {
+            format: FORMAT,
+            format_version: FORMAT_VERSION,
+            created_at: Utc::now(),
+            app_version: app.package_info().version.to_string(),
+            profile: {
+                let active = profiles::active();
+                profiles::list(app)
+                    .profiles
+                    .into_iter()
+                    .find(|p| p.id == active)
+                    .map_or(active, |p| p.name)
+            },
+            includes_logs: include_logs,
+            files: Vec::new(),
+            left_out: LEFT_OUT.to_vec(),
+            skipped: Vec::new(),
+        },
+    };
+    let written = write(app, &mut archive, include_logs).and_then(|_| {
+        let manifest = serde_json::to_vec_pretty(&archive.manifest).map_err(|e| e.to_string())?;
+        archive
+            .zip
+            .start_file(MANIFEST, SimpleFileOptions::default())
+            .and_then(|_| Ok(archive.zip.write_all(&manifest)?))
+            .map_err(|e| e.to_string())
+    });
+    let finished = written.and_then(|_| archive.zip.finish().map_err(|e| e.to_string()));
+    if let Err(e) = finished.and_then(|_| fs::rename(&partial, target).map_err(|e| e.to_string())) {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    let summary = DataExport {
+        path: target.display().to_string(),
+        files: archive.manifest.files.len(),
+        size: fs::metadata(target).map(|m| m.len()).unwrap_or_default(),
+        skipped: archive.manifest.skipped.len(),
+    };
+    info!(
+        files = summary.files,
+        skipped = summary.skipped,
+        include_logs,
+        "data exported"
+    );
+    audit::record(
+        app,
+        Action::DataExported,
+        json!({ "files": summary.files, "include_logs": include_logs }),
+    );
+    Ok(summary)
+}
+
+// Asks where to save it; None when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_personal_data(
+    app: AppHandle,
+    include_logs: bool,
+) -> Result<Option<DataExport>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(chosen) = app
+            .dialog()
+            .file()
+            .set_title(t("data-export-title"))
+            .set_file_name(format!(
+                "currobot-data-{}.zip",
+                Local::now().format("%Y%m%d")
+            ))
+            .add_filter(t("data-export-filter"), &["zip"])
+            .blocking_save_file()
+        else {
+            return Ok(None);
+        };
+        let target: PathBuf = chosen.into_path().map_err(|e| e.to_string())?;
+        export(&app, &target, include_logs).map(Some)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn temp(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("jobbot-dataexport-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn archive(path: &Path) -> Archive {
+        Archive {
+            zip: ZipWriter::new(File::create(path).unwrap()),
+            manifest: Manifest {
+                format: FORMAT,
+                format_version: FORMAT_VERSION,
+                created_at: Utc::now(),
+                app_version: "1.0.0".into(),
+                profile: "default".into(),
+                includes_logs: false,
+                files: Vec::new(),
+                left_out: LEFT_OUT.to_vec(),
+                skipped: Vec::new(),
+            },
+        }
+    }
+
+    // The entries written, and what the manifest says of them
+    fn finish(archive: Archive, path: &Path) -> (Vec<String>, Vec<String>) {
+        archive.zip.finish().unwrap();
+        let read = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut names: Vec<String> = read.file_names().map(|n| n.unwrap().to_string()).collect();
+        names.sort();
+        let listed = archive.manifest.files.into_iter().map(|f| f.path).collect();
+        (names, listed)
+    }
+
+    fn entry(path: &Path, name: &str) -> String {
+        let mut read = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut text = String::new();
+        read.by_name(name)
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        text
+    }
+
+    #[test]
+    fn cells_as_json() {
+        let db = Connection::open_in_memory().unwrap();
+        let row: Vec<Value> = db
+            .query_row("SELECT NULL, 7, 1.5, 'café', x'00ff'", [], |r| {
+                (0..5).map(|i| Ok(cell(r.get_ref(i)?))).collect()
+            })
+            .unwrap();
+        assert_eq!(
+            row,
+            [
+                Value::Null,
+                json!(7),
+                json!(1.5),
+                json!("café"),
+                json!({ "base64": "AP8=" })
+            ]
+        );
+    }
+
+    #[test]
+    fn rows_by_column_name() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(
+            "CREATE TABLE \"odd \"\"name\" (id INTEGER, company TEXT);
+             INSERT INTO \"odd \"\"name\" VALUES (1, 'Acme'), (2, NULL);",
+        )
+        .unwrap();
+        assert_eq!(
+            rows(&db, "odd \"name").unwrap(),
+            [
+                json!({ "id": 1, "company": "Acme" }),
+                json!({ "id": 2, "company": null })
+            ]
+        );
+        assert!(rows(&db, "missing").is_err());
+    }
+
+    #[test]
+    fn tables_without_search_indexes() {
+        let dir = temp("database");
+        let path = dir.join("export.zip");
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        db.execute(
+            "INSERT INTO applications (id, company, role, status, submitted_at, recorded_at)
+             VALUES (1, 'Acme', 'Cajero', 'applied', '2026-10-01T09:00:00Z', '')",
+            [],
+        )
+        .unwrap();
+        let mut export = archive(&path);
+        export.database("database/history", &db).unwrap();
+        let (names, listed) = finish(export, &path);
+        assert_eq!(names, listed);
+        assert!(names.contains(&"database/history/applications.json".to_string()));
+        assert!(!names.iter().any(|n| n.contains("application_search")));
+        let applications: Value =
+            serde_json::from_str(&entry(&path, "database/history/applications.json")).unwrap();
+        assert_eq!(applications[0]["company"], "Acme");
+        assert_eq!(applications.as_array().unwrap().len(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn trees_skip_partial_and_hidden_files() {
+        let dir = temp("tree");
+        let source = dir.join("documents");
+        fs::create_dir_all(source.join("letters")).unwrap();
+        fs::write(source.join("cv.pdf"), "cv").unwrap();
+        fs::write(source.join("letters/acme.txt"), "dear acme").unwrap();
+        fs::write(source.join("upload.pdf.partial"), "half").unwrap();
+        fs::write(source.join(".DS_Store"), "").unwrap();
+        fs::write(dir.join("alerts.json"), "[]").unwrap();
+        let path = dir.join("export.zip");
+        let mut export = archive(&path);
+        export.tree("files/documents", &source);
+        export.tree("settings/alerts.json", &dir.join("alerts.json"));
+        export.tree("settings/missing.json", &dir.join("missing.json"));
+        assert!(export.manifest.skipped.is_empty());
+        let (names, listed) = finish(export, &path);
+        assert_eq!(
+            names,
+            [
+                "files/documents/cv.pdf",
+                "files/documents/letters/acme.txt",
+                "settings/alerts.json"
+            ]
+        );
+        assert_eq!(listed.len(), 3);
+        assert_eq!(
+            entry(&path, "files/documents/letters/acme.txt"),
+            "dear acme"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf, thread, time::Duration};
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::{
+    backend, quarantine,
+    settings::{self, UpdateChannel},
+};
+
+// Site selectors and cover-letter templates change far more often than code,
+// so the engine can read them from versioned data packs instead of its own
+// bundle. Layout under <app data>/datapacks/:
+//   index.json               {"selectors": "2024.06.1", ...}
+//   <name>/<version>.json    downloaded pack
+//   <name>/current.json      copy of the active version, swapped by rename
+// The backend gets the root via JOBBOT_DATA_PACKS_DIR and re-reads it on
+// POST /api/data-packs/reload.
+const STABLE_MANIFEST: &str =
+    "https://github.com/IFAKA/currobot/releases/download/data-packs/datapacks.json";
+const BETA_MANIFEST: &str =
+    "https://github.com/IFAKA/currobot/releases/download/data-packs-beta/datapacks.json";
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(2 * 60);
+
+// Selectors and templates; anything near this is not a real pack
+const MAX_PACK_BYTES: u64 = 16 * 1024 * 1024;
+
+pub const DATA_PACKS_ENV: &str = "JOBBOT_DATA_PACKS_DIR";
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    packs: BTreeMap<String, Pack>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Pack {
+    version: String,
+    url: String,
+    sha256: String,
+    signature: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataPackInfo {
+    pub name: String,
+    pub version: String,
+}
+
+pub fn root(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::paths::base_data_dir(app)?.join("datapacks"))
+}
+
+fn load_index(app: &AppHandle) -> BTreeMap<String, String> {
+    root(app)
+        .ok()
+        .and_then(|r| fs::read_to_string(r.join("index.json")).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+// Write-then-rename so readers never see a half-written file.
+fn write_atomic(path: &std::path::Path, data: &[u8]) -> Result<(), String> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, data).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+fn fetch_manifest(app: &AppHandle) -> Result<Manifest, String> {
+    let url = match settings::get(app).update.channel {
+        UpdateChannel::Stable => STABLE_MANIFEST,
+        UpdateChannel::Beta => BETA_MANIFEST,
+    };
+    crate::net::client(Duration::from_secs(30))?
+        .get(url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.json())
+        .map_err(|e| e.to_string())
+}
+
+fn install(app: &AppHandle, name: &str, pack: &Pack) -> Result<(), String> {
+    let file = quarantine::fetch(
+        app,
+        &pack.url,
+        Duration::from_secs(120),
+        &quarantine::Expected {
+            name: &format!("data pack {name} {}", pack.version),
+            max_bytes: MAX_PACK_BYTES,
+            file_type: quarantine::FileType::Json,
+            sha256: &pack.sha256,
+            signature: &pack.signature,
+        },
+    )?;
+    let bytes = file.bytes()?;
+    let dir = root(app)?.join(name);
+    file.release(&dir.join(format!("{}.json", pack.version)))?;
+    write_atomic(&dir.join("current.json"), &bytes)
+}
+
+// The manifest's packs at a version other than the one installed.
+fn outdated<'a>(
+    manifest: &'a Manifest,
+    index: &BTreeMap<String, String>,
+) -> Vec<(&'a String, &'a Pack)> {
+    manifest
+        .packs
+        .iter()
+        .filter(|(name, pack)| index.get(*name) != Some(&pack.version))
+        .collect()
+}
+
+// Returns the packs that changed. The backend is told to reload once, after
+// every pack has been swapped in.
+fn check_and_apply(app: &AppHandle) -> Result<Vec<DataPackInfo>, String> {
+    let manifest = fetch_manifest(app)?;
+    let mut index = load_index(app);
+    let mut updated = Vec::new();
+    for (name, pack) in outdated(&manifest, &index) {
+        if let Err(e) = install(app, name, pack) {
+            warn!("data pack {name} update failed: {e}");
+            continue;
+        }
+        index.insert(name.clone(), pack.version.clone());
+        updated.push(DataPackInfo {
+            name: name.clone(),
+            version: pack.version.clone(),
+        });
+    }
+    if updated.is_empty() {
+        return Ok(updated);
+    }
+    let json = serde_json::to_vec_pretty(&index).map_err(|e| e.to_string())?;
+    write_atomic(&root(app)?.join("index.json"), &json)?;
+    // Not fatal: the backend also reads the packs on its next start
+    if let Err(e) = backend::post_json("/api/data-packs/reload", &serde_json::json!({})) {
+        warn!("data pack reload failed: {e}");
+    }
+    Ok(updated)
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(FIRST_CHECK_DELAY);
+        loop {
+            if let Err(e) = check_and_apply(&app) {
+                warn!("data pack check failed: {e}");
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_data_packs(app: AppHandle) -> Vec<DataPackInfo> {
+    load_index(&app)
+        .into_iter()
+        .map(|(name, version)| DataPackInfo { name, version })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn check_data_packs(app: AppHandle) -> Result<Vec<DataPackInfo>, String> {
+    crate::paths::updates_allowed()?;
+    tauri::async_runtime::spawn_blocking(move || check_and_apply(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn only_other_versions() {
+        let manifest: Manifest = serde_json::from_value(json!({ "packs": {
+            "selectors": { "version": "2026.10.2", "url": "u", "sha256": "s", "signature": "g" },
+            "templates": { "version": "2026.09.1", "url": "u", "sha256": "s", "signature": "g" },
+            "keywords": { "version": "2026.01.1", "url": "u", "sha256": "s", "signature": "g" },
+        }}))
+        .unwrap();
+        let index = BTreeMap::from([
+            ("selectors".to_string(), "2026.10.1".to_string()),
+            ("templates".to_string(), "2026.09.1".to_string()),
+        ]);
+        // A newer, an older and a new pack all count; the same doesn't
+        let names: Vec<&str> = outdated(&manifest, &index)
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, ["keywords", "selectors"]);
+    }
+
+    #[test]
+    fn atomic_writes_replace() {
+        let dir = std::env::temp_dir().join(format!("datapacks-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("current.json");
+        write_atomic(&path, b"{\"v\": 1}").unwrap();
+        write_atomic(&path, b"{\"v\": 2}").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"{\"v\": 2}");
+        assert!(!path.with_extension("tmp").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{
+    async_runtime::Receiver,
+    menu::{CheckMenuItem, IsMenuItem, MenuItem, Submenu},
+    AppHandle, Emitter, Manager, Wry,
+};
+use tauri_plugin_shell::process::CommandEvent;
+use tracing::{info, warn};
+
+use crate::{i18n::t, paths, settings};
+
+// Debug mode (`debug_mode` in settings.json) for chasing problems on a user's
+// machine: the shell logs at debug level or above, the tray gains a Developer
+// submenu, the engine's output is echoed to a console and the pages show an
+// overlay with internal state. Debug builds have a tray item for it; release
+// builds only the setting. The engine's own console level follows on its
+// next start.
+pub const DEBUG_MODE_CHANGED: &str = "debug-mode-changed";
+
+static ON: AtomicBool = AtomicBool::new(false);
+
+pub fn is_on() -> bool {
+    ON.load(Ordering::Relaxed)
+}
+
+// At startup and whenever settings change.
+pub fn refresh(app: &AppHandle) {
+    let on = settings::get(app).debug_mode;
+    if ON.swap(on, Ordering::Relaxed) == on {
+        return;
+    }
+    platform::console(on);
+    info!(on, "debug mode");
+    let _ = app.emit(DEBUG_MODE_CHANGED, on);
+}
+
+pub fn backend_env() -> Option<(&'static str, &'static str)> {
+    // backend/config.py `debug`, through pydantic-settings
+    is_on().then_some(("DEBUG", "true"))
+}
+
+// Drains the engine's stdout and stderr, printing them while debug mode is
+// on. Not reading them would eventually stall the engine on a full pipe.
+pub fn forward_output(mut rx: Receiver<CommandEvent>) {
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            if let CommandEvent::Stdout(line) | CommandEvent::Stderr(line) = event {
+                if is_on() {
+                    println!("[engine] {}", String::from_utf8_lossy(&line).trim_end());
+                }
+            }
+        }
+    });
+}
+
+// Toggle item for debug builds; None in release.
+pub fn tray_toggle(app: &AppHandle) -> tauri::Result<Option<CheckMenuItem<Wry>>> {
+    if !cfg!(debug_assertions) {
+        return Ok(None);
+    }
+    CheckMenuItem::with_id(
+        app,
+        "debug_mode",
+        t("tray-debug-mode"),
+        true,
+        is_on(),
+        None::<&str>,
+    )
+    .map(Some)
+}
+
+pub fn tray_menu(app: &AppHandle) -> tauri::Result<Option<Submenu<Wry>>> {
+    if !is_on() {
+        return Ok(None);
+    }
+    let mut items = vec![
+        MenuItem::with_id(app, "dev:logs", t("tray-dev-logs"), true, None::<&str>)?,
+        MenuItem::with_id(
+            app,
+            "dev:restart",
+            t("tray-dev-restart-engine"),
+            true,
+            None::<&str>,
+        )?,
+        MenuItem::with_id(app, "dev:reload", t("tray-dev-reload"), true, None::<&str>)?,
+    ];
+    // The inspector is only compiled into debug builds
+    if cfg!(debug_assertions) {
+        items.push(MenuItem::with_id(
+            app,
+            "dev:devtools",
+            t("tray-dev-devtools"),
+            true,
+            None::<&str>,
+        )?);
+    }
+    let refs: Vec<&dyn IsMenuItem<Wry>> = items.iter().map(|i| i as _).collect();
+    Submenu::with_id_and_items(app, "developer", t("tray-developer"), true, &refs).map(Some)
+}
+
+// The tray items above.
+#[allow(deprecated)]
+pub fn on_menu_event(app: &AppHandle, id: &str) {
+    use tauri_plugin_shell::ShellExt;
+    match id {
+        "debug_mode" => {
+            let on = !is_on();
+            if let Err(e) = set_debug_mode(app.clone(), on) {
+                warn!("debug mode not saved: {e}");
+            }
+        }
+        "dev:logs" => {
+            if let Ok(dir) = paths::log_dir(app) {
+                let _ = app.shell().open(dir.to_string_lossy(), None);
+            }
+        }
+        "dev:restart" => {
+            if let Err(e) = crate::restart_backend(app) {
+                warn!("engine restart failed: {e}");
+            }
+        }
+        "dev:reload" => {
+            if let Some(w) = app.get_webview_window(crate::scope::MAIN_WINDOW) {
+                let _ = w.eval("window.location.reload()");
+            }
+        }
+        "dev:devtools" => open_devtools(app),
+        _ => {}
+    }
+}
+
+#[cfg(debug_assertions)]
+fn open_devtools(app: &AppHandle) {
+    if let Some(w) = app.get_webview_window(crate::scope::MAIN_WINDOW) {
+        w.open_devtools();
+    }
+}
+
+#[cfg(not(debug_assertions))]
+fn open_devtools(_app: &AppHandle) {}
+
+#[tauri::command]
+pub fn get_debug_mode() -> bool {
+    is_on()
+}
+
+#[tauri::command]
+pub fn set_debug_mode(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update(&app, |s| s.debug_mode = enabled)?;
+    refresh(&app);
+    crate::refresh_tray_menu(&app);
+    Ok(())
+}
+
+// Release builds on Windows have no console; one is opened for the engine's
+// output while debug mode is on.
+#[cfg(windows)]
+mod platform {
+    use windows::Win32::System::Console::{AllocConsole, FreeConsole};
+
+    pub fn console(on: bool) {
+        if cfg!(debug_assertions) {
+            return;
+        }
+        unsafe {
+            let _ = if on { AllocConsole() } else { FreeConsole() };
+        }
+    }
+}
+
+// Started from a terminal, macOS and Linux builds print there already.
+#[cfg(not(windows))]
+mod platform {
+    pub fn console(_on: bool) {}
+}
@@ -0,0 +1,242 @@
+use chrono::{DateTime, Local, NaiveTime};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::{thread, time::Duration};
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::{
+    activity::{self, DayActivity},
+    calendar, history,
+    i18n::{t, t_args},
+    notifications, settings,
+    telemetry::{self, Counter},
+};
+
+#[derive(Debug, Serialize)]
+pub struct DigestSummary {
+    pub submitted_today: u64,
+    pub needs_review: u64,
+    pub interviews_scheduled: u64,
+    // Only when the digest is set to include it
+    pub activity: Option<DayActivity>,
+}
+
+impl DigestSummary {
+    pub fn message(&self) -> String {
+        let body = t_args(
+            "digest-body",
+            &[
+                ("submitted", self.submitted_today.into()),
+                ("review", self.needs_review.into()),
+                ("interviews", self.interviews_scheduled.into()),
+            ],
+        );
+        let Some(day) = &self.activity else {
+            return body;
+        };
+        let activity = t_args(
+            "digest-activity",
+            &[
+                ("focus", activity::duration(day.focus_seconds).into()),
+                ("runs", day.runs.into()),
+                ("run_time", activity::duration(day.run_seconds).into()),
+            ],
+        );
+        format!("{body}\n{activity}")
+    }
+}
+
+// From the history (history.rs), so there's a digest while the engine is
+// down: what was sent today, what waits for review, and the interviews from
+// today on.
+fn summarize(db: &Connection, now: DateTime<Local>) -> Result<DigestSummary, String> {
+    let today = now.date_naive();
+    let submitted: Vec<Option<String>> = db
+        .prepare(
+            "SELECT submitted_at FROM applications
+             WHERE imported_from IS NULL AND submitted_at IS NOT NULL",
+        )
+        .and_then(|mut s| s.query_map([], |r| r.get(0))?.collect())
+        .map_err(|e| e.to_string())?;
+    let needs_review: u64 = db
+        .query_row(
+            "SELECT COUNT(*) FROM applications WHERE status = 'pending_human_review'",
+            [],
+            |r| r.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let interviews = calendar::list(db, None)?
+        .into_iter()
+        .filter(|e| {
+            e.kind == calendar::Kind::Interview
+                && e.starts_at.with_timezone(&Local).date_naive() >= today
+        })
+        .count();
+    Ok(DigestSummary {
+        submitted_today: submitted
+            .iter()
+            .filter(|at| history::local(at).is_some_and(|t| t.date() == today))
+            .count() as u64,
+        needs_review,
+        interviews_scheduled: interviews as u64,
+        activity: None,
+    })
+}
+
+pub fn compute(app: &AppHandle) -> Result<DigestSummary, String> {
+    let mut summary = summarize(&history::open(app)?, Local::now())?;
+    if settings::get(app).digest.include_activity {
+        summary.activity = activity::today(app)
+            .map_err(|e| warn!("digest without activity: {e}"))
+            .ok();
+    }
+    Ok(summary)
+}
+
+fn send(app: &AppHandle) -> Result<(), String> {
+    let summary = compute(app)?;
+    notifications::notify_route(app, &t("digest-title"), &summary.message(), "/");
+    Ok(())
+}
+
+fn is_due(cfg: &settings::DigestSettings, now: DateTime<Local>) -> bool {
+    let Ok(at) = NaiveTime::parse_from_str(&cfg.time, "%H:%M") else {
+        return false;
+    };
+    let today = now.date_naive().to_string();
+    cfg.enabled && now.time() >= at && cfg.last_sent_on.as_deref() != Some(today.as_str())
+}
+
+// Polls once a minute rather than sleeping until the target time, so changes
+// to the chosen time and system sleep/wake are picked up without extra wiring.
+pub fn start_scheduler(app: AppHandle) {
+    thread::spawn(move || loop {
+        let now = Local::now();
+        if is_due(&settings::get(&app).digest, now) {
+            match send(&app) {
+                Ok(()) => {
+                    let today = now.date_naive().to_string();
+                    let _ = settings::update(&app, |s| s.digest.last_sent_on = Some(today));
+                }
+                Err(e) => warn!("daily digest failed: {e}"),
+            }
+        }
+        thread::sleep(Duration::from_secs(60));
+    });
+}
+
+#[tauri::command]
+pub fn get_digest_settings(app: AppHandle) -> settings::DigestSettings {
+    settings::get(&app).digest
+}
+
+// Without `include_activity`, that stays as it was.
+#[tauri::command]
+pub fn set_digest_settings(
+    app: AppHandle,
+    enabled: bool,
+    time: String,
+    include_activity: Option<bool>,
+) -> Result<(), String> {
+    NaiveTime::parse_from_str(&time, "%H:%M").map_err(|_| format!("invalid time: {time}"))?;
+    settings::update(&app, |s| {
+        s.digest.enabled = enabled;
+        s.digest.time = time;
+        if let Some(include) = include_activity {
+            s.digest.include_activity = include;
+        }
+    })
+    .map(|_| ())
+}
+
+// Lets the Settings page preview the digest without waiting for the schedule.
+#[tauri::command]
+pub async fn send_digest_now(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        telemetry::record(&app, Counter::DigestSentNow);
+        send(&app)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn cfg(time: &str, last_sent_on: Option<&str>) -> settings::DigestSettings {
+        settings::DigestSettings {
+            time: time.into(),
+            last_sent_on: last_sent_on.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 10, 15, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn due_once_a_day_after_the_time() {
+        assert!(!is_due(&cfg("20:00", None), at(19, 59)));
+        assert!(is_due(&cfg("20:00", None), at(20, 0)));
+        assert!(is_due(&cfg("20:00", Some("2026-10-14")), at(23, 0)));
+        assert!(!is_due(&cfg("20:00", Some("2026-10-15")), at(23, 0)));
+    }
+
+    #[test]
+    fn not_due_when_off_or_unreadable() {
+        let off = settings::DigestSettings {
+            enabled: false,
+            ..cfg("08:00", None)
+        };
+        assert!(!is_due(&off, at(12, 0)));
+        assert!(!is_due(&cfg("8pm", None), at(23, 0)));
+    }
+
+    #[test]
+    fn counts_from_the_history() {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (id, status, submitted_at, imported_from) in [
+            (1, "applied", Some("2026-10-15T09:00:00Z"), None),
+            (2, "applied", Some("2026-10-15T11:30:00"), None),
+            (3, "applied", Some("2026-10-14T09:00:00Z"), None),
+            (4, "applied", Some("2026-10-15T09:00:00Z"), Some("linkedin")),
+            (5, "pending_human_review", None, None),
+            (6, "pending_human_review", None, None),
+            (7, "interview_scheduled", Some("2026-09-01T09:00:00Z"), None),
+        ] {
+            db.execute(
+                "INSERT INTO applications (id, company, status, submitted_at, imported_from,
+                    recorded_at)
+                 VALUES (?1, 'Acme', ?2, ?3, ?4, '')",
+                rusqlite::params![id, status, submitted_at, imported_from],
+            )
+            .unwrap();
+        }
+        for (kind, starts_at) in [
+            ("interview", "2026-10-15T08:00:00Z"),
+            ("interview", "2026-10-21T10:00:00Z"),
+            ("interview", "2026-10-01T10:00:00Z"),
+            ("follow_up", "2026-10-20T09:00:00Z"),
+        ] {
+            db.execute(
+                "INSERT INTO calendar_events (application_id, kind, starts_at, duration_minutes,
+                    created_at, updated_at)
+                 VALUES (7, ?1, ?2, 45, '', '')",
+                [kind, starts_at],
+            )
+            .unwrap();
+        }
+        let summary = summarize(&db, at(20, 0)).unwrap();
+        assert_eq!(summary.submitted_today, 2);
+        assert_eq!(summary.needs_review, 2);
+        assert_eq!(summary.interviews_scheduled, 2);
+        assert!(summary.activity.is_none());
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    i18n::{t, t_args},
+    notifications,
+};
+
+// Event-driven notifications go through here instead of straight to the OS:
+// the first one in a category is shown immediately, anything arriving within
+// that category's interval is held and later flushed as one summary of what
+// was held ("5 more applications submitted in the last 10 minutes").
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Category {
+    Submitted,
+    ReviewReady,
+    ReviewExpiring,
+    ScraperError,
+    JobAlert,
+}
+
+impl Category {
+    fn min_interval(self) -> Duration {
+        match self {
+            Category::Submitted => Duration::from_secs(10 * 60),
+            Category::ReviewReady => Duration::from_secs(5 * 60),
+            Category::ScraperError => Duration::from_secs(30 * 60),
+            Category::JobAlert => Duration::from_secs(5 * 60),
+            // Time-critical: the form session is about to be lost
+            Category::ReviewExpiring => Duration::ZERO,
+        }
+    }
+
+    // Where a click on the summary leads.
+    fn route(self) -> &'static str {
+        match self {
+            Category::Submitted | Category::ReviewReady | Category::ReviewExpiring => {
+                "/applications"
+            }
+            Category::ScraperError => "/",
+            Category::JobAlert => "/jobs",
+        }
+    }
+
+    fn summary(self, count: usize, minutes: u64) -> (String, String) {
+        let (title, body) = match self {
+            Category::Submitted => ("submitted-summary-title", "submitted-summary-body"),
+            Category::ReviewReady => ("review-ready-title", "review-ready-summary-body"),
+            Category::ReviewExpiring => (
+                "review-expiring-summary-title",
+                "review-expiring-summary-body",
+            ),
+            Category::ScraperError => ("scraper-error-summary-title", "scraper-error-summary-body"),
+            Category::JobAlert => ("job-alert-summary-title", "job-alert-summary-body"),
+        };
+        let args = [("count", count.into()), ("minutes", minutes.into())];
+        (t(title), t_args(body, &args))
+    }
+}
+
+struct Held {
+    first_at: Instant,
+    // title, body, route
+    items: Vec<(String, String, String)>,
+}
+
+#[derive(Default)]
+struct Slot {
+    last_sent: Option<Instant>,
+    held: Option<Held>,
+}
+
+#[derive(Default)]
+pub struct DispatcherState(Mutex<HashMap<Category, Slot>>);
+
+fn is_limited(slot: &Slot, category: Category, now: Instant) -> bool {
+    slot.last_sent
+        .is_some_and(|t| now.duration_since(t) < category.min_interval())
+}
+
+pub fn submit(app: &AppHandle, category: Category, title: &str, body: &str, route: &str) {
+    let now = Instant::now();
+    let state = app.state::<DispatcherState>();
+    let mut slots = state.0.lock().unwrap();
+    let slot = slots.entry(category).or_default();
+    if slot.held.is_none() && !is_limited(slot, category, now) {
+        slot.last_sent = Some(now);
+        drop(slots);
+        notifications::notify_route(app, title, body, route);
+        return;
+    }
+    slot.held
+        .get_or_insert_with(|| Held {
+            first_at: now,
+            items: Vec::new(),
+        })
+        .items
+        .push((title.into(), body.into(), route.into()));
+}
+
+fn flush(app: &AppHandle) {
+    let now = Instant::now();
+    let mut ready = Vec::new();
+    {
+        let state = app.state::<DispatcherState>();
+        let mut slots = state.0.lock().unwrap();
+        for (category, slot) in slots.iter_mut() {
+            if slot.held.is_none() || is_limited(slot, *category, now) {
+                continue;
+            }
+            let held = slot.held.take().unwrap();
+            slot.last_sent = Some(now);
+            ready.push((*category, held));
+        }
+    }
+    for (category, held) in ready {
+        let (title, body, route) = announce(category, held, now);
+        notifications::notify_route(app, &title, &body, &route);
+    }
+}
+
+// What was held, as itself when there's one, otherwise as a count of them;
+// the notification shown before they were held isn't counted again.
+fn announce(category: Category, mut held: Held, now: Instant) -> (String, String, String) {
+    if held.items.len() == 1 {
+        return held.items.pop().unwrap();
+    }
+    let minutes = now
+        .duration_since(held.first_at)
+        .as_secs()
+        .div_ceil(60)
+        .max(1);
+    let (title, body) = category.summary(held.items.len(), minutes);
+    (title, body, category.route().to_string())
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_secs(15));
+        flush(&app);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limited_within_the_interval() {
+        let now = Instant::now();
+        let fresh = Slot::default();
+        assert!(!is_limited(&fresh, Category::Submitted, now));
+        let sent = Slot {
+            last_sent: Some(now),
+            held: None,
+        };
+        assert!(is_limited(
+            &sent,
+            Category::Submitted,
+            now + Duration::from_secs(60)
+        ));
+        assert!(!is_limited(
+            &sent,
+            Category::Submitted,
+            now + Category::Submitted.min_interval()
+        ));
+        // Expiring reviews are never held back
+        assert!(!is_limited(&sent, Category::ReviewExpiring, now));
+    }
+
+    #[test]
+    fn summaries_lead_to_their_page() {
+        assert_eq!(Category::JobAlert.route(), "/jobs");
+        assert_eq!(Category::ScraperError.route(), "/");
+        let (title, body) = Category::Submitted.summary(5, 10);
+        assert!(!title.is_empty());
+        assert!(body.contains('5') && body.contains("10"));
+    }
+
+    #[test]
+    fn held_notifications_are_counted_alone() {
+        let now = Instant::now();
+        let item = |n: u32| {
+            (
+                format!("Sent {n}"),
+                format!("Acme {n}"),
+                "/applications".to_string(),
+            )
+        };
+        let one = Held {
+            first_at: now,
+            items: vec![item(1)],
+        };
+        assert_eq!(announce(Category::Submitted, one, now), item(1));
+        let three = Held {
+            first_at: now - Duration::from_secs(4 * 60 + 1),
+            items: vec![item(1), item(2), item(3)],
+        };
+        let (_, body, route) = announce(Category::Submitted, three, now);
+        assert_eq!(route, "/applications");
+        assert!(body.contains('3') && body.contains('5'), "{body}");
+        assert!(!body.contains('4'), "{body}");
+    }
+}
@@ -0,0 +1,147 @@
+use std::thread;
+use tauri::AppHandle;
+
+use crate::automation;
+
+// The Dock icon's menu on macOS, for people who use the Dock rather than the
+// menu bar: Open as in the tray, pausing and running the scheduled scrapers
+// and the last few applications. Its items are plain menu items, so clicks
+// reach `build_tray`'s handler like the tray's own. Rebuilt off the main
+// thread when the engine reports a change; AppKit asks for the current one
+// each time the menu opens.
+pub fn init(app: &AppHandle) {
+    platform::init();
+    refresh(app);
+}
+
+pub fn refresh(app: &AppHandle) {
+    // Only the macOS build has a Dock menu
+    if cfg!(target_os = "macos") {
+        let app = app.clone();
+        thread::spawn(move || platform::rebuild(&app));
+    }
+}
+
+// The items added in `platform::build`.
+pub fn on_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        "dock:pause" => automation::set_paused(true),
+        "dock:resume" => automation::set_paused(false),
+        "dock:run" => automation::run_now(app),
+        id => {
+            if let Some(application) = id
+                .strip_prefix("dock:recent:")
+                .and_then(|id| id.parse().ok())
+            {
+                automation::open_application(app, application);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use muda::{CheckMenuItem, ContextMenu, Menu, MenuItem, PredefinedMenuItem, Submenu};
+    use objc2::{
+        ffi,
+        runtime::{AnyClass, AnyObject, Imp, Sel},
+        sel, MainThreadMarker,
+    };
+    use objc2_app_kit::NSApplication;
+    use std::cell::RefCell;
+    use tauri::AppHandle;
+    use tracing::warn;
+
+    use crate::{automation, i18n::t};
+
+    const RECENT_COUNT: usize = 5;
+
+    thread_local! {
+        // Set and read on the main thread only
+        static MENU: RefCell<Option<Menu>> = const { RefCell::new(None) };
+    }
+
+    struct Snapshot {
+        // None while the engine doesn't answer
+        paused: Option<bool>,
+        recent: Vec<(i64, String)>,
+    }
+
+    fn build(snapshot: &Snapshot) -> muda::Result<Menu> {
+        let reachable = snapshot.paused.is_some();
+        let paused = snapshot.paused.unwrap_or(false);
+        let recent = Submenu::new(t("automation-recent"), !snapshot.recent.is_empty());
+        for (id, label) in &snapshot.recent {
+            recent.append(&MenuItem::with_id(
+                format!("dock:recent:{id}"),
+                label,
+                true,
+                None,
+            ))?;
+        }
+        Menu::with_items(&[
+            &MenuItem::with_id("open", t("tray-open"), true, None),
+            &PredefinedMenuItem::separator(),
+            &CheckMenuItem::with_id(
+                if paused { "dock:resume" } else { "dock:pause" },
+                t("automation-pause"),
+                reachable,
+                paused,
+                None,
+            ),
+            &MenuItem::with_id("dock:run", t("automation-run-now"), reachable, None),
+            &recent,
+        ])
+    }
+
+    // `applicationDockMenu:` on the delegate tao installed.
+    extern "C-unwind" fn dock_menu(_: &AnyObject, _: Sel, _: &AnyObject) -> *mut AnyObject {
+        MENU.with_borrow(|menu| {
+            menu.as_ref()
+                .map_or(std::ptr::null_mut(), |m| m.ns_menu().cast())
+        })
+    }
+
+    pub fn init() {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+        let Some(delegate) = NSApplication::sharedApplication(mtm).delegate() else {
+            warn!("no application delegate; no dock menu");
+            return;
+        };
+        let object: &AnyObject = (*delegate).as_ref();
+        let class = object.class() as *const AnyClass as *mut AnyClass;
+        let added = unsafe {
+            let imp: Imp = std::mem::transmute(
+                dock_menu as extern "C-unwind" fn(&AnyObject, Sel, &AnyObject) -> *mut AnyObject,
+            );
+            ffi::class_addMethod(class, sel!(applicationDockMenu:), imp, c"@@:@".as_ptr())
+        };
+        if !added.as_bool() {
+            warn!("dock menu not installed");
+        }
+    }
+
+    // From a worker thread: asks the engine, then swaps the menu on the main
+    // thread.
+    pub fn rebuild(app: &AppHandle) {
+        let snapshot = Snapshot {
+            paused: automation::paused(),
+            recent: automation::recent(RECENT_COUNT),
+        };
+        let _ = app.run_on_main_thread(move || match build(&snapshot) {
+            Ok(menu) => MENU.with_borrow_mut(|m| *m = Some(menu)),
+            Err(e) => warn!("dock menu not built: {e}"),
+        });
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use tauri::AppHandle;
+
+    pub fn init() {}
+
+    pub fn rebuild(_app: &AppHandle) {}
+}
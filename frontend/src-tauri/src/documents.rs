@@ -0,0 +1,395 @@
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_dialog::DialogExt;
+use tracing::info;
+
+use crate::{i18n::t, paths};
+
+// Résumés and cover letters the user keeps for applying, in `documents` in
+// the profile's data folder. Files are stored once each under their SHA-256
+// in `blobs`, so adding the same file again, to any document, copies
+// nothing. A document keeps every version added to it; retiring one hides
+// it from the list but keeps its files, since the engine may still point at
+// them. `index.json` is the catalogue, and the engine reads it too
+// (backend/documents/library.py), finding the folder through DOCUMENTS_ENV
+// and a document by its id.
+pub const DOCUMENTS_ENV: &str = "JOBBOT_DOCUMENTS_DIR";
+pub const DOCUMENTS_CHANGED: &str = "documents-changed";
+const INDEX: &str = "index.json";
+const BLOBS: &str = "blobs";
+const MAX_BYTES: u64 = 20 * 1024 * 1024;
+const EXTENSIONS: &[&str] = &["pdf", "docx", "doc", "odt", "rtf", "txt", "md"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Resume,
+    CoverLetter,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Version {
+    // From 1
+    pub version: u32,
+    pub sha256: String,
+    // In `blobs`: the hash and the original extension
+    pub file: String,
+    // As it was called when added
+    pub file_name: String,
+    pub size: u64,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Document {
+    pub id: String,
+    pub kind: Kind,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub target_role: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub retired_at: Option<DateTime<Utc>>,
+    // Oldest first; the last is current
+    pub versions: Vec<Version>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Index {
+    documents: Vec<Document>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentMetadata {
+    pub kind: Kind,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub target_role: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddedDocument {
+    pub document: Document,
+    // The file was already there, as this document or its current version
+    pub duplicate: bool,
+}
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+pub fn root(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join("documents"))
+}
+
+fn load(root: &Path) -> Index {
+    fs::read_to_string(root.join(INDEX))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+// Written beside and renamed over, so the engine never reads half a file.
+fn save(root: &Path, index: &Index) -> Result<(), String> {
+    fs::create_dir_all(root).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    let temp = root.join(format!("{INDEX}.tmp"));
+    fs::write(&temp, json).map_err(|e| e.to_string())?;
+    fs::rename(temp, root.join(INDEX)).map_err(|e| e.to_string())
+}
+
+fn change<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&Path, &mut Index) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = LOCK.lock().unwrap();
+    let root = root(app)?;
+    let mut index = load(&root);
+    let result = f(&root, &mut index)?;
+    save(&root, &index)?;
+    let _ = app.emit(DOCUMENTS_CHANGED, ());
+    Ok(result)
+}
+
+fn new_id() -> String {
+    format!("doc-{}", Local::now().format("%Y%m%d-%H%M%S%3f"))
+}
+
+fn clean_metadata(metadata: DocumentMetadata) -> Result<DocumentMetadata, String> {
+    let name = metadata.name.trim().to_string();
+    if name.is_empty() {
+        return Err("a document needs a name".into());
+    }
+    let mut tags: Vec<String> = metadata
+        .tags
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    Ok(DocumentMetadata {
+        kind: metadata.kind,
+        name,
+        tags,
+        target_role: metadata
+            .target_role
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty()),
+    })
+}
+
+// Copies the file into `blobs` unless its content is already there.
+fn store(root: &Path, source: &Path) -> Result<(String, String, u64), String> {
+    let extension = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .filter(|e| EXTENSIONS.contains(&e.as_str()))
+        .ok_or_else(|| format!("only {} files can be added", EXTENSIONS.join(", ")))?;
+    let size = fs::metadata(source).map_err(|e| e.to_string())?.len();
+    if size > MAX_BYTES {
+        return Err(format!("larger than {} MB", MAX_BYTES / 1024 / 1024));
+    }
+    let bytes = fs::read(source).map_err(|e| e.to_string())?;
+    let sha256: String = Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    let file = format!("{sha256}.{extension}");
+    let blobs = root.join(BLOBS);
+    let target = blobs.join(&file);
+    if !target.exists() {
+        fs::create_dir_all(&blobs).map_err(|e| e.to_string())?;
+        let temp = blobs.join(format!("{file}.tmp"));
+        fs::write(&temp, &bytes).map_err(|e| e.to_string())?;
+        fs::rename(temp, &target).map_err(|e| e.to_string())?;
+    }
+    Ok((sha256, file, size))
+}
+
+fn add(
+    app: &AppHandle,
+    path: PathBuf,
+    document_id: Option<String>,
+    metadata: Option<DocumentMetadata>,
+) -> Result<AddedDocument, String> {
+    let metadata = metadata.map(clean_metadata).transpose()?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    change(app, |root, index| {
+        let (sha256, file, size) = store(root, &path)?;
+        let version = |number| Version {
+            version: number,
+            sha256: sha256.clone(),
+            file: file.clone(),
+            file_name: file_name.clone(),
+            size,
+            added_at: Utc::now(),
+        };
+        let current = |d: &Document| d.versions.last().is_some_and(|v| v.sha256 == sha256);
+        match document_id {
+            // A new version, unless it's the one the document has
+            Some(id) => {
+                let document = index
+                    .documents
+                    .iter_mut()
+                    .find(|d| d.id == id)
+                    .ok_or_else(|| format!("no document {id}"))?;
+                let duplicate = current(document);
+                if !duplicate {
+                    document
+                        .versions
+                        .push(version(document.versions.len() as u32 + 1));
+                    info!(
+                        id,
+                        version = document.versions.len(),
+                        "document version added"
+                    );
+                }
+                Ok(AddedDocument {
+                    document: document.clone(),
+                    duplicate,
+                })
+            }
+            None => {
+                if let Some(existing) = index
+                    .documents
+                    .iter()
+                    .find(|d| d.retired_at.is_none() && current(d))
+                {
+                    return Ok(AddedDocument {
+                        document: existing.clone(),
+                        duplicate: true,
+                    });
+                }
+                let metadata = metadata.ok_or("a new document needs a name and kind")?;
+                let document = Document {
+                    id: new_id(),
+                    kind: metadata.kind,
+                    name: metadata.name,
+                    tags: metadata.tags,
+                    target_role: metadata.target_role,
+                    created_at: Utc::now(),
+                    retired_at: None,
+                    versions: vec![version(1)],
+                };
+                info!(id = document.id, "document added");
+                index.documents.push(document.clone());
+                Ok(AddedDocument {
+                    document,
+                    duplicate: false,
+                })
+            }
+        }
+    })
+}
+
+#[tauri::command]
+pub fn list_documents(app: AppHandle, include_retired: bool) -> Result<Vec<Document>, String> {
+    let mut documents = load(&root(&app)?).documents;
+    if !include_retired {
+        documents.retain(|d| d.retired_at.is_none());
+    }
+    Ok(documents)
+}
+
+// A new document from `path`, or with `document_id` a new version of one.
+// Without a path the user picks the file; None when they cancel.
+#[tauri::command]
+pub async fn add_document(
+    app: AppHandle,
+    path: Option<String>,
+    document_id: Option<String>,
+    metadata: Option<DocumentMetadata>,
+) -> Result<Option<AddedDocument>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let Some(chosen) = app
+                    .dialog()
+                    .file()
+                    .set_title(t("documents-add-title"))
+                    .add_filter(t("documents-filter"), EXTENSIONS)
+                    .blocking_pick_file()
+                else {
+                    return Ok(None);
+                };
+                chosen.into_path().map_err(|e| e.to_string())?
+            }
+        };
+        add(&app, path, document_id, metadata).map(Some)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn update_document(
+    app: AppHandle,
+    id: String,
+    metadata: DocumentMetadata,
+) -> Result<Document, String> {
+    let metadata = clean_metadata(metadata)?;
+    change(&app, |_, index| {
+        let document = index
+            .documents
+            .iter_mut()
+            .find(|d| d.id == id)
+            .ok_or_else(|| format!("no document {id}"))?;
+        document.kind = metadata.kind;
+        document.name = metadata.name;
+        document.tags = metadata.tags;
+        document.target_role = metadata.target_role;
+        Ok(document.clone())
+    })
+}
+
+// Hidden from the list, or back again; the files stay.
+#[tauri::command]
+pub fn retire_document(app: AppHandle, id: String, retired: bool) -> Result<Document, String> {
+    change(&app, |_, index| {
+        let document = index
+            .documents
+            .iter_mut()
+            .find(|d| d.id == id)
+            .ok_or_else(|| format!("no document {id}"))?;
+        document.retired_at = retired.then(Utc::now);
+        Ok(document.clone())
+    })
+}
+
+// Where a version's file is, for opening it; the current one by default.
+#[tauri::command]
+pub fn document_path(app: AppHandle, id: String, version: Option<u32>) -> Result<String, String> {
+    let root = root(&app)?;
+    let index = load(&root);
+    let document = index
+        .documents
+        .iter()
+        .find(|d| d.id == id)
+        .ok_or_else(|| format!("no document {id}"))?;
+    let version = match version {
+        Some(n) => document.versions.iter().find(|v| v.version == n),
+        None => document.versions.last(),
+    }
+    .ok_or("no such version")?;
+    Ok(root.join(BLOBS).join(&version.file).display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(name: &str, tags: &[&str], target_role: Option<&str>) -> DocumentMetadata {
+        DocumentMetadata {
+            kind: Kind::Resume,
+            name: name.into(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            target_role: target_role.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn metadata_is_tidied() {
+        let clean = clean_metadata(metadata(
+            "  Backend CV ",
+            &["Rust", " rust", "", "remote "],
+            Some("  "),
+        ))
+        .unwrap();
+        assert_eq!(clean.name, "Backend CV");
+        assert_eq!(clean.tags, ["remote", "rust"]);
+        assert_eq!(clean.target_role, None);
+        assert!(clean_metadata(metadata(" ", &[], None)).is_err());
+    }
+
+    #[test]
+    fn blobs_are_stored_once_by_content() {
+        let dir = std::env::temp_dir().join(format!("jobbot-documents-{}", std::process::id()));
+        let root = dir.join("documents");
+        fs::create_dir_all(&dir).unwrap();
+        let (a, b, other) = (dir.join("cv.PDF"), dir.join("copy.pdf"), dir.join("cv.exe"));
+        for path in [&a, &b, &other] {
+            fs::write(path, b"%PDF-1.7").unwrap();
+        }
+
+        let (sha, file, size) = store(&root, &a).unwrap();
+        assert_eq!(file, format!("{sha}.pdf"));
+        assert_eq!(size, 8);
+        assert_eq!(store(&root, &b).unwrap().1, file);
+        assert_eq!(fs::read_dir(root.join(BLOBS)).unwrap().count(), 1);
+        assert!(store(&root, &other).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,480 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{Cursor, Read},
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::Duration,
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{quarantine, signing};
+
+// Chrome and Edge only take orders from a webdriver built for their own
+// major version, and both update themselves, so the driver that worked last
+// week stops working today. The shell finds which of them is installed and
+// at what version, and keeps the matching driver in
+// <app data>/drivers/<browser>/. Neither vendor publishes checksums, so
+// drivers come through our own manifest, which lists each driver zip with
+// its SHA-256 and a signature like data packs do, and pass quarantine.
+// Updates replace the binary by rename, keeping its path, so the engine
+// (given the path through WEBDRIVER_ENV when it starts) picks up a new
+// driver the next time it launches one. The binary's own SHA-256 is
+// recorded at install and checked again before the path is handed over.
+const MANIFEST: &str = "https://github.com/IFAKA/currobot/releases/download/drivers/drivers.json";
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+const MAX_ZIP_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_DRIVER_BYTES: u64 = 128 * 1024 * 1024;
+
+pub const WEBDRIVER_ENV: &str = "JOBBOT_WEBDRIVER";
+pub const WEBDRIVER_BROWSER_ENV: &str = "JOBBOT_WEBDRIVER_BROWSER";
+pub const DRIVERS_CHANGED: &str = "drivers-changed";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Browser {
+    Chrome,
+    Edge,
+}
+
+impl Browser {
+    // In order of preference when both are installed
+    const ALL: [Browser; 2] = [Browser::Chrome, Browser::Edge];
+
+    fn key(self) -> &'static str {
+        match self {
+            Browser::Chrome => "chrome",
+            Browser::Edge => "edge",
+        }
+    }
+
+    fn binary(self) -> String {
+        let name = match self {
+            Browser::Chrome => "chromedriver",
+            Browser::Edge => "msedgedriver",
+        };
+        format!("{name}{}", std::env::consts::EXE_SUFFIX)
+    }
+}
+
+// {"chrome": {"131": {"version": "131.0.6778.85",
+//   "downloads": {"linux64": {"url": ..., "sha256": ..., "signature": ...}}}}}
+type Manifest = BTreeMap<Browser, BTreeMap<u32, Release>>;
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    version: String,
+    downloads: BTreeMap<String, Download>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Download {
+    url: String,
+    sha256: String,
+    signature: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Installed {
+    version: String,
+    sha256: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DriverStatus {
+    pub browser: Browser,
+    // None when the browser isn't installed
+    pub browser_version: Option<String>,
+    pub driver_version: Option<String>,
+    // Same major version as the browser, and the file is intact
+    pub up_to_date: bool,
+    pub path: Option<String>,
+}
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+pub fn root(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::paths::base_data_dir(app)?.join("drivers"))
+}
+
+// The names Chrome for Testing uses, which the manifest follows.
+fn platform_key() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("linux64"),
+        ("macos", "aarch64") => Some("mac-arm64"),
+        ("macos", "x86_64") => Some("mac-x64"),
+        ("windows", "x86_64") => Some("win64"),
+        ("windows", "x86") => Some("win32"),
+        _ => None,
+    }
+}
+
+// The first dotted number, e.g. from "Google Chrome 131.0.6778.85".
+fn parse_version(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .find(|w| w.contains('.') && w.chars().all(|c| c.is_ascii_digit() || c == '.'))
+        .map(str::to_string)
+}
+
+fn major(version: &str) -> Option<u32> {
+    version.split('.').next()?.parse().ok()
+}
+
+fn load_index(app: &AppHandle) -> BTreeMap<Browser, Installed> {
+    root(app)
+        .ok()
+        .and_then(|r| fs::read_to_string(r.join("index.json")).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(app: &AppHandle, index: &BTreeMap<Browser, Installed>) -> Result<(), String> {
+    let path = root(app)?.join("index.json");
+    let tmp = path.with_extension("tmp");
+    let json = serde_json::to_vec_pretty(index).map_err(|e| e.to_string())?;
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+fn driver_path(app: &AppHandle, browser: Browser) -> Result<PathBuf, String> {
+    Ok(root(app)?.join(browser.key()).join(browser.binary()))
+}
+
+// The driver's path when it is there and unchanged since it was installed.
+fn verified(app: &AppHandle, browser: Browser, installed: &Installed) -> Option<PathBuf> {
+    let path = driver_path(app, browser).ok()?;
+    let bytes = fs::read(&path).ok()?;
+    if signing::sha256_hex(&bytes) != installed.sha256 {
+        warn!(
+            browser = browser.key(),
+            "browser driver changed since it was installed"
+        );
+        return None;
+    }
+    Some(path)
+}
+
+fn fetch_manifest() -> Result<Manifest, String> {
+    crate::net::client(Duration::from_secs(30))?
+        .get(MANIFEST)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.json())
+        .map_err(|e| e.to_string())
+}
+
+// Reading an entry to the end checks its CRC.
+fn extract(zip: &[u8], binary: &str) -> Result<Vec<u8>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(zip)).map_err(|e| e.to_string())?;
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        // e.g. chromedriver-linux64/chromedriver
+        let found = entry.is_file()
+            && entry
+                .enclosed_name()
+                .is_some_and(|p| p.file_name().is_some_and(|n| n == binary));
+        if !found {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        entry
+            .take(MAX_DRIVER_BYTES + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| e.to_string())?;
+        if bytes.len() as u64 > MAX_DRIVER_BYTES {
+            return Err(format!("{binary} is larger than {MAX_DRIVER_BYTES} bytes"));
+        }
+        return Ok(bytes);
+    }
+    Err(format!("{binary} is not in the download"))
+}
+
+fn install(
+    app: &AppHandle,
+    browser: Browser,
+    release: &Release,
+    index: &mut BTreeMap<Browser, Installed>,
+) -> Result<(), String> {
+    let platform = platform_key().ok_or("no browser drivers for this platform")?;
+    let download = release.downloads.get(platform).ok_or_else(|| {
+        format!(
+            "no {platform} driver for {} {}",
+            browser.key(),
+            release.version
+        )
+    })?;
+    let file = quarantine::fetch(
+        app,
+        &download.url,
+        Duration::from_secs(300),
+        &quarantine::Expected {
+            name: &format!("{} driver {}", browser.key(), release.version),
+            max_bytes: MAX_ZIP_BYTES,
+            file_type: quarantine::FileType::Zip,
+            sha256: &download.sha256,
+            signature: &download.signature,
+        },
+    )?;
+    let binary = extract(&file.bytes()?, &browser.binary())?;
+    drop(file);
+    let path = driver_path(app, browser)?;
+    let dir = path.parent().ok_or("no driver folder")?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("download");
+    fs::write(&tmp, &binary).map_err(|e| e.to_string())?;
+    quarantine::make_executable(&tmp)?;
+    // On Windows this fails while the engine has the old one running; the
+    // next check tries again
+    fs::rename(&tmp, &path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        e.to_string()
+    })?;
+    index.insert(
+        browser,
+        Installed {
+            version: release.version.clone(),
+            sha256: signing::sha256_hex(&binary),
+        },
+    );
+    info!(
+        browser = browser.key(),
+        version = release.version,
+        "browser driver installed"
+    );
+    Ok(())
+}
+
+fn statuses(app: &AppHandle) -> Vec<DriverStatus> {
+    let index = load_index(app);
+    Browser::ALL
+        .into_iter()
+        .map(|browser| {
+            let browser_version = platform::browser_version(browser);
+            let installed = index.get(&browser);
+            let path = installed.and_then(|i| verified(app, browser, i));
+            let browser_major = browser_version.as_deref().and_then(major);
+            let up_to_date = path.is_some()
+                && browser_major.is_some()
+                && browser_major == installed.and_then(|i| major(&i.version));
+            DriverStatus {
+                browser,
+                browser_version,
+                driver_version: installed.map(|i| i.version.clone()),
+                up_to_date,
+                path: path.map(|p| p.display().to_string()),
+            }
+        })
+        .collect()
+}
+
+fn check_and_update(app: &AppHandle) -> Result<Vec<DriverStatus>, String> {
+    let _guard = LOCK.lock().unwrap();
+    let mut manifest: Option<Manifest> = None;
+    let mut index = load_index(app);
+    let mut changed = false;
+    for status in statuses(app) {
+        if status.up_to_date {
+            continue;
+        }
+        let Some(wanted) = status.browser_version.as_deref().and_then(major) else {
+            continue;
+        };
+        // Only fetched when some driver needs replacing
+        if manifest.is_none() {
+            manifest = Some(fetch_manifest()?);
+        }
+        let Some(release) = manifest
+            .as_ref()
+            .and_then(|m| m.get(&status.browser))
+            .and_then(|releases| releases.get(&wanted))
+        else {
+            warn!(
+                browser = status.browser.key(),
+                wanted, "no browser driver published for this version yet"
+            );
+            continue;
+        };
+        match install(app, status.browser, release, &mut index) {
+            Ok(()) => changed = true,
+            Err(e) => warn!(
+                browser = status.browser.key(),
+                "browser driver update failed: {e}"
+            ),
+        }
+    }
+    if changed {
+        save_index(app, &index)?;
+        let _ = app.emit(DRIVERS_CHANGED, ());
+    }
+    Ok(statuses(app))
+}
+
+// The first browser with an intact driver of its own version, for the
+// engine. Nothing when there's none, rather than a driver that won't work.
+pub fn backend_env(app: &AppHandle) -> Vec<(&'static str, String)> {
+    let ready = statuses(app)
+        .into_iter()
+        .find(|s| s.up_to_date)
+        .and_then(|s| Some((s.browser, s.path?)));
+    match ready {
+        Some((browser, path)) => vec![
+            (WEBDRIVER_ENV, path),
+            (WEBDRIVER_BROWSER_ENV, browser.key().to_string()),
+        ],
+        None => Vec::new(),
+    }
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(FIRST_CHECK_DELAY);
+        loop {
+            if let Err(e) = check_and_update(&app) {
+                warn!("browser driver check failed: {e}");
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_browser_drivers(app: AppHandle) -> Result<Vec<DriverStatus>, String> {
+    tauri::async_runtime::spawn_blocking(move || statuses(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_browser_drivers(app: AppHandle) -> Result<Vec<DriverStatus>, String> {
+    crate::paths::updates_allowed()?;
+    tauri::async_runtime::spawn_blocking(move || check_and_update(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::Browser;
+
+    // Both keep their current version in BLBeacon, per user or per machine
+    // depending on how they were installed.
+    pub fn browser_version(browser: Browser) -> Option<String> {
+        let key = match browser {
+            Browser::Chrome => r"Software\Google\Chrome\BLBeacon",
+            Browser::Edge => r"Software\Microsoft\Edge\BLBeacon",
+        };
+        [
+            windows_registry::CURRENT_USER,
+            windows_registry::LOCAL_MACHINE,
+        ]
+        .into_iter()
+        .find_map(|root| root.open(key).ok()?.get_string("version").ok())
+        .and_then(|v| super::parse_version(&v))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::Browser;
+    use std::{fs, path::PathBuf};
+
+    // The bundle's Info.plist, in /Applications or ~/Applications.
+    pub fn browser_version(browser: Browser) -> Option<String> {
+        let bundle = match browser {
+            Browser::Chrome => "Google Chrome.app",
+            Browser::Edge => "Microsoft Edge.app",
+        };
+        let home = std::env::var_os("HOME").map(|h| PathBuf::from(h).join("Applications"));
+        [Some(PathBuf::from("/Applications")), home]
+            .into_iter()
+            .flatten()
+            .find_map(|dir| {
+                let plist =
+                    fs::read_to_string(dir.join(bundle).join("Contents/Info.plist")).ok()?;
+                let after = plist
+                    .split("<key>CFBundleShortVersionString</key>")
+                    .nth(1)?;
+                let start = after.find("<string>")? + "<string>".len();
+                let end = start + after[start..].find("</string>")?;
+                super::parse_version(&after[start..end])
+            })
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::Browser;
+    use std::process::Command;
+
+    pub fn browser_version(browser: Browser) -> Option<String> {
+        let programs: &[&str] = match browser {
+            Browser::Chrome => &[
+                "google-chrome",
+                "google-chrome-stable",
+                "chromium",
+                "chromium-browser",
+            ],
+            Browser::Edge => &["microsoft-edge", "microsoft-edge-stable"],
+        };
+        programs.iter().find_map(|program| {
+            let out = Command::new(program).arg("--version").output().ok()?;
+            super::parse_version(&String::from_utf8_lossy(&out.stdout))
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+mod platform {
+    use super::Browser;
+
+    pub fn browser_version(_browser: Browser) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, bytes) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(bytes).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn versions_from_browser_output() {
+        let version = parse_version("Google Chrome 131.0.6778.85 \n").unwrap();
+        assert_eq!(version, "131.0.6778.85");
+        assert_eq!(major(&version), Some(131));
+        assert_eq!(parse_version("Mozilla Firefox 128.0esr"), None);
+        assert_eq!(major("beta"), None);
+    }
+
+    #[test]
+    fn driver_from_the_download() {
+        let archive = zip(&[
+            ("chromedriver-linux64/LICENSE.chromedriver", b"license"),
+            ("chromedriver-linux64/chromedriver", b"\x7fELF driver"),
+        ]);
+        assert_eq!(
+            extract(&archive, "chromedriver").unwrap(),
+            b"\x7fELF driver"
+        );
+        assert!(extract(&archive, "msedgedriver").is_err());
+        assert!(extract(b"not a zip", "chromedriver").is_err());
+    }
+}
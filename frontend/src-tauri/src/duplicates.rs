@@ -0,0 +1,483 @@
+use chrono::{Duration, Local};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeSet, thread};
+use tauri::{AppHandle, Emitter};
+use tracing::warn;
+
+use crate::{events::BackendEvent, history, i18n::t_args, notifications};
+
+// Catches applying twice to the same job, whether it came from two boards or
+// was scraped again. Every application in the history gets a fingerprint of
+// its company (without legal suffixes), the words of its title (without
+// filler or gender endings, in any order) and its city, kept in
+// `duplicate_index` beside it and rebuilt when any of those change. A new
+// one matches an earlier one of the same company when the fingerprints are
+// equal, or the titles share TITLE_MATCH of their words and the cities don't
+// differ. Applications ready for review are checked as the engine announces
+// them; a match is kept as a flag the review page shows, and notified.
+pub const DUPLICATE_FLAGGED: &str = "duplicate-flagged";
+// Older applications don't count: reapplying after this is deliberate
+const WINDOW_DAYS: i64 = 180;
+const TITLE_MATCH: f64 = 0.6;
+// Never sent, and not going to be
+const IGNORED_STATUSES: &[&str] = &["scraped", "qualified", "expired"];
+const TITLE_NOISE: &[&str] = &[
+    "de", "del", "la", "el", "los", "las", "y", "en", "para", "con", "por", "the", "of", "and",
+    "for", "in", "at", "mf", "hm", "fm", "jornada", "completa", "parcial", "full", "part", "time",
+];
+// Left out when matching company names
+const LEGAL_WORDS: &[&str] = &[
+    "sa", "sl", "slu", "sau", "inc", "ltd", "llc", "gmbh", "group", "grupo", "spain", "espana",
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DuplicateMatch {
+    pub application_id: i64,
+    pub company: String,
+    pub role: Option<String>,
+    pub location: Option<String>,
+    pub status: String,
+    pub applied_at: Option<String>,
+    // Share of title words in common, 1.0 for the same fingerprint
+    pub score: f64,
+    pub exact: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateFlag {
+    pub application_id: i64,
+    pub matches: Vec<DuplicateMatch>,
+    pub flagged_at: String,
+}
+
+// Lowercase words without accents or punctuation, space separated and padded,
+// so " mercadona " can be looked for whole.
+pub fn normalize(text: &str) -> String {
+    let plain: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'ä' => 'a',
+            'é' | 'è' | 'ë' => 'e',
+            'í' | 'ï' => 'i',
+            'ó' | 'ö' => 'o',
+            'ú' | 'ü' => 'u',
+            'ñ' => 'n',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect();
+    format!(
+        " {} ",
+        plain.split_whitespace().collect::<Vec<_>>().join(" ")
+    )
+}
+
+// A normalized company name without its legal form.
+pub fn company_name(company: &str) -> String {
+    let words: Vec<&str> = company
+        .split_whitespace()
+        .filter(|w| !LEGAL_WORDS.contains(w))
+        .collect();
+    words.join(" ")
+}
+
+fn title_words(title: &str) -> BTreeSet<String> {
+    normalize(title)
+        .split_whitespace()
+        .filter(|w| w.len() > 1 && !TITLE_NOISE.contains(w))
+        .map(|w| {
+            // cajero/cajera, dependiente/dependienta
+            match w.strip_suffix(['a', 'o', 'e']) {
+                Some(stem) if stem.len() >= 4 => stem.to_string(),
+                _ => w.to_string(),
+            }
+        })
+        .collect()
+}
+
+// "Madrid, Comunidad de Madrid" and "Madrid (Centro)" are both Madrid.
+fn city(location: &str) -> String {
+    let first = location.split([',', '(', '-', '/']).next().unwrap_or("");
+    normalize(first).trim().to_string()
+}
+
+struct Key {
+    company: String,
+    words: BTreeSet<String>,
+    city: String,
+    fingerprint: String,
+}
+
+fn key(company: &str, title: &str, location: &str) -> Key {
+    let company = company_name(normalize(company).trim());
+    let words = title_words(title);
+    let city = city(location);
+    let joined = words.iter().cloned().collect::<Vec<_>>().join(" ");
+    let digest = Sha256::digest(format!("{company}|{joined}|{city}").as_bytes());
+    Key {
+        fingerprint: digest[..8].iter().map(|b| format!("{b:02x}")).collect(),
+        company,
+        words,
+        city,
+    }
+}
+
+fn similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+// Indexes the applications that are new or changed since.
+fn refresh(db: &Connection) -> Result<(), String> {
+    let stale: Vec<(i64, String, String, String)> = db
+        .prepare(
+            "SELECT a.id, a.company, COALESCE(a.role, ''), COALESCE(a.location, '')
+             FROM applications a LEFT JOIN duplicate_index d ON d.application_id = a.id
+             WHERE d.source IS NOT a.company || '|' || COALESCE(a.role, '') || '|' || COALESCE(a.location, '')",
+        )
+        .and_then(|mut s| {
+            s.query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| e.to_string())?;
+    for (id, company, role, location) in stale {
+        let key = key(&company, &role, &location);
+        let words = key.words.into_iter().collect::<Vec<_>>().join(" ");
+        db.execute(
+            "INSERT OR REPLACE INTO duplicate_index
+                (application_id, source, company, title_words, city, fingerprint)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                id,
+                format!("{company}|{role}|{location}"),
+                key.company,
+                words,
+                key.city,
+                key.fingerprint
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn find(
+    db: &Connection,
+    company: &str,
+    title: &str,
+    location: &str,
+    exclude: Option<i64>,
+) -> Result<Vec<DuplicateMatch>, String> {
+    refresh(db)?;
+    let key = key(company, title, location);
+    if key.company.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ignored = IGNORED_STATUSES
+        .iter()
+        .map(|s| format!("'{s}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut statement = db
+        .prepare(&format!(
+            "SELECT a.id, a.company, a.role, a.location, a.status,
+                    COALESCE(a.submitted_at, a.created_at, a.recorded_at),
+                    d.title_words, d.city, d.fingerprint
+             FROM duplicate_index d JOIN applications a ON a.id = d.application_id
+             WHERE d.company = ?1 AND a.id IS NOT ?2 AND a.status NOT IN ({ignored})"
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map(params![key.company, exclude], |r| {
+            Ok((
+                DuplicateMatch {
+                    application_id: r.get(0)?,
+                    company: r.get(1)?,
+                    role: r.get(2)?,
+                    location: r.get(3)?,
+                    status: r.get(4)?,
+                    applied_at: r.get(5)?,
+                    score: 0.0,
+                    exact: false,
+                },
+                r.get::<_, String>(6)?,
+                r.get::<_, String>(7)?,
+                r.get::<_, String>(8)?,
+            ))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    let since = Local::now().naive_local() - Duration::days(WINDOW_DAYS);
+    let mut matches: Vec<DuplicateMatch> = rows
+        .into_iter()
+        .filter(|(m, ..)| history::local(&m.applied_at).is_none_or(|at| at >= since))
+        .filter_map(|(mut m, words, city, fingerprint)| {
+            let words: BTreeSet<String> = words.split_whitespace().map(str::to_string).collect();
+            m.exact = fingerprint == key.fingerprint;
+            m.score = if m.exact {
+                1.0
+            } else {
+                similarity(&key.words, &words)
+            };
+            let same_place = city.is_empty() || key.city.is_empty() || city == key.city;
+            (m.exact || (m.score >= TITLE_MATCH && same_place)).then_some(m)
+        })
+        .collect();
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    Ok(matches)
+}
+
+fn flag(
+    app: &AppHandle,
+    id: i64,
+    company: &str,
+    title: &str,
+    location: &str,
+) -> Result<(), String> {
+    let db = history::open(app)?;
+    let matches = find(&db, company, title, location, Some(id))?;
+    let Some(first) = matches.first() else {
+        return Ok(());
+    };
+    let json = serde_json::to_string(&matches).map_err(|e| e.to_string())?;
+    db.execute(
+        "INSERT OR REPLACE INTO duplicate_flags (application_id, matches, flagged_at, dismissed)
+         VALUES (?1, ?2, ?3, 0)",
+        params![id, json, chrono::Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+    warn!(
+        application = id,
+        earlier = first.application_id,
+        "possible duplicate application"
+    );
+    let when = history::local(&first.applied_at)
+        .map(|at| at.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    notifications::notify_route(
+        app,
+        &t_args(
+            "duplicate-title",
+            &[("company", company.to_string().into())],
+        ),
+        &t_args(
+            "duplicate-body",
+            &[
+                ("date", when.into()),
+                ("status", first.status.replace('_', " ").into()),
+            ],
+        ),
+        &format!("/review?id={id}"),
+    );
+    let _ = app.emit(
+        DUPLICATE_FLAGGED,
+        serde_json::json!({ "application_id": id, "matches": matches }),
+    );
+    Ok(())
+}
+
+// From the event bridge: applications about to be reviewed are checked.
+pub fn on_event(app: &AppHandle, event: &BackendEvent) {
+    if event.event != "review_ready" {
+        return;
+    }
+    let d = &event.data;
+    let (Some(id), Some(company)) = (d["application_id"].as_i64(), d["company"].as_str()) else {
+        return;
+    };
+    let app = app.clone();
+    let company = company.to_string();
+    let title = d["title"].as_str().unwrap_or("").to_string();
+    let location = d["location"].as_str().unwrap_or("").to_string();
+    thread::spawn(move || {
+        if let Err(e) = flag(&app, id, &company, &title, &location) {
+            warn!(application = id, "duplicate check failed: {e}");
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn check_duplicate(
+    app: AppHandle,
+    company: String,
+    title: String,
+    location: Option<String>,
+    exclude_id: Option<i64>,
+) -> Result<Vec<DuplicateMatch>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = history::open(&app)?;
+        find(
+            &db,
+            &company,
+            &title,
+            location.as_deref().unwrap_or(""),
+            exclude_id,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// The flag on an application, unless dismissed.
+#[tauri::command]
+pub fn get_duplicate_flag(
+    app: AppHandle,
+    application_id: i64,
+) -> Result<Option<DuplicateFlag>, String> {
+    let db = history::open(&app)?;
+    let row: Option<(String, String)> = db
+        .query_row(
+            "SELECT matches, flagged_at FROM duplicate_flags
+             WHERE application_id = ?1 AND dismissed = 0",
+            [application_id],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|(matches, flagged_at)| DuplicateFlag {
+        application_id,
+        matches: serde_json::from_str(&matches).unwrap_or_default(),
+        flagged_at,
+    }))
+}
+
+#[tauri::command]
+pub fn dismiss_duplicate_flag(app: AppHandle, application_id: i64) -> Result<(), String> {
+    let db = history::open(&app)?;
+    db.execute(
+        "UPDATE duplicate_flags SET dismissed = 1 WHERE application_id = ?1",
+        [application_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        let recent = (chrono::Utc::now() - Duration::days(20)).to_rfc3339();
+        let old = (chrono::Utc::now() - Duration::days(WINDOW_DAYS + 20)).to_rfc3339();
+        for (id, company, role, location, status, submitted) in [
+            (
+                1,
+                "Mercadona SA",
+                "Cajero/a jornada completa",
+                "Valencia, Comunidad Valenciana",
+                "applied",
+                &recent,
+            ),
+            (
+                2,
+                "Mercadona",
+                "Reponedor de tienda",
+                "Valencia",
+                "applied",
+                &recent,
+            ),
+            (
+                3,
+                "Mercadona",
+                "Cajera",
+                "Madrid (Centro)",
+                "applied",
+                &recent,
+            ),
+            (4, "Mercadona", "Cajero", "Sevilla", "scraped", &recent),
+            (5, "Mercadona", "Cajero", "Bilbao", "applied", &old),
+        ] {
+            db.execute(
+                "INSERT INTO applications (id, company, role, location, status, submitted_at, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, '')",
+                params![id, company, role, location, status, submitted],
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn names_titles_and_cities() {
+        assert_eq!(
+            normalize("  Ayudante de Cocina, Málaga!"),
+            " ayudante de cocina malaga "
+        );
+        assert_eq!(company_name("mercadona sa"), "mercadona");
+        assert_eq!(company_name("grupo"), "");
+        assert_eq!(title_words("Cajero/a"), title_words("Cajera"));
+        assert_eq!(title_words("Dependienta"), title_words("dependiente"));
+        assert_eq!(
+            title_words("Mozo de almacén (H/M) - jornada completa"),
+            ["almacen", "mozo"].map(String::from).into()
+        );
+        assert_eq!(city("Madrid, Comunidad de Madrid"), "madrid");
+        assert_eq!(city("Madrid (Centro)"), "madrid");
+        assert_eq!(city(""), "");
+    }
+
+    #[test]
+    fn fingerprints_ignore_order_and_legal_form() {
+        let a = key("Mercadona SA", "Cajero de tienda", "Valencia");
+        let b = key("MERCADONA", "tienda cajera", "Valencia, España");
+        assert_eq!(a.fingerprint, b.fingerprint);
+        assert_ne!(
+            a.fingerprint,
+            key("Mercadona", "Cajero", "Valencia").fingerprint
+        );
+        let words = |s: &str| title_words(s);
+        assert_eq!(similarity(&words("cajero tienda"), &words("cajero")), 0.5);
+        assert_eq!(similarity(&words(""), &words("")), 0.0);
+    }
+
+    #[test]
+    fn earlier_applications_match() {
+        let db = db();
+        let found = find(&db, "MERCADONA SA", "Cajera", "Valencia", None).unwrap();
+        let ids: Vec<i64> = found.iter().map(|m| m.application_id).collect();
+        // 2 is another job, 3 another city, 4 never sent and 5 too old
+        assert_eq!(ids, [1]);
+        assert!(found[0].exact);
+        assert_eq!(found[0].score, 1.0);
+
+        let found = find(&db, "Mercadona", "Reponedor tienda noche", "", None).unwrap();
+        assert_eq!(found[0].application_id, 2);
+        assert!(!found[0].exact);
+        assert!((found[0].score - 2.0 / 3.0).abs() < 1e-9);
+        assert!(
+            find(&db, "Mercadona", "Reponedor tienda", "Valencia", Some(2))
+                .unwrap()
+                .is_empty()
+        );
+        assert!(find(&db, "Grupo SL", "Reponedor", "", None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn index_follows_edits() {
+        let db = db();
+        assert_eq!(
+            find(&db, "Mercadona", "Cajero", "Madrid", None)
+                .unwrap()
+                .len(),
+            1
+        );
+        db.execute(
+            "UPDATE applications SET location = 'Toledo' WHERE id = 3",
+            [],
+        )
+        .unwrap();
+        assert!(find(&db, "Mercadona", "Cajero", "Madrid", None)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Url};
+use tracing::{info, warn};
+
+use crate::{crashreport::CrashReport, flags, settings};
+
+// Forwards the shell's crash reports and failed command calls to a Sentry
+// project of the user's choosing (`error_reporting.dsn`), for teams that run
+// JobBot internally and want its errors in one place. Nothing is sent unless
+// the `error_reporting` flag is on and the user opted in under Settings.
+// Messages and log lines are redacted (see `redact`) before they leave; no
+// user, host name or profile is attached. A crashed process can't send
+// anything, so crash reports go when they're announced on the next launch.
+const FLAG: &str = "error_reporting";
+const CLIENT: &str = concat!("jobbot/", env!("CARGO_PKG_VERSION"));
+const TIMEOUT: Duration = Duration::from_secs(15);
+// So a command failing in a loop can't flood the project
+const MAX_EVENTS: usize = 30;
+const RATE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Serialize)]
+pub struct ErrorReportingStatus {
+    pub enabled: bool,
+    pub dsn: String,
+    // False while the flag is off; the setting is kept either way
+    pub available: bool,
+}
+
+struct Dsn {
+    raw: String,
+    key: String,
+    envelope_url: String,
+}
+
+static SENT: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+
+// https://<public key>@<host>[/<path>]/<project id>
+fn parse_dsn(dsn: &str) -> Result<Dsn, String> {
+    let url = Url::parse(dsn.trim()).map_err(|e| format!("invalid DSN: {e}"))?;
+    if !matches!(url.scheme(), "https" | "http") {
+        return Err("the DSN must be an http(s) URL".into());
+    }
+    if url.username().is_empty() {
+        return Err("the DSN has no public key".into());
+    }
+    let host = url.host_str().ok_or("the DSN has no host")?;
+    let mut segments: Vec<&str> = url
+        .path_segments()
+        .map(|s| s.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    let project = segments.pop().ok_or("the DSN has no project id")?;
+    let prefix: String = segments.iter().map(|s| format!("/{s}")).collect();
+    let port = url.port().map(|p| format!(":{p}")).unwrap_or_default();
+    Ok(Dsn {
+        raw: dsn.trim().to_string(),
+        key: url.username().to_string(),
+        envelope_url: format!(
+            "{}://{host}{port}{prefix}/api/{project}/envelope/",
+            url.scheme()
+        ),
+    })
+}
+
+fn available(app: &AppHandle) -> bool {
+    flags::all(app).get(FLAG).is_some_and(|flag| flag.enabled)
+}
+
+fn active(app: &AppHandle) -> Option<Dsn> {
+    let config = settings::get(app).error_reporting;
+    if !config.enabled || !available(app) {
+        return None;
+    }
+    parse_dsn(&config.dsn).ok()
+}
+
+fn event_id() -> String {
+    let mut id = [0u8; 16];
+    OsRng.fill_bytes(&mut id);
+    id.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn event(app: &AppHandle, level: &str, kind: &str, message: &str) -> Value {
+    let channel = serde_json::to_value(settings::get(app).update.channel).unwrap_or_default();
+    json!({
+        "event_id": event_id(),
+        "timestamp": Utc::now().to_rfc3339(),
+        "platform": "native",
+        "level": level,
+        "logger": "jobbot-shell",
+        "release": format!("jobbot@{}", app.package_info().version),
+        "environment": channel,
+        "exception": { "values": [{ "type": kind, "value": message }] },
+        "contexts": {
+            "os": { "name": std::env::consts::OS },
+            "device": { "arch": std::env::consts::ARCH },
+        },
+    })
+}
+
+// Sentry's envelope format: a header line, an item header, the event.
+fn post(dsn: &Dsn, event: &Value) -> Result<(), String> {
+    let payload = serde_json::to_string(event).map_err(|e| e.to_string())?;
+    let header = json!({
+        "event_id": event["event_id"],
+        "sent_at": Utc::now().to_rfc3339(),
+        "dsn": dsn.raw,
+    });
+    let item = json!({ "type": "event", "length": payload.len() });
+    let body = format!("{header}\n{item}\n{payload}\n");
+    crate::net::client(TIMEOUT)?
+        .post(&dsn.envelope_url)
+        .header("Content-Type", "application/x-sentry-envelope")
+        .header(
+            "X-Sentry-Auth",
+            format!(
+                "Sentry sentry_version=7, sentry_client={CLIENT}, sentry_key={}",
+                dsn.key
+            ),
+        )
+        .body(body)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn within_rate() -> bool {
+    let now = Instant::now();
+    let mut sent = SENT.lock().unwrap();
+    while sent
+        .front()
+        .is_some_and(|t| now.duration_since(*t) > RATE_WINDOW)
+    {
+        sent.pop_front();
+    }
+    if sent.len() >= MAX_EVENTS {
+        return false;
+    }
+    sent.push_back(now);
+    true
+}
+
+fn send(dsn: Dsn, event: Value) {
+    if !within_rate() {
+        return;
+    }
+    thread::spawn(move || {
+        if let Err(e) = post(&dsn, &event) {
+            warn!("error report not sent: {e}");
+        }
+    });
+}
+
+// From `crashreport::announce`, with the report already redacted.
+pub fn crash(app: &AppHandle, report: &CrashReport) {
+    let Some(dsn) = active(app) else {
+        return;
+    };
+    let mut event = event(app, "fatal", &report.kind, &report.message);
+    event["timestamp"] = json!(report.at);
+    event["tags"] = json!({ "source": "crash", "crash_id": report.id });
+    event["extra"] = json!({
+        "location": report.location,
+        "thread": report.thread,
+        "backtrace": report.backtrace,
+        "engine_version": report.engine_version,
+        "log_tail": report.log_tail,
+    });
+    send(dsn, event);
+}
+
+// From `ipc`, for each rejected call, with the message already redacted.
+pub fn command_error(app: &AppHandle, command: &str, message: &str) {
+    let Some(dsn) = active(app) else {
+        return;
+    };
+    let mut event = event(app, "error", command, message);
+    event["tags"] = json!({ "source": "command", "command": command });
+    event["fingerprint"] = json!(["command", command, message]);
+    send(dsn, event);
+}
+
+#[tauri::command]
+pub fn get_error_reporting(app: AppHandle) -> ErrorReportingStatus {
+    let config = settings::get(&app).error_reporting;
+    ErrorReportingStatus {
+        enabled: config.enabled,
+        dsn: config.dsn,
+        available: available(&app),
+    }
+}
+
+#[tauri::command]
+pub fn set_error_reporting(app: AppHandle, enabled: bool, dsn: String) -> Result<(), String> {
+    if enabled {
+        parse_dsn(&dsn)?;
+    }
+    settings::update(&app, |s| {
+        s.error_reporting.enabled = enabled;
+        s.error_reporting.dsn = dsn.trim().to_string();
+    })?;
+    Ok(())
+}
+
+// Sends one test event straight away, so a wrong DSN shows up in Settings.
+#[tauri::command]
+pub async fn test_error_reporting(app: AppHandle, dsn: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dsn = parse_dsn(&dsn)?;
+        let mut event = event(&app, "info", "Test", "Test event from JobBot");
+        event["tags"] = json!({ "source": "test" });
+        post(&dsn, &event)?;
+        info!("test error report sent");
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelope_url_from_the_dsn() {
+        let dsn = parse_dsn(" https://abc123@o42.ingest.sentry.io/4501 ").unwrap();
+        assert_eq!(dsn.raw, "https://abc123@o42.ingest.sentry.io/4501");
+        assert_eq!(dsn.key, "abc123");
+        assert_eq!(
+            dsn.envelope_url,
+            "https://o42.ingest.sentry.io/api/4501/envelope/"
+        );
+    }
+
+    #[test]
+    fn self_hosted_with_port_and_path() {
+        let dsn = parse_dsn("http://key@sentry.local:9000/errors/7").unwrap();
+        assert_eq!(
+            dsn.envelope_url,
+            "http://sentry.local:9000/errors/api/7/envelope/"
+        );
+    }
+
+    #[test]
+    fn unusable_dsns() {
+        assert!(parse_dsn("").is_err());
+        assert!(parse_dsn("ftp://key@host/1").is_err());
+        assert_eq!(
+            parse_dsn("https://host/1").err().as_deref(),
+            Some("the DSN has no public key")
+        );
+        assert_eq!(
+            parse_dsn("https://key@host/").err().as_deref(),
+            Some("the DSN has no project id")
+        );
+    }
+}
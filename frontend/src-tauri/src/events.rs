@@ -0,0 +1,220 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    io::{BufRead, BufReader},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{debug, warn};
+
+use crate::{
+    activity, alerts,
+    audit::{self, Action},
+    backend, cookievault,
+    dispatcher::{self, Category},
+    dock, duplicates, evidence, history,
+    i18n::{t, t_args},
+    jumplist, keepawake, metrics, oauth, offlinequeue, progress, proxypool, redact, taskbar,
+    webhooks,
+};
+
+// Every backend SSE event is re-emitted to the webview under this name, so
+// the frontend no longer needs its own EventSource when running inside Tauri.
+pub const BACKEND_EVENT: &str = "backend-event";
+
+// Whether the stream is open, and when it last delivered anything (the
+// backend's heartbeat included), for the health report
+static CONNECTED: AtomicBool = AtomicBool::new(false);
+static LAST_HEARTBEAT: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+// Bumped by `reconnect`; a bridge thread from an older one stops at the next
+// line it reads
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BackendEvent {
+    pub event: String,
+    pub data: Value,
+}
+
+// Wording mirrors backend/notifications/notifier.py (locales/en.ftl).
+fn notify(app: &AppHandle, event: &BackendEvent) {
+    let d = &event.data;
+    let text = |key: &str| d[key].as_str().unwrap_or("").to_string();
+    let review = format!("/review?id={}", d["application_id"]);
+    match event.event.as_str() {
+        "application_submitted" => dispatcher::submit(
+            app,
+            Category::Submitted,
+            &t("submitted-title"),
+            &t_args(
+                "submitted-body",
+                &[("id", d["application_id"].to_string().into())],
+            ),
+            "/applications",
+        ),
+        "review_ready" => dispatcher::submit(
+            app,
+            Category::ReviewReady,
+            &t("review-ready-title"),
+            &format!("{} — {}", text("company"), text("title")),
+            &review,
+        ),
+        "review_expiring" => dispatcher::submit(
+            app,
+            Category::ReviewExpiring,
+            &t("review-expiring-title"),
+            &t_args(
+                "review-expiring-body",
+                &[("minutes", d["minutes_remaining"].to_string().into())],
+            ),
+            &review,
+        ),
+        "scraper_error" => dispatcher::submit(
+            app,
+            Category::ScraperError,
+            &t("scraper-error-title"),
+            &format!("{}: {}", text("site"), text("error")),
+            "/",
+        ),
+        _ => {}
+    }
+}
+
+fn dispatch(app: &AppHandle, event: BackendEvent) {
+    metrics::event_received();
+    // The name only; payloads can hold job and profile data
+    debug!(event = event.event, "engine event");
+    match event.event.as_str() {
+        "run_progress" => progress::on_progress(app, &event.data),
+        "run_finished" => progress::on_finished(app, &event.data),
+        "automation_changed" => taskbar::on_automation_changed(app, &event.data),
+        "session_saved" | "session_requested" => cookievault::on_event(app, &event),
+        "proxy_requested" | "proxy_outcome" => proxypool::on_event(app, &event),
+        "oauth_required" => oauth::on_event(app, &event),
+        // The name on a CV the engine read, for the shell's logs too
+        "redaction_names" => {
+            let names = event.data["names"].as_array().into_iter().flatten();
+            redact::add_names(app, names.filter_map(|n| n.as_str().map(str::to_string)));
+            return;
+        }
+        "application_submitted" => {
+            audit::record(app, Action::ApplicationSubmitted, event.data.clone());
+            notify(app, &event);
+        }
+        _ => notify(app, &event),
+    }
+    history::on_event(app, &event);
+    alerts::on_event(app, &event);
+    activity::on_event(app, &event);
+    duplicates::on_event(app, &event);
+    webhooks::on_event(app, &event);
+    evidence::on_event(app, &event);
+    if event.event.starts_with("application_") || event.event == "automation_changed" {
+        dock::refresh(app);
+        jumplist::refresh();
+    }
+    let _ = app.emit(BACKEND_EVENT, event);
+}
+
+// For events raised by the shell itself, on the same channel as the backend's.
+pub fn emit(app: &AppHandle, event: &str, data: Value) {
+    let _ = app.emit(
+        BACKEND_EVENT,
+        BackendEvent {
+            event: event.into(),
+            data,
+        },
+    );
+}
+
+// Minimal SSE parser: `event:` + `data:` lines, blank line terminates a
+// message, `:` lines are comments (the backend's heartbeat).
+fn read_stream(app: &AppHandle, generation: u64) -> Result<(), String> {
+    let response = backend::open_stream("/api/events")?;
+    metrics::engine_ready();
+    CONNECTED.store(true, Ordering::Relaxed);
+    // The engine is (back) up: the Dock menu and jump list can ask it again
+    dock::refresh(app);
+    jumplist::refresh();
+    keepawake::on_connected(app);
+    taskbar::on_connected(app);
+    history::start_sync(app);
+    offlinequeue::on_connected(app);
+    oauth::on_connected(app);
+    let mut name = String::new();
+    let mut data = String::new();
+    for line in BufReader::new(response).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if GENERATION.load(Ordering::Relaxed) != generation {
+            return Ok(());
+        }
+        *LAST_HEARTBEAT.lock().unwrap() = Some(Utc::now());
+        if line.is_empty() {
+            if !data.is_empty() {
+                dispatch(
+                    app,
+                    BackendEvent {
+                        event: if name.is_empty() {
+                            "message".into()
+                        } else {
+                            name.clone()
+                        },
+                        data: serde_json::from_str(&data).unwrap_or(Value::String(data.clone())),
+                    },
+                );
+            }
+            name.clear();
+            data.clear();
+        } else if let Some(v) = line.strip_prefix("event:") {
+            name = v.trim().to_string();
+        } else if let Some(v) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(v.trim_start());
+        }
+    }
+    Ok(())
+}
+
+pub fn stream_status() -> (bool, Option<DateTime<Utc>>) {
+    (
+        CONNECTED.load(Ordering::Relaxed),
+        *LAST_HEARTBEAT.lock().unwrap(),
+    )
+}
+
+// Runs for the lifetime of the app; reconnects with capped exponential backoff
+// because the backend may still be starting (or restarting) when this begins.
+pub fn start_bridge(app: AppHandle) {
+    let generation = GENERATION.load(Ordering::Relaxed);
+    thread::spawn(move || {
+        let mut backoff = 1;
+        while GENERATION.load(Ordering::Relaxed) == generation {
+            let result = read_stream(&app, generation);
+            if GENERATION.load(Ordering::Relaxed) != generation {
+                return;
+            }
+            CONNECTED.store(false, Ordering::Relaxed);
+            match result {
+                Ok(()) => backoff = 1,
+                Err(e) => warn!("event stream error: {e}"),
+            }
+            thread::sleep(Duration::from_secs(backoff));
+            backoff = (backoff * 2).min(30);
+        }
+    });
+}
+
+// Drops the current stream, which may have died without an error (after the
+// system slept), for a fresh one.
+pub fn reconnect(app: &AppHandle) {
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+    start_bridge(app.clone());
+}
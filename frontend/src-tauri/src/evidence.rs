@@ -0,0 +1,558 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::Mutex,
+    thread,
+};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::ShellExt;
+use tracing::{info, warn};
+
+use crate::{
+    events::BackendEvent,
+    history, paths,
+    settings::{self, EvidenceSettings},
+};
+
+// Proof of what was sent: for each submitted application, the engine's
+// screenshot and HTML of the filled form just before it clicked submit, and of
+// the page that came back, as its `application_submitted` event lists them.
+// They're copied out of the engine's folder, where a rerun or a reset would
+// lose them, into `evidence` in the profile's data folder, each file once
+// under its SHA-256 in `blobs`, and catalogued in the history database.
+// Evidence older than `retention_days` goes, then the oldest until the
+// archive fits in `max_mb`; a file goes once nothing points at it.
+pub const EVIDENCE_CHANGED: &str = "evidence-changed";
+const BLOBS: &str = "blobs";
+// The engine's keys in the event, which are also the kinds kept
+const KINDS: &[&str] = &[
+    "submitted_form_screenshot",
+    "submitted_form_html",
+    "confirmation_screenshot",
+    "confirmation_html",
+];
+const MAX_BYTES: u64 = 50 * 1024 * 1024;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize)]
+pub struct Evidence {
+    pub id: i64,
+    pub application_id: i64,
+    pub kind: String,
+    pub sha256: String,
+    pub size: u64,
+    pub media_type: &'static str,
+    pub captured_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EvidenceData {
+    pub media_type: &'static str,
+    pub base64: String,
+}
+
+pub fn root(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join("evidence"))
+}
+
+fn media_type(file: &str) -> &'static str {
+    match Path::new(file).extension().and_then(|e| e.to_str()) {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("html" | "htm") => "text/html",
+        _ => "application/octet-stream",
+    }
+}
+
+// Copies the file into `blobs` unless its content is already there.
+fn store(root: &Path, source: &Path) -> Result<(String, String, u64), String> {
+    let size = fs::metadata(source).map_err(|e| e.to_string())?.len();
+    if size > MAX_BYTES {
+        return Err(format!("larger than {} MB", MAX_BYTES / 1024 / 1024));
+    }
+    let bytes = fs::read(source).map_err(|e| e.to_string())?;
+    let sha256: String = Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    let extension = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_else(|| "bin".into());
+    let file = format!("{sha256}.{extension}");
+    let blobs = root.join(BLOBS);
+    let target = blobs.join(&file);
+    if !target.exists() {
+        fs::create_dir_all(&blobs).map_err(|e| e.to_string())?;
+        let temp = blobs.join(format!("{file}.tmp"));
+        fs::write(&temp, &bytes).map_err(|e| e.to_string())?;
+        fs::rename(temp, &target).map_err(|e| e.to_string())?;
+    }
+    Ok((sha256, file, size))
+}
+
+// Drops what's past retention or over the size limit, then unused files.
+fn prune(app: &AppHandle, db: &Connection) -> Result<(), String> {
+    let removed = expire(db, &settings::get(app).evidence, Utc::now())?;
+    sweep(&root(app)?, db)?;
+    if removed > 0 {
+        info!(removed, "old submission evidence removed");
+    }
+    Ok(())
+}
+
+// Drops the rows past retention or over the size limit, and says how many.
+fn expire(db: &Connection, cfg: &EvidenceSettings, now: DateTime<Utc>) -> Result<usize, String> {
+    let mut removed = 0;
+    if cfg.retention_days > 0 {
+        let cutoff = now - Duration::days(cfg.retention_days.into());
+        removed += db
+            .execute(
+                "DELETE FROM evidence WHERE captured_at < ?1",
+                [cutoff.to_rfc3339()],
+            )
+            .map_err(|e| e.to_string())?;
+    }
+    let max = u64::from(cfg.max_mb) * 1024 * 1024;
+    loop {
+        // Each file counts once, however many rows share it
+        let total: u64 = db
+            .query_row(
+                "SELECT COALESCE(SUM(size), 0) FROM
+                    (SELECT MAX(size) AS size FROM evidence GROUP BY file)",
+                [],
+                |r| r.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if cfg.max_mb == 0 || total <= max {
+            break;
+        }
+        let oldest: Option<i64> = db
+            .query_row(
+                "SELECT application_id FROM evidence ORDER BY captured_at LIMIT 1",
+                [],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        let Some(oldest) = oldest else {
+            break;
+        };
+        removed += db
+            .execute("DELETE FROM evidence WHERE application_id = ?1", [oldest])
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(removed)
+}
+
+// Deletes the files no row points at any more.
+fn sweep(root: &Path, db: &Connection) -> Result<(), String> {
+    let used: HashSet<String> = db
+        .prepare("SELECT DISTINCT file FROM evidence")
+        .and_then(|mut s| {
+            s.query_map([], |r| r.get(0))?
+                .collect::<Result<HashSet<_>, _>>()
+        })
+        .map_err(|e| e.to_string())?;
+    let blobs = root.join(BLOBS);
+    for entry in fs::read_dir(&blobs).into_iter().flatten().flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !used.contains(&name) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+    Ok(())
+}
+
+fn select(
+    db: &Connection,
+    filter: &str,
+    args: &[&dyn rusqlite::ToSql],
+) -> Result<Vec<Evidence>, String> {
+    let mut statement = db
+        .prepare(&format!(
+            "SELECT {COLUMNS} FROM evidence WHERE {filter} ORDER BY captured_at, id"
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map(args, |r| row(r).map(|(evidence, _)| evidence))
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string());
+    rows
+}
+
+// Screenshots captured before `cutoff`, for `retention`.
+pub fn screenshots_before(db: &Connection, cutoff: DateTime<Utc>) -> Result<Vec<Evidence>, String> {
+    select(
+        db,
+        "kind LIKE '%screenshot' AND captured_at < ?1",
+        &[&cutoff.to_rfc3339()],
+    )
+}
+
+// Everything kept for the application, for `retention`.
+pub fn of_application(db: &Connection, application_id: i64) -> Result<Vec<Evidence>, String> {
+    select(db, "application_id = ?1", &[&application_id])
+}
+
+// Drops these, and their files where nothing else shares them.
+pub fn forget(app: &AppHandle, db: &Connection, ids: &[i64]) -> Result<(), String> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let _guard = LOCK.lock().unwrap();
+    for id in ids {
+        db.execute("DELETE FROM evidence WHERE id = ?1", [id])
+            .map_err(|e| e.to_string())?;
+    }
+    sweep(&root(app)?, db)?;
+    let _ = app.emit(EVIDENCE_CHANGED, ());
+    Ok(())
+}
+
+fn record(app: &AppHandle, id: i64, files: Vec<(&'static str, PathBuf)>) -> Result<(), String> {
+    let _guard = LOCK.lock().unwrap();
+    let root = root(app)?;
+    let db = history::open(app)?;
+    let now = Utc::now().to_rfc3339();
+    for (kind, source) in files {
+        match store(&root, &source) {
+            Ok((sha256, file, size)) => {
+                db.execute(
+                    "INSERT OR IGNORE INTO evidence
+                        (application_id, kind, sha256, file, size, captured_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![id, kind, sha256, file, size, now],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+            Err(e) => warn!(application = id, kind, "evidence not kept: {e}"),
+        }
+    }
+    prune(app, &db)?;
+    let _ = app.emit(EVIDENCE_CHANGED, id);
+    Ok(())
+}
+
+// From the event bridge: what the engine captured as it submitted.
+pub fn on_event(app: &AppHandle, event: &BackendEvent) {
+    if event.event != "application_submitted" {
+        return;
+    }
+    let Some(id) = event.data["application_id"].as_i64() else {
+        return;
+    };
+    let Ok(engine) = paths::engine_dir(app) else {
+        return;
+    };
+    // Only files in the engine's own folder
+    let files: Vec<(&'static str, PathBuf)> = KINDS
+        .iter()
+        .filter_map(|kind| {
+            let path = PathBuf::from(event.data["evidence"][kind].as_str()?);
+            let inside =
+                path.starts_with(&engine) && path.components().all(|c| c != Component::ParentDir);
+            inside.then_some((*kind, path))
+        })
+        .collect();
+    if files.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    thread::spawn(move || {
+        if let Err(e) = record(&app, id, files) {
+            warn!(application = id, "submission evidence not kept: {e}");
+        }
+    });
+}
+
+fn prune_in_background(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let _guard = LOCK.lock().unwrap();
+        if let Err(e) = history::open(&app).and_then(|db| prune(&app, &db)) {
+            warn!("submission evidence not pruned: {e}");
+        }
+        let _ = app.emit(EVIDENCE_CHANGED, ());
+    });
+}
+
+// Retention is applied at startup as well, for evidence that aged since.
+pub fn start(app: AppHandle) {
+    prune_in_background(&app);
+}
+
+#[tauri::command]
+pub fn get_evidence_settings(app: AppHandle) -> EvidenceSettings {
+    settings::get(&app).evidence
+}
+
+// 0 for either keeps evidence regardless.
+#[tauri::command]
+pub fn set_evidence_settings(
+    app: AppHandle,
+    retention_days: u32,
+    max_mb: u32,
+) -> Result<(), String> {
+    settings::update(&app, |s| {
+        s.evidence = EvidenceSettings {
+            retention_days,
+            max_mb,
+        }
+    })?;
+    prune_in_background(&app);
+    Ok(())
+}
+
+const COLUMNS: &str = "id, application_id, kind, sha256, size, captured_at, file";
+
+// A row of COLUMNS, and its file in `blobs`.
+fn row(r: &Row) -> rusqlite::Result<(Evidence, String)> {
+    let file: String = r.get(6)?;
+    Ok((
+        Evidence {
+            id: r.get(0)?,
+            application_id: r.get(1)?,
+            kind: r.get(2)?,
+            sha256: r.get(3)?,
+            size: r.get(4)?,
+            media_type: media_type(&file),
+            captured_at: r.get(5)?,
+        },
+        file,
+    ))
+}
+
+fn get(app: &AppHandle, id: i64) -> Result<(Evidence, PathBuf), String> {
+    let db = history::open(app)?;
+    let (evidence, file) = db
+        .query_row(
+            &format!("SELECT {COLUMNS} FROM evidence WHERE id = ?1"),
+            [id],
+            row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("no evidence {id}"))?;
+    Ok((evidence, root(app)?.join(BLOBS).join(file)))
+}
+
+#[tauri::command]
+pub fn list_evidence(app: AppHandle, application_id: i64) -> Result<Vec<Evidence>, String> {
+    of_application(&history::open(&app)?, application_id)
+}
+
+// The file's content, checked against its hash, for showing in the page.
+#[tauri::command]
+pub async fn read_evidence(app: AppHandle, id: i64) -> Result<EvidenceData, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (evidence, path) = get(&app, id)?;
+        let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+        let sha256: String = Sha256::digest(&bytes)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        if sha256 != evidence.sha256 {
+            return Err("the evidence file was changed since it was kept".into());
+        }
+        Ok(EvidenceData {
+            media_type: evidence.media_type,
+            base64: STANDARD.encode(bytes),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// In the system's viewer or browser.
+#[tauri::command]
+#[allow(deprecated)]
+pub fn open_evidence(app: AppHandle, id: i64) -> Result<(), String> {
+    let (_, path) = get(&app, id)?;
+    app.shell()
+        .open(path.to_string_lossy(), None)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    fn db(rows: &[(i64, &str, &str, u64, &str)]) -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (application_id, kind, file, size, captured_at) in rows {
+            db.execute(
+                "INSERT INTO evidence (application_id, kind, sha256, file, size, captured_at)
+                 VALUES (?1, ?2, ?3, ?3, ?4, ?5)",
+                params![application_id, kind, file, size, captured_at],
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    fn left(db: &Connection) -> Vec<i64> {
+        db.prepare("SELECT DISTINCT application_id FROM evidence ORDER BY 1")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn media_types() {
+        assert_eq!(media_type("ab12.png"), "image/png");
+        assert_eq!(media_type("ab12.jpeg"), "image/jpeg");
+        assert_eq!(media_type("ab12.html"), "text/html");
+        assert_eq!(media_type("ab12.bin"), "application/octet-stream");
+        assert_eq!(media_type("ab12"), "application/octet-stream");
+    }
+
+    #[test]
+    fn stored_once_by_content() {
+        let dir = std::env::temp_dir().join(format!("jobbot-evidence-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("form.PNG"), b"abc").unwrap();
+        fs::write(dir.join("again.png"), b"abc").unwrap();
+        let root = dir.join("evidence");
+        let (sha256, file, size) = store(&root, &dir.join("form.PNG")).unwrap();
+        assert_eq!(
+            sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!((file.as_str(), size), (format!("{sha256}.png").as_str(), 3));
+        assert_eq!(store(&root, &dir.join("again.png")).unwrap().1, file);
+        assert_eq!(fs::read_dir(root.join(BLOBS)).unwrap().count(), 1);
+        assert!(store(&root, &dir.join("missing.png")).is_err());
+
+        // Only what a row points at stays
+        fs::write(root.join(BLOBS).join("old.html"), b"").unwrap();
+        let db = db(&[(
+            1,
+            "confirmation_screenshot",
+            &file,
+            3,
+            "2026-10-01T00:00:00Z",
+        )]);
+        sweep(&root, &db).unwrap();
+        let names: Vec<String> = fs::read_dir(root.join(BLOBS))
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, [file]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn past_retention() {
+        let db = db(&[
+            (1, "confirmation_html", "a.html", 10, "2024-01-01T00:00:00Z"),
+            (2, "confirmation_html", "b.html", 10, "2026-10-01T00:00:00Z"),
+        ]);
+        let now = "2026-10-15T00:00:00Z".parse().unwrap();
+        let cfg = EvidenceSettings {
+            retention_days: 365,
+            max_mb: 0,
+        };
+        assert_eq!(expire(&db, &cfg, now), Ok(1));
+        assert_eq!(left(&db), [2]);
+        let forever = EvidenceSettings {
+            retention_days: 0,
+            max_mb: 0,
+        };
+        assert_eq!(expire(&db, &forever, now), Ok(0));
+    }
+
+    #[test]
+    fn oldest_applications_go_until_it_fits() {
+        let db = db(&[
+            (
+                1,
+                "confirmation_screenshot",
+                "a.png",
+                2 * MB,
+                "2026-10-01T00:00:00Z",
+            ),
+            (
+                1,
+                "confirmation_html",
+                "a.html",
+                MB / 2,
+                "2026-10-01T00:00:00Z",
+            ),
+            // The same file twice counts once
+            (
+                2,
+                "submitted_form_screenshot",
+                "b.png",
+                2 * MB,
+                "2026-10-02T00:00:00Z",
+            ),
+            (
+                3,
+                "submitted_form_screenshot",
+                "b.png",
+                2 * MB,
+                "2026-10-03T00:00:00Z",
+            ),
+            (
+                4,
+                "confirmation_screenshot",
+                "c.png",
+                MB,
+                "2026-10-04T00:00:00Z",
+            ),
+        ]);
+        let cfg = EvidenceSettings {
+            retention_days: 0,
+            max_mb: 3,
+        };
+        assert_eq!(expire(&db, &cfg, Utc::now()), Ok(2));
+        assert_eq!(left(&db), [2, 3, 4]);
+    }
+
+    #[test]
+    fn screenshots_and_applications() {
+        let db = db(&[
+            (
+                1,
+                "confirmation_screenshot",
+                "a.png",
+                1,
+                "2026-10-01T00:00:00Z",
+            ),
+            (1, "confirmation_html", "a.html", 1, "2026-10-01T00:00:00Z"),
+            (
+                2,
+                "submitted_form_screenshot",
+                "b.png",
+                1,
+                "2026-10-10T00:00:00Z",
+            ),
+        ]);
+        let cutoff = "2026-10-05T00:00:00Z".parse().unwrap();
+        let kinds = |found: Vec<Evidence>| found.into_iter().map(|e| e.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds(screenshots_before(&db, cutoff).unwrap()),
+            ["confirmation_screenshot"]
+        );
+        assert_eq!(
+            kinds(of_application(&db, 1).unwrap()),
+            ["confirmation_screenshot", "confirmation_html"]
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::{fs, path::Path, sync::Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+
+use crate::{
+    audit::{self, Action},
+    backend,
+    i18n::{t, t_args},
+    proxy, settings,
+    telemetry::{self, Counter},
+    vault,
+};
+
+// A `.jobbot` file carries everything needed to set up another machine: the
+// shell settings, the backend's settings and company sources, and optionally
+// the stored credentials sealed with a passphrase. Machine-local state (update
+// bookkeeping, last digest date) is left out.
+const FORMAT: &str = "jobbot-export";
+const FORMAT_VERSION: u32 = 1;
+const PBKDF2_ROUNDS: u32 = 600_000;
+
+// Opening a .jobbot file (double-click / file association) lands here; the
+// frontend asks for confirmation and a passphrase, then calls import_settings.
+pub const IMPORT_REQUESTED: &str = "import-requested";
+
+// Backend settings worth carrying over; setup/TOS state is per machine.
+const BACKEND_SETTINGS: &[&str] = &["ollama_host", "ollama_model", "sound_enabled"];
+
+// Credentials the engine keeps in the keychain (backend/security/keychain.py).
+pub const BACKEND_SECRETS: &[(&str, &str)] = &[
+    ("jobbot", "infojobs/username"),
+    ("jobbot", "infojobs/password"),
+];
+
+#[derive(Default)]
+pub struct PendingImport(Mutex<Option<String>>);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportFile {
+    format: String,
+    format_version: u32,
+    exported_at: String,
+    app_version: String,
+    shell: Value,
+    #[serde(default)]
+    backend: Option<Value>,
+    #[serde(default)]
+    secrets: Option<Sealed>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Sealed {
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Secret {
+    service: String,
+    account: String,
+    secret: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub company_sources: usize,
+    pub secrets_restored: usize,
+    pub warnings: Vec<String>,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], rounds: u32) -> Key<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+    key.into()
+}
+
+// Passphrase encryption shared with settings sync.
+pub fn seal(plain: &[u8], passphrase: &str) -> Result<Sealed, String> {
+    seal_with_rounds(plain, passphrase, PBKDF2_ROUNDS)
+}
+
+fn seal_with_rounds(plain: &[u8], passphrase: &str, rounds: u32) -> Result<Sealed, String> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt, rounds));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, plain).map_err(|e| e.to_string())?;
+    Ok(Sealed {
+        kdf: "pbkdf2-sha256".into(),
+        iterations: rounds,
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    })
+}
+
+pub fn open(sealed: &Sealed, passphrase: &str) -> Result<Vec<u8>, String> {
+    if sealed.kdf != "pbkdf2-sha256" {
+        return Err(format!("unsupported key derivation {}", sealed.kdf));
+    }
+    let decode = |s: &str| STANDARD.decode(s).map_err(|e| e.to_string());
+    let salt = decode(&sealed.salt)?;
+    let nonce = decode(&sealed.nonce)?;
+    if nonce.len() != 12 {
+        return Err("corrupt secrets block".into());
+    }
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt, sealed.iterations));
+    cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            decode(&sealed.ciphertext)?.as_slice(),
+        )
+        .map_err(|_| "wrong passphrase or corrupt file".to_string())
+}
+
+// Sealed config values are bound to this machine's key; they travel opened
+// inside the passphrase-sealed block under this service name instead.
+const CONFIG_SECRET_SERVICE: &str = "jobbot-config";
+
+fn collect_secrets(app: &AppHandle) -> Vec<Secret> {
+    let shell = settings::get(app);
+    let config_secrets = shell.secrets.iter().filter_map(|(name, sealed)| {
+        Some(Secret {
+            service: CONFIG_SECRET_SERVICE.into(),
+            account: name.clone(),
+            secret: vault::open(sealed).ok()?,
+        })
+    });
+    let proxy_entry = shell
+        .proxy
+        .username
+        .map(|u| (proxy::KEYCHAIN_SERVICE.to_string(), u));
+    let secrets: Vec<Secret> = BACKEND_SECRETS
+        .iter()
+        .map(|(s, a)| (s.to_string(), a.to_string()))
+        .chain(proxy_entry)
+        .filter_map(|(service, account)| {
+            let secret = keyring::Entry::new(&service, &account)
+                .ok()?
+                .get_password()
+                .ok()?;
+            Some(Secret {
+                service,
+                account,
+                secret,
+            })
+        })
+        .chain(config_secrets)
+        .collect();
+    if !secrets.is_empty() {
+        let used: Vec<String> = secrets
+            .iter()
+            .map(|s| format!("{} / {}", s.service, s.account))
+            .collect();
+        audit::record(
+            app,
+            Action::CredentialUsed,
+            json!({ "secrets": used, "for": "export" }),
+        );
+    }
+    secrets
+}
+
+fn backend_snapshot() -> Result<Value, String> {
+    let all = backend::get_json("/api/settings")?;
+    let settings: serde_json::Map<String, Value> = BACKEND_SETTINGS
+        .iter()
+        .filter_map(|k| all.get(*k).map(|v| (k.to_string(), v.clone())))
+        .collect();
+    let mut sources = backend::get_json("/api/company-sources")?["items"].take();
+    // Ids are per database; the target machine assigns its own
+    if let Some(items) = sources.as_array_mut() {
+        for item in items.iter_mut().filter_map(Value::as_object_mut) {
+            item.remove("id");
+        }
+    }
+    Ok(json!({ "settings": settings, "company_sources": sources }))
+}
+
+fn export(app: &AppHandle, path: &Path, passphrase: Option<&str>) -> Result<(), String> {
+    let shell = settings::portable(&settings::get(app));
+
+    let secrets = match passphrase.filter(|p| !p.is_empty()) {
+        Some(p) => {
+            let plain = serde_json::to_vec(&collect_secrets(app)).map_err(|e| e.to_string())?;
+            Some(seal(&plain, p)?)
+        }
+        None => None,
+    };
+    let file = ExportFile {
+        format: FORMAT.into(),
+        format_version: FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        app_version: app.package_info().version.to_string(),
+        shell: settings::to_value(&shell)?,
+        // A stopped backend shouldn't block exporting the shell side
+        backend: backend_snapshot()
+            .map_err(|e| warn!("export without backend data: {e}"))
+            .ok(),
+        secrets,
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn import(app: &AppHandle, path: &Path, passphrase: Option<&str>) -> Result<ImportSummary, String> {
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file: ExportFile =
+        serde_json::from_str(&raw).map_err(|e| format!("not a JobBot export: {e}"))?;
+    if file.format != FORMAT {
+        return Err("not a JobBot export".into());
+    }
+    if file.format_version > FORMAT_VERSION {
+        return Err(format!(
+            "exported by a newer JobBot ({}); update this one first",
+            file.app_version
+        ));
+    }
+    let mut summary = ImportSummary::default();
+
+    // Secrets first: a wrong passphrase should fail before anything changes
+    let secrets: Vec<Secret> = match (&file.secrets, passphrase.filter(|p| !p.is_empty())) {
+        (Some(sealed), Some(p)) => {
+            serde_json::from_slice(&open(sealed, p)?).map_err(|e| e.to_string())?
+        }
+        (Some(_), None) => {
+            summary.warnings.push(t("import-secrets-skipped"));
+            Vec::new()
+        }
+        (None, _) => Vec::new(),
+    };
+
+    let (imported, warnings) = settings::validated(settings::from_value(file.shell)?);
+    summary.warnings.extend(warnings);
+    let imported = settings::with_local(imported, &settings::get(app));
+    settings::update(app, |s| *s = imported)?;
+
+    for secret in &secrets {
+        if secret.service == CONFIG_SECRET_SERVICE {
+            match vault::seal(&secret.secret) {
+                Ok(sealed) => {
+                    settings::update(app, |s| {
+                        s.secrets.insert(secret.account.clone(), sealed);
+                    })?;
+                    summary.secrets_restored += 1;
+                }
+                Err(e) => summary.warnings.push(t_args(
+                    "import-secret-not-encrypted",
+                    &[
+                        ("name", secret.account.as_str().into()),
+                        ("error", e.into()),
+                    ],
+                )),
+            }
+            continue;
+        }
+        match keyring::Entry::new(&secret.service, &secret.account)
+            .and_then(|e| e.set_password(&secret.secret))
+        {
+            Ok(()) => summary.secrets_restored += 1,
+            Err(e) => summary.warnings.push(t_args(
+                "import-secret-not-stored",
+                &[
+                    ("name", secret.account.as_str().into()),
+                    ("error", e.to_string().into()),
+                ],
+            )),
+        }
+    }
+
+    if let Some(backend_data) = file.backend {
+        if let Err(e) = backend::post_json("/api/settings", &backend_data["settings"]) {
+            summary.warnings.push(t_args(
+                "import-engine-settings-failed",
+                &[("error", e.into())],
+            ));
+        }
+        for source in backend_data["company_sources"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            match backend::post_json("/api/company-sources", source) {
+                Ok(_) => summary.company_sources += 1,
+                Err(e) => summary.warnings.push(t_args(
+                    "import-company-source-failed",
+                    &[("error", e.into())],
+                )),
+            }
+        }
+    }
+
+    proxy::refresh(app);
+    crate::restart_backend(app)?;
+    crate::refresh_tray_menu(app);
+    Ok(summary)
+}
+
+pub fn is_export_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("jobbot"))
+}
+
+pub fn request_import(app: &AppHandle, path: &Path) {
+    let path = path.to_string_lossy().into_owned();
+    *app.state::<PendingImport>().0.lock().unwrap() = Some(path.clone());
+    crate::show_window(app);
+    let _ = app.emit(IMPORT_REQUESTED, path);
+}
+
+#[tauri::command]
+pub async fn export_settings(
+    app: AppHandle,
+    path: String,
+    passphrase: Option<String>,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        telemetry::record(&app, Counter::SettingsExport);
+        export(&app, Path::new(&path), passphrase.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn import_settings(
+    app: AppHandle,
+    path: String,
+    passphrase: Option<String>,
+) -> Result<ImportSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        telemetry::record(&app, Counter::SettingsImport);
+        import(&app, Path::new(&path), passphrase.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// For a window that loaded after the file was opened and missed the event.
+#[tauri::command]
+pub fn take_pending_import(app: AppHandle) -> Option<String> {
+    app.state::<PendingImport>().0.lock().unwrap().take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Few rounds, for speed; the count travels in the file
+    const ROUNDS: u32 = 1_000;
+
+    #[test]
+    fn seals_and_opens() {
+        let sealed = seal_with_rounds(b"infojobs/password", "correct horse", ROUNDS).unwrap();
+        assert_eq!(sealed.iterations, ROUNDS);
+        assert_eq!(
+            open(&sealed, "correct horse").unwrap(),
+            b"infojobs/password"
+        );
+        assert_eq!(
+            open(&sealed, "wrong horse").unwrap_err(),
+            "wrong passphrase or corrupt file"
+        );
+    }
+
+    #[test]
+    fn refuses_other_files() {
+        let mut sealed = seal_with_rounds(b"x", "p", ROUNDS).unwrap();
+        sealed.nonce = STANDARD.encode([0u8; 8]);
+        assert!(open(&sealed, "p").is_err());
+        sealed.kdf = "scrypt".into();
+        assert!(open(&sealed, "p").unwrap_err().contains("scrypt"));
+    }
+
+    #[test]
+    fn export_file_extension() {
+        assert!(is_export_file(Path::new("/tmp/settings.jobbot")));
+        assert!(!is_export_file(Path::new("/tmp/settings.json")));
+    }
+}
@@ -0,0 +1,260 @@
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::RwLock, thread, time::Duration};
+use tauri::{AppHandle, Emitter};
+use tracing::warn;
+
+use crate::settings::{self, UpdateChannel};
+
+// Experimental capabilities ship dark and are switched on gradually. The
+// effective value of a flag is, from strongest to weakest:
+//   1. `flags` in settings.json (the user or support forcing it)
+//   2. the remote manifest, optionally limited to a rollout percentage
+//   3. the default compiled in below
+// The last manifest fetched is cached so offline launches behave the same.
+const DEFAULTS: &[(&str, bool)] = &[
+    // Always-on-top mini window with today's counts
+    ("widget_window", false),
+    // Scraping cadence tuned per site from past yields
+    ("adaptive_scheduler", false),
+    // Sending opted-in usage counts; off here stops every install at once
+    ("telemetry", true),
+    // Forwarding errors to a team's own Sentry project, for internal rollouts
+    ("error_reporting", false),
+];
+
+const STABLE_MANIFEST: &str =
+    "https://github.com/IFAKA/currobot/releases/download/flags/flags.json";
+const BETA_MANIFEST: &str =
+    "https://github.com/IFAKA/currobot/releases/download/flags-beta/flags.json";
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+pub const FLAGS_CHANGED: &str = "flags-changed";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    flags: BTreeMap<String, RemoteFlag>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RemoteFlag {
+    enabled: bool,
+    // Share of installs (0-100) that get `enabled`; the rest keep the default
+    #[serde(default)]
+    rollout: Option<u8>,
+    // Oldest app version the flag applies to
+    #[serde(default)]
+    min_version: Option<String>,
+}
+
+// flags.json next to the profiles registry: shared by all profiles, since
+// rollout buckets are per install.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cache {
+    install_id: String,
+    #[serde(default)]
+    manifest: Manifest,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagSource {
+    Default,
+    Remote,
+    Config,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Flag {
+    pub enabled: bool,
+    pub source: FlagSource,
+}
+
+static CACHE: RwLock<Option<Cache>> = RwLock::new(None);
+
+fn cache_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::paths::base_config_dir(app)?.join("flags.json"))
+}
+
+fn save_cache(app: &AppHandle, cache: &Cache) -> Result<(), String> {
+    let path = cache_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(cache).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+pub fn init(app: &AppHandle) {
+    let mut cache: Cache = cache_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    if cache.install_id.is_empty() {
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+        cache.install_id = id.iter().map(|b| format!("{b:02x}")).collect();
+        if let Err(e) = save_cache(app, &cache) {
+            warn!("flags cache not saved: {e}");
+        }
+    }
+    *CACHE.write().unwrap() = Some(cache);
+}
+
+// Stable 0-99 bucket per install and flag, so a rollout widening from 10% to
+// 50% keeps the first 10% enabled.
+fn bucket(install_id: &str, name: &str) -> u8 {
+    let hash = Sha256::digest(format!("{install_id}:{name}").as_bytes());
+    (u16::from_be_bytes([hash[0], hash[1]]) % 100) as u8
+}
+
+fn applies(version: &semver::Version, install_id: &str, name: &str, flag: &RemoteFlag) -> bool {
+    let version_ok =
+        flag.min_version
+            .as_deref()
+            .is_none_or(|min| match semver::Version::parse(min) {
+                Ok(min) => *version >= min,
+                Err(_) => false,
+            });
+    let rolled_out = flag
+        .rollout
+        .is_none_or(|percent| bucket(install_id, name) < percent);
+    version_ok && rolled_out
+}
+
+pub fn all(app: &AppHandle) -> BTreeMap<String, Flag> {
+    let mut flags: BTreeMap<String, Flag> = DEFAULTS
+        .iter()
+        .map(|(name, enabled)| {
+            let flag = Flag {
+                enabled: *enabled,
+                source: FlagSource::Default,
+            };
+            (name.to_string(), flag)
+        })
+        .collect();
+    if let Some(cache) = CACHE.read().unwrap().as_ref() {
+        for (name, remote) in &cache.manifest.flags {
+            if applies(&app.package_info().version, &cache.install_id, name, remote) {
+                let flag = Flag {
+                    enabled: remote.enabled,
+                    source: FlagSource::Remote,
+                };
+                flags.insert(name.clone(), flag);
+            }
+        }
+    }
+    for (name, enabled) in settings::get(app).flags {
+        let flag = Flag {
+            enabled,
+            source: FlagSource::Config,
+        };
+        flags.insert(name, flag);
+    }
+    flags
+}
+
+// Called when settings.json changes, so config overrides reach listeners.
+pub fn notify_changed(app: &AppHandle) {
+    let _ = app.emit(FLAGS_CHANGED, all(app));
+}
+
+fn fetch_manifest(app: &AppHandle) -> Result<Manifest, String> {
+    let url = match settings::get(app).update.channel {
+        UpdateChannel::Stable => STABLE_MANIFEST,
+        UpdateChannel::Beta => BETA_MANIFEST,
+    };
+    crate::net::client(Duration::from_secs(30))?
+        .get(url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.json())
+        .map_err(|e| e.to_string())
+}
+
+fn refresh(app: &AppHandle) -> Result<(), String> {
+    let manifest = fetch_manifest(app)?;
+    let before = all(app);
+    {
+        let mut guard = CACHE.write().unwrap();
+        let cache = guard.get_or_insert_with(Cache::default);
+        cache.manifest = manifest;
+        save_cache(app, cache)?;
+    }
+    if all(app) != before {
+        notify_changed(app);
+    }
+    Ok(())
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(FIRST_CHECK_DELAY);
+        loop {
+            if let Err(e) = refresh(&app) {
+                warn!("flag manifest check failed: {e}");
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_flags(app: AppHandle) -> BTreeMap<String, Flag> {
+    all(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(rollout: Option<u8>, min_version: Option<&str>) -> RemoteFlag {
+        RemoteFlag {
+            enabled: true,
+            rollout,
+            min_version: min_version.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn buckets_are_stable_and_in_range() {
+        for i in 0..200 {
+            let id = format!("install-{i}");
+            let b = bucket(&id, "widget_window");
+            assert!(b < 100);
+            assert_eq!(b, bucket(&id, "widget_window"));
+        }
+    }
+
+    #[test]
+    fn rollout_widens_without_dropping_anyone() {
+        let version = semver::Version::new(1, 0, 0);
+        let ids: Vec<String> = (0..200).map(|i| format!("install-{i}")).collect();
+        let enabled = |percent| -> Vec<&String> {
+            ids.iter()
+                .filter(|id| applies(&version, id, "f", &remote(Some(percent), None)))
+                .collect()
+        };
+        let (ten, fifty) = (enabled(10), enabled(50));
+        assert!(ten.iter().all(|id| fifty.contains(id)));
+        assert!(ten.len() < fifty.len());
+        assert!(enabled(0).is_empty());
+        assert_eq!(enabled(100).len(), ids.len());
+    }
+
+    #[test]
+    fn min_version() {
+        let version = semver::Version::new(1, 4, 2);
+        assert!(applies(&version, "id", "f", &remote(None, None)));
+        assert!(applies(&version, "id", "f", &remote(None, Some("1.4.2"))));
+        assert!(!applies(&version, "id", "f", &remote(None, Some("1.5.0"))));
+        assert!(!applies(&version, "id", "f", &remote(None, Some("soon"))));
+    }
+}
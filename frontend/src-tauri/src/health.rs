@@ -0,0 +1,182 @@
+use chrono::Utc;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::{
+    backend, events, metrics, net,
+    notifications::{self, NotificationPermission},
+    sleepwake, sysinfo,
+    updater::{self, UpdateStatus},
+    wayland::{self, DisplayCompat},
+};
+
+// Everything the health panel shows, gathered in one call. `status` sums it
+// up: down while the engine doesn't answer, degraded when something else
+// needs attention.
+const HEARTBEAT_STALE: Duration = Duration::from_secs(60);
+// Same figure as the first-launch check
+const LOW_DISK_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+// Where releases, flags and data packs come from
+const PROBE_URL: &str = "https://github.com";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Down,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EngineHealth {
+    pub running: bool,
+    pub pid: Option<u32>,
+    // /api/health answered
+    pub responding: bool,
+    pub starts: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StreamHealth {
+    pub connected: bool,
+    // RFC 3339
+    pub last_heartbeat_at: Option<String>,
+    pub stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiskHealth {
+    pub free_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+    pub low: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkHealth {
+    pub online: bool,
+    pub latency_ms: Option<u64>,
+    pub via_proxy: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    // RFC 3339
+    pub checked_at: String,
+    pub engine: EngineHealth,
+    pub events: StreamHealth,
+    pub disk: DiskHealth,
+    pub update: UpdateStatus,
+    pub notification_permission: NotificationPermission,
+    pub network: NetworkHealth,
+    // Linux only
+    pub display: Option<DisplayCompat>,
+}
+
+fn engine(app: &AppHandle) -> EngineHealth {
+    let pid = crate::backend_pid(app);
+    EngineHealth {
+        running: pid.is_some(),
+        pid,
+        responding: backend::get_json("/api/health").is_ok(),
+        starts: metrics::engine_starts(),
+    }
+}
+
+fn stream() -> StreamHealth {
+    let (connected, last) = events::stream_status();
+    // No heartbeat is expected while asleep, nor for a while after waking
+    let since = last.max(sleepwake::woke_at());
+    let stale = !sleepwake::is_asleep()
+        && since.is_none_or(|at| {
+            (Utc::now() - at)
+                .to_std()
+                .is_ok_and(|age| age > HEARTBEAT_STALE)
+        });
+    StreamHealth {
+        connected,
+        last_heartbeat_at: last.map(|at| at.to_rfc3339()),
+        stale,
+    }
+}
+
+fn disk(app: &AppHandle) -> DiskHealth {
+    let volume = sysinfo::data_volume(app);
+    DiskHealth {
+        free_bytes: volume.map(|(free, _)| free),
+        total_bytes: volume.map(|(_, total)| total),
+        low: volume.is_some_and(|(free, _)| free < LOW_DISK_BYTES),
+    }
+}
+
+// Through the same proxy as every other outside call.
+fn network() -> NetworkHealth {
+    let via_proxy = net::proxy_url().is_some();
+    let started = Instant::now();
+    let result = net::client(PROBE_TIMEOUT).and_then(|client| {
+        client
+            .head(PROBE_URL)
+            .send()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    });
+    match result {
+        Ok(()) => NetworkHealth {
+            online: true,
+            latency_ms: Some(started.elapsed().as_millis() as u64),
+            via_proxy,
+            error: None,
+        },
+        Err(e) => NetworkHealth {
+            online: false,
+            latency_ms: None,
+            via_proxy,
+            error: Some(e),
+        },
+    }
+}
+
+pub fn report(app: &AppHandle) -> HealthReport {
+    let engine = engine(app);
+    let events = stream();
+    let disk = disk(app);
+    let update = updater::get_update_status(app.clone());
+    let notification_permission = notifications::refresh_permission(app);
+    let network = network();
+    let display = wayland::report();
+    let status = if !engine.responding {
+        HealthStatus::Down
+    } else if !events.connected
+        || events.stale
+        || disk.low
+        || !network.online
+        || notification_permission == NotificationPermission::Denied
+        || matches!(update, UpdateStatus::Failed { .. })
+        || display.as_ref().is_some_and(|d| !d.tray_host)
+    {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Ok
+    };
+    HealthReport {
+        status,
+        checked_at: Utc::now().to_rfc3339(),
+        engine,
+        events,
+        disk,
+        update,
+        notification_permission,
+        network,
+        display,
+    }
+}
+
+#[tauri::command]
+pub async fn get_health_report(app: AppHandle) -> Result<HealthReport, String> {
+    tauri::async_runtime::spawn_blocking(move || report(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
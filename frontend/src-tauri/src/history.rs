@@ -0,0 +1,772 @@
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use rusqlite::{params, params_from_iter, types::Value as Sql, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeSet, path::PathBuf, sync::Mutex, thread, time::Duration};
+use tauri::{AppHandle, Emitter};
+use tracing::{debug, warn};
+
+use crate::{
+    backend,
+    events::BackendEvent,
+    historycrypt, paths,
+    salary::{self, Salary},
+    search, tags,
+};
+
+// The shell's own record of applications, in `history.db` in the profile's
+// data folder: company, role, link, status with every change to it, the
+// engine's timestamps and the files it produced. Filled from engine events
+// as they arrive, and from the engine's list whenever the event stream
+// (re)connects, so it survives the engine's database being reset or moved
+// and can be searched while the engine is down. Rows are only ever added to
+// or updated, never removed because the engine no longer has them.
+pub const FILE: &str = "history.db";
+const PAGE: u32 = 50;
+const MAX_LIMIT: u32 = 200;
+pub const HISTORY_CHANGED: &str = "history-changed";
+// Of the schema, in `PRAGMA user_version`; see migrate
+const VERSION: i32 = 1;
+// The databases migrated since launch, so each is only checked once
+static MIGRATED: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS applications (
+    id INTEGER PRIMARY KEY,
+    company TEXT NOT NULL DEFAULT '',
+    role TEXT,
+    url TEXT,
+    status TEXT NOT NULL DEFAULT '',
+    cv_profile TEXT,
+    created_at TEXT,
+    updated_at TEXT,
+    authorized_at TEXT,
+    submitted_at TEXT,
+    recorded_at TEXT NOT NULL,
+    -- The job board the job came from
+    site TEXT,
+    -- Set for rows read from another site's export (historyimport.rs)
+    imported_from TEXT,
+    location TEXT,
+    -- As the posting gave it, and read into yearly figures as JSON (salary.rs)
+    salary_raw TEXT,
+    salary TEXT,
+    description TEXT
+);
+CREATE INDEX IF NOT EXISTS applications_status ON applications (status);
+CREATE INDEX IF NOT EXISTS applications_company ON applications (company);
+CREATE TABLE IF NOT EXISTS status_changes (
+    application_id INTEGER NOT NULL REFERENCES applications (id),
+    status TEXT NOT NULL,
+    at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS status_changes_application ON status_changes (application_id);
+CREATE TABLE IF NOT EXISTS artifacts (
+    application_id INTEGER NOT NULL REFERENCES applications (id),
+    kind TEXT NOT NULL,
+    location TEXT NOT NULL,
+    recorded_at TEXT NOT NULL,
+    UNIQUE (application_id, kind, location)
+);
+CREATE TABLE IF NOT EXISTS calendar_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    application_id INTEGER REFERENCES applications (id),
+    kind TEXT NOT NULL,
+    starts_at TEXT NOT NULL,
+    duration_minutes INTEGER NOT NULL,
+    location TEXT,
+    notes TEXT,
+    sequence INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    -- When it went off (reminders.rs); done_at for follow-ups only
+    notified_at TEXT,
+    done_at TEXT,
+    -- The wall-clock time starts_at comes from, in timezone or, without
+    -- one, wherever the user is (tz.rs)
+    local_time TEXT,
+    timezone TEXT
+);
+CREATE INDEX IF NOT EXISTS calendar_events_application ON calendar_events (application_id);
+CREATE TABLE IF NOT EXISTS duplicate_index (
+    application_id INTEGER PRIMARY KEY REFERENCES applications (id),
+    -- Company, role and location it was built from
+    source TEXT NOT NULL,
+    company TEXT NOT NULL,
+    title_words TEXT NOT NULL,
+    city TEXT NOT NULL,
+    fingerprint TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS duplicate_index_company ON duplicate_index (company);
+CREATE TABLE IF NOT EXISTS evidence (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    application_id INTEGER NOT NULL,
+    -- submitted_form_screenshot, submitted_form_html, confirmation_screenshot
+    -- or confirmation_html (evidence.rs)
+    kind TEXT NOT NULL,
+    sha256 TEXT NOT NULL,
+    -- In the archive's `blobs`
+    file TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    captured_at TEXT NOT NULL,
+    UNIQUE (application_id, kind, sha256)
+);
+CREATE INDEX IF NOT EXISTS evidence_application ON evidence (application_id);
+CREATE TABLE IF NOT EXISTS duplicate_flags (
+    application_id INTEGER PRIMARY KEY,
+    matches TEXT NOT NULL,
+    flagged_at TEXT NOT NULL,
+    dismissed INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS pipeline_stages (
+    id TEXT PRIMARY KEY,
+    label TEXT NOT NULL,
+    -- JSON list of the statuses it holds; the first is what a move sets
+    statuses TEXT NOT NULL,
+    position INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pipeline_cards (
+    application_id INTEGER PRIMARY KEY,
+    -- Within its stage; NULL until moved by hand
+    position INTEGER,
+    notes TEXT NOT NULL DEFAULT '',
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    -- #rrggbb
+    color TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS application_tags (
+    application_id INTEGER NOT NULL,
+    tag_id INTEGER NOT NULL,
+    PRIMARY KEY (application_id, tag_id)
+);
+CREATE INDEX IF NOT EXISTS application_tags_tag ON application_tags (tag_id);
+CREATE TABLE IF NOT EXISTS activity (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- focus (the window in front) or run (an engine run, activity.rs)
+    kind TEXT NOT NULL,
+    -- The run's, as the engine names it
+    label TEXT,
+    started_at TEXT NOT NULL,
+    ended_at TEXT NOT NULL,
+    seconds INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS activity_started ON activity (started_at);
+";
+
+#[derive(Debug, Serialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub company: String,
+    pub role: Option<String>,
+    pub url: Option<String>,
+    pub status: String,
+    pub cv_profile: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub authorized_at: Option<String>,
+    pub submitted_at: Option<String>,
+    // When the shell last heard of it
+    pub recorded_at: String,
+    pub site: Option<String>,
+    pub imported_from: Option<String>,
+    pub location: Option<String>,
+    // As the posting gave it, and read into yearly figures (salary.rs)
+    pub salary_raw: Option<String>,
+    pub salary: Option<Salary>,
+    // Tag ids (tags.rs), by tag name
+    pub tags: Vec<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusChange {
+    pub status: String,
+    pub at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Artifact {
+    // "form_screenshot" or "form"
+    pub kind: String,
+    // A path on this machine or a URL
+    pub location: String,
+    pub recorded_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryDetail {
+    #[serde(flatten)]
+    pub entry: HistoryEntry,
+    pub status_changes: Vec<StatusChange>,
+    pub artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct HistoryQuery {
+    pub status: Option<String>,
+    // A tag id
+    pub tag: Option<i64>,
+    // Part of the company or role
+    pub search: Option<String>,
+    // Created on or after / before, as ISO dates
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub offset: u32,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryPage {
+    pub items: Vec<HistoryEntry>,
+    pub total: u32,
+}
+
+// What an event or the engine's list says about one application; None is
+// "not said", not "cleared".
+#[derive(Default)]
+struct Update {
+    company: Option<String>,
+    role: Option<String>,
+    url: Option<String>,
+    status: Option<String>,
+    cv_profile: Option<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    authorized_at: Option<String>,
+    submitted_at: Option<String>,
+    site: Option<String>,
+    location: Option<String>,
+    salary_raw: Option<String>,
+    description: Option<String>,
+    artifacts: Vec<(&'static str, String)>,
+}
+
+fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join(FILE))
+}
+
+// Brings the database up to VERSION, each step once, as `user_version` says.
+// Version 1 is the schema with its search index.
+pub fn migrate(db: &Connection) -> Result<(), String> {
+    let version: i32 = db
+        .query_row("PRAGMA user_version", [], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+    if version >= VERSION {
+        return Ok(());
+    }
+    db.execute_batch("BEGIN IMMEDIATE")
+        .map_err(|e| e.to_string())?;
+    let migrated = (|| {
+        if version < 1 {
+            db.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+            search::prepare(db)?;
+        }
+        db.execute_batch(&format!("PRAGMA user_version = {VERSION}; COMMIT"))
+            .map_err(|e| e.to_string())
+    })();
+    if migrated.is_err() {
+        let _ = db.execute_batch("ROLLBACK");
+    }
+    migrated
+}
+
+// Opened per use: the profile or the data folder can change under us. Also
+// for the interviews and reminders kept beside the history (calendar.rs).
+pub fn open(app: &AppHandle) -> Result<Connection, String> {
+    let path = path(app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let key = historycrypt::prepare(app, &path)?;
+    let db = Connection::open(&path).map_err(|e| e.to_string())?;
+    if let Some(key) = key {
+        historycrypt::unlock(&db, &key)?;
+    }
+    db.busy_timeout(Duration::from_secs(5))
+        .map_err(|e| e.to_string())?;
+    let mut migrated = MIGRATED.lock().unwrap();
+    if !migrated.contains(&path) {
+        migrate(&db)?;
+        migrated.insert(path);
+    }
+    Ok(db)
+}
+
+// For a reset of the applications (reset.rs); the app relaunches after.
+pub fn wipe(app: &AppHandle) -> Result<(), String> {
+    match std::fs::remove_file(path(app)?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn text(v: &Value) -> Option<String> {
+    v.as_str().filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+// An item of GET /api/applications (`_serialize_application`).
+fn from_listing(item: &Value) -> Update {
+    let mut artifacts = Vec::new();
+    if let Some(path) = text(&item["form_screenshot_path"]) {
+        artifacts.push(("form_screenshot", path));
+    }
+    if let Some(url) = text(&item["form_url"]) {
+        artifacts.push(("form", url));
+    }
+    Update {
+        company: text(&item["company"]),
+        url: text(&item["form_url"]),
+        status: text(&item["status"]),
+        cv_profile: text(&item["cv_profile"]),
+        created_at: text(&item["created_at"]),
+        updated_at: text(&item["updated_at"]),
+        authorized_at: text(&item["authorized_at"]),
+        site: text(&item["site"]),
+        location: text(&item["location"]),
+        salary_raw: text(&item["salary_raw"]),
+        description: text(&item["description"]),
+        artifacts,
+        ..Default::default()
+    }
+}
+
+fn from_event(event: &BackendEvent) -> Update {
+    let d = &event.data;
+    let now = Some(Utc::now().to_rfc3339());
+    match event.event.as_str() {
+        "review_ready" => {
+            let mut update = Update {
+                company: text(&d["company"]),
+                role: text(&d["title"]),
+                url: text(&d["form_url"]),
+                location: text(&d["location"]),
+                ..Default::default()
+            };
+            if let Some(path) = text(&d["screenshot_path"]) {
+                update.artifacts.push(("form_screenshot", path));
+            }
+            if let Some(url) = text(&d["form_url"]) {
+                update.artifacts.push(("form", url));
+            }
+            update
+        }
+        "application_submitted" => Update {
+            status: text(&d["status"]),
+            submitted_at: now,
+            ..Default::default()
+        },
+        "application_authorized" => Update {
+            authorized_at: now,
+            ..Default::default()
+        },
+        "application_status_updated" => Update {
+            status: text(&d["status"]),
+            ..Default::default()
+        },
+        _ => Update::default(),
+    }
+}
+
+fn apply(db: &Connection, id: i64, update: &Update) -> Result<(), String> {
+    let now = Utc::now().to_rfc3339();
+    // Yearly and in euros, with the rates of the day it came in
+    let salary = update
+        .salary_raw
+        .as_deref()
+        .and_then(|raw| salary::normalize(raw, Some(salary::DEFAULT_CURRENCY)))
+        .and_then(|s| serde_json::to_string(&s).ok());
+    let previous: Option<String> = db
+        .query_row("SELECT status FROM applications WHERE id = ?1", [id], |r| {
+            r.get(0)
+        })
+        .optional()
+        .map_err(|e| e.to_string())?;
+    db.execute(
+        "INSERT INTO applications (id, recorded_at) VALUES (?1, ?2)
+         ON CONFLICT (id) DO UPDATE SET recorded_at = ?2",
+        params![id, now],
+    )
+    .map_err(|e| e.to_string())?;
+    db.execute(
+        "UPDATE applications SET
+            company = COALESCE(?2, company),
+            role = COALESCE(?3, role),
+            url = COALESCE(?4, url),
+            status = COALESCE(?5, status),
+            cv_profile = COALESCE(?6, cv_profile),
+            created_at = COALESCE(created_at, ?7),
+            updated_at = COALESCE(?8, updated_at),
+            authorized_at = COALESCE(authorized_at, ?9),
+            submitted_at = COALESCE(submitted_at, ?10),
+            site = COALESCE(?11, site),
+            location = COALESCE(?12, location),
+            salary_raw = COALESCE(?13, salary_raw),
+            salary = COALESCE(?14, salary),
+            description = COALESCE(?15, description)
+         WHERE id = ?1",
+        params![
+            id,
+            update.company,
+            update.role,
+            update.url,
+            update.status,
+            update.cv_profile,
+            update.created_at,
+            update.updated_at,
+            update.authorized_at,
+            update.submitted_at,
+            update.site,
+            update.location,
+            update.salary_raw,
+            salary,
+            update.description,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    if let Some(status) = &update.status {
+        if previous.as_deref() != Some(status) {
+            db.execute(
+                "INSERT INTO status_changes (application_id, status, at) VALUES (?1, ?2, ?3)",
+                params![id, status, now],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    for (kind, location) in &update.artifacts {
+        db.execute(
+            "INSERT OR IGNORE INTO artifacts (application_id, kind, location, recorded_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![id, kind, location, now],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// A status set here (pipeline.rs), ahead of the engine's event for it.
+pub fn record_status(db: &Connection, id: i64, status: &str) -> Result<(), String> {
+    apply(
+        db,
+        id,
+        &Update {
+            status: Some(status.to_string()),
+            updated_at: Some(Utc::now().to_rfc3339()),
+            ..Default::default()
+        },
+    )
+}
+
+// The engine's current row for one application; its list pages downwards
+// from a cursor, so the page just above `id` starts with it.
+fn fetch(id: i64) -> Option<Value> {
+    backend::get_json(&format!("/api/applications?cursor={}&limit=1", id + 1))
+        .ok()?
+        .get("items")?
+        .as_array()?
+        .first()
+        .filter(|item| item["id"].as_i64() == Some(id))
+        .cloned()
+}
+
+fn record(app: &AppHandle, event: &BackendEvent) -> Result<(), String> {
+    let Some(id) = event.data["application_id"].as_i64() else {
+        return Ok(());
+    };
+    let db = open(app)?;
+    apply(&db, id, &from_event(event))?;
+    if let Some(item) = fetch(id) {
+        apply(&db, id, &from_listing(&item))?;
+    }
+    let _ = app.emit(HISTORY_CHANGED, id);
+    Ok(())
+}
+
+// From the event bridge, for events about one application.
+pub fn on_event(app: &AppHandle, event: &BackendEvent) {
+    if event.data["application_id"].as_i64().is_none() {
+        return;
+    }
+    let app = app.clone();
+    let event = event.clone();
+    thread::spawn(move || {
+        if let Err(e) = record(&app, &event) {
+            warn!(event = event.event, "application history not updated: {e}");
+        }
+    });
+}
+
+fn sync(app: &AppHandle) -> Result<usize, String> {
+    let db = open(app)?;
+    let mut cursor: Option<i64> = None;
+    let mut count = 0;
+    loop {
+        let query = match cursor {
+            Some(cursor) => format!("/api/applications?limit={PAGE}&cursor={cursor}"),
+            None => format!("/api/applications?limit={PAGE}"),
+        };
+        let page = backend::get_json(&query)?;
+        for item in page["items"].as_array().into_iter().flatten() {
+            if let Some(id) = item["id"].as_i64() {
+                apply(&db, id, &from_listing(item))?;
+                count += 1;
+            }
+        }
+        match page["next_cursor"].as_i64() {
+            Some(next) => cursor = Some(next),
+            None => return Ok(count),
+        }
+    }
+}
+
+// Whenever the event stream connects: picks up what happened while the
+// shell wasn't listening.
+pub fn start_sync(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || match sync(&app) {
+        Ok(count) => {
+            debug!(count, "application history synced");
+            let _ = app.emit(HISTORY_CHANGED, ());
+        }
+        Err(e) => warn!("application history not synced: {e}"),
+    });
+}
+
+pub fn entry(r: &Row) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        id: r.get("id")?,
+        company: r.get("company")?,
+        role: r.get("role")?,
+        url: r.get("url")?,
+        status: r.get("status")?,
+        cv_profile: r.get("cv_profile")?,
+        created_at: r.get("created_at")?,
+        updated_at: r.get("updated_at")?,
+        authorized_at: r.get("authorized_at")?,
+        submitted_at: r.get("submitted_at")?,
+        recorded_at: r.get("recorded_at")?,
+        site: r.get("site")?,
+        imported_from: r.get("imported_from")?,
+        location: r.get("location")?,
+        salary_raw: r.get("salary_raw")?,
+        salary: r
+            .get::<_, Option<String>>("salary")?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+        tags: Vec::new(),
+    })
+}
+
+// Fills in each entry's tags.
+pub fn with_tags(
+    db: &Connection,
+    mut items: Vec<HistoryEntry>,
+) -> Result<Vec<HistoryEntry>, String> {
+    let ids: Vec<i64> = items.iter().map(|e| e.id).collect();
+    let mut tags = tags::of(db, &ids)?;
+    for item in &mut items {
+        item.tags = tags.remove(&item.id).unwrap_or_default();
+    }
+    Ok(items)
+}
+
+// The WHERE clause for a query's filters, with its arguments.
+pub fn filter(q: &HistoryQuery) -> (String, Vec<Sql>) {
+    let mut clauses = Vec::new();
+    let mut args: Vec<Sql> = Vec::new();
+    if let Some(status) = q.status.as_deref().filter(|s| !s.is_empty()) {
+        clauses.push("status = ?");
+        args.push(Sql::Text(status.into()));
+    }
+    if let Some(tag) = q.tag {
+        clauses.push("id IN (SELECT application_id FROM application_tags WHERE tag_id = ?)");
+        args.push(Sql::Integer(tag));
+    }
+    if let Some(search) = q.search.as_deref().filter(|s| !s.trim().is_empty()) {
+        clauses.push("(company LIKE ? ESCAPE '\\' OR role LIKE ? ESCAPE '\\')");
+        let escaped = search
+            .trim()
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let pattern = format!("%{escaped}%");
+        args.push(Sql::Text(pattern.clone()));
+        args.push(Sql::Text(pattern));
+    }
+    if let Some(since) = q.since.as_deref().filter(|s| !s.is_empty()) {
+        clauses.push("created_at >= ?");
+        args.push(Sql::Text(since.into()));
+    }
+    if let Some(until) = q.until.as_deref().filter(|s| !s.is_empty()) {
+        // Dates take in the whole day
+        clauses.push("substr(created_at, 1, 10) <= ?");
+        args.push(Sql::Text(until.into()));
+    }
+    let filter = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+    (filter, args)
+}
+
+fn query(app: &AppHandle, q: HistoryQuery) -> Result<HistoryPage, String> {
+    let db = open(app)?;
+    let (filter, mut args) = filter(&q);
+    let total: u32 = db
+        .query_row(
+            &format!("SELECT COUNT(*) FROM applications {filter}"),
+            params_from_iter(&args),
+            |r| r.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let limit = q.limit.unwrap_or(PAGE).clamp(1, MAX_LIMIT);
+    args.push(Sql::Integer(limit.into()));
+    args.push(Sql::Integer(q.offset.into()));
+    let mut statement = db
+        .prepare(&format!(
+            "SELECT * FROM applications {filter} ORDER BY id DESC LIMIT ? OFFSET ?"
+        ))
+        .map_err(|e| e.to_string())?;
+    let items = statement
+        .query_map(params_from_iter(&args), entry)
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    let items = with_tags(&db, items)?;
+    Ok(HistoryPage { items, total })
+}
+
+// A stored time in local time. The engine's are UTC without an offset;
+// the shell's carry one.
+pub fn local(time: &Option<String>) -> Option<NaiveDateTime> {
+    let time = time.as_deref()?;
+    let utc = DateTime::parse_from_rfc3339(time)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| time.parse::<NaiveDateTime>().map(|t| t.and_utc()))
+        .ok()?;
+    Some(utc.with_timezone(&Local).naive_local())
+}
+
+// Every match, oldest first, for the exports (spreadsheet.rs, pdf.rs).
+pub fn entries(app: &AppHandle, q: &HistoryQuery) -> Result<Vec<HistoryEntry>, String> {
+    let db = open(app)?;
+    let (filter, args) = filter(q);
+    let mut statement = db
+        .prepare(&format!("SELECT * FROM applications {filter} ORDER BY id"))
+        .map_err(|e| e.to_string())?;
+    let items = statement
+        .query_map(params_from_iter(&args), entry)
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    with_tags(&db, items)
+}
+
+pub fn detail(app: &AppHandle, id: i64) -> Result<Option<HistoryDetail>, String> {
+    let db = open(app)?;
+    let Some(mut entry) = db
+        .query_row("SELECT * FROM applications WHERE id = ?1", [id], entry)
+        .optional()
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    entry.tags = tags::of(&db, &[id])?.remove(&id).unwrap_or_default();
+    let status_changes = db
+        .prepare("SELECT status, at FROM status_changes WHERE application_id = ?1 ORDER BY rowid")
+        .and_then(|mut s| {
+            s.query_map([id], |r| {
+                Ok(StatusChange {
+                    status: r.get(0)?,
+                    at: r.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| e.to_string())?;
+    let artifacts = db
+        .prepare(
+            "SELECT kind, location, recorded_at FROM artifacts WHERE application_id = ?1
+             ORDER BY rowid",
+        )
+        .and_then(|mut s| {
+            s.query_map([id], |r| {
+                Ok(Artifact {
+                    kind: r.get(0)?,
+                    location: r.get(1)?,
+                    recorded_at: r.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(Some(HistoryDetail {
+        entry,
+        status_changes,
+        artifacts,
+    }))
+}
+
+#[tauri::command]
+pub async fn query_history(app: AppHandle, query: HistoryQuery) -> Result<HistoryPage, String> {
+    tauri::async_runtime::spawn_blocking(move || self::query(&app, query))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn get_history_entry(app: AppHandle, id: i64) -> Result<Option<HistoryDetail>, String> {
+    tauri::async_runtime::spawn_blocking(move || detail(&app, id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Statuses present, for the filter.
+#[tauri::command]
+pub async fn history_statuses(app: AppHandle) -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = open(&app)?;
+        let mut statement = db
+            .prepare("SELECT DISTINCT status FROM applications WHERE status != '' ORDER BY status")
+            .map_err(|e| e.to_string())?;
+        let statuses = statement
+            .query_map([], |r| r.get(0))
+            .and_then(|rows| rows.collect::<Result<Vec<String>, _>>())
+            .map_err(|e| e.to_string());
+        statuses
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_once() {
+        let db = Connection::open_in_memory().unwrap();
+        migrate(&db).unwrap();
+        let version: i32 = db
+            .query_row("PRAGMA user_version", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(version, VERSION);
+        db.execute(
+            "INSERT INTO applications (company, description, recorded_at)
+             VALUES ('Acme', 'Rust work', '2026-01-01')",
+            [],
+        )
+        .unwrap();
+        migrate(&db).unwrap();
+        let found: i64 = db
+            .query_row(
+                "SELECT count(*) FROM application_search WHERE application_search MATCH 'rust'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(found, 1);
+    }
+}
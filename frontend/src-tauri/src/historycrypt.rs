@@ -0,0 +1,251 @@
+use aes_gcm::{
+    aead::{KeyInit, OsRng},
+    Aes256Gcm,
+};
+use rusqlite::{params, Connection};
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::Path,
+    sync::Mutex,
+    time::Duration,
+};
+use tauri::AppHandle;
+use tracing::info;
+
+use crate::{history, settings};
+
+// Optional encryption of the application history at rest, with SQLCipher.
+// The key is random, made the first time encryption is turned on, and kept in
+// the OS keychain, so history.db alone can't be read. The file is brought in
+// line with the setting whenever it's opened: an encrypted or plain copy is
+// written beside it and swapped in. That also covers a history restored from
+// a backup (which holds it decrypted) or a data folder from another machine.
+pub const KEYCHAIN_SERVICE: &str = "com.currobot.app.history";
+pub const KEYCHAIN_ACCOUNT: &str = "database-key";
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+// Hex, as SQLCipher takes a raw key
+static KEY: Mutex<Option<String>> = Mutex::new(None);
+// One conversion at a time
+static CONVERTING: Mutex<()> = Mutex::new(());
+
+fn entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT).map_err(|e| e.to_string())
+}
+
+fn key(create: bool) -> Result<String, String> {
+    let mut cached = KEY.lock().unwrap();
+    if let Some(key) = cached.as_ref() {
+        return Ok(key.clone());
+    }
+    let entry = entry()?;
+    let key = match entry.get_password() {
+        Ok(key) if key.len() == 64 && key.bytes().all(|b| b.is_ascii_hexdigit()) => key,
+        Ok(_) => return Err("the history key in the keychain is corrupt".into()),
+        Err(keyring::Error::NoEntry) if create => {
+            let key: String = Aes256Gcm::generate_key(&mut OsRng)
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            entry.set_password(&key).map_err(|e| e.to_string())?;
+            key
+        }
+        Err(keyring::Error::NoEntry) => {
+            return Err("the history key is missing from the keychain".into())
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    *cached = Some(key.clone());
+    Ok(key)
+}
+
+// Whether `path` is a database SQLCipher encrypted: it has pages, but not
+// SQLite's header. Missing and empty files are not.
+pub fn is_encrypted(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .is_ok_and(|_| header != *SQLITE_HEADER)
+}
+
+// Keys a connection just opened. SQLCipher only checks the key on the first
+// read, so this reads.
+pub fn unlock(db: &Connection, key: &str) -> Result<(), String> {
+    db.execute_batch(&format!("PRAGMA key = \"x'{key}'\""))
+        .map_err(|e| e.to_string())?;
+    db.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+        .map_err(|_| "the history can't be opened with the key in the keychain".to_string())
+}
+
+// Writes `path` over with its contents encrypted with `to`, or plain with
+// None. `from` is the key it has now.
+fn convert(path: &Path, from: Option<&str>, to: Option<&str>) -> Result<(), String> {
+    let converted = path.with_extension("db.converting");
+    let _ = fs::remove_file(&converted);
+    let result = (|| {
+        let db = Connection::open(path).map_err(|e| e.to_string())?;
+        db.busy_timeout(Duration::from_secs(5))
+            .map_err(|e| e.to_string())?;
+        if let Some(key) = from {
+            unlock(&db, key)?;
+        }
+        let version: i32 = db
+            .query_row("PRAGMA user_version", [], |r| r.get(0))
+            .map_err(|e| e.to_string())?;
+        db.execute(
+            "ATTACH DATABASE ?1 AS converted KEY ?2",
+            params![
+                converted.to_string_lossy(),
+                to.map(|key| format!("x'{key}'")).unwrap_or_default()
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        db.query_row("SELECT sqlcipher_export('converted')", [], |_| Ok(()))
+            .map_err(|e| e.to_string())?;
+        db.execute_batch(&format!(
+            "PRAGMA converted.user_version = {version}; DETACH DATABASE converted"
+        ))
+        .map_err(|e| e.to_string())?;
+        db.close().map_err(|(_, e)| e.to_string())?;
+        fs::rename(&converted, path).map_err(|e| e.to_string())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&converted);
+    }
+    result
+}
+
+// Brings the history at `path` in line with the setting, and gives the key
+// to open it with, if it's encrypted. A new file is made encrypted by keying
+// it before the first write.
+pub fn prepare(app: &AppHandle, path: &Path) -> Result<Option<String>, String> {
+    let encrypt = settings::get(app).encrypt_history;
+    let _guard = CONVERTING.lock().unwrap();
+    match (is_encrypted(path), encrypt) {
+        (true, true) => key(false).map(Some),
+        (false, false) => Ok(None),
+        (false, true) => {
+            let key = key(true)?;
+            if path.metadata().is_ok_and(|m| m.len() > 0) {
+                convert(path, None, Some(&key))?;
+                info!("application history encrypted");
+            }
+            Ok(Some(key))
+        }
+        (true, false) => {
+            convert(path, Some(&key(false)?), None)?;
+            info!("application history decrypted");
+            Ok(None)
+        }
+    }
+}
+
+// A plain copy of the encrypted history at `source`, for a backup.
+pub fn decrypted_copy(source: &Path, target: &Path) -> Result<(), String> {
+    let _ = fs::remove_file(target);
+    let db = Connection::open(source).map_err(|e| e.to_string())?;
+    db.busy_timeout(Duration::from_secs(5))
+        .map_err(|e| e.to_string())?;
+    unlock(&db, &key(false)?)?;
+    db.execute(
+        "ATTACH DATABASE ?1 AS plain KEY ''",
+        [target.to_string_lossy()],
+    )
+    .map_err(|e| e.to_string())?;
+    db.query_row("SELECT sqlcipher_export('plain')", [], |_| Ok(()))
+        .map_err(|e| e.to_string())?;
+    db.execute_batch("DETACH DATABASE plain")
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_history_encryption(app: AppHandle) -> bool {
+    settings::get(&app).encrypt_history
+}
+
+// Converts the history now rather than on its next use; the setting is put
+// back if that fails.
+#[tauri::command]
+pub async fn set_history_encryption(app: AppHandle, enabled: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let before = settings::get(&app).encrypt_history;
+        settings::update(&app, |s| s.encrypt_history = enabled)?;
+        history::open(&app).map(drop).inspect_err(|_| {
+            let _ = settings::update(&app, |s| s.encrypt_history = before);
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_KEY: &str = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff";
+
+    fn scratch(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("historycrypt-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("history.db")
+    }
+
+    fn count(db: &Connection) -> i64 {
+        db.query_row(
+            "SELECT count(*) FROM notes WHERE notes MATCH 'kept'",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn converts_both_ways() {
+        let path = scratch("convert");
+        {
+            let db = Connection::open(&path).unwrap();
+            db.execute_batch(
+                "CREATE VIRTUAL TABLE notes USING fts5(body);
+                 INSERT INTO notes (body) VALUES ('kept across conversions');
+                 PRAGMA user_version = 3;",
+            )
+            .unwrap();
+        }
+        assert!(!is_encrypted(&path));
+
+        convert(&path, None, Some(TEST_KEY)).unwrap();
+        assert!(is_encrypted(&path));
+        assert!(Connection::open(&path)
+            .unwrap()
+            .query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+            .is_err());
+        let db = Connection::open(&path).unwrap();
+        unlock(&db, TEST_KEY).unwrap();
+        assert_eq!(count(&db), 1);
+        let version: i32 = db
+            .query_row("PRAGMA user_version", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(version, 3);
+        drop(db);
+
+        convert(&path, Some(TEST_KEY), None).unwrap();
+        assert!(!is_encrypted(&path));
+        assert_eq!(count(&Connection::open(&path).unwrap()), 1);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn wrong_key_is_refused() {
+        let path = scratch("wrong");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x); INSERT INTO t VALUES (1);")
+            .unwrap();
+        convert(&path, None, Some(TEST_KEY)).unwrap();
+        let db = Connection::open(&path).unwrap();
+        assert!(unlock(&db, &TEST_KEY.replace('0', "1")).is_err());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
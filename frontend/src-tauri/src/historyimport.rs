@@ -0,0 +1,461 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_dialog::DialogExt;
+use tracing::info;
+
+use crate::{
+    history::{self, HISTORY_CHANGED},
+    i18n::t,
+};
+
+// Brings in the applications a new user already sent elsewhere: LinkedIn's
+// data archive (its "Jobs/Job Applications.csv") and Indeed's, as the ZIP
+// or a CSV taken out of it. Columns are found by their header, so other
+// trackers' CSVs with a company, title and date load too. Rows go into the
+// shell's history (history.rs) with negative ids, which the engine never
+// uses, marked with where they came from; one already there for the same
+// company and role on the same day is skipped, so importing twice is
+// harmless.
+const MAX_CSV_BYTES: u64 = 20 * 1024 * 1024;
+const MAX_ERRORS: usize = 10;
+
+const COMPANY: &[&str] = &["company name", "company", "employer", "empresa"];
+const ROLE: &[&str] = &["job title", "title", "position", "role", "puesto"];
+const DATE: &[&str] = &[
+    "application date",
+    "applied date",
+    "date applied",
+    "applied on",
+    "applied at",
+    "date",
+    "fecha",
+];
+const URL: &[&str] = &["job url", "job link", "url", "link"];
+const STATUS: &[&str] = &["status", "application status", "estado"];
+
+const DATE_FORMATS: &[&str] = &[
+    // LinkedIn: "10/14/24, 3:12 PM"
+    "%m/%d/%y, %I:%M %p",
+    "%m/%d/%Y, %I:%M %p",
+    "%m/%d/%Y %I:%M %p",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+const DAY_FORMATS: &[&str] = &["%Y-%m-%d", "%m/%d/%Y", "%m/%d/%y", "%b %d, %Y", "%d %b %Y"];
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    // "linkedin", "indeed" or "csv"
+    pub source: String,
+    pub files: usize,
+    pub rows: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+    // Without a company or a date that could be read
+    pub skipped: usize,
+    // The first few, with their file and line
+    pub errors: Vec<String>,
+}
+
+// A CSV to read, and what its name says about where it came from.
+struct Table {
+    name: String,
+    text: String,
+}
+
+fn tables(path: &Path) -> Result<Vec<Table>, String> {
+    let is_zip = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("zip"));
+    if !is_zip {
+        let mut text = String::new();
+        File::open(path)
+            .map_err(|e| e.to_string())?
+            .take(MAX_CSV_BYTES)
+            .read_to_string(&mut text)
+            .map_err(|e| e.to_string())?;
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        return Ok(vec![Table {
+            name: name.into_owned(),
+            text,
+        }]);
+    }
+    let mut archive = zip::ZipArchive::new(File::open(path).map_err(|e| e.to_string())?)
+        .map_err(|e| e.to_string())?;
+    let mut found = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
+        let name = file.name().map_err(|e| e.to_string())?.into_owned();
+        let lower = name.to_lowercase();
+        // LinkedIn's archive holds dozens of CSVs; only the applications matter
+        let wanted = lower.ends_with(".csv")
+            && (lower.contains("appli")
+                || lower.contains("candidatura")
+                || lower.contains("indeed"));
+        if !wanted || file.size() > MAX_CSV_BYTES {
+            continue;
+        }
+        let mut text = String::new();
+        file.read_to_string(&mut text).map_err(|e| e.to_string())?;
+        found.push(Table { name, text });
+    }
+    if found.is_empty() {
+        return Err("no application list found in the archive".into());
+    }
+    Ok(found)
+}
+
+fn column(headers: &[String], names: &[&str]) -> Option<usize> {
+    names
+        .iter()
+        .find_map(|name| headers.iter().position(|h| h == name))
+}
+
+fn source(path: &Path, table: &Table, headers: &[String]) -> &'static str {
+    let origin = format!("{} {}", path.display(), table.name).to_lowercase();
+    if origin.contains("linkedin") || headers.iter().any(|h| h == "resume name") {
+        "linkedin"
+    } else if origin.contains("indeed") {
+        "indeed"
+    } else {
+        "csv"
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    DATE_FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+        .or_else(|| {
+            DAY_FORMATS
+                .iter()
+                .find_map(|f| NaiveDate::parse_from_str(value, f).ok())
+                .and_then(|d| d.and_hms_opt(12, 0, 0))
+        })
+}
+
+// What other trackers call the states the engine has names for.
+fn status(value: Option<&str>) -> &'static str {
+    let value = value.unwrap_or("").to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| value.contains(w));
+    if has(&["offer", "hired", "oferta", "contratad"]) {
+        "offered"
+    } else if has(&[
+        "not selected",
+        "no longer",
+        "rejected",
+        "declined",
+        "descartad",
+        "rechazad",
+    ]) {
+        "rejected"
+    } else if has(&["interview", "entrevista"]) {
+        "interview_scheduled"
+    } else if has(&["viewed", "reviewed", "vista", "revisad"]) {
+        "acknowledged"
+    } else {
+        "applied"
+    }
+}
+
+fn key(company: &str, role: &str, day: NaiveDate) -> String {
+    let clean = |s: &str| {
+        s.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    format!("{}|{}|{day}", clean(company), clean(role))
+}
+
+// Company, role and local day of every application already in the history.
+fn existing(db: &Connection) -> Result<HashSet<String>, String> {
+    let mut statement = db
+        .prepare(
+            "SELECT company, COALESCE(role, ''), COALESCE(submitted_at, created_at)
+             FROM applications",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map([], |r| {
+            Ok((
+                r.get::<_, String>(0)?,
+                r.get::<_, String>(1)?,
+                r.get::<_, Option<String>>(2)?,
+            ))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(company, role, at)| Some(key(&company, &role, history::local(&at)?.date())))
+        .collect())
+}
+
+fn import(app: &AppHandle, path: &Path) -> Result<ImportSummary, String> {
+    let summary = import_into(&mut history::open(app)?, path)?;
+    if summary.imported > 0 {
+        let _ = app.emit(HISTORY_CHANGED, ());
+    }
+    Ok(summary)
+}
+
+fn import_into(db: &mut Connection, path: &Path) -> Result<ImportSummary, String> {
+    let tables = tables(path)?;
+    let mut seen = existing(db)?;
+    let mut next_id: i64 = db
+        .query_row("SELECT MIN(MIN(id), 0) - 1 FROM applications", [], |r| {
+            r.get::<_, Option<i64>>(0)
+        })
+        .map_err(|e| e.to_string())?
+        .unwrap_or(-1);
+    let tx = db.transaction().map_err(|e| e.to_string())?;
+    let now = Utc::now().to_rfc3339();
+    let mut summary = ImportSummary {
+        files: tables.len(),
+        ..Default::default()
+    };
+    let error = |summary: &mut ImportSummary, message: String| {
+        if summary.errors.len() < MAX_ERRORS {
+            summary.errors.push(message);
+        }
+    };
+    for table in &tables {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(table.text.trim_start_matches('\u{feff}').as_bytes());
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(|e| format!("{}: {e}", table.name))?
+            .iter()
+            .map(|h| h.trim().to_lowercase())
+            .collect();
+        let (Some(company_at), Some(date_at)) = (column(&headers, COMPANY), column(&headers, DATE))
+        else {
+            error(
+                &mut summary,
+                format!("{}: no company or date column", table.name),
+            );
+            continue;
+        };
+        let role_at = column(&headers, ROLE);
+        let url_at = column(&headers, URL);
+        let status_at = column(&headers, STATUS);
+        let origin = source(path, table, &headers);
+        summary.source = origin.to_string();
+        for (line, record) in reader.records().enumerate() {
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    error(
+                        &mut summary,
+                        format!("{} line {}: {e}", table.name, line + 2),
+                    );
+                    continue;
+                }
+            };
+            summary.rows += 1;
+            let field = |at: Option<usize>| {
+                at.and_then(|i| record.get(i))
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+            };
+            let (Some(company), Some(when)) = (
+                field(Some(company_at)),
+                field(Some(date_at)).and_then(parse_time),
+            ) else {
+                summary.skipped += 1;
+                continue;
+            };
+            let role = field(role_at).unwrap_or("");
+            if !seen.insert(key(company, role, when.date())) {
+                summary.duplicates += 1;
+                continue;
+            }
+            let sent_at = Local
+                .from_local_datetime(&when)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc).to_rfc3339())
+                .unwrap_or_else(|| when.and_utc().to_rfc3339());
+            let status = status(field(status_at));
+            tx.execute(
+                "INSERT INTO applications
+                    (id, company, role, url, status, created_at, submitted_at, recorded_at,
+                     site, imported_from)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6, ?7, ?8, ?8)",
+                params![
+                    next_id,
+                    company,
+                    (!role.is_empty()).then_some(role),
+                    field(url_at),
+                    status,
+                    sent_at,
+                    now,
+                    origin,
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            // When it moved on from there isn't in the exports
+            tx.execute(
+                "INSERT INTO status_changes (application_id, status, at) VALUES (?1, 'applied', ?2)",
+                params![next_id, sent_at],
+            )
+            .map_err(|e| e.to_string())?;
+            next_id -= 1;
+            summary.imported += 1;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    info!(
+        source = summary.source,
+        imported = summary.imported,
+        duplicates = summary.duplicates,
+        skipped = summary.skipped,
+        "application history imported"
+    );
+    Ok(summary)
+}
+
+// `path` None asks for the file.
+#[tauri::command]
+pub async fn import_application_history(
+    app: AppHandle,
+    path: Option<String>,
+) -> Result<Option<ImportSummary>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let Some(chosen) = app
+                    .dialog()
+                    .file()
+                    .set_title(t("history-import-title"))
+                    .add_filter(t("history-import-filter"), &["zip", "csv"])
+                    .blocking_pick_file()
+                else {
+                    return Ok(None);
+                };
+                chosen.into_path().map_err(|e| e.to_string())?
+            }
+        };
+        import(&app, &path).map(Some)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        db
+    }
+
+    fn csv_file(name: &str, text: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jobbot-import-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, text).unwrap();
+        path
+    }
+
+    #[test]
+    fn dates_in_the_exports_formats() {
+        let at = |s: &str| parse_time(s).map(|t| t.to_string());
+        assert_eq!(
+            at("10/14/24, 3:12 PM").as_deref(),
+            Some("2024-10-14 15:12:00")
+        );
+        assert_eq!(at("2024-10-14").as_deref(), Some("2024-10-14 12:00:00"));
+        assert_eq!(at("Oct 14, 2024").as_deref(), Some("2024-10-14 12:00:00"));
+        assert_eq!(at("yesterday"), None);
+    }
+
+    #[test]
+    fn statuses_by_their_wording() {
+        assert_eq!(status(Some("Offer extended")), "offered");
+        assert_eq!(status(Some("No longer under consideration")), "rejected");
+        assert_eq!(status(Some("Entrevista programada")), "interview_scheduled");
+        assert_eq!(status(Some("Application viewed")), "acknowledged");
+        assert_eq!(status(None), "applied");
+    }
+
+    #[test]
+    fn columns_and_sources() {
+        let headers: Vec<String> = ["date", "company name", "resume name"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(column(&headers, COMPANY), Some(1));
+        assert_eq!(column(&headers, ROLE), None);
+        let table = Table {
+            name: "Job Applications.csv".into(),
+            text: String::new(),
+        };
+        assert_eq!(
+            source(Path::new("export.csv"), &table, &headers),
+            "linkedin"
+        );
+        assert_eq!(source(Path::new("indeed.csv"), &table, &[]), "indeed");
+        assert_eq!(source(Path::new("mine.csv"), &table, &[]), "csv");
+    }
+
+    #[test]
+    fn rows_imported_once() {
+        let path = csv_file(
+            "applications.csv",
+            "\u{feff}Company Name,Job Title,Application Date,Status\n\
+             Acme,Engineer,2026-09-01,Application viewed\n\
+             Acme, Engineer ,2026-09-01,\n\
+             ,Analyst,2026-09-02,\n\
+             Initech,Analyst,someday,\n\
+             Globex,,2026-09-03 10:00,Rejected\n",
+        );
+        let mut db = db();
+        let summary = import_into(&mut db, &path).unwrap();
+        assert_eq!(summary.source, "csv");
+        assert_eq!(
+            (
+                summary.rows,
+                summary.imported,
+                summary.duplicates,
+                summary.skipped
+            ),
+            (5, 2, 1, 2)
+        );
+        let ids: Vec<(i64, String)> = db
+            .prepare("SELECT id, status FROM applications ORDER BY id DESC")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(ids, [(-1, "acknowledged".into()), (-2, "rejected".into())]);
+
+        // Importing the same file again adds nothing
+        let again = import_into(&mut db, &path).unwrap();
+        assert_eq!((again.imported, again.duplicates), (0, 3));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn files_without_the_columns() {
+        let path = csv_file("notes.csv", "Name,Notes\nAcme,call back\n");
+        let summary = import_into(&mut db(), &path).unwrap();
+        assert_eq!(summary.imported, 0);
+        assert_eq!(summary.errors, ["notes.csv: no company or date column"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,237 @@
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use serde::Serialize;
+use std::sync::{OnceLock, RwLock};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+use unic_langid::LanguageIdentifier;
+
+use crate::settings;
+
+// Text the shell shows itself (tray, window titles, notifications) comes from
+// the Fluent files in locales/, compiled in. The preference lives in
+// settings.json as `language`; unset follows the OS. Missing messages fall
+// back to English, then to the message id.
+const LOCALES: &[(&str, &str, &str)] = &[
+    ("en", "English", include_str!("../locales/en.ftl")),
+    ("es", "Español", include_str!("../locales/es.ftl")),
+];
+const FALLBACK: &str = "en";
+
+pub const LANGUAGE_CHANGED: &str = "language-changed";
+
+type Bundle = FluentBundle<FluentResource>;
+
+static CURRENT: RwLock<Option<(String, Bundle)>> = RwLock::new(None);
+static ENGLISH: OnceLock<Bundle> = OnceLock::new();
+
+#[derive(Debug, Serialize)]
+pub struct LanguageOption {
+    pub code: &'static str,
+    pub name: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LanguageInfo {
+    // None means "same as the system"
+    pub preference: Option<String>,
+    pub effective: String,
+    pub available: Vec<LanguageOption>,
+}
+
+fn is_supported(code: &str) -> bool {
+    LOCALES.iter().any(|(c, _, _)| *c == code)
+}
+
+fn build(code: &str) -> Bundle {
+    let source = LOCALES
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map_or(LOCALES[0].2, |(_, _, s)| s);
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+            warn!("locale {code} has syntax errors: {errors:?}");
+            resource
+        });
+    let lang: LanguageIdentifier = code.parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new_concurrent(vec![lang]);
+    // Bidi isolation marks show up as boxes in some tray/notification fonts
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        warn!("locale {code} has duplicate messages: {errors:?}");
+    }
+    bundle
+}
+
+// "es-ES", "es_ES.UTF-8" → "es" when we have it.
+fn system_language() -> String {
+    sys_locale::get_locale()
+        .and_then(|l| {
+            let primary = l.split(['-', '_', '.']).next()?.to_ascii_lowercase();
+            is_supported(&primary).then_some(primary)
+        })
+        .unwrap_or_else(|| FALLBACK.into())
+}
+
+fn resolve(preference: Option<&str>) -> String {
+    preference
+        .filter(|p| is_supported(p))
+        .map(str::to_string)
+        .unwrap_or_else(system_language)
+}
+
+// For dates chrono formats, in the current language.
+pub fn chrono_locale() -> chrono::Locale {
+    match current().as_str() {
+        "es" => chrono::Locale::es_ES,
+        _ => chrono::Locale::en_US,
+    }
+}
+
+pub fn current() -> String {
+    CURRENT
+        .read()
+        .unwrap()
+        .as_ref()
+        .map_or_else(system_language, |(code, _)| code.clone())
+}
+
+fn format(bundle: &Bundle, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        warn!("message {id} formatted with errors: {errors:?}");
+    }
+    Some(text.into_owned())
+}
+
+fn lookup(id: &str, args: Option<&FluentArgs>) -> String {
+    // Strings can be needed before settings are loaded (load warnings)
+    if CURRENT.read().unwrap().is_none() {
+        let code = system_language();
+        CURRENT
+            .write()
+            .unwrap()
+            .get_or_insert_with(|| (code.clone(), build(&code)));
+    }
+    let text = CURRENT
+        .read()
+        .unwrap()
+        .as_ref()
+        .and_then(|(_, bundle)| format(bundle, id, args));
+    text.or_else(|| format(ENGLISH.get_or_init(|| build(FALLBACK)), id, args))
+        .unwrap_or_else(|| id.to_string())
+}
+
+pub fn t(id: &str) -> String {
+    lookup(id, None)
+}
+
+pub fn t_args(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    lookup(id, Some(&fluent_args))
+}
+
+// Loads the bundle for the current preference and refreshes everything the
+// shell has already put on screen.
+pub fn apply(app: &AppHandle) {
+    let code = resolve(settings::get(app).language.as_deref());
+    if CURRENT
+        .read()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|(c, _)| *c == code)
+    {
+        return;
+    }
+    *CURRENT.write().unwrap() = Some((code.clone(), build(&code)));
+    for (label, title) in [
+        ("onboarding", "window-onboarding"),
+        ("release-notes", "window-release-notes"),
+        ("uninstall", "window-uninstall"),
+    ] {
+        if let Some(w) = app.get_webview_window(label) {
+            let _ = w.set_title(&t(title));
+        }
+    }
+    crate::refresh_tray_menu(app);
+    let _ = app.emit(LANGUAGE_CHANGED, code);
+}
+
+#[tauri::command]
+pub fn get_language(app: AppHandle) -> LanguageInfo {
+    LanguageInfo {
+        preference: settings::get(&app).language,
+        effective: current(),
+        available: LOCALES
+            .iter()
+            .map(|(code, name, _)| LanguageOption { code, name })
+            .collect(),
+    }
+}
+
+// `None` goes back to following the system language.
+#[tauri::command]
+pub fn set_language(app: AppHandle, language: Option<String>) -> Result<(), String> {
+    if let Some(code) = &language {
+        if !is_supported(code) {
+            return Err(format!("unsupported language {code}"));
+        }
+    }
+    settings::update(&app, |s| s.language = language)?;
+    apply(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    // Message ids start a line: `tray-quit = Quit`
+    fn ids(source: &str) -> BTreeSet<&str> {
+        if let Err((_, errors)) = FluentResource::try_new(source.to_string()) {
+            panic!("syntax errors: {errors:?}");
+        }
+        source
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once('=').map(|(id, _)| id.trim()))
+            .collect()
+    }
+
+    #[test]
+    fn every_language_has_every_message() {
+        let english = ids(LOCALES[0].2);
+        for (code, _, source) in &LOCALES[1..] {
+            let other = ids(source);
+            let missing: Vec<_> = english.difference(&other).collect();
+            let extra: Vec<_> = other.difference(&english).collect();
+            assert!(missing.is_empty(), "{code} lacks {missing:?}");
+            assert!(extra.is_empty(), "{code} has unknown {extra:?}");
+        }
+    }
+
+    #[test]
+    fn supported_preferences_win() {
+        assert!(is_supported("es"));
+        assert!(!is_supported("fr"));
+        assert_eq!(resolve(Some("es")), "es");
+        assert!(is_supported(&resolve(Some("fr"))));
+        assert!(is_supported(&resolve(None)));
+    }
+
+    #[test]
+    fn formats_arguments_without_isolation_marks() {
+        let bundle = build("en");
+        let mut args = FluentArgs::new();
+        args.set("start", "Mon 09:00");
+        let text = format(&bundle, "working-hours-refusal-until", Some(&args)).unwrap();
+        assert!(text.contains("Mon 09:00"));
+        assert!(!text.contains('\u{2068}'));
+        assert!(format(&bundle, "no-such-message", None).is_none());
+    }
+}
@@ -0,0 +1,254 @@
+use std::thread;
+use tauri::AppHandle;
+use tracing::debug;
+
+use crate::{export, jumplist};
+
+// One running instance per user. A second launch (a jump list entry, an
+// opened .jobbot file, the shortcut clicked again) hands its arguments to the
+// first and exits instead of starting another engine. Windows uses a named
+// pipe only this user can write to; Linux the session bus, where the app
+// answers as an org.freedesktop.Application. macOS does this itself.
+
+// At the very start of `run`; true when another instance took the arguments
+// and this one should exit.
+pub fn forward() -> bool {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    platform::send(&args)
+}
+
+pub fn listen(app: AppHandle) {
+    thread::spawn(move || {
+        platform::serve(move |args| {
+            debug!(count = args.len(), "second launch");
+            on_args(&app, &args, true);
+        })
+    });
+}
+
+// What a launch asked for beyond starting; `args` without the program. A
+// second launch that asked for nothing brings the window up.
+pub fn on_args(app: &AppHandle, args: &[String], second: bool) {
+    let mut handled = jumplist::on_args(app, args);
+    // Windows and Linux pass an opened .jobbot file on the command line
+    if let Some(path) = args
+        .iter()
+        .map(std::path::PathBuf::from)
+        .find(|p| export::is_export_file(p))
+    {
+        export::request_import(app, &path);
+        handled = true;
+    }
+    if second && !handled {
+        crate::show_window(app);
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use tracing::warn;
+    use windows::{
+        core::HSTRING,
+        Win32::{
+            Foundation::{CloseHandle, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED},
+            Storage::FileSystem::{
+                CreateFileW, ReadFile, WriteFile, FILE_FLAGS_AND_ATTRIBUTES,
+                FILE_FLAG_FIRST_PIPE_INSTANCE, FILE_GENERIC_WRITE, FILE_SHARE_NONE, OPEN_EXISTING,
+                PIPE_ACCESS_INBOUND,
+            },
+            System::Pipes::{
+                ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, WaitNamedPipeW,
+                PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_WAIT,
+            },
+        },
+    };
+
+    const BUFFER: u32 = 64 * 1024;
+    const WAIT_MS: u32 = 2_000;
+
+    // Per user: the default security only lets the creator's account write.
+    fn name() -> HSTRING {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        HSTRING::from(format!(r"\\.\pipe\com.currobot.app-{user}"))
+    }
+
+    pub fn send(args: &[String]) -> bool {
+        let Ok(message) = serde_json::to_vec(args) else {
+            return false;
+        };
+        let name = name();
+        for _ in 0..2 {
+            let pipe = unsafe {
+                CreateFileW(
+                    &name,
+                    FILE_GENERIC_WRITE.0,
+                    FILE_SHARE_NONE,
+                    None,
+                    OPEN_EXISTING,
+                    FILE_FLAGS_AND_ATTRIBUTES(0),
+                    None,
+                )
+            };
+            match pipe {
+                Ok(pipe) => {
+                    let sent = unsafe { WriteFile(pipe, Some(&message), None, None) };
+                    let _ = unsafe { CloseHandle(pipe) };
+                    return sent.is_ok();
+                }
+                // Another launch is being served; wait for the next instance
+                Err(e) if e.code() == ERROR_PIPE_BUSY.to_hresult() => unsafe {
+                    let _ = WaitNamedPipeW(&name, WAIT_MS);
+                },
+                // Nobody is listening: this is the first instance
+                Err(_) => return false,
+            }
+        }
+        false
+    }
+
+    pub fn serve(on_args: impl Fn(Vec<String>) + Send + Sync + 'static) {
+        let name = name();
+        loop {
+            let pipe = unsafe {
+                CreateNamedPipeW(
+                    &name,
+                    PIPE_ACCESS_INBOUND | FILE_FLAG_FIRST_PIPE_INSTANCE,
+                    PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                    1,
+                    0,
+                    BUFFER,
+                    0,
+                    None,
+                )
+            };
+            if pipe.is_invalid() {
+                warn!("second launches can't reach this instance");
+                return;
+            }
+            let connected = unsafe { ConnectNamedPipe(pipe, None) };
+            if connected.is_ok()
+                || connected.is_err_and(|e| e.code() == ERROR_PIPE_CONNECTED.to_hresult())
+            {
+                let mut message = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let mut read = 0u32;
+                    match unsafe { ReadFile(pipe, Some(&mut buf), Some(&mut read), None) } {
+                        Ok(()) if read > 0 => message.extend_from_slice(&buf[..read as usize]),
+                        // Including ERROR_BROKEN_PIPE: the sender closed its end
+                        _ => break,
+                    }
+                }
+                on_args(serde_json::from_slice(&message).unwrap_or_default());
+            }
+            unsafe {
+                let _ = DisconnectNamedPipe(pipe);
+                let _ = CloseHandle(pipe);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::{collections::HashMap, path::Path, sync::OnceLock};
+    use tauri::Url;
+    use tracing::warn;
+    use zbus::{
+        blocking::{connection, Connection, Proxy},
+        interface,
+        zvariant::OwnedValue,
+    };
+
+    use crate::{export, jumplist::TASK_ARG};
+
+    // The app's identifier, as a desktop file with DBusActivatable would use
+    const NAME: &str = "com.currobot.app";
+    const PATH: &str = "/com/currobot/app";
+    const INTERFACE: &str = "org.freedesktop.Application";
+
+    // Served from zbus's own thread for as long as it's held
+    static CONNECTION: OnceLock<Connection> = OnceLock::new();
+
+    type PlatformData = HashMap<String, OwnedValue>;
+
+    struct Application {
+        on_args: Box<dyn Fn(Vec<String>) + Send + Sync>,
+    }
+
+    #[interface(name = "org.freedesktop.Application")]
+    impl Application {
+        fn activate(&self, _platform_data: PlatformData) {
+            (self.on_args)(Vec::new());
+        }
+
+        fn open(&self, uris: Vec<String>, _platform_data: PlatformData) {
+            let paths = uris
+                .iter()
+                .filter_map(|uri| Url::parse(uri).ok()?.to_file_path().ok())
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect();
+            (self.on_args)(paths);
+        }
+
+        // The jump list's tasks, for desktop file actions
+        fn activate_action(
+            &self,
+            action_name: String,
+            _parameter: Vec<OwnedValue>,
+            _platform_data: PlatformData,
+        ) {
+            (self.on_args)(vec![format!("{TASK_ARG}{action_name}")]);
+        }
+    }
+
+    // Opened files go as URIs; anything else just brings the window up.
+    pub fn send(args: &[String]) -> bool {
+        let Ok(connection) = Connection::session() else {
+            return false;
+        };
+        let Ok(proxy) = Proxy::new(&connection, NAME, PATH, INTERFACE) else {
+            return false;
+        };
+        let uris: Vec<String> = args
+            .iter()
+            .map(Path::new)
+            .filter(|path| export::is_export_file(path))
+            .filter_map(|path| Url::from_file_path(std::path::absolute(path).ok()?).ok())
+            .map(String::from)
+            .collect();
+        let platform_data = PlatformData::new();
+        let sent = if uris.is_empty() {
+            proxy.call_method("Activate", &(platform_data,))
+        } else {
+            proxy.call_method("Open", &(uris, platform_data))
+        };
+        // Fails with ServiceUnknown when no instance runs
+        sent.is_ok()
+    }
+
+    pub fn serve(on_args: impl Fn(Vec<String>) + Send + Sync + 'static) {
+        let application = Application {
+            on_args: Box::new(on_args),
+        };
+        let built = connection::Builder::session()
+            .and_then(|b| b.name(NAME))
+            .and_then(|b| b.serve_at(PATH, application))
+            .and_then(|b| b.build());
+        match built {
+            Ok(connection) => {
+                let _ = CONNECTION.set(connection);
+            }
+            Err(e) => warn!("second launches can't reach this instance: {e}"),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    pub fn send(_args: &[String]) -> bool {
+        false
+    }
+
+    pub fn serve(_on_args: impl Fn(Vec<String>) + Send + Sync + 'static) {}
+}
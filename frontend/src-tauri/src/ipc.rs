@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Mutex,
+};
+use tauri::{AppHandle, WebviewWindow};
+use tracing::{debug, info, warn};
+
+use crate::{errorreport, redact};
+
+// Timing and outcome of every command call, to find the ones that make the UI
+// feel slow. The shell can't see when an async command's answer reaches the
+// page, so components/IpcMonitor.tsx wraps `invoke` in each window, measures
+// the round trip as the page waits for it and sends them here in batches.
+// `scope::guard` logs each call as it arrives.
+const SLOW_MS: f64 = 500.0;
+// Durations kept per command for the percentiles
+const RECENT: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct Call {
+    pub command: String,
+    pub ms: f64,
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+struct Stats {
+    count: u64,
+    errors: u64,
+    total_ms: f64,
+    max_ms: f64,
+    recent: VecDeque<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommandStats {
+    pub command: String,
+    pub count: u64,
+    pub errors: u64,
+    pub mean_ms: f64,
+    // Over the last 200 calls
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+static STATS: Mutex<BTreeMap<String, Stats>> = Mutex::new(BTreeMap::new());
+
+// A coarse class for the log, so failures can be counted without reading
+// every message.
+fn error_kind(message: &str) -> &'static str {
+    let lower = message.to_lowercase();
+    if lower.contains("is not available in this window") {
+        "refused"
+    } else if lower.contains("invalid args") || lower.contains("missing required key") {
+        "invalid_args"
+    } else if lower.contains("timed out") || lower.contains("timeout") {
+        "timeout"
+    } else if lower.contains("not found") || lower.starts_with("no ") {
+        "not_found"
+    } else {
+        "failed"
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let i = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[i]
+}
+
+#[tauri::command]
+pub fn record_ipc_calls(app: AppHandle, window: WebviewWindow, calls: Vec<Call>) {
+    let mut stats = STATS.lock().unwrap();
+    for call in calls {
+        let entry = stats.entry(call.command.clone()).or_default();
+        entry.count += 1;
+        entry.total_ms += call.ms;
+        entry.max_ms = entry.max_ms.max(call.ms);
+        entry.recent.push_back(call.ms);
+        if entry.recent.len() > RECENT {
+            entry.recent.pop_front();
+        }
+        let ms = call.ms.round() as u64;
+        match &call.error {
+            Some(message) => {
+                entry.errors += 1;
+                let message = redact::redact(&app, message);
+                warn!(
+                    command = call.command,
+                    window = window.label(),
+                    ms,
+                    kind = error_kind(&message),
+                    "command failed: {message}"
+                );
+                if !call.command.starts_with("plugin:") {
+                    errorreport::command_error(&app, &call.command, &message);
+                }
+            }
+            None if call.ms >= SLOW_MS => {
+                info!(
+                    command = call.command,
+                    window = window.label(),
+                    ms,
+                    "slow command"
+                )
+            }
+            None => debug!(
+                command = call.command,
+                window = window.label(),
+                ms,
+                "command"
+            ),
+        }
+    }
+}
+
+// Slowest first, by p95.
+#[tauri::command]
+pub fn get_ipc_stats() -> Vec<CommandStats> {
+    let stats = STATS.lock().unwrap();
+    let mut all: Vec<CommandStats> = stats
+        .iter()
+        .map(|(command, s)| {
+            let mut sorted: Vec<f64> = s.recent.iter().copied().collect();
+            sorted.sort_by(f64::total_cmp);
+            CommandStats {
+                command: command.clone(),
+                count: s.count,
+                errors: s.errors,
+                mean_ms: s.total_ms / s.count.max(1) as f64,
+                p50_ms: percentile(&sorted, 0.5),
+                p95_ms: percentile(&sorted, 0.95),
+                max_ms: s.max_ms,
+            }
+        })
+        .collect();
+    all.sort_by(|a, b| b.p95_ms.total_cmp(&a.p95_ms));
+    all
+}
+
+#[tauri::command]
+pub fn reset_ipc_stats() {
+    STATS.lock().unwrap().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_kinds() {
+        assert_eq!(
+            error_kind("save_credentials is not available in this window"),
+            "refused"
+        );
+        assert_eq!(
+            error_kind("invalid args `id` for command `forget_session`"),
+            "invalid_args"
+        );
+        assert_eq!(error_kind("operation timed out"), "timeout");
+        assert_eq!(error_kind("No crash report with that id"), "not_found");
+        assert_eq!(error_kind("disk full"), "failed");
+    }
+
+    #[test]
+    fn percentiles() {
+        assert_eq!(percentile(&[], 0.5), 0.0);
+        let sorted: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 0.5), 51.0);
+        assert_eq!(percentile(&sorted, 0.95), 95.0);
+        assert_eq!(percentile(&sorted, 1.0), 100.0);
+    }
+}
@@ -0,0 +1,537 @@
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::{io::Read, sync::LazyLock, time::Duration};
+
+use crate::net;
+
+// The shell's own look at a job posting, for when the engine can't: it's down
+// (the intake is queued with what's found here), or the site turned away its
+// fetch. A plain GET, no browser: the posting's JSON-LD `JobPosting` where the
+// site publishes one (most boards do, for search engines), otherwise the page
+// block with the most prose, readability-style, and the Open Graph tags.
+const TIMEOUT: Duration = Duration::from_secs(20);
+const MAX_BODY: u64 = 2 * 1024 * 1024;
+// What a desktop browser sends; some boards refuse anything else
+const USER_AGENT: &str =
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0 Safari/537.36";
+// Below this, the page block is more likely a teaser than the posting
+const MIN_DESCRIPTION: usize = 200;
+const MIN_PARAGRAPH: usize = 25;
+
+// Contents never shown as the posting's text
+const SKIPPED: &[&str] = &[
+    "script", "style", "noscript", "svg", "nav", "header", "footer", "aside", "form", "template",
+    "iframe", "select", "button",
+];
+const VOID: &[&str] = &[
+    "br", "img", "input", "meta", "link", "hr", "source", "wbr", "area", "base", "col", "embed",
+];
+const BLOCKS: &[&str] = &[
+    "p",
+    "li",
+    "div",
+    "section",
+    "article",
+    "main",
+    "td",
+    "tr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "ul",
+    "ol",
+    "dd",
+    "dt",
+    "blockquote",
+    "pre",
+    "table",
+];
+const CONTAINERS: &[&str] = &["div", "section", "article", "main", "td"];
+const POSITIVE: &[&str] = &[
+    "job",
+    "description",
+    "descripcion",
+    "offer",
+    "oferta",
+    "content",
+    "posting",
+    "detail",
+    "vacancy",
+    "body",
+];
+const NEGATIVE: &[&str] = &[
+    "comment", "footer", "sidebar", "nav", "cookie", "share", "related", "menu", "banner",
+    "social", "promo", "similar",
+];
+
+static TAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)<!--.*?-->|<(/?)([a-zA-Z][a-zA-Z0-9-]*)((?:[^>"']|"[^"]*"|'[^']*')*)>"#)
+        .unwrap()
+});
+static ATTR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([a-zA-Z_:][-a-zA-Z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap()
+});
+static LD_JSON: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)<script[^>]*type\s*=\s*["']?application/ld\+json["']?[^>]*>(.*?)</script>"#)
+        .unwrap()
+});
+static TITLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap());
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Posting {
+    pub url: String,
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    // "json-ld" or "page", for the log
+    pub source: &'static str,
+}
+
+pub fn fetch(url: &str) -> Result<Posting, String> {
+    let response = net::client(TIMEOUT)?
+        .get(url)
+        .header(reqwest::header::USER_AGENT, USER_AGENT)
+        .header(reqwest::header::ACCEPT, "text/html,application/xhtml+xml")
+        .send()
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("the site answered {}", response.status()));
+    }
+    let mut body = Vec::new();
+    response
+        .take(MAX_BODY)
+        .read_to_end(&mut body)
+        .map_err(|e| e.to_string())?;
+    let posting = extract(url, &String::from_utf8_lossy(&body));
+    tracing::info!(
+        source = posting.source,
+        described = posting.description.is_some(),
+        "fetched job posting"
+    );
+    Ok(posting)
+}
+
+pub fn extract(url: &str, html: &str) -> Posting {
+    let mut posting = json_ld(html).unwrap_or_else(|| Posting {
+        description: readable(html),
+        source: "page",
+        ..Default::default()
+    });
+    posting.url = url.to_string();
+    posting.title = posting
+        .title
+        .or_else(|| meta(html, "og:title"))
+        .or_else(|| TITLE.captures(html).map(|c| clean(&decode(&c[1]))))
+        .filter(|t| !t.is_empty());
+    posting.company = posting.company.or_else(|| meta(html, "og:site_name"));
+    if posting
+        .description
+        .as_ref()
+        .is_none_or(|d| d.len() < MIN_DESCRIPTION)
+    {
+        posting.description = meta(html, "og:description")
+            .or_else(|| meta(html, "description"))
+            .filter(|m| {
+                posting
+                    .description
+                    .as_ref()
+                    .is_none_or(|d| m.len() > d.len())
+            })
+            .or(posting.description);
+    }
+    posting
+}
+
+// --- JSON-LD ---
+
+fn json_ld(html: &str) -> Option<Posting> {
+    LD_JSON
+        .captures_iter(html)
+        .filter_map(|c| serde_json::from_str::<Value>(c[1].trim()).ok())
+        .find_map(|v| job_posting(&v).map(from_schema))
+}
+
+fn job_posting(v: &Value) -> Option<&Value> {
+    match v {
+        Value::Array(items) => items.iter().find_map(job_posting),
+        Value::Object(o) => {
+            let is_posting = match &o.get("@type") {
+                Some(Value::String(t)) => t == "JobPosting",
+                Some(Value::Array(ts)) => ts.iter().any(|t| t == "JobPosting"),
+                _ => false,
+            };
+            if is_posting {
+                Some(v)
+            } else {
+                o.get("@graph").and_then(job_posting)
+            }
+        }
+        _ => None,
+    }
+}
+
+fn text_of(v: &Value) -> Option<String> {
+    v.as_str()
+        .map(|s| clean(&decode(s)))
+        .filter(|s| !s.is_empty())
+}
+
+fn from_schema(v: &Value) -> Posting {
+    let org = &v["hiringOrganization"];
+    let place = match &v["jobLocation"] {
+        Value::Array(places) => places.first().cloned().unwrap_or_default(),
+        place => place.clone(),
+    };
+    let address = &place["address"];
+    Posting {
+        title: text_of(&v["title"]),
+        company: text_of(&org["name"]).or_else(|| text_of(org)),
+        location: text_of(&address["addressLocality"])
+            .or_else(|| text_of(&address["addressRegion"]))
+            .or_else(|| text_of(address)),
+        // Usually HTML itself
+        description: v["description"]
+            .as_str()
+            .map(|d| page_text(&decode(d)))
+            .filter(|d| !d.is_empty()),
+        source: "json-ld",
+        ..Default::default()
+    }
+}
+
+// --- Page ---
+
+fn attrs(raw: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    ATTR.captures_iter(raw).map(|c| {
+        let value = c
+            .get(2)
+            .or(c.get(3))
+            .or(c.get(4))
+            .map_or("", |m| m.as_str());
+        (c[1].to_lowercase(), decode(value))
+    })
+}
+
+fn meta(html: &str, name: &str) -> Option<String> {
+    TAG.captures_iter(html)
+        .filter(|c| {
+            c.get(2)
+                .is_some_and(|t| t.as_str().eq_ignore_ascii_case("meta"))
+        })
+        .find_map(|c| {
+            let attrs: Vec<_> = attrs(&c[3]).collect();
+            let named = attrs
+                .iter()
+                .any(|(k, v)| (k == "property" || k == "name") && v.eq_ignore_ascii_case(name));
+            attrs
+                .into_iter()
+                .find(|(k, _)| named && k == "content")
+                .map(|(_, v)| clean(&v))
+        })
+        .filter(|v| !v.is_empty())
+}
+
+struct Frame {
+    tag: String,
+    start: usize,
+    links: usize,
+    score: f32,
+    weight: f32,
+}
+
+// Walks the page's tags once, keeping its visible text in one buffer, each
+// open element where its text starts. Paragraphs score the containers they sit
+// in (all to the nearest, half to the next out), as readability does; the best
+// container, less its share of link text, is the posting.
+fn readable(html: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut best: Option<(f32, usize, usize)> = None;
+    let mut at = 0;
+    // Same offsets, for finding the end of skipped elements
+    let lower = html.to_ascii_lowercase();
+
+    let close = |frame: Frame, text: &str, best: &mut Option<(f32, usize, usize)>| {
+        if frame.score <= 0.0 {
+            return;
+        }
+        let len = text[frame.start..].trim().len().max(1);
+        let density = frame.links as f32 / len as f32;
+        let score = (frame.score + frame.weight) * (1.0 - density.min(1.0));
+        if best.is_none_or(|(b, _, _)| score > b) {
+            *best = Some((score, frame.start, text.len()));
+        }
+    };
+
+    while let Some(c) = TAG.captures_at(html, at) {
+        let whole = c.get(0).unwrap();
+        push_text(&mut text, &html[at..whole.start()]);
+        at = whole.end();
+        let Some(name) = c.get(2).map(|t| t.as_str().to_lowercase()) else {
+            continue;
+        };
+        let closing = &c[1] == "/";
+        if BLOCKS.contains(&name.as_str()) || name == "br" {
+            text.push('\n');
+        }
+        if closing {
+            let Some(i) = stack.iter().rposition(|f| f.tag == name) else {
+                continue;
+            };
+            // Whatever was left open inside closes with it
+            while stack.len() > i + 1 {
+                let frame = stack.pop().unwrap();
+                close(frame, &text, &mut best);
+            }
+            let frame = stack.pop().unwrap();
+            let len = text[frame.start..].trim().len();
+            match name.as_str() {
+                "a" => stack.iter_mut().for_each(|f| f.links += len),
+                "p" | "li" if len >= MIN_PARAGRAPH => {
+                    let commas = text[frame.start..].matches([',', ';']).count() as f32;
+                    let score = 1.0 + commas + (len as f32 / 100.0).min(3.0);
+                    let mut share = score;
+                    for f in stack
+                        .iter_mut()
+                        .rev()
+                        .filter(|f| CONTAINERS.contains(&f.tag.as_str()))
+                        .take(2)
+                    {
+                        f.score += share;
+                        share /= 2.0;
+                    }
+                }
+                _ => {}
+            }
+            close(frame, &text, &mut best);
+        } else if SKIPPED.contains(&name.as_str()) {
+            let end = format!("</{name}");
+            at = lower[at..].find(&end).map_or(html.len(), |i| at + i);
+        } else if !VOID.contains(&name.as_str()) && !c[3].trim_end().ends_with('/') {
+            let weight = attrs(&c[3])
+                .filter(|(k, _)| k == "class" || k == "id")
+                .map(|(_, v)| class_weight(&v.to_lowercase()))
+                .sum();
+            stack.push(Frame {
+                tag: name,
+                start: text.len(),
+                links: 0,
+                score: 0.0,
+                weight,
+            });
+        }
+    }
+    while let Some(frame) = stack.pop() {
+        close(frame, &text, &mut best);
+    }
+    best.map(|(_, start, end)| clean_lines(&text[start..end]))
+        .filter(|t| !t.is_empty())
+}
+
+fn class_weight(names: &str) -> f32 {
+    let mut weight = 0.0;
+    if POSITIVE.iter().any(|p| names.contains(p)) {
+        weight += 25.0;
+    }
+    if NEGATIVE.iter().any(|n| names.contains(n)) {
+        weight -= 25.0;
+    }
+    weight
+}
+
+fn push_text(text: &mut String, raw: &str) {
+    let decoded = decode(raw);
+    for word in decoded.split_whitespace() {
+        if !text.is_empty() && !text.ends_with([' ', '\n']) {
+            text.push(' ');
+        }
+        text.push_str(word);
+    }
+    if decoded.ends_with(char::is_whitespace) && !text.ends_with([' ', '\n']) {
+        text.push(' ');
+    }
+}
+
+// Text of an HTML fragment, a line per block
+fn page_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut at = 0;
+    while let Some(c) = TAG.captures_at(html, at) {
+        let whole = c.get(0).unwrap();
+        push_text(&mut text, &html[at..whole.start()]);
+        at = whole.end();
+        if let Some(name) = c.get(2).map(|t| t.as_str().to_lowercase()) {
+            if BLOCKS.contains(&name.as_str()) || name == "br" {
+                text.push('\n');
+            }
+        }
+    }
+    push_text(&mut text, &html[at..]);
+    clean_lines(&text)
+}
+
+fn clean(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn clean_lines(s: &str) -> String {
+    s.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn decode(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| entity(&rest[1..=end]).map(|c| (c, end + 2)));
+        match entity {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => code.parse().ok(),
+            }
+            .and_then(char::from_u32)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn fetch_job_posting(url: String) -> Result<Posting, String> {
+    tauri::async_runtime::spawn_blocking(move || fetch(&url))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAGRAPH: &str = "Buscamos una persona para atender a los clientes en caja, reponer el \
+        producto y mantener la tienda en orden durante el turno de mañana.";
+
+    #[test]
+    fn entities() {
+        assert_eq!(
+            decode("Caf&eacute; &amp; t&#233; &#x41;&lt;b&gt;&nbsp;&bogus; & fin"),
+            "Caf&eacute; & té A<b> &bogus; & fin"
+        );
+        assert_eq!(decode("no entities"), "no entities");
+    }
+
+    #[test]
+    fn fragments_as_lines() {
+        assert_eq!(
+            page_text(
+                "<p>Uno <b>dos</b></p><ul><li>tres</li><li>cuatro&amp;</li></ul>cinco<br>seis"
+            ),
+            "Uno dos\ntres\ncuatro&\ncinco\nseis"
+        );
+    }
+
+    #[test]
+    fn meta_tags_by_property_or_name() {
+        let html = r#"<meta property="og:title" content="Cajero/a &amp; reponedor">
+            <META name='description' content='  Turno   de mañana '>"#;
+        assert_eq!(
+            meta(html, "og:title").as_deref(),
+            Some("Cajero/a & reponedor")
+        );
+        assert_eq!(
+            meta(html, "description").as_deref(),
+            Some("Turno de mañana")
+        );
+        assert_eq!(meta(html, "og:site_name"), None);
+    }
+
+    #[test]
+    fn json_ld_posting_first() {
+        let html = r#"<html><head><title>Ignored</title>
+            <script type="application/ld+json">{"@type": "WebSite"}</script>
+            <script type="application/ld+json">{"@graph": [{"@type": ["JobPosting"],
+                "title": "Cajero/a", "hiringOrganization": {"name": "Mercadona"},
+                "jobLocation": [{"address": {"addressLocality": "Valencia"}}],
+                "description": "&lt;p&gt;Atención al cliente&lt;/p&gt;&lt;p&gt;Caja&lt;/p&gt;"}]}
+            </script></head></html>"#;
+        let posting = extract("https://jobs.example/1", html);
+        assert_eq!(posting.source, "json-ld");
+        assert_eq!(posting.url, "https://jobs.example/1");
+        assert_eq!(posting.title.as_deref(), Some("Cajero/a"));
+        assert_eq!(posting.company.as_deref(), Some("Mercadona"));
+        assert_eq!(posting.location.as_deref(), Some("Valencia"));
+        assert_eq!(
+            posting.description.as_deref(),
+            Some("Atención al cliente\nCaja")
+        );
+    }
+
+    #[test]
+    fn readable_block_over_the_rest() {
+        let html = format!(
+            r#"<html><head><title> Cajero - Tienda </title>
+            <meta property="og:site_name" content="Tienda"></head><body>
+            <nav><a href="/">Inicio</a> <a href="/ofertas">Ofertas</a></nav>
+            <div class="sidebar related"><p>Otras ofertas que te pueden interesar en tu zona hoy</p></div>
+            <div class="job-description"><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p></div>
+            <footer>Aviso legal y cookies de este sitio web</footer></body></html>"#
+        );
+        let posting = extract("https://jobs.example/2", &html);
+        assert_eq!(posting.source, "page");
+        assert_eq!(posting.title.as_deref(), Some("Cajero - Tienda"));
+        assert_eq!(posting.company.as_deref(), Some("Tienda"));
+        let description = posting.description.unwrap();
+        assert_eq!(description, [PARAGRAPH; 3].join("\n"));
+    }
+
+    #[test]
+    fn short_text_gives_way_to_the_meta_description() {
+        let html = r#"<title>Oferta</title>
+            <meta name="description" content="Cajero/a en Valencia, jornada completa, incorporación inmediata">
+            <div class="content"><p>Cajero/a en Valencia, ya.</p></div>"#;
+        let posting = extract("https://jobs.example/3", html);
+        assert_eq!(
+            posting.description.as_deref(),
+            Some("Cajero/a en Valencia, jornada completa, incorporación inmediata")
+        );
+        assert_eq!(extract("https://jobs.example/4", "").description, None);
+    }
+}
@@ -0,0 +1,184 @@
+use std::thread;
+use tauri::AppHandle;
+
+use crate::automation;
+
+// The taskbar button's jump list on Windows: tasks to open the dashboard,
+// pause or resume automation and start a new application (the Jobs page),
+// and a Recent Applications category. Each entry launches the app again with
+// one of the arguments below; the running instance receives them through
+// `instance` and `on_args` acts on them. Rebuilt whenever the Dock menu is.
+pub const TASK_ARG: &str = "--task=";
+const APPLICATION_ARG: &str = "--application=";
+
+// Before any window exists, so the taskbar button, the jump list and the
+// installer's shortcuts share one identity.
+pub fn set_app_id() {
+    platform::set_app_id();
+}
+
+pub fn refresh() {
+    if cfg!(windows) {
+        thread::spawn(platform::rebuild);
+    }
+}
+
+// Whether `args` held a jump list entry.
+pub fn on_args(app: &AppHandle, args: &[String]) -> bool {
+    let mut handled = false;
+    for arg in args {
+        if let Some(task) = arg.strip_prefix(TASK_ARG) {
+            match task {
+                "dashboard" => automation::open_route(app, "/"),
+                "pause" => automation::set_paused(true),
+                "resume" => automation::set_paused(false),
+                "new-application" => automation::open_route(app, "/jobs"),
+                _ => continue,
+            }
+            handled = true;
+        } else if let Some(id) = arg
+            .strip_prefix(APPLICATION_ARG)
+            .and_then(|id| id.parse().ok())
+        {
+            automation::open_application(app, id);
+            handled = true;
+        }
+    }
+    handled
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::collections::HashSet;
+    use tracing::warn;
+    use windows::{
+        core::{Interface, Result, HSTRING},
+        Win32::{
+            Storage::EnhancedStorage::PKEY_Title,
+            System::Com::{
+                CoCreateInstance, CoInitializeEx, StructuredStorage::PROPVARIANT,
+                CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+            },
+            UI::Shell::{
+                Common::{IObjectArray, IObjectCollection},
+                DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW,
+                PropertiesSystem::IPropertyStore,
+                SetCurrentProcessExplicitAppUserModelID, ShellLink,
+            },
+        },
+    };
+
+    use super::{APPLICATION_ARG, TASK_ARG};
+    use crate::{automation, i18n::t};
+
+    // tauri.conf.json `identifier`, which the installer gives its shortcuts
+    const APP_ID: &str = "com.currobot.app";
+    const RECENT_COUNT: usize = 5;
+
+    pub fn set_app_id() {
+        if let Err(e) = unsafe { SetCurrentProcessExplicitAppUserModelID(&HSTRING::from(APP_ID)) } {
+            warn!("app id not set: {e}");
+        }
+    }
+
+    fn link(exe: &HSTRING, args: &str, title: &str) -> Result<IShellLinkW> {
+        unsafe {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+            link.SetPath(exe)?;
+            link.SetArguments(&HSTRING::from(args))?;
+            link.SetIconLocation(exe, 0)?;
+            let store: IPropertyStore = link.cast()?;
+            store.SetValue(&PKEY_Title, &PROPVARIANT::from(title))?;
+            store.Commit()?;
+            Ok(link)
+        }
+    }
+
+    fn collection() -> Result<IObjectCollection> {
+        unsafe { CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER) }
+    }
+
+    // Entries the user removed from the list must not be added back.
+    fn removed_args(removed: &IObjectArray) -> Result<HashSet<String>> {
+        let mut args = HashSet::new();
+        unsafe {
+            for i in 0..removed.GetCount()? {
+                let Ok(link) = removed.GetAt::<IShellLinkW>(i) else {
+                    continue;
+                };
+                let mut buf = [0u16; 260];
+                link.GetArguments(&mut buf)?;
+                let len = buf.iter().position(|c| *c == 0).unwrap_or(buf.len());
+                args.insert(String::from_utf16_lossy(&buf[..len]));
+            }
+        }
+        Ok(args)
+    }
+
+    fn commit(exe: &HSTRING, paused: Option<bool>, recent: &[(i64, String)]) -> Result<()> {
+        unsafe {
+            let list: ICustomDestinationList =
+                CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+            list.SetAppID(&HSTRING::from(APP_ID))?;
+            let mut slots = 0u32;
+            let removed = removed_args(&list.BeginList::<IObjectArray>(&mut slots)?)?;
+
+            let items = collection()?;
+            for (id, label) in recent {
+                let args = format!("{APPLICATION_ARG}{id}");
+                if !removed.contains(&args) {
+                    items.AddObject(&link(exe, &args, label)?)?;
+                }
+            }
+            if items.GetCount()? > 0 {
+                list.AppendCategory(
+                    &HSTRING::from(t("automation-recent")),
+                    &items.cast::<IObjectArray>()?,
+                )?;
+            }
+
+            let tasks = collection()?;
+            tasks.AddObject(&link(
+                exe,
+                &format!("{TASK_ARG}dashboard"),
+                &t("jumplist-dashboard"),
+            )?)?;
+            // Left out while the engine doesn't answer
+            if let Some(paused) = paused {
+                let (task, title) = if paused {
+                    ("resume", "automation-resume")
+                } else {
+                    ("pause", "automation-pause")
+                };
+                tasks.AddObject(&link(exe, &format!("{TASK_ARG}{task}"), &t(title))?)?;
+            }
+            tasks.AddObject(&link(
+                exe,
+                &format!("{TASK_ARG}new-application"),
+                &t("jumplist-new-application"),
+            )?)?;
+            list.AddUserTasks(&tasks.cast::<IObjectArray>()?)?;
+            list.CommitList()
+        }
+    }
+
+    // From a worker thread.
+    pub fn rebuild() {
+        let Ok(exe) = std::env::current_exe() else {
+            return;
+        };
+        let paused = automation::paused();
+        let recent = automation::recent(RECENT_COUNT);
+        let _ = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+        if let Err(e) = commit(&HSTRING::from(exe.as_os_str()), paused, &recent) {
+            warn!("jump list not updated: {e}");
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    pub fn set_app_id() {}
+
+    pub fn rebuild() {}
+}
@@ -0,0 +1,181 @@
+use std::{collections::HashSet, sync::Mutex};
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::{i18n::t, settings};
+
+// Keeps the machine from sleeping while the engine has a run going
+// (`progress`), so an overnight batch isn't cut off halfway; the display may
+// still turn off. `power.keep_awake` in settings.json, on by default. macOS
+// takes an IOPMAssertion, Windows SetThreadExecutionState and Linux a
+// logind sleep inhibitor, each released when the last run ends.
+struct KeepAwake {
+    runs: HashSet<String>,
+    held: Option<platform::Assertion>,
+}
+
+static STATE: Mutex<Option<KeepAwake>> = Mutex::new(None);
+
+fn with(app: &AppHandle, change: impl FnOnce(&mut HashSet<String>)) {
+    let mut guard = STATE.lock().unwrap();
+    let state = guard.get_or_insert_with(|| KeepAwake {
+        runs: HashSet::new(),
+        held: None,
+    });
+    change(&mut state.runs);
+    let wanted = !state.runs.is_empty() && settings::get(app).power.keep_awake;
+    if wanted && state.held.is_none() {
+        match platform::Assertion::take(&t("keep-awake-reason")) {
+            Ok(assertion) => {
+                info!("keeping the system awake");
+                state.held = Some(assertion);
+            }
+            Err(e) => warn!("system sleep not held off: {e}"),
+        }
+    } else if !wanted && state.held.take().is_some() {
+        info!("system may sleep again");
+    }
+}
+
+pub fn on_progress(app: &AppHandle, run_id: &str) {
+    with(app, |runs| {
+        runs.insert(run_id.to_string());
+    });
+}
+
+pub fn on_finished(app: &AppHandle, run_id: &str) {
+    with(app, |runs| {
+        runs.remove(run_id);
+    });
+}
+
+// When the event stream (re)connects: runs of an engine that went away never
+// finish. Ones still going are picked up again from their next progress event.
+pub fn on_connected(app: &AppHandle) {
+    with(app, |runs| runs.clear());
+}
+
+// When the setting changes.
+pub fn refresh(app: &AppHandle) {
+    with(app, |_| {});
+}
+
+#[tauri::command]
+pub fn get_keep_awake(app: AppHandle) -> bool {
+    settings::get(&app).power.keep_awake
+}
+
+#[tauri::command]
+pub fn set_keep_awake(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update(&app, |s| s.power.keep_awake = enabled)?;
+    refresh(&app);
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2_foundation::NSString;
+    use std::ffi::c_void;
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOPMAssertionCreateWithName(
+            assertion_type: *const c_void,
+            level: u32,
+            name: *const c_void,
+            id: *mut u32,
+        ) -> i32;
+        fn IOPMAssertionRelease(id: u32) -> i32;
+    }
+
+    const LEVEL_ON: u32 = 255;
+
+    pub struct Assertion(u32);
+
+    impl Assertion {
+        pub fn take(reason: &str) -> Result<Self, String> {
+            // NSString and CFString are toll-free bridged
+            let kind = NSString::from_str("PreventUserIdleSystemSleep");
+            let name = NSString::from_str(reason);
+            let mut id = 0;
+            let status = unsafe {
+                IOPMAssertionCreateWithName(
+                    &*kind as *const NSString as *const c_void,
+                    LEVEL_ON,
+                    &*name as *const NSString as *const c_void,
+                    &mut id,
+                )
+            };
+            if status == 0 {
+                Ok(Self(id))
+            } else {
+                Err(format!("IOPMAssertionCreateWithName returned {status:#x}"))
+            }
+        }
+    }
+
+    impl Drop for Assertion {
+        fn drop(&mut self) {
+            unsafe { IOPMAssertionRelease(self.0) };
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{
+        sync::mpsc::{self, Sender},
+        thread,
+    };
+    use windows::Win32::System::Power::{
+        SetThreadExecutionState, ES_CONTINUOUS, ES_SYSTEM_REQUIRED,
+    };
+
+    // The request belongs to the thread that made it, so one is kept waiting
+    // until the assertion is dropped.
+    pub struct Assertion(#[allow(dead_code)] Sender<()>);
+
+    impl Assertion {
+        pub fn take(_reason: &str) -> Result<Self, String> {
+            let (release, released) = mpsc::channel::<()>();
+            let (started, taken) = mpsc::channel();
+            thread::spawn(move || {
+                let previous =
+                    unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED) };
+                let _ = started.send(previous.0 != 0);
+                // Until the sender is dropped
+                let _ = released.recv();
+                unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+            });
+            match taken.recv() {
+                Ok(true) => Ok(Self(release)),
+                _ => Err("SetThreadExecutionState failed".into()),
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use zbus::{blocking::Connection, zvariant::OwnedFd};
+
+    // logind lifts the inhibitor when the descriptor is closed.
+    pub struct Assertion(#[allow(dead_code)] OwnedFd);
+
+    impl Assertion {
+        pub fn take(reason: &str) -> Result<Self, String> {
+            let connection = Connection::system().map_err(|e| e.to_string())?;
+            let reply = connection
+                .call_method(
+                    Some("org.freedesktop.login1"),
+                    "/org/freedesktop/login1",
+                    Some("org.freedesktop.login1.Manager"),
+                    "Inhibit",
+                    &("sleep", "currobot", reason, "block"),
+                )
+                .map_err(|e| e.to_string())?;
+            let fd: OwnedFd = reply.body().deserialize().map_err(|e| e.to_string())?;
+            Ok(Self(fd))
+        }
+    }
+}
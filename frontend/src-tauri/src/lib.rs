@@ -1,62 +1,239 @@
 use std::sync::Mutex;
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Manager, RunEvent,
+    AppHandle, Manager, RunEvent, Wry,
 };
 use tauri_plugin_shell::{process::CommandChild, ShellExt};
+use tracing::{debug, error, info, warn};
+
+use logging::TRAY_TARGET;
+
+mod activity;
+mod alerts;
+mod applock;
+mod appmenu;
+mod audit;
+mod automation;
+mod backend;
+mod backup;
+mod browserprofiles;
+mod calendar;
+mod clipboard;
+mod clipwatch;
+mod cookievault;
+mod crashreport;
+mod datadir;
+mod dataexport;
+mod datapacks;
+mod debugmode;
+mod digest;
+mod dispatcher;
+mod dock;
+mod documents;
+mod drivers;
+mod duplicates;
+mod errorreport;
+mod events;
+mod evidence;
+mod export;
+mod flags;
+mod health;
+mod history;
+mod historycrypt;
+mod historyimport;
+mod i18n;
+mod instance;
+mod ipc;
+mod jobfetch;
+mod jumplist;
+mod keepawake;
+mod logging;
+mod loginitem;
+mod logretention;
+mod mailwatch;
+mod menubar;
+mod metrics;
+mod nativehost;
+mod net;
+mod notifications;
+mod oauth;
+mod offlinequeue;
+mod onboarding;
+mod overrides;
+mod paths;
+mod pdf;
+mod pdftext;
+mod pipeline;
+mod profiles;
+mod progress;
+mod proxy;
+mod proxypool;
+mod quarantine;
+mod quotas;
+mod redact;
+mod reminders;
+mod reset;
+mod resume;
+mod retention;
+mod salary;
+mod schedule;
+mod scope;
+mod search;
+mod settings;
+mod sidecar;
+mod sidecar_env;
+mod signing;
+mod sleepwake;
+mod spreadsheet;
+mod stats;
+mod sync;
+mod sysinfo;
+mod tags;
+mod taskbar;
+mod telemetry;
+mod templates;
+mod toast;
+mod trayhost;
+mod trayicon;
+mod tz;
+mod uninstall;
+mod updater;
+mod vault;
+mod wayland;
+mod webhooks;
+mod workhours;
+
+pub(crate) const TRAY_ID: &str = "main";
+
+// Passed by the login item so login-time launches stay in the tray.
+pub(crate) const AUTOSTART_ARG: &str = "--autostart";
 
 struct BackendState(Mutex<Option<CommandChild>>);
 
 fn spawn_backend(app: &AppHandle) -> Result<CommandChild, String> {
-    let (_, child) = app
-        .shell()
-        .sidecar("jobbot-backend")
-        .map_err(|e| e.to_string())?
-        .spawn()
-        .map_err(|e| e.to_string())?;
+    // A separately updated backend takes precedence over the bundled sidecar
+    let mut command = match sidecar::verified_binary(app)? {
+        Some(path) => app.shell().command(path),
+        None => app
+            .shell()
+            .sidecar("jobbot-backend")
+            .map_err(|e| e.to_string())?,
+    };
+    command = command.env_clear().envs(sidecar_env::inherited());
+    if let Ok(dir) = datapacks::root(app) {
+        command = command.env(datapacks::DATA_PACKS_ENV, dir);
+    }
+    // The engine's database, logs and browser profiles follow the active
+    // profile (and portable mode / JOBBOT_DATA_DIR)
+    command = command.env("JOBBOT_DATA_DIR", paths::engine_dir(app)?);
+    if let Ok(dir) = documents::root(app) {
+        command = command.env(documents::DOCUMENTS_ENV, dir);
+    }
+    if let Ok(dir) = browserprofiles::root(app) {
+        command = command.env(browserprofiles::BROWSER_PROFILES_ENV, dir);
+    }
+    // backend/config.py reads PORT through pydantic-settings
+    command = command.env("PORT", overrides::backend_port().to_string());
+    command = command.env(backend::TOKEN_ENV, backend::token());
+    match proxy::current() {
+        proxy::Resolved::Url(url) => {
+            command = command
+                .env("HTTP_PROXY", &url)
+                .env("HTTPS_PROXY", &url)
+                .env("NO_PROXY", "127.0.0.1,localhost");
+        }
+        proxy::Resolved::Direct => {
+            command = command
+                .env("HTTP_PROXY", "")
+                .env("HTTPS_PROXY", "")
+                .env("NO_PROXY", "*");
+        }
+        proxy::Resolved::Auto => {}
+    }
+    command = command.envs(vault::backend_env(app));
+    command = command.envs(redact::backend_env(app));
+    command = command.envs([cookievault::backend_env()]);
+    command = command.envs([proxypool::backend_env()]);
+    command = command.envs(logretention::backend_env(app));
+    command = command.envs(debugmode::backend_env());
+    command = command.envs(drivers::backend_env(app));
+    let (output, child) = command.spawn().map_err(|e| e.to_string())?;
+    debugmode::forward_output(output);
+    metrics::engine_started();
+    crashreport::note_engine_version(
+        sidecar::active_version(app).unwrap_or_else(|| app.package_info().version.to_string()),
+    );
+    info!(pid = child.pid(), "engine started");
     Ok(child)
 }
 
-fn show_window(app: &AppHandle) {
+pub(crate) fn restart_backend(app: &AppHandle) -> Result<(), String> {
+    kill_backend(app);
+    let child = spawn_backend(app)?;
+    *app.state::<BackendState>().0.lock().unwrap() = Some(child);
+    Ok(())
+}
+
+pub(crate) fn show_window(app: &AppHandle) {
+    if applock::is_locked(app) {
+        applock::unlock_then_show(app);
+        return;
+    }
     if let Some(w) = app.get_webview_window("main") {
+        menubar::on_shown(app);
         let _ = w.show();
         let _ = w.set_focus();
     }
+    notifications::clear_unread(app);
 }
 
-fn kill_backend(app: &AppHandle) {
+pub(crate) fn backend_pid(app: &AppHandle) -> Option<u32> {
+    app.state::<BackendState>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(CommandChild::pid)
+}
+
+pub(crate) fn kill_backend(app: &AppHandle) {
     let child = app.state::<BackendState>().0.lock().unwrap().take();
     if let Some(c) = child {
+        info!(pid = c.pid(), "engine stopped");
         let _ = c.kill();
     }
 }
 
+// Off the main thread: it may wait on an administrator prompt.
 fn toggle_autolaunch(app: &AppHandle) {
-    use tauri_plugin_autostart::ManagerExt;
-    let al = app.autolaunch();
-    if al.is_enabled().unwrap_or(false) {
-        let _ = al.disable();
-    } else {
-        let _ = al.enable();
-    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let result = if loginitem::is_enabled(&app) {
+            loginitem::disable(&app)
+        } else {
+            loginitem::enable(&app)
+        };
+        match result {
+            Ok(()) => refresh_tray_menu(&app),
+            Err(e) => warn!("start on login not changed: {e}"),
+        }
+    });
 }
 
 // Disables autolaunch, notifies the user, then quits cleanly.
-// Called from the "Uninstall JobBot..." tray item so no dead login item remains.
+// Last step of the uninstall window (opened from the "Uninstall JobBot..."
+// tray item) so no dead login item remains.
 fn handle_uninstall(app: &AppHandle) {
-    use tauri_plugin_autostart::ManagerExt;
     use tauri_plugin_notification::NotificationExt;
-    let _ = app.autolaunch().disable();
+    let _ = loginitem::disable(app);
+    toast::unregister();
+    nativehost::unregister();
     let _ = app
         .notification()
         .builder()
         .title("JobBot")
-        .body(
-            "Autolaunch disabled. \
-             macOS: move JobBot.app to Trash. \
-             Windows: use Add/Remove Programs.",
-        )
+        .body(i18n::t("uninstall-body"))
         .show();
     kill_backend(app);
     app.exit(0);
@@ -64,59 +241,204 @@ fn handle_uninstall(app: &AppHandle) {
 
 #[tauri::command]
 fn get_autolaunch_enabled(app: AppHandle) -> bool {
-    use tauri_plugin_autostart::ManagerExt;
-    app.autolaunch().is_enabled().unwrap_or(false)
+    loginitem::is_enabled(&app)
 }
 
 #[tauri::command]
-fn set_autolaunch(app: AppHandle, enabled: bool) -> Result<(), String> {
-    use tauri_plugin_autostart::ManagerExt;
-    if enabled {
-        app.autolaunch().enable().map_err(|e| e.to_string())
-    } else {
-        app.autolaunch().disable().map_err(|e| e.to_string())
+async fn set_autolaunch(app: AppHandle, enabled: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if enabled {
+            loginitem::enable(&app)
+        } else {
+            loginitem::disable(&app)
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Called from the Settings page and the uninstall window — disables autolaunch
+// so the user can safely delete the app without leaving a dead login item
+// behind. `wipe` also deletes everything listed by `get_wipe_plan`.
+#[tauri::command]
+fn cleanup_for_uninstall(app: AppHandle, wipe: bool) -> Result<(), String> {
+    let _ = loginitem::disable(&app);
+    audit::record(
+        &app,
+        audit::Action::UninstallCleanup,
+        serde_json::json!({ "wipe": wipe }),
+    );
+    if wipe {
+        if let Err(e) = uninstall::wipe(&app) {
+            // Bring the engine back so the app stays usable
+            let _ = restart_backend(&app);
+            return Err(e);
+        }
     }
+    Ok(())
 }
 
-// Called from the Settings page — disables autolaunch so the user can safely
-// delete the app without leaving a dead login item behind.
 #[tauri::command]
-fn cleanup_for_uninstall(app: AppHandle) {
-    use tauri_plugin_autostart::ManagerExt;
-    let _ = app.autolaunch().disable();
+fn quit_for_uninstall(app: AppHandle) {
+    handle_uninstall(&app);
 }
 
-fn build_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    use tauri_plugin_autostart::ManagerExt;
-    let autolaunch_on = app.autolaunch().is_enabled().unwrap_or(false);
+// Rebuilt from current state whenever something it reflects changes.
+fn tray_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let autolaunch_on = loginitem::is_enabled(app);
 
-    let open = MenuItem::with_id(app, "open", "Open JobBot", true, None::<&str>)?;
+    let open = MenuItem::with_id(app, "open", i18n::t("tray-open"), true, None::<&str>)?;
     let autolaunch = MenuItem::with_id(
         app,
         "autolaunch",
-        if autolaunch_on { "✓  Start on Login" } else { "Start on Login" },
+        i18n::t(if autolaunch_on {
+            "tray-start-on-login-checked"
+        } else {
+            "tray-start-on-login"
+        }),
         true,
         None::<&str>,
     )?;
+    let list = profiles::list(app);
+    let profile_items = list
+        .profiles
+        .iter()
+        .map(|p| {
+            CheckMenuItem::with_id(
+                app,
+                format!("profile:{}", p.id),
+                &p.name,
+                true,
+                p.id == list.active,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let profile_refs: Vec<&dyn tauri::menu::IsMenuItem<Wry>> =
+        profile_items.iter().map(|i| i as _).collect();
+    let profile_menu = Submenu::with_id_and_items(
+        app,
+        "profiles",
+        i18n::t("tray-profile"),
+        true,
+        &profile_refs,
+    )?;
+    let debug_toggle = debugmode::tray_toggle(app)?;
+    let developer = debugmode::tray_menu(app)?;
     let sep1 = PredefinedMenuItem::separator(app)?;
-    let uninstall = MenuItem::with_id(app, "uninstall", "Uninstall JobBot...", true, None::<&str>)?;
+    let uninstall = MenuItem::with_id(
+        app,
+        "uninstall",
+        i18n::t("tray-uninstall"),
+        true,
+        None::<&str>,
+    )?;
     let sep2 = PredefinedMenuItem::separator(app)?;
-    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&open, &autolaunch, &sep1, &uninstall, &sep2, &quit])?;
+    let quit = MenuItem::with_id(app, "quit", i18n::t("tray-quit"), true, None::<&str>)?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &open,
+            &autolaunch,
+            &profile_menu,
+            &sep1,
+            &uninstall,
+            &sep2,
+            &quit,
+        ],
+    )?;
+    if paths::is_portable() {
+        menu.remove(&autolaunch)?;
+    }
+    // Before the separator above Uninstall
+    let at = menu.items()?.len() - 4;
+    if let Some(developer) = &developer {
+        menu.insert(developer, at)?;
+    }
+    if let Some(toggle) = &debug_toggle {
+        menu.insert(toggle, at)?;
+    }
+    let working_hours = workhours::tray_item()
+        .map(|(id, label)| MenuItem::with_id(app, id, label, true, None::<&str>))
+        .transpose()?;
+    if let Some(item) = &working_hours {
+        menu.insert(item, at)?;
+    }
+
+    if updater::is_ready(app) {
+        let update = MenuItem::with_id(
+            app,
+            "update",
+            i18n::t("tray-update-ready"),
+            true,
+            None::<&str>,
+        )?;
+        menu.prepend_items(&[&update, &PredefinedMenuItem::separator(app)?])?;
+    }
+    Ok(menu)
+}
 
-    TrayIconBuilder::new()
-        .icon(app.default_window_icon().unwrap().clone())
+pub(crate) fn refresh_tray_menu(app: &AppHandle) {
+    // Same labels, same language
+    appmenu::refresh(app);
+    dock::refresh(app);
+    jumplist::refresh();
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match tray_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => warn!(target: TRAY_TARGET, "tray menu rebuild failed: {e}"),
+    }
+}
+
+fn switch_from_tray(app: &AppHandle, id: &str) {
+    if let Err(e) = profiles::switch(app, id) {
+        warn!(target: TRAY_TARGET, "profile switch failed: {e}");
+    }
+    // The check mark moved (or must be put back on failure)
+    refresh_tray_menu(app);
+}
+
+fn build_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = tray_menu(app.handle())?;
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(trayicon::icon(app.handle()))
         .menu(&menu)
         .show_menu_on_left_click(false)
-        .on_menu_event(|app, event| match event.id.as_ref() {
-            "open" => show_window(app),
-            "autolaunch" => toggle_autolaunch(app),
-            "uninstall" => handle_uninstall(app),
-            "quit" => {
-                kill_backend(app);
-                app.exit(0);
+        .on_menu_event(|app, event| {
+            debug!(target: TRAY_TARGET, item = event.id.as_ref(), "tray menu");
+            match event.id.as_ref() {
+                "open" => show_window(app),
+                "autolaunch" => toggle_autolaunch(app),
+                "uninstall" => uninstall::open_window(app),
+                "update" => updater::show_release_notes(app),
+                id if id.starts_with("app:") => appmenu::on_menu_event(app, id),
+                id if id.starts_with("dock:") => dock::on_menu_event(app, id),
+                id if id.starts_with("workhours:") => workhours::on_menu_event(app, id),
+                id if id == "debug_mode" || id.starts_with("dev:") => {
+                    debugmode::on_menu_event(app, id)
+                }
+                id if id.starts_with("profile:") => {
+                    let id = id["profile:".len()..].to_string();
+                    // While locked the switch waits for the unlock, and the
+                    // check mark goes back meanwhile
+                    let later = id.clone();
+                    if applock::gate(app, move |app| switch_from_tray(app, &later)) {
+                        switch_from_tray(app, &id);
+                    } else {
+                        refresh_tray_menu(app);
+                    }
+                }
+                "quit" => {
+                    kill_backend(app);
+                    app.exit(0);
+                }
+                _ => {}
             }
-            _ => {}
         })
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click {
@@ -134,44 +456,403 @@ fn build_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
 }
 
 pub fn run() {
+    // Started by a browser for the extension: the host, not the app
+    if nativehost::is_host_launch() {
+        nativehost::serve_stdio();
+        return;
+    }
+    wayland::init();
+    if instance::forward() {
+        return;
+    }
+    jumplist::set_app_id();
+    metrics::init();
+    logging::init();
+    crashreport::init();
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
-            None,
+            Some(vec![AUTOSTART_ARG]),
         ))
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        // Lets the frontend follow JOBBOT_BACKEND_PORT (see lib/api.ts)
+        .plugin(
+            tauri::plugin::Builder::<Wry>::new("jobbot-env")
+                .js_init_script(format!(
+                    "window.__JOBBOT_BACKEND_URL__ = {:?}; window.__JOBBOT_BACKEND_TOKEN__ = {:?};",
+                    overrides::backend_url(),
+                    backend::token()
+                ))
+                .build(),
+        )
         .manage(BackendState(Mutex::new(None)))
+        .manage(progress::ProgressState::default())
+        .manage(notifications::NotificationState::default())
+        .manage(dispatcher::DispatcherState::default())
+        .manage(updater::UpdaterState::default())
+        .manage(export::PendingImport::default())
+        .manage(sync::SyncState::default())
+        .manage(applock::AppLockState::default())
         .setup(|app| {
             let handle = app.handle().clone();
+            datadir::init(&handle);
+            profiles::init(&handle);
+            app.manage(settings::SettingsState::load(&handle));
+            crashreport::attach(&handle);
+            logging::attach(&handle);
+            debugmode::refresh(&handle);
+            i18n::apply(&handle);
+            flags::init(&handle);
+            proxy::refresh(&handle);
             match spawn_backend(&handle) {
                 Ok(child) => {
                     *app.state::<BackendState>().0.lock().unwrap() = Some(child);
                 }
                 Err(e) => {
-                    eprintln!("[jobbot] backend spawn failed: {e}");
+                    error!("backend spawn failed: {e}");
                 }
             }
             build_tray(app)?;
+            toast::register(handle.clone());
+            dock::init(&handle);
+            metrics::tray_ready();
+            // The main window starts hidden (tauri.conf.json); only manual
+            // launches bring it up, or onboarding until that's been finished.
+            // Without a tray to open it from, login launches do too.
+            if !trayhost::available() {
+                warn!(target: TRAY_TARGET, "no tray host; the window stays reachable");
+            }
+            if !std::env::args().any(|a| a == AUTOSTART_ARG) || !trayhost::can_hide(&handle) {
+                if onboarding::is_complete(&handle) {
+                    show_window(&handle);
+                } else {
+                    onboarding::open_window(&handle);
+                }
+            }
+            menubar::refresh(&handle);
+            // Login items created before AUTOSTART_ARG existed launch without
+            // it; re-registering rewrites them with the current arguments.
+            loginitem::reregister(&handle);
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            instance::on_args(&handle, &args, false);
+            instance::listen(handle.clone());
+            telemetry::start(handle.clone());
+            let h = handle.clone();
+            std::thread::spawn(move || {
+                notifications::refresh_permission(&h);
+                settings::report_warnings(&h);
+                crashreport::announce(&h);
+            });
+            settings::watch(handle.clone());
+            dispatcher::start(handle.clone());
+            events::start_bridge(handle.clone());
+            sleepwake::start(handle.clone());
+            digest::start_scheduler(handle.clone());
+            schedule::start(handle.clone());
+            flags::start(handle.clone());
+            sync::start(handle.clone());
+            applock::start(handle.clone());
+            logretention::start(handle.clone());
+            proxypool::start(handle.clone());
+            offlinequeue::start(handle.clone());
+            mailwatch::start(handle.clone());
+            reminders::start(handle.clone());
+            webhooks::start(handle.clone());
+            backup::start(handle.clone());
+            quotas::start(handle.clone());
+            evidence::start(handle.clone());
+            clipwatch::start(handle.clone());
+            nativehost::start(handle.clone());
+            salary::start(handle.clone());
+            alerts::start(handle.clone());
+            activity::start(handle.clone());
+            retention::start(handle.clone());
+            workhours::start(handle.clone());
+            if !paths::is_portable() {
+                quarantine::sweep(&handle);
+                updater::start(handle.clone());
+                sidecar::start(handle.clone());
+                drivers::start(handle.clone());
+                datapacks::start(handle);
+            }
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(scope::guard(tauri::generate_handler![
             get_autolaunch_enabled,
             set_autolaunch,
             cleanup_for_uninstall,
-        ])
+            quit_for_uninstall,
+            uninstall::get_wipe_plan,
+            applock::get_app_lock,
+            applock::set_app_lock,
+            applock::lock_app_now,
+            applock::app_lock_activity,
+            audit::get_audit_log,
+            audit::export_audit_log,
+            redact::get_redaction,
+            redact::set_redaction,
+            redact::preview_redaction,
+            clipboard::copy_credential,
+            cookievault::list_sessions,
+            cookievault::forget_session,
+            logging::get_recent_logs,
+            logging::get_recent_events,
+            logging::get_log_levels,
+            logging::set_log_level,
+            crashreport::list_crash_reports,
+            crashreport::send_crash_report,
+            crashreport::delete_crash_report,
+            logretention::get_log_usage,
+            logretention::set_log_retention,
+            telemetry::get_telemetry,
+            metrics::get_metrics,
+            sysinfo::get_system_info,
+            health::get_health_report,
+            ipc::record_ipc_calls,
+            ipc::get_ipc_stats,
+            ipc::reset_ipc_stats,
+            debugmode::get_debug_mode,
+            debugmode::set_debug_mode,
+            metrics::mark_first_paint,
+            telemetry::set_telemetry,
+            errorreport::get_error_reporting,
+            errorreport::set_error_reporting,
+            errorreport::test_error_reporting,
+            digest::get_digest_settings,
+            digest::set_digest_settings,
+            digest::send_digest_now,
+            notifications::get_notification_permission,
+            notifications::clear_in_app_notifications,
+            updater::check_for_update,
+            updater::install_update,
+            updater::get_release_notes,
+            updater::open_release_notes,
+            updater::get_update_status,
+            updater::get_previous_version,
+            updater::rollback_update,
+            updater::snooze_update,
+            updater::get_update_channel,
+            updater::set_update_channel,
+            sidecar::get_backend_version_info,
+            sidecar::check_backend_update,
+            sidecar::rollback_backend,
+            sidecar::pin_backend_version,
+            datapacks::get_data_packs,
+            datapacks::check_data_packs,
+            paths::is_portable_mode,
+            overrides::get_effective_config,
+            proxy::get_proxy_settings,
+            proxy::set_proxy_settings,
+            export::export_settings,
+            export::import_settings,
+            export::take_pending_import,
+            profiles::list_profiles,
+            profiles::create_profile,
+            profiles::switch_profile,
+            profiles::delete_profile,
+            reset::reset_app_data,
+            datadir::get_data_dir,
+            datadir::move_data_dir,
+            onboarding::get_onboarding,
+            onboarding::advance_onboarding,
+            onboarding::request_notification_permission,
+            onboarding::save_credentials,
+            onboarding::restart_onboarding,
+            vault::list_config_secrets,
+            vault::set_config_secret,
+            flags::get_flags,
+            i18n::get_language,
+            i18n::set_language,
+            sync::get_sync_status,
+            sync::enable_sync,
+            sync::disable_sync,
+            sync::sync_now,
+            sync::resolve_sync_conflict,
+            trayhost::get_tray_status,
+            trayhost::set_background_without_tray,
+            menubar::get_menu_bar_status,
+            menubar::set_menu_bar_only,
+            keepawake::get_keep_awake,
+            keepawake::set_keep_awake,
+            automation::take_pending_route,
+            wayland::get_display_compat,
+            wayland::set_wayland_compat,
+            loginitem::get_login_item,
+            loginitem::set_login_item_method,
+            loginitem::open_login_items_settings,
+            schedule::list_schedules,
+            schedule::create_schedule,
+            schedule::update_schedule,
+            schedule::delete_schedule,
+            schedule::run_schedule_now,
+            schedule::preview_schedule,
+            history::query_history,
+            history::get_history_entry,
+            history::history_statuses,
+            search::search_applications,
+            tags::list_tags,
+            tags::create_tag,
+            tags::update_tag,
+            tags::delete_tag,
+            tags::set_application_tags,
+            spreadsheet::export_applications,
+            calendar::list_calendar_events,
+            calendar::save_calendar_event,
+            calendar::delete_calendar_event,
+            calendar::export_calendar_event,
+            calendar::export_calendar,
+            tz::list_timezones,
+            tz::convert_time,
+            documents::list_documents,
+            documents::add_document,
+            documents::update_document,
+            documents::retire_document,
+            documents::document_path,
+            pdf::export_cover_letter_pdf,
+            pdf::export_weekly_report_pdf,
+            templates::list_templates,
+            templates::save_template,
+            templates::restore_template_version,
+            templates::delete_template,
+            templates::save_letter_sender,
+            templates::render_template,
+            templates::export_templates,
+            templates::import_templates,
+            resume::parse_resume,
+            salary::normalize_salary,
+            activity::activity_report,
+            alerts::list_job_alerts,
+            alerts::save_job_alert,
+            alerts::delete_job_alert,
+            alerts::clear_job_alert_matches,
+            drivers::get_browser_drivers,
+            drivers::update_browser_drivers,
+            browserprofiles::list_browser_profiles,
+            browserprofiles::create_browser_profile,
+            browserprofiles::set_active_browser_profile,
+            browserprofiles::reset_browser_profile,
+            browserprofiles::wipe_browser_profile,
+            proxypool::list_proxy_pool,
+            proxypool::add_pool_proxy,
+            proxypool::set_pool_proxy_enabled,
+            proxypool::remove_pool_proxy,
+            proxypool::check_proxy_pool,
+            proxypool::rotate_proxy_pool,
+            offlinequeue::get_offline_queue,
+            offlinequeue::queue_offline_action,
+            offlinequeue::replay_offline_queue,
+            offlinequeue::discard_offline_action,
+            offlinequeue::dismiss_offline_conflict,
+            mailwatch::get_mail_watch,
+            mailwatch::set_mail_watch,
+            mailwatch::check_mail_now,
+            oauth::get_oauth_status,
+            oauth::set_oauth_client,
+            oauth::sign_in_oauth,
+            oauth::cancel_oauth_sign_in,
+            oauth::sign_out_oauth,
+            stats::get_stats,
+            historycrypt::get_history_encryption,
+            historycrypt::set_history_encryption,
+            historyimport::import_application_history,
+            duplicates::check_duplicate,
+            duplicates::get_duplicate_flag,
+            duplicates::dismiss_duplicate_flag,
+            reminders::list_reminders,
+            reminders::add_reminder,
+            reminders::snooze_reminder,
+            reminders::complete_reminder,
+            webhooks::list_webhooks,
+            webhooks::save_webhook,
+            webhooks::remove_webhook,
+            webhooks::list_webhook_deliveries,
+            webhooks::test_webhook,
+            webhooks::redeliver_webhook,
+            backup::get_backups,
+            backup::set_backup_settings,
+            backup::create_backup,
+            backup::inspect_backup,
+            backup::restore_backup,
+            quotas::get_quota,
+            quotas::set_quota_settings,
+            quotas::authorize_application,
+            evidence::list_evidence,
+            evidence::read_evidence,
+            evidence::open_evidence,
+            evidence::get_evidence_settings,
+            evidence::set_evidence_settings,
+            retention::get_retention_settings,
+            retention::set_retention_settings,
+            retention::preview_retention,
+            retention::run_retention_now,
+            dataexport::export_personal_data,
+            workhours::get_working_hours,
+            workhours::set_working_hours,
+            workhours::override_working_hours,
+            clipwatch::get_clipboard_job,
+            clipwatch::accept_clipboard_job,
+            clipwatch::dismiss_clipboard_job,
+            clipwatch::get_clipboard_watch,
+            clipwatch::set_clipboard_watch,
+            jobfetch::fetch_job_posting,
+            nativehost::get_browser_extension,
+            nativehost::set_browser_extension,
+            pipeline::get_pipeline,
+            pipeline::move_pipeline_card,
+            pipeline::set_pipeline_status,
+            pipeline::set_pipeline_notes,
+            pipeline::create_pipeline_stage,
+            pipeline::update_pipeline_stage,
+            pipeline::delete_pipeline_stage,
+            pipeline::reorder_pipeline_stages,
+            pipeline::reset_pipeline_stages,
+        ]))
         .on_window_event(|window, event| {
-            // Closing the window hides it to tray — the app keeps running
+            // Closing the main window hides it to tray — the app keeps running.
+            // With no tray (and no opt-in to run without one) it quits.
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                window.hide().unwrap();
+                if window.label() != "main" {
+                    return;
+                }
                 api.prevent_close();
+                if trayhost::can_hide(window.app_handle()) {
+                    window.hide().unwrap();
+                    menubar::on_hidden(window.app_handle());
+                } else {
+                    window.app_handle().exit(0);
+                }
+            }
+            if let tauri::WindowEvent::Focused(focused) = event {
+                if window.label() == "main" {
+                    activity::on_focus(window.app_handle(), *focused);
+                    if *focused {
+                        taskbar::on_focused(window.app_handle());
+                    }
+                }
+            }
+            if let tauri::WindowEvent::ScaleFactorChanged { .. } = event {
+                trayicon::refresh(window.app_handle());
             }
         })
         .build(tauri::generate_context!())
         .expect("error building tauri application")
-        .run(|app, event| {
-            if let RunEvent::Exit = event {
+        .run(|app, event| match event {
+            RunEvent::Exit => {
+                activity::on_exit(app);
                 kill_backend(app);
             }
+            // macOS delivers opened files (the .jobbot association) as events
+            #[cfg(target_os = "macos")]
+            RunEvent::Opened { urls } => {
+                for path in urls.iter().filter_map(|u| u.to_file_path().ok()) {
+                    if export::is_export_file(&path) {
+                        export::request_import(app, &path);
+                    }
+                }
+            }
+            _ => {}
         });
 }
@@ -0,0 +1,495 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::{
+    collections::VecDeque,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex,
+    },
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{
+    field::{Field, Visit},
+    span,
+    subscriber::Interest,
+    Event, Level, Metadata, Subscriber,
+};
+
+use crate::{overrides, paths, redact, settings};
+
+// The shell's diagnostics go through `tracing`. Every event is written, as
+// one JSON object per line, to shell.log in the log directory, echoed to
+// stderr in development builds, kept in a short in-memory backlog and
+// forwarded to the webview as `shell-log` for the in-app log viewer. Lines
+// are redacted (see `redact`) before they reach any of those. Level comes
+// from JOBBOT_LOG_LEVEL, default info, or per area from settings (AREAS);
+// other crates' events are kept at warn and above whatever the level.
+// shell.log is moved aside as shell-<timestamp>.log when it reaches
+// ROTATE_BYTES or a new day starts; `logretention` deletes the old ones.
+//
+// Separately, the last RING events at debug and above are always kept in
+// memory, whatever the level and whether or not the file could be opened,
+// so crash reports and `get_recent_events` have the context.
+pub const LOG_EVENT: &str = "shell-log";
+pub const LOG_FILE: &str = "shell.log";
+const ROTATE_BYTES: u64 = 5 * 1024 * 1024;
+// Backlog for a viewer opened after the fact; also what's held before the
+// file can be opened
+const RECENT: usize = 500;
+const RING: usize = 1000;
+
+// Parts of the shell whose level can be set on their own (`logs.levels` in
+// settings), by the exact targets they log under. Unlisted targets follow
+// JOBBOT_LOG_LEVEL.
+pub const TRAY_TARGET: &str = "jobbot_lib::tray";
+const AREAS: [(&str, &[&str]); 4] = [
+    (
+        "supervisor",
+        &[
+            "jobbot_lib",
+            "jobbot_lib::sidecar",
+            "jobbot_lib::sidecar_env",
+            "jobbot_lib::quarantine",
+        ],
+    ),
+    ("tray", &[TRAY_TARGET]),
+    ("ipc", &["jobbot_lib::scope", "jobbot_lib::ipc"]),
+    (
+        "backend-bridge",
+        &[
+            "jobbot_lib::events",
+            "jobbot_lib::backend",
+            "jobbot_lib::cookievault",
+        ],
+    ),
+];
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LogEntry {
+    // RFC 3339, UTC
+    pub at: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, Value>,
+}
+
+struct Sinks {
+    app: Option<AppHandle>,
+    dir: Option<PathBuf>,
+    file: Option<File>,
+    // Size of shell.log and the local day it was started on (for a file left
+    // by an earlier run, last written on)
+    written: u64,
+    started_on: Option<NaiveDate>,
+    recent: VecDeque<LogEntry>,
+}
+
+static SINKS: Mutex<Sinks> = Mutex::new(Sinks {
+    app: None,
+    dir: None,
+    file: None,
+    written: 0,
+    started_on: None,
+    recent: VecDeque::new(),
+});
+static RECENT_EVENTS: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+// tracing's Level as 1 (error) ..= 5 (trace)
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(3);
+// Same numbering by position in AREAS; 0 follows MAX_LEVEL
+static AREA_LEVELS: [AtomicU8; AREAS.len()] = [const { AtomicU8::new(0) }; AREAS.len()];
+
+fn level_number(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 1,
+        Level::WARN => 2,
+        Level::INFO => 3,
+        Level::DEBUG => 4,
+        Level::TRACE => 5,
+    }
+}
+
+#[derive(Default)]
+struct Fields {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl Fields {
+    fn put(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            };
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.put(field, Value::String(redact::redact_cached(value)));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.put(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.put(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.put(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.put(
+            field,
+            Value::String(redact::redact_cached(&format!("{value:?}"))),
+        );
+    }
+}
+
+// Whether an event goes to the file, console and webview.
+fn passes(metadata: &Metadata<'_>) -> bool {
+    let target = metadata.target();
+    let area = AREAS
+        .iter()
+        .position(|(_, targets)| targets.contains(&target))
+        .map(|i| AREA_LEVELS[i].load(Ordering::Relaxed))
+        .filter(|level| *level > 0);
+    let max = match area {
+        Some(level) => level,
+        None if target.starts_with("jobbot") => MAX_LEVEL.load(Ordering::Relaxed),
+        None => level_number(&Level::WARN),
+    };
+    let max = if crate::debugmode::is_on() && target.starts_with("jobbot") {
+        max.max(level_number(&Level::DEBUG))
+    } else {
+        max
+    };
+    level_number(metadata.level()) <= max
+}
+
+// Whether an event goes to the in-memory ring.
+fn kept(metadata: &Metadata<'_>) -> bool {
+    metadata.target().starts_with("jobbot") && *metadata.level() <= Level::DEBUG
+}
+
+struct ShellSubscriber;
+
+impl Subscriber for ShellSubscriber {
+    // Decided per event so the level can change at runtime
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        kept(metadata) || passes(metadata)
+    }
+
+    // Spans aren't recorded; the shell only emits events
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        let entry = LogEntry {
+            at: Utc::now().to_rfc3339(),
+            level: metadata.level().to_string().to_lowercase(),
+            target: metadata.target().to_string(),
+            message: fields.message,
+            fields: fields.fields,
+        };
+        remember(entry.clone());
+        if passes(metadata) {
+            write(entry);
+        }
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+fn console_line(entry: &LogEntry) -> String {
+    let mut line = format!(
+        "{} {:>5} {}: {}",
+        Local::now().format("%H:%M:%S"),
+        entry.level.to_uppercase(),
+        entry.target,
+        entry.message
+    );
+    for (name, value) in &entry.fields {
+        match value {
+            Value::String(s) => line.push_str(&format!(" {name}={s}")),
+            other => line.push_str(&format!(" {name}={other}")),
+        }
+    }
+    line
+}
+
+fn remember(entry: LogEntry) {
+    let mut ring = RECENT_EVENTS.lock().unwrap();
+    if ring.len() == RING {
+        ring.pop_front();
+    }
+    ring.push_back(entry);
+}
+
+fn write(entry: LogEntry) {
+    if cfg!(debug_assertions) {
+        eprintln!("{}", console_line(&entry));
+    }
+    let app = {
+        let mut sinks = SINKS.lock().unwrap();
+        append(&mut sinks, &entry);
+        if sinks.recent.len() == RECENT {
+            sinks.recent.pop_front();
+        }
+        sinks.recent.push_back(entry.clone());
+        sinks.app.clone()
+    };
+    // Outside the lock: emitting may run code that logs
+    if let Some(app) = app {
+        let _ = app.emit(LOG_EVENT, entry);
+    }
+}
+
+fn open_file(dir: &Path) -> Result<File, String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(LOG_FILE))
+        .map_err(|e| e.to_string())
+}
+
+// Closes shell.log before renaming it; Windows won't rename an open file.
+fn rotate(sinks: &mut Sinks) {
+    let Some(dir) = sinks.dir.clone() else {
+        return;
+    };
+    sinks.file = None;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let _ = fs::rename(dir.join(LOG_FILE), dir.join(format!("shell-{stamp}.log")));
+    sinks.file = open_file(&dir).ok();
+    sinks.written = 0;
+    sinks.started_on = None;
+}
+
+fn append(sinks: &mut Sinks, entry: &LogEntry) {
+    let today = Local::now().date_naive();
+    if sinks.written >= ROTATE_BYTES || sinks.started_on.is_some_and(|day| day != today) {
+        rotate(sinks);
+    }
+    let Some(file) = sinks.file.as_mut() else {
+        return;
+    };
+    if let Ok(json) = serde_json::to_string(entry) {
+        if writeln!(file, "{json}").is_ok() {
+            sinks.written += json.len() as u64 + 1;
+            sinks.started_on.get_or_insert(today);
+        }
+    }
+}
+
+// First thing in `run`, so nothing logged during startup is lost.
+pub fn init() {
+    if tracing::subscriber::set_global_default(ShellSubscriber).is_err() {
+        return;
+    }
+    if let Some(level) = overrides::get()
+        .log_level
+        .as_deref()
+        .and_then(|l| l.parse::<Level>().ok())
+    {
+        MAX_LEVEL.store(level_number(&level), Ordering::Relaxed);
+    }
+}
+
+pub fn is_area(name: &str) -> bool {
+    AREAS.iter().any(|(area, _)| *area == name)
+}
+
+fn parse_level(level: &str) -> Option<Level> {
+    overrides::LOG_LEVELS
+        .contains(&level)
+        .then(|| level.parse().ok())
+        .flatten()
+}
+
+// After settings were loaded or `logs.levels` changed.
+pub fn refresh_levels(app: &AppHandle) {
+    let levels = settings::get(app).logs.levels;
+    for (i, (area, _)) in AREAS.iter().enumerate() {
+        let level = levels
+            .get(*area)
+            .and_then(|l| parse_level(l))
+            .map_or(0, |l| level_number(&l));
+        AREA_LEVELS[i].store(level, Ordering::Relaxed);
+    }
+}
+
+// Once paths are known: opens the file and writes out what was held so far.
+pub fn attach(app: &AppHandle) {
+    redact::refresh(app);
+    refresh_levels(app);
+    let dir = paths::log_dir(app);
+    let file = dir.clone().and_then(|dir| open_file(&dir));
+    let mut sinks = SINKS.lock().unwrap();
+    sinks.app = Some(app.clone());
+    match file {
+        Ok(file) => {
+            // Picks up where the last run left off, for rotation's sake
+            if let Ok(meta) = file.metadata() {
+                sinks.written = meta.len();
+                sinks.started_on = meta
+                    .modified()
+                    .ok()
+                    .filter(|_| meta.len() > 0)
+                    .map(|t| DateTime::<Local>::from(t).date_naive());
+            }
+            sinks.dir = dir.ok();
+            sinks.file = Some(file);
+            let backlog: Vec<LogEntry> = sinks.recent.iter().cloned().collect();
+            for entry in &backlog {
+                append(&mut sinks, entry);
+            }
+        }
+        Err(e) => {
+            drop(sinks);
+            tracing::warn!("log file unavailable: {e}");
+        }
+    }
+}
+
+// The last `count` events from the ring as JSON lines, for crash reports.
+// Gives up rather than wait if the logger itself is what failed.
+pub fn tail(count: usize) -> Vec<String> {
+    let Ok(ring) = RECENT_EVENTS.try_lock() else {
+        return Vec::new();
+    };
+    let skip = ring.len().saturating_sub(count);
+    ring.iter()
+        .skip(skip)
+        .filter_map(|entry| serde_json::to_string(entry).ok())
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub struct AreaLevel {
+    pub area: &'static str,
+    // None follows the global level
+    pub level: Option<String>,
+    pub effective: String,
+}
+
+#[tauri::command]
+pub fn get_log_levels(app: AppHandle) -> Vec<AreaLevel> {
+    let levels = settings::get(&app).logs.levels;
+    let global = MAX_LEVEL.load(Ordering::Relaxed);
+    AREAS
+        .iter()
+        .enumerate()
+        .map(|(i, (area, _))| {
+            let own = AREA_LEVELS[i].load(Ordering::Relaxed);
+            let effective = if own > 0 { own } else { global };
+            AreaLevel {
+                area,
+                level: levels.get(*area).cloned(),
+                effective: overrides::LOG_LEVELS[usize::from(effective) - 1].to_string(),
+            }
+        })
+        .collect()
+}
+
+// `level` None puts the area back on the global level. Kept in settings, so
+// it survives restarts until it's reset.
+#[tauri::command]
+pub fn set_log_level(app: AppHandle, area: String, level: Option<String>) -> Result<(), String> {
+    if !is_area(&area) {
+        return Err(format!("unknown log area {area}"));
+    }
+    let level = level.map(|l| l.to_lowercase());
+    if let Some(l) = &level {
+        parse_level(l).ok_or_else(|| format!("unknown log level {l}"))?;
+    }
+    settings::update(&app, |s| match level {
+        Some(l) => {
+            s.logs.levels.insert(area, l);
+        }
+        None => {
+            s.logs.levels.remove(&area);
+        }
+    })?;
+    refresh_levels(&app);
+    Ok(())
+}
+
+// Oldest first, for a viewer that opens after events were emitted.
+#[tauri::command]
+pub fn get_recent_logs() -> Vec<LogEntry> {
+    SINKS.lock().unwrap().recent.iter().cloned().collect()
+}
+
+// The ring, oldest first: the last `limit` events (all by default),
+// optionally only from targets starting with `target`.
+#[tauri::command]
+pub fn get_recent_events(limit: Option<usize>, target: Option<String>) -> Vec<LogEntry> {
+    let ring = RECENT_EVENTS.lock().unwrap();
+    let matching: Vec<&LogEntry> = ring
+        .iter()
+        .filter(|e| target.as_deref().is_none_or(|t| e.target.starts_with(t)))
+        .collect();
+    let skip = matching.len().saturating_sub(limit.unwrap_or(RING));
+    matching.into_iter().skip(skip).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn areas_and_levels() {
+        assert!(is_area("tray"));
+        assert!(!is_area("jobbot_lib::tray"));
+        assert_eq!(parse_level("debug"), Some(Level::DEBUG));
+        assert_eq!(parse_level("DEBUG"), None);
+        assert_eq!(parse_level("verbose"), None);
+        assert!(level_number(&Level::ERROR) < level_number(&Level::TRACE));
+    }
+
+    #[test]
+    fn console_lines_carry_the_fields() {
+        let entry = LogEntry {
+            at: "2026-10-15T09:00:00Z".into(),
+            level: "warn".into(),
+            target: TRAY_TARGET.into(),
+            message: "icon missing".into(),
+            fields: json!({ "name": "busy", "size": 32 })
+                .as_object()
+                .unwrap()
+                .clone(),
+        };
+        let line = console_line(&entry);
+        assert!(line.ends_with(" WARN jobbot_lib::tray: icon missing name=busy size=32"));
+    }
+}
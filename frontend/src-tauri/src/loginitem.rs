@@ -0,0 +1,532 @@
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_autostart::ManagerExt;
+use tracing::{info, warn};
+
+use crate::{
+    paths,
+    settings::{self, LoginItemMethod},
+};
+
+// Start on login. By default the autostart plugin registers it: a LaunchAgent
+// in ~/Library/LaunchAgents on macOS, the HKCU Run key on Windows. Linux gets
+// an autostart entry written here (see its `platform`). `login_item` in
+// settings.json can pick another way where one exists:
+// - macOS 13+: the same agent, bundled in the app (macos/ in this crate),
+//   registered with SMAppService. It shows under Login Items in System
+//   Settings and isn't blocked by MDM policies that forbid LaunchAgents.
+// - Windows: a Task Scheduler task that starts the app for every user who
+//   logs on, for per-machine installs. Changing it asks for administrator
+//   rights, and IT can deploy it the same way.
+// Auto prefers SMAppService on macOS and an existing all-users task on
+// Windows.
+#[derive(Debug, Serialize)]
+pub struct LoginItemInfo {
+    pub enabled: bool,
+    pub method: LoginItemMethod,
+    // What `method` comes down to on this machine
+    pub effective: LoginItemMethod,
+    // The ones that can be picked here; none when there's no choice
+    pub methods: Vec<LoginItemMethod>,
+    // Registered, but waiting to be allowed in System Settings
+    pub requires_approval: bool,
+}
+
+// What the plugin does, under each OS's name for it.
+fn by_plugin(method: LoginItemMethod) -> bool {
+    matches!(
+        method,
+        LoginItemMethod::Auto | LoginItemMethod::LaunchAgent | LoginItemMethod::RunKey
+    )
+}
+
+fn effective(app: &AppHandle) -> LoginItemMethod {
+    match settings::get(app).login_item {
+        method if method != LoginItemMethod::Auto && platform::METHODS.contains(&method) => method,
+        _ => platform::preferred(),
+    }
+}
+
+fn enable_with(app: &AppHandle, method: LoginItemMethod) -> Result<(), String> {
+    if by_plugin(method) {
+        app.autolaunch().enable().map_err(|e| e.to_string())
+    } else {
+        platform::register(method)
+    }
+}
+
+fn disable_with(app: &AppHandle, method: LoginItemMethod) -> Result<(), String> {
+    if by_plugin(method) {
+        app.autolaunch().disable().map_err(|e| e.to_string())
+    } else {
+        platform::unregister(method)
+    }
+}
+
+fn enabled_with(app: &AppHandle, method: LoginItemMethod) -> bool {
+    if by_plugin(method) {
+        app.autolaunch().is_enabled().unwrap_or(false)
+    } else {
+        platform::registered(method)
+    }
+}
+
+pub fn is_enabled(app: &AppHandle) -> bool {
+    !paths::is_portable() && enabled_with(app, effective(app))
+}
+
+// May ask for administrator rights on Windows; not from the main thread.
+pub fn enable(app: &AppHandle) -> Result<(), String> {
+    if paths::is_portable() {
+        return Err("start on login is not available in portable mode".into());
+    }
+    enable_with(app, effective(app))
+}
+
+// The current way, and the plugin's in any case.
+pub fn disable(app: &AppHandle) -> Result<(), String> {
+    let method = effective(app);
+    let result = disable_with(app, LoginItemMethod::Auto);
+    if !by_plugin(method) {
+        disable_with(app, method)?;
+    }
+    result
+}
+
+// At startup: login items from older versions, or left by another method,
+// are registered again the current way with the current arguments. Ways
+// that need administrator rights are left alone rather than prompting.
+pub fn reregister(app: &AppHandle) {
+    if paths::is_portable() {
+        return;
+    }
+    let method = effective(app);
+    let mut moving = false;
+    for other in platform::METHODS.iter().copied() {
+        if other != method
+            && !platform::needs_elevation(other)
+            && !(by_plugin(other) && by_plugin(method))
+            && enabled_with(app, other)
+        {
+            info!(?method, from = ?other, "moving the login item");
+            let _ = disable_with(app, other);
+            moving = true;
+        }
+    }
+    if platform::needs_elevation(method) || !(moving || enabled_with(app, method)) {
+        return;
+    }
+    if let Err(e) = enable_with(app, method) {
+        warn!("login item not registered: {e}");
+    }
+}
+
+#[tauri::command]
+pub fn get_login_item(app: AppHandle) -> LoginItemInfo {
+    let effective = effective(&app);
+    LoginItemInfo {
+        enabled: is_enabled(&app),
+        method: settings::get(&app).login_item,
+        effective,
+        methods: platform::METHODS
+            .iter()
+            .copied()
+            .filter(|m| platform::offered(*m))
+            .collect(),
+        requires_approval: platform::requires_approval(effective),
+    }
+}
+
+// Moves an enabled login item over to the new method.
+#[tauri::command]
+pub async fn set_login_item_method(app: AppHandle, method: LoginItemMethod) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let was_enabled = is_enabled(&app);
+        if was_enabled {
+            disable(&app)?;
+        }
+        settings::update(&app, |s| s.login_item = method)?;
+        if was_enabled {
+            enable(&app)?;
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn open_login_items_settings() {
+    platform::open_settings();
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2::{
+        msg_send,
+        rc::Retained,
+        runtime::{AnyClass, AnyObject},
+    };
+    use objc2_foundation::{NSError, NSString};
+
+    use crate::settings::LoginItemMethod;
+
+    pub const METHODS: &[LoginItemMethod] = &[
+        LoginItemMethod::Auto,
+        LoginItemMethod::AppService,
+        LoginItemMethod::LaunchAgent,
+    ];
+
+    // Bundled at Contents/Library/LaunchAgents (tauri.conf.json)
+    const PLIST: &str = "com.currobot.app.login.plist";
+
+    #[link(name = "ServiceManagement", kind = "framework")]
+    extern "C" {}
+
+    // SMAppServiceStatus
+    #[derive(Debug, PartialEq, Eq)]
+    enum Status {
+        NotRegistered,
+        Enabled,
+        RequiresApproval,
+    }
+
+    fn class() -> Option<&'static AnyClass> {
+        AnyClass::get(c"SMAppService")
+    }
+
+    fn service() -> Option<Retained<AnyObject>> {
+        let name = NSString::from_str(PLIST);
+        unsafe { msg_send![class()?, agentServiceWithPlistName: &*name] }
+    }
+
+    // The class is new in macOS 13.
+    fn available() -> bool {
+        class().is_some()
+    }
+
+    pub fn offered(_method: LoginItemMethod) -> bool {
+        available()
+    }
+
+    pub fn preferred() -> LoginItemMethod {
+        if available() {
+            LoginItemMethod::AppService
+        } else {
+            LoginItemMethod::LaunchAgent
+        }
+    }
+
+    pub fn needs_elevation(_method: LoginItemMethod) -> bool {
+        false
+    }
+
+    fn status() -> Status {
+        let Some(service) = service() else {
+            return Status::NotRegistered;
+        };
+        let status: isize = unsafe { msg_send![&*service, status] };
+        match status {
+            1 => Status::Enabled,
+            2 => Status::RequiresApproval,
+            // 0, and 3 for a plist missing from the bundle
+            _ => Status::NotRegistered,
+        }
+    }
+
+    // Waiting for approval counts: it starts once allowed.
+    pub fn registered(_method: LoginItemMethod) -> bool {
+        status() != Status::NotRegistered
+    }
+
+    pub fn requires_approval(method: LoginItemMethod) -> bool {
+        method == LoginItemMethod::AppService && status() == Status::RequiresApproval
+    }
+
+    pub fn register(_method: LoginItemMethod) -> Result<(), String> {
+        let service = service().ok_or("SMAppService is not available")?;
+        let result: Result<(), Retained<NSError>> =
+            unsafe { msg_send![&*service, registerAndReturnError: _] };
+        result.map_err(|e| e.localizedDescription().to_string())
+    }
+
+    pub fn unregister(_method: LoginItemMethod) -> Result<(), String> {
+        if status() == Status::NotRegistered {
+            return Ok(());
+        }
+        let service = service().ok_or("SMAppService is not available")?;
+        let result: Result<(), Retained<NSError>> =
+            unsafe { msg_send![&*service, unregisterAndReturnError: _] };
+        result.map_err(|e| e.localizedDescription().to_string())
+    }
+
+    pub fn open_settings() {
+        if let Some(class) = class() {
+            let _: () = unsafe { msg_send![class, openSystemSettingsLoginItems] };
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{os::windows::process::CommandExt, process::Command};
+    use windows::{
+        core::{HSTRING, PCWSTR},
+        Win32::{
+            Foundation::{CloseHandle, WAIT_OBJECT_0},
+            System::Threading::{GetExitCodeProcess, WaitForSingleObject, INFINITE},
+            UI::{
+                Shell::{
+                    ShellExecuteExW, SEE_MASK_NOCLOSEPROCESS, SEE_MASK_NO_CONSOLE,
+                    SHELLEXECUTEINFOW,
+                },
+                WindowsAndMessaging::SW_HIDE,
+            },
+        },
+    };
+
+    use crate::settings::LoginItemMethod;
+
+    pub const METHODS: &[LoginItemMethod] = &[
+        LoginItemMethod::Auto,
+        LoginItemMethod::RunKey,
+        LoginItemMethod::AllUsers,
+    ];
+
+    const TASK: &str = "currobot login";
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    // The Users group, so the task starts for whoever logs on, as them
+    const USERS_SID: &str = "S-1-5-32-545";
+
+    pub fn offered(_method: LoginItemMethod) -> bool {
+        true
+    }
+
+    // A task IT (or an earlier choice) set up for everyone wins.
+    pub fn preferred() -> LoginItemMethod {
+        if registered(LoginItemMethod::AllUsers) {
+            LoginItemMethod::AllUsers
+        } else {
+            LoginItemMethod::RunKey
+        }
+    }
+
+    pub fn needs_elevation(method: LoginItemMethod) -> bool {
+        method == LoginItemMethod::AllUsers
+    }
+
+    // Anyone can read tasks; only administrators can change them.
+    pub fn registered(_method: LoginItemMethod) -> bool {
+        Command::new("schtasks.exe")
+            .args(["/Query", "/TN", TASK])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .is_ok_and(|o| o.status.success())
+    }
+
+    pub fn requires_approval(_method: LoginItemMethod) -> bool {
+        false
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn task_xml(exe: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <Triggers>
+    <LogonTrigger><Enabled>true</Enabled></LogonTrigger>
+  </Triggers>
+  <Principals>
+    <Principal id="Users">
+      <GroupId>{USERS_SID}</GroupId>
+      <RunLevel>LeastPrivilege</RunLevel>
+    </Principal>
+  </Principals>
+  <Settings>
+    <MultipleInstancesPolicy>Parallel</MultipleInstancesPolicy>
+    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+    <ExecutionTimeLimit>PT0S</ExecutionTimeLimit>
+    <Enabled>true</Enabled>
+  </Settings>
+  <Actions Context="Users">
+    <Exec>
+      <Command>{}</Command>
+      <Arguments>{}</Arguments>
+    </Exec>
+  </Actions>
+</Task>
+"#,
+            escape(exe),
+            crate::AUTOSTART_ARG
+        )
+    }
+
+    // schtasks.exe through the UAC prompt; waits for it to finish.
+    fn elevated_schtasks(args: &str) -> Result<(), String> {
+        let verb = HSTRING::from("runas");
+        let file = HSTRING::from("schtasks.exe");
+        let parameters = HSTRING::from(args);
+        let mut info = SHELLEXECUTEINFOW {
+            cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+            fMask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_NO_CONSOLE,
+            lpVerb: PCWSTR(verb.as_ptr()),
+            lpFile: PCWSTR(file.as_ptr()),
+            lpParameters: PCWSTR(parameters.as_ptr()),
+            nShow: SW_HIDE.0,
+            ..Default::default()
+        };
+        // Fails with ERROR_CANCELLED when the prompt is declined
+        unsafe { ShellExecuteExW(&mut info) }.map_err(|e| e.message())?;
+        let mut code = 1u32;
+        unsafe {
+            if WaitForSingleObject(info.hProcess, INFINITE) == WAIT_OBJECT_0 {
+                let _ = GetExitCodeProcess(info.hProcess, &mut code);
+            }
+            let _ = CloseHandle(info.hProcess);
+        }
+        if code == 0 {
+            Ok(())
+        } else {
+            Err(format!("schtasks exited with {code}"))
+        }
+    }
+
+    pub fn register(_method: LoginItemMethod) -> Result<(), String> {
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        let xml = task_xml(&exe.to_string_lossy());
+        // Task Scheduler reads UTF-16 with a byte order mark
+        let bytes: Vec<u8> = std::iter::once(0xFEFF)
+            .chain(xml.encode_utf16())
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let file = std::env::temp_dir().join("currobot-login-task.xml");
+        std::fs::write(&file, bytes).map_err(|e| e.to_string())?;
+        let result = elevated_schtasks(&format!(
+            r#"/Create /TN "{TASK}" /XML "{}" /F"#,
+            file.display()
+        ));
+        let _ = std::fs::remove_file(&file);
+        result
+    }
+
+    pub fn unregister(method: LoginItemMethod) -> Result<(), String> {
+        if !registered(method) {
+            return Ok(());
+        }
+        elevated_schtasks(&format!(r#"/Delete /TN "{TASK}" /F"#))
+    }
+
+    pub fn open_settings() {}
+}
+
+// An XDG autostart entry written here rather than by the plugin, which
+// points at the AppImage's mount (gone on the next boot) instead of the
+// AppImage, quotes nothing and ignores XDG_CONFIG_HOME.
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::{fs, path::PathBuf};
+
+    use crate::settings::LoginItemMethod;
+
+    pub const METHODS: &[LoginItemMethod] = &[LoginItemMethod::Auto, LoginItemMethod::DesktopEntry];
+
+    const FILE: &str = "jobbot.desktop";
+
+    pub fn offered(_method: LoginItemMethod) -> bool {
+        false
+    }
+
+    pub fn preferred() -> LoginItemMethod {
+        LoginItemMethod::DesktopEntry
+    }
+
+    pub fn needs_elevation(_method: LoginItemMethod) -> bool {
+        false
+    }
+
+    fn path() -> Option<PathBuf> {
+        let config = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config.join("autostart").join(FILE))
+    }
+
+    // Desktop environments hide an entry the user turned off rather than
+    // deleting it.
+    pub fn registered(_method: LoginItemMethod) -> bool {
+        let Some(text) = path().and_then(|p| fs::read_to_string(p).ok()) else {
+            return false;
+        };
+        !text.lines().any(|line| {
+            matches!(
+                line.replace(' ', "").as_str(),
+                "Hidden=true" | "X-GNOME-Autostart-enabled=false"
+            )
+        })
+    }
+
+    pub fn requires_approval(_method: LoginItemMethod) -> bool {
+        false
+    }
+
+    // An argument of Exec, quoted as the Desktop Entry spec asks.
+    fn quote(arg: &str) -> String {
+        let mut quoted = String::from("\"");
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        // `%` starts a field code; `\` is itself escaped in a string value
+        quoted.replace('%', "%%").replace('\\', "\\\\")
+    }
+
+    pub fn register(_method: LoginItemMethod) -> Result<(), String> {
+        let path = path().ok_or("no home directory")?;
+        // An AppImage runs from a mount that changes every launch
+        let exe = match std::env::var_os("APPIMAGE") {
+            Some(appimage) => PathBuf::from(appimage),
+            None => std::env::current_exe().map_err(|e| e.to_string())?,
+        };
+        let icon = std::env::current_exe()
+            .ok()
+            .and_then(|p| Some(p.file_stem()?.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "jobbot".into());
+        let entry = format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name=currobot\n\
+             Comment=Start currobot in the background\n\
+             Exec={} {}\n\
+             Icon={icon}\n\
+             Terminal=false\n\
+             StartupNotify=false\n\
+             X-GNOME-Autostart-enabled=true\n",
+            quote(&exe.to_string_lossy()),
+            crate::AUTOSTART_ARG
+        );
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(&path, entry).map_err(|e| e.to_string())
+    }
+
+    pub fn unregister(_method: LoginItemMethod) -> Result<(), String> {
+        match path() {
+            Some(path) if path.exists() => fs::remove_file(path).map_err(|e| e.to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn open_settings() {}
+}
@@ -0,0 +1,258 @@
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::{logging, paths, settings};
+
+// Keeps the shell's and the engine's logs within `logs.budget_mb` in total
+// and drops anything older than `logs.max_age_days`. Both sides rotate their
+// own files (shell.log in `logging`, jobbot-<date>.jsonl by size and day in
+// backend/logging_config.py); this only deletes the rotated ones, oldest
+// first. The file each side is writing to is counted but never removed.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// The engine's rotation size, passed as JOBBOT_LOG_ROTATE_BYTES
+const ENGINE_ROTATE_BYTES: u64 = 10 * 1024 * 1024;
+const ROTATE_ENV: &str = "JOBBOT_LOG_ROTATE_BYTES";
+// pydantic-settings name of the engine's own `logs_retention_days`
+const RETENTION_ENV: &str = "LOGS_RETENTION_DAYS";
+
+#[derive(Debug, Serialize)]
+pub struct LogUsage {
+    pub shell_bytes: u64,
+    pub engine_bytes: u64,
+    pub budget_bytes: u64,
+    pub max_age_days: u32,
+}
+
+struct LogFile {
+    path: PathBuf,
+    bytes: u64,
+    modified: SystemTime,
+    active: bool,
+}
+
+fn engine_log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::engine_dir(app)?.join("logs"))
+}
+
+// Regular files in `dir`; the newest one is taken to be the engine's current
+// file, as its name depends on the day it started.
+fn list(dir: &Path, active_name: Option<&str>) -> Vec<LogFile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<LogFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some(LogFile {
+                active: active_name.is_some_and(|n| entry.file_name() == n),
+                path: entry.path(),
+                bytes: meta.len(),
+                modified: meta.modified().ok()?,
+            })
+        })
+        .collect();
+    files.sort_by_key(|f| f.modified);
+    if active_name.is_none() {
+        if let Some(newest) = files.last_mut() {
+            newest.active = true;
+        }
+    }
+    files
+}
+
+fn shell_files(app: &AppHandle) -> Vec<LogFile> {
+    paths::log_dir(app)
+        .map(|dir| list(&dir, Some(logging::LOG_FILE)))
+        .unwrap_or_default()
+}
+
+fn engine_files(app: &AppHandle) -> Vec<LogFile> {
+    engine_log_dir(app)
+        .map(|dir| list(&dir, None))
+        .unwrap_or_default()
+}
+
+fn total(files: &[LogFile]) -> u64 {
+    files.iter().map(|f| f.bytes).sum()
+}
+
+// Inactive files to remove, oldest first: anything past `max_age`, then more
+// until what's left fits the budget.
+fn doomed(files: &[LogFile], now: SystemTime, budget: u64, max_age: Duration) -> Vec<&LogFile> {
+    let mut used = total(files);
+    let mut doomed = Vec::new();
+    for file in files.iter().filter(|f| !f.active) {
+        let expired = now
+            .duration_since(file.modified)
+            .is_ok_and(|age| age > max_age);
+        if expired || used > budget {
+            used -= file.bytes;
+            doomed.push(file);
+        }
+    }
+    doomed
+}
+
+pub fn enforce(app: &AppHandle) {
+    let limits = settings::get(app).logs;
+    let budget = u64::from(limits.budget_mb) * 1024 * 1024;
+    let max_age = Duration::from_secs(u64::from(limits.max_age_days) * 24 * 60 * 60);
+    let mut files = shell_files(app);
+    files.extend(engine_files(app));
+    files.sort_by_key(|f| f.modified);
+    let mut removed = 0;
+    for file in doomed(&files, SystemTime::now(), budget, max_age) {
+        match fs::remove_file(&file.path) {
+            Ok(()) => removed += 1,
+            Err(e) => warn!("could not remove {}: {e}", file.path.display()),
+        }
+    }
+    if removed > 0 {
+        info!(removed, budget, "old logs removed");
+    }
+}
+
+// Hourly, so the engine's files are caught between restarts as well.
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        enforce(&app);
+        thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+pub fn backend_env(app: &AppHandle) -> [(&'static str, String); 2] {
+    [
+        (ROTATE_ENV, ENGINE_ROTATE_BYTES.to_string()),
+        (
+            RETENTION_ENV,
+            settings::get(app).logs.max_age_days.to_string(),
+        ),
+    ]
+}
+
+#[tauri::command]
+pub fn get_log_usage(app: AppHandle) -> LogUsage {
+    let limits = settings::get(&app).logs;
+    LogUsage {
+        shell_bytes: total(&shell_files(&app)),
+        engine_bytes: total(&engine_files(&app)),
+        budget_bytes: u64::from(limits.budget_mb) * 1024 * 1024,
+        max_age_days: limits.max_age_days,
+    }
+}
+
+// Takes effect for the engine on its next start.
+#[tauri::command]
+pub fn set_log_retention(app: AppHandle, budget_mb: u32, max_age_days: u32) -> Result<(), String> {
+    if budget_mb == 0 || max_age_days == 0 {
+        return Err("the size and age limits must be above zero".into());
+    }
+    settings::update(&app, |s| {
+        s.logs.budget_mb = budget_mb;
+        s.logs.max_age_days = max_age_days;
+    })?;
+    enforce(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn file(name: &str, bytes: u64, age_days: u32, active: bool) -> LogFile {
+        LogFile {
+            path: PathBuf::from(name),
+            bytes,
+            modified: SystemTime::UNIX_EPOCH + DAY * (100 - age_days),
+            active,
+        }
+    }
+
+    fn names(files: Vec<&LogFile>) -> Vec<&str> {
+        files.iter().map(|f| f.path.to_str().unwrap()).collect()
+    }
+
+    fn now() -> SystemTime {
+        SystemTime::UNIX_EPOCH + DAY * 100
+    }
+
+    // Oldest first, as `enforce` sorts them
+    fn files() -> Vec<LogFile> {
+        vec![
+            file("old.log", 10, 40, false),
+            file("week.log", 10, 7, false),
+            file("yesterday.log", 10, 1, false),
+            file("shell.log", 10, 0, true),
+        ]
+    }
+
+    #[test]
+    fn within_limits_nothing_goes() {
+        assert!(doomed(&files(), now(), 100, DAY * 60).is_empty());
+    }
+
+    #[test]
+    fn expired_files_go() {
+        assert_eq!(names(doomed(&files(), now(), 100, DAY * 30)), ["old.log"]);
+        assert_eq!(
+            names(doomed(&files(), now(), 100, DAY * 3)),
+            ["old.log", "week.log"]
+        );
+    }
+
+    #[test]
+    fn oldest_go_until_the_rest_fits() {
+        let files = files();
+        assert_eq!(
+            names(doomed(&files, now(), 25, DAY * 60)),
+            ["old.log", "week.log"]
+        );
+    }
+
+    #[test]
+    fn active_files_stay_over_budget() {
+        let files = files();
+        assert_eq!(
+            names(doomed(&files, now(), 0, DAY * 60)),
+            ["old.log", "week.log", "yesterday.log"]
+        );
+    }
+
+    #[test]
+    fn newest_file_is_active_when_unnamed() {
+        let dir = std::env::temp_dir().join(format!("jobbot-logs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, age) in [
+            ("engine-1.log", 2),
+            ("engine-3.log", 0),
+            ("engine-2.log", 1),
+        ] {
+            let f = fs::File::create(dir.join(name)).unwrap();
+            f.set_modified(SystemTime::now() - DAY * age).unwrap();
+        }
+        fs::create_dir(dir.join("archive")).unwrap();
+
+        let active = |files: Vec<LogFile>| -> Vec<String> {
+            files
+                .iter()
+                .filter(|f| f.active)
+                .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+        let files = list(&dir, None);
+        assert_eq!(files.len(), 3);
+        assert_eq!(active(files), ["engine-3.log"]);
+        assert_eq!(active(list(&dir, Some("engine-1.log"))), ["engine-1.log"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,658 @@
+use chrono::{DateTime, Utc};
+use mailparse::MailHeaderMap;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{fs, path::PathBuf, sync::Mutex, thread, time::Duration};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    audit::{self, Action},
+    backend::{self, Failure},
+    duplicates::{company_name, normalize},
+    history,
+    i18n::t_args,
+    notifications, offlinequeue, paths,
+    settings::{self, MailSettings},
+};
+
+// Closes the loop after an application is sent: a mail folder the user's own
+// rules fill with what employers write back is read over IMAP every
+// `interval_minutes`. Each new message is sorted by its wording into a
+// confirmation, an interview, an offer or a rejection, matched to a sent
+// application in the history by the company (and role) it mentions, and the
+// application is moved on in the engine; with the engine down, through the
+// offline queue. The folder is only examined, never changed, so messages
+// stay unread. Where it got to and the last messages seen are kept in
+// `mail-watch.json` in the profile's data folder; the password in the OS
+// keychain.
+pub const MAIL_WATCH_CHANGED: &str = "mail-watch-changed";
+const KEYCHAIN_SERVICE: &str = "com.currobot.app.mail";
+const STATE_FILE: &str = "mail-watch.json";
+// How far back the first look at a folder goes
+const FIRST_LOOK_DAYS: i64 = 30;
+const MAX_MESSAGES: usize = 200;
+// Larger messages are judged by their headers alone
+const MAX_MESSAGE_BYTES: u32 = 2 * 1024 * 1024;
+const RECENT: usize = 50;
+const TICK: Duration = Duration::from_secs(60);
+
+// Checked in this order: a rejection usually thanks you for applying too.
+const REJECTION: &[&str] = &[
+    "lamentamos",
+    "no ha sido seleccionad",
+    "no has sido seleccionad",
+    "no continuar",
+    "otros candidatos",
+    "otras candidaturas",
+    "descartad",
+    "unfortunately",
+    "not moving forward",
+    "not to move forward",
+    "other candidates",
+    "not been selected",
+    "regret to inform",
+];
+const OFFER: &[&str] = &[
+    "propuesta de contratación",
+    "carta de oferta",
+    "nos complace ofrecerte",
+    "job offer",
+    "offer letter",
+    "pleased to offer",
+    "happy to offer",
+];
+const INTERVIEW: &[&str] = &[
+    "entrevista",
+    "videollamada",
+    "llamada telefónica",
+    "agendar una llamada",
+    "interview",
+    "phone screen",
+    "schedule a call",
+];
+const CONFIRMATION: &[&str] = &[
+    "hemos recibido tu candidatura",
+    "hemos recibido su candidatura",
+    "gracias por tu candidatura",
+    "gracias por inscribirte",
+    "te has inscrito",
+    "candidatura recibida",
+    "application received",
+    "thank you for applying",
+    "thanks for applying",
+    "received your application",
+    "application has been received",
+];
+// Sent and not yet settled, from least to most advanced
+const OPEN_STATUSES: &[&str] = &[
+    "applied",
+    "submitted_ambiguous",
+    "acknowledged",
+    "interview_scheduled",
+    "interviewed",
+    "offered",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MailKind {
+    Confirmation,
+    Interview,
+    Offer,
+    Rejection,
+}
+
+impl MailKind {
+    fn status(self) -> &'static str {
+        match self {
+            MailKind::Confirmation => "acknowledged",
+            MailKind::Interview => "interview_scheduled",
+            MailKind::Offer => "offered",
+            MailKind::Rejection => "rejected",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            MailKind::Confirmation => "mail-confirmation",
+            MailKind::Interview => "mail-interview",
+            MailKind::Offer => "mail-offer",
+            MailKind::Rejection => "mail-rejection",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MailMatch {
+    pub message_id: String,
+    pub received_at: Option<DateTime<Utc>>,
+    pub from: String,
+    pub subject: String,
+    pub kind: Option<MailKind>,
+    pub application_id: Option<i64>,
+    pub company: Option<String>,
+    // What the application was moved to (or queued to move to)
+    pub status: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct State {
+    uid_validity: Option<u32>,
+    last_uid: u32,
+    last_check: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    // Newest first
+    recent: Vec<MailMatch>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MailWatchStatus {
+    pub settings: MailSettings,
+    pub has_password: bool,
+    pub last_check: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub recent: Vec<MailMatch>,
+}
+
+static CHECKING: Mutex<()> = Mutex::new(());
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join(STATE_FILE))
+}
+
+fn load(app: &AppHandle) -> State {
+    state_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, state: &State) -> Result<(), String> {
+    let path = state_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())?;
+    let _ = app.emit(MAIL_WATCH_CHANGED, ());
+    Ok(())
+}
+
+fn keychain_entry(username: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, username).map_err(|e| e.to_string())
+}
+
+fn password(username: &str) -> Option<String> {
+    keychain_entry(username).ok()?.get_password().ok()
+}
+
+fn classify(text: &str) -> Option<MailKind> {
+    [
+        (REJECTION, MailKind::Rejection),
+        (OFFER, MailKind::Offer),
+        (INTERVIEW, MailKind::Interview),
+        (CONFIRMATION, MailKind::Confirmation),
+    ]
+    .into_iter()
+    .find(|(words, _)| words.iter().any(|w| text.contains(w)))
+    .map(|(_, kind)| kind)
+}
+
+fn rank(status: &str) -> usize {
+    match status {
+        "applied" | "submitted_ambiguous" => 0,
+        other => OPEN_STATUSES.iter().position(|s| *s == other).unwrap_or(0),
+    }
+}
+
+// The sent application the message is about: its company named in the
+// text or sender's domain, the role too if there's a choice, then the most
+// recently sent.
+fn find_application(
+    db: &Connection,
+    text: &str,
+    domain: &str,
+) -> Result<Option<(i64, String, String)>, String> {
+    let placeholders = vec!["?"; OPEN_STATUSES.len()].join(", ");
+    let mut statement = db
+        .prepare(&format!(
+            "SELECT id, company, role, status FROM applications WHERE status IN ({placeholders})
+             ORDER BY COALESCE(submitted_at, created_at) DESC"
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map(rusqlite::params_from_iter(OPEN_STATUSES), |r| {
+            Ok((
+                r.get::<_, i64>(0)?,
+                r.get::<_, String>(1)?,
+                r.get::<_, Option<String>>(2)?,
+                r.get::<_, String>(3)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let domain = domain.replace(['-', '.'], "");
+    let mut best: Option<(usize, (i64, String, String))> = None;
+    for row in rows {
+        let (id, company, role, status) = row.map_err(|e| e.to_string())?;
+        let name = company_name(normalize(&company).trim());
+        if name.len() < 3 {
+            continue;
+        }
+        let named = text.contains(&format!(" {name} "));
+        if !named && !domain.contains(&name.replace(' ', "")) {
+            continue;
+        }
+        let role_named = role
+            .map(|r| normalize(&r))
+            .is_some_and(|r| r.trim().len() >= 3 && text.contains(&r));
+        let score = 1 + usize::from(named) + usize::from(role_named);
+        if best.as_ref().is_none_or(|(s, _)| score > *s) {
+            best = Some((score, (id, company, status)));
+        }
+    }
+    Ok(best.map(|(_, found)| found))
+}
+
+// Moves the application on, never back; with the engine down, queued.
+fn advance(
+    app: &AppHandle,
+    id: i64,
+    company: &str,
+    current: &str,
+    kind: MailKind,
+) -> Option<String> {
+    let status = kind.status();
+    let forward = match kind {
+        MailKind::Rejection => true,
+        _ => rank(status) > rank(current),
+    };
+    if !forward {
+        return None;
+    }
+    let path = format!("/api/applications/{id}/status");
+    let body = json!({ "status": status });
+    match backend::send_json("PATCH", &path, Some(&body)) {
+        Ok(_) => Some(status.to_string()),
+        Err(Failure::Unreachable(_)) => {
+            let label = format!("Mark {company} as {status} (from mail)");
+            match offlinequeue::queue(app, label, "PATCH", path, Some(body)) {
+                Ok(_) => Some(status.to_string()),
+                Err(e) => {
+                    warn!(id, "could not queue a status change from mail: {e}");
+                    None
+                }
+            }
+        }
+        Err(Failure::Refused(code, detail)) => {
+            warn!(id, code, "status change from mail refused: {detail}");
+            None
+        }
+    }
+}
+
+fn body_text(mail: &mailparse::ParsedMail) -> String {
+    if mail.subparts.is_empty() {
+        return match mail.ctype.mimetype.as_str() {
+            "text/plain" | "text/html" => mail.get_body().unwrap_or_default(),
+            _ => String::new(),
+        };
+    }
+    mail.subparts
+        .iter()
+        .map(body_text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn process(app: &AppHandle, cfg: &MailSettings, raw: &[u8]) -> Result<MailMatch, String> {
+    let mail = mailparse::parse_mail(raw).map_err(|e| e.to_string())?;
+    let headers = mail.get_headers();
+    let subject = headers.get_first_value("Subject").unwrap_or_default();
+    let from = headers.get_first_value("From").unwrap_or_default();
+    let received_at = headers
+        .get_first_value("Date")
+        .and_then(|d| mailparse::dateparse(&d).ok())
+        .and_then(|secs| DateTime::from_timestamp(secs, 0));
+    let message_id = headers
+        .get_first_value("Message-ID")
+        .unwrap_or_else(|| format!("{from}|{subject}"));
+    let domain = from
+        .rsplit('@')
+        .next()
+        .unwrap_or("")
+        .trim_end_matches('>')
+        .to_lowercase();
+    let body = body_text(&mail);
+    // The wording lists keep their accents; company names are matched without
+    let kind = classify(&format!("{subject}\n{body}").to_lowercase());
+    let text = normalize(&format!("{subject}\n{from}\n{body}"));
+    let mut found = MailMatch {
+        message_id,
+        received_at,
+        from,
+        subject,
+        kind,
+        application_id: None,
+        company: None,
+        status: None,
+    };
+    let Some(kind) = kind else {
+        return Ok(found);
+    };
+    if let Some((id, company, current)) = find_application(&history::open(app)?, &text, &domain)? {
+        if cfg.update_status {
+            found.status = advance(app, id, &company, &current, kind);
+        }
+        found.application_id = Some(id);
+        found.company = Some(company);
+    }
+    let company = found.company.clone().unwrap_or_else(|| found.from.clone());
+    notifications::notify_route(
+        app,
+        &t_args(
+            &format!("{}-title", kind.key()),
+            &[("company", company.into())],
+        ),
+        &found.subject,
+        "/applications",
+    );
+    info!(kind = ?kind, application = found.application_id, "application mail");
+    Ok(found)
+}
+
+fn check(app: &AppHandle) -> Result<usize, String> {
+    let _guard = CHECKING.lock().unwrap();
+    let cfg = settings::get(app).mail;
+    if !cfg.enabled {
+        return Ok(0);
+    }
+    let result = read_folder(app, &cfg);
+    let mut state = load(app);
+    state.last_check = Some(Utc::now());
+    let count = match result {
+        Ok((uid_validity, last_uid, found)) => {
+            state.uid_validity = uid_validity;
+            state.last_uid = last_uid;
+            state.last_error = None;
+            let count = found.len();
+            for item in found {
+                state.recent.retain(|m| m.message_id != item.message_id);
+                state.recent.insert(0, item);
+            }
+            state.recent.truncate(RECENT);
+            Ok(count)
+        }
+        Err(e) => {
+            state.last_error = Some(e.clone());
+            Err(e)
+        }
+    };
+    save(app, &state)?;
+    count
+}
+
+type FolderResult = (Option<u32>, u32, Vec<MailMatch>);
+
+fn read_folder(app: &AppHandle, cfg: &MailSettings) -> Result<FolderResult, String> {
+    let password = password(&cfg.username).ok_or("no password saved for the mailbox")?;
+    let client = imap::ClientBuilder::new(cfg.host.trim(), cfg.port)
+        .connect()
+        .map_err(|e| e.to_string())?;
+    let mut session = client
+        .login(&cfg.username, &password)
+        .map_err(|(e, _)| e.to_string())?;
+    audit::record(
+        app,
+        Action::CredentialUsed,
+        json!({ "service": KEYCHAIN_SERVICE, "account": cfg.username, "for": "mail" }),
+    );
+    // Read-only: nothing is marked as read
+    let mailbox = session.examine(&cfg.folder).map_err(|e| e.to_string())?;
+    let state = load(app);
+    let fresh = state.last_uid == 0 || state.uid_validity != mailbox.uid_validity;
+    let query = if fresh {
+        let since = Utc::now() - chrono::Duration::days(FIRST_LOOK_DAYS);
+        format!("SINCE {}", since.format("%d-%b-%Y"))
+    } else {
+        format!("UID {}:*", state.last_uid + 1)
+    };
+    let mut uids: Vec<u32> = session
+        .uid_search(query)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        // `n:*` always returns the newest message, even if older than n
+        .filter(|uid| fresh || *uid > state.last_uid)
+        .collect();
+    uids.sort_unstable();
+    uids.truncate(MAX_MESSAGES);
+    let mut last_uid = if fresh { 0 } else { state.last_uid };
+    let mut found = Vec::new();
+    for uid in uids {
+        let query = "(UID RFC822.SIZE BODY.PEEK[HEADER])";
+        let fetches = session
+            .uid_fetch(uid.to_string(), query)
+            .map_err(|e| e.to_string())?;
+        let Some(fetch) = fetches.iter().next() else {
+            continue;
+        };
+        let raw = if fetch.size.unwrap_or(0) <= MAX_MESSAGE_BYTES {
+            let whole = session
+                .uid_fetch(uid.to_string(), "BODY.PEEK[]")
+                .map_err(|e| e.to_string())?;
+            whole
+                .iter()
+                .next()
+                .and_then(|f| f.body().map(<[u8]>::to_vec))
+        } else {
+            fetch.header().map(<[u8]>::to_vec)
+        };
+        if let Some(raw) = raw {
+            // Already handled before the folder's UIDs were renumbered
+            let id = mailparse::parse_headers(&raw)
+                .ok()
+                .and_then(|(h, _)| h.get_first_value("Message-ID"));
+            let seen = id.is_some_and(|id| state.recent.iter().any(|m| m.message_id == id));
+            if !seen {
+                match process(app, cfg, &raw) {
+                    Ok(item) => found.push(item),
+                    Err(e) => warn!(uid, "mail not understood: {e}"),
+                }
+            }
+        }
+        last_uid = last_uid.max(uid);
+    }
+    let _ = session.logout();
+    Ok((mailbox.uid_validity, last_uid, found))
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let cfg = settings::get(&app).mail;
+        let due = load(&app).last_check.is_none_or(|last| {
+            Utc::now() - last >= chrono::Duration::minutes(i64::from(cfg.interval_minutes))
+        });
+        if cfg.enabled && due {
+            if let Err(e) = check(&app) {
+                warn!("mailbox check failed: {e}");
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_mail_watch(app: AppHandle) -> MailWatchStatus {
+    let cfg = settings::get(&app).mail;
+    let state = load(&app);
+    MailWatchStatus {
+        has_password: !cfg.username.is_empty() && password(&cfg.username).is_some(),
+        settings: cfg,
+        last_check: state.last_check,
+        last_error: state.last_error,
+        recent: state.recent,
+    }
+}
+
+// `password`: Some("") deletes the stored one, None leaves it unchanged.
+#[tauri::command]
+pub fn set_mail_watch(
+    app: AppHandle,
+    mail: MailSettings,
+    password: Option<String>,
+) -> Result<(), String> {
+    let mail = MailSettings {
+        host: mail.host.trim().to_string(),
+        username: mail.username.trim().to_string(),
+        folder: match mail.folder.trim() {
+            "" => MailSettings::default().folder,
+            folder => folder.to_string(),
+        },
+        ..mail
+    };
+    if mail.enabled && (mail.host.is_empty() || mail.port == 0 || mail.username.is_empty()) {
+        return Err("watching the mailbox needs a server, port and user".into());
+    }
+    if let Some(pass) = password.filter(|_| !mail.username.is_empty()) {
+        let entry = keychain_entry(&mail.username)?;
+        if pass.is_empty() {
+            let _ = entry.delete_credential();
+        } else {
+            entry.set_password(&pass).map_err(|e| e.to_string())?;
+        }
+    }
+    let previous = settings::get(&app).mail;
+    let moved = previous.host != mail.host
+        || previous.username != mail.username
+        || previous.folder != mail.folder;
+    settings::update(&app, |s| s.mail = mail)?;
+    // Another folder starts from its last FIRST_LOOK_DAYS again
+    if moved {
+        save(&app, &State::default())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn check_mail_now(app: AppHandle) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || check(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Reset → saved passwords.
+pub fn forget_password(app: &AppHandle) {
+    let username = settings::get(app).mail.username;
+    if let Ok(entry) = keychain_entry(&username) {
+        let _ = entry.delete_credential();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (id, company, role, status, submitted) in [
+            (
+                1,
+                "Acme Logistics SL",
+                "Backend Engineer",
+                "applied",
+                "2026-09-01",
+            ),
+            (
+                2,
+                "Acme Logistics SL",
+                "Data Engineer",
+                "applied",
+                "2026-09-10",
+            ),
+            (3, "Initech", "Analyst", "rejected", "2026-09-12"),
+            (4, "Globex", "Engineer", "interviewed", "2026-09-05"),
+        ] {
+            db.execute(
+                "INSERT INTO applications (id, company, role, status, submitted_at, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, '')",
+                rusqlite::params![id, company, role, status, submitted],
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    fn found(text: &str, domain: &str) -> Option<i64> {
+        find_application(&db(), &normalize(text), domain)
+            .unwrap()
+            .map(|(id, _, _)| id)
+    }
+
+    #[test]
+    fn wording_sorts_the_mail() {
+        let kind = |text: &str| classify(&text.to_lowercase());
+        assert_eq!(
+            kind("Unfortunately, we'll proceed with other candidates"),
+            Some(MailKind::Rejection)
+        );
+        assert_eq!(
+            kind("Nos complace ofrecerte el puesto"),
+            Some(MailKind::Offer)
+        );
+        assert_eq!(
+            kind("¿Podemos agendar una llamada?"),
+            Some(MailKind::Interview)
+        );
+        assert_eq!(
+            kind("Hemos recibido tu candidatura"),
+            Some(MailKind::Confirmation)
+        );
+        // A rejection that mentions the interview is still a rejection
+        assert_eq!(
+            kind("After your interview we regret to inform you..."),
+            Some(MailKind::Rejection)
+        );
+        assert_eq!(kind("Weekly newsletter"), None);
+    }
+
+    #[test]
+    fn statuses_only_move_forward() {
+        assert!(rank("interview_scheduled") > rank("acknowledged"));
+        assert!(rank("offered") > rank("interviewed"));
+        assert_eq!(rank("applied"), rank("submitted_ambiguous"));
+    }
+
+    #[test]
+    fn application_by_company_role_and_recency() {
+        // Both Acme ones are named; the newer wins
+        assert_eq!(
+            found("Your application to Acme Logistics", "mail.example"),
+            Some(2)
+        );
+        // The role settles it
+        assert_eq!(
+            found("Acme Logistics: Backend Engineer", "mail.example"),
+            Some(1)
+        );
+        // From the sender's domain alone
+        assert_eq!(found("Thanks for applying", "globex.com"), Some(4));
+        // Closed applications aren't matched
+        assert_eq!(found("Initech update", "initech.com"), None);
+    }
+
+    #[test]
+    fn text_from_every_part() {
+        let raw = b"Content-Type: multipart/alternative; boundary=b\r\n\r\n\
+--b\r\nContent-Type: text/plain\r\n\r\nHello plain\r\n\
+--b\r\nContent-Type: image/png\r\n\r\nPNGDATA\r\n\
+--b\r\nContent-Type: text/html\r\n\r\n<p>Hello html</p>\r\n--b--\r\n";
+        let mail = mailparse::parse_mail(raw).unwrap();
+        let text = body_text(&mail);
+        assert!(text.contains("Hello plain") && text.contains("Hello html"));
+        assert!(!text.contains("PNGDATA"));
+    }
+}
@@ -0,0 +1,84 @@
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::settings;
+
+// macOS menu-bar-only mode (`menu_bar_only` in settings.json): while the main
+// window is hidden the app takes the Accessory activation policy, so it has
+// no Dock icon and lives in the menu bar alone. Showing the window restores
+// the Regular policy, for Cmd-Tab and the app menu. Nothing happens
+// elsewhere, where the tray is the only icon anyway.
+
+// Before the main window is shown.
+pub fn on_shown(app: &AppHandle) {
+    platform::set_dock_icon(app, true);
+}
+
+// After the main window is hidden.
+pub fn on_hidden(app: &AppHandle) {
+    if settings::get(app).menu_bar_only {
+        platform::set_dock_icon(app, false);
+    }
+}
+
+// At startup and when the setting changes.
+pub fn refresh(app: &AppHandle) {
+    // Onboarding and other windows count too
+    let visible = app
+        .webview_windows()
+        .values()
+        .any(|w| w.is_visible().unwrap_or(false));
+    if visible {
+        on_shown(app);
+    } else if settings::get(app).menu_bar_only {
+        on_hidden(app);
+    } else {
+        platform::set_dock_icon(app, true);
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MenuBarStatus {
+    // Only macOS has a Dock icon to hide
+    pub supported: bool,
+    pub menu_bar_only: bool,
+}
+
+#[tauri::command]
+pub fn get_menu_bar_status(app: AppHandle) -> MenuBarStatus {
+    MenuBarStatus {
+        supported: cfg!(target_os = "macos"),
+        menu_bar_only: settings::get(&app).menu_bar_only,
+    }
+}
+
+#[tauri::command]
+pub fn set_menu_bar_only(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update(&app, |s| s.menu_bar_only = enabled)?;
+    refresh(&app);
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use tauri::{ActivationPolicy, AppHandle};
+    use tracing::warn;
+
+    pub fn set_dock_icon(app: &AppHandle, visible: bool) {
+        let policy = if visible {
+            ActivationPolicy::Regular
+        } else {
+            ActivationPolicy::Accessory
+        };
+        if let Err(e) = app.set_activation_policy(policy) {
+            warn!("activation policy not set: {e}");
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use tauri::AppHandle;
+
+    pub fn set_dock_icon(_app: &AppHandle, _visible: bool) {}
+}
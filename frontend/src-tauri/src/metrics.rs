@@ -0,0 +1,199 @@
+use serde::Serialize;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, WebviewWindow};
+use tracing::info;
+
+use crate::scope::MAIN_WINDOW;
+
+// Startup milestones and a few live numbers for the diagnostics page. Times
+// are milliseconds since the process started; memory is resident size.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize)]
+pub struct Startup {
+    pub tray_ms: Option<u64>,
+    pub backend_ready_ms: Option<u64>,
+    pub first_paint_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Metrics {
+    pub uptime_secs: u64,
+    pub startup: Startup,
+    pub engine_starts: u64,
+    // From the latest engine start to its event stream answering
+    pub engine_ready_ms: Option<u64>,
+    pub shell_memory_bytes: Option<u64>,
+    pub backend_memory_bytes: Option<u64>,
+    pub events_total: u64,
+    // Backend events in the last minute
+    pub events_per_minute: usize,
+}
+
+struct Marks {
+    tray: Option<Duration>,
+    backend_ready: Option<Duration>,
+    first_paint: Option<Duration>,
+    engine_started: Option<Instant>,
+    engine_ready: Option<Duration>,
+}
+
+static START: OnceLock<Instant> = OnceLock::new();
+static MARKS: Mutex<Marks> = Mutex::new(Marks {
+    tray: None,
+    backend_ready: None,
+    first_paint: None,
+    engine_started: None,
+    engine_ready: None,
+});
+static ENGINE_STARTS: AtomicU64 = AtomicU64::new(0);
+static EVENTS_TOTAL: AtomicU64 = AtomicU64::new(0);
+static RECENT_EVENTS: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+
+// First thing in `run`; everything is measured from here.
+pub fn init() {
+    START.get_or_init(Instant::now);
+}
+
+fn since_start() -> Duration {
+    START.get().map(Instant::elapsed).unwrap_or_default()
+}
+
+fn millis(d: Option<Duration>) -> Option<u64> {
+    d.map(|d| d.as_millis() as u64)
+}
+
+// Sets a startup milestone once; later calls are ignored.
+fn mark(name: &str, slot: impl FnOnce(&mut Marks) -> &mut Option<Duration>) {
+    let elapsed = since_start();
+    let mut marks = MARKS.lock().unwrap();
+    let slot = slot(&mut marks);
+    if slot.is_none() {
+        *slot = Some(elapsed);
+        info!(ms = elapsed.as_millis() as u64, "{name}");
+    }
+}
+
+pub fn tray_ready() {
+    mark("tray ready", |m| &mut m.tray);
+}
+
+pub fn engine_started() {
+    ENGINE_STARTS.fetch_add(1, Ordering::Relaxed);
+    let mut marks = MARKS.lock().unwrap();
+    marks.engine_started = Some(Instant::now());
+    marks.engine_ready = None;
+}
+
+// The event stream connected, so the engine is serving requests.
+pub fn engine_ready() {
+    mark("engine ready", |m| &mut m.backend_ready);
+    let mut marks = MARKS.lock().unwrap();
+    if marks.engine_ready.is_none() {
+        marks.engine_ready = marks.engine_started.map(|t| t.elapsed());
+    }
+}
+
+pub fn engine_starts() -> u64 {
+    ENGINE_STARTS.load(Ordering::Relaxed)
+}
+
+pub fn event_received() {
+    EVENTS_TOTAL.fetch_add(1, Ordering::Relaxed);
+    let now = Instant::now();
+    let mut recent = RECENT_EVENTS.lock().unwrap();
+    recent.push_back(now);
+    while recent
+        .front()
+        .is_some_and(|t| now.duration_since(*t) > RATE_WINDOW)
+    {
+        recent.pop_front();
+    }
+}
+
+fn events_per_minute() -> usize {
+    let recent = RECENT_EVENTS.lock().unwrap();
+    recent.iter().filter(|t| t.elapsed() <= RATE_WINDOW).count()
+}
+
+#[cfg(target_os = "linux")]
+fn resident_bytes(pid: u32) -> Option<u64> {
+    // Second field of statm: resident pages
+    let statm = std::fs::read_to_string(format!("/proc/{pid}/statm")).ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Some(pages * u64::try_from(page_size).ok()?)
+}
+
+#[cfg(target_os = "macos")]
+fn resident_bytes(pid: u32) -> Option<u64> {
+    let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let read = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTASKINFO,
+            0,
+            (&mut info as *mut libc::proc_taskinfo).cast(),
+            size,
+        )
+    };
+    (read == size).then_some(info.pti_resident_size)
+}
+
+#[cfg(windows)]
+fn resident_bytes(pid: u32) -> Option<u64> {
+    use windows::Win32::{
+        Foundation::CloseHandle,
+        System::{
+            ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+            Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
+        },
+    };
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut counters = PROCESS_MEMORY_COUNTERS::default();
+        let read = GetProcessMemoryInfo(
+            process,
+            &mut counters,
+            std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+        );
+        let _ = CloseHandle(process);
+        read.ok()?;
+        Some(counters.WorkingSetSize as u64)
+    }
+}
+
+// Called by the webview once the main window has drawn its first frame.
+#[tauri::command]
+pub fn mark_first_paint(window: WebviewWindow) {
+    if window.label() == MAIN_WINDOW {
+        mark("first paint", |m| &mut m.first_paint);
+    }
+}
+
+#[tauri::command]
+pub fn get_metrics(app: AppHandle) -> Metrics {
+    let marks = MARKS.lock().unwrap();
+    Metrics {
+        uptime_secs: since_start().as_secs(),
+        startup: Startup {
+            tray_ms: millis(marks.tray),
+            backend_ready_ms: millis(marks.backend_ready),
+            first_paint_ms: millis(marks.first_paint),
+        },
+        engine_starts: engine_starts(),
+        engine_ready_ms: millis(marks.engine_ready),
+        shell_memory_bytes: resident_bytes(std::process::id()),
+        backend_memory_bytes: crate::backend_pid(&app).and_then(resident_bytes),
+        events_total: EVENTS_TOTAL.load(Ordering::Relaxed),
+        events_per_minute: events_per_minute(),
+    }
+}
@@ -0,0 +1,663 @@
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+use tauri::{AppHandle, Url};
+use tracing::{info, warn};
+
+use crate::{
+    clipwatch, history, paths,
+    settings::{self, BrowserExtensionSettings},
+};
+
+// The native messaging host for the companion browser extension, which adds
+// the posting being viewed to the jobs and asks what became of applications
+// for the page. Browsers start the host from a manifest registered per user
+// (Chrome, Chromium and Edge by the extension's ID, Firefox by its own) and
+// talk to it over stdin and stdout, each message a JSON document behind its
+// length. The host is this same executable, told apart by the arguments the
+// browser passes; it can't reach the engine itself (the token is the running
+// app's), so it relays each message to the running app over a loopback
+// socket whose port and key are in `native-host.json`, readable only by the
+// user. Off until turned on in Settings; registering follows the setting.
+pub const HOST_NAME: &str = "com.currobot.jobbot";
+const MANIFEST_FILE: &str = "com.currobot.jobbot.json";
+const FIREFOX_EXTENSION_ID: &str = "jobbot@currobot.app";
+const BRIDGE_FILE: &str = "native-host.json";
+// Chrome's limit for what the host sends; ours stay far below
+const MAX_MESSAGE: usize = 1024 * 1024;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+// Adding a job fetches the page when the extension sends no title
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+const NOT_RUNNING: &str = "JobBot isn't running";
+
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Family {
+    Chromium,
+    Firefox,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Bridge {
+    port: u16,
+    key: String,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    Ping,
+    AddJob {
+        url: String,
+        #[serde(default)]
+        title: Option<String>,
+        #[serde(default)]
+        company: Option<String>,
+    },
+    Status {
+        url: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct PageApplication {
+    id: i64,
+    company: String,
+    role: Option<String>,
+    status: String,
+    updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrowserExtensionStatus {
+    pub settings: BrowserExtensionSettings,
+    // Browsers the host is registered with
+    pub registered: Vec<&'static str>,
+    pub firefox_extension_id: &'static str,
+}
+
+fn bridge_path() -> Option<PathBuf> {
+    Some(paths::early_base_config_dir()?.join(BRIDGE_FILE))
+}
+
+fn read_message(input: &mut impl Read) -> io::Result<Option<Value>> {
+    let mut len = [0u8; 4];
+    match input.read_exact(&mut len) {
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        read => read?,
+    }
+    let len = u32::from_ne_bytes(len) as usize;
+    if len > MAX_MESSAGE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message too large",
+        ));
+    }
+    let mut message = vec![0; len];
+    input.read_exact(&mut message)?;
+    serde_json::from_slice(&message)
+        .map(Some)
+        .map_err(io::Error::other)
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let bytes = serde_json::to_vec(message).map_err(io::Error::other)?;
+    output.write_all(&(bytes.len() as u32).to_ne_bytes())?;
+    output.write_all(&bytes)?;
+    output.flush()
+}
+
+// Chrome passes the extension's origin, Firefox the manifest's path.
+pub fn is_host_launch() -> bool {
+    std::env::args().skip(1).any(|a| {
+        a.starts_with("chrome-extension://")
+            || Path::new(&a)
+                .file_name()
+                .is_some_and(|n| n == MANIFEST_FILE)
+    })
+}
+
+// The host, in place of the app, until the browser closes stdin. Nothing
+// else may write to stdout.
+pub fn serve_stdio() {
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+    while let Ok(Some(request)) = read_message(&mut input) {
+        let reply = relay(&request).unwrap_or_else(|e| json!({ "ok": false, "error": e }));
+        if write_message(&mut output, &reply).is_err() {
+            break;
+        }
+    }
+}
+
+fn relay(request: &Value) -> Result<Value, String> {
+    let bridge: Bridge = bridge_path()
+        .and_then(|p| fs::read(p).ok())
+        .and_then(|b| serde_json::from_slice(&b).ok())
+        .ok_or(NOT_RUNNING)?;
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, bridge.port));
+    let mut stream =
+        TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).map_err(|_| NOT_RUNNING)?;
+    stream
+        .set_read_timeout(Some(REPLY_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    let message = json!({ "key": bridge.key, "request": request });
+    writeln!(stream, "{message}").map_err(|e| e.to_string())?;
+    let mut reply = String::new();
+    BufReader::new(stream)
+        .take(MAX_MESSAGE as u64)
+        .read_line(&mut reply)
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&reply).map_err(|e| e.to_string())
+}
+
+// The page's address as applications store it, without the fragment or a
+// trailing slash.
+fn page(url: &str) -> Option<String> {
+    let mut url = Url::parse(url.trim()).ok()?;
+    url.set_fragment(None);
+    Some(url.as_str().trim_end_matches('/').to_string())
+}
+
+fn applications_for(db: &Connection, url: &str) -> Result<Vec<PageApplication>, String> {
+    let Some(wanted) = page(url) else {
+        return Ok(Vec::new());
+    };
+    let rows = db
+        .prepare(
+            "SELECT id, company, role, status, COALESCE(updated_at, recorded_at), url
+             FROM applications WHERE url IS NOT NULL ORDER BY id DESC",
+        )
+        .and_then(|mut s| {
+            s.query_map([], |r| {
+                Ok((
+                    PageApplication {
+                        id: r.get(0)?,
+                        company: r.get(1)?,
+                        role: r.get(2)?,
+                        status: r.get(3)?,
+                        updated_at: r.get(4)?,
+                    },
+                    r.get::<_, String>(5)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .filter(|(_, stored)| page(stored).as_deref() == Some(wanted.as_str()))
+        .map(|(application, _)| application)
+        .collect())
+}
+
+fn answer(app: &AppHandle, request: &Value) -> Result<Value, String> {
+    let request: Request = serde_json::from_value(request.clone()).map_err(|e| e.to_string())?;
+    match request {
+        Request::Ping => {
+            Ok(json!({ "ok": true, "version": app.package_info().version.to_string() }))
+        }
+        Request::AddJob {
+            url,
+            title,
+            company,
+        } => {
+            let intake = clipwatch::send_to_intake(
+                app,
+                json!({ "url": url, "source": "extension", "title": title, "company": company }),
+            )?;
+            info!("job added from the browser extension");
+            Ok(json!({ "ok": true, "intake": intake }))
+        }
+        Request::Status { url } => {
+            Ok(json!({ "ok": true, "applications": applications_for(&history::open(app)?, &url)? }))
+        }
+    }
+}
+
+fn handle(app: &AppHandle, stream: TcpStream, key: &str) -> Result<(), String> {
+    stream
+        .set_read_timeout(Some(REPLY_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    let mut line = String::new();
+    BufReader::new(&stream)
+        .take(MAX_MESSAGE as u64)
+        .read_line(&mut line)
+        .map_err(|e| e.to_string())?;
+    let message: Value = serde_json::from_str(&line).map_err(|e| e.to_string())?;
+    let reply = if message["key"].as_str() != Some(key) {
+        json!({ "ok": false, "error": "not allowed" })
+    } else if !settings::get(app).browser_extension.enabled {
+        json!({ "ok": false, "error": "the browser extension is turned off in JobBot" })
+    } else {
+        answer(app, &message["request"]).unwrap_or_else(|e| json!({ "ok": false, "error": e }))
+    };
+    writeln!(&stream, "{reply}").map_err(|e| e.to_string())
+}
+
+fn write_bridge(bridge: &Bridge) -> Result<(), String> {
+    let path = bridge_path().ok_or("no config folder")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path).map_err(|e| e.to_string())?;
+    let json = serde_json::to_vec(bridge).map_err(|e| e.to_string())?;
+    file.write_all(&json).map_err(|e| e.to_string())
+}
+
+// Once per run; the listener outlives turning the setting off, and refuses.
+fn listen(app: &AppHandle) {
+    if LISTENING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    thread::spawn(move || {
+        let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, 0)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!("browser extension bridge not started: {e}");
+                LISTENING.store(false, Ordering::SeqCst);
+                return;
+            }
+        };
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let key: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        let port = listener.local_addr().map(|a| a.port()).unwrap_or_default();
+        if let Err(e) = write_bridge(&Bridge {
+            port,
+            key: key.clone(),
+        }) {
+            warn!("browser extension bridge not published: {e}");
+        }
+        for stream in listener.incoming().flatten() {
+            let app = app.clone();
+            let key = key.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle(&app, stream, &key) {
+                    warn!("browser extension request failed: {e}");
+                }
+            });
+        }
+    });
+}
+
+fn manifest(family: Family, cfg: &BrowserExtensionSettings) -> Result<Value, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let mut manifest = json!({
+        "name": HOST_NAME,
+        "description": "JobBot",
+        "path": exe,
+        "type": "stdio",
+    });
+    match family {
+        Family::Chromium => {
+            manifest["allowed_origins"] = cfg
+                .chrome_extension_ids
+                .iter()
+                .map(|id| format!("chrome-extension://{id}/"))
+                .collect();
+        }
+        Family::Firefox => manifest["allowed_extensions"] = json!([FIREFOX_EXTENSION_ID]),
+    }
+    Ok(manifest)
+}
+
+// Registers with every installed browser the settings allow; the path is
+// refreshed at each start, for a moved or updated app.
+fn register(cfg: &BrowserExtensionSettings) -> Result<Vec<&'static str>, String> {
+    let mut registered = Vec::new();
+    for &(browser, family, location) in platform::BROWSERS {
+        if family == Family::Chromium && cfg.chrome_extension_ids.is_empty() {
+            platform::remove(family, location);
+            continue;
+        }
+        if platform::install(family, location, &manifest(family, cfg)?)? {
+            registered.push(browser);
+        }
+    }
+    Ok(registered)
+}
+
+pub fn unregister() {
+    for &(_, family, location) in platform::BROWSERS {
+        platform::remove(family, location);
+    }
+    if let Some(path) = bridge_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn registered() -> Vec<&'static str> {
+    platform::BROWSERS
+        .iter()
+        .filter(|(_, family, location)| platform::is_installed(*family, location))
+        .map(|(browser, _, _)| *browser)
+        .collect()
+}
+
+pub fn start(app: AppHandle) {
+    let cfg = settings::get(&app).browser_extension;
+    if !cfg.enabled {
+        return;
+    }
+    if let Err(e) = register(&cfg) {
+        warn!("browser extension host not registered: {e}");
+    }
+    listen(&app);
+}
+
+#[tauri::command]
+pub fn get_browser_extension(app: AppHandle) -> BrowserExtensionStatus {
+    BrowserExtensionStatus {
+        settings: settings::get(&app).browser_extension,
+        registered: registered(),
+        firefox_extension_id: FIREFOX_EXTENSION_ID,
+    }
+}
+
+#[tauri::command]
+pub async fn set_browser_extension(
+    app: AppHandle,
+    enabled: bool,
+    chrome_extension_ids: Vec<String>,
+) -> Result<BrowserExtensionStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let ids: Vec<String> = chrome_extension_ids
+            .iter()
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect();
+        // Chrome's IDs: 32 letters from a to p
+        if let Some(bad) = ids
+            .iter()
+            .find(|id| id.len() != 32 || !id.bytes().all(|b| (b'a'..=b'p').contains(&b)))
+        {
+            return Err(format!("{bad} isn't an extension ID"));
+        }
+        let cfg = BrowserExtensionSettings {
+            enabled,
+            chrome_extension_ids: ids,
+        };
+        settings::update(&app, |s| s.browser_extension = cfg.clone())?;
+        if enabled {
+            let registered = register(&cfg)?;
+            info!(browsers = ?registered, "browser extension host registered");
+            listen(&app);
+        } else {
+            unregister();
+            info!("browser extension host unregistered");
+        }
+        Ok(get_browser_extension(app))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Manifests go in each browser's NativeMessagingHosts folder under the home
+// folder, for the browsers whose own folder is there.
+#[cfg(unix)]
+mod platform {
+    use serde_json::Value;
+    use std::{fs, path::PathBuf};
+
+    use super::{Family, MANIFEST_FILE};
+
+    #[cfg(target_os = "linux")]
+    pub const BROWSERS: &[(&str, Family, &str)] = &[
+        (
+            "Chrome",
+            Family::Chromium,
+            ".config/google-chrome/NativeMessagingHosts",
+        ),
+        (
+            "Chromium",
+            Family::Chromium,
+            ".config/chromium/NativeMessagingHosts",
+        ),
+        (
+            "Edge",
+            Family::Chromium,
+            ".config/microsoft-edge/NativeMessagingHosts",
+        ),
+        (
+            "Firefox",
+            Family::Firefox,
+            ".mozilla/native-messaging-hosts",
+        ),
+    ];
+
+    #[cfg(target_os = "macos")]
+    pub const BROWSERS: &[(&str, Family, &str)] = &[
+        (
+            "Chrome",
+            Family::Chromium,
+            "Library/Application Support/Google/Chrome/NativeMessagingHosts",
+        ),
+        (
+            "Chromium",
+            Family::Chromium,
+            "Library/Application Support/Chromium/NativeMessagingHosts",
+        ),
+        (
+            "Edge",
+            Family::Chromium,
+            "Library/Application Support/Microsoft Edge/NativeMessagingHosts",
+        ),
+        (
+            "Firefox",
+            Family::Firefox,
+            "Library/Application Support/Mozilla/NativeMessagingHosts",
+        ),
+    ];
+
+    fn folder(location: &str) -> Option<PathBuf> {
+        Some(PathBuf::from(std::env::var_os("HOME")?).join(location))
+    }
+
+    // False when the browser isn't there.
+    pub fn install(_family: Family, location: &str, manifest: &Value) -> Result<bool, String> {
+        let Some(folder) = folder(location) else {
+            return Ok(false);
+        };
+        if !folder.parent().is_some_and(|p| p.is_dir()) {
+            return Ok(false);
+        }
+        fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+        let json = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+        fs::write(folder.join(MANIFEST_FILE), json).map_err(|e| e.to_string())?;
+        Ok(true)
+    }
+
+    pub fn remove(_family: Family, location: &str) {
+        if let Some(folder) = folder(location) {
+            let _ = fs::remove_file(folder.join(MANIFEST_FILE));
+        }
+    }
+
+    pub fn is_installed(_family: Family, location: &str) -> bool {
+        folder(location).is_some_and(|f| f.join(MANIFEST_FILE).is_file())
+    }
+}
+
+// Windows finds the manifest through a registry value per browser; the files
+// themselves live in the config folder, one per family, since Firefox's
+// arguments name its manifest.
+#[cfg(windows)]
+mod platform {
+    use serde_json::Value;
+    use std::{fs, path::PathBuf};
+    use windows_registry::CURRENT_USER;
+
+    use super::{Family, HOST_NAME, MANIFEST_FILE};
+    use crate::paths;
+
+    pub const BROWSERS: &[(&str, Family, &str)] = &[
+        ("Chrome", Family::Chromium, r"Software\Google\Chrome"),
+        ("Chromium", Family::Chromium, r"Software\Chromium"),
+        ("Edge", Family::Chromium, r"Software\Microsoft\Edge"),
+        ("Firefox", Family::Firefox, r"Software\Mozilla"),
+    ];
+
+    fn manifest_path(family: Family) -> Option<PathBuf> {
+        let folder = match family {
+            Family::Chromium => "chromium",
+            Family::Firefox => "firefox",
+        };
+        Some(
+            paths::early_base_config_dir()?
+                .join("native-messaging")
+                .join(folder)
+                .join(MANIFEST_FILE),
+        )
+    }
+
+    fn key(location: &str) -> String {
+        format!(r"{location}\NativeMessagingHosts\{HOST_NAME}")
+    }
+
+    // False when the browser isn't there.
+    pub fn install(family: Family, location: &str, manifest: &Value) -> Result<bool, String> {
+        if CURRENT_USER.open(location).is_err() {
+            return Ok(false);
+        }
+        let path = manifest_path(family).ok_or("no config folder")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+        fs::write(&path, json).map_err(|e| e.to_string())?;
+        CURRENT_USER
+            .create(key(location))
+            .and_then(|k| k.set_string("", path.to_string_lossy()))
+            .map_err(|e| e.to_string())?;
+        Ok(true)
+    }
+
+    pub fn remove(family: Family, location: &str) {
+        let _ = CURRENT_USER.remove_tree(key(location));
+        if let Some(path) = manifest_path(family) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    pub fn is_installed(_family: Family, location: &str) -> bool {
+        CURRENT_USER.open(key(location)).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_behind_their_length() {
+        let mut wire = Vec::new();
+        write_message(&mut wire, &json!({ "type": "ping" })).unwrap();
+        write_message(
+            &mut wire,
+            &json!({ "type": "status", "url": "https://a.example" }),
+        )
+        .unwrap();
+        assert_eq!(&wire[..4], &15u32.to_ne_bytes());
+        let mut input = &wire[..];
+        assert_eq!(
+            read_message(&mut input).unwrap(),
+            Some(json!({ "type": "ping" }))
+        );
+        assert_eq!(
+            read_message(&mut input).unwrap().unwrap()["url"],
+            "https://a.example"
+        );
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn oversized_or_cut_messages() {
+        let mut large = &((MAX_MESSAGE as u32 + 1).to_ne_bytes())[..];
+        assert!(read_message(&mut large).is_err());
+        let mut cut = Vec::from(10u32.to_ne_bytes());
+        cut.extend_from_slice(b"{}");
+        assert!(read_message(&mut &cut[..]).is_err());
+    }
+
+    #[test]
+    fn requests_by_type() {
+        let add: Request = serde_json::from_value(
+            json!({ "type": "add_job", "url": "https://a.example/1", "title": "Cajero" }),
+        )
+        .unwrap();
+        assert!(matches!(
+            add,
+            Request::AddJob { title: Some(t), company: None, .. } if t == "Cajero"
+        ));
+        assert!(serde_json::from_value::<Request>(json!({ "type": "delete" })).is_err());
+    }
+
+    #[test]
+    fn applications_for_the_page() {
+        assert_eq!(
+            page(" https://jobs.example/offer/12/#apply").as_deref(),
+            Some("https://jobs.example/offer/12")
+        );
+        assert_eq!(page("not a url"), None);
+
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (id, url) in [
+            (1, "https://jobs.example/offer/12"),
+            (2, "https://jobs.example/offer/12/"),
+            (3, "https://jobs.example/offer/13"),
+        ] {
+            db.execute(
+                "INSERT INTO applications (id, company, status, url, recorded_at)
+                 VALUES (?1, 'Acme', 'applied', ?2, '2026-10-01')",
+                rusqlite::params![id, url],
+            )
+            .unwrap();
+        }
+        let ids: Vec<i64> = applications_for(&db, "https://jobs.example/offer/12#top")
+            .unwrap()
+            .iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(ids, [2, 1]);
+        assert!(applications_for(&db, "nothing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn manifests_name_the_allowed_extensions() {
+        let cfg = BrowserExtensionSettings {
+            enabled: true,
+            chrome_extension_ids: vec!["abcdefghijklmnop".into()],
+        };
+        let chromium = manifest(Family::Chromium, &cfg).unwrap();
+        assert_eq!(chromium["name"], HOST_NAME);
+        assert_eq!(chromium["type"], "stdio");
+        assert_eq!(
+            chromium["allowed_origins"],
+            json!(["chrome-extension://abcdefghijklmnop/"])
+        );
+        let firefox = manifest(Family::Firefox, &cfg).unwrap();
+        assert_eq!(firefox["allowed_extensions"], json!([FIREFOX_EXTENSION_ID]));
+        assert!(firefox.get("allowed_origins").is_none());
+    }
+}
@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use crate::proxy::{self, Resolved};
+
+// Client for calls leaving the machine (release manifests, downloads, notes).
+// Requests to the local backend go through backend.rs and never use a proxy.
+pub fn client(timeout: Duration) -> Result<reqwest::blocking::Client, String> {
+    let mut builder = reqwest::blocking::Client::builder().timeout(timeout);
+    match proxy::current() {
+        Resolved::Auto => {}
+        Resolved::Direct => builder = builder.no_proxy(),
+        Resolved::Url(url) => {
+            builder = builder.proxy(reqwest::Proxy::all(url).map_err(|e| e.to_string())?)
+        }
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+pub fn proxy_url() -> Option<String> {
+    match proxy::current() {
+        Resolved::Url(url) => Some(url),
+        Resolved::Auto | Resolved::Direct => None,
+    }
+}
@@ -0,0 +1,202 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{quotas, toast, trayicon};
+
+// The desktop notification plugin always reports "granted" and shows
+// notifications fire-and-forget, so a user who blocked JobBot in system
+// settings would silently miss everything. Permission is detected natively
+// here and blocked notifications are routed to the webview instead.
+pub const IN_APP_NOTIFICATION: &str = "in-app-notification";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationPermission {
+    Granted,
+    Denied,
+    // Not asked yet, or the platform can't tell (e.g. unbundled dev builds)
+    Unknown,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InAppNotification {
+    pub title: String,
+    pub body: String,
+}
+
+pub struct NotificationState {
+    permission: Mutex<NotificationPermission>,
+    unread: Mutex<u32>,
+}
+
+impl Default for NotificationState {
+    fn default() -> Self {
+        Self {
+            permission: Mutex::new(NotificationPermission::Unknown),
+            unread: Mutex::new(0),
+        }
+    }
+}
+
+pub fn refresh_permission(app: &AppHandle) -> NotificationPermission {
+    let permission = platform::detect(app);
+    *app.state::<NotificationState>().permission.lock().unwrap() = permission;
+    permission
+}
+
+pub fn permission(app: &AppHandle) -> NotificationPermission {
+    *app.state::<NotificationState>().permission.lock().unwrap()
+}
+
+// Single entry point for every notification the shell raises.
+pub fn notify(app: &AppHandle, title: &str, body: &str) {
+    use tauri_plugin_notification::NotificationExt;
+    if permission(app) == NotificationPermission::Denied {
+        show_in_app(app, title, body);
+        return;
+    }
+    let shown = app.notification().builder().title(title).body(body).show();
+    if shown.is_err() {
+        show_in_app(app, title, body);
+    }
+}
+
+// `notify` for a notification about a page, which a click opens where the
+// platform allows (`toast`).
+pub fn notify_route(app: &AppHandle, title: &str, body: &str, route: &str) {
+    if permission(app) == NotificationPermission::Denied || !toast::show(app, title, body, route) {
+        notify(app, title, body);
+    }
+}
+
+pub fn show_in_app(app: &AppHandle, title: &str, body: &str) {
+    let _ = app.emit(
+        IN_APP_NOTIFICATION,
+        InAppNotification {
+            title: title.into(),
+            body: body.into(),
+        },
+    );
+    let unread = {
+        let state = app.state::<NotificationState>();
+        let mut unread = state.unread.lock().unwrap();
+        *unread += 1;
+        *unread
+    };
+    update_tray_badge(app, unread);
+}
+
+// Called whenever the main window is brought up — the user sees the toasts.
+pub fn clear_unread(app: &AppHandle) {
+    *app.state::<NotificationState>().unread.lock().unwrap() = 0;
+    update_tray_badge(app, 0);
+}
+
+// Tray titles render next to the icon on macOS and Linux; Windows only has the
+// tooltip, so both are set, besides the badge on the icon itself. The tooltip
+// also says what's left of today's quota (`quotas`), and when it's outside
+// the working hours (`workhours`).
+fn update_tray_badge(app: &AppHandle, unread: u32) {
+    trayicon::set_unread(app, unread > 0);
+    let Some(tray) = app.tray_by_id(crate::TRAY_ID) else {
+        return;
+    };
+    let mut tooltip = if unread == 0 {
+        let _ = tray.set_title(None::<&str>);
+        "JobBot".to_string()
+    } else {
+        let _ = tray.set_title(Some(unread.to_string()));
+        format!("JobBot — {unread} unread")
+    };
+    if let Some(quota) = quotas::tooltip_line() {
+        tooltip = format!("{tooltip}\n{quota}");
+    }
+    if let Some(hours) = crate::workhours::tooltip_line() {
+        tooltip = format!("{tooltip}\n{hours}");
+    }
+    let _ = tray.set_tooltip(Some(tooltip));
+}
+
+// The tray again, for a change to something besides the unread count.
+pub fn refresh_tray(app: &AppHandle) {
+    let unread = *app.state::<NotificationState>().unread.lock().unwrap();
+    update_tray_badge(app, unread);
+}
+
+#[tauri::command]
+pub fn get_notification_permission(app: AppHandle) -> NotificationPermission {
+    refresh_permission(&app)
+}
+
+#[tauri::command]
+pub fn clear_in_app_notifications(app: AppHandle) {
+    clear_unread(&app);
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::NotificationPermission;
+    use block2::RcBlock;
+    use objc2_user_notifications::{
+        UNAuthorizationStatus, UNNotificationSettings, UNUserNotificationCenter,
+    };
+    use std::{ptr::NonNull, sync::mpsc, time::Duration};
+    use tauri::AppHandle;
+
+    pub fn detect(_app: &AppHandle) -> NotificationPermission {
+        // UNUserNotificationCenter throws when the process isn't a bundled .app
+        let bundled = std::env::current_exe()
+            .map(|p| p.to_string_lossy().contains(".app/Contents/MacOS"))
+            .unwrap_or(false);
+        if !bundled {
+            return NotificationPermission::Unknown;
+        }
+        let (tx, rx) = mpsc::channel();
+        let handler = RcBlock::new(move |settings: NonNull<UNNotificationSettings>| {
+            let status = unsafe { settings.as_ref() }.authorizationStatus();
+            let _ = tx.send(status);
+        });
+        UNUserNotificationCenter::currentNotificationCenter()
+            .getNotificationSettingsWithCompletionHandler(&handler);
+        match rx.recv_timeout(Duration::from_secs(2)) {
+            Ok(UNAuthorizationStatus::Denied) => NotificationPermission::Denied,
+            Ok(UNAuthorizationStatus::NotDetermined) | Err(_) => NotificationPermission::Unknown,
+            Ok(_) => NotificationPermission::Granted,
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::NotificationPermission;
+    use tauri::AppHandle;
+    use windows::{
+        core::HSTRING,
+        UI::Notifications::{NotificationSetting, ToastNotificationManager},
+    };
+
+    pub fn detect(app: &AppHandle) -> NotificationPermission {
+        let id = HSTRING::from(&app.config().identifier);
+        match ToastNotificationManager::CreateToastNotifierWithId(&id).and_then(|n| n.Setting()) {
+            Ok(NotificationSetting::Enabled) => NotificationPermission::Granted,
+            Ok(_) => NotificationPermission::Denied,
+            Err(_) => NotificationPermission::Unknown,
+        }
+    }
+}
+
+// Linux has no permission model; "denied" means no notification daemon is
+// listening on the session bus.
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::NotificationPermission;
+    use tauri::AppHandle;
+
+    pub fn detect(_app: &AppHandle) -> NotificationPermission {
+        match notify_rust::get_server_information() {
+            Ok(_) => NotificationPermission::Granted,
+            Err(_) => NotificationPermission::Denied,
+        }
+    }
+}
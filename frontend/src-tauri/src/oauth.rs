@@ -0,0 +1,616 @@
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    audit::{self, Action},
+    backend,
+    events::BackendEvent,
+    i18n::{t, t_args},
+    net, notifications,
+    settings::{self, OAuthSettings},
+};
+
+// Signing in to LinkedIn and Google from the desktop. Providers block their
+// login pages inside the webview, so the authorization page opens in the
+// system browser and the redirect comes back to a listener on 127.0.0.1 that
+// only runs while a sign-in waits (at most LOGIN_TIMEOUT). The code is
+// exchanged with PKCE and the tokens kept in the OS keychain; the engine is
+// handed the access token alone, when it connects and when it asks for one
+// (`oauth_required`), refreshed first if it's about to expire. The apps
+// registered with each provider are in settings, their secrets in the
+// keychain.
+pub const OAUTH_CHANGED: &str = "oauth-changed";
+const KEYCHAIN_SERVICE: &str = "com.currobot.app.oauth";
+const CALLBACK_PATH: &str = "/callback";
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const POLL: Duration = Duration::from_millis(200);
+// Refreshed when closer than this to expiring
+const EXPIRY_MARGIN_MINUTES: i64 = 5;
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    Linkedin,
+    Google,
+}
+
+impl Provider {
+    const ALL: [Provider; 2] = [Provider::Linkedin, Provider::Google];
+
+    fn id(self) -> &'static str {
+        match self {
+            Provider::Linkedin => "linkedin",
+            Provider::Google => "google",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Provider::Linkedin => "LinkedIn",
+            Provider::Google => "Google",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.id() == id)
+    }
+
+    fn auth_url(self) -> &'static str {
+        match self {
+            Provider::Linkedin => "https://www.linkedin.com/oauth/v2/authorization",
+            Provider::Google => "https://accounts.google.com/o/oauth2/v2/auth",
+        }
+    }
+
+    fn token_url(self) -> &'static str {
+        match self {
+            Provider::Linkedin => "https://www.linkedin.com/oauth/v2/accessToken",
+            Provider::Google => "https://oauth2.googleapis.com/token",
+        }
+    }
+
+    fn scopes(self) -> &'static str {
+        match self {
+            Provider::Linkedin => "openid profile email",
+            Provider::Google => "openid email profile",
+        }
+    }
+
+    fn client_id(self, cfg: &OAuthSettings) -> &str {
+        match self {
+            Provider::Linkedin => &cfg.linkedin_client_id,
+            Provider::Google => &cfg.google_client_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+    scope: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OAuthStatus {
+    pub provider: Provider,
+    pub client_id: String,
+    pub has_client_secret: bool,
+    pub callback_port: u16,
+    pub connected: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub signing_in: bool,
+}
+
+// The sign-in waiting for its redirect, and how to call it off
+static PENDING: Mutex<Option<(Provider, Arc<AtomicBool>)>> = Mutex::new(None);
+
+fn entry(provider: Provider, what: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &format!("{}/{what}", provider.id()))
+        .map_err(|e| e.to_string())
+}
+
+fn client_secret(provider: Provider) -> Option<String> {
+    entry(provider, "client-secret").ok()?.get_password().ok()
+}
+
+fn load_token(provider: Provider) -> Option<StoredToken> {
+    let json = entry(provider, "token").ok()?.get_password().ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+fn save_token(app: &AppHandle, provider: Provider, token: &StoredToken) -> Result<(), String> {
+    let json = serde_json::to_string(token).map_err(|e| e.to_string())?;
+    entry(provider, "token")?
+        .set_password(&json)
+        .map_err(|e| e.to_string())?;
+    let _ = app.emit(OAUTH_CHANGED, ());
+    Ok(())
+}
+
+fn random_string(bytes: usize) -> String {
+    let mut buf = vec![0u8; bytes];
+    OsRng.fill_bytes(&mut buf);
+    URL_SAFE_NO_PAD.encode(buf)
+}
+
+fn page(title: &str) -> String {
+    format!(
+        "<!doctype html><meta charset=\"utf-8\"><title>currobot</title>\
+         <body style=\"font-family:system-ui;text-align:center;padding-top:20vh\">\
+         <h2>{title}</h2><p>{}</p></body>",
+        t("oauth-page-close")
+    )
+}
+
+fn respond(mut stream: &TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+// The query of a `GET /callback?...` request, or None for anything else the
+// browser asks for (a favicon).
+fn read_callback(mut stream: &TcpStream) -> Option<Vec<(String, String)>> {
+    stream.set_read_timeout(Some(Duration::from_secs(5))).ok()?;
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let target = request
+        .lines()
+        .next()?
+        .strip_prefix("GET ")?
+        .split(' ')
+        .next()?;
+    let url = reqwest::Url::parse(&format!("http://127.0.0.1{target}")).ok()?;
+    (url.path() == CALLBACK_PATH).then(|| url.query_pairs().into_owned().collect())
+}
+
+// Waits for the provider's redirect and returns its code.
+fn wait_for_code(
+    listener: &TcpListener,
+    state: &str,
+    cancelled: &AtomicBool,
+) -> Result<String, String> {
+    let deadline = Instant::now() + LOGIN_TIMEOUT;
+    while Instant::now() < deadline {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err("sign-in cancelled".into());
+        }
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                thread::sleep(POLL);
+                continue;
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+        let _ = stream.set_nonblocking(false);
+        let Some(query) = read_callback(&stream) else {
+            respond(&stream, "404 Not Found", "");
+            continue;
+        };
+        let param = |key: &str| query.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        // Someone else's redirect, or a stale tab: not ours to act on
+        if param("state").as_deref() != Some(state) {
+            respond(&stream, "400 Bad Request", &page(&t("oauth-page-failed")));
+            continue;
+        }
+        if let Some(error) = param("error") {
+            respond(&stream, "200 OK", &page(&t("oauth-page-failed")));
+            let detail = param("error_description").unwrap_or(error);
+            return Err(format!("the provider refused: {detail}"));
+        }
+        let Some(code) = param("code") else {
+            respond(&stream, "400 Bad Request", &page(&t("oauth-page-failed")));
+            continue;
+        };
+        respond(&stream, "200 OK", &page(&t("oauth-page-done")));
+        return Ok(code);
+    }
+    Err("no answer from the browser in time".into())
+}
+
+fn token_request(provider: Provider, form: &[(&str, &str)]) -> Result<StoredToken, String> {
+    let response = net::client(Duration::from_secs(30))?
+        .post(provider.token_url())
+        .header("Accept", "application/json")
+        .form(form)
+        .send()
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    let body: Value = response.json().map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        let detail = body["error_description"]
+            .as_str()
+            .or(body["error"].as_str())
+            .unwrap_or("");
+        return Err(format!("{}: {status} {detail}", provider.name()));
+    }
+    let access_token = body["access_token"]
+        .as_str()
+        .ok_or("the provider sent no access token")?
+        .to_string();
+    Ok(StoredToken {
+        access_token,
+        refresh_token: body["refresh_token"].as_str().map(str::to_string),
+        expires_at: body["expires_in"]
+            .as_i64()
+            .map(|secs| Utc::now() + ChronoDuration::seconds(secs)),
+        scope: body["scope"].as_str().map(str::to_string),
+    })
+}
+
+fn sign_in(app: &AppHandle, provider: Provider) -> Result<(), String> {
+    let cfg = settings::get(app).oauth;
+    let client_id = provider.client_id(&cfg).trim().to_string();
+    if client_id.is_empty() {
+        return Err(format!(
+            "no {} app is set up to sign in with",
+            provider.name()
+        ));
+    }
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut pending = PENDING.lock().unwrap();
+        if pending.is_some() {
+            return Err("another sign-in is already waiting for the browser".into());
+        }
+        *pending = Some((provider, cancelled.clone()));
+    }
+    let _ = app.emit(OAUTH_CHANGED, ());
+    let result = authorize(app, provider, &cfg, &client_id, &cancelled);
+    *PENDING.lock().unwrap() = None;
+    let _ = app.emit(OAUTH_CHANGED, ());
+    result
+}
+
+fn authorize(
+    app: &AppHandle,
+    provider: Provider,
+    cfg: &OAuthSettings,
+    client_id: &str,
+    cancelled: &AtomicBool,
+) -> Result<(), String> {
+    // Loopback only: nothing else on the network can reach it
+    let listener = TcpListener::bind(("127.0.0.1", cfg.callback_port))
+        .map_err(|e| format!("port {} is not free: {e}", cfg.callback_port))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let redirect = format!("http://127.0.0.1:{port}{CALLBACK_PATH}");
+    let state = random_string(16);
+    let verifier = random_string(32);
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    let mut params = vec![
+        ("response_type", "code"),
+        ("client_id", client_id),
+        ("redirect_uri", &redirect),
+        ("scope", provider.scopes()),
+        ("state", &state),
+        ("code_challenge", &challenge),
+        ("code_challenge_method", "S256"),
+    ];
+    if provider == Provider::Google {
+        // Without these Google sends no refresh token
+        params.extend([("access_type", "offline"), ("prompt", "consent")]);
+    }
+    let url =
+        reqwest::Url::parse_with_params(provider.auth_url(), &params).map_err(|e| e.to_string())?;
+    open_in_browser(app, url.as_str())?;
+    info!(
+        provider = provider.id(),
+        port, "waiting for the sign-in redirect"
+    );
+    let code = wait_for_code(&listener, &state, cancelled)?;
+    drop(listener);
+
+    let secret = client_secret(provider);
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code.as_str()),
+        ("redirect_uri", redirect.as_str()),
+        ("client_id", client_id),
+        ("code_verifier", verifier.as_str()),
+    ];
+    if let Some(secret) = &secret {
+        form.push(("client_secret", secret));
+        audit::record(
+            app,
+            Action::CredentialUsed,
+            json!({ "service": KEYCHAIN_SERVICE, "account": format!("{}/client-secret", provider.id()), "for": "sign-in" }),
+        );
+    }
+    let token = token_request(provider, &form)?;
+    save_token(app, provider, &token)?;
+    info!(provider = provider.id(), "signed in");
+    if let Err(e) = hand_over(app, provider) {
+        // The engine gets it when it next connects
+        warn!(
+            provider = provider.id(),
+            "token not handed to the engine: {e}"
+        );
+    }
+    Ok(())
+}
+
+#[allow(deprecated)]
+fn open_in_browser(app: &AppHandle, url: &str) -> Result<(), String> {
+    use tauri_plugin_shell::ShellExt;
+    app.shell().open(url, None).map_err(|e| e.to_string())
+}
+
+// A token that still has EXPIRY_MARGIN_MINUTES to go, refreshing it if
+// there's a refresh token.
+fn fresh_token(app: &AppHandle, provider: Provider) -> Result<Option<StoredToken>, String> {
+    let Some(token) = load_token(provider) else {
+        return Ok(None);
+    };
+    let expiring = token
+        .expires_at
+        .is_some_and(|at| at - Utc::now() < ChronoDuration::minutes(EXPIRY_MARGIN_MINUTES));
+    if !expiring {
+        return Ok(Some(token));
+    }
+    let Some(refresh) = token.refresh_token.clone() else {
+        return Ok(None);
+    };
+    let cfg = settings::get(app).oauth;
+    let secret = client_secret(provider);
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh.as_str()),
+        ("client_id", provider.client_id(&cfg)),
+    ];
+    if let Some(secret) = &secret {
+        form.push(("client_secret", secret));
+    }
+    let mut fresh = token_request(provider, &form)?;
+    // Google only sends a new one now and then
+    fresh.refresh_token = fresh.refresh_token.or(Some(refresh));
+    save_token(app, provider, &fresh)?;
+    info!(provider = provider.id(), "token refreshed");
+    Ok(Some(fresh))
+}
+
+// Gives the engine the access token; never the refresh token.
+fn hand_over(app: &AppHandle, provider: Provider) -> Result<bool, String> {
+    let Some(token) = fresh_token(app, provider)? else {
+        return Ok(false);
+    };
+    backend::post_json(
+        &format!("/api/oauth/{}", provider.id()),
+        &json!({
+            "access_token": token.access_token,
+            "expires_at": token.expires_at.map(|at| at.timestamp()),
+            "scope": token.scope,
+        }),
+    )?;
+    audit::record(
+        app,
+        Action::CredentialUsed,
+        json!({ "service": KEYCHAIN_SERVICE, "account": format!("{}/token", provider.id()), "for": "engine" }),
+    );
+    Ok(true)
+}
+
+// The engine is (back) up and has no tokens yet.
+pub fn on_connected(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        for provider in Provider::ALL {
+            if load_token(provider).is_none() {
+                continue;
+            }
+            if let Err(e) = hand_over(&app, provider) {
+                warn!(
+                    provider = provider.id(),
+                    "token not handed to the engine: {e}"
+                );
+            }
+        }
+    });
+}
+
+// `oauth_required`: the engine needs a token it doesn't have, or one that
+// has expired. Without a usable one the user is asked to sign in.
+pub fn on_event(app: &AppHandle, event: &BackendEvent) {
+    let Some(provider) = event.data["provider"].as_str().and_then(Provider::from_id) else {
+        return;
+    };
+    let app = app.clone();
+    thread::spawn(move || match hand_over(&app, provider) {
+        Ok(true) => {}
+        result => {
+            if let Err(e) = result {
+                warn!(provider = provider.id(), "token refresh failed: {e}");
+            }
+            notifications::notify_route(
+                &app,
+                &t_args(
+                    "oauth-required-title",
+                    &[("provider", provider.name().into())],
+                ),
+                &t("oauth-required-body"),
+                "/settings",
+            );
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_oauth_status(app: AppHandle) -> Vec<OAuthStatus> {
+    let cfg = settings::get(&app).oauth;
+    let pending = PENDING.lock().unwrap().as_ref().map(|(p, _)| *p);
+    Provider::ALL
+        .into_iter()
+        .map(|provider| {
+            let token = load_token(provider);
+            OAuthStatus {
+                provider,
+                client_id: provider.client_id(&cfg).to_string(),
+                has_client_secret: client_secret(provider).is_some(),
+                callback_port: cfg.callback_port,
+                connected: token.is_some(),
+                expires_at: token.and_then(|t| t.expires_at),
+                signing_in: pending == Some(provider),
+            }
+        })
+        .collect()
+}
+
+// `client_secret`: Some("") deletes the stored one, None leaves it unchanged.
+#[tauri::command]
+pub fn set_oauth_client(
+    app: AppHandle,
+    provider: Provider,
+    client_id: String,
+    client_secret: Option<String>,
+    callback_port: u16,
+) -> Result<(), String> {
+    if let Some(secret) = client_secret {
+        let entry = entry(provider, "client-secret")?;
+        if secret.is_empty() {
+            let _ = entry.delete_credential();
+        } else {
+            entry.set_password(&secret).map_err(|e| e.to_string())?;
+        }
+    }
+    let client_id = client_id.trim().to_string();
+    settings::update(&app, |s| {
+        match provider {
+            Provider::Linkedin => s.oauth.linkedin_client_id = client_id,
+            Provider::Google => s.oauth.google_client_id = client_id,
+        }
+        s.oauth.callback_port = callback_port;
+    })?;
+    let _ = app.emit(OAUTH_CHANGED, ());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn sign_in_oauth(app: AppHandle, provider: Provider) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || sign_in(&app, provider))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn cancel_oauth_sign_in() {
+    if let Some((_, cancelled)) = PENDING.lock().unwrap().as_ref() {
+        cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tauri::command]
+pub fn sign_out_oauth(app: AppHandle, provider: Provider) -> Result<(), String> {
+    let _ = entry(provider, "token")?.delete_credential();
+    // Best effort: a stopped engine holds no token anyway
+    let _ = backend::send_json("DELETE", &format!("/api/oauth/{}", provider.id()), None);
+    info!(provider = provider.id(), "signed out");
+    let _ = app.emit(OAUTH_CHANGED, ());
+    Ok(())
+}
+
+// Reset → saved passwords.
+pub fn forget_tokens() {
+    for provider in Provider::ALL {
+        for what in ["token", "client-secret"] {
+            if let Ok(entry) = entry(provider, what) {
+                let _ = entry.delete_credential();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(port: u16, target: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(stream, "GET {target} HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    fn listener() -> (TcpListener, u16) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        (listener, port)
+    }
+
+    #[test]
+    fn providers_by_id() {
+        assert_eq!(Provider::from_id("google"), Some(Provider::Google));
+        assert_eq!(Provider::from_id("Google"), None);
+    }
+
+    #[test]
+    fn code_from_our_redirect_only() {
+        let (listener, port) = listener();
+        let browser = thread::spawn(move || {
+            [
+                get(port, "/favicon.ico"),
+                get(port, "/callback?state=stale&code=old"),
+                get(port, "/callback?state=s1&code=c%2B1"),
+            ]
+        });
+        let code = wait_for_code(&listener, "s1", &AtomicBool::new(false));
+        assert_eq!(code.unwrap(), "c+1");
+        let [favicon, stale, ours] = browser.join().unwrap();
+        assert!(favicon.starts_with("HTTP/1.1 404"));
+        assert!(stale.starts_with("HTTP/1.1 400"));
+        assert!(ours.starts_with("HTTP/1.1 200"));
+    }
+
+    #[test]
+    fn provider_refusal() {
+        let (listener, port) = listener();
+        let browser = thread::spawn(move || {
+            get(
+                port,
+                "/callback?state=s1&error=access_denied&error_description=Denied",
+            )
+        });
+        let result = wait_for_code(&listener, "s1", &AtomicBool::new(false));
+        assert_eq!(result.unwrap_err(), "the provider refused: Denied");
+        browser.join().unwrap();
+    }
+
+    #[test]
+    fn cancelled_sign_in() {
+        let (listener, _) = listener();
+        let result = wait_for_code(&listener, "s1", &AtomicBool::new(true));
+        assert_eq!(result.unwrap_err(), "sign-in cancelled");
+    }
+}
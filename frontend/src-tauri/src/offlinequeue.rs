@@ -0,0 +1,443 @@
+use chrono::{DateTime, Local, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    backend::{self, Failure},
+    events,
+    i18n::{t, t_args},
+    notifications, paths,
+};
+
+// Changes the user makes while the engine is down (a status update, a
+// scraper run, a new company source) are kept here instead of failing, and
+// sent in the order they were made once it's back: when the event stream
+// reconnects, and every RETRY_INTERVAL while anything waits. An action the
+// engine refuses (the application is gone, or already moved on) becomes a
+// conflict the user sees and dismisses, and the rest carry on; one it can't
+// process right now is tried again, up to MAX_ATTEMPTS. Only the requests in
+// QUEUEABLE can be queued. Kept in `offline-queue.json` in the profile's
+// data folder, so a restart loses nothing.
+pub const OFFLINE_QUEUE_CHANGED: &str = "offline-queue-changed";
+const FILE: &str = "offline-queue.json";
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_ATTEMPTS: u32 = 5;
+const MAX_QUEUED: usize = 200;
+
+// Method and path of what may wait for the engine (lib/api.ts `queueable`)
+static QUEUEABLE: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    [
+        ("PATCH", r"^/api/applications/\d+/status$"),
+        ("POST", r"^/api/applications/\d+/reject$"),
+        ("POST", r"^/api/scrapers/[a-z0-9_]+/trigger$"),
+        ("POST", r"^/api/company-sources$"),
+        ("POST", r"^/api/jobs/intake$"),
+    ]
+    .into_iter()
+    .map(|(method, path)| (method, Regex::new(path).unwrap()))
+    .collect()
+});
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueuedAction {
+    pub id: String,
+    // What the user did, as the app described it
+    pub label: String,
+    pub method: String,
+    pub path: String,
+    pub body: Option<Value>,
+    pub queued_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Conflict {
+    pub action: QueuedAction,
+    // The engine's answer; 0 when it gave up after MAX_ATTEMPTS
+    pub status: u16,
+    pub detail: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OfflineQueue {
+    pub actions: Vec<QueuedAction>,
+    pub conflicts: Vec<Conflict>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ReplayReport {
+    pub sent: usize,
+    pub conflicts: usize,
+    pub remaining: usize,
+}
+
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+static REPLAYING: AtomicBool = AtomicBool::new(false);
+
+fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join(FILE))
+}
+
+fn load(app: &AppHandle) -> OfflineQueue {
+    path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn change<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut OfflineQueue) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = FILE_LOCK.lock().unwrap();
+    let mut queue = load(app);
+    let result = f(&mut queue)?;
+    let path = path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&queue).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())?;
+    let _ = app.emit(OFFLINE_QUEUE_CHANGED, ());
+    Ok(result)
+}
+
+enum Step {
+    Sent,
+    Conflict(u16, String),
+    Retry(String),
+    Stop(String),
+}
+
+fn send(action: &QueuedAction) -> Step {
+    match backend::send_json(&action.method, &action.path, action.body.as_ref()) {
+        Ok(_) => Step::Sent,
+        Err(Failure::Unreachable(e)) => Step::Stop(e),
+        Err(Failure::Refused(status, detail)) if status < 500 => Step::Conflict(status, detail),
+        Err(Failure::Refused(_, detail)) => Step::Retry(detail),
+    }
+}
+
+// Applies the engine's answer to `action`, the first in the queue. Whether
+// to stop replaying for now.
+fn settle(
+    queue: &mut OfflineQueue,
+    action: &QueuedAction,
+    step: Step,
+    report: &mut ReplayReport,
+) -> bool {
+    // Discarded by the user meanwhile
+    if queue.actions.first().map(|a| &a.id) != Some(&action.id) {
+        return false;
+    }
+    let retry = matches!(step, Step::Retry(_));
+    let mut conflict = |status, detail| {
+        queue.conflicts.push(Conflict {
+            action: queue.actions.remove(0),
+            status,
+            detail,
+            at: Utc::now(),
+        });
+        report.conflicts += 1;
+        false
+    };
+    match step {
+        Step::Sent => {
+            queue.actions.remove(0);
+            report.sent += 1;
+            false
+        }
+        Step::Conflict(status, detail) => {
+            warn!(
+                action = action.label,
+                status, "queued action refused: {detail}"
+            );
+            conflict(status, detail)
+        }
+        Step::Retry(detail) if action.attempts + 1 >= MAX_ATTEMPTS => conflict(0, detail),
+        Step::Retry(detail) | Step::Stop(detail) => {
+            let first = &mut queue.actions[0];
+            first.attempts += u32::from(retry);
+            first.last_error = Some(detail);
+            true
+        }
+    }
+}
+
+// Oldest first, one at a time, so each action sees the ones before it.
+fn replay(app: &AppHandle) -> Result<ReplayReport, String> {
+    if REPLAYING.swap(true, Ordering::SeqCst) {
+        return Ok(ReplayReport {
+            remaining: load(app).actions.len(),
+            ..Default::default()
+        });
+    }
+    let mut report = ReplayReport::default();
+    let result = loop {
+        let Some(action) = load(app).actions.first().cloned() else {
+            break Ok(());
+        };
+        let step = send(&action);
+        let stop = change(app, |queue| Ok(settle(queue, &action, step, &mut report)));
+        match stop {
+            Ok(false) => continue,
+            Ok(true) => break Ok(()),
+            Err(e) => break Err(e),
+        }
+    };
+    REPLAYING.store(false, Ordering::SeqCst);
+    result?;
+    report.remaining = load(app).actions.len();
+    if report.sent > 0 || report.conflicts > 0 {
+        info!(
+            sent = report.sent,
+            conflicts = report.conflicts,
+            remaining = report.remaining,
+            "offline queue replayed"
+        );
+        events::emit(app, "offline_queue_replayed", json!(report));
+    }
+    if report.conflicts > 0 {
+        notifications::notify(
+            app,
+            &t("offline-conflicts-title"),
+            &t_args(
+                "offline-conflicts-body",
+                &[("count", report.conflicts.into())],
+            ),
+        );
+    }
+    Ok(report)
+}
+
+fn replay_in_background(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        if let Err(e) = replay(&app) {
+            warn!("offline queue replay failed: {e}");
+        }
+    });
+}
+
+// The engine is (back) up.
+pub fn on_connected(app: &AppHandle) {
+    if !load(app).actions.is_empty() {
+        replay_in_background(app);
+    }
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(RETRY_INTERVAL);
+        if !load(&app).actions.is_empty() && events::stream_status().0 {
+            if let Err(e) = replay(&app) {
+                warn!("offline queue replay failed: {e}");
+            }
+        }
+    });
+}
+
+// What's still waiting to be sent, oldest first.
+pub fn pending(app: &AppHandle) -> Vec<QueuedAction> {
+    load(app).actions
+}
+
+#[tauri::command]
+pub fn get_offline_queue(app: AppHandle) -> OfflineQueue {
+    load(&app)
+}
+
+fn queueable(method: &str, path: &str) -> bool {
+    QUEUEABLE
+        .iter()
+        .any(|(m, p)| *m == method && p.is_match(path))
+}
+
+// For a request that couldn't reach the engine, from the app or from the
+// shell itself (mailwatch.rs). If it's up again already, the queue is sent
+// straight away.
+pub fn queue(
+    app: &AppHandle,
+    label: String,
+    method: &str,
+    path: String,
+    body: Option<Value>,
+) -> Result<QueuedAction, String> {
+    let method = method.to_uppercase();
+    if !queueable(&method, &path) {
+        return Err(format!("{method} {path} can't wait for the engine"));
+    }
+    let action = QueuedAction {
+        id: format!("action-{}", Local::now().format("%Y%m%d-%H%M%S%3f")),
+        label,
+        method,
+        path,
+        body,
+        queued_at: Utc::now(),
+        attempts: 0,
+        last_error: None,
+    };
+    change(app, |queue| {
+        if queue.actions.len() >= MAX_QUEUED {
+            return Err("too many changes are waiting for the engine".into());
+        }
+        queue.actions.push(action.clone());
+        Ok(())
+    })?;
+    info!(action = action.label, "queued until the engine is back");
+    if events::stream_status().0 {
+        replay_in_background(app);
+    }
+    Ok(action)
+}
+
+#[tauri::command]
+pub fn queue_offline_action(
+    app: AppHandle,
+    label: String,
+    method: String,
+    path: String,
+    body: Option<Value>,
+) -> Result<QueuedAction, String> {
+    queue(&app, label, &method, path, body)
+}
+
+#[tauri::command]
+pub async fn replay_offline_queue(app: AppHandle) -> Result<ReplayReport, String> {
+    tauri::async_runtime::spawn_blocking(move || replay(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn discard_offline_action(app: AppHandle, id: String) -> Result<(), String> {
+    change(&app, |queue| {
+        queue.actions.retain(|a| a.id != id);
+        Ok(())
+    })
+}
+
+// One conflict by its action's id, or all of them.
+#[tauri::command]
+pub fn dismiss_offline_conflict(app: AppHandle, id: Option<String>) -> Result<(), String> {
+    change(&app, |queue| {
+        queue
+            .conflicts
+            .retain(|c| id.as_ref().is_some_and(|id| *id != c.action.id));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(id: &str, attempts: u32) -> QueuedAction {
+        QueuedAction {
+            id: id.into(),
+            label: "Marked as rejected".into(),
+            method: "POST".into(),
+            path: "/api/applications/4/reject".into(),
+            body: None,
+            queued_at: Utc::now(),
+            attempts,
+            last_error: None,
+        }
+    }
+
+    fn queue(actions: Vec<QueuedAction>) -> OfflineQueue {
+        OfflineQueue {
+            actions,
+            conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn only_listed_requests_wait() {
+        assert!(queueable("PATCH", "/api/applications/12/status"));
+        assert!(queueable("POST", "/api/scrapers/indeed_es/trigger"));
+        assert!(!queueable("POST", "/api/applications/12/status"));
+        assert!(!queueable("DELETE", "/api/applications/12"));
+        assert!(!queueable("POST", "/api/applications/x/reject"));
+        assert!(!queueable("POST", "/api/jobs/intake/extra"));
+    }
+
+    #[test]
+    fn sent_actions_leave_the_queue() {
+        let (a, b) = (action("a", 0), action("b", 0));
+        let mut q = queue(vec![a.clone(), b]);
+        let mut report = ReplayReport::default();
+        assert!(!settle(&mut q, &a, Step::Sent, &mut report));
+        assert_eq!(q.actions[0].id, "b");
+        assert_eq!(report.sent, 1);
+    }
+
+    #[test]
+    fn refusals_become_conflicts() {
+        let a = action("a", 0);
+        let mut q = queue(vec![a.clone()]);
+        let mut report = ReplayReport::default();
+        assert!(!settle(
+            &mut q,
+            &a,
+            Step::Conflict(409, "already rejected".into()),
+            &mut report
+        ));
+        assert!(q.actions.is_empty());
+        assert_eq!(q.conflicts[0].status, 409);
+        assert_eq!(report.conflicts, 1);
+    }
+
+    #[test]
+    fn retries_until_the_last_attempt() {
+        let a = action("a", 0);
+        let mut q = queue(vec![a.clone()]);
+        let mut report = ReplayReport::default();
+        assert!(settle(&mut q, &a, Step::Retry("502".into()), &mut report));
+        assert_eq!(q.actions[0].attempts, 1);
+        assert!(settle(
+            &mut q,
+            &a,
+            Step::Stop("refused".into()),
+            &mut report
+        ));
+        assert_eq!(q.actions[0].attempts, 1);
+        assert_eq!(q.actions[0].last_error.as_deref(), Some("refused"));
+
+        let last = action("a", MAX_ATTEMPTS - 1);
+        assert!(!settle(
+            &mut q,
+            &last,
+            Step::Retry("502".into()),
+            &mut report
+        ));
+        assert_eq!(q.conflicts[0].status, 0);
+    }
+
+    #[test]
+    fn discarded_meanwhile() {
+        let mut q = queue(vec![action("b", 0)]);
+        let mut report = ReplayReport::default();
+        assert!(!settle(&mut q, &action("a", 0), Step::Sent, &mut report));
+        assert_eq!(q.actions.len(), 1);
+        assert_eq!(report.sent, 0);
+    }
+}
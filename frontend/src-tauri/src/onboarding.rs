@@ -0,0 +1,168 @@
+use serde::Serialize;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_notification::{NotificationExt, PermissionState};
+use tracing::warn;
+
+use crate::{
+    applock, backend, export, i18n,
+    notifications::{self, NotificationPermission},
+    paths,
+    settings::{self, OnboardingStep},
+};
+
+// First-launch walkthrough for the desktop side: notifications, start on
+// login, stored credentials. (The engine's own setup — terms, CV, model — is
+// the /setup page.) It runs in its own window until the last step is passed;
+// the step is persisted so quitting halfway resumes where the user left off.
+pub const ONBOARDING_WINDOW: &str = "onboarding";
+
+impl OnboardingStep {
+    fn next(self) -> Self {
+        match self {
+            Self::Welcome => Self::Notifications,
+            // Nothing to decide about login items on a portable copy
+            Self::Notifications if paths::is_portable() => Self::Credentials,
+            Self::Notifications => Self::Autostart,
+            Self::Autostart => Self::Credentials,
+            Self::Credentials | Self::Done => Self::Done,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SystemChecks {
+    pub notification_permission: NotificationPermission,
+    pub autostart_enabled: bool,
+    pub backend_healthy: bool,
+    pub credentials_added: bool,
+    pub portable: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OnboardingState {
+    pub step: OnboardingStep,
+    pub completed: bool,
+    pub checks: SystemChecks,
+}
+
+pub fn is_complete(app: &AppHandle) -> bool {
+    settings::get(app).onboarding.step == OnboardingStep::Done
+}
+
+fn checks(app: &AppHandle) -> SystemChecks {
+    let credentials_added = export::BACKEND_SECRETS.iter().all(|(service, account)| {
+        keyring::Entry::new(service, account).is_ok_and(|e| e.get_password().is_ok())
+    });
+    SystemChecks {
+        notification_permission: notifications::refresh_permission(app),
+        autostart_enabled: crate::loginitem::is_enabled(app),
+        backend_healthy: backend::get_json("/api/health").is_ok(),
+        credentials_added,
+        portable: paths::is_portable(),
+    }
+}
+
+fn state(app: &AppHandle) -> OnboardingState {
+    let step = settings::get(app).onboarding.step;
+    OnboardingState {
+        step,
+        completed: step == OnboardingStep::Done,
+        checks: checks(app),
+    }
+}
+
+pub fn open_window(app: &AppHandle) {
+    if !applock::gate(app, open_window) {
+        return;
+    }
+    if let Some(w) = app.get_webview_window(ONBOARDING_WINDOW) {
+        let _ = w.show();
+        let _ = w.set_focus();
+        return;
+    }
+    let built =
+        WebviewWindowBuilder::new(app, ONBOARDING_WINDOW, WebviewUrl::App("onboarding".into()))
+            .title(i18n::t("window-onboarding"))
+            .inner_size(560.0, 640.0)
+            .resizable(false)
+            .center()
+            .build();
+    if let Err(e) = built {
+        warn!("onboarding window failed: {e}");
+        // Don't leave a first-time user with no window at all
+        crate::show_window(app);
+    }
+}
+
+fn finish(app: &AppHandle) {
+    if let Some(w) = app.get_webview_window(ONBOARDING_WINDOW) {
+        let _ = w.close();
+    }
+    crate::show_window(app);
+}
+
+// Checks hit the backend and the keychain, so run them off the IPC thread.
+#[tauri::command]
+pub async fn get_onboarding(app: AppHandle) -> Result<OnboardingState, String> {
+    tauri::async_runtime::spawn_blocking(move || state(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Moves past `from`. Stale calls (a step already passed, e.g. from a double
+// click) leave the state as it is.
+#[tauri::command]
+pub async fn advance_onboarding(
+    app: AppHandle,
+    from: OnboardingStep,
+) -> Result<OnboardingState, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let current = settings::get(&app).onboarding.step;
+        if current == from && current != OnboardingStep::Done {
+            let next = current.next();
+            settings::update(&app, |s| {
+                s.onboarding.step = next;
+                if next == OnboardingStep::Done {
+                    s.onboarding.completed_at = Some(chrono::Utc::now().to_rfc3339());
+                }
+            })?;
+            if next == OnboardingStep::Done {
+                finish(&app);
+            }
+        }
+        Ok(state(&app))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn request_notification_permission(app: AppHandle) -> NotificationPermission {
+    if let Ok(PermissionState::Denied) = app.notification().request_permission() {
+        return NotificationPermission::Denied;
+    }
+    notifications::refresh_permission(&app)
+}
+
+// The InfoJobs login the scraper reads from the keychain; optional, the
+// credentials step can be skipped.
+#[tauri::command]
+pub fn save_credentials(username: String, password: String) -> Result<(), String> {
+    if username.trim().is_empty() || password.is_empty() {
+        return Err("username and password are required".into());
+    }
+    let values = [username.trim(), password.as_str()];
+    for ((service, account), value) in export::BACKEND_SECRETS.iter().zip(values) {
+        keyring::Entry::new(service, account)
+            .and_then(|e| e.set_password(value))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn restart_onboarding(app: AppHandle) -> Result<(), String> {
+    settings::update(&app, |s| s.onboarding = Default::default())?;
+    open_window(&app);
+    Ok(())
+}
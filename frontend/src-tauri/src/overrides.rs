@@ -0,0 +1,156 @@
+use serde::Serialize;
+use std::{env, path::PathBuf, sync::OnceLock};
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::paths;
+
+// JOBBOT_* environment variables win over settings and defaults. They are read
+// once at startup, so scripted setups can pin the port, data location or proxy
+// without touching the config file:
+//   JOBBOT_BACKEND_PORT  port the sidecar listens on (default 8000)
+//   JOBBOT_LOG_LEVEL     error | warn | info | debug | trace
+//   JOBBOT_DATA_DIR      replaces the OS data dir (and portable ./data)
+//   JOBBOT_PROXY         proxy URL for the shell's and the engine's HTTP calls
+const DEFAULT_BACKEND_PORT: u16 = 8000;
+pub const LOG_LEVELS: &[&str] = &["error", "warn", "info", "debug", "trace"];
+
+#[derive(Debug, Default)]
+pub struct Overrides {
+    pub backend_port: Option<u16>,
+    pub log_level: Option<String>,
+    pub data_dir: Option<PathBuf>,
+    pub proxy: Option<String>,
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok()
+}
+
+// Unusable values are reported and ignored rather than aborting startup.
+fn read(var: impl Fn(&str) -> Option<String>) -> Overrides {
+    let var = |name: &str| var(name).filter(|v| !v.trim().is_empty());
+    let backend_port = var("JOBBOT_BACKEND_PORT").and_then(|v| match v.parse::<u16>() {
+        Ok(p) if p > 0 => Some(p),
+        _ => {
+            warn!("ignoring JOBBOT_BACKEND_PORT={v}: not a port number");
+            None
+        }
+    });
+    let log_level = var("JOBBOT_LOG_LEVEL")
+        .map(|v| v.to_lowercase())
+        .and_then(|v| {
+            if LOG_LEVELS.contains(&v.as_str()) {
+                Some(v)
+            } else {
+                warn!("ignoring JOBBOT_LOG_LEVEL={v}");
+                None
+            }
+        });
+    let proxy = var("JOBBOT_PROXY").and_then(|v| match reqwest::Proxy::all(&v) {
+        Ok(_) => Some(v),
+        Err(e) => {
+            warn!("ignoring JOBBOT_PROXY={v}: {e}");
+            None
+        }
+    });
+    Overrides {
+        backend_port,
+        log_level,
+        data_dir: var("JOBBOT_DATA_DIR").map(PathBuf::from),
+        proxy,
+    }
+}
+
+pub fn get() -> &'static Overrides {
+    static OVERRIDES: OnceLock<Overrides> = OnceLock::new();
+    OVERRIDES.get_or_init(|| read(var))
+}
+
+pub fn backend_port() -> u16 {
+    get().backend_port.unwrap_or(DEFAULT_BACKEND_PORT)
+}
+
+pub fn backend_url() -> String {
+    format!("http://127.0.0.1:{}", backend_port())
+}
+
+#[derive(Debug, Serialize)]
+pub struct EffectiveConfig {
+    pub backend_url: String,
+    pub backend_port: u16,
+    pub log_level: String,
+    pub config_dir: Option<PathBuf>,
+    pub data_dir: Option<PathBuf>,
+    pub proxy: Option<String>,
+    pub portable: bool,
+    // Names of the JOBBOT_* variables that took effect
+    pub overridden: Vec<&'static str>,
+}
+
+#[tauri::command]
+pub fn get_effective_config(app: AppHandle) -> EffectiveConfig {
+    let o = get();
+    let overridden = [
+        ("JOBBOT_BACKEND_PORT", o.backend_port.is_some()),
+        ("JOBBOT_LOG_LEVEL", o.log_level.is_some()),
+        ("JOBBOT_DATA_DIR", o.data_dir.is_some()),
+        ("JOBBOT_PROXY", o.proxy.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(name, set)| set.then_some(name))
+    .collect();
+    EffectiveConfig {
+        backend_url: backend_url(),
+        backend_port: backend_port(),
+        log_level: o.log_level.clone().unwrap_or_else(|| "info".into()),
+        config_dir: paths::config_dir(&app).ok(),
+        data_dir: paths::data_dir(&app).ok(),
+        proxy: crate::net::proxy_url(),
+        portable: paths::is_portable(),
+        overridden,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn read_from(vars: &[(&str, &str)]) -> Overrides {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        read(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn usable_values() {
+        let o = read_from(&[
+            ("JOBBOT_BACKEND_PORT", "8123"),
+            ("JOBBOT_LOG_LEVEL", "DEBUG"),
+            ("JOBBOT_DATA_DIR", "/srv/jobbot"),
+            ("JOBBOT_PROXY", "http://proxy.example:3128"),
+        ]);
+        assert_eq!(o.backend_port, Some(8123));
+        assert_eq!(o.log_level.as_deref(), Some("debug"));
+        assert_eq!(o.data_dir, Some(PathBuf::from("/srv/jobbot")));
+        assert_eq!(o.proxy.as_deref(), Some("http://proxy.example:3128"));
+    }
+
+    #[test]
+    fn unusable_values_ignored() {
+        let o = read_from(&[
+            ("JOBBOT_BACKEND_PORT", "0"),
+            ("JOBBOT_LOG_LEVEL", "verbose"),
+            ("JOBBOT_DATA_DIR", "  "),
+            ("JOBBOT_PROXY", "not a url"),
+        ]);
+        assert!(o.backend_port.is_none() && o.log_level.is_none());
+        assert!(o.data_dir.is_none() && o.proxy.is_none());
+        assert!(read_from(&[("JOBBOT_BACKEND_PORT", "70000")])
+            .backend_port
+            .is_none());
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+use tauri::{AppHandle, Manager};
+
+// A `portable.flag` file next to the executable switches to portable mode:
+// config and data live in ./config and ./data beside the binary instead of the
+// per-user OS locations, and updates and autostart are turned off (USB sticks,
+// locked-down corporate machines).
+const PORTABLE_FLAG: &str = "portable.flag";
+
+fn portable_root() -> Option<&'static Path> {
+    static ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        let dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+        dir.join(PORTABLE_FLAG).exists().then_some(dir)
+    })
+    .as_deref()
+}
+
+pub fn is_portable() -> bool {
+    portable_root().is_some()
+}
+
+// Guard for every update entry point; portable copies are updated by hand.
+pub fn updates_allowed() -> Result<(), String> {
+    if is_portable() {
+        return Err("updates are disabled in portable mode".into());
+    }
+    Ok(())
+}
+
+// App-wide locations, shared by all profiles (downloaded backends, data packs,
+// the profile registry itself).
+pub fn base_config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    match portable_root() {
+        Some(root) => Ok(root.join("config")),
+        None => app.path().app_config_dir().map_err(|e| e.to_string()),
+    }
+}
+
+// `base_config_dir` before the app exists, for what must be settled before
+// it starts or is read by another process (`nativehost`). Tauri's own rule.
+pub fn early_base_config_dir() -> Option<PathBuf> {
+    if let Some(root) = portable_root() {
+        return Some(root.join("config"));
+    }
+    #[cfg(target_os = "linux")]
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    #[cfg(target_os = "macos")]
+    let config = PathBuf::from(std::env::var_os("HOME")?).join("Library/Application Support");
+    #[cfg(windows)]
+    let config = std::env::var_os("APPDATA").map(PathBuf::from)?;
+    Some(config.join("com.currobot.app"))
+}
+
+// The shell's own log files; app-wide like the registry.
+pub fn log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    match portable_root() {
+        Some(root) => Ok(root.join("logs")),
+        None => app.path().app_log_dir().map_err(|e| e.to_string()),
+    }
+}
+
+// Where data lives when nothing relocates it.
+pub fn default_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    match portable_root() {
+        Some(root) => Ok(root.join("data")),
+        None => app.path().app_data_dir().map_err(|e| e.to_string()),
+    }
+}
+
+// JOBBOT_DATA_DIR, then a location chosen in the app (datadir.rs), then the
+// default. Refused while the data is being moved.
+pub fn base_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    crate::datadir::check()?;
+    if let Some(dir) = &crate::overrides::get().data_dir {
+        return Ok(dir.clone());
+    }
+    if let Some(dir) = crate::datadir::custom() {
+        return Ok(dir);
+    }
+    default_data_dir(app)
+}
+
+// Locations of the active profile.
+pub fn config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::profiles::scoped(base_config_dir(app)?))
+}
+
+pub fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::profiles::scoped(base_data_dir(app)?))
+}
+
+// Handed to the backend as JOBBOT_DATA_DIR (see backend/config.py).
+pub fn engine_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(data_dir(app)?.join("engine"))
+}
+
+#[tauri::command]
+pub fn is_portable_mode() -> bool {
+    is_portable()
+}
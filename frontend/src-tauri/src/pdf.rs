@@ -0,0 +1,853 @@
+use chrono::{Days, Local, NaiveDate, NaiveDateTime, Weekday};
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use serde::Deserialize;
+use std::fmt::Write;
+use std::fs;
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+use tracing::info;
+
+use crate::{
+    activity,
+    calendar::{self, Kind},
+    history::{self, HistoryQuery},
+    i18n::{self, t, t_args},
+    templates,
+};
+
+// Cover letters and weekly reports drawn by the shell as PDF, so they come
+// out the same on every system instead of depending on how a webview
+// prints. Pages are A4 in the standard Helvetica faces, which every PDF
+// reader has, so nothing is embedded; text is written in WinAnsi, which
+// covers English and Spanish, and anything outside it shows as "?".
+const WIDTH: f32 = 595.0;
+const HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 64.0;
+const TOP: f32 = HEIGHT - 72.0;
+const BOTTOM: f32 = 72.0;
+const REGULAR: Name = Name(b"F1");
+const BOLD: Name = Name(b"F2");
+
+// Helvetica's advance widths for ' ' to '~', in thousandths of the size.
+#[rustfmt::skip]
+const REGULAR_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556,
+    278, 278, 584, 584, 584, 556, 1015,
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667,
+    778, 722, 667, 611, 722, 667, 944, 667, 667, 611,
+    278, 278, 278, 469, 556, 333,
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, 556,
+    556, 333, 500, 278, 556, 500, 722, 500, 500, 500,
+    334, 260, 334, 584,
+];
+
+#[rustfmt::skip]
+const BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556,
+    333, 333, 584, 584, 584, 611, 975,
+    722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667,
+    778, 722, 667, 611, 722, 667, 944, 667, 667, 611,
+    333, 278, 333, 584, 556, 333,
+    556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, 611,
+    611, 389, 556, 333, 611, 556, 778, 556, 556, 500,
+    389, 280, 389, 584,
+];
+
+#[derive(Clone, Copy)]
+enum Style {
+    Title,
+    Heading,
+    Body,
+    Strong,
+    Muted,
+    Label,
+    Cell,
+}
+
+impl Style {
+    fn bold(self) -> bool {
+        matches!(
+            self,
+            Style::Title | Style::Heading | Style::Strong | Style::Label
+        )
+    }
+
+    fn size(self) -> f32 {
+        match self {
+            Style::Title => 20.0,
+            Style::Heading => 13.0,
+            Style::Body | Style::Strong => 11.0,
+            Style::Cell => 10.0,
+            Style::Muted | Style::Label => 9.0,
+        }
+    }
+
+    fn leading(self) -> f32 {
+        self.size() * 1.45
+    }
+
+    fn gray(self) -> f32 {
+        match self {
+            Style::Muted | Style::Label => 0.45,
+            _ => 0.0,
+        }
+    }
+}
+
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x20..=0x7e | 0xa0..=0xff => c as u8,
+            _ => match c {
+                '€' => 0x80,
+                '…' => 0x85,
+                '‘' => 0x91,
+                '’' => 0x92,
+                '“' => 0x93,
+                '”' => 0x94,
+                '•' => 0x95,
+                '–' => 0x96,
+                '—' => 0x97,
+                '\t' => b' ',
+                _ => b'?',
+            },
+        })
+        .collect()
+}
+
+// Accented letters are as wide as the plain ones, near enough.
+fn plain(b: u8) -> Option<u8> {
+    Some(match b {
+        0xc0..=0xc5 => b'A',
+        0xc7 => b'C',
+        0xc8..=0xcb => b'E',
+        0xcc..=0xcf => b'I',
+        0xd1 => b'N',
+        0xd2..=0xd6 | 0xd8 => b'O',
+        0xd9..=0xdc => b'U',
+        0xdd => b'Y',
+        0xe0..=0xe5 => b'a',
+        0xe7 => b'c',
+        0xe8..=0xeb => b'e',
+        0xec..=0xef => b'i',
+        0xf1 => b'n',
+        0xf2..=0xf6 | 0xf8 => b'o',
+        0xf9..=0xfc => b'u',
+        0xfd | 0xff => b'y',
+        _ => return None,
+    })
+}
+
+fn width(text: &str, style: Style) -> f32 {
+    let widths = if style.bold() {
+        &BOLD_WIDTHS
+    } else {
+        &REGULAR_WIDTHS
+    };
+    let glyph = |b: u8| -> u16 {
+        match plain(b).unwrap_or(b) {
+            b @ 0x20..=0x7e => widths[usize::from(b - 0x20)],
+            0x85 | 0x97 => 1000,
+            0x91 | 0x92 | 0xa0 => 278,
+            0x93 | 0x94 => 333,
+            0x95 => 350,
+            _ => 556,
+        }
+    };
+    let total: u32 = encode(text).into_iter().map(|b| u32::from(glyph(b))).sum();
+    total as f32 * style.size() / 1000.0
+}
+
+// Lines no wider than `max`, breaking between words, and inside a word
+// only when it is wider than a line by itself.
+fn wrap(text: &str, style: Style, max: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let joined = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{line} {word}")
+            };
+            if width(&joined, style) <= max {
+                line = joined;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in word.chars() {
+                line.push(c);
+                if width(&line, style) > max {
+                    line.pop();
+                    lines.push(std::mem::replace(&mut line, c.to_string()));
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn clip(text: &str, style: Style, max: f32) -> String {
+    if width(text, style) <= max {
+        return text.to_string();
+    }
+    let mut clipped = String::new();
+    for c in text.chars() {
+        clipped.push(c);
+        if width(&format!("{clipped}…"), style) > max {
+            clipped.pop();
+            break;
+        }
+    }
+    format!("{}…", clipped.trim_end())
+}
+
+fn show(content: &mut Content, x: f32, y: f32, text: &str, style: Style) {
+    content
+        .begin_text()
+        .set_fill_gray(style.gray())
+        .set_font(if style.bold() { BOLD } else { REGULAR }, style.size())
+        .next_line(x, y)
+        .show(Str(&encode(text)))
+        .end_text();
+}
+
+// Blocks laid out top to bottom, onto a new page when one is full.
+struct Document {
+    pages: Vec<Content>,
+    y: f32,
+}
+
+impl Document {
+    fn new() -> Self {
+        Document {
+            pages: vec![Content::new()],
+            y: TOP,
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Content::new());
+        self.y = TOP;
+    }
+
+    fn need(&mut self, height: f32) {
+        if self.y - height < BOTTOM {
+            self.new_page();
+        }
+    }
+
+    fn line(&mut self, style: Style) -> (&mut Content, f32) {
+        self.need(style.leading());
+        self.y -= style.leading();
+        let y = self.y;
+        (self.pages.last_mut().unwrap(), y)
+    }
+
+    fn text(&mut self, text: &str, style: Style) {
+        for line in wrap(text, style, WIDTH - 2.0 * MARGIN) {
+            let (content, y) = self.line(style);
+            show(content, MARGIN, y, &line, style);
+        }
+    }
+
+    fn right(&mut self, text: &str, style: Style) {
+        let x = WIDTH - MARGIN - width(text, style);
+        let (content, y) = self.line(style);
+        show(content, x, y, text, style);
+    }
+
+    fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn rule(&mut self) {
+        self.need(12.0);
+        self.y -= 6.0;
+        let y = self.y;
+        self.pages
+            .last_mut()
+            .unwrap()
+            .set_stroke_gray(0.8)
+            .set_line_width(0.5)
+            .move_to(MARGIN, y)
+            .line_to(WIDTH - MARGIN, y)
+            .stroke();
+        self.y -= 6.0;
+    }
+
+    // Columns as shares of the width; cells too long are cut short. The
+    // header is repeated at the top of each page the table runs onto.
+    fn table(&mut self, columns: &[(String, f32)], rows: &[Vec<String>]) {
+        let full = WIDTH - 2.0 * MARGIN;
+        let header = |document: &mut Document| {
+            let (content, y) = document.line(Style::Label);
+            let mut x = MARGIN;
+            for (title, share) in columns {
+                show(
+                    content,
+                    x,
+                    y,
+                    &clip(title, Style::Label, share * full - 8.0),
+                    Style::Label,
+                );
+                x += share * full;
+            }
+        };
+        self.need(Style::Label.leading() + Style::Cell.leading());
+        header(self);
+        for row in rows {
+            if self.y - Style::Cell.leading() < BOTTOM {
+                self.new_page();
+                header(self);
+            }
+            let (content, y) = self.line(Style::Cell);
+            let mut x = MARGIN;
+            for ((_, share), cell) in columns.iter().zip(row) {
+                show(
+                    content,
+                    x,
+                    y,
+                    &clip(cell, Style::Cell, share * full - 8.0),
+                    Style::Cell,
+                );
+                x += share * full;
+            }
+        }
+    }
+
+    fn finish(self, title: &str) -> Vec<u8> {
+        let mut pdf = Pdf::new();
+        let catalog = Ref::new(1);
+        let tree = Ref::new(2);
+        let (regular, bold) = (Ref::new(3), Ref::new(4));
+        let info = Ref::new(5);
+        let ids: Vec<(Ref, Ref)> = (0..self.pages.len() as i32)
+            .map(|i| (Ref::new(6 + 2 * i), Ref::new(7 + 2 * i)))
+            .collect();
+        pdf.catalog(catalog).pages(tree);
+        pdf.pages(tree)
+            .kids(ids.iter().map(|(page, _)| *page))
+            .count(ids.len() as i32);
+        pdf.type1_font(regular)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        pdf.type1_font(bold)
+            .base_font(Name(b"Helvetica-Bold"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        pdf.document_info(info)
+            .title(TextStr(title))
+            .creator(TextStr("currobot"));
+        let pages = self.pages.len();
+        for (n, (mut content, (page_id, content_id))) in self.pages.into_iter().zip(ids).enumerate()
+        {
+            if pages > 1 {
+                let number = t_args(
+                    "pdf-page",
+                    &[("page", (n + 1).into()), ("pages", pages.into())],
+                );
+                let x = (WIDTH - width(&number, Style::Muted)) / 2.0;
+                show(&mut content, x, BOTTOM / 2.0, &number, Style::Muted);
+            }
+            let mut page = pdf.page(page_id);
+            page.media_box(Rect::new(0.0, 0.0, WIDTH, HEIGHT))
+                .parent(tree)
+                .contents(content_id);
+            page.resources()
+                .fonts()
+                .pair(REGULAR, regular)
+                .pair(BOLD, bold);
+            page.finish();
+            pdf.stream(content_id, &content.finish());
+        }
+        pdf.finish()
+    }
+}
+
+// With the strftime format the language's `format_id` gives; a broken one
+// in a translation falls back to ISO rather than failing.
+pub fn date(date: NaiveDate, format_id: &str) -> String {
+    let mut text = String::new();
+    match write!(
+        text,
+        "{}",
+        date.format_localized(&t(format_id), i18n::chrono_locale())
+    ) {
+        Ok(()) => text,
+        Err(_) => date.format("%Y-%m-%d").to_string(),
+    }
+}
+
+fn slug(text: &str) -> String {
+    let slug: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    slug.join("-")
+}
+
+fn save(app: &AppHandle, file_name: String, bytes: Vec<u8>) -> Result<Option<String>, String> {
+    let Some(chosen) = app
+        .dialog()
+        .file()
+        .set_title(t("pdf-save-title"))
+        .set_file_name(file_name)
+        .add_filter("PDF", &["pdf"])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let path = chosen.into_path().map_err(|e| e.to_string())?;
+    fs::write(&path, bytes).map_err(|e| e.to_string())?;
+    Ok(Some(path.display().to_string()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CoverLetterInput {
+    // Fills in the company and role when given
+    pub application_id: Option<i64>,
+    pub company: Option<String>,
+    pub role: Option<String>,
+    pub sender_name: String,
+    // Address, email, phone: one per line
+    #[serde(default)]
+    pub sender_details: String,
+    pub recipient: Option<String>,
+    // The letter, with its placeholders (templates::values) filled in;
+    // paragraphs are separated by a blank line
+    pub body: String,
+}
+
+fn cover_letter(letter: &CoverLetterInput, body: &str, company: &str, role: &str) -> Vec<u8> {
+    let today = date(Local::now().date_naive(), "pdf-date-long");
+    let mut document = Document::new();
+    document.text(letter.sender_name.trim(), Style::Heading);
+    for line in letter
+        .sender_details
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+    {
+        document.text(line.trim(), Style::Muted);
+    }
+    document.space(28.0);
+    document.right(&today, Style::Body);
+    document.space(20.0);
+    if let Some(recipient) = letter.recipient.as_deref().filter(|r| !r.trim().is_empty()) {
+        document.text(recipient.trim(), Style::Body);
+    }
+    if !company.is_empty() {
+        document.text(company, Style::Body);
+    }
+    if !role.is_empty() {
+        document.space(14.0);
+        document.text(
+            &t_args("pdf-letter-subject", &[("role", role.into())]),
+            Style::Strong,
+        );
+    }
+    document.space(20.0);
+    for paragraph in body.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        document.text(paragraph, Style::Body);
+        document.space(10.0);
+    }
+    let title = if company.is_empty() {
+        t("pdf-letter-title")
+    } else {
+        t_args("pdf-letter-title-for", &[("company", company.into())])
+    };
+    document.finish(&title)
+}
+
+fn in_week(time: Option<NaiveDateTime>, monday: NaiveDate) -> bool {
+    time.is_some_and(|t| t.date() >= monday && t.date() < monday + Days::new(7))
+}
+
+fn status(text: &str) -> String {
+    text.replace('_', " ")
+}
+
+fn weekly_report(app: &AppHandle, monday: NaiveDate) -> Result<Vec<u8>, String> {
+    let entries = history::entries(app, &HistoryQuery::default())?;
+    let db = history::open(app)?;
+    let mut statement = db
+        .prepare(
+            "SELECT a.company, s.status, s.at FROM status_changes s
+             JOIN applications a ON a.id = s.application_id ORDER BY s.at",
+        )
+        .map_err(|e| e.to_string())?;
+    let changes: Vec<(String, String, Option<NaiveDateTime>)> = statement
+        .query_map([], |r| {
+            Ok((r.get(0)?, r.get(1)?, history::local(&Some(r.get(2)?))))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|(_, _, at)| in_week(*at, monday))
+        .collect();
+    let events: Vec<_> = calendar::list(&db, None)?
+        .into_iter()
+        .filter(|e| {
+            in_week(
+                Some(e.starts_at.with_timezone(&Local).naive_local()),
+                monday,
+            )
+        })
+        .collect();
+    let added = entries
+        .iter()
+        .filter(|e| in_week(history::local(&e.created_at), monday))
+        .count();
+    let submitted = entries
+        .iter()
+        .filter(|e| in_week(history::local(&e.submitted_at), monday))
+        .count();
+    let time = activity::week(&db, monday)?;
+    let active: Vec<_> = entries
+        .iter()
+        .filter(|e| {
+            [&e.created_at, &e.updated_at, &e.submitted_at]
+                .into_iter()
+                .any(|t| in_week(history::local(t), monday))
+        })
+        .collect();
+
+    let title = t("pdf-report-title");
+    let mut document = Document::new();
+    document.text(&title, Style::Title);
+    document.text(
+        &t_args(
+            "pdf-report-week",
+            &[
+                ("from", date(monday, "pdf-date-long").into()),
+                ("to", date(monday + Days::new(6), "pdf-date-long").into()),
+            ],
+        ),
+        Style::Body,
+    );
+    document.text(
+        &t_args(
+            "pdf-report-generated",
+            &[(
+                "date",
+                date(Local::now().date_naive(), "pdf-date-long").into(),
+            )],
+        ),
+        Style::Muted,
+    );
+    document.rule();
+
+    document.space(6.0);
+    document.text(&t("pdf-report-summary"), Style::Heading);
+    for (id, count) in [
+        ("pdf-report-added", added),
+        ("pdf-report-submitted", submitted),
+        ("pdf-report-changes", changes.len()),
+        ("pdf-report-events", events.len()),
+    ] {
+        document.text(&t_args(id, &[("count", count.into())]), Style::Body);
+    }
+
+    let nothing = |document: &mut Document| document.text(&t("pdf-report-nothing"), Style::Muted);
+
+    document.space(16.0);
+    document.text(&t("pdf-report-activity"), Style::Heading);
+    let days: Vec<_> = time
+        .days
+        .iter()
+        .filter(|d| d.focus_seconds > 0 || d.runs > 0)
+        .collect();
+    if days.is_empty() {
+        nothing(&mut document);
+    } else {
+        document.text(
+            &t_args(
+                "pdf-report-focus",
+                &[("time", activity::duration(time.focus_seconds).into())],
+            ),
+            Style::Body,
+        );
+        document.text(
+            &t_args(
+                "pdf-report-runs",
+                &[
+                    ("count", time.runs.into()),
+                    ("time", activity::duration(time.run_seconds).into()),
+                ],
+            ),
+            Style::Body,
+        );
+        document.space(6.0);
+        let rows: Vec<Vec<String>> = days
+            .iter()
+            .map(|d| {
+                vec![
+                    date(d.date, "pdf-date-short"),
+                    activity::duration(d.focus_seconds),
+                    d.runs.to_string(),
+                    activity::duration(d.run_seconds),
+                ]
+            })
+            .collect();
+        document.table(
+            &[
+                (t("pdf-column-date"), 0.16),
+                (t("pdf-column-focus"), 0.3),
+                (t("pdf-column-runs"), 0.24),
+                (t("pdf-column-run-time"), 0.3),
+            ],
+            &rows,
+        );
+    }
+
+    document.space(16.0);
+    document.text(&t("pdf-report-applications"), Style::Heading);
+    if active.is_empty() {
+        nothing(&mut document);
+    } else {
+        let rows: Vec<Vec<String>> = active
+            .iter()
+            .map(|e| {
+                vec![
+                    e.company.clone(),
+                    e.role.clone().unwrap_or_default(),
+                    status(&e.status),
+                    history::local(&e.submitted_at)
+                        .map(|t| date(t.date(), "pdf-date-short"))
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect();
+        document.table(
+            &[
+                (t("export-column-company"), 0.3),
+                (t("export-column-role"), 0.34),
+                (t("export-column-status"), 0.2),
+                (t("export-column-submitted-at"), 0.16),
+            ],
+            &rows,
+        );
+    }
+
+    document.space(16.0);
+    document.text(&t("pdf-report-status-changes"), Style::Heading);
+    if changes.is_empty() {
+        nothing(&mut document);
+    } else {
+        let rows: Vec<Vec<String>> = changes
+            .iter()
+            .map(|(company, to, at)| {
+                vec![
+                    at.map(|t| date(t.date(), "pdf-date-short"))
+                        .unwrap_or_default(),
+                    company.clone(),
+                    status(to),
+                ]
+            })
+            .collect();
+        document.table(
+            &[
+                (t("pdf-column-date"), 0.16),
+                (t("export-column-company"), 0.44),
+                (t("export-column-status"), 0.4),
+            ],
+            &rows,
+        );
+    }
+
+    document.space(16.0);
+    document.text(&t("pdf-report-calendar"), Style::Heading);
+    if events.is_empty() {
+        nothing(&mut document);
+    } else {
+        let rows: Vec<Vec<String>> = events
+            .iter()
+            .map(|e| {
+                let starts = e.starts_at.with_timezone(&Local);
+                vec![
+                    format!(
+                        "{} {}",
+                        date(starts.date_naive(), "pdf-date-short"),
+                        starts.format("%H:%M")
+                    ),
+                    t(match e.kind {
+                        Kind::Interview => "calendar-interview",
+                        Kind::FollowUp => "calendar-follow-up",
+                    }),
+                    e.company.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        document.table(
+            &[
+                (t("pdf-column-when"), 0.22),
+                (t("pdf-column-event"), 0.38),
+                (t("export-column-company"), 0.4),
+            ],
+            &rows,
+        );
+    }
+    Ok(document.finish(&title))
+}
+
+// The path written, or None when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_cover_letter_pdf(
+    app: AppHandle,
+    letter: CoverLetterInput,
+) -> Result<Option<String>, String> {
+    if letter.sender_name.trim().is_empty() || letter.body.trim().is_empty() {
+        return Err("a letter needs a sender and a body".into());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let entry = match letter.application_id {
+            Some(id) => history::detail(&app, id)?.map(|d| d.entry),
+            None => None,
+        };
+        let given =
+            |v: &Option<String>| v.as_deref().map(str::trim).unwrap_or_default().to_string();
+        let company = entry
+            .as_ref()
+            .map(|e| e.company.clone())
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| given(&letter.company));
+        let role = entry
+            .as_ref()
+            .and_then(|e| e.role.clone())
+            .filter(|r| !r.is_empty())
+            .unwrap_or_else(|| given(&letter.role));
+        let values = templates::values(
+            entry.as_ref(),
+            &company,
+            &role,
+            &letter.sender_name,
+            letter.recipient.as_deref(),
+        );
+        let body = templates::fill(&letter.body, &values).text;
+        let bytes = cover_letter(&letter, &body, &company, &role);
+        let name = match slug(&company).as_str() {
+            "" => "cover-letter.pdf".to_string(),
+            company => format!("cover-letter-{company}.pdf"),
+        };
+        save(&app, name, bytes)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// The week, Monday to Sunday, with `week_of` (YYYY-MM-DD) in it; this
+// week by default.
+#[tauri::command]
+pub async fn export_weekly_report_pdf(
+    app: AppHandle,
+    week_of: Option<String>,
+) -> Result<Option<String>, String> {
+    let day = match week_of.as_deref().filter(|d| !d.is_empty()) {
+        Some(day) => NaiveDate::parse_from_str(day, "%Y-%m-%d").map_err(|e| e.to_string())?,
+        None => Local::now().date_naive(),
+    };
+    let monday = day.week(Weekday::Mon).first_day();
+    tauri::async_runtime::spawn_blocking(move || {
+        let bytes = weekly_report(&app, monday)?;
+        let written = save(&app, format!("weekly-report-{monday}.pdf"), bytes)?;
+        if written.is_some() {
+            info!(%monday, "weekly report exported");
+        }
+        Ok(written)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_in_win_ansi() {
+        assert_eq!(encode("Año — 5 €"), b"A\xf1o \x97 5 \x80");
+        assert_eq!(encode("日本\tok"), b"?? ok");
+    }
+
+    #[test]
+    fn accents_measure_as_plain_letters() {
+        assert_eq!(width("Peña", Style::Body), width("Pena", Style::Body));
+        assert!(width("Pena", Style::Strong) > width("Pena", Style::Body));
+    }
+
+    #[test]
+    fn wrapped_lines_fit() {
+        let text = "Ten years building data pipelines for logistics and retail teams.\n\nMadrid";
+        let lines = wrap(text, Style::Body, 150.0);
+        assert!(lines.len() > 2);
+        assert!(lines.iter().all(|l| width(l, Style::Body) <= 150.0));
+        let words = |s: &str| s.split_whitespace().map(str::to_string).collect::<Vec<_>>();
+        assert_eq!(words(&lines.join(" ")), words(text));
+        // The blank line between paragraphs is kept
+        assert!(lines.iter().any(String::is_empty));
+
+        let long = wrap(&"x".repeat(200), Style::Body, 100.0);
+        assert!(long.len() > 1);
+        assert_eq!(long.concat(), "x".repeat(200));
+    }
+
+    #[test]
+    fn clipped_with_an_ellipsis() {
+        assert_eq!(clip("Acme", Style::Cell, 100.0), "Acme");
+        let clipped = clip("Senior platform engineer, payments", Style::Cell, 60.0);
+        assert!(clipped.ends_with('…'));
+        assert!(width(&clipped, Style::Cell) <= 60.0);
+    }
+
+    #[test]
+    fn file_name_slugs() {
+        assert_eq!(slug("Acme Corp. (Madrid)"), "acme-corp-madrid");
+        assert_eq!(slug("  "), "");
+    }
+
+    #[test]
+    fn weeks_run_from_monday() {
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let at = |d: u32| {
+            NaiveDate::from_ymd_opt(2026, 10, d)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+        };
+        assert!(in_week(at(12), monday));
+        assert!(in_week(at(18), monday));
+        assert!(!in_week(at(19), monday));
+        assert!(!in_week(at(11), monday));
+        assert!(!in_week(None, monday));
+    }
+
+    #[test]
+    fn cover_letters_read_back() {
+        let letter = CoverLetterInput {
+            application_id: None,
+            company: None,
+            role: None,
+            sender_name: "Ana Peña".into(),
+            sender_details: "ana@example.com\n\n+34 600 000 000".into(),
+            recipient: Some("Hiring team".into()),
+            body: String::new(),
+        };
+        let body = "Dear team,\n\nI would like to apply.";
+        let bytes = cover_letter(&letter, body, "Acme", "Engineer");
+        assert!(bytes.starts_with(b"%PDF-"));
+        let text = crate::pdftext::text(&bytes).unwrap();
+        for expected in [
+            "Ana Peña",
+            "ana@example.com",
+            "Hiring team",
+            "Acme",
+            "I would like to apply.",
+        ] {
+            assert!(text.contains(expected), "{expected} in {text:?}");
+        }
+    }
+}
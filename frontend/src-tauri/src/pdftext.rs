@@ -0,0 +1,930 @@
+use flate2::read::ZlibDecoder;
+use regex::bytes::Regex;
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+    rc::Rc,
+    sync::LazyLock,
+};
+
+// The text of a PDF, page by page and line by line, for reading résumés
+// without the engine (`resume`). Enough of the format for what word
+// processors and CV builders write: objects, direct or in object streams,
+// Flate streams, the page tree, and fonts' ToUnicode maps; fonts without one
+// are read as WinAnsi. There are no glyph widths here, so where a line is
+// split into pieces the gap between them is estimated from the font size.
+// Encrypted files and scans (no text at all) are refused.
+const MAX_DEPTH: usize = 16;
+
+static OBJ: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+)\s+\d+\s+obj\b").unwrap());
+
+#[derive(Debug, Clone)]
+enum Obj {
+    Null,
+    Num(f64),
+    Name(String),
+    Str(Vec<u8>),
+    Array(Vec<Obj>),
+    Dict(HashMap<String, Obj>),
+    Ref(u32),
+    // A bare word: an operator in content streams, `true`/`false` elsewhere
+    Op(String),
+}
+
+impl Obj {
+    fn get(&self, key: &str) -> &Obj {
+        match self {
+            Obj::Dict(d) => d.get(key).unwrap_or(&Obj::Null),
+            _ => &Obj::Null,
+        }
+    }
+
+    fn num(&self) -> Option<f64> {
+        match self {
+            Obj::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        match self {
+            Obj::Name(n) => Some(n),
+            _ => None,
+        }
+    }
+}
+
+fn is_space(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\r' | b'\n' | b'\x0c' | b'\0')
+}
+
+fn is_delimiter(b: u8) -> bool {
+    matches!(
+        b,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+struct Lexer<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(data: &'a [u8], pos: usize) -> Self {
+        Self { data, pos }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_space(&mut self) {
+        while let Some(b) = self.peek() {
+            if is_space(b) {
+                self.pos += 1;
+            } else if b == b'%' {
+                while self.peek().is_some_and(|b| b != b'\n' && b != b'\r') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn word(&mut self) -> &'a [u8] {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| !is_space(b) && !is_delimiter(b))
+        {
+            self.pos += 1;
+        }
+        &self.data[start..self.pos]
+    }
+
+    fn starts_with(&self, s: &[u8]) -> bool {
+        self.data[self.pos.min(self.data.len())..].starts_with(s)
+    }
+
+    // The next object, or None at the end or on a closing `]` / `>>`
+    fn parse(&mut self, depth: usize) -> Option<Obj> {
+        self.skip_space();
+        let b = self.peek()?;
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        match b {
+            b'/' => {
+                self.pos += 1;
+                Some(Obj::Name(name(self.word())))
+            }
+            b'(' => Some(Obj::Str(self.literal())),
+            b'<' if self.starts_with(b"<<") => {
+                self.pos += 2;
+                let mut dict = HashMap::new();
+                loop {
+                    self.skip_space();
+                    if self.starts_with(b">>") {
+                        self.pos += 2;
+                        break;
+                    }
+                    match self.parse(depth + 1) {
+                        Some(Obj::Name(key)) => {
+                            let value = self.parse(depth + 1).unwrap_or(Obj::Null);
+                            dict.insert(key, value);
+                        }
+                        Some(_) => {}
+                        None => {
+                            if self.peek().is_none() {
+                                break;
+                            }
+                            self.pos += 1;
+                        }
+                    }
+                }
+                Some(Obj::Dict(dict))
+            }
+            b'<' => Some(Obj::Str(self.hex())),
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_space();
+                    match self.peek() {
+                        None => break,
+                        Some(b']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        _ => match self.parse(depth + 1) {
+                            Some(item) => items.push(item),
+                            None => self.pos += 1,
+                        },
+                    }
+                }
+                Some(Obj::Array(items))
+            }
+            b']' | b'>' | b')' | b'{' | b'}' => None,
+            b'0'..=b'9' | b'+' | b'-' | b'.' => {
+                let n = self.number();
+                // `n g R`, a reference
+                let save = self.pos;
+                self.skip_space();
+                if n.fract() == 0.0 && self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                    let generation = self.word();
+                    self.skip_space();
+                    if generation.iter().all(u8::is_ascii_digit)
+                        && self.starts_with(b"R")
+                        && self
+                            .data
+                            .get(self.pos + 1)
+                            .is_none_or(|&b| is_space(b) || is_delimiter(b))
+                    {
+                        self.pos += 1;
+                        return Some(Obj::Ref(n as u32));
+                    }
+                }
+                self.pos = save;
+                Some(Obj::Num(n))
+            }
+            _ => {
+                let word = self.word();
+                if word.is_empty() {
+                    return None;
+                }
+                Some(match word {
+                    b"null" => Obj::Null,
+                    _ => Obj::Op(String::from_utf8_lossy(word).into_owned()),
+                })
+            }
+        }
+    }
+
+    fn number(&mut self) -> f64 {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.'))
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.data[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0.0)
+    }
+
+    fn literal(&mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut nesting = 0;
+        self.pos += 1;
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            match b {
+                b'(' => {
+                    nesting += 1;
+                    out.push(b);
+                }
+                b')' if nesting == 0 => break,
+                b')' => {
+                    nesting -= 1;
+                    out.push(b);
+                }
+                b'\\' => {
+                    let Some(e) = self.peek() else { break };
+                    self.pos += 1;
+                    match e {
+                        b'n' => out.push(b'\n'),
+                        b'r' => out.push(b'\r'),
+                        b't' => out.push(b'\t'),
+                        b'b' => out.push(8),
+                        b'f' => out.push(12),
+                        b'\r' => {
+                            if self.peek() == Some(b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => {}
+                        b'0'..=b'7' => {
+                            let mut code = u32::from(e - b'0');
+                            for _ in 0..2 {
+                                match self.peek() {
+                                    Some(d @ b'0'..=b'7') => {
+                                        code = code * 8 + u32::from(d - b'0');
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            out.push(code as u8);
+                        }
+                        other => out.push(other),
+                    }
+                }
+                _ => out.push(b),
+            }
+        }
+        out
+    }
+
+    fn hex(&mut self) -> Vec<u8> {
+        self.pos += 1;
+        let mut digits = Vec::new();
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            if b == b'>' {
+                break;
+            }
+            if let Some(d) = (b as char).to_digit(16) {
+                digits.push(d as u8);
+            }
+        }
+        if digits.len() % 2 == 1 {
+            digits.push(0);
+        }
+        digits.chunks(2).map(|p| p[0] << 4 | p[1]).collect()
+    }
+
+    // Past an inline image's data, after its `ID`
+    fn skip_inline_image(&mut self) {
+        while self.pos + 2 < self.data.len() {
+            if is_space(self.data[self.pos])
+                && self.data[self.pos + 1..].starts_with(b"EI")
+                && self
+                    .data
+                    .get(self.pos + 3)
+                    .is_none_or(|&b| is_space(b) || is_delimiter(b))
+            {
+                self.pos += 3;
+                return;
+            }
+            self.pos += 1;
+        }
+        self.pos = self.data.len();
+    }
+}
+
+// Names may escape bytes as #xx
+fn name(raw: &[u8]) -> String {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] == b'#' {
+            if let Some(b) = raw
+                .get(i + 1..i + 3)
+                .and_then(|h| std::str::from_utf8(h).ok())
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+            {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(raw[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// An object and, for a stream, its decoded data
+type Entry = (Obj, Option<Rc<Vec<u8>>>);
+
+struct Document {
+    objects: HashMap<u32, Entry>,
+}
+
+impl Document {
+    fn load(data: &[u8]) -> Self {
+        let mut objects = HashMap::new();
+        let mut streams = Vec::new();
+        for c in OBJ.captures_iter(data) {
+            let Some(number) = std::str::from_utf8(&c[1]).ok().and_then(|n| n.parse().ok()) else {
+                continue;
+            };
+            let mut lexer = Lexer::new(data, c.get(0).unwrap().end());
+            let Some(obj) = lexer.parse(0) else {
+                continue;
+            };
+            lexer.skip_space();
+            let stream = lexer
+                .starts_with(b"stream")
+                .then(|| raw_stream(data, lexer.pos + b"stream".len(), &obj))
+                .and_then(|raw| decode(&obj, raw))
+                .map(Rc::new);
+            if obj.get("Type").name() == Some("ObjStm") {
+                if let Some(stream) = &stream {
+                    streams.push((obj.clone(), stream.clone()));
+                }
+            }
+            // Later revisions of an object come later in the file
+            objects.insert(number, (obj, stream));
+        }
+        for (header, data) in streams {
+            let count = header.get("N").num().unwrap_or(0.0) as usize;
+            let first = header.get("First").num().unwrap_or(0.0) as usize;
+            let mut lexer = Lexer::new(&data, 0);
+            let mut entries = Vec::new();
+            for _ in 0..count {
+                match (lexer.parse(0), lexer.parse(0)) {
+                    (Some(Obj::Num(n)), Some(Obj::Num(offset))) => {
+                        entries.push((n as u32, offset as usize))
+                    }
+                    _ => break,
+                }
+            }
+            for (number, offset) in entries {
+                if let Some(obj) = Lexer::new(&data, first + offset).parse(0) {
+                    objects.entry(number).or_insert((obj, None));
+                }
+            }
+        }
+        Self { objects }
+    }
+
+    fn resolve<'a>(&'a self, obj: &'a Obj) -> &'a Obj {
+        let mut obj = obj;
+        for _ in 0..MAX_DEPTH {
+            match obj {
+                Obj::Ref(n) => match self.objects.get(n) {
+                    Some((target, _)) => obj = target,
+                    None => return &Obj::Null,
+                },
+                _ => return obj,
+            }
+        }
+        &Obj::Null
+    }
+
+    fn stream(&self, obj: &Obj) -> Option<Rc<Vec<u8>>> {
+        match obj {
+            Obj::Ref(n) => self.objects.get(n).and_then(|(_, s)| s.clone()),
+            _ => None,
+        }
+    }
+
+    // Pages in order with their resources, inherited down the tree
+    fn pages(&self) -> Vec<(&Obj, &Obj)> {
+        let mut pages = Vec::new();
+        let root = self
+            .objects
+            .values()
+            .find(|(o, _)| o.get("Type").name() == Some("Catalog"))
+            .map(|(o, _)| o.get("Pages"));
+        if let Some(root) = root {
+            let mut seen = HashSet::new();
+            self.walk(root, &Obj::Null, &mut pages, &mut seen);
+        }
+        if pages.is_empty() {
+            let mut loose: Vec<_> = self
+                .objects
+                .iter()
+                .filter(|(_, (o, _))| o.get("Type").name() == Some("Page"))
+                .collect();
+            loose.sort_by_key(|(n, _)| **n);
+            pages = loose
+                .into_iter()
+                .map(|(_, (o, _))| (o, self.resolve(o.get("Resources"))))
+                .collect();
+        }
+        pages
+    }
+
+    fn walk<'a>(
+        &'a self,
+        node: &'a Obj,
+        inherited: &'a Obj,
+        pages: &mut Vec<(&'a Obj, &'a Obj)>,
+        seen: &mut HashSet<u32>,
+    ) {
+        if let Obj::Ref(n) = node {
+            if !seen.insert(*n) {
+                return;
+            }
+        }
+        let node = self.resolve(node);
+        let resources = match self.resolve(node.get("Resources")) {
+            Obj::Null => inherited,
+            own => own,
+        };
+        match node.get("Kids") {
+            Obj::Null if node.get("Type").name() != Some("Pages") => pages.push((node, resources)),
+            kids => {
+                if let Obj::Array(kids) = self.resolve(kids) {
+                    for kid in kids {
+                        self.walk(kid, resources, pages, seen);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn raw_stream<'a>(data: &'a [u8], mut start: usize, dict: &Obj) -> &'a [u8] {
+    if data.get(start) == Some(&b'\r') {
+        start += 1;
+    }
+    if data.get(start) == Some(&b'\n') {
+        start += 1;
+    }
+    let start = start.min(data.len());
+    // A direct /Length is trusted when `endstream` follows it
+    if let Some(len) = dict.get("Length").num() {
+        let end = start + len as usize;
+        if end <= data.len() {
+            let mut lexer = Lexer::new(data, end);
+            lexer.skip_space();
+            if lexer.starts_with(b"endstream") {
+                return &data[start..end];
+            }
+        }
+    }
+    let end = data[start..]
+        .windows(9)
+        .position(|w| w == b"endstream")
+        .map_or(data.len(), |i| start + i);
+    let mut raw = &data[start..end];
+    while raw.last().is_some_and(|b| *b == b'\n' || *b == b'\r') {
+        raw = &raw[..raw.len() - 1];
+    }
+    raw
+}
+
+fn decode(dict: &Obj, raw: &[u8]) -> Option<Vec<u8>> {
+    let filters = match dict.get("Filter") {
+        Obj::Name(n) => vec![n.as_str()],
+        Obj::Array(items) => items.iter().filter_map(Obj::name).collect(),
+        _ => Vec::new(),
+    };
+    let mut data = raw.to_vec();
+    for filter in filters {
+        data = match filter {
+            "FlateDecode" | "Fl" => {
+                let mut out = Vec::new();
+                // A damaged tail still leaves what came before it
+                let _ = ZlibDecoder::new(data.as_slice()).read_to_end(&mut out);
+                out
+            }
+            _ => return None,
+        };
+    }
+    Some(data)
+}
+
+// A font's ToUnicode map: codes of `width` bytes to text
+struct CMap {
+    width: usize,
+    map: HashMap<u32, String>,
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|p| u16::from_be_bytes([p[0], *p.get(1).unwrap_or(&0)]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn code(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |n, b| n << 8 | u32::from(*b))
+}
+
+fn parse_cmap(data: &[u8]) -> CMap {
+    let mut cmap = CMap {
+        width: 0,
+        map: HashMap::new(),
+    };
+    let mut lexer = Lexer::new(data, 0);
+    let mut section = String::new();
+    let mut operands = Vec::new();
+    while lexer.pos < data.len() {
+        let Some(obj) = lexer.parse(0) else {
+            lexer.pos += 1;
+            continue;
+        };
+        match obj {
+            Obj::Op(op) if op.starts_with("begin") => {
+                section = op;
+                operands.clear();
+            }
+            Obj::Op(op) if op.starts_with("end") => section.clear(),
+            obj => {
+                operands.push(obj);
+                match (section.as_str(), operands.as_slice()) {
+                    ("begincodespacerange", [Obj::Str(lo), Obj::Str(_)]) => {
+                        cmap.width = cmap.width.max(lo.len());
+                        operands.clear();
+                    }
+                    ("beginbfchar", [Obj::Str(src), Obj::Str(dst)]) => {
+                        cmap.map.insert(code(src), utf16(dst));
+                        operands.clear();
+                    }
+                    ("beginbfrange", [Obj::Str(lo), Obj::Str(hi), dst]) => {
+                        let (lo, hi) = (code(lo), code(hi));
+                        for (i, c) in (lo..=hi.min(lo + 0xffff)).enumerate() {
+                            let text = match dst {
+                                Obj::Str(base) if base.len() >= 2 => {
+                                    let mut base = base.clone();
+                                    let n = base.len();
+                                    let last = u16::from_be_bytes([base[n - 2], base[n - 1]])
+                                        .wrapping_add(i as u16);
+                                    base[n - 2..].copy_from_slice(&last.to_be_bytes());
+                                    utf16(&base)
+                                }
+                                Obj::Array(items) => match items.get(i) {
+                                    Some(Obj::Str(s)) => utf16(s),
+                                    _ => continue,
+                                },
+                                _ => continue,
+                            };
+                            cmap.map.insert(c, text);
+                        }
+                        operands.clear();
+                    }
+                    (_, ops) if ops.len() >= 3 => operands.clear(),
+                    _ => {}
+                }
+            }
+        }
+    }
+    if cmap.width == 0 {
+        cmap.width = if cmap.map.keys().any(|c| *c > 0xff) {
+            2
+        } else {
+            1
+        };
+    }
+    cmap
+}
+
+// WinAnsi's printable characters in 0x80-0x9f; the rest is Latin-1
+const WIN_ANSI: [char; 32] = [
+    '€', ' ', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', ' ', 'Ž', ' ', ' ', '‘', '’',
+    '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', ' ', 'ž', 'Ÿ',
+];
+
+#[derive(Clone, Default)]
+struct Font {
+    cmap: Option<Rc<CMap>>,
+    // Type0: two-byte codes, unreadable without a map
+    composite: bool,
+}
+
+impl Font {
+    fn decode(&self, bytes: &[u8]) -> String {
+        match &self.cmap {
+            Some(cmap) => bytes
+                .chunks(cmap.width.max(1))
+                .filter_map(|c| {
+                    let code = code(c);
+                    cmap.map
+                        .get(&code)
+                        .cloned()
+                        .or_else(|| (cmap.width == 1).then(|| char::from(c[0]).to_string()))
+                })
+                .collect(),
+            None if self.composite => String::new(),
+            None => bytes
+                .iter()
+                .map(|&b| match b {
+                    0x80..=0x9f => WIN_ANSI[usize::from(b - 0x80)],
+                    _ => char::from(b),
+                })
+                .collect(),
+        }
+    }
+}
+
+struct Reader<'a> {
+    doc: &'a Document,
+    cmaps: HashMap<u32, Rc<CMap>>,
+    out: String,
+    // Where the text line starts, where the last piece is estimated to have
+    // ended, and the scale of text space
+    line: (f64, f64),
+    end_x: f64,
+    last_y: Option<f64>,
+    scale: f64,
+    size: f64,
+    leading: f64,
+    font: Font,
+}
+
+impl<'a> Reader<'a> {
+    fn fonts(&mut self, resources: &'a Obj) -> HashMap<String, Font> {
+        let mut fonts = HashMap::new();
+        let Obj::Dict(entries) = self.doc.resolve(resources.get("Font")) else {
+            return fonts;
+        };
+        for (key, font) in entries {
+            let font_ref = font;
+            let font = self.doc.resolve(font_ref);
+            let to_unicode = font.get("ToUnicode");
+            let cmap = match to_unicode {
+                Obj::Ref(n) => match self.cmaps.get(n) {
+                    Some(cmap) => Some(cmap.clone()),
+                    None => self.doc.stream(to_unicode).map(|data| {
+                        let cmap = Rc::new(parse_cmap(&data));
+                        self.cmaps.insert(*n, cmap.clone());
+                        cmap
+                    }),
+                },
+                _ => None,
+            };
+            fonts.insert(
+                key.clone(),
+                Font {
+                    cmap,
+                    composite: font.get("Subtype").name() == Some("Type0"),
+                },
+            );
+        }
+        fonts
+    }
+
+    fn show(&mut self, bytes: &[u8]) {
+        let text = self.font.decode(bytes);
+        let chars = text.chars().count();
+        if chars == 0 {
+            return;
+        }
+        let height = (self.size * self.scale).abs().max(1.0);
+        let (x, y) = self.line;
+        let x = x.max(self.end_x);
+        match self.last_y {
+            Some(last) if (y - last).abs() > height * 0.4 => self.out.push('\n'),
+            Some(_) if self.line.0 > self.end_x + height * 0.2 => self.out.push(' '),
+            _ => {}
+        }
+        self.out.push_str(&text);
+        self.last_y = Some(y);
+        self.end_x = x + chars as f64 * height * 0.5;
+        self.line.0 = self.end_x;
+    }
+
+    fn run(&mut self, content: &[u8], resources: &'a Obj, depth: usize) {
+        if depth > 4 {
+            return;
+        }
+        let fonts = self.fonts(resources);
+        let mut lexer = Lexer::new(content, 0);
+        let mut operands: Vec<Obj> = Vec::new();
+        while lexer.pos < content.len() {
+            let Some(obj) = lexer.parse(0) else {
+                lexer.pos += 1;
+                continue;
+            };
+            let Obj::Op(op) = obj else {
+                operands.push(obj);
+                continue;
+            };
+            let n = |i: usize| {
+                operands
+                    .len()
+                    .checked_sub(i)
+                    .and_then(|i| operands[i].num())
+                    .unwrap_or(0.0)
+            };
+            match op.as_str() {
+                "BT" => {
+                    self.line = (0.0, 0.0);
+                    self.scale = 1.0;
+                }
+                "Tf" => {
+                    self.size = n(1);
+                    if let Some(Obj::Name(name)) =
+                        operands.len().checked_sub(2).map(|i| &operands[i])
+                    {
+                        self.font = fonts.get(name).cloned().unwrap_or_default();
+                    }
+                }
+                "TL" => self.leading = n(1),
+                "Td" | "TD" => {
+                    let (tx, ty) = (n(2), n(1));
+                    if op == "TD" {
+                        self.leading = -ty;
+                    }
+                    self.line = (self.line.0 + tx * self.scale, self.line.1 + ty * self.scale);
+                    if tx < 0.0 || ty != 0.0 {
+                        self.end_x = f64::MIN;
+                    }
+                }
+                "Tm" => {
+                    self.scale = n(6).hypot(n(5)).max(f64::EPSILON);
+                    self.line = (n(2), n(1));
+                    self.end_x = f64::MIN;
+                }
+                "T*" => self.next_line(),
+                "Tj" => {
+                    if let Some(Obj::Str(s)) = operands.last() {
+                        self.show(&s.clone());
+                    }
+                }
+                "'" | "\"" => {
+                    self.next_line();
+                    if let Some(Obj::Str(s)) = operands.last() {
+                        self.show(&s.clone());
+                    }
+                }
+                "TJ" => {
+                    if let Some(Obj::Array(items)) = operands.last() {
+                        for item in items.clone() {
+                            match item {
+                                Obj::Str(s) => self.show(&s),
+                                // Past kerning, a gap between words
+                                Obj::Num(k) if k < -120.0 => {
+                                    self.out.push(' ');
+                                    self.line.0 += -k / 1000.0 * self.size * self.scale;
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                "Do" => {
+                    if let Some(Obj::Name(name)) = operands.last() {
+                        let xobject = self.doc.resolve(resources.get("XObject")).get(name);
+                        let form = self.doc.resolve(xobject);
+                        if form.get("Subtype").name() == Some("Form") {
+                            if let Some(data) = self.doc.stream(xobject) {
+                                let own = match self.doc.resolve(form.get("Resources")) {
+                                    Obj::Null => resources,
+                                    own => own,
+                                };
+                                self.run(&data, own, depth + 1);
+                            }
+                        }
+                    }
+                }
+                "ID" => lexer.skip_inline_image(),
+                _ => {}
+            }
+            operands.clear();
+        }
+    }
+
+    fn next_line(&mut self) {
+        let step = if self.leading != 0.0 {
+            self.leading
+        } else {
+            self.size.max(1.0)
+        };
+        self.line.1 -= step * self.scale;
+        self.end_x = f64::MIN;
+    }
+}
+
+pub fn text(data: &[u8]) -> Result<String, String> {
+    if !data.starts_with(b"%PDF") {
+        return Err("not a PDF".into());
+    }
+    if data.windows(8).any(|w| w == b"/Encrypt") {
+        return Err("the PDF is encrypted".into());
+    }
+    let doc = Document::load(data);
+    let mut reader = Reader {
+        doc: &doc,
+        cmaps: HashMap::new(),
+        out: String::new(),
+        line: (0.0, 0.0),
+        end_x: f64::MIN,
+        last_y: None,
+        scale: 1.0,
+        size: 10.0,
+        leading: 0.0,
+        font: Font::default(),
+    };
+    for (page, resources) in doc.pages() {
+        let contents = match doc.resolve(page.get("Contents")) {
+            Obj::Array(parts) => parts.clone(),
+            _ => vec![page.get("Contents").clone()],
+        };
+        let mut content = Vec::new();
+        for part in &contents {
+            if let Some(data) = doc.stream(part) {
+                content.extend_from_slice(&data);
+                content.push(b'\n');
+            }
+        }
+        reader.last_y = None;
+        reader.run(&content, resources, 0);
+        reader.out.push('\n');
+    }
+    let text = reader
+        .out
+        .lines()
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.is_empty() {
+        return Err("the PDF has no text to read (a scan?)".into());
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::ZlibEncoder, Compression};
+    use std::io::Write;
+
+    // A one-page PDF around `content`, Flate-compressed when asked. No xref
+    // table: objects are found by scanning, as for damaged files.
+    fn pdf(content: &[u8], flate: bool) -> Vec<u8> {
+        let (stream, filter) = if flate {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(content).unwrap();
+            (encoder.finish().unwrap(), " /Filter /FlateDecode")
+        } else {
+            (content.to_vec(), "")
+        };
+        let mut out = b"%PDF-1.4\n".to_vec();
+        out.extend_from_slice(
+            b"1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj\n\
+              2 0 obj << /Type /Pages /Kids [3 0 R] /Count 1 >> endobj\n\
+              3 0 obj << /Type /Page /Parent 2 0 R /Contents 4 0 R\n\
+                /Resources << /Font << /F1 5 0 R >> >> >> endobj\n\
+              5 0 obj << /Type /Font /Subtype /Type1 /BaseFont /Helvetica >> endobj\n",
+        );
+        out.extend_from_slice(
+            format!("4 0 obj << /Length {}{filter} >>\nstream\n", stream.len()).as_bytes(),
+        );
+        out.extend_from_slice(&stream);
+        out.extend_from_slice(b"\nendstream endobj\n%%EOF\n");
+        out
+    }
+
+    #[test]
+    fn flate_stream() {
+        let data = pdf(
+            b"BT /F1 12 Tf 72 720 Td (Jane Doe) Tj 0 -14 Td (jane@example.com) Tj ET",
+            true,
+        );
+        assert_eq!(text(&data).unwrap(), "Jane Doe\njane@example.com");
+    }
+
+    #[test]
+    fn tj_kerning() {
+        // Small adjustments are kerning inside a word; large ones a space
+        let data = pdf(
+            b"BT /F1 12 Tf 72 720 Td [(Sen) -20 (ior) -250 (Engi) 15 (neer)] TJ ET",
+            false,
+        );
+        assert_eq!(text(&data).unwrap(), "Senior Engineer");
+    }
+
+    #[test]
+    fn hex_strings() {
+        // "Señora" in WinAnsi, with a space in the hex digits as allowed
+        let data = pdf(b"BT /F1 12 Tf 72 720 Td <5365 F16F7261> Tj ET", false);
+        assert_eq!(text(&data).unwrap(), "Señora");
+    }
+
+    #[test]
+    fn refused() {
+        assert!(text(b"not a pdf").is_err());
+        assert!(text(&pdf(b"0 0 m 10 10 l S", false)).is_err());
+    }
+}
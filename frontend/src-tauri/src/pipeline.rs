@@ -0,0 +1,616 @@
+use chrono::Utc;
+use regex::Regex;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+use tauri::{AppHandle, Emitter};
+use tracing::info;
+
+use crate::{
+    backend::{self, Failure},
+    history::{self, HISTORY_CHANGED},
+    offlinequeue,
+};
+
+// The Applications board, kept in the history database so it works with the
+// engine down: its stages (columns, each holding one or more statuses, in
+// order), where each card sits within its stage, and notes on each card,
+// which stay on this machine. A status change from the board is sent to the
+// engine straight away, or queued in the offline queue while it's
+// unreachable and recorded here either way; until a queued change is sent,
+// the board shows it over whatever the engine last said.
+pub const PIPELINE_CHANGED: &str = "pipeline-changed";
+const MAX_NOTES: usize = 10_000;
+// What the engine accepts from the user (PATCH .../status)
+const MANUAL_STATUSES: &[&str] = &[
+    "acknowledged",
+    "interview_scheduled",
+    "interviewed",
+    "offered",
+    "rejected",
+    "withdrawn",
+];
+const DEFAULT_STAGES: &[(&str, &str, &[&str])] = &[
+    (
+        "pending_human_review",
+        "Pending Review",
+        &["pending_human_review"],
+    ),
+    ("cv_generating", "CV Generating", &["cv_generating"]),
+    (
+        "applied",
+        "Applied",
+        &["applied", "submitted_ambiguous", "acknowledged"],
+    ),
+    (
+        "interview",
+        "Interview",
+        &["interview_scheduled", "interviewed"],
+    ),
+    ("offered", "Offered", &["offered"]),
+    ("rejected", "Rejected", &["rejected"]),
+];
+
+static STATUS_PATH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^/api/applications/(\d+)/status$").unwrap());
+
+// Moves read the board, then write it
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Stage {
+    pub id: String,
+    pub label: String,
+    pub statuses: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Card {
+    pub application_id: i64,
+    pub company: String,
+    pub role: Option<String>,
+    pub status: String,
+    pub cv_profile: Option<String>,
+    pub site: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: String,
+    pub authorized_at: Option<String>,
+    // None for a status no stage holds
+    pub stage: Option<String>,
+    #[serde(skip)]
+    position: Option<i64>,
+    pub notes: String,
+    // A status change waiting in the offline queue
+    pub syncing: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Pipeline {
+    pub stages: Vec<Stage>,
+    // In board order within each stage
+    pub cards: Vec<Card>,
+}
+
+fn stages(db: &Connection) -> Result<Vec<Stage>, String> {
+    let count: i64 = db
+        .query_row("SELECT COUNT(*) FROM pipeline_stages", [], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+    if count == 0 {
+        let defaults: Vec<Stage> = DEFAULT_STAGES
+            .iter()
+            .map(|(id, label, statuses)| Stage {
+                id: id.to_string(),
+                label: label.to_string(),
+                statuses: statuses.iter().map(|s| s.to_string()).collect(),
+            })
+            .collect();
+        save_stages(db, &defaults)?;
+        return Ok(defaults);
+    }
+    db.prepare("SELECT id, label, statuses FROM pipeline_stages ORDER BY position")
+        .and_then(|mut s| {
+            s.query_map([], |r| {
+                let statuses: String = r.get(2)?;
+                Ok(Stage {
+                    id: r.get(0)?,
+                    label: r.get(1)?,
+                    statuses: serde_json::from_str(&statuses).unwrap_or_default(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| e.to_string())
+}
+
+// Replaces every stage, in the order given.
+fn save_stages(db: &Connection, stages: &[Stage]) -> Result<(), String> {
+    let tx = db.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM pipeline_stages", [])
+        .map_err(|e| e.to_string())?;
+    for (position, stage) in stages.iter().enumerate() {
+        let statuses = serde_json::to_string(&stage.statuses).map_err(|e| e.to_string())?;
+        tx.execute(
+            "INSERT INTO pipeline_stages (id, label, statuses, position) VALUES (?1, ?2, ?3, ?4)",
+            params![stage.id, stage.label, statuses, position as i64],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+// Status changes still in the offline queue, the latest for each application.
+fn queued_statuses(actions: Vec<offlinequeue::QueuedAction>) -> HashMap<i64, String> {
+    actions
+        .into_iter()
+        .filter(|a| a.method == "PATCH")
+        .filter_map(|a| {
+            let id = STATUS_PATH.captures(&a.path)?[1].parse().ok()?;
+            Some((id, a.body?["status"].as_str()?.to_string()))
+        })
+        .collect()
+}
+
+fn load(app: &AppHandle, db: &Connection) -> Result<Pipeline, String> {
+    board(db, &queued_statuses(offlinequeue::pending(app)))
+}
+
+// The board as the database has it, with the `queued` statuses over it.
+fn board(db: &Connection, queued: &HashMap<i64, String>) -> Result<Pipeline, String> {
+    let stages = stages(db)?;
+    let mut cards = db
+        .prepare(
+            "SELECT a.id, a.company, a.role, a.status, a.cv_profile, a.site, a.location, a.url,
+                    a.created_at, COALESCE(a.updated_at, a.recorded_at), a.authorized_at,
+                    c.position, COALESCE(c.notes, '')
+             FROM applications a LEFT JOIN pipeline_cards c ON c.application_id = a.id",
+        )
+        .and_then(|mut s| {
+            s.query_map([], |r| {
+                Ok(Card {
+                    application_id: r.get(0)?,
+                    company: r.get(1)?,
+                    role: r.get(2)?,
+                    status: r.get(3)?,
+                    cv_profile: r.get(4)?,
+                    site: r.get(5)?,
+                    location: r.get(6)?,
+                    url: r.get(7)?,
+                    created_at: r.get(8)?,
+                    updated_at: r.get(9)?,
+                    authorized_at: r.get(10)?,
+                    stage: None,
+                    position: r.get(11)?,
+                    notes: r.get(12)?,
+                    syncing: false,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| e.to_string())?;
+    for card in &mut cards {
+        if let Some(status) = queued.get(&card.application_id) {
+            card.syncing = true;
+            card.status = status.clone();
+        }
+        card.stage = stages
+            .iter()
+            .find(|s| s.statuses.contains(&card.status))
+            .map(|s| s.id.clone());
+    }
+    // Placed cards by their place, then the rest newest first
+    cards.sort_by(|a, b| {
+        let key = |c: &Card| {
+            (
+                c.position.unwrap_or(i64::MAX),
+                Reverse(c.updated_at.clone()),
+            )
+        };
+        key(a).cmp(&key(b))
+    });
+    Ok(Pipeline { stages, cards })
+}
+
+fn changed(app: &AppHandle) {
+    let _ = app.emit(PIPELINE_CHANGED, ());
+}
+
+// Sends the change, or queues it while the engine is away, and records it.
+fn set_status(app: &AppHandle, db: &Connection, card: &Card, status: &str) -> Result<(), String> {
+    if !MANUAL_STATUSES.contains(&status) {
+        return Err(format!(
+            "applications can't be set to {} by hand",
+            status.replace('_', " ")
+        ));
+    }
+    let id = card.application_id;
+    let path = format!("/api/applications/{id}/status");
+    let body = json!({ "status": status });
+    match backend::send_json("PATCH", &path, Some(&body)) {
+        Ok(_) => {}
+        Err(Failure::Unreachable(_)) => {
+            let label = format!("Mark {} as {}", card.company, status.replace('_', " "));
+            offlinequeue::queue(app, label, "PATCH", path, Some(body))?;
+        }
+        Err(Failure::Refused(_, detail)) => return Err(detail),
+    }
+    history::record_status(db, id, status)?;
+    info!(application = id, status, "status changed from the board");
+    let _ = app.emit(HISTORY_CHANGED, id);
+    Ok(())
+}
+
+fn card(pipeline: &Pipeline, id: i64) -> Result<Card, String> {
+    pipeline
+        .cards
+        .iter()
+        .find(|c| c.application_id == id)
+        .cloned()
+        .ok_or_else(|| format!("no application {id}"))
+}
+
+// Puts the card at `index` among the stage's cards, changing its status to
+// the stage's first when it comes from another.
+fn move_card(app: &AppHandle, id: i64, stage_id: &str, index: usize) -> Result<(), String> {
+    let db = history::open(app)?;
+    let pipeline = load(app, &db)?;
+    let card = card(&pipeline, id)?;
+    let stage = pipeline
+        .stages
+        .iter()
+        .find(|s| s.id == stage_id)
+        .ok_or_else(|| format!("no stage {stage_id}"))?;
+    if card.stage.as_deref() != Some(stage_id) {
+        let status = stage.statuses.first().ok_or("the stage holds no status")?;
+        set_status(app, &db, &card, status)?;
+    }
+    let mut order: Vec<i64> = pipeline
+        .cards
+        .iter()
+        .filter(|c| c.stage.as_deref() == Some(stage_id) && c.application_id != id)
+        .map(|c| c.application_id)
+        .collect();
+    order.insert(index.min(order.len()), id);
+    let now = Utc::now().to_rfc3339();
+    let tx = db.unchecked_transaction().map_err(|e| e.to_string())?;
+    for (position, application_id) in order.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO pipeline_cards (application_id, position, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT (application_id) DO UPDATE SET position = ?2, updated_at = ?3",
+            params![application_id, position as i64, now],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+fn slug(label: &str) -> String {
+    label
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+// A stage's label and statuses, checked against the other stages.
+fn check_stage(stages: &[Stage], id: &str, label: &str, statuses: &[String]) -> Result<(), String> {
+    if label.trim().is_empty() {
+        return Err("a stage needs a name".into());
+    }
+    if statuses.is_empty() {
+        return Err("a stage needs at least one status".into());
+    }
+    for status in statuses {
+        if let Some(other) = stages
+            .iter()
+            .find(|s| s.id != id && s.statuses.contains(status))
+        {
+            return Err(format!(
+                "{} is already in {}",
+                status.replace('_', " "),
+                other.label
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Runs `f` on the board under the lock, then answers with the board.
+fn with_board(
+    app: AppHandle,
+    f: impl FnOnce(&AppHandle, &Connection) -> Result<(), String>,
+) -> Result<Pipeline, String> {
+    let _guard = LOCK.lock().unwrap();
+    let db = history::open(&app)?;
+    f(&app, &db)?;
+    changed(&app);
+    load(&app, &db)
+}
+
+#[tauri::command]
+pub async fn get_pipeline(app: AppHandle) -> Result<Pipeline, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let _guard = LOCK.lock().unwrap();
+        load(&app, &history::open(&app)?)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn move_pipeline_card(
+    app: AppHandle,
+    application_id: i64,
+    stage: String,
+    index: usize,
+) -> Result<Pipeline, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_board(app, move |app, _| {
+            move_card(app, application_id, &stage, index)
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// A status change that leaves the card's place to the board.
+#[tauri::command]
+pub async fn set_pipeline_status(
+    app: AppHandle,
+    application_id: i64,
+    status: String,
+) -> Result<Pipeline, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_board(app, move |app, db| {
+            let card = card(&load(app, db)?, application_id)?;
+            if card.status == status {
+                return Ok(());
+            }
+            set_status(app, db, &card, &status)
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn set_pipeline_notes(
+    app: AppHandle,
+    application_id: i64,
+    notes: String,
+) -> Result<Pipeline, String> {
+    if notes.chars().count() > MAX_NOTES {
+        return Err(format!("notes are limited to {MAX_NOTES} characters"));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        with_board(app, move |_, db| {
+            db.execute(
+                "INSERT INTO pipeline_cards (application_id, notes, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (application_id) DO UPDATE SET notes = ?2, updated_at = ?3",
+                params![application_id, notes.trim(), Utc::now().to_rfc3339()],
+            )
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn create_pipeline_stage(
+    app: AppHandle,
+    label: String,
+    statuses: Vec<String>,
+) -> Result<Pipeline, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_board(app, move |_, db| {
+            let mut all = stages(db)?;
+            let base = slug(&label);
+            let base = if base.is_empty() {
+                "stage".into()
+            } else {
+                base
+            };
+            let mut id = base.clone();
+            let mut n = 2;
+            while all.iter().any(|s| s.id == id) {
+                id = format!("{base}_{n}");
+                n += 1;
+            }
+            check_stage(&all, &id, &label, &statuses)?;
+            all.push(Stage {
+                id,
+                label: label.trim().to_string(),
+                statuses,
+            });
+            save_stages(db, &all)
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn update_pipeline_stage(
+    app: AppHandle,
+    id: String,
+    label: String,
+    statuses: Vec<String>,
+) -> Result<Pipeline, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_board(app, move |_, db| {
+            let mut all = stages(db)?;
+            check_stage(&all, &id, &label, &statuses)?;
+            let stage = all
+                .iter_mut()
+                .find(|s| s.id == id)
+                .ok_or_else(|| format!("no stage {id}"))?;
+            stage.label = label.trim().to_string();
+            stage.statuses = statuses;
+            save_stages(db, &all)
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Its cards stay put and show again once a stage holds their status.
+#[tauri::command]
+pub async fn delete_pipeline_stage(app: AppHandle, id: String) -> Result<Pipeline, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_board(app, move |_, db| {
+            let mut all = stages(db)?;
+            all.retain(|s| s.id != id);
+            save_stages(db, &all)
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// `ids` in their new order; any left out keep theirs, after.
+#[tauri::command]
+pub async fn reorder_pipeline_stages(app: AppHandle, ids: Vec<String>) -> Result<Pipeline, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_board(app, move |_, db| {
+            let mut all = stages(db)?;
+            all.sort_by_key(|s| ids.iter().position(|id| *id == s.id).unwrap_or(usize::MAX));
+            save_stages(db, &all)
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Back to the stages the board started with; places and notes are kept.
+#[tauri::command]
+pub async fn reset_pipeline_stages(app: AppHandle) -> Result<Pipeline, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_board(app, |_, db| {
+            db.execute("DELETE FROM pipeline_stages", [])
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (id, status, updated_at) in [
+            (1, "applied", "2026-10-01"),
+            (2, "applied", "2026-10-03"),
+            (3, "interviewed", "2026-10-02"),
+            (4, "expired", "2026-10-02"),
+        ] {
+            db.execute(
+                "INSERT INTO applications (id, company, status, updated_at, recorded_at)
+                 VALUES (?1, 'Acme', ?2, ?3, '')",
+                params![id, status, updated_at],
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    fn stage(id: &str, statuses: &[&str]) -> Stage {
+        Stage {
+            id: id.into(),
+            label: id.to_uppercase(),
+            statuses: statuses.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn ids(pipeline: &Pipeline) -> Vec<i64> {
+        pipeline.cards.iter().map(|c| c.application_id).collect()
+    }
+
+    #[test]
+    fn stage_ids_and_checks() {
+        assert_eq!(slug("  Phone screen / HR "), "phone_screen_hr");
+        let stages = [stage("applied", &["applied"]), stage("talks", &["offered"])];
+        let statuses = |s: &[&str]| s.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(check_stage(&stages, "talks", "Talks", &statuses(&["offered"])).is_ok());
+        assert_eq!(
+            check_stage(&stages, "new", "New", &statuses(&["offered"])),
+            Err("offered is already in TALKS".into())
+        );
+        assert!(check_stage(&stages, "new", " ", &statuses(&["withdrawn"])).is_err());
+        assert!(check_stage(&stages, "new", "New", &[]).is_err());
+    }
+
+    #[test]
+    fn default_stages_until_changed() {
+        let db = db();
+        let ids: Vec<String> = stages(&db).unwrap().into_iter().map(|s| s.id).collect();
+        assert_eq!(ids.len(), DEFAULT_STAGES.len());
+        assert_eq!(ids[0], "pending_human_review");
+        save_stages(&db, &[stage("b", &["offered"]), stage("a", &["applied"])]).unwrap();
+        let saved = stages(&db).unwrap();
+        assert_eq!(saved[0].id, "b");
+        assert_eq!(saved[1].statuses, ["applied"]);
+    }
+
+    #[test]
+    fn latest_queued_status_per_application() {
+        let action = |method: &str, path: &str, status: &str| offlinequeue::QueuedAction {
+            id: String::new(),
+            label: String::new(),
+            method: method.into(),
+            path: path.into(),
+            body: Some(json!({ "status": status })),
+            queued_at: Utc::now(),
+            attempts: 0,
+            last_error: None,
+        };
+        let queued = queued_statuses(vec![
+            action("PATCH", "/api/applications/1/status", "interviewed"),
+            action("PATCH", "/api/applications/1/status", "offered"),
+            action("POST", "/api/applications/2/status", "rejected"),
+            action("PATCH", "/api/applications/3", "rejected"),
+        ]);
+        assert_eq!(queued, HashMap::from([(1, "offered".to_string())]));
+    }
+
+    #[test]
+    fn cards_in_board_order() {
+        let db = db();
+        let pipeline = board(&db, &HashMap::new()).unwrap();
+        // Newest first until placed
+        assert_eq!(ids(&pipeline), [2, 3, 4, 1]);
+        let stage_of = |p: &Pipeline, id: i64| card(p, id).unwrap().stage;
+        assert_eq!(stage_of(&pipeline, 3).as_deref(), Some("interview"));
+        assert_eq!(stage_of(&pipeline, 4), None);
+        assert!(card(&pipeline, 9).is_err());
+
+        db.execute(
+            "INSERT INTO pipeline_cards (application_id, position, updated_at)
+             VALUES (1, 0, ''), (2, 1, '')",
+            [],
+        )
+        .unwrap();
+        let queued = HashMap::from([(3, "offered".to_string())]);
+        let pipeline = board(&db, &queued).unwrap();
+        assert_eq!(ids(&pipeline), [1, 2, 3, 4]);
+        let moved = card(&pipeline, 3).unwrap();
+        assert!(moved.syncing);
+        assert_eq!(
+            (moved.status.as_str(), moved.stage.as_deref()),
+            ("offered", Some("offered"))
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf, sync::RwLock};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    paths, proxy, settings,
+    telemetry::{self, Counter},
+};
+
+// Profiles keep separate job searches apart (e.g. two people on one machine):
+// each has its own settings, and the engine gets its own data dir, so the
+// database and CV library are separate too. The default profile uses the
+// plain config/data dirs, which keeps existing installs where they are;
+// others live under <dir>/profiles/<id>/.
+pub const DEFAULT_PROFILE: &str = "default";
+pub const PROFILE_CHANGED: &str = "profile-changed";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Registry {
+    active: String,
+    profiles: Vec<Profile>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE.into(),
+            profiles: vec![Profile {
+                id: DEFAULT_PROFILE.into(),
+                name: "Default".into(),
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProfileList {
+    pub active: String,
+    pub profiles: Vec<Profile>,
+}
+
+static ACTIVE: RwLock<String> = RwLock::new(String::new());
+
+pub fn active() -> String {
+    let id = ACTIVE.read().unwrap();
+    if id.is_empty() {
+        DEFAULT_PROFILE.into()
+    } else {
+        id.clone()
+    }
+}
+
+pub fn dir_for(base: PathBuf, id: &str) -> PathBuf {
+    if id == DEFAULT_PROFILE {
+        base
+    } else {
+        base.join("profiles").join(id)
+    }
+}
+
+pub fn scoped(base: PathBuf) -> PathBuf {
+    dir_for(base, &active())
+}
+
+fn registry_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::base_config_dir(app)?.join("profiles.json"))
+}
+
+fn load_registry(app: &AppHandle) -> Registry {
+    let mut registry: Registry = registry_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    if !registry.profiles.iter().any(|p| p.id == DEFAULT_PROFILE) {
+        registry
+            .profiles
+            .insert(0, Registry::default().profiles.remove(0));
+    }
+    if !registry.profiles.iter().any(|p| p.id == registry.active) {
+        registry.active = DEFAULT_PROFILE.into();
+    }
+    registry
+}
+
+fn save_registry(app: &AppHandle, registry: &Registry) -> Result<(), String> {
+    let path = registry_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(registry).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+// Must run before anything resolves config or data paths.
+pub fn init(app: &AppHandle) {
+    *ACTIVE.write().unwrap() = load_registry(app).active;
+}
+
+pub fn list(app: &AppHandle) -> ProfileList {
+    let registry = load_registry(app);
+    ProfileList {
+        active: registry.active,
+        profiles: registry.profiles,
+    }
+}
+
+fn slug(name: &str) -> String {
+    let mut id = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c);
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    id.trim_end_matches('-').to_string()
+}
+
+// Points every path at the new profile, then brings up its settings and
+// its own backend instance.
+pub fn switch(app: &AppHandle, id: &str) -> Result<(), String> {
+    let mut registry = load_registry(app);
+    let profile = registry
+        .profiles
+        .iter()
+        .find(|p| p.id == id)
+        .cloned()
+        .ok_or_else(|| format!("no profile {id}"))?;
+    if registry.active == id {
+        return Ok(());
+    }
+    registry.active = id.to_string();
+    save_registry(app, &registry)?;
+    *ACTIVE.write().unwrap() = id.to_string();
+
+    settings::reload(app);
+    proxy::refresh(app);
+    crate::restart_backend(app)?;
+    crate::refresh_tray_menu(app);
+    let _ = app.emit(PROFILE_CHANGED, profile);
+    // Everything on screen belonged to the previous profile
+    if let Some(w) = app.get_webview_window("main") {
+        let _ = w.reload();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_profiles(app: AppHandle) -> ProfileList {
+    list(&app)
+}
+
+#[tauri::command]
+pub fn create_profile(app: AppHandle, name: String) -> Result<Profile, String> {
+    let name = name.trim().to_string();
+    let base = slug(&name);
+    if base.is_empty() {
+        return Err("profile name needs at least one letter or digit".into());
+    }
+    let mut registry = load_registry(&app);
+    let mut id = base.clone();
+    let mut n = 2;
+    while registry.profiles.iter().any(|p| p.id == id) {
+        id = format!("{base}-{n}");
+        n += 1;
+    }
+    let profile = Profile { id, name };
+    registry.profiles.push(profile.clone());
+    save_registry(&app, &registry)?;
+    crate::refresh_tray_menu(&app);
+    Ok(profile)
+}
+
+#[tauri::command]
+pub fn switch_profile(app: AppHandle, id: String) -> Result<(), String> {
+    telemetry::record(&app, Counter::ProfileSwitch);
+    switch(&app, &id)
+}
+
+// Removes the profile and everything stored for it. The default profile and
+// the one in use can't be deleted.
+#[tauri::command]
+pub fn delete_profile(app: AppHandle, id: String) -> Result<(), String> {
+    if id == DEFAULT_PROFILE {
+        return Err("the default profile can't be deleted".into());
+    }
+    if id == active() {
+        return Err("switch to another profile before deleting this one".into());
+    }
+    let mut registry = load_registry(&app);
+    let before = registry.profiles.len();
+    registry.profiles.retain(|p| p.id != id);
+    if registry.profiles.len() == before {
+        return Err(format!("no profile {id}"));
+    }
+    save_registry(&app, &registry)?;
+    for base in [paths::base_config_dir(&app)?, paths::base_data_dir(&app)?] {
+        let dir = dir_for(base, &id);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+        }
+    }
+    crate::refresh_tray_menu(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_from_names() {
+        assert_eq!(slug("Work"), "work");
+        assert_eq!(slug("  Search 2026 — Madrid! "), "search-2026-madrid");
+        assert_eq!(slug("***"), "");
+    }
+
+    #[test]
+    fn default_profile_at_the_root() {
+        let base = PathBuf::from("/data");
+        assert_eq!(dir_for(base.clone(), DEFAULT_PROFILE), base);
+        assert_eq!(dir_for(base, "work"), PathBuf::from("/data/profiles/work"));
+    }
+}
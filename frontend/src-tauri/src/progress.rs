@@ -0,0 +1,325 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::{collections::HashMap, sync::Mutex};
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    i18n::{t, t_args},
+    keepawake, notifications, taskbar,
+};
+
+// Backend contract for long runs:
+//   run_progress  { "run_id": "…", "label": "Applying", "current": 4, "total": 10 }
+//   run_finished  { "run_id": "…", "label": "Applying", "succeeded": 9, "total": 10 }
+// One progress notification per run replaces a popup per item.
+#[derive(Debug, Deserialize)]
+struct RunProgress {
+    run_id: String,
+    #[serde(default = "default_label")]
+    label: String,
+    current: u32,
+    total: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct RunFinished {
+    run_id: String,
+    #[serde(default = "default_label")]
+    label: String,
+    succeeded: u32,
+    total: u32,
+}
+
+fn default_label() -> String {
+    t("progress-default-label")
+}
+
+// Last quarter (0–4) announced per run, used where notifications are only
+// updated at each step (macOS, Linux).
+#[derive(Default)]
+pub struct ProgressState(Mutex<HashMap<String, u32>>);
+
+fn quarter(current: u32, total: u32) -> u32 {
+    (current.min(total) * 4).checked_div(total).unwrap_or(0)
+}
+
+pub fn on_progress(app: &AppHandle, data: &Value) {
+    let Ok(p) = serde_json::from_value::<RunProgress>(data.clone()) else {
+        return;
+    };
+    let state = app.state::<ProgressState>();
+    let mut runs = state.0.lock().unwrap();
+    let first = !runs.contains_key(&p.run_id);
+    let q = quarter(p.current, p.total);
+    let announce = first || runs.get(&p.run_id).is_some_and(|last| q > *last);
+    runs.insert(p.run_id.clone(), q);
+    drop(runs);
+    keepawake::on_progress(app, &p.run_id);
+    taskbar::on_progress(app, &p.run_id, p.current, p.total);
+    platform::show_progress(app, &p, first, announce);
+}
+
+pub fn on_finished(app: &AppHandle, data: &Value) {
+    let Ok(f) = serde_json::from_value::<RunFinished>(data.clone()) else {
+        return;
+    };
+    app.state::<ProgressState>()
+        .0
+        .lock()
+        .unwrap()
+        .remove(&f.run_id);
+    keepawake::on_finished(app, &f.run_id);
+    taskbar::on_finished(app, &f.run_id, f.succeeded < f.total);
+    platform::show_finished(app, &f);
+}
+
+fn status_line(label: &str, current: u32, total: u32) -> String {
+    t_args(
+        "progress-status",
+        &[
+            ("label", label.into()),
+            ("current", current.into()),
+            ("total", total.into()),
+        ],
+    )
+}
+
+fn finished_line(f: &RunFinished) -> String {
+    let failed = f.total - f.succeeded.min(f.total);
+    let id = if failed == 0 {
+        "progress-finished"
+    } else {
+        "progress-finished-with-failures"
+    };
+    t_args(
+        id,
+        &[
+            ("label", f.label.as_str().into()),
+            ("succeeded", f.succeeded.into()),
+            ("total", f.total.into()),
+            ("failed", failed.into()),
+        ],
+    )
+}
+
+fn notify(app: &AppHandle, body: &str) {
+    notifications::notify(app, "JobBot", body);
+}
+
+// Windows toasts carry a real progress bar that is updated in place by tag.
+#[cfg(windows)]
+mod platform {
+    use super::*;
+    use notifications::NotificationPermission;
+    use tauri_winrt_notification::{Progress, Toast};
+    use tracing::warn;
+
+    fn toast_progress(tag: &str, label: &str, current: u32, total: u32, status: &str) -> Progress {
+        Progress {
+            tag: tag.to_string(),
+            title: label.to_string(),
+            status: status.to_string(),
+            value: if total == 0 {
+                0.0
+            } else {
+                current as f32 / total as f32
+            },
+            value_string: format!("{current}/{total}"),
+        }
+    }
+
+    pub fn show_progress(app: &AppHandle, p: &RunProgress, first: bool, announce: bool) {
+        if notifications::permission(app) == NotificationPermission::Denied {
+            if announce {
+                notify(app, &status_line(&p.label, p.current, p.total));
+            }
+            return;
+        }
+        let app_id = &app.config().identifier;
+        let progress = toast_progress(
+            &p.run_id,
+            &p.label,
+            p.current,
+            p.total,
+            &status_line(&p.label, p.current, p.total),
+        );
+        let toast = Toast::new(app_id).title("JobBot");
+        let result = if first {
+            toast.progress(&progress).show()
+        } else {
+            toast.set_progress(&progress).map(|_| ())
+        };
+        if let Err(e) = result {
+            warn!("progress toast failed: {e}");
+        }
+    }
+
+    pub fn show_finished(app: &AppHandle, f: &RunFinished) {
+        let app_id = &app.config().identifier;
+        let progress = toast_progress(&f.run_id, &f.label, f.total, f.total, &finished_line(f));
+        if notifications::permission(app) == NotificationPermission::Denied
+            || Toast::new(app_id).set_progress(&progress).is_err()
+        {
+            notify(app, &finished_line(f));
+        }
+    }
+}
+
+// macOS replaces a notification posted again under the same identifier, the
+// run id here. A new banner still shows each time, so the run is updated at
+// start and at each 25% step rather than per item.
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+    use notifications::NotificationPermission;
+    use objc2_foundation::NSString;
+    use objc2_user_notifications::{
+        UNMutableNotificationContent, UNNotificationRequest, UNUserNotificationCenter,
+    };
+
+    fn post(app: &AppHandle, run_id: &str, body: &str) {
+        // Also when the app isn't a bundled .app, where the center throws
+        if notifications::permission(app) != NotificationPermission::Granted {
+            notify(app, body);
+            return;
+        }
+        let content = UNMutableNotificationContent::new();
+        content.setTitle(&NSString::from_str("JobBot"));
+        content.setBody(&NSString::from_str(body));
+        let request = UNNotificationRequest::requestWithIdentifier_content_trigger(
+            &NSString::from_str(run_id),
+            &content,
+            None,
+        );
+        UNUserNotificationCenter::currentNotificationCenter()
+            .addNotificationRequest_withCompletionHandler(&request, None);
+    }
+
+    pub fn show_progress(app: &AppHandle, p: &RunProgress, _first: bool, announce: bool) {
+        if announce {
+            post(app, &p.run_id, &status_line(&p.label, p.current, p.total));
+        }
+    }
+
+    pub fn show_finished(app: &AppHandle, f: &RunFinished) {
+        post(app, &f.run_id, &finished_line(f));
+    }
+}
+
+// The notification server replaces a notification sent with the id it gave
+// an earlier one, and servers that draw progress bars take the "value" hint.
+// As on macOS, updated at start and at each 25% step.
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::*;
+    use notifications::NotificationPermission;
+    use notify_rust::{Hint, Notification};
+    use tracing::warn;
+
+    // Server notification id per run
+    static IDS: Mutex<Option<HashMap<String, u32>>> = Mutex::new(None);
+
+    fn post(app: &AppHandle, run_id: &str, body: &str, percent: Option<i32>, done: bool) {
+        if notifications::permission(app) == NotificationPermission::Denied {
+            notify(app, body);
+            return;
+        }
+        let previous = {
+            let mut ids = IDS.lock().unwrap();
+            let ids = ids.get_or_insert_with(HashMap::new);
+            if done {
+                ids.remove(run_id)
+            } else {
+                ids.get(run_id).copied()
+            }
+        };
+        let mut notification = Notification::new();
+        notification.appname("JobBot").summary("JobBot").body(body);
+        if let Some(id) = previous {
+            notification.id(id);
+        }
+        if let Some(percent) = percent {
+            notification.hint(Hint::CustomInt("value".into(), percent));
+        }
+        match notification.show() {
+            Ok(handle) if !done => {
+                IDS.lock()
+                    .unwrap()
+                    .get_or_insert_with(HashMap::new)
+                    .insert(run_id.to_string(), handle.id());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!("progress notification failed: {e}");
+                notify(app, body);
+            }
+        }
+    }
+
+    pub fn show_progress(app: &AppHandle, p: &RunProgress, _first: bool, announce: bool) {
+        if announce {
+            let percent = (p.current.min(p.total) * 100)
+                .checked_div(p.total)
+                .unwrap_or(0);
+            post(
+                app,
+                &p.run_id,
+                &status_line(&p.label, p.current, p.total),
+                Some(percent as i32),
+                false,
+            );
+        }
+    }
+
+    pub fn show_finished(app: &AppHandle, f: &RunFinished) {
+        post(app, &f.run_id, &finished_line(f), None, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn quarters() {
+        assert_eq!(quarter(0, 10), 0);
+        assert_eq!(quarter(2, 10), 0);
+        assert_eq!(quarter(3, 10), 1);
+        assert_eq!(quarter(5, 10), 2);
+        assert_eq!(quarter(10, 10), 4);
+        // Past the end, or nothing to do
+        assert_eq!(quarter(12, 10), 4);
+        assert_eq!(quarter(0, 0), 0);
+    }
+
+    #[test]
+    fn backend_contract() {
+        let p: RunProgress = serde_json::from_value(json!({
+            "run_id": "r1", "label": "Applying", "current": 4, "total": 10
+        }))
+        .unwrap();
+        assert_eq!((p.run_id.as_str(), p.current, p.total), ("r1", 4, 10));
+        let f: RunFinished = serde_json::from_value(json!({
+            "run_id": "r1", "succeeded": 9, "total": 10
+        }))
+        .unwrap();
+        assert_eq!(f.label, default_label());
+        assert!(serde_json::from_value::<RunProgress>(json!({ "run_id": "r1" })).is_err());
+    }
+
+    #[test]
+    fn failures_in_the_finished_line() {
+        let line = |succeeded, total| {
+            finished_line(&RunFinished {
+                run_id: "r1".into(),
+                label: "Applying".into(),
+                succeeded,
+                total,
+            })
+        };
+        assert_ne!(line(9, 10), line(10, 10));
+        // More succeeded than were run is read as none failed
+        assert_eq!(line(12, 10).replace("12", "10"), line(10, 10));
+    }
+}
@@ -0,0 +1,712 @@
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    cmp::Ordering,
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Emitter, Url};
+use tracing::{info, warn};
+
+use crate::{
+    audit::{self, Action},
+    backend,
+    events::BackendEvent,
+    paths,
+};
+
+// Proxies the engine scrapes job sites through, separate from the proxy the
+// app itself uses (proxy.rs). Each site the engine opens is assigned one of
+// the user's proxies, kept for ROTATE_AFTER_HOURS and then moved to the best other
+// one. The pool health-checks every proxy on a timer and counts what the
+// engine reports per page load, so a proxy that keeps getting banned or
+// shown captchas is rested and its sites move elsewhere. The engine asks
+// for a site's proxy over the event stream and gets it back through
+// /api/proxy-pool/{site} (backend/scrapers/proxy_pool.py). Addresses live
+// in `proxy-pool.json` in the profile's config folder; passwords in the OS
+// keychain.
+pub const POOL_ENV: &str = "JOBBOT_PROXY_POOL";
+pub const PROXY_POOL_CHANGED: &str = "proxy-pool-changed";
+const KEYCHAIN_SERVICE: &str = "com.currobot.app.proxypool";
+const FILE: &str = "proxy-pool.json";
+// Answers 204 and nothing else, so any other reply means the proxy meddles
+const CHECK_URL: &str = "https://www.gstatic.com/generate_204";
+const CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const SCHEMES: &[&str] = &["http", "https", "socks5"];
+// Bans or captchas in a row before a proxy is rested
+const STRIKES: u32 = 3;
+const REST_MINUTES: i64 = 60;
+const ROTATE_AFTER_HOURS: i64 = 6;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PoolProxy {
+    pub id: String,
+    pub label: String,
+    pub scheme: String,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub enabled: bool,
+    pub added_at: DateTime<Utc>,
+    // Last health check
+    pub healthy: Option<bool>,
+    pub latency_ms: Option<u64>,
+    pub checked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    // Page loads the engine made through it, and how many hit a wall
+    #[serde(default)]
+    pub requests: u64,
+    #[serde(default)]
+    pub bans: u64,
+    #[serde(default)]
+    pub captchas: u64,
+    #[serde(default)]
+    strikes: u32,
+    pub rested_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Assignment {
+    pub site: String,
+    pub proxy_id: String,
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Pool {
+    proxies: Vec<PoolProxy>,
+    assignments: Vec<Assignment>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewPoolProxy {
+    #[serde(default)]
+    pub label: String,
+    pub scheme: String,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PoolProxyStatus {
+    #[serde(flatten)]
+    pub proxy: PoolProxy,
+    pub has_password: bool,
+    // Of the page loads through it, 0 to 1
+    pub ban_rate: f64,
+    pub captcha_rate: f64,
+    pub sites: Vec<String>,
+}
+
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::config_dir(app)?.join(FILE))
+}
+
+fn load(app: &AppHandle) -> Pool {
+    path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, pool: &Pool) -> Result<(), String> {
+    let path = path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(pool).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn change<T>(app: &AppHandle, f: impl FnOnce(&mut Pool) -> Result<T, String>) -> Result<T, String> {
+    let _guard = FILE_LOCK.lock().unwrap();
+    let mut pool = load(app);
+    let result = f(&mut pool)?;
+    save(app, &pool)?;
+    let _ = app.emit(PROXY_POOL_CHANGED, ());
+    Ok(result)
+}
+
+fn keychain_entry(id: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, id).map_err(|e| e.to_string())
+}
+
+fn password(id: &str) -> Option<String> {
+    keychain_entry(id).ok()?.get_password().ok()
+}
+
+fn rate(count: u64, of: u64) -> f64 {
+    if of == 0 {
+        0.0
+    } else {
+        count as f64 / of as f64
+    }
+}
+
+fn usable(proxy: &PoolProxy, now: DateTime<Utc>) -> bool {
+    proxy.enabled
+        && proxy.healthy != Some(false)
+        && proxy.rested_until.is_none_or(|until| until <= now)
+}
+
+// The usable proxy with the fewest sites, then the fewest walls hit, then
+// the fastest; `avoid` only when it's the only one left.
+fn pick(pool: &Pool, avoid: Option<&str>) -> Option<String> {
+    let now = Utc::now();
+    let load = |id: &str| pool.assignments.iter().filter(|a| a.proxy_id == id).count();
+    let mut candidates: Vec<&PoolProxy> = pool.proxies.iter().filter(|p| usable(p, now)).collect();
+    candidates.sort_by(|a, b| {
+        (Some(a.id.as_str()) == avoid)
+            .cmp(&(Some(b.id.as_str()) == avoid))
+            .then(load(&a.id).cmp(&load(&b.id)))
+            .then(
+                rate(a.bans + a.captchas, a.requests)
+                    .partial_cmp(&rate(b.bans + b.captchas, b.requests))
+                    .unwrap_or(Ordering::Equal),
+            )
+            .then(
+                a.latency_ms
+                    .unwrap_or(u64::MAX)
+                    .cmp(&b.latency_ms.unwrap_or(u64::MAX)),
+            )
+    });
+    candidates.first().map(|p| p.id.clone())
+}
+
+fn server(proxy: &PoolProxy) -> String {
+    format!("{}://{}:{}", proxy.scheme, proxy.host, proxy.port)
+}
+
+// What the engine hands Playwright, or null to go direct.
+fn engine_proxy(app: &AppHandle, pool: &Pool, site: &str) -> Value {
+    let Some(proxy) = pool
+        .assignments
+        .iter()
+        .find(|a| a.site == site)
+        .and_then(|a| pool.proxies.iter().find(|p| p.id == a.proxy_id))
+    else {
+        return Value::Null;
+    };
+    let password = proxy.username.as_ref().and_then(|_| password(&proxy.id));
+    if password.is_some() {
+        let detail = json!({ "service": KEYCHAIN_SERVICE, "account": proxy.id, "for": site });
+        audit::record(app, Action::CredentialUsed, detail);
+    }
+    json!({
+        "id": proxy.id,
+        "server": server(proxy),
+        "username": proxy.username,
+        "password": password,
+    })
+}
+
+fn push(app: &AppHandle, site: &str) -> Result<(), String> {
+    let proxy = engine_proxy(app, &load(app), site);
+    backend::post_json(
+        &format!("/api/proxy-pool/{site}"),
+        &json!({ "proxy": proxy }),
+    )
+    .map(|_| ())
+}
+
+// Moves sites off proxies that can't be used now, and off ones they've had
+// for ROTATE_AFTER_HOURS when there's another. Returns the sites that moved.
+fn rotate(pool: &mut Pool, force: Option<&str>) -> Vec<String> {
+    let now = Utc::now();
+    let mut moved = Vec::new();
+    for i in 0..pool.assignments.len() {
+        let assignment = &pool.assignments[i];
+        let current = pool.proxies.iter().find(|p| p.id == assignment.proxy_id);
+        let stale = current.is_none_or(|p| !usable(p, now));
+        let due = force.is_some_and(|s| s == assignment.site)
+            || now - assignment.since > chrono::Duration::hours(ROTATE_AFTER_HOURS);
+        if !stale && !due {
+            continue;
+        }
+        let avoid = assignment.proxy_id.clone();
+        match pick(pool, Some(&avoid)) {
+            Some(next) if next != avoid => {
+                let assignment = &mut pool.assignments[i];
+                info!(
+                    site = assignment.site,
+                    from = avoid,
+                    to = next,
+                    "proxy rotated"
+                );
+                assignment.proxy_id = next;
+                assignment.since = now;
+                moved.push(assignment.site.clone());
+            }
+            // Kept if it's still fine; otherwise the site goes direct
+            _ if !stale => pool.assignments[i].since = now,
+            _ => moved.push(pool.assignments[i].site.clone()),
+        }
+    }
+    let gone: Vec<String> = pool
+        .assignments
+        .iter()
+        .filter(|a| {
+            pool.proxies
+                .iter()
+                .find(|p| p.id == a.proxy_id)
+                .is_none_or(|p| !usable(p, now))
+        })
+        .map(|a| a.site.clone())
+        .collect();
+    pool.assignments.retain(|a| !gone.contains(&a.site));
+    moved
+}
+
+fn push_all(app: &AppHandle, sites: &[String]) {
+    for site in sites {
+        if let Err(e) = push(app, site) {
+            warn!("could not hand {site} its proxy: {e}");
+        }
+    }
+}
+
+fn check_one(proxy: &PoolProxy) -> Result<u64, String> {
+    let mut url = Url::parse(&server(proxy)).map_err(|e| e.to_string())?;
+    if let Some(user) = &proxy.username {
+        let _ = url.set_username(user);
+        let _ = url.set_password(password(&proxy.id).as_deref());
+    }
+    let client = reqwest::blocking::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .proxy(reqwest::Proxy::all(url.as_str()).map_err(|e| e.to_string())?)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let started = Instant::now();
+    let status = client
+        .get(CHECK_URL)
+        .send()
+        .map_err(|e| e.to_string())?
+        .status();
+    if status.as_u16() != 204 {
+        return Err(format!("answered {status}"));
+    }
+    Ok(started.elapsed().as_millis() as u64)
+}
+
+// Checks every enabled proxy, then rotates whatever that made unusable.
+fn check_all(app: &AppHandle) -> Result<(), String> {
+    let proxies: Vec<PoolProxy> = load(app)
+        .proxies
+        .into_iter()
+        .filter(|p| p.enabled)
+        .collect();
+    let results: Vec<(String, Result<u64, String>)> = proxies
+        .iter()
+        .map(|p| (p.id.clone(), check_one(p)))
+        .collect();
+    let moved = change(app, |pool| {
+        for (id, result) in results {
+            let Some(proxy) = pool.proxies.iter_mut().find(|p| p.id == id) else {
+                continue;
+            };
+            proxy.checked_at = Some(Utc::now());
+            match result {
+                Ok(ms) => {
+                    proxy.healthy = Some(true);
+                    proxy.latency_ms = Some(ms);
+                    proxy.last_error = None;
+                }
+                Err(e) => {
+                    warn!(proxy = proxy.id, "proxy check failed: {e}");
+                    proxy.healthy = Some(false);
+                    proxy.latency_ms = None;
+                    proxy.last_error = Some(e);
+                }
+            }
+        }
+        Ok(rotate(pool, None))
+    })?;
+    push_all(app, &moved);
+    Ok(())
+}
+
+fn answer(app: &AppHandle, site: &str) -> Result<(), String> {
+    change(app, |pool| {
+        let now = Utc::now();
+        let assigned = pool.assignments.iter().any(|a| {
+            a.site == site
+                && pool
+                    .proxies
+                    .iter()
+                    .any(|p| p.id == a.proxy_id && usable(p, now))
+        });
+        if !assigned {
+            pool.assignments.retain(|a| a.site != site);
+            if let Some(proxy_id) = pick(pool, None) {
+                pool.assignments.push(Assignment {
+                    site: site.to_string(),
+                    proxy_id,
+                    since: now,
+                });
+            }
+        }
+        Ok(())
+    })?;
+    push(app, site)
+}
+
+fn record(app: &AppHandle, proxy_id: &str, outcome: &str) -> Result<(), String> {
+    let moved = change(app, |pool| {
+        let Some(proxy) = pool.proxies.iter_mut().find(|p| p.id == proxy_id) else {
+            return Ok(Vec::new());
+        };
+        proxy.requests += 1;
+        match outcome {
+            "ban" => proxy.bans += 1,
+            "captcha" => proxy.captchas += 1,
+            _ => {
+                proxy.strikes = 0;
+                return Ok(Vec::new());
+            }
+        }
+        proxy.strikes += 1;
+        if proxy.strikes < STRIKES {
+            return Ok(Vec::new());
+        }
+        proxy.strikes = 0;
+        proxy.rested_until = Some(Utc::now() + chrono::Duration::minutes(REST_MINUTES));
+        info!(
+            proxy = proxy.id,
+            "proxy rested after repeated bans or captchas"
+        );
+        Ok(rotate(pool, None))
+    })?;
+    push_all(app, &moved);
+    Ok(())
+}
+
+// Called for every engine event; answers off the event stream's thread.
+pub fn on_event(app: &AppHandle, event: &BackendEvent) {
+    let Some(site) = event.data["site"]
+        .as_str()
+        .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .map(str::to_string)
+    else {
+        return;
+    };
+    let (app, name, data) = (app.clone(), event.event.clone(), event.data.clone());
+    thread::spawn(move || {
+        let result = match (
+            name.as_str(),
+            data["proxy_id"].as_str(),
+            data["outcome"].as_str(),
+        ) {
+            ("proxy_requested", _, _) => answer(&app, &site),
+            ("proxy_outcome", Some(id), Some(outcome)) => record(&app, id, outcome),
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            warn!("{name} for {site} failed: {e}");
+        }
+    });
+}
+
+pub fn backend_env() -> (&'static str, &'static str) {
+    (POOL_ENV, "1")
+}
+
+// Reset → saved passwords.
+pub fn forget_passwords(app: &AppHandle) {
+    for proxy in load(app).proxies {
+        if let Ok(entry) = keychain_entry(&proxy.id) {
+            let _ = entry.delete_credential();
+        }
+    }
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        if let Err(e) = check_all(&app) {
+            warn!("proxy pool check failed: {e}");
+        }
+        thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn list_proxy_pool(app: AppHandle) -> Vec<PoolProxyStatus> {
+    let pool = load(&app);
+    pool.proxies
+        .iter()
+        .map(|p| PoolProxyStatus {
+            has_password: password(&p.id).is_some(),
+            ban_rate: rate(p.bans, p.requests),
+            captcha_rate: rate(p.captchas, p.requests),
+            sites: pool
+                .assignments
+                .iter()
+                .filter(|a| a.proxy_id == p.id)
+                .map(|a| a.site.clone())
+                .collect(),
+            proxy: p.clone(),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn add_pool_proxy(app: AppHandle, proxy: NewPoolProxy) -> Result<PoolProxy, String> {
+    let scheme = proxy.scheme.trim().to_lowercase();
+    if !SCHEMES.contains(&scheme.as_str()) {
+        return Err(format!("a proxy is one of {}", SCHEMES.join(", ")));
+    }
+    let host = proxy.host.trim().to_string();
+    if host.is_empty() || proxy.port == 0 {
+        return Err("a proxy needs a host and port".into());
+    }
+    let username = proxy
+        .username
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty());
+    // Chromium has no way to sign in to a SOCKS proxy
+    if scheme == "socks5" && username.is_some() {
+        return Err("SOCKS proxies with a username can't be used by the browser".into());
+    }
+    Url::parse(&format!("{scheme}://{host}:{}", proxy.port))
+        .map_err(|e| format!("invalid proxy address: {e}"))?;
+    let entry = PoolProxy {
+        id: format!("proxy-{}", Local::now().format("%Y%m%d-%H%M%S%3f")),
+        label: match proxy.label.trim() {
+            "" => format!("{host}:{}", proxy.port),
+            label => label.to_string(),
+        },
+        scheme,
+        host,
+        port: proxy.port,
+        username,
+        enabled: true,
+        added_at: Utc::now(),
+        healthy: None,
+        latency_ms: None,
+        checked_at: None,
+        last_error: None,
+        requests: 0,
+        bans: 0,
+        captchas: 0,
+        strikes: 0,
+        rested_until: None,
+    };
+    if let (Some(_), Some(pass)) = (&entry.username, proxy.password.filter(|p| !p.is_empty())) {
+        keychain_entry(&entry.id)?
+            .set_password(&pass)
+            .map_err(|e| e.to_string())?;
+    }
+    change(&app, |pool| {
+        pool.proxies.push(entry.clone());
+        Ok(())
+    })?;
+    info!(id = entry.id, "proxy added to the pool");
+    Ok(entry)
+}
+
+// Disabling moves its sites to other proxies straight away.
+#[tauri::command]
+pub async fn set_pool_proxy_enabled(
+    app: AppHandle,
+    id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let moved = change(&app, |pool| {
+            let proxy = pool
+                .proxies
+                .iter_mut()
+                .find(|p| p.id == id)
+                .ok_or_else(|| format!("no proxy {id}"))?;
+            proxy.enabled = enabled;
+            Ok(rotate(pool, None))
+        })?;
+        push_all(&app, &moved);
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn remove_pool_proxy(app: AppHandle, id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let moved = change(&app, |pool| {
+            pool.proxies.retain(|p| p.id != id);
+            Ok(rotate(pool, None))
+        })?;
+        if let Ok(entry) = keychain_entry(&id) {
+            let _ = entry.delete_credential();
+        }
+        push_all(&app, &moved);
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn check_proxy_pool(app: AppHandle) -> Result<Vec<PoolProxyStatus>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        check_all(&app)?;
+        Ok(list_proxy_pool(app))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Moves `site` to another proxy now, or every site when None.
+#[tauri::command]
+pub async fn rotate_proxy_pool(app: AppHandle, site: Option<String>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let moved = change(&app, |pool| {
+            let sites: Vec<String> = match site {
+                Some(site) => vec![site],
+                None => pool.assignments.iter().map(|a| a.site.clone()).collect(),
+            };
+            let mut moved = Vec::new();
+            for site in sites {
+                moved.extend(rotate(pool, Some(&site)));
+            }
+            moved.sort();
+            moved.dedup();
+            Ok(moved)
+        })?;
+        push_all(&app, &moved);
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxy(id: &str) -> PoolProxy {
+        PoolProxy {
+            id: id.into(),
+            label: String::new(),
+            scheme: "http".into(),
+            host: format!("{id}.example"),
+            port: 8080,
+            username: None,
+            enabled: true,
+            added_at: Utc::now(),
+            healthy: Some(true),
+            latency_ms: Some(100),
+            checked_at: None,
+            last_error: None,
+            requests: 0,
+            bans: 0,
+            captchas: 0,
+            strikes: 0,
+            rested_until: None,
+        }
+    }
+
+    fn assigned(site: &str, proxy_id: &str, hours_ago: i64) -> Assignment {
+        Assignment {
+            site: site.into(),
+            proxy_id: proxy_id.into(),
+            since: Utc::now() - chrono::Duration::hours(hours_ago),
+        }
+    }
+
+    #[test]
+    fn rates() {
+        assert_eq!(rate(0, 0), 0.0);
+        assert_eq!(rate(1, 4), 0.25);
+    }
+
+    #[test]
+    fn unusable_proxies() {
+        let now = Utc::now();
+        assert!(usable(&proxy("a"), now));
+        let mut p = proxy("a");
+        p.healthy = None;
+        assert!(usable(&p, now));
+        p.enabled = false;
+        assert!(!usable(&p, now));
+        let mut p = proxy("a");
+        p.healthy = Some(false);
+        assert!(!usable(&p, now));
+        let mut p = proxy("a");
+        p.rested_until = Some(now + chrono::Duration::minutes(5));
+        assert!(!usable(&p, now));
+        assert!(usable(&p, now + chrono::Duration::minutes(5)));
+    }
+
+    #[test]
+    fn pick_the_least_loaded_then_cleanest_then_fastest() {
+        let (mut a, mut b, mut c) = (proxy("a"), proxy("b"), proxy("c"));
+        a.latency_ms = Some(50);
+        b.requests = 10;
+        b.bans = 5;
+        c.latency_ms = Some(300);
+        let mut pool = Pool {
+            proxies: vec![a, b, c],
+            assignments: Vec::new(),
+        };
+        assert_eq!(pick(&pool, None).as_deref(), Some("a"));
+        assert_eq!(pick(&pool, Some("a")).as_deref(), Some("c"));
+        pool.assignments.push(assigned("linkedin", "a", 0));
+        pool.assignments.push(assigned("indeed", "c", 0));
+        assert_eq!(pick(&pool, None).as_deref(), Some("b"));
+        pool.proxies.truncate(1);
+        assert_eq!(pick(&pool, Some("a")).as_deref(), Some("a"));
+        pool.proxies[0].enabled = false;
+        assert_eq!(pick(&pool, None), None);
+    }
+
+    #[test]
+    fn sites_move_off_unusable_and_old_proxies() {
+        let mut down = proxy("down");
+        down.healthy = Some(false);
+        let mut pool = Pool {
+            proxies: vec![proxy("a"), proxy("b"), down],
+            assignments: vec![
+                assigned("linkedin", "down", 0),
+                assigned("indeed", "a", ROTATE_AFTER_HOURS + 1),
+                assigned("infojobs", "b", 0),
+            ],
+        };
+        let moved = rotate(&mut pool, None);
+        assert_eq!(moved, ["linkedin", "indeed"]);
+        let of = |site: &str| {
+            pool.assignments
+                .iter()
+                .find(|a| a.site == site)
+                .map(|a| a.proxy_id.clone())
+        };
+        assert_ne!(of("linkedin").as_deref(), Some("down"));
+        assert_eq!(of("indeed").as_deref(), Some("b"));
+        assert_eq!(of("infojobs").as_deref(), Some("b"));
+        assert_eq!(rotate(&mut pool, Some("infojobs")), ["infojobs"]);
+    }
+
+    #[test]
+    fn sites_go_direct_when_nothing_is_left() {
+        let mut down = proxy("down");
+        down.enabled = false;
+        let mut pool = Pool {
+            proxies: vec![down],
+            assignments: vec![
+                assigned("linkedin", "down", 0),
+                assigned("indeed", "gone", 0),
+            ],
+        };
+        assert_eq!(rotate(&mut pool, None), ["linkedin", "indeed"]);
+        assert!(pool.assignments.is_empty());
+    }
+}
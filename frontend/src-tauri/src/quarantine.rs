@@ -0,0 +1,265 @@
+use serde_json::json;
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tauri::AppHandle;
+
+use crate::{events, signing};
+
+// Everything the shell downloads outside the Tauri updater (engine builds,
+// data packs, browser drivers) is written to <app data>/quarantine/ first
+// and only moved into place once its size, file type, checksum and
+// signature all check out. A failed check deletes the file and is reported
+// on the backend event channel as `download_failed`, next to the engine's
+// own `*_error` events.
+const DIR: &str = "quarantine";
+pub const DOWNLOAD_FAILED: &str = "download_failed";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileType {
+    // Native executable for this platform
+    Executable,
+    Json,
+    Zip,
+}
+
+impl FileType {
+    fn describe(self) -> &'static str {
+        match self {
+            FileType::Executable => "an executable for this platform",
+            FileType::Json => "JSON",
+            FileType::Zip => "a zip archive",
+        }
+    }
+}
+
+pub struct Expected<'a> {
+    // For messages and the failure event, e.g. "engine 1.4.0"
+    pub name: &'a str,
+    pub max_bytes: u64,
+    pub file_type: FileType,
+    pub sha256: &'a str,
+    // Base64 `.sig` content from `tauri signer sign`
+    pub signature: &'a str,
+}
+
+// A downloaded file that passed every check. Dropping it without calling
+// `release` deletes it.
+pub struct Quarantined {
+    path: PathBuf,
+    file_type: FileType,
+}
+
+fn dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::paths::base_data_dir(app)?.join(DIR))
+}
+
+// Leftovers from downloads interrupted by a crash or quit.
+pub fn sweep(app: &AppHandle) {
+    if let Ok(dir) = dir(app) {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+#[cfg(windows)]
+fn is_executable(head: &[u8]) -> bool {
+    head.starts_with(b"MZ")
+}
+
+#[cfg(target_os = "macos")]
+fn is_executable(head: &[u8]) -> bool {
+    const MAGIC: &[[u8; 4]] = &[
+        [0xcf, 0xfa, 0xed, 0xfe], // 64-bit Mach-O
+        [0xca, 0xfe, 0xba, 0xbe], // universal binary
+    ];
+    MAGIC.iter().any(|m| head.starts_with(m))
+}
+
+#[cfg(target_os = "linux")]
+fn is_executable(head: &[u8]) -> bool {
+    head.starts_with(b"\x7fELF")
+}
+
+#[cfg(unix)]
+pub fn make_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|e| e.to_string())
+}
+
+#[cfg(windows)]
+pub fn make_executable(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+// Type and checksum; the signature needs the app's public key.
+fn check_contents(bytes: &[u8], expected: &Expected) -> Result<(), String> {
+    let type_ok = match expected.file_type {
+        FileType::Executable => is_executable(bytes),
+        FileType::Json => serde_json::from_slice::<serde_json::Value>(bytes).is_ok(),
+        FileType::Zip => bytes.starts_with(b"PK\x03\x04"),
+    };
+    if !type_ok {
+        return Err(format!(
+            "{} is not {}",
+            expected.name,
+            expected.file_type.describe()
+        ));
+    }
+    if signing::sha256_hex(bytes) != expected.sha256.to_lowercase() {
+        return Err(format!("{} checksum mismatch", expected.name));
+    }
+    Ok(())
+}
+
+fn check(bytes: &[u8], app: &AppHandle, expected: &Expected) -> Result<(), String> {
+    check_contents(bytes, expected)?;
+    signing::verify(app, bytes, expected.signature)
+}
+
+fn download(
+    app: &AppHandle,
+    url: &str,
+    timeout: Duration,
+    expected: &Expected,
+) -> Result<Quarantined, String> {
+    let mut response = crate::net::client(timeout)?
+        .get(url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?;
+    let too_big = || {
+        format!(
+            "{} is larger than {} bytes",
+            expected.name, expected.max_bytes
+        )
+    };
+    if response
+        .content_length()
+        .is_some_and(|n| n > expected.max_bytes)
+    {
+        return Err(too_big());
+    }
+    let dir = dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let file_name: String = expected
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    // Owns the file from here on, so every early return below deletes it
+    let quarantined = Quarantined {
+        path: dir.join(format!("{file_name}.download")),
+        file_type: expected.file_type,
+    };
+    let mut file = File::create(&quarantined.path).map_err(|e| e.to_string())?;
+    // The server's length header can lie; stop reading one byte past the cap
+    let written = io::copy(&mut (&mut response).take(expected.max_bytes + 1), &mut file)
+        .map_err(|e| e.to_string())?;
+    drop(file);
+    if written > expected.max_bytes {
+        return Err(too_big());
+    }
+    let bytes = fs::read(&quarantined.path).map_err(|e| e.to_string())?;
+    check(&bytes, app, expected)?;
+    Ok(quarantined)
+}
+
+// Downloads `url` into quarantine and checks it. Failures are also reported
+// to the webview; callers only need to log or propagate the error.
+pub fn fetch(
+    app: &AppHandle,
+    url: &str,
+    timeout: Duration,
+    expected: &Expected,
+) -> Result<Quarantined, String> {
+    download(app, url, timeout, expected).inspect_err(|e| {
+        events::emit(
+            app,
+            DOWNLOAD_FAILED,
+            json!({ "name": expected.name, "url": url, "error": e }),
+        );
+    })
+}
+
+impl Quarantined {
+    pub fn bytes(&self) -> Result<Vec<u8>, String> {
+        fs::read(&self.path).map_err(|e| e.to_string())
+    }
+
+    // Moves the file to `dest`. The quarantine folder sits in the same data
+    // directory as every destination, so this is a rename, never a copy.
+    pub fn release(self, dest: &Path) -> Result<(), String> {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        if self.file_type == FileType::Executable {
+            make_executable(&self.path)?;
+        }
+        fs::rename(&self.path, dest).map_err(|e| e.to_string())
+    }
+}
+
+impl Drop for Quarantined {
+    fn drop(&mut self) {
+        // Gone already when it was released
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(file_type: FileType, sha256: &str) -> Expected<'_> {
+        Expected {
+            name: "pack",
+            max_bytes: 1024,
+            file_type,
+            sha256,
+            signature: "",
+        }
+    }
+
+    #[test]
+    fn type_and_checksum() {
+        let json = br#"{"version": 2}"#;
+        // Published checksums are sometimes upper case
+        let sha = signing::sha256_hex(json).to_uppercase();
+        assert!(check_contents(json, &expected(FileType::Json, &sha)).is_ok());
+        assert_eq!(
+            check_contents(b"<html>", &expected(FileType::Json, &sha)).unwrap_err(),
+            "pack is not JSON"
+        );
+        assert_eq!(
+            check_contents(b"{}", &expected(FileType::Json, &sha)).unwrap_err(),
+            "pack checksum mismatch"
+        );
+        let zip = b"PK\x03\x04rest";
+        let sha = signing::sha256_hex(zip);
+        assert!(check_contents(zip, &expected(FileType::Zip, &sha)).is_ok());
+        assert!(check_contents(json, &expected(FileType::Zip, &sha)).is_err());
+    }
+
+    #[test]
+    fn executables_for_this_platform_only() {
+        let native: &[u8] = if cfg!(windows) {
+            b"MZ\x90\x00"
+        } else if cfg!(target_os = "macos") {
+            &[0xcf, 0xfa, 0xed, 0xfe]
+        } else {
+            b"\x7fELF\x02"
+        };
+        assert!(is_executable(native));
+        assert!(!is_executable(b"#!/bin/sh\n"));
+        assert!(!is_executable(b""));
+    }
+}
@@ -0,0 +1,413 @@
+use chrono::{DateTime, Days, Local, NaiveDate, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::json;
+use std::{collections::BTreeMap, sync::Mutex, thread, time::Duration};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    backend, history,
+    i18n::t_args,
+    notifications,
+    schedule::Target,
+    settings::{self, QuotaSettings},
+};
+
+// Limits on how fast applications go out, kept by the shell whatever the
+// engine does: at most so many a day on each board (the scraper's site),
+// counted from local midnight, and a minimum time between one and the next.
+// An application counts once it's authorized, whether here or in the browser,
+// from the history database, leaving out imported ones. Authorizing goes
+// through `authorize_application` here, which refuses while a limit holds;
+// scheduled and Dock runs of a board whose applications can't go out today
+// are skipped, and a run of every board once all of them are used up. What's
+// left shows in the tray tooltip, kept current by a thread every minute.
+pub const QUOTA_CHANGED: &str = "quota-changed";
+const REFRESH: Duration = Duration::from_secs(60);
+
+// Checking and authorizing happen together, so two can't both take the last
+static GATE: Mutex<()> = Mutex::new(());
+// The tray tooltip's line, computed off the main thread
+static TOOLTIP: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BoardQuota {
+    pub site: String,
+    pub used: u32,
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct QuotaStatus {
+    pub settings: QuotaSettings,
+    // Boards with a cap or used today
+    pub boards: Vec<BoardQuota>,
+    pub last_sent_at: Option<DateTime<Utc>>,
+    // Set while the minimum delay holds
+    pub next_allowed_at: Option<DateTime<Utc>>,
+    // Next local midnight
+    pub resets_at: DateTime<Utc>,
+}
+
+impl QuotaStatus {
+    fn board(&self, site: &str) -> Option<&BoardQuota> {
+        self.boards.iter().find(|b| b.site == site)
+    }
+
+    fn used_up(&self, site: &str) -> bool {
+        self.board(site).is_some_and(|b| b.remaining == Some(0))
+    }
+}
+
+fn limit(cfg: &QuotaSettings, site: &str) -> Option<u32> {
+    cfg.boards
+        .get(site)
+        .copied()
+        .or((cfg.daily_per_board > 0).then_some(cfg.daily_per_board))
+}
+
+fn midnight_after(day: NaiveDate) -> DateTime<Utc> {
+    let next = day + Days::new(1);
+    next.and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(Local).earliest())
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now)
+}
+
+pub fn status(app: &AppHandle) -> Result<QuotaStatus, String> {
+    tally(&history::open(app)?, settings::get(app).quota, Local::now())
+}
+
+// Where the limits stand at `now`, from the applications in `db`.
+fn tally(db: &Connection, cfg: QuotaSettings, now: DateTime<Local>) -> Result<QuotaStatus, String> {
+    let today = now.date_naive();
+    // Stored times start with the date, whatever their zone; a day back
+    // covers any offset
+    let since = (today - Days::new(1)).format("%Y-%m-%d").to_string();
+    let rows: Vec<(String, Option<String>)> = db
+        .prepare(
+            "SELECT COALESCE(site, ''), COALESCE(authorized_at, submitted_at) FROM applications
+             WHERE imported_from IS NULL AND COALESCE(authorized_at, submitted_at) >= ?1",
+        )
+        .and_then(|mut s| {
+            s.query_map([since], |r| Ok((r.get(0)?, r.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| e.to_string())?;
+    let mut used: BTreeMap<String, u32> = cfg.boards.keys().map(|s| (s.clone(), 0)).collect();
+    let mut last_sent: Option<DateTime<Utc>> = None;
+    for (site, at) in rows {
+        let Some(at) = history::local(&at) else {
+            continue;
+        };
+        let utc = at
+            .and_local_timezone(Local)
+            .earliest()
+            .map(|t| t.with_timezone(&Utc));
+        last_sent = last_sent.max(utc);
+        if at.date() == today {
+            *used.entry(site).or_default() += 1;
+        }
+    }
+    let boards = used
+        .into_iter()
+        .map(|(site, used)| {
+            let limit = limit(&cfg, &site);
+            BoardQuota {
+                remaining: limit.map(|l| l.saturating_sub(used)),
+                limit,
+                used,
+                site,
+            }
+        })
+        .collect();
+    let delay = chrono::Duration::minutes(cfg.min_delay_minutes.into());
+    let next_allowed_at = last_sent
+        .map(|last| last + delay)
+        .filter(|next| cfg.min_delay_minutes > 0 && *next > now);
+    Ok(QuotaStatus {
+        settings: cfg,
+        boards,
+        last_sent_at: last_sent,
+        next_allowed_at,
+        resets_at: midnight_after(today),
+    })
+}
+
+// Why an application from `site` can't go out now, if it can't.
+fn refusal(status: &QuotaStatus, site: &str) -> Option<String> {
+    if !status.settings.enabled {
+        return None;
+    }
+    if status.used_up(site) {
+        let limit = status.board(site).and_then(|b| b.limit).unwrap_or(0);
+        return Some(format!(
+            "the daily limit of {limit} applications on {site} is reached; it resets at midnight"
+        ));
+    }
+    status.next_allowed_at.map(|next| {
+        format!(
+            "applications are at least {} minutes apart; the next can go at {}",
+            status.settings.min_delay_minutes,
+            next.with_timezone(&Local).format("%H:%M")
+        )
+    })
+}
+
+// The engine's boards, as its scraper status lists them.
+fn engine_boards() -> Vec<String> {
+    backend::get_json("/api/scrapers/status")
+        .ok()
+        .and_then(|v| v["scrapers"].as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|s| s["site"].as_str().map(str::to_string))
+        .collect()
+}
+
+// Why a run of `target` would be pointless today, if it would: nothing it
+// finds could be sent.
+pub fn run_refusal(app: &AppHandle, target: &Target) -> Option<String> {
+    let status = status(app)
+        .inspect_err(|e| warn!("quota not checked: {e}"))
+        .ok()?;
+    if !status.settings.enabled {
+        return None;
+    }
+    match target {
+        Target::Scraper { site } => status
+            .used_up(site)
+            .then(|| format!("the daily limit on {site} is reached")),
+        Target::AllScrapers => {
+            let boards = engine_boards();
+            (!boards.is_empty() && boards.iter().all(|b| status.used_up(b)))
+                .then(|| "the daily limit on every board is reached".to_string())
+        }
+    }
+}
+
+fn tooltip(status: &QuotaStatus) -> Option<String> {
+    if !status.settings.enabled {
+        return None;
+    }
+    let (remaining, limit) = status
+        .boards
+        .iter()
+        .filter_map(|b| Some((b.remaining?, b.limit?)))
+        .fold((0, 0), |(r, l), (br, bl)| (r + br, l + bl));
+    if let Some(next) = status.next_allowed_at {
+        return Some(t_args(
+            "quota-tooltip-wait",
+            &[(
+                "time",
+                next.with_timezone(&Local)
+                    .format("%H:%M")
+                    .to_string()
+                    .into(),
+            )],
+        ));
+    }
+    (limit > 0).then(|| {
+        t_args(
+            "quota-tooltip",
+            &[("remaining", remaining.into()), ("limit", limit.into())],
+        )
+    })
+}
+
+// The tray tooltip's quota line, as of the last refresh.
+pub fn tooltip_line() -> Option<String> {
+    TOOLTIP.lock().unwrap().clone()
+}
+
+fn refresh(app: &AppHandle) {
+    let line = match status(app) {
+        Ok(status) => tooltip(&status),
+        Err(e) => {
+            warn!("quota not refreshed: {e}");
+            return;
+        }
+    };
+    let changed = {
+        let mut current = TOOLTIP.lock().unwrap();
+        let changed = *current != line;
+        *current = line;
+        changed
+    };
+    if changed {
+        notifications::refresh_tray(app);
+    }
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        refresh(&app);
+        thread::sleep(REFRESH);
+    });
+}
+
+#[tauri::command]
+pub async fn get_quota(app: AppHandle) -> Result<QuotaStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || status(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn set_quota_settings(app: AppHandle, quota: QuotaSettings) -> Result<(), String> {
+    if quota.boards.keys().any(|s| s.trim().is_empty()) {
+        return Err("a board needs a name".into());
+    }
+    settings::update(&app, |s| s.quota = quota)?;
+    let _ = app.emit(QUOTA_CHANGED, ());
+    let app = app.clone();
+    thread::spawn(move || refresh(&app));
+    Ok(())
+}
+
+// The review page's Authorize, with the limits checked first.
+#[tauri::command]
+pub async fn authorize_application(app: AppHandle, id: i64) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let _gate = GATE.lock().unwrap();
+        let db = history::open(&app)?;
+        let site: String = db
+            .query_row(
+                "SELECT COALESCE(site, '') FROM applications WHERE id = ?1",
+                [id],
+                |r| r.get(0),
+            )
+            .unwrap_or_default();
+        if let Some(reason) = refusal(&status(&app)?, &site) {
+            info!(application = id, site, "authorization held back: {reason}");
+            return Err(reason);
+        }
+        backend::post_json(&format!("/api/applications/{id}/authorize"), &json!({}))?;
+        // Counted now rather than when the engine's event arrives
+        db.execute(
+            "UPDATE applications SET authorized_at = COALESCE(authorized_at, ?2) WHERE id = ?1",
+            (id, Utc::now().to_rfc3339()),
+        )
+        .map_err(|e| e.to_string())?;
+        refresh(&app);
+        let _ = app.emit(QUOTA_CHANGED, ());
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg() -> QuotaSettings {
+        QuotaSettings {
+            enabled: true,
+            daily_per_board: 3,
+            boards: [("infojobs".to_string(), 1)].into(),
+            min_delay_minutes: 0,
+        }
+    }
+
+    // Sent by the time of `now`, so many minutes before it.
+    fn db(now: DateTime<Local>, sent: &[(&str, i64, bool)]) -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (site, minutes_ago, imported) in sent {
+            let at = (now - chrono::Duration::minutes(*minutes_ago)).to_rfc3339();
+            db.execute(
+                "INSERT INTO applications (company, status, site, authorized_at, recorded_at,
+                    imported_from)
+                 VALUES ('Acme', 'applied', ?1, ?2, '', ?3)",
+                rusqlite::params![site, at, imported.then_some("export.csv")],
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    fn noon() -> DateTime<Local> {
+        Local::now()
+            .date_naive()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap()
+    }
+
+    #[test]
+    fn board_caps_over_the_default() {
+        let cfg = cfg();
+        assert_eq!(limit(&cfg, "infojobs"), Some(1));
+        assert_eq!(limit(&cfg, "linkedin"), Some(3));
+        let uncapped = QuotaSettings::default();
+        assert_eq!(limit(&uncapped, "linkedin"), None);
+    }
+
+    #[test]
+    fn counts_todays_applications_per_board() {
+        let now = noon();
+        let db = db(
+            now,
+            &[
+                ("infojobs", 30, false),
+                ("linkedin", 60, false),
+                ("linkedin", 90, true),
+                // Yesterday
+                ("linkedin", 24 * 60, false),
+            ],
+        );
+        let status = tally(&db, cfg(), now).unwrap();
+        let board = |site: &str| status.board(site).map(|b| (b.used, b.remaining));
+        assert_eq!(board("infojobs"), Some((1, Some(0))));
+        assert_eq!(board("linkedin"), Some((1, Some(2))));
+        assert!(status.used_up("infojobs"));
+        assert!(!status.used_up("linkedin"));
+        assert!(!status.used_up("indeed"));
+        assert_eq!(
+            status.last_sent_at,
+            Some((now - chrono::Duration::minutes(30)).with_timezone(&Utc))
+        );
+        assert_eq!(status.next_allowed_at, None);
+        assert_eq!(status.resets_at, midnight_after(now.date_naive()));
+        assert!(status.resets_at > now);
+    }
+
+    #[test]
+    fn refused_while_a_limit_holds() {
+        let now = noon();
+        let mut cfg = cfg();
+        cfg.min_delay_minutes = 45;
+        let status = tally(&db(now, &[("infojobs", 30, false)]), cfg.clone(), now).unwrap();
+        assert_eq!(
+            status.next_allowed_at,
+            Some((now + chrono::Duration::minutes(15)).with_timezone(&Utc))
+        );
+        assert!(refusal(&status, "infojobs")
+            .unwrap()
+            .starts_with("the daily limit of 1 applications on infojobs"));
+        assert!(refusal(&status, "linkedin")
+            .unwrap()
+            .starts_with("applications are at least 45 minutes apart"));
+
+        let status = tally(&db(now, &[("infojobs", 60, false)]), cfg.clone(), now).unwrap();
+        assert_eq!(refusal(&status, "linkedin"), None);
+        cfg.enabled = false;
+        let status = tally(&db(now, &[("infojobs", 30, false)]), cfg, now).unwrap();
+        assert_eq!(refusal(&status, "infojobs"), None);
+        assert_eq!(tooltip(&status), None);
+    }
+
+    #[test]
+    fn tooltip_adds_up_the_boards() {
+        let now = noon();
+        let status = tally(&db(now, &[("linkedin", 30, false)]), cfg(), now).unwrap();
+        // infojobs 1 of 1 and linkedin 2 of 3 left
+        let line = tooltip(&status).unwrap();
+        assert!(line.contains('3') && line.contains('4'), "{line}");
+    }
+}
@@ -0,0 +1,339 @@
+use regex::{Regex, RegexBuilder};
+use std::sync::{LazyLock, RwLock};
+use tauri::AppHandle;
+
+use crate::{
+    profiles,
+    settings::{self, RedactionSettings},
+};
+
+// Personal data and secrets are stripped from anything the shell writes to a
+// log or puts in a diagnostics bundle. Built-in rules cover emails, phone
+// numbers and tokens; `redaction` in settings.json adds patterns and names,
+// and the active profile's name is always included, as is the name on a CV
+// once the shell or the engine has read one. The backend applies the same
+// rules to its own logs (backend/security/redaction.py); keep the two in
+// step.
+pub const PATTERNS_ENV: &str = "JOBBOT_REDACT_PATTERNS";
+pub const NAMES_ENV: &str = "JOBBOT_REDACT_NAMES";
+
+// Shorter name parts hit too many ordinary words
+const MIN_NAME_PART: usize = 3;
+
+static BUILTIN: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        (r"(?i)\bbearer\s+[A-Za-z0-9._~+/=\-]+", "Bearer [token]"),
+        (
+            r#"(?i)\b(token|password|passwd|secret|api[_\-]?key|cookie)(["']?\s*[:=]\s*["']?)[^\s"'&,;]+"#,
+            "${1}${2}[token]",
+        ),
+        (r"\beyJ[\w\-]+\.[\w\-]+\.[\w\-]+", "[token]"),
+        // Long hex only after a key that says it's secret; hashes are left
+        (
+            r#"(?i)\b([\w\-]*(?:key|token|secret|session|signature|auth)[\w\-]*["']?(?:\s*[:=]\s*|\s+)["']?)[0-9a-f]{32,}\b"#,
+            "${1}[token]",
+        ),
+        (
+            r"[a-zA-Z0-9._%+\-]+@[a-zA-Z0-9.\-]+\.[a-zA-Z]{2,}",
+            "[email]",
+        ),
+        // Nine digits alone are as likely a pid or a size: a phone number
+        // has a country code, is written in groups or follows a phone key
+        (
+            r"\+\d{1,3}[\s\-]?\d{3}[\s\-]?\d{3}[\s\-]?\d{3}\b",
+            "[phone]",
+        ),
+        (r"\b\d{3}[\s\-]\d{3}[\s\-]\d{3}\b", "[phone]"),
+        (
+            r"(?i)\b(phone|tel|telephone|mobile|m[oó]vil|tel[eé]fono|whatsapp)(\W{1,4})\d{9}\b",
+            "${1}${2}[phone]",
+        ),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+    .collect()
+});
+
+// Compiled user rules and the input they were built from.
+type Compiled = (RedactionSettings, String, usize, Vec<Regex>, Vec<Regex>);
+static COMPILED: RwLock<Option<Compiled>> = RwLock::new(None);
+// Names read off CVs since launch
+static LEARNED: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+pub fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("invalid pattern {pattern}: {e}"))
+}
+
+// Full names first, then each part, longest first.
+fn name_rules(names: &[String]) -> Vec<Regex> {
+    let mut candidates: Vec<&str> = names
+        .iter()
+        .map(|n| n.trim())
+        .filter(|n| !n.is_empty())
+        .flat_map(|n| {
+            std::iter::once(n).chain(
+                n.split_whitespace()
+                    .filter(|p| p.chars().count() >= MIN_NAME_PART),
+            )
+        })
+        .collect();
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.len()));
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .filter_map(|c| {
+            RegexBuilder::new(&format!(r"\b{}\b", regex::escape(c)))
+                .case_insensitive(true)
+                .build()
+                .ok()
+        })
+        .collect()
+}
+
+// Names the backend and the shell hide: configured ones plus the profile's
+// and those read off CVs.
+pub fn names(app: &AppHandle, redaction: &RedactionSettings) -> Vec<String> {
+    let active = profiles::active();
+    let profile_name = profiles::list(app)
+        .profiles
+        .into_iter()
+        .find(|p| p.id == active && p.id != profiles::DEFAULT_PROFILE)
+        .map(|p| p.name);
+    redaction
+        .names
+        .iter()
+        .cloned()
+        .chain(profile_name)
+        .chain(LEARNED.read().unwrap().iter().cloned())
+        .collect()
+}
+
+// The name on a CV, from the shell's own reading (resume.rs) or the engine's
+// (its `redaction_names` event).
+pub fn add_names(app: &AppHandle, names: impl IntoIterator<Item = String>) {
+    let added = {
+        let mut learned = LEARNED.write().unwrap();
+        let before = learned.len();
+        for name in names.into_iter().map(|n| n.trim().to_string()) {
+            if !name.is_empty() && !learned.contains(&name) {
+                learned.push(name);
+            }
+        }
+        learned.len() > before
+    };
+    if added {
+        refresh(app);
+    }
+}
+
+fn with_rules<T>(app: &AppHandle, f: impl FnOnce(&[Regex], &[Regex]) -> T) -> T {
+    let redaction = settings::get(app).redaction;
+    let active = profiles::active();
+    let learned = LEARNED.read().unwrap().len();
+    let fresh = COMPILED
+        .read()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|(r, p, l, _, _)| *r == redaction && *p == active && *l == learned);
+    if !fresh {
+        let custom = redaction
+            .patterns
+            .iter()
+            .filter_map(|p| compile_pattern(p).ok())
+            .collect();
+        let names = name_rules(&names(app, &redaction));
+        *COMPILED.write().unwrap() = Some((redaction, active, learned, custom, names));
+    }
+    let guard = COMPILED.read().unwrap();
+    let (_, _, _, custom, names) = guard.as_ref().unwrap();
+    f(custom, names)
+}
+
+fn apply_rules(text: &str, custom: &[Regex], names: &[Regex]) -> String {
+    let mut text = text.to_string();
+    for (rule, replacement) in BUILTIN.iter() {
+        text = rule.replace_all(&text, *replacement).into_owned();
+    }
+    for rule in custom {
+        text = rule.replace_all(&text, "[redacted]").into_owned();
+    }
+    for rule in names {
+        text = rule.replace_all(&text, "[name]").into_owned();
+    }
+    text
+}
+
+pub fn redact(app: &AppHandle, text: &str) -> String {
+    with_rules(app, |custom, names| apply_rules(text, custom, names))
+}
+
+// For the log writer, which can run while settings are locked or before
+// they're loaded: uses the rules as last compiled, built-ins only until then.
+pub fn redact_cached(text: &str) -> String {
+    match COMPILED.read().unwrap().as_ref() {
+        Some((_, _, _, custom, names)) => apply_rules(text, custom, names),
+        None => apply_rules(text, &[], &[]),
+    }
+}
+
+// Recompiles after the rules or the active profile changed.
+pub fn refresh(app: &AppHandle) {
+    with_rules(app, |_, _| ());
+}
+
+// JSON lists for backend/security/redaction.py.
+pub fn backend_env(app: &AppHandle) -> Vec<(&'static str, String)> {
+    let redaction = settings::get(app).redaction;
+    let names = names(app, &redaction);
+    [(PATTERNS_ENV, redaction.patterns), (NAMES_ENV, names)]
+        .into_iter()
+        .filter(|(_, list)| !list.is_empty())
+        .filter_map(|(name, list)| Some((name, serde_json::to_string(&list).ok()?)))
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_redaction(app: AppHandle) -> RedactionSettings {
+    settings::get(&app).redaction
+}
+
+// Rejects the whole change if any pattern doesn't compile.
+#[tauri::command]
+pub fn set_redaction(app: AppHandle, redaction: RedactionSettings) -> Result<(), String> {
+    for pattern in &redaction.patterns {
+        compile_pattern(pattern)?;
+    }
+    settings::update(&app, |s| s.redaction = redaction)?;
+    crate::restart_backend(&app)
+}
+
+// Lets the settings page show what a line looks like after redaction.
+#[tauri::command]
+pub fn preview_redaction(app: AppHandle, text: String) -> String {
+    redact(&app, &text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builtin(text: &str) -> String {
+        apply_rules(text, &[], &[])
+    }
+
+    #[test]
+    fn email() {
+        assert_eq!(
+            builtin("sent to ana.garcia+jobs@example.es ok"),
+            "sent to [email] ok"
+        );
+    }
+
+    #[test]
+    fn phone() {
+        for phone in [
+            "phone 612345678",
+            "612 345 678",
+            "+34 612-345-678",
+            "+34612345678",
+            "Teléfono: 612345678",
+        ] {
+            let redacted = builtin(&format!("call {phone} now"));
+            assert!(redacted.contains("[phone]"), "{redacted}");
+            assert!(!redacted.contains("345"), "{redacted}");
+        }
+    }
+
+    #[test]
+    fn bare_numbers_untouched() {
+        for line in [
+            "engine started pid=612345678",
+            "downloaded 734003200 bytes",
+            "at 1760515200 took 123456789ms",
+        ] {
+            assert_eq!(builtin(line), line);
+        }
+    }
+
+    #[test]
+    fn bearer() {
+        assert_eq!(
+            builtin("Authorization: Bearer abc.def-123"),
+            "Authorization: Bearer [token]"
+        );
+    }
+
+    #[test]
+    fn key_value_secrets() {
+        for (line, expected) in [
+            ("password=hunter2", "password=[token]"),
+            (r#"{"api_key": "sk-123"}"#, r#"{"api_key": "[token]"}"#),
+            (
+                "GET /api/events?token=deadbeef&x=1",
+                "GET /api/events?token=[token]&x=1",
+            ),
+        ] {
+            assert_eq!(builtin(line), expected);
+        }
+    }
+
+    #[test]
+    fn long_hex_after_a_key() {
+        let hex = "ab12".repeat(16);
+        assert_eq!(builtin(&format!("key {hex}")), "key [token]");
+        assert_eq!(
+            builtin(&format!("session_token={hex}")),
+            "session_token=[token]"
+        );
+        assert_eq!(builtin(&format!("X-Session: {hex}")), "X-Session: [token]");
+    }
+
+    #[test]
+    fn hashes_untouched() {
+        let hex = "ab12".repeat(16);
+        for line in [
+            format!("jobbot-backend does not match this build (sha256 {hex}, expected {hex})"),
+            format!("stored {hex}.pdf"),
+        ] {
+            assert_eq!(builtin(&line), line);
+        }
+    }
+
+    #[test]
+    fn ordinary_text_untouched() {
+        let line = "scraper.done site=infojobs jobs=42 at 2026-10-15T08:00:00 application_id=1234";
+        assert_eq!(builtin(line), line);
+        let line = "settings file moved to settings.corrupt-20261015-083000.json";
+        assert_eq!(builtin(line), line);
+    }
+
+    #[test]
+    fn full_name_and_parts() {
+        let names = name_rules(&["Ana García López".into()]);
+        assert_eq!(
+            apply_rules("CV for Ana García López", &[], &names),
+            "CV for [name]"
+        );
+        assert_eq!(apply_rules("signed: garcía", &[], &names), "signed: [name]");
+    }
+
+    #[test]
+    fn short_parts_ignored() {
+        let names = name_rules(&["Li Wu".into()]);
+        assert_eq!(apply_rules("Li Wu applied", &[], &names), "[name] applied");
+        assert_eq!(
+            apply_rules("a list of wu-tang", &[], &names),
+            "a list of wu-tang"
+        );
+    }
+
+    #[test]
+    fn custom_patterns() {
+        assert!(compile_pattern("(").is_err());
+        let custom = [compile_pattern(r"NIE-\w+").unwrap()];
+        assert_eq!(
+            apply_rules("doc NIE-X1234 ok", &custom, &[]),
+            "doc [redacted] ok"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use rusqlite::{params, Connection};
+use std::{
+    sync::{Condvar, Mutex},
+    thread,
+    time::Duration as StdDuration,
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    calendar::{self, CalendarEvent},
+    history,
+    i18n::{t, t_args},
+    notifications, tz,
+};
+
+// Follow-up reminders: the calendar's follow-up events (calendar.rs), which
+// also go off as notifications. One thread sleeps until the earliest one due
+// and notifies everything due by then, marking it so it goes off once; adding,
+// moving or removing one wakes it to look again. Being in the history
+// database, reminders outlive restarts, and one that came due while the app
+// was closed goes off as soon as it starts. A reminder that went off stays
+// listed until it's snoozed, which sets it again, or marked done. Interviews
+// go off too, an hour before they start. Times are instants, so a reminder
+// goes off on time wherever the user is; each look first works them out again
+// from their wall-clock times (calendar::reschedule), so "9:00" stays 9:00
+// after a DST change or a flight, and an interview stays at the recruiter's.
+pub const REMINDERS_CHANGED: &str = "reminders-changed";
+// Never sleeps longer, so system sleep or a clock change can't delay one much
+const MAX_SLEEP: StdDuration = StdDuration::from_secs(60);
+// "In 7 days" means that morning
+const REMIND_AT: (u32, u32) = (9, 0);
+const DURATION_MINUTES: u32 = 15;
+const MAX_DAYS: u32 = 365;
+const INTERVIEW_WARNING_MINUTES: i64 = 60;
+
+static WAKE: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
+
+// Reminders changed; look for the next one again.
+pub fn wake() {
+    let (changed, signal) = &WAKE;
+    *changed.lock().unwrap() = true;
+    signal.notify_one();
+}
+
+fn pending(db: &Connection) -> Result<Vec<CalendarEvent>, String> {
+    Ok(calendar::list(db, None)?
+        .into_iter()
+        .filter(|e| e.kind == calendar::Kind::FollowUp && e.done_at.is_none())
+        .collect())
+}
+
+// When it goes off, unless it already has or no longer can.
+fn due(e: &CalendarEvent, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if e.notified_at.is_some() || e.done_at.is_some() {
+        return None;
+    }
+    match e.kind {
+        calendar::Kind::FollowUp => Some(e.starts_at),
+        calendar::Kind::Interview if e.starts_at > now => {
+            Some(e.starts_at - Duration::minutes(INTERVIEW_WARNING_MINUTES))
+        }
+        calendar::Kind::Interview => None,
+    }
+}
+
+// "Starts at 15:00", and the time where it's set when that's not here.
+fn interview_body(e: &CalendarEvent) -> String {
+    let here = tz::local_zone();
+    let time = |zone: &str| {
+        tz::zoned(zone, e.starts_at)
+            .map(|z| z.local_time.format("%H:%M").to_string())
+            .unwrap_or_else(|_| e.starts_at.format("%H:%M UTC").to_string())
+    };
+    match e.timezone.as_deref().filter(|zone| *zone != here) {
+        Some(zone) => t_args(
+            "reminder-interview-body-zone",
+            &[
+                ("time", time(&here).into()),
+                ("their_time", time(zone).into()),
+                ("zone", zone.to_string().into()),
+            ],
+        ),
+        None => t_args("reminder-interview-body", &[("time", time(&here).into())]),
+    }
+}
+
+fn fire(app: &AppHandle, e: &CalendarEvent) {
+    if e.kind == calendar::Kind::Interview {
+        notifications::notify_route(app, &calendar::summary(e), &interview_body(e), "/history");
+        return;
+    }
+    let body = match (e.notes.as_deref(), e.role.as_deref()) {
+        (Some(notes), _) => notes.to_string(),
+        (None, Some(role)) if !role.is_empty() => {
+            t_args("reminder-body-role", &[("role", role.to_string().into())])
+        }
+        _ => t("reminder-body"),
+    };
+    notifications::notify_route(app, &calendar::summary(e), &body, "/history");
+}
+
+// Notifies what is due, and says how long until the next one.
+fn tick(app: &AppHandle) -> Result<StdDuration, String> {
+    let db = history::open(app)?;
+    let moved = calendar::reschedule(&db)?;
+    if moved > 0 {
+        info!(count = moved, "calendar events moved with the time zone");
+    }
+    let now = Utc::now();
+    let waiting: Vec<(CalendarEvent, DateTime<Utc>)> = calendar::list(&db, None)?
+        .into_iter()
+        .filter_map(|e| due(&e, now).map(|at| (e, at)))
+        .collect();
+    let mut fired = 0;
+    for (e, _) in waiting.iter().filter(|(_, at)| *at <= now) {
+        db.execute(
+            "UPDATE calendar_events SET notified_at = ?2 WHERE id = ?1",
+            params![e.id, now.to_rfc3339()],
+        )
+        .map_err(|e| e.to_string())?;
+        fire(app, e);
+        fired += 1;
+    }
+    if fired > 0 || moved > 0 {
+        info!(count = fired, "reminders went off");
+        let _ = app.emit(REMINDERS_CHANGED, ());
+    }
+    let next = waiting
+        .iter()
+        .filter(|(_, at)| *at > now)
+        .map(|(_, at)| (*at - now).to_std().unwrap_or_default())
+        .min()
+        .unwrap_or(MAX_SLEEP);
+    Ok(next.min(MAX_SLEEP))
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        let sleep = tick(&app).unwrap_or_else(|e| {
+            warn!("reminders failed: {e}");
+            MAX_SLEEP
+        });
+        let (changed, signal) = &WAKE;
+        let guard = changed.lock().unwrap();
+        let (mut guard, _) = signal
+            .wait_timeout_while(guard, sleep, |changed| !*changed)
+            .unwrap();
+        *guard = false;
+    });
+}
+
+fn changed(app: &AppHandle) {
+    wake();
+    let _ = app.emit(REMINDERS_CHANGED, ());
+}
+
+// The ones not done yet, soonest first.
+#[tauri::command]
+pub async fn list_reminders(app: AppHandle) -> Result<Vec<CalendarEvent>, String> {
+    tauri::async_runtime::spawn_blocking(move || pending(&history::open(&app)?))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// A nudge `days` from today, in the morning wherever the user is by then.
+#[tauri::command]
+pub async fn add_reminder(
+    app: AppHandle,
+    application_id: i64,
+    days: u32,
+    notes: Option<String>,
+) -> Result<CalendarEvent, String> {
+    if days == 0 || days > MAX_DAYS {
+        return Err(format!("a reminder must be 1 to {MAX_DAYS} days away"));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let (hour, minute) = REMIND_AT;
+        let here = tz::local_zone();
+        let today = tz::zoned(&here, Utc::now())?.local_time.date();
+        let wall = (today + Duration::days(days.into()))
+            .and_time(NaiveTime::from_hms_opt(hour, minute, 0).unwrap());
+        let at = tz::resolve(&here, wall)?;
+        let notes = notes
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+        let db = history::open(&app)?;
+        let now = Utc::now().to_rfc3339();
+        db.execute(
+            "INSERT INTO calendar_events (application_id, kind, starts_at, duration_minutes,
+                notes, created_at, updated_at, local_time)
+             VALUES (?1, 'follow_up', ?2, ?3, ?4, ?5, ?5, ?6)",
+            params![
+                application_id,
+                at.to_rfc3339(),
+                DURATION_MINUTES,
+                notes,
+                now,
+                calendar::format_local(wall)
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        let event = calendar::get(&db, db.last_insert_rowid())?;
+        changed(&app);
+        Ok(event)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Goes off again `minutes` from now, however far that is on the clock.
+#[tauri::command]
+pub async fn snooze_reminder(
+    app: AppHandle,
+    id: i64,
+    minutes: u32,
+) -> Result<CalendarEvent, String> {
+    if minutes == 0 || minutes > MAX_DAYS * 24 * 60 {
+        return Err("that's not a snooze".into());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = history::open(&app)?;
+        let now = Utc::now();
+        let updated = db
+            .execute(
+                "UPDATE calendar_events SET starts_at = ?2, notified_at = NULL, done_at = NULL,
+                    local_time = NULL, timezone = NULL, updated_at = ?3, sequence = sequence + 1
+                 WHERE id = ?1 AND kind = 'follow_up'",
+                params![
+                    id,
+                    (now + Duration::minutes(minutes.into())).to_rfc3339(),
+                    now.to_rfc3339()
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        if updated == 0 {
+            return Err(format!("no reminder {id}"));
+        }
+        let event = calendar::get(&db, id)?;
+        changed(&app);
+        Ok(event)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Done following up; it won't go off, or be listed, again.
+#[tauri::command]
+pub async fn complete_reminder(app: AppHandle, id: i64) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = history::open(&app)?;
+        let updated = db
+            .execute(
+                "UPDATE calendar_events SET done_at = ?2
+                 WHERE id = ?1 AND kind = 'follow_up'",
+                params![id, Utc::now().to_rfc3339()],
+            )
+            .map_err(|e| e.to_string())?;
+        if updated == 0 {
+            return Err(format!("no reminder {id}"));
+        }
+        changed(&app);
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: calendar::Kind, starts_at: &str) -> CalendarEvent {
+        CalendarEvent {
+            id: 1,
+            application_id: None,
+            kind,
+            starts_at: starts_at.parse().unwrap(),
+            local_time: None,
+            timezone: None,
+            duration_minutes: DURATION_MINUTES,
+            location: None,
+            notes: None,
+            sequence: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+            notified_at: None,
+            done_at: None,
+            company: None,
+            role: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn follow_ups_when_set_and_interviews_an_hour_before() {
+        let now: DateTime<Utc> = "2026-10-15T12:00:00Z".parse().unwrap();
+        let follow_up = event(calendar::Kind::FollowUp, "2026-10-01T09:00:00Z");
+        assert_eq!(due(&follow_up, now), Some(follow_up.starts_at));
+        let interview = event(calendar::Kind::Interview, "2026-10-15T15:00:00Z");
+        assert_eq!(
+            due(&interview, now),
+            Some("2026-10-15T14:00:00Z".parse().unwrap())
+        );
+        // Too late to warn about
+        let started = event(calendar::Kind::Interview, "2026-10-15T11:30:00Z");
+        assert_eq!(due(&started, now), None);
+    }
+
+    #[test]
+    fn once_only() {
+        let now = Utc::now();
+        let mut notified = event(calendar::Kind::FollowUp, "2026-10-01T09:00:00Z");
+        notified.notified_at = Some(now.to_rfc3339());
+        assert_eq!(due(&notified, now), None);
+        let mut done = event(calendar::Kind::FollowUp, "2026-10-01T09:00:00Z");
+        done.done_at = Some(now.to_rfc3339());
+        assert_eq!(due(&done, now), None);
+    }
+
+    #[test]
+    fn pending_follow_ups_soonest_first() {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (kind, starts_at, done_at) in [
+            ("follow_up", "2026-10-20T09:00:00Z", None),
+            ("interview", "2026-10-16T09:00:00Z", None),
+            ("follow_up", "2026-10-18T09:00:00Z", None),
+            (
+                "follow_up",
+                "2026-10-10T09:00:00Z",
+                Some("2026-10-11T09:00:00Z"),
+            ),
+        ] {
+            db.execute(
+                "INSERT INTO calendar_events (kind, starts_at, duration_minutes, created_at,
+                    updated_at, done_at)
+                 VALUES (?1, ?2, 15, '', '', ?3)",
+                params![kind, starts_at, done_at],
+            )
+            .unwrap();
+        }
+        let starts: Vec<String> = pending(&db)
+            .unwrap()
+            .iter()
+            .map(|e| e.starts_at.to_rfc3339())
+            .collect();
+        assert_eq!(
+            starts,
+            ["2026-10-18T09:00:00+00:00", "2026-10-20T09:00:00+00:00"]
+        );
+    }
+}
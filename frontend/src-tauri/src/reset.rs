@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{fs, path::Path};
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    audit::{self, Action},
+    browserprofiles, cookievault, datapacks, documents, evidence, export, history, mailwatch,
+    oauth, paths, proxy, proxypool, settings, vault, webhooks,
+};
+
+// Factory reset for the active profile, one scope at a time. The backend is
+// stopped first so nothing holds the database open, and the app relaunches
+// afterwards so no in-memory state writes the old values back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetScope {
+    // Shell settings (settings.json)
+    Config,
+    // Webview cache and downloaded data packs
+    Cache,
+    // The engine's database, CVs, browser sessions and logs, and the
+    // shell's application history, document library and browser profiles
+    Database,
+    // Passwords kept in the OS keychain, and config secrets sealed with it
+    Credentials,
+}
+
+pub fn remove_dir(dir: &Path) -> Result<(), String> {
+    match fs::remove_dir_all(dir) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("{}: {e}", dir.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn remove_file(path: &Path) -> Result<(), String> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("{}: {e}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn wipe_credentials(app: &AppHandle) {
+    let proxy_user = settings::get(app).proxy.username;
+    let entries = export::BACKEND_SECRETS
+        .iter()
+        .map(|(s, a)| (s.to_string(), a.to_string()))
+        .chain(proxy_user.map(|u| (proxy::KEYCHAIN_SERVICE.to_string(), u)));
+    for (service, account) in entries {
+        if let Ok(entry) = keyring::Entry::new(&service, &account) {
+            let _ = entry.delete_credential();
+        }
+    }
+}
+
+fn wipe(app: &AppHandle, scope: ResetScope) -> Result<(), String> {
+    match scope {
+        ResetScope::Config => {
+            let dir = paths::config_dir(app)?;
+            remove_file(&dir.join("settings.json"))?;
+            for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+                let name = entry.file_name();
+                if name.to_string_lossy().starts_with("settings.corrupt-") {
+                    remove_file(&entry.path())?;
+                }
+            }
+            Ok(())
+        }
+        ResetScope::Cache => {
+            remove_dir(&app.path().app_cache_dir().map_err(|e| e.to_string())?)?;
+            remove_dir(&datapacks::root(app)?)
+        }
+        ResetScope::Database => {
+            remove_dir(&paths::engine_dir(app)?)?;
+            remove_dir(&documents::root(app)?)?;
+            remove_dir(&evidence::root(app)?)?;
+            remove_dir(&browserprofiles::root(app)?)?;
+            history::wipe(app)?;
+            cookievault::wipe(app)
+        }
+        ResetScope::Credentials => {
+            wipe_credentials(app);
+            proxypool::forget_passwords(app);
+            mailwatch::forget_password(app);
+            oauth::forget_tokens();
+            webhooks::forget_secrets(app);
+            vault::wipe(app);
+            // Sealed with the key that was just removed
+            cookievault::wipe(app)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn reset_app_data(app: AppHandle, scopes: Vec<ResetScope>) -> Result<(), String> {
+    if scopes.is_empty() {
+        return Err("choose at least one thing to reset".into());
+    }
+    audit::record(&app, Action::DataReset, json!({ "scopes": scopes }));
+    crate::kill_backend(&app);
+    // Credentials first: finding the proxy and mail accounts needs the config
+    let mut ordered = scopes;
+    ordered.sort_by_key(|s| (*s != ResetScope::Credentials, *s as u8));
+    ordered.dedup();
+    let errors: Vec<String> = ordered
+        .into_iter()
+        .filter_map(|scope| wipe(&app, scope).err())
+        .collect();
+    if !errors.is_empty() {
+        // Bring the engine back so the app stays usable
+        let _ = crate::restart_backend(&app);
+        return Err(errors.join("; "));
+    }
+    app.restart();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_is_already_removed() {
+        let dir = std::env::temp_dir().join(format!("reset-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("nested/file"), b"x").unwrap();
+        remove_file(&dir.join("nested/file")).unwrap();
+        remove_file(&dir.join("nested/file")).unwrap();
+        remove_dir(&dir).unwrap();
+        assert!(!dir.exists());
+        remove_dir(&dir).unwrap();
+    }
+}
@@ -0,0 +1,613 @@
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use std::{
+    io::{Cursor, Read},
+    sync::LazyLock,
+};
+use tauri::{
+    ipc::{InvokeBody, Request},
+    AppHandle,
+};
+use tracing::info;
+
+use crate::{pdftext, redact};
+
+// Reads a résumé in the shell, PDF or DOCX, into the engine's canonical CV
+// shape (backend/documents/cv_parser.py: contact details, summary,
+// experience, education, skills, languages, certifications, and the raw
+// text), so the CV page can show what was found straight away and hand it
+// to the engine with the upload instead of the engine parsing it again. The
+// headers, patterns and heuristics are the engine's, kept in step with it.
+const MAX_BYTES: usize = 20 * 1024 * 1024;
+const BULLETS: &[char] = &['•', '-', '·', '–', '▪', '*', '○', '◦'];
+
+const SECTIONS: &[(&str, &[&str])] = &[
+    (
+        "experience",
+        &[
+            "experiencia",
+            "experience",
+            "experiencia laboral",
+            "experiencia profesional",
+            "work experience",
+            "employment history",
+            "historial laboral",
+            "trayectoria profesional",
+        ],
+    ),
+    (
+        "education",
+        &[
+            "educación",
+            "education",
+            "formación",
+            "formación académica",
+            "estudios",
+            "academic background",
+            "titulación",
+        ],
+    ),
+    (
+        "skills",
+        &[
+            "habilidades",
+            "skills",
+            "competencias",
+            "conocimientos",
+            "tecnologías",
+            "technologies",
+            "tech stack",
+            "hard skills",
+            "soft skills",
+            "aptitudes",
+        ],
+    ),
+    (
+        "languages",
+        &["idiomas", "languages", "lenguas", "language skills"],
+    ),
+    (
+        "summary",
+        &[
+            "resumen",
+            "summary",
+            "perfil",
+            "profile",
+            "sobre mí",
+            "about me",
+            "objetivo",
+            "objective",
+            "presentación",
+        ],
+    ),
+    (
+        "certifications",
+        &[
+            "certificaciones",
+            "certifications",
+            "certificados",
+            "certificates",
+            "cursos",
+            "courses",
+            "formación complementaria",
+        ],
+    ),
+    ("projects", &["proyectos", "projects", "portfolio"]),
+];
+
+const CITIES: &[&str] = &[
+    "madrid",
+    "barcelona",
+    "valencia",
+    "sevilla",
+    "seville",
+    "bilbao",
+    "málaga",
+    "malaga",
+    "alicante",
+    "granada",
+    "murcia",
+    "palma",
+    "las palmas",
+    "santander",
+    "pamplona",
+    "san sebastián",
+    "donostia",
+    "vitoria",
+    "gasteiz",
+    "zaragoza",
+    "valladolid",
+    "córdoba",
+    "cordoba",
+    "vigo",
+    "gijón",
+    "gijon",
+    "hospitalet",
+    "badalona",
+    "terrassa",
+    "sabadell",
+    "jerez",
+    "cartagena",
+    "alcalá",
+    "almería",
+    "almeria",
+];
+
+fn insensitive(pattern: &str) -> Regex {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .unwrap()
+}
+
+static EMAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[a-zA-Z0-9._%+\-]+@[a-zA-Z0-9.\-]+\.[a-zA-Z]{2,}").unwrap());
+static PHONE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:\+34[\s\-]?)?(?:\d{3}[\s\-]?\d{3}[\s\-]?\d{3}|\d{9})").unwrap()
+});
+static LINKEDIN: LazyLock<Regex> =
+    LazyLock::new(|| insensitive(r"(?:https?://)?(?:www\.)?linkedin\.com/in/([\w\-]+)"));
+static GITHUB: LazyLock<Regex> =
+    LazyLock::new(|| insensitive(r"(?:https?://)?(?:www\.)?github\.com/([\w\-]+)"));
+static YEAR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(19|20)\d{2}\b").unwrap());
+static DATE_RANGE: LazyLock<Regex> = LazyLock::new(|| {
+    insensitive(
+        r"([\w\./ ]+\d{4})\s*[-–—]\s*([\w\./ ]+\d{4}|[Pp]resente|[Aa]ctual|[Cc]urrent|[Pp]resent|[Hh]oy)",
+    )
+});
+static PLACE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-ZÀ-Ú][a-zA-ZÀ-ú\s]+,\s*[A-ZÀ-Ú][a-zA-ZÀ-ú\s]+$").unwrap());
+static LEVEL: LazyLock<Regex> = LazyLock::new(|| {
+    insensitive(
+        r"(nativo|native|bilingüe|bilingual|avanzado|advanced|intermedio|intermediate|básico|basic|elemental|c2|c1|b2|b1|a2|a1|fluent|fluido|profesional|professional)",
+    )
+});
+static DOCX_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<(/?)w:(p|t|tab|br|cr)\b[^>]*?(/?)>").unwrap());
+
+#[derive(Debug, Default, Serialize)]
+pub struct Experience {
+    pub company: String,
+    pub title: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub bullets: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Education {
+    pub institution: String,
+    pub degree: String,
+    pub year: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Language {
+    pub language: String,
+    pub level: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Resume {
+    pub name: String,
+    pub email: String,
+    pub phone: String,
+    pub location: String,
+    pub linkedin: String,
+    pub github: String,
+    pub summary: String,
+    pub experience: Vec<Experience>,
+    pub education: Vec<Education>,
+    pub skills: Vec<String>,
+    pub languages: Vec<Language>,
+    pub certifications: Vec<String>,
+    pub raw_text: String,
+}
+
+pub fn parse(data: &[u8]) -> Result<Resume, String> {
+    if data.len() > MAX_BYTES {
+        return Err("the file is over 20 MB".into());
+    }
+    let text = if data.starts_with(b"%PDF") {
+        pdftext::text(data)?
+    } else if data.starts_with(b"PK") {
+        docx_text(data)?
+    } else {
+        return Err("only PDF and DOCX résumés can be read".into());
+    };
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let mut resume = extract(&lines, &text);
+    resume.raw_text = text;
+    info!(
+        experience = resume.experience.len(),
+        skills = resume.skills.len(),
+        "parsed résumé"
+    );
+    Ok(resume)
+}
+
+// word/document.xml's paragraphs, a line each
+fn docx_text(data: &[u8]) -> Result<String, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).map_err(|e| e.to_string())?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|_| "not a Word document".to_string())?
+        .read_to_string(&mut xml)
+        .map_err(|e| e.to_string())?;
+    let mut text = String::new();
+    let mut in_text = false;
+    let mut at = 0;
+    for c in DOCX_TAG.captures_iter(&xml) {
+        let tag = c.get(0).unwrap();
+        if in_text {
+            text.push_str(&unescape(&xml[at..tag.start()]));
+        }
+        at = tag.end();
+        let closing = &c[1] == "/";
+        let empty = &c[3] == "/";
+        match &c[2] {
+            "t" => in_text = !closing && !empty,
+            "p" if closing => text.push('\n'),
+            "tab" => text.push('\t'),
+            "br" | "cr" => text.push('\n'),
+            _ => {}
+        }
+    }
+    if text.trim().is_empty() {
+        return Err("the document has no text".into());
+    }
+    Ok(text)
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn extract(lines: &[&str], text: &str) -> Resume {
+    let sections = split_sections(lines);
+    let section = |key: &str| {
+        sections
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .map(|(_, lines)| lines.as_slice())
+    };
+    let email = EMAIL
+        .find(text)
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_default();
+    let phone = phone(text);
+    let location = location(lines, &email, &phone);
+    Resume {
+        name: name(lines),
+        linkedin: LINKEDIN
+            .captures(text)
+            .map(|c| format!("https://linkedin.com/in/{}", &c[1]))
+            .unwrap_or_default(),
+        github: GITHUB
+            .captures(text)
+            .filter(|c| {
+                !matches!(
+                    c[1].to_lowercase().as_str(),
+                    "features" | "pricing" | "about" | "login" | "signup"
+                )
+            })
+            .map(|c| format!("https://github.com/{}", &c[1]))
+            .unwrap_or_default(),
+        summary: section("summary")
+            .map(|l| l.join(" ").trim().to_string())
+            .unwrap_or_default(),
+        experience: section("experience").map(experience).unwrap_or_default(),
+        education: section("education").map(education).unwrap_or_default(),
+        skills: section("skills").map(skills).unwrap_or_default(),
+        languages: section("languages").map(languages).unwrap_or_default(),
+        certifications: section("certifications")
+            .map(|l| {
+                l.iter()
+                    .filter(|l| l.chars().count() > 3)
+                    .map(|l| l.to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        email,
+        phone,
+        location,
+        raw_text: String::new(),
+    }
+}
+
+// Lines under each header; a header seen again starts its section over
+fn split_sections<'a>(lines: &[&'a str]) -> Vec<(&'static str, Vec<&'a str>)> {
+    let mut sections: Vec<(&'static str, Vec<&'a str>)> = Vec::new();
+    for line in lines {
+        match header(line) {
+            Some(key) => sections.push((key, Vec::new())),
+            None => {
+                if let Some((_, body)) = sections.last_mut() {
+                    body.push(line);
+                }
+            }
+        }
+    }
+    sections
+}
+
+fn header(line: &str) -> Option<&'static str> {
+    let clean = line.trim().to_lowercase();
+    let clean = clean.trim_end_matches(':');
+    if clean.split_whitespace().count() > 5 {
+        return None;
+    }
+    SECTIONS
+        .iter()
+        .find(|(_, names)| names.contains(&clean))
+        .map(|(key, _)| *key)
+}
+
+fn phone(text: &str) -> String {
+    let Some(m) = PHONE.find(text) else {
+        return String::new();
+    };
+    let digits: String = m
+        .as_str()
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect();
+    if digits.len() == 9 && digits.starts_with(['6', '7', '8', '9']) {
+        format!("+34 {} {} {}", &digits[..3], &digits[3..6], &digits[6..])
+    } else {
+        m.as_str().to_string()
+    }
+}
+
+// The first short, capitalised line without digits or contact details
+fn name(lines: &[&str]) -> String {
+    for line in lines.iter().take(5) {
+        if EMAIL.is_match(line) || PHONE.is_match(line) {
+            continue;
+        }
+        let lower = line.to_lowercase();
+        if lower.contains("linkedin") || lower.contains("github") {
+            continue;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if (2..=5).contains(&words.len())
+            && !line.chars().any(|c| c.is_ascii_digit())
+            && words
+                .iter()
+                .all(|w| w.chars().next().is_some_and(char::is_uppercase))
+        {
+            return line.to_string();
+        }
+    }
+    lines.first().map(|l| l.to_string()).unwrap_or_default()
+}
+
+fn location(lines: &[&str], email: &str, phone: &str) -> String {
+    for line in lines.iter().take(15) {
+        let lower = line.to_lowercase();
+        if (!email.is_empty() && lower.contains(&email.to_lowercase()))
+            || (!phone.is_empty() && lower.contains(phone))
+        {
+            continue;
+        }
+        if lower.contains("linkedin") || lower.contains("github") || lower.contains('@') {
+            continue;
+        }
+        if CITIES.iter().any(|c| lower.contains(c)) || PLACE.is_match(line) {
+            return line.to_string();
+        }
+    }
+    String::new()
+}
+
+fn is_bullet(line: &str) -> bool {
+    line.starts_with(BULLETS)
+}
+
+fn experience(lines: &[&str]) -> Vec<Experience> {
+    let mut entries = Vec::new();
+    let mut current: Option<Experience> = None;
+    for line in lines {
+        // A date range starts an entry
+        if let Some(c) = DATE_RANGE.captures(line) {
+            entries.extend(current.take());
+            current = Some(Experience {
+                start_date: c[1].trim().to_string(),
+                end_date: c[2].trim().to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let entry = current.get_or_insert_with(Experience::default);
+        let bullet = is_bullet(line);
+        let clean = line.trim_start_matches(BULLETS).trim();
+        let len = clean.chars().count();
+        if entry.company.is_empty() && !bullet && len < 80 {
+            entry.company = clean.to_string();
+        } else if entry.title.is_empty() && !bullet && len < 100 {
+            entry.title = clean.to_string();
+        } else if !clean.is_empty() {
+            entry.bullets.push(clean.to_string());
+        }
+    }
+    if let Some(entry) = current {
+        if !entry.company.is_empty() || !entry.title.is_empty() || !entry.bullets.is_empty() {
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+fn education(lines: &[&str]) -> Vec<Education> {
+    let mut entries = Vec::new();
+    let mut current: Option<Education> = None;
+    for line in lines {
+        let clean = line.trim_start_matches(&BULLETS[..6]).trim();
+        if clean.is_empty() {
+            continue;
+        }
+        let short = clean.chars().count() < 120;
+        match YEAR.find(line) {
+            Some(year) if !line.starts_with(&BULLETS[..6]) => {
+                entries.extend(current.take());
+                current = Some(Education {
+                    degree: YEAR
+                        .replace_all(line, "")
+                        .trim_matches(|c: char| c.is_whitespace() || "-–—|·•/".contains(c))
+                        .to_string(),
+                    year: year.as_str().to_string(),
+                    ..Default::default()
+                });
+            }
+            _ => match &mut current {
+                Some(entry) if entry.institution.is_empty() && short => {
+                    entry.institution = clean.to_string();
+                }
+                Some(_) => {}
+                None if short => {
+                    current = Some(Education {
+                        degree: clean.to_string(),
+                        ..Default::default()
+                    })
+                }
+                None => {}
+            },
+        }
+    }
+    entries.extend(current.filter(|e| !e.institution.is_empty() || !e.degree.is_empty()));
+    entries
+}
+
+fn skills(lines: &[&str]) -> Vec<String> {
+    let mut skills: Vec<String> = Vec::new();
+    for line in lines {
+        let clean = line.trim_start_matches(BULLETS).trim();
+        if clean.is_empty() {
+            continue;
+        }
+        let separator = if clean.contains(',') {
+            Some(',')
+        } else if clean.contains('|') {
+            Some('|')
+        } else if clean.contains('/') && clean.chars().count() < 80 {
+            Some('/')
+        } else {
+            None
+        };
+        match separator {
+            Some(sep) => skills.extend(
+                clean
+                    .split(sep)
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(String::from),
+            ),
+            None if clean.chars().count() < 60 => skills.push(clean.to_string()),
+            None => {}
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    skills.retain(|s| seen.insert(s.to_lowercase()));
+    skills
+}
+
+fn languages(lines: &[&str]) -> Vec<Language> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let clean = line.trim_start_matches(&BULLETS[..6]).trim();
+            if clean.is_empty() {
+                return None;
+            }
+            match LEVEL.find(clean) {
+                Some(level) => {
+                    let language = LEVEL
+                        .replace_all(clean, "")
+                        .trim_matches(|c: char| c.is_whitespace() || "-–:,|".contains(c))
+                        .to_string();
+                    (!language.is_empty()).then(|| Language {
+                        language,
+                        level: level.as_str().to_string(),
+                    })
+                }
+                None => (clean.chars().count() < 40).then(|| Language {
+                    language: clean.to_string(),
+                    level: String::new(),
+                }),
+            }
+        })
+        .collect()
+}
+
+// The file's bytes as the raw request body: `invoke("parse_resume", bytes)`.
+#[tauri::command]
+pub async fn parse_resume(app: AppHandle, request: Request<'_>) -> Result<Resume, String> {
+    let InvokeBody::Raw(data) = request.body() else {
+        return Err("send the file's bytes".into());
+    };
+    let data = data.clone();
+    let resume = tauri::async_runtime::spawn_blocking(move || parse(&data))
+        .await
+        .map_err(|e| e.to_string())??;
+    redact::add_names(&app, [resume.name.clone()]);
+    Ok(resume)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn docx(body: &str) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("word/document.xml", SimpleFileOptions::default())
+            .unwrap();
+        write!(
+            zip,
+            r#"<?xml version="1.0"?><w:document xmlns:w="urn:w"><w:body>{body}</w:body></w:document>"#
+        )
+        .unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn docx_paragraphs_tabs_and_breaks() {
+        let data = docx(concat!(
+            r#"<w:p><w:r><w:t>Jane Doe</w:t></w:r></w:p>"#,
+            r#"<w:p><w:r><w:t xml:space="preserve">Madrid</w:t><w:tab/><w:t>jane@example.com</w:t></w:r></w:p>"#,
+            r#"<w:p><w:r><w:t>R&amp;D lead</w:t><w:br/><w:t>2019 &lt;&gt; 2023</w:t></w:r></w:p>"#,
+        ));
+        assert_eq!(
+            docx_text(&data).unwrap(),
+            "Jane Doe\nMadrid\tjane@example.com\nR&D lead\n2019 <> 2023\n"
+        );
+    }
+
+    #[test]
+    fn docx_without_text() {
+        assert!(docx_text(&docx("<w:p><w:r><w:tab/></w:r></w:p>")).is_err());
+        assert!(parse(b"plain text").is_err());
+    }
+
+    #[test]
+    fn contact_details() {
+        let data = docx(concat!(
+            "<w:p><w:r><w:t>Jane Doe</w:t></w:r></w:p>",
+            "<w:p><w:r><w:t>jane@example.com</w:t></w:r></w:p>",
+        ));
+        let resume = parse(&data).unwrap();
+        assert_eq!(resume.name, "Jane Doe");
+        assert_eq!(resume.email, "jane@example.com");
+    }
+}
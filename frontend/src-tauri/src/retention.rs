@@ -0,0 +1,490 @@
+use chrono::{DateTime, Duration, Local, Months, Utc};
+use rusqlite::{params_from_iter, Connection};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, sync::Mutex, thread};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    audit::{self, Action},
+    backend::{self, Failure},
+    evidence,
+    history::{self, HISTORY_CHANGED},
+    settings::{self, RetentionSettings},
+};
+
+// Old data going on a schedule the user sets: applications that went
+// nowhere (rejected by default) once untouched for `applications_months`,
+// and screenshots after `screenshots_days`, both the engine's and those kept
+// as evidence. The engine removes its own (POST /api/retention/purge, with
+// the application's events, CV records and files); the shell then removes
+// the history's copy with its tags, notes, calendar and evidence. The
+// schedule is off until turned on, then runs at most once a day; the preview
+// lists the same things without deleting any. Applications are only deleted
+// with the engine there, or its next sync would bring them back.
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+const MIN_GAP: Duration = Duration::hours(24);
+const PURGE: &str = "/api/retention/purge";
+// Statuses an application doesn't leave; only these can be set to go
+pub const CLOSED_STATUSES: &[&str] = &["rejected", "withdrawn", "expired"];
+// Tables holding an application's rows besides `evidence`, in deletion
+// order: calendar_events before the search index its trigger refills
+const APPLICATION_TABLES: &[(&str, &str)] = &[
+    ("calendar_events", "application_id"),
+    ("status_changes", "application_id"),
+    ("artifacts", "application_id"),
+    ("pipeline_cards", "application_id"),
+    ("application_tags", "application_id"),
+    ("duplicate_index", "application_id"),
+    ("duplicate_flags", "application_id"),
+    ("application_search", "rowid"),
+    ("applications", "id"),
+];
+
+static RUNNING: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize)]
+pub struct PrunedApplication {
+    pub id: i64,
+    pub company: String,
+    pub role: Option<String>,
+    pub status: String,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrunedScreenshot {
+    pub application_id: i64,
+    // "engine" or "evidence"
+    pub source: &'static str,
+    pub path: Option<String>,
+    pub bytes: u64,
+    pub taken_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub applications: Vec<PrunedApplication>,
+    pub screenshots: Vec<PrunedScreenshot>,
+    // Screenshots and the applications' evidence together
+    pub bytes: u64,
+    // Without it, only the history's side is listed
+    pub engine_reachable: bool,
+}
+
+fn cutoffs(
+    cfg: &RetentionSettings,
+    now: DateTime<Utc>,
+) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let applications = (cfg.applications_months > 0)
+        .then(|| now.checked_sub_months(Months::new(cfg.applications_months)))
+        .flatten();
+    let screenshots =
+        (cfg.screenshots_days > 0).then(|| now - Duration::days(cfg.screenshots_days.into()));
+    (applications, screenshots)
+}
+
+// The history's applications past the cutoff; ones the engine never had
+// (imported) as well.
+fn stale(
+    db: &Connection,
+    cfg: &RetentionSettings,
+    cutoff: DateTime<Utc>,
+) -> Result<Vec<PrunedApplication>, String> {
+    if cfg.statuses.is_empty() {
+        return Ok(Vec::new());
+    }
+    let marks = vec!["?"; cfg.statuses.len()].join(", ");
+    let mut statement = db
+        .prepare(&format!(
+            "SELECT id, company, role, status, COALESCE(updated_at, recorded_at)
+             FROM applications WHERE status IN ({marks})"
+        ))
+        .map_err(|e| e.to_string())?;
+    let cutoff = cutoff.with_timezone(&Local).naive_local();
+    let rows = statement
+        .query_map(params_from_iter(&cfg.statuses), |r| {
+            Ok(PrunedApplication {
+                id: r.get(0)?,
+                company: r.get(1)?,
+                role: r.get(2)?,
+                status: r.get(3)?,
+                updated_at: r.get(4)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .filter(|a| history::local(&a.updated_at).is_some_and(|t| t < cutoff))
+        .collect())
+}
+
+// The engine counts whole days back from now; those up to the same cutoffs,
+// so both sides remove the same applications.
+fn engine_request(cfg: &RetentionSettings, now: DateTime<Utc>, dry_run: bool) -> Value {
+    let (applications, screenshots) = cutoffs(cfg, now);
+    let days = |cutoff: Option<DateTime<Utc>>| cutoff.map_or(0, |c| (now - c).num_days());
+    json!({
+        "dry_run": dry_run,
+        "statuses": cfg.statuses,
+        "applications_days": days(applications),
+        "screenshots_days": days(screenshots),
+    })
+}
+
+fn remove_applications(app: &AppHandle, db: &mut Connection, ids: &[i64]) -> Result<(), String> {
+    for id in ids {
+        let kept: Vec<i64> = evidence::of_application(db, *id)?
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        evidence::forget(app, db, &kept)?;
+    }
+    delete_rows(db, ids)
+}
+
+// The applications' rows in every table but `evidence`, in one transaction.
+fn delete_rows(db: &mut Connection, ids: &[i64]) -> Result<(), String> {
+    let tx = db.transaction().map_err(|e| e.to_string())?;
+    for id in ids {
+        for (table, column) in APPLICATION_TABLES {
+            tx.execute(&format!("DELETE FROM {table} WHERE {column} = ?1"), [id])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+fn run(app: &AppHandle, dry_run: bool) -> Result<RetentionReport, String> {
+    let _guard = RUNNING.lock().unwrap();
+    let cfg = settings::get(app).retention;
+    let now = Utc::now();
+    let (applications_cutoff, screenshots_cutoff) = cutoffs(&cfg, now);
+    let mut db = history::open(app)?;
+
+    // The engine's side first, so the history isn't emptied of what a sync
+    // would bring back
+    let (engine, engine_reachable) =
+        match backend::send_json("POST", PURGE, Some(&engine_request(&cfg, now, dry_run))) {
+            Ok(answer) => (answer, true),
+            Err(Failure::Unreachable(_)) if dry_run => (Value::Null, false),
+            Err(Failure::Unreachable(e)) => return Err(format!("the engine isn't running: {e}")),
+            Err(Failure::Refused(_, detail)) => return Err(detail),
+        };
+
+    let mut applications: BTreeMap<i64, PrunedApplication> = BTreeMap::new();
+    for item in engine["applications"].as_array().into_iter().flatten() {
+        let Some(id) = item["id"].as_i64() else {
+            continue;
+        };
+        applications.insert(
+            id,
+            PrunedApplication {
+                id,
+                company: item["company"].as_str().unwrap_or_default().to_string(),
+                role: None,
+                status: item["status"].as_str().unwrap_or_default().to_string(),
+                updated_at: item["updated_at"].as_str().map(String::from),
+            },
+        );
+    }
+    if let Some(cutoff) = applications_cutoff {
+        for application in stale(&db, &cfg, cutoff)? {
+            applications.insert(application.id, application);
+        }
+    }
+    let ids: Vec<i64> = applications.keys().copied().collect();
+
+    let mut screenshots = Vec::new();
+    for item in engine["screenshots"].as_array().into_iter().flatten() {
+        screenshots.push(PrunedScreenshot {
+            application_id: item["application_id"].as_i64().unwrap_or_default(),
+            source: "engine",
+            path: item["path"].as_str().map(String::from),
+            bytes: item["bytes"].as_u64().unwrap_or_default(),
+            taken_at: item["modified_at"].as_str().map(String::from),
+        });
+    }
+    let old_evidence = match screenshots_cutoff {
+        Some(cutoff) => evidence::screenshots_before(&db, cutoff)?
+            .into_iter()
+            .filter(|e| !ids.contains(&e.application_id))
+            .collect(),
+        None => Vec::new(),
+    };
+    for e in &old_evidence {
+        screenshots.push(PrunedScreenshot {
+            application_id: e.application_id,
+            source: "evidence",
+            path: None,
+            bytes: e.size,
+            taken_at: Some(e.captured_at.clone()),
+        });
+    }
+    let mut bytes: u64 = screenshots.iter().map(|s| s.bytes).sum();
+    for id in &ids {
+        bytes += evidence::of_application(&db, *id)?
+            .iter()
+            .map(|e| e.size)
+            .sum::<u64>();
+    }
+
+    if !dry_run {
+        let old: Vec<i64> = old_evidence.iter().map(|e| e.id).collect();
+        evidence::forget(app, &db, &old)?;
+        for path in screenshots.iter().filter_map(|s| s.path.as_deref()) {
+            db.execute(
+                "DELETE FROM artifacts WHERE kind = 'form_screenshot' AND location = ?1",
+                [path],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        remove_applications(app, &mut db, &ids)?;
+        if !ids.is_empty() || !screenshots.is_empty() {
+            info!(
+                applications = ids.len(),
+                screenshots = screenshots.len(),
+                "old data removed"
+            );
+            audit::record(
+                app,
+                Action::DataPruned,
+                json!({ "applications": ids.len(), "screenshots": screenshots.len(), "bytes": bytes }),
+            );
+            let _ = app.emit(HISTORY_CHANGED, ());
+        }
+        settings::update(app, |s| {
+            s.retention.last_run_at = Some(Utc::now().to_rfc3339());
+        })?;
+    }
+    Ok(RetentionReport {
+        dry_run,
+        applications: applications.into_values().collect(),
+        screenshots,
+        bytes,
+        engine_reachable,
+    })
+}
+
+fn due(cfg: &RetentionSettings, now: DateTime<Utc>) -> bool {
+    cfg.enabled
+        && cfg
+            .last_run_at
+            .as_deref()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .is_none_or(|t| now - t.with_timezone(&Utc) >= MIN_GAP)
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+        if due(&settings::get(&app).retention, Utc::now()) {
+            if let Err(e) = run(&app, false) {
+                warn!("old data not removed: {e}");
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_retention_settings(app: AppHandle) -> RetentionSettings {
+    settings::get(&app).retention
+}
+
+// 0 for either keeps that for good.
+#[tauri::command]
+pub fn set_retention_settings(
+    app: AppHandle,
+    enabled: bool,
+    applications_months: u32,
+    statuses: Vec<String>,
+    screenshots_days: u32,
+) -> Result<RetentionSettings, String> {
+    if let Some(status) = statuses
+        .iter()
+        .find(|s| !CLOSED_STATUSES.contains(&s.as_str()))
+    {
+        return Err(format!(
+            "only {} applications can be removed, not {status}",
+            CLOSED_STATUSES.join(", ")
+        ));
+    }
+    Ok(settings::update(&app, |s| {
+        s.retention.enabled = enabled;
+        s.retention.applications_months = applications_months;
+        s.retention.statuses = statuses;
+        s.retention.screenshots_days = screenshots_days;
+    })?
+    .retention)
+}
+
+// What a run would remove now, removing nothing.
+#[tauri::command]
+pub async fn preview_retention(app: AppHandle) -> Result<RetentionReport, String> {
+    tauri::async_runtime::spawn_blocking(move || run(&app, true))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn run_retention_now(app: AppHandle) -> Result<RetentionReport, String> {
+    tauri::async_runtime::spawn_blocking(move || run(&app, false))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg() -> RetentionSettings {
+        RetentionSettings {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    fn at(text: &str) -> DateTime<Utc> {
+        text.parse().unwrap()
+    }
+
+    fn db() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (id, status, updated_at) in [
+            (1, "rejected", Some("2025-01-10T12:00:00Z")),
+            (2, "rejected", Some("2026-09-01T12:00:00Z")),
+            (3, "applied", Some("2025-01-10T12:00:00Z")),
+            (4, "withdrawn", Some("2025-01-10T12:00:00Z")),
+            // Only ever recorded
+            (5, "rejected", None),
+        ] {
+            db.execute(
+                "INSERT INTO applications (id, company, status, updated_at, recorded_at)
+                 VALUES (?1, 'Acme', ?2, ?3, '2025-02-01T12:00:00Z')",
+                rusqlite::params![id, status, updated_at],
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    fn ids(found: Vec<PrunedApplication>) -> Vec<i64> {
+        let mut ids: Vec<i64> = found.into_iter().map(|a| a.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn cutoffs_by_months_and_days() {
+        let now = at("2026-10-15T12:00:00Z");
+        assert_eq!(
+            cutoffs(&cfg(), now),
+            (
+                Some(at("2025-10-15T12:00:00Z")),
+                Some(at("2026-07-17T12:00:00Z"))
+            )
+        );
+        let keep = RetentionSettings {
+            applications_months: 0,
+            screenshots_days: 0,
+            ..cfg()
+        };
+        assert_eq!(cutoffs(&keep, now), (None, None));
+    }
+
+    #[test]
+    fn due_once_a_day_when_on() {
+        let now = at("2026-10-15T12:00:00Z");
+        let mut cfg = cfg();
+        assert!(due(&cfg, now));
+        cfg.last_run_at = Some("2026-10-14T13:00:00+00:00".into());
+        assert!(!due(&cfg, now));
+        cfg.last_run_at = Some("2026-10-14T12:00:00Z".into());
+        assert!(due(&cfg, now));
+        cfg.last_run_at = Some("garbled".into());
+        assert!(due(&cfg, now));
+        cfg.enabled = false;
+        assert!(!due(&cfg, now));
+    }
+
+    #[test]
+    fn stale_applications_in_the_chosen_statuses() {
+        let db = db();
+        let cutoff = at("2025-10-15T12:00:00Z");
+        assert_eq!(ids(stale(&db, &cfg(), cutoff).unwrap()), [1, 5]);
+        let closed = RetentionSettings {
+            statuses: vec!["rejected".into(), "withdrawn".into()],
+            ..cfg()
+        };
+        assert_eq!(ids(stale(&db, &closed, cutoff).unwrap()), [1, 4, 5]);
+        let none = RetentionSettings {
+            statuses: Vec::new(),
+            ..cfg()
+        };
+        assert!(stale(&db, &none, cutoff).unwrap().is_empty());
+    }
+
+    #[test]
+    fn engine_days_reach_the_same_cutoffs() {
+        for now in ["2026-10-15T12:00:00Z", "2024-03-31T08:00:00Z"] {
+            let now = at(now);
+            let request = engine_request(&cfg(), now, true);
+            let (applications, screenshots) = cutoffs(&cfg(), now);
+            let back = |key: &str| now - Duration::days(request[key].as_i64().unwrap());
+            assert_eq!(Some(back("applications_days")), applications);
+            assert_eq!(Some(back("screenshots_days")), screenshots);
+        }
+        let request = engine_request(&cfg(), at("2026-10-15T12:00:00Z"), true);
+        assert_eq!(
+            request,
+            json!({
+                "dry_run": true,
+                "statuses": ["rejected"],
+                "applications_days": 365,
+                "screenshots_days": 90,
+            })
+        );
+        let keep = RetentionSettings {
+            applications_months: 0,
+            ..cfg()
+        };
+        assert_eq!(
+            engine_request(&keep, at("2026-10-15T12:00:00Z"), false)["applications_days"],
+            0
+        );
+    }
+
+    #[test]
+    fn every_row_of_an_application_goes() {
+        let mut db = db();
+        db.execute_batch(
+            "INSERT INTO status_changes (application_id, status, at) VALUES (1, 'rejected', '');
+             INSERT INTO artifacts (application_id, kind, location, recorded_at)
+                VALUES (1, 'cv', 'cv.pdf', '');
+             INSERT INTO pipeline_cards (application_id, notes, updated_at) VALUES (1, 'call back', '');
+             INSERT INTO application_tags (application_id, tag_id) VALUES (1, 1), (2, 1);
+             INSERT INTO calendar_events (application_id, kind, starts_at, duration_minutes,
+                created_at, updated_at) VALUES (1, 'follow_up', '2026-10-20T09:00:00Z', 15, '', '');",
+        )
+        .unwrap();
+        delete_rows(&mut db, &[1]).unwrap();
+        for (table, column) in APPLICATION_TABLES {
+            let left: i64 = db
+                .query_row(
+                    &format!("SELECT COUNT(*) FROM {table} WHERE {column} = 1"),
+                    [],
+                    |r| r.get(0),
+                )
+                .unwrap();
+            assert_eq!(left, 0, "{table}");
+        }
+        let tagged: i64 = db
+            .query_row("SELECT COUNT(*) FROM application_tags", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(tagged, 1);
+    }
+}
@@ -0,0 +1,431 @@
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, sync::LazyLock, sync::Mutex, thread, time::Duration};
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::{net, paths};
+
+// Salary text as the boards write it ("£55–65k", "$40/hr", "1.500 € brutos
+// al mes, 14 pagas") read into a range with its currency and period, and
+// the same range a year, as the history keeps it for each application. A
+// figure without a currency is taken as euros, as on the Spanish boards, and
+// without a period it's judged by size: up to 200 an hour, up to 10,000 a
+// month, a year above that. Conversion uses the ECB's daily reference rates,
+// fetched twice a day and kept in `exchange-rates.json` for when offline;
+// with no rates yet there's no conversion, just the original currency.
+pub const DEFAULT_CURRENCY: &str = "EUR";
+const RATES_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml";
+const RATES_FILE: &str = "exchange-rates.json";
+const REFRESH: Duration = Duration::from_secs(12 * 60 * 60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const HOURS_A_YEAR: f64 = 40.0 * 52.0;
+const DAYS_A_YEAR: f64 = 5.0 * 52.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Converted {
+    pub currency: String,
+    pub annual_min: Option<f64>,
+    pub annual_max: Option<f64>,
+    // The ECB's date for the rates used
+    pub rates_date: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Salary {
+    // As written, in `currency` per `period`; one may be missing ("hasta
+    // 30.000 €", "from £40k")
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub currency: String,
+    pub period: Period,
+    // Payments a year for a monthly figure; 14 on many Spanish contracts
+    pub payments: u32,
+    pub annual_min: Option<f64>,
+    pub annual_max: Option<f64>,
+    // In the currency asked for, where it isn't the posting's
+    pub converted: Option<Converted>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Rates {
+    // Per euro
+    rates: BTreeMap<String, f64>,
+    date: String,
+    fetched_at: DateTime<Utc>,
+}
+
+static RATES: Mutex<Option<Rates>> = Mutex::new(None);
+
+fn insensitive(pattern: &str) -> Regex {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .unwrap()
+}
+
+// 55 / 55.5k / 40,000 / 1.500,50 / 40 000 / 30 mil
+static NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    insensitive(r"(\d{1,3}(?:[.,\u{a0}\u{202f} ]\d{3})+|\d+)(?:[.,](\d{1,2}))?(?:\s*(k|mil)\b)?")
+});
+static RANGE_SEPARATOR: LazyLock<Regex> =
+    LazyLock::new(|| insensitive(r"^\s*(?:-|–|—|to|a|y|and|hasta)\s*$"));
+static PAYMENTS: LazyLock<Regex> = LazyLock::new(|| insensitive(r"\b(1[2-6])\s*pagas\b"));
+static FROM: LazyLock<Regex> =
+    LazyLock::new(|| insensitive(r"\b(?:desde|from|m[ií]n(?:imum|imo|\.)?|a partir de)\s*$"));
+static UP_TO: LazyLock<Regex> =
+    LazyLock::new(|| insensitive(r"\b(?:hasta|up to|max(?:imum|imo|\.)?)\s*$"));
+static PERIODS: LazyLock<Vec<(Period, Regex)>> = LazyLock::new(|| {
+    vec![
+        (
+            Period::Hour,
+            insensitive(
+                r"/\s*h(?:r|our|ora)?\b|\bper hour\b|\ban hour\b|\bhourly\b|\bpor hora\b|\bla hora\b|\bp/h\b",
+            ),
+        ),
+        (
+            Period::Day,
+            insensitive(
+                r"/\s*(?:day|d[ií]a)\b|\bper day\b|\ba day\b|\bdaily\b|\bal d[ií]a\b|\bpor d[ií]a\b|\bdiari[oa]\b|\bp/d\b",
+            ),
+        ),
+        (
+            Period::Week,
+            insensitive(
+                r"/\s*(?:week|wk|semana)\b|\bper week\b|\ba week\b|\bweekly\b|\bsemanal(?:es)?\b|\bla semana\b|\bpor semana\b|\bp/w\b",
+            ),
+        ),
+        (
+            Period::Month,
+            insensitive(
+                r"/\s*(?:month|mo|mes)\b|\bper month\b|\ba month\b|\bmonthly\b|\bmensual(?:es)?\b|\bal mes\b|\bpor mes\b|\bp\.?c?m\b|\bpagas\b",
+            ),
+        ),
+        (
+            Period::Year,
+            insensitive(
+                r"/\s*(?:year|yr|a[ñn]o)\b|\bper (?:year|annum)\b|\ba year\b|\bannual(?:ly)?\b|\byearly\b|\banual(?:es)?\b|\bal a[ñn]o\b|\bpor a[ñn]o\b|\bp\.?a\b|\bb/a\b",
+            ),
+        ),
+    ]
+});
+// Codes first, so "US$" is dollars and "CHF" isn't read as anything else
+static CURRENCIES: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    [
+        ("USD", r"US\$|\bUSD\b|\bdollars?\b|\bd[oó]lar(?:es)?\b"),
+        ("CAD", r"CA?\$|\bCAD\b"),
+        ("AUD", r"AU?\$|\bAUD\b"),
+        ("BRL", r"R\$|\bBRL\b"),
+        ("MXN", r"MX\$|\bMXN\b"),
+        ("GBP", r"£|\bGBP\b|\bpounds?\b|\blibras?\b"),
+        ("EUR", r"€|\bEUR\b|\beuros?\b"),
+        ("CHF", r"\bCHF\b"),
+        ("SEK", r"\bSEK\b"),
+        ("NOK", r"\bNOK\b"),
+        ("DKK", r"\bDKK\b"),
+        ("PLN", r"\bPLN\b|zł"),
+        ("CZK", r"\bCZK\b|Kč"),
+        ("JPY", r"¥|\bJPY\b"),
+        ("INR", r"₹|\bINR\b"),
+        ("USD", r"\$"),
+    ]
+    .into_iter()
+    .map(|(code, pattern)| (code, insensitive(pattern)))
+    .collect()
+});
+
+struct Figure {
+    value: f64,
+    thousands: bool,
+    start: usize,
+    end: usize,
+}
+
+fn figures(text: &str) -> Vec<Figure> {
+    NUMBER
+        .captures_iter(text)
+        .filter(|c| {
+            // "14 pagas" is a count, not money
+            !text[c.get(0).unwrap().end()..]
+                .trim_start()
+                .to_lowercase()
+                .starts_with("pagas")
+        })
+        .filter_map(|c| {
+            let whole: String = c[1].chars().filter(char::is_ascii_digit).collect();
+            let mut value: f64 = whole.parse().ok()?;
+            if let Some(decimals) = c.get(2) {
+                value += format!("0.{}", decimals.as_str()).parse::<f64>().ok()?;
+            }
+            let thousands = c.get(3).is_some();
+            if thousands {
+                value *= 1000.0;
+            }
+            let m = c.get(0).unwrap();
+            Some(Figure {
+                value,
+                thousands,
+                start: m.start(),
+                end: m.end(),
+            })
+        })
+        .filter(|f| f.value > 0.0)
+        .collect()
+}
+
+fn currency(text: &str) -> Option<&'static str> {
+    CURRENCIES
+        .iter()
+        .find(|(_, re)| re.is_match(text))
+        .map(|(code, _)| *code)
+}
+
+fn annual(value: f64, period: Period, payments: u32) -> f64 {
+    let a_year = match period {
+        Period::Hour => HOURS_A_YEAR,
+        Period::Day => DAYS_A_YEAR,
+        Period::Week => 52.0,
+        Period::Month => f64::from(payments),
+        Period::Year => 1.0,
+    };
+    (value * a_year).round()
+}
+
+// None when the text has no amount in it.
+pub fn parse(text: &str) -> Option<Salary> {
+    let figures = figures(text);
+    let first = figures.first()?;
+    let mut min = Some(first.value);
+    let mut max = None;
+    if let Some(second) = figures.get(1) {
+        let between = &text[first.end..second.start];
+        let stripped = CURRENCIES.iter().fold(between.to_string(), |s, (_, re)| {
+            re.replace_all(&s, "").into_owned()
+        });
+        if RANGE_SEPARATOR.is_match(&stripped) {
+            // "55–65k", "entre 30 y 35 mil": the unit is written once
+            let mut low = first.value;
+            if second.thousands && !first.thousands && low * 1000.0 <= second.value {
+                low *= 1000.0;
+            }
+            min = Some(low);
+            max = Some(second.value);
+        }
+    }
+    if max.is_none() {
+        let before = &text[..first.start];
+        let before = CURRENCIES.iter().fold(before.to_string(), |s, (_, re)| {
+            re.replace_all(&s, "").into_owned()
+        });
+        if UP_TO.is_match(&before) {
+            (min, max) = (None, min);
+        } else if !FROM.is_match(&before) {
+            max = min;
+        }
+    }
+    let top = max.or(min)?;
+    let period = PERIODS
+        .iter()
+        .filter_map(|(period, re)| re.find(text).map(|m| (m.start(), *period)))
+        .min_by_key(|(at, _)| *at)
+        .map(|(_, period)| period)
+        .unwrap_or(match top {
+            t if t <= 200.0 => Period::Hour,
+            t if t <= 10_000.0 => Period::Month,
+            _ => Period::Year,
+        });
+    let payments = PAYMENTS
+        .captures(text)
+        .and_then(|c| c[1].parse().ok())
+        .unwrap_or(12);
+    Some(Salary {
+        min,
+        max,
+        currency: currency(text).unwrap_or(DEFAULT_CURRENCY).to_string(),
+        period,
+        payments,
+        annual_min: min.map(|v| annual(v, period, payments)),
+        annual_max: max.map(|v| annual(v, period, payments)),
+        converted: None,
+    })
+}
+
+// `parse`, with the yearly figures also in `target` when the rates allow.
+pub fn normalize(text: &str, target: Option<&str>) -> Option<Salary> {
+    let mut salary = parse(text)?;
+    let Some(target) = target.map(str::to_uppercase) else {
+        return Some(salary);
+    };
+    if target == salary.currency {
+        return Some(salary);
+    }
+    if let Some(rates) = RATES.lock().unwrap().as_ref() {
+        let per_euro = |code: &str| match code {
+            "EUR" => Some(1.0),
+            code => rates.rates.get(code).copied(),
+        };
+        if let (Some(from), Some(to)) = (per_euro(&salary.currency), per_euro(&target)) {
+            let convert = |v: f64| (v / from * to).round();
+            salary.converted = Some(Converted {
+                currency: target,
+                annual_min: salary.annual_min.map(convert),
+                annual_max: salary.annual_max.map(convert),
+                rates_date: rates.date.clone(),
+            });
+        }
+    }
+    Some(salary)
+}
+
+// --- Rates ---
+
+fn fetch_rates() -> Result<Rates, String> {
+    static RATE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"currency=['"]([A-Z]{3})['"]\s+rate=['"]([0-9.]+)['"]"#).unwrap()
+    });
+    static DATE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"time=['"](\d{4}-\d{2}-\d{2})['"]"#).unwrap());
+    let xml = net::client(Duration::from_secs(20))?
+        .get(RATES_URL)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.text())
+        .map_err(|e| e.to_string())?;
+    let rates: BTreeMap<String, f64> = RATE
+        .captures_iter(&xml)
+        .filter_map(|c| Some((c[1].to_string(), c[2].parse().ok()?)))
+        .collect();
+    if rates.is_empty() {
+        return Err("no rates in the ECB's answer".into());
+    }
+    Ok(Rates {
+        rates,
+        date: DATE
+            .captures(&xml)
+            .map(|c| c[1].to_string())
+            .unwrap_or_default(),
+        fetched_at: Utc::now(),
+    })
+}
+
+fn refresh(app: &AppHandle) -> Result<(), String> {
+    let stale = RATES
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_none_or(|r| (Utc::now() - r.fetched_at).to_std().unwrap_or_default() >= REFRESH);
+    if !stale {
+        return Ok(());
+    }
+    let rates = fetch_rates()?;
+    info!(date = rates.date, "exchange rates updated");
+    let path = paths::base_data_dir(app)?.join(RATES_FILE);
+    let json = serde_json::to_vec(&rates).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())?;
+    *RATES.lock().unwrap() = Some(rates);
+    Ok(())
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || {
+        let cached = paths::base_data_dir(&app)
+            .ok()
+            .and_then(|dir| fs::read(dir.join(RATES_FILE)).ok())
+            .and_then(|data| serde_json::from_slice::<Rates>(&data).ok());
+        if let Some(cached) = cached {
+            *RATES.lock().unwrap() = Some(cached);
+        }
+        loop {
+            if let Err(e) = refresh(&app) {
+                warn!("exchange rates not updated: {e}");
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+// `currency`, an ISO code, for the yearly figures in it besides the posting's
+// own; none for just the posting's.
+#[tauri::command]
+pub fn normalize_salary(text: String, currency: Option<String>) -> Option<Salary> {
+    normalize(&text, currency.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(text: &str) -> Salary {
+        parse(text).unwrap_or_else(|| panic!("no salary in {text:?}"))
+    }
+
+    #[test]
+    fn range_with_the_unit_once() {
+        let s = read("£55–65k");
+        assert_eq!((s.min, s.max), (Some(55_000.0), Some(65_000.0)));
+        assert_eq!(s.currency, "GBP");
+        assert_eq!(s.period, Period::Year);
+        assert_eq!(
+            (s.annual_min, s.annual_max),
+            (Some(55_000.0), Some(65_000.0))
+        );
+    }
+
+    #[test]
+    fn hourly() {
+        let s = read("$40/hr");
+        assert_eq!((s.min, s.max), (Some(40.0), Some(40.0)));
+        assert_eq!(s.currency, "USD");
+        assert_eq!(s.period, Period::Hour);
+        assert_eq!(s.annual_max, Some(40.0 * HOURS_A_YEAR));
+    }
+
+    #[test]
+    fn monthly_with_extra_payments() {
+        let s = read("1.500 € brutos al mes, 14 pagas");
+        assert_eq!((s.min, s.max), (Some(1_500.0), Some(1_500.0)));
+        assert_eq!(s.currency, "EUR");
+        assert_eq!(s.period, Period::Month);
+        assert_eq!(s.payments, 14);
+        assert_eq!(s.annual_min, Some(21_000.0));
+    }
+
+    #[test]
+    fn upper_bound_only() {
+        let s = read("hasta 30.000 €");
+        assert_eq!((s.min, s.max), (None, Some(30_000.0)));
+        assert_eq!(s.period, Period::Year);
+        assert_eq!((s.annual_min, s.annual_max), (None, Some(30_000.0)));
+    }
+
+    #[test]
+    fn lower_bound_only() {
+        let s = read("from £40k");
+        assert_eq!((s.min, s.max), (Some(40_000.0), None));
+    }
+
+    #[test]
+    fn spanish_range_in_thousands() {
+        let s = read("entre 30 y 35 mil");
+        assert_eq!((s.min, s.max), (Some(30_000.0), Some(35_000.0)));
+        assert_eq!(s.currency, DEFAULT_CURRENCY);
+        assert_eq!(s.period, Period::Year);
+    }
+
+    #[test]
+    fn no_amount() {
+        assert!(parse("competitive salary").is_none());
+        assert!(parse("14 pagas").is_none());
+    }
+}
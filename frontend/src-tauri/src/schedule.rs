@@ -0,0 +1,661 @@
+use chrono::{
+    DateTime, Datelike, Duration as Span, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{fs, path::PathBuf, sync::Mutex, thread, time::Duration};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{automation, backend, paths, quotas, sleepwake, workhours};
+
+// Engine runs on the user's own timetable, kept by the shell beside the
+// engine's built-in schedule: a cron expression (minute, hour, day of month,
+// month, day of week, in local time) or every N hours between two hours of
+// the day, optionally on weekdays only. Schedules live in the profile's
+// `schedules.json` with the last occurrence each one dealt with, so a
+// restart carries on from there. Occurrences missed while the machine slept
+// or the app was closed are made up with a single run, unless the schedule
+// says to skip them. Nothing runs while automation is paused, or outside
+// the working hours (`workhours`).
+const FILE: &str = "schedules.json";
+const TICK: Duration = Duration::from_secs(30);
+// Later than this, an occurrence counts as missed
+const GRACE_MINUTES: i64 = 5;
+const PREVIEW_COUNT: usize = 5;
+pub const SCHEDULES_CHANGED: &str = "schedules-changed";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Timing {
+    Cron {
+        expression: String,
+    },
+    Every {
+        hours: u32,
+        from_hour: u32,
+        to_hour: u32,
+        #[serde(default)]
+        weekdays_only: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Target {
+    AllScrapers,
+    Scraper { site: String },
+}
+
+fn yes() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduleInput {
+    pub name: String,
+    pub timing: Timing,
+    pub target: Target,
+    #[serde(default = "yes")]
+    pub enabled: bool,
+    // Make up missed occurrences with one run
+    #[serde(default = "yes")]
+    pub catch_up: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    #[serde(flatten)]
+    pub input: ScheduleInput,
+    pub created_at: DateTime<Utc>,
+    // Occurrences up to here were run or skipped
+    pub handled_until: Option<DateTime<Utc>>,
+    pub last_run: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleInfo {
+    #[serde(flatten)]
+    pub schedule: Schedule,
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Store {
+    schedules: Vec<Schedule>,
+}
+
+// Commands and the ticker both read, change and write the file.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::config_dir(app)?.join(FILE))
+}
+
+fn load(app: &AppHandle) -> Store {
+    path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, store: &Store) -> Result<(), String> {
+    let path = path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn change<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut Store) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = LOCK.lock().unwrap();
+    let mut store = load(app);
+    let result = f(&mut store)?;
+    save(app, &store)?;
+    let _ = app.emit(SCHEDULES_CHANGED, ());
+    Ok(result)
+}
+
+// Cron fields as bit sets.
+struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+fn field(text: &str, min: u32, max: u32, name: &str) -> Result<u64, String> {
+    let bad = || format!("invalid {name} field: {text}");
+    let number = |n: &str| n.parse::<u32>().map_err(|_| bad());
+    let mut bits = 0u64;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, number(step)?),
+            None => (part, 1),
+        };
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (number(low)?, number(high)?)
+        } else {
+            // "5/15" runs from 5 to the end
+            let n = number(range)?;
+            (n, if part.contains('/') { max } else { n })
+        };
+        if step == 0 || low < min || high > max || low > high {
+            return Err(bad());
+        }
+        for value in (low..=high).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+impl Cron {
+    fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err("a cron expression has five fields: minute hour day month weekday".into());
+        };
+        let mut weekdays = field(weekday, 0, 7, "weekday")?;
+        // 7 is Sunday too
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(Self {
+            minutes: field(minute, 0, 59, "minute")?,
+            hours: field(hour, 0, 23, "hour")?,
+            days: field(day, 1, 31, "day")?,
+            months: field(month, 1, 12, "month")?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    // With both restricted, either may match, as in cron.
+    fn day_matches(&self, t: &NaiveDateTime) -> bool {
+        let day = self.days & (1 << t.day()) != 0;
+        let weekday = self.weekdays & (1 << t.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            (true, false) => weekday,
+            _ => day,
+        }
+    }
+
+    // Walks wall-clock time; only a match is looked up in the zone.
+    fn next_after<Tz: TimeZone>(&self, after: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let zone = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Span::minutes(1);
+        let limit = start + Span::days(366 * 5);
+        let mut t = start;
+        while t < limit {
+            if self.months & (1 << t.month()) == 0 {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+            } else if !self.day_matches(&t) {
+                t = (t.date() + Span::days(1)).and_hms_opt(0, 0, 0)?;
+            } else if self.hours & (1 << t.hour()) == 0 {
+                t = t.with_minute(0)? + Span::hours(1);
+            } else if self.minutes & (1 << t.minute()) == 0 {
+                t += Span::minutes(1);
+            } else {
+                // Skips times a clock change jumps over
+                if let Some(at) = zone.from_local_datetime(&t).earliest() {
+                    if at > after {
+                        return Some(at);
+                    }
+                }
+                t += Span::minutes(1);
+            }
+        }
+        None
+    }
+}
+
+impl Timing {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Timing::Cron { expression } => Cron::parse(expression).map(|_| ()),
+            Timing::Every {
+                hours,
+                from_hour,
+                to_hour,
+                ..
+            } => {
+                if *hours == 0 || *hours > 24 {
+                    Err("hours must be between 1 and 24".into())
+                } else if from_hour > to_hour || *to_hour > 23 {
+                    Err("the hours of the day must be in order, from 0 to 23".into())
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let after = after.with_timezone(&Local);
+        let next = match self {
+            Timing::Cron { expression } => Cron::parse(expression).ok()?.next_after(after),
+            Timing::Every {
+                hours,
+                from_hour,
+                to_hour,
+                weekdays_only,
+            } => (0..8).find_map(|offset| {
+                let date = after.date_naive() + Span::days(offset);
+                if *weekdays_only && date.weekday().number_from_monday() > 5 {
+                    return None;
+                }
+                (*from_hour..=*to_hour)
+                    .step_by((*hours).max(1) as usize)
+                    .filter_map(|hour| {
+                        Local
+                            .from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+                            .earliest()
+                    })
+                    .find(|at| *at > after)
+            }),
+        };
+        next.map(|at| at.with_timezone(&Utc))
+    }
+}
+
+impl Schedule {
+    fn next_run(&self) -> Option<DateTime<Utc>> {
+        if !self.input.enabled {
+            return None;
+        }
+        let since = self.handled_until.unwrap_or(self.created_at);
+        self.input.timing.next_after(since)
+    }
+}
+
+fn info(schedule: Schedule) -> ScheduleInfo {
+    ScheduleInfo {
+        next_run: schedule.next_run(),
+        schedule,
+    }
+}
+
+fn trigger(target: &Target) -> Result<(), String> {
+    let path = match target {
+        Target::AllScrapers => "/api/automation/run".to_string(),
+        Target::Scraper { site } => format!("/api/scrapers/{site}/trigger"),
+    };
+    backend::post_json(&path, &json!({})).map(|_| ())
+}
+
+// What to do with a schedule that's due.
+enum Action {
+    Run,
+    Skip,
+}
+
+fn tick(app: &AppHandle) {
+    let now = Utc::now();
+    let due: Vec<(String, Action)> = {
+        let _guard = LOCK.lock().unwrap();
+        load(app)
+            .schedules
+            .iter()
+            .filter_map(|s| {
+                let next = s.next_run().filter(|next| *next <= now)?;
+                let missed = now - next > Span::minutes(GRACE_MINUTES);
+                let action = if missed && !s.input.catch_up {
+                    Action::Skip
+                } else {
+                    Action::Run
+                };
+                Some((s.id.clone(), action))
+            })
+            .collect()
+    };
+    if due.is_empty() {
+        return;
+    }
+    let paused = automation::paused() == Some(true);
+    let closed = workhours::refusal(app);
+    for (id, action) in due {
+        let result = change(app, |store| {
+            let Some(schedule) = store.schedules.iter_mut().find(|s| s.id == id) else {
+                return Ok(());
+            };
+            match action {
+                _ if paused => info!(name = schedule.input.name, "schedule skipped: paused"),
+                _ if closed.is_some() => {
+                    info!(
+                        name = schedule.input.name,
+                        "schedule skipped: outside working hours"
+                    )
+                }
+                Action::Skip => info!(name = schedule.input.name, "missed runs skipped"),
+                Action::Run => match quotas::run_refusal(app, &schedule.input.target) {
+                    Some(reason) => info!(name = schedule.input.name, "schedule skipped: {reason}"),
+                    None => match trigger(&schedule.input.target) {
+                        Ok(()) => {
+                            info!(name = schedule.input.name, "scheduled run started");
+                            schedule.last_run = Some(now);
+                            schedule.last_error = None;
+                        }
+                        // Left due, so it's tried again next tick
+                        Err(e) => {
+                            schedule.last_error = Some(e.clone());
+                            return Err(e);
+                        }
+                    },
+                },
+            }
+            schedule.handled_until = Some(now);
+            Ok(())
+        });
+        if let Err(e) = result {
+            warn!(id, "scheduled run not started: {e}");
+        }
+    }
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        // Caught up on the first tick after waking, once the engine answers
+        if !sleepwake::is_asleep() {
+            tick(&app);
+        }
+    });
+}
+
+fn new_id() -> String {
+    format!("schedule-{}", Local::now().format("%Y%m%d-%H%M%S%3f"))
+}
+
+fn validate(input: &ScheduleInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("a schedule needs a name".into());
+    }
+    if let Target::Scraper { site } = &input.target {
+        if site.is_empty() || !site.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("invalid site: {site}"));
+        }
+    }
+    input.timing.validate()
+}
+
+#[tauri::command]
+pub fn list_schedules(app: AppHandle) -> Vec<ScheduleInfo> {
+    load(&app).schedules.into_iter().map(info).collect()
+}
+
+#[tauri::command]
+pub fn create_schedule(app: AppHandle, schedule: ScheduleInput) -> Result<ScheduleInfo, String> {
+    validate(&schedule)?;
+    let created = Schedule {
+        id: new_id(),
+        input: schedule,
+        created_at: Utc::now(),
+        handled_until: None,
+        last_run: None,
+        last_error: None,
+    };
+    change(&app, |store| {
+        store.schedules.push(created.clone());
+        Ok(())
+    })?;
+    Ok(info(created))
+}
+
+// A new timing starts counting from now, so it doesn't fire for the past.
+#[tauri::command]
+pub fn update_schedule(
+    app: AppHandle,
+    id: String,
+    schedule: ScheduleInput,
+) -> Result<ScheduleInfo, String> {
+    validate(&schedule)?;
+    change(&app, |store| {
+        let existing = store
+            .schedules
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| format!("no schedule {id}"))?;
+        if existing.input.timing != schedule.timing || (!existing.input.enabled && schedule.enabled)
+        {
+            existing.handled_until = Some(Utc::now());
+        }
+        existing.input = schedule;
+        Ok(info(existing.clone()))
+    })
+}
+
+#[tauri::command]
+pub fn delete_schedule(app: AppHandle, id: String) -> Result<(), String> {
+    change(&app, |store| {
+        store.schedules.retain(|s| s.id != id);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn run_schedule_now(app: AppHandle, id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let target = load(&app)
+            .schedules
+            .into_iter()
+            .find(|s| s.id == id)
+            .map(|s| s.input.target)
+            .ok_or_else(|| format!("no schedule {id}"))?;
+        let refusal = workhours::refusal(&app).or_else(|| quotas::run_refusal(&app, &target));
+        if let Some(reason) = refusal {
+            return Err(reason);
+        }
+        trigger(&target)?;
+        change(&app, |store| {
+            if let Some(schedule) = store.schedules.iter_mut().find(|s| s.id == id) {
+                schedule.last_run = Some(Utc::now());
+                schedule.last_error = None;
+            }
+            Ok(())
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// The next few times a timing would run, for the form.
+#[tauri::command]
+pub fn preview_schedule(timing: Timing) -> Result<Vec<DateTime<Utc>>, String> {
+    timing.validate()?;
+    let mut runs = Vec::new();
+    let mut after = Utc::now();
+    while runs.len() < PREVIEW_COUNT {
+        let Some(next) = timing.next_after(after) else {
+            break;
+        };
+        runs.push(next);
+        after = next;
+    }
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, LocalResult, NaiveDate};
+
+    // UTC-3, and UTC-2 from 00:00 on 1 November 2026, when clocks go
+    // straight to 01:00 (as in some of South America).
+    #[derive(Clone, Copy, Debug)]
+    struct MidnightDst;
+
+    fn before() -> FixedOffset {
+        FixedOffset::west_opt(3 * 3600).unwrap()
+    }
+
+    fn after() -> FixedOffset {
+        FixedOffset::west_opt(2 * 3600).unwrap()
+    }
+
+    fn change() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 11, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    impl TimeZone for MidnightDst {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            MidnightDst
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(12, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            if *local < change() {
+                LocalResult::Single(before())
+            } else if *local < change() + Span::hours(1) {
+                LocalResult::None
+            } else {
+                LocalResult::Single(after())
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            if *utc < change() + Span::hours(3) {
+                before()
+            } else {
+                after()
+            }
+        }
+    }
+
+    fn at(text: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M")
+            .unwrap()
+            .and_utc()
+    }
+
+    // The run after `from`, both as UTC wall-clock text.
+    fn next(expression: &str, from: &str) -> Option<String> {
+        Cron::parse(expression)
+            .unwrap()
+            .next_after(at(from))
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+    }
+
+    #[test]
+    fn fields() {
+        assert!(Cron::parse("* * * *").is_err());
+        assert!(Cron::parse("60 * * * *").is_err());
+        assert!(Cron::parse("*/0 * * * *").is_err());
+        assert!(Cron::parse("5-1 * * * *").is_err());
+        assert!(Cron::parse("0 9 * * 1-5").is_ok());
+    }
+
+    #[test]
+    fn steps_and_ranges() {
+        // 2026-10-15 is a Thursday
+        assert_eq!(
+            next("*/15 * * * *", "2026-10-15 10:07").as_deref(),
+            Some("2026-10-15 10:15")
+        );
+        assert_eq!(
+            next("5/20 * * * *", "2026-10-15 10:30").as_deref(),
+            Some("2026-10-15 10:45")
+        );
+        assert_eq!(
+            next("0 9-17/4 * * *", "2026-10-15 13:00").as_deref(),
+            Some("2026-10-15 17:00")
+        );
+        assert_eq!(
+            next("30 8 * * 1-5", "2026-10-16 09:00").as_deref(),
+            Some("2026-10-19 08:30")
+        );
+        assert_eq!(
+            next("0 0 1 1,7 *", "2026-10-15 00:00").as_deref(),
+            Some("2027-01-01 00:00")
+        );
+    }
+
+    #[test]
+    fn sunday_as_seven() {
+        assert_eq!(
+            next("0 10 * * 7", "2026-10-15 00:00").as_deref(),
+            Some("2026-10-18 10:00")
+        );
+        assert_eq!(
+            next("0 10 * * 0", "2026-10-15 00:00"),
+            next("0 10 * * 7", "2026-10-15 00:00")
+        );
+    }
+
+    #[test]
+    fn day_or_weekday() {
+        // The 20th or any Monday, whichever comes first
+        assert_eq!(
+            next("0 12 20 * 1", "2026-10-15 00:00").as_deref(),
+            Some("2026-10-19 12:00")
+        );
+        assert_eq!(
+            next("0 12 20 * 1", "2026-10-19 13:00").as_deref(),
+            Some("2026-10-20 12:00")
+        );
+        // Only one restricted: that one alone
+        assert_eq!(
+            next("0 12 20 * *", "2026-10-15 00:00").as_deref(),
+            Some("2026-10-20 12:00")
+        );
+        assert_eq!(
+            next("0 12 * * 1", "2026-10-15 00:00").as_deref(),
+            Some("2026-10-19 12:00")
+        );
+    }
+
+    #[test]
+    fn impossible_dates_end() {
+        assert_eq!(next("0 0 31 2 *", "2026-10-15 00:00"), None);
+    }
+
+    #[test]
+    fn month_starting_in_a_clock_change() {
+        let after = MidnightDst.from_utc_datetime(&at("2026-10-20 12:00").naive_utc());
+        // Midnight on the 1st doesn't exist; the first run is the next minute
+        // that does
+        let first = Cron::parse("* * * 11 *")
+            .unwrap()
+            .next_after(after)
+            .unwrap();
+        assert_eq!(first.naive_local().to_string(), "2026-11-01 01:00:00");
+        // A run set for the missing hour is skipped that day
+        let daily = Cron::parse("30 0 * 11 *")
+            .unwrap()
+            .next_after(after)
+            .unwrap();
+        assert_eq!(daily.naive_local().to_string(), "2026-11-02 00:30:00");
+    }
+}
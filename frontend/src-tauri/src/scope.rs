@@ -0,0 +1,141 @@
+use tauri::{ipc::Invoke, Manager, Runtime, Url};
+use tracing::{debug, warn};
+
+use crate::{onboarding, uninstall, updater};
+
+// Which webview may call which of the app's own commands, checked in Rust
+// before any command runs. The main window gets everything; each secondary
+// window gets only what its page uses, so the credential and wipe commands
+// stay with main plus the one window whose whole job is that step (the
+// welcome guide saves the first login, the uninstall window confirms the
+// wipe). A webview that isn't listed here, or that has navigated away from
+// the app's own pages (a job site, say), can call nothing. Plugin commands
+// are gated the same way by capabilities/default.json.
+pub const MAIN_WINDOW: &str = "main";
+
+// Called by the layout in every window listed here
+const EVERY_WINDOW: &[&str] = &[
+    "app_lock_activity",
+    "mark_first_paint",
+    "record_ipc_calls",
+    "get_debug_mode",
+];
+
+const SECONDARY: &[(&str, &[&str])] = &[
+    (
+        onboarding::ONBOARDING_WINDOW,
+        &[
+            "get_onboarding",
+            "advance_onboarding",
+            "request_notification_permission",
+            "save_credentials",
+            "set_autolaunch",
+            "get_system_info",
+        ],
+    ),
+    (
+        updater::RELEASE_NOTES_WINDOW,
+        &["get_release_notes", "install_update", "snooze_update"],
+    ),
+    (
+        uninstall::UNINSTALL_WINDOW,
+        &[
+            "get_wipe_plan",
+            "cleanup_for_uninstall",
+            "quit_for_uninstall",
+        ],
+    ),
+];
+
+fn allowed(label: &str, command: &str) -> bool {
+    if label == MAIN_WINDOW {
+        return true;
+    }
+    let commands: &[&str] = SECONDARY
+        .iter()
+        .find(|(window, _)| *window == label)
+        .map_or(&[], |(_, commands)| commands);
+    commands.contains(&command) || (!commands.is_empty() && EVERY_WINDOW.contains(&command))
+}
+
+// The bundled pages (tauri:// on macOS and Linux, http(s)://tauri.localhost
+// on Windows) or, in development, the dev server.
+fn is_app_origin<R: Runtime>(app: &tauri::AppHandle<R>, url: &Url) -> bool {
+    if url.scheme() == "tauri" || url.host_str() == Some("tauri.localhost") {
+        return true;
+    }
+    cfg!(debug_assertions)
+        && app
+            .config()
+            .build
+            .dev_url
+            .as_ref()
+            .is_some_and(|dev| dev.origin() == url.origin())
+}
+
+// Wraps the generated handler; a refused call is rejected, not dropped, so
+// the page sees an error instead of a promise that never settles.
+pub fn guard<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke: Invoke<R>| {
+        let webview = invoke.message.webview_ref();
+        let label = webview.label().to_string();
+        let command = invoke.message.command().to_string();
+        let local = webview
+            .url()
+            .is_ok_and(|url| is_app_origin(webview.app_handle(), &url));
+        if !local || !allowed(&label, &command) {
+            warn!("refused {command} from window {label}");
+            invoke
+                .resolver
+                .reject(format!("{command} is not available in this window"));
+            return true;
+        }
+        debug!(command, window = label, "invoke");
+        handler(invoke)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_window_calls_anything() {
+        assert!(allowed(MAIN_WINDOW, "save_credentials"));
+        assert!(allowed(MAIN_WINDOW, "cleanup_for_uninstall"));
+    }
+
+    #[test]
+    fn secondary_windows_call_their_own_commands() {
+        assert!(allowed(onboarding::ONBOARDING_WINDOW, "save_credentials"));
+        assert!(allowed(
+            uninstall::UNINSTALL_WINDOW,
+            "cleanup_for_uninstall"
+        ));
+        assert!(!allowed(updater::RELEASE_NOTES_WINDOW, "save_credentials"));
+        assert!(!allowed(
+            onboarding::ONBOARDING_WINDOW,
+            "cleanup_for_uninstall"
+        ));
+    }
+
+    #[test]
+    fn secondary_windows_call_the_layout_commands() {
+        for command in EVERY_WINDOW {
+            assert!(allowed(updater::RELEASE_NOTES_WINDOW, command));
+        }
+    }
+
+    #[test]
+    fn unknown_windows_call_nothing() {
+        let every = EVERY_WINDOW
+            .iter()
+            .chain(SECONDARY.iter().flat_map(|(_, commands)| commands.iter()));
+        for command in every.chain(["save_credentials", "get_history"].iter()) {
+            assert!(!allowed("job-site", command), "{command}");
+            assert!(!allowed("", command), "{command}");
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use rusqlite::{params_from_iter, types::Value as Sql, Connection};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::history::{self, HistoryEntry, HistoryQuery};
+
+// Full-text search over the application history: company, role, location,
+// the job's description and the user's notes (the board's and the
+// calendar's), in an SQLite FTS5 index beside the history. Triggers keep it
+// in step with every writer of those tables, so nothing else has to know
+// about it; it's filled from what's there the first time it's made. Words
+// match without regard to case or accents, the last one as a prefix, and
+// hits come best first (bm25, company and role counting most) with a
+// snippet of where they matched.
+const PAGE: u32 = 50;
+const MAX_LIMIT: u32 = 200;
+const MAX_TERMS: usize = 16;
+// Around the matched words in a snippet
+const OPEN: char = '\u{2}';
+const CLOSE: char = '\u{3}';
+const SNIPPET_WORDS: u32 = 16;
+
+// One application's row of the index
+const ROW: &str = "
+SELECT a.id, a.company, a.role, a.location, a.description,
+    (SELECT group_concat(notes, char(10)) FROM (
+        SELECT notes FROM pipeline_cards WHERE application_id = a.id AND notes != ''
+        UNION ALL
+        SELECT notes FROM calendar_events WHERE application_id = a.id AND notes IS NOT NULL
+    ))
+FROM applications a";
+
+#[derive(Debug, Serialize)]
+pub struct Segment {
+    pub text: String,
+    // One of the words searched for
+    pub hit: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub entry: HistoryEntry,
+    pub snippet: Vec<Segment>,
+    // Lower is better
+    pub rank: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchPage {
+    pub items: Vec<SearchHit>,
+    pub total: u32,
+}
+
+// Brings the application's row of the index up to date.
+fn refresh(id: &str) -> String {
+    format!(
+        "DELETE FROM application_search WHERE rowid = {id};
+         INSERT INTO application_search (rowid, company, role, location, description, notes)
+         {ROW} WHERE a.id = {id};"
+    )
+}
+
+// The index and its triggers, in the migration of history::open.
+pub fn prepare(db: &Connection) -> Result<(), String> {
+    let exists = db
+        .prepare("SELECT 1 FROM sqlite_master WHERE name = 'application_search'")
+        .and_then(|mut s| s.exists([]))
+        .map_err(|e| e.to_string())?;
+    if exists {
+        return Ok(());
+    }
+    let application = refresh("new.id");
+    let card = refresh("new.application_id");
+    let event = refresh("new.application_id");
+    let old_event = refresh("old.application_id");
+    db.execute_batch(&format!(
+        "CREATE VIRTUAL TABLE application_search USING fts5(
+             company, role, location, description, notes,
+             tokenize = 'unicode61 remove_diacritics 2'
+         );
+         INSERT INTO application_search (rowid, company, role, location, description, notes)
+         {ROW};
+         CREATE TRIGGER application_search_insert AFTER INSERT ON applications
+         BEGIN {application} END;
+         CREATE TRIGGER application_search_update
+         AFTER UPDATE OF company, role, location, description ON applications
+         WHEN new.company IS NOT old.company OR new.role IS NOT old.role
+             OR new.location IS NOT old.location OR new.description IS NOT old.description
+         BEGIN {application} END;
+         CREATE TRIGGER application_search_card_insert AFTER INSERT ON pipeline_cards
+         BEGIN {card} END;
+         CREATE TRIGGER application_search_card_update AFTER UPDATE OF notes ON pipeline_cards
+         BEGIN {card} END;
+         CREATE TRIGGER application_search_event_insert AFTER INSERT ON calendar_events
+         WHEN new.application_id IS NOT NULL
+         BEGIN {event} END;
+         CREATE TRIGGER application_search_event_update
+         AFTER UPDATE OF notes, application_id ON calendar_events
+         BEGIN {event} {old_event} END;
+         CREATE TRIGGER application_search_event_delete AFTER DELETE ON calendar_events
+         WHEN old.application_id IS NOT NULL
+         BEGIN {old_event} END;"
+    ))
+    .map_err(|e| e.to_string())
+}
+
+// The user's words as an FTS5 query: each quoted, so none is read as an
+// operator, all needed, and the last also matching as the start of a word
+// while it's being typed.
+fn terms(query: &str) -> Option<String> {
+    let words: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .take(MAX_TERMS)
+        .map(|w| format!("\"{w}\""))
+        .collect();
+    let last = words.len().checked_sub(1)?;
+    Some(
+        words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                if i == last {
+                    format!("{w}*")
+                } else {
+                    w.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+fn segments(snippet: &str) -> Vec<Segment> {
+    // The markers come in pairs, unless the text had them already
+    if snippet.matches(OPEN).count() != snippet.matches(CLOSE).count() {
+        return vec![Segment {
+            text: snippet.replace([OPEN, CLOSE], ""),
+            hit: false,
+        }];
+    }
+    let mut segments = Vec::new();
+    let mut hit = false;
+    for part in snippet.split([OPEN, CLOSE]) {
+        if !part.is_empty() {
+            segments.push(Segment {
+                text: part.to_string(),
+                hit,
+            });
+        }
+        hit = !hit;
+    }
+    segments
+}
+
+fn search(app: &AppHandle, query: &str, filters: HistoryQuery) -> Result<SearchPage, String> {
+    let terms = terms(query).ok_or("nothing to search for")?;
+    let (limit, offset) = (filters.limit, filters.offset);
+    let db = history::open(app)?;
+    let (filter, filter_args) = history::filter(&HistoryQuery {
+        search: None,
+        ..filters
+    });
+    let matches = format!(
+        "FROM applications JOIN (
+             SELECT rowid AS hit_id,
+                 snippet(application_search, -1, char(2), char(3), '…', {SNIPPET_WORDS}) AS snippet,
+                 bm25(application_search, 10.0, 10.0, 3.0, 1.0, 5.0) AS rank
+             FROM application_search WHERE application_search MATCH ?
+         ) ON hit_id = applications.id
+         {filter}"
+    );
+    let mut args = vec![Sql::Text(terms)];
+    args.extend(filter_args);
+    let total: u32 = db
+        .query_row(
+            &format!("SELECT COUNT(*) {matches}"),
+            params_from_iter(&args),
+            |r| r.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let limit = limit.unwrap_or(PAGE).clamp(1, MAX_LIMIT);
+    args.push(Sql::Integer(limit.into()));
+    args.push(Sql::Integer(offset.into()));
+    let mut statement = db
+        .prepare(&format!(
+            "SELECT applications.*, snippet, rank {matches}
+             ORDER BY rank, applications.id DESC LIMIT ? OFFSET ?"
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map(params_from_iter(&args), |r| {
+            Ok((
+                history::entry(r)?,
+                r.get::<_, Option<String>>("snippet")?,
+                r.get::<_, f64>("rank")?,
+            ))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    let (entries, found): (Vec<_>, Vec<_>) = rows
+        .into_iter()
+        .map(|(entry, snippet, rank)| (entry, (snippet, rank)))
+        .unzip();
+    let items = history::with_tags(&db, entries)?
+        .into_iter()
+        .zip(found)
+        .map(|(entry, (snippet, rank))| SearchHit {
+            entry,
+            snippet: segments(snippet.as_deref().unwrap_or_default()),
+            rank,
+        })
+        .collect();
+    Ok(SearchPage { items, total })
+}
+
+// Best matches first, narrowed by the history's filters (its own `search`
+// aside).
+#[tauri::command]
+pub async fn search_applications(
+    app: AppHandle,
+    query: String,
+    filters: Option<HistoryQuery>,
+) -> Result<SearchPage, String> {
+    tauri::async_runtime::spawn_blocking(move || search(&app, &query, filters.unwrap_or_default()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marked(segments: &[Segment]) -> String {
+        segments
+            .iter()
+            .map(|s| {
+                if s.hit {
+                    format!("[{}]", s.text)
+                } else {
+                    s.text.clone()
+                }
+            })
+            .collect()
+    }
+
+    // Companies matching `query`, best first, each with its snippet marked
+    fn find(db: &Connection, query: &str) -> Vec<(String, String)> {
+        let mut statement = db
+            .prepare(&format!(
+                "SELECT company,
+                     snippet(application_search, -1, char(2), char(3), '…', {SNIPPET_WORDS})
+                 FROM application_search WHERE application_search MATCH ?
+                 ORDER BY bm25(application_search, 10.0, 10.0, 3.0, 1.0, 5.0)"
+            ))
+            .unwrap();
+        statement
+            .query_map([terms(query).unwrap()], |r| {
+                Ok((r.get(0)?, marked(&segments(&r.get::<_, String>(1)?))))
+            })
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn history() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        db.execute_batch(
+            "INSERT INTO applications (id, company, role, description, recorded_at) VALUES
+                 (1, 'Acme', 'Rust developer', 'Backend work in Rust and Python', '2026-01-01'),
+                 (2, 'Telefónica', 'Data engineer', 'Spark pipelines', '2026-01-02'),
+                 (3, 'NEAR Labs', 'Engineer', 'AND OR NOT \"quoted\" text', '2026-01-03');
+             INSERT INTO pipeline_cards (application_id, notes, updated_at)
+                 VALUES (2, 'Recruiter said rustaceans welcome', '2026-01-04');",
+        )
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn words_are_quoted() {
+        assert_eq!(terms("rust dev").as_deref(), Some("\"rust\" \"dev\"*"));
+        // Operators, quotes and column filters are only words
+        assert_eq!(
+            terms("role:\"rust\" AND -python*").as_deref(),
+            Some("\"role\" \"rust\" \"AND\" \"python\"*")
+        );
+        assert_eq!(terms("  \"*- ( )"), None);
+        let many = "a ".repeat(MAX_TERMS + 4);
+        assert_eq!(terms(&many).unwrap().matches('"').count(), MAX_TERMS * 2);
+    }
+
+    #[test]
+    fn operators_match_as_words() {
+        let db = history();
+        let found = find(&db, "AND OR NOT");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "NEAR Labs");
+        assert_eq!(find(&db, "near").len(), 1);
+        assert_eq!(find(&db, "\"quoted").len(), 1);
+    }
+
+    #[test]
+    fn last_word_is_a_prefix() {
+        let db = history();
+        // "rust" also starts "rustaceans" in a card's notes
+        let companies: Vec<String> = find(&db, "rust").into_iter().map(|(c, _)| c).collect();
+        assert_eq!(companies, ["Acme", "Telefónica"]);
+        // Only the last
+        assert!(find(&db, "rus developer").is_empty());
+        assert_eq!(find(&db, "rust dev").len(), 1);
+        // Without regard to case or accents
+        assert_eq!(find(&db, "TELEFONICA")[0].0, "Telefónica");
+    }
+
+    #[test]
+    fn snippets_mark_the_hits() {
+        let db = history();
+        let (_, snippet) = &find(&db, "spark")[0];
+        assert_eq!(snippet, "[Spark] pipelines");
+    }
+
+    #[test]
+    fn markers_in_pairs() {
+        let text = format!("a {OPEN}b{CLOSE} c {OPEN}d{CLOSE}");
+        assert_eq!(marked(&segments(&text)), "a [b] c [d]");
+        let hits: Vec<bool> = segments(&text).iter().map(|s| s.hit).collect();
+        assert_eq!(hits, [false, true, false, true]);
+        // A stray marker from the text itself: no highlighting at all
+        let stray = format!("a {OPEN}b{CLOSE} c {CLOSE}");
+        let segments = segments(&stray);
+        assert_eq!(segments.len(), 1);
+        assert!(!segments[0].hit);
+        assert_eq!(segments[0].text, "a b c ");
+        assert!(super::segments("").is_empty());
+    }
+}
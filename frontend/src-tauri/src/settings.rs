@@ -0,0 +1,1028 @@
+use chrono::{DateTime, Local, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime},
+};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{error, warn};
+
+use crate::{
+    audit::{self, Action},
+    i18n::{t, t_args},
+};
+
+// Shell-side preferences. The backend keeps its own settings table; this file
+// only holds what the Rust side needs before (or without) the backend running.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub digest: DigestSettings,
+    pub update: UpdateSettings,
+    pub proxy: ProxySettings,
+    pub onboarding: OnboardingSettings,
+    // Engine config secrets by backend setting name, sealed by `vault`
+    pub secrets: Secrets,
+    // Feature flag overrides by name; see `flags`
+    pub flags: BTreeMap<String, bool>,
+    // UI language code for the shell's own text; None follows the system
+    pub language: Option<String>,
+    pub sync: SyncSettings,
+    pub lock: LockSettings,
+    pub redaction: RedactionSettings,
+    pub clipboard: ClipboardSettings,
+    pub logs: LogSettings,
+    pub telemetry: TelemetrySettings,
+    pub error_reporting: ErrorReportingSettings,
+    pub power: PowerSettings,
+    // Verbose logs, developer menu and overlays; see `debugmode`
+    pub debug_mode: bool,
+    // history.db encrypted with a key in this machine's keychain; see
+    // `historycrypt`
+    pub encrypt_history: bool,
+    // With no tray to return from, keep running when the window closes; see
+    // `trayhost`
+    pub background_without_tray: bool,
+    // macOS: no Dock icon while the window is hidden; see `menubar`
+    pub menu_bar_only: bool,
+    // How start on login is registered; see `loginitem`
+    pub login_item: LoginItemMethod,
+    pub mail: MailSettings,
+    pub oauth: OAuthSettings,
+    pub backup: BackupSettings,
+    pub quota: QuotaSettings,
+    pub evidence: EvidenceSettings,
+    pub browser_extension: BrowserExtensionSettings,
+    pub retention: RetentionSettings,
+    pub working_hours: WorkingHoursSettings,
+}
+
+pub type Secrets = BTreeMap<String, String>;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestSettings {
+    pub enabled: bool,
+    // Local wall-clock time, "HH:MM"
+    pub time: String,
+    // Date (YYYY-MM-DD) of the last digest sent, so restarts don't resend it
+    pub last_sent_on: Option<String>,
+    // Add the day's time in the app and in runs (activity.rs)
+    pub include_activity: bool,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            time: "20:00".into(),
+            last_sent_on: None,
+            include_activity: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateSettings {
+    pub channel: UpdateChannel,
+    // Shell version and sidecar override (None = bundled) that were running
+    // before the last update was installed; what "Roll back" restores.
+    pub previous_version: Option<String>,
+    pub previous_backend: Option<String>,
+    // A release the user rolled back from; never offered again
+    pub skipped_version: Option<String>,
+    // RFC 3339; non-critical "update ready" prompts are held until then
+    pub snoozed_until: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyMode {
+    // Whatever the OS (or HTTP(S)_PROXY) says
+    #[default]
+    System,
+    Manual,
+    None,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginItemMethod {
+    // The best one for this machine
+    #[default]
+    Auto,
+    // macOS
+    LaunchAgent,
+    AppService,
+    // Windows: HKCU Run key, or a logon task for every user
+    RunKey,
+    AllUsers,
+    // Linux: an autostart entry the app writes itself
+    DesktopEntry,
+}
+
+// The proxy password never lands in this file; it lives in the OS keychain
+// (see proxy.rs).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub mode: ProxyMode,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    #[default]
+    Welcome,
+    Notifications,
+    Autostart,
+    Credentials,
+    Done,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnboardingSettings {
+    pub step: OnboardingStep,
+    // RFC 3339, set when the last step is passed
+    pub completed_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncSettings {
+    // Folder shared between machines (Dropbox, OneDrive...); None = off
+    pub folder: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LockSettings {
+    // Showing the main window needs OS authentication; see `applock`
+    pub enabled: bool,
+    // Locks again after this long without input
+    pub idle_minutes: u32,
+}
+
+impl Default for LockSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            idle_minutes: 5,
+        }
+    }
+}
+
+// Extra rules for scrubbing logs and diagnostics; see `redact`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionSettings {
+    // Regular expressions; matches become "[redacted]"
+    pub patterns: Vec<String>,
+    // The user's own name(s); matches become "[name]"
+    pub names: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipboardSettings {
+    // Copied passwords are wiped from the clipboard after this long
+    pub clear_after_secs: u32,
+    // Offer to add copied job-posting URLs; see `clipwatch`
+    pub watch_job_urls: bool,
+    // Sites whose links count as postings, subdomains included
+    pub job_domains: Vec<String>,
+}
+
+impl Default for ClipboardSettings {
+    fn default() -> Self {
+        Self {
+            clear_after_secs: 30,
+            watch_job_urls: false,
+            job_domains: [
+                "infojobs.net",
+                "linkedin.com",
+                "indeed.com",
+                "indeed.es",
+                "tecnoempleo.com",
+                "glassdoor.es",
+                "jobtoday.com",
+                "welcometothejungle.com",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+// See `keepawake`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerSettings {
+    // Hold off system sleep while a run is going
+    pub keep_awake: bool,
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self { keep_awake: true }
+    }
+}
+
+// Limits for the shell's and the engine's log files; see `logretention`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogSettings {
+    // Both sides' logs together
+    pub budget_mb: u32,
+    pub max_age_days: u32,
+    // Level by shell area ("supervisor", "tray"...); see `logging`
+    pub levels: BTreeMap<String, String>,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            budget_mb: 50,
+            max_age_days: 30,
+            levels: BTreeMap::new(),
+        }
+    }
+}
+
+// Anonymous usage counts; see `telemetry`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+}
+
+// Forwarding errors to a Sentry project; see `errorreport`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErrorReportingSettings {
+    pub enabled: bool,
+    pub dsn: String,
+}
+
+// The mailbox watched for replies to applications; see `mailwatch`. The
+// password lives in the OS keychain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MailSettings {
+    pub enabled: bool,
+    pub host: String,
+    // 993 for TLS; 143 upgrades with STARTTLS
+    pub port: u16,
+    pub username: String,
+    // A folder the user's mail rules fill, not the whole inbox
+    pub folder: String,
+    pub interval_minutes: u32,
+    // Move applications on what the mail says, or only notify
+    pub update_status: bool,
+}
+
+impl Default for MailSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: 993,
+            username: String::new(),
+            folder: "currobot".into(),
+            interval_minutes: 15,
+            update_status: true,
+        }
+    }
+}
+
+// Apps the user registered with each provider for signing in from the
+// desktop; their secrets are in the keychain, see `oauth`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OAuthSettings {
+    pub linkedin_client_id: String,
+    pub google_client_id: String,
+    // The loopback redirect is http://127.0.0.1:<port>/callback; LinkedIn
+    // needs it registered exactly. 0 picks a free port (Google only)
+    pub callback_port: u16,
+}
+
+impl Default for OAuthSettings {
+    fn default() -> Self {
+        Self {
+            linkedin_client_id: String::new(),
+            google_client_id: String::new(),
+            callback_port: 47823,
+        }
+    }
+}
+
+// Archives of all app data; see `backup`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupSettings {
+    pub enabled: bool,
+    pub interval_hours: u32,
+    // Scheduled backups kept; ones made by hand are never removed
+    pub keep: u32,
+    // None is a "backups" folder in the data folder
+    pub folder: Option<PathBuf>,
+    pub last_backup_at: Option<DateTime<Utc>>,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: 24,
+            keep: 7,
+            folder: None,
+            last_backup_at: None,
+        }
+    }
+}
+
+// Pacing of submissions; see `quotas`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaSettings {
+    pub enabled: bool,
+    // Applications a day on every board, 0 for no cap
+    pub daily_per_board: u32,
+    // Caps for particular boards (scraper sites), over `daily_per_board`
+    pub boards: BTreeMap<String, u32>,
+    pub min_delay_minutes: u32,
+}
+
+// Retention of the screenshots and pages kept as proof; see `evidence`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvidenceSettings {
+    // 0 keeps it for good
+    pub retention_days: u32,
+    pub max_mb: u32,
+}
+
+impl Default for EvidenceSettings {
+    fn default() -> Self {
+        Self {
+            retention_days: 730,
+            max_mb: 2048,
+        }
+    }
+}
+
+// What goes once it's old enough; see `retention`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionSettings {
+    // Off until the user turns it on; the preview works either way
+    pub enabled: bool,
+    // Applications in `statuses` untouched this long; 0 keeps them
+    pub applications_months: u32,
+    pub statuses: Vec<String>,
+    // The engine's screenshots and those kept as evidence; 0 keeps them
+    pub screenshots_days: u32,
+    // RFC 3339, UTC
+    pub last_run_at: Option<String>,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            applications_months: 12,
+            statuses: vec!["rejected".into()],
+            screenshots_days: 90,
+            last_run_at: None,
+        }
+    }
+}
+
+// When automation may run; see `workhours`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkingHoursSettings {
+    pub enabled: bool,
+    // Local hours, from the start of `from_hour` to the start of `to_hour`
+    // (24 for midnight)
+    pub from_hour: u32,
+    pub to_hour: u32,
+    // Days it applies, 1 for Monday to 7 for Sunday
+    pub days: Vec<u32>,
+    // Running anyway until then, the next time the hours start
+    pub override_until: Option<DateTime<Utc>>,
+    // The engine was paused for being outside the hours, and is resumed when
+    // they start
+    pub holding: bool,
+}
+
+impl Default for WorkingHoursSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            from_hour: 8,
+            to_hour: 20,
+            days: (1..=6).collect(),
+            override_until: None,
+            holding: false,
+        }
+    }
+}
+
+// The companion browser extension's native messaging host; see `nativehost`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrowserExtensionSettings {
+    pub enabled: bool,
+    // Chrome, Chromium and Edge allow extensions by ID, as chrome://extensions
+    // shows it; Firefox's is fixed
+    pub chrome_extension_ids: Vec<String>,
+}
+
+// Bump when the on-disk shape changes and add a step to `migrate`.
+const SCHEMA_VERSION: u64 = 2;
+
+// Emitted with the full settings whenever they change, from the app or on disk.
+pub const SETTINGS_CHANGED: &str = "settings-changed";
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+pub struct SettingsState {
+    settings: Mutex<Settings>,
+    // Problems found while loading, reported to the user once at startup
+    warnings: Mutex<Vec<String>>,
+}
+
+fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = crate::paths::config_dir(app)?;
+    Ok(dir.join("settings.json"))
+}
+
+// Brings an older file up to SCHEMA_VERSION one step at a time.
+fn migrate(value: &mut Value, from: u64) -> Result<(), String> {
+    for version in from..SCHEMA_VERSION {
+        match version {
+            // Files from before the schema was versioned have the v1 shape
+            0 => {}
+            // v2 added onboarding; anyone with a settings file is past it
+            1 => value["onboarding"] = json!({ "step": "done" }),
+            v => return Err(format!("no migration from settings version {v}")),
+        }
+        value["version"] = (version + 1).into();
+    }
+    Ok(())
+}
+
+// Resets individual fields that deserialized but hold values nothing else can
+// use, rather than throwing the whole file away.
+fn validate(settings: &mut Settings, warnings: &mut Vec<String>) {
+    if NaiveTime::parse_from_str(&settings.digest.time, "%H:%M").is_err() {
+        warnings.push(t_args(
+            "settings-invalid-digest-time",
+            &[("time", settings.digest.time.as_str().into())],
+        ));
+        settings.digest.time = DigestSettings::default().time;
+    }
+    if settings.lock.idle_minutes == 0 {
+        settings.lock.idle_minutes = LockSettings::default().idle_minutes;
+    }
+    let mail = &mut settings.mail;
+    if mail.enabled && (mail.host.trim().is_empty() || mail.port == 0 || mail.username.is_empty()) {
+        warnings.push(t("settings-invalid-mail"));
+        mail.enabled = false;
+    }
+    if mail.interval_minutes == 0 {
+        mail.interval_minutes = MailSettings::default().interval_minutes;
+    }
+    if settings.clipboard.clear_after_secs == 0 {
+        settings.clipboard.clear_after_secs = ClipboardSettings::default().clear_after_secs;
+    }
+    let hours = &mut settings.working_hours;
+    hours.days.retain(|d| (1..=7).contains(d));
+    if hours.from_hour >= hours.to_hour || hours.to_hour > 24 || hours.days.is_empty() {
+        let defaults = WorkingHoursSettings::default();
+        hours.from_hour = defaults.from_hour;
+        hours.to_hour = defaults.to_hour;
+        hours.days = defaults.days;
+    }
+    let defaults = LogSettings::default();
+    if settings.logs.budget_mb == 0 {
+        settings.logs.budget_mb = defaults.budget_mb;
+    }
+    if settings.logs.max_age_days == 0 {
+        settings.logs.max_age_days = defaults.max_age_days;
+    }
+    settings.logs.levels.retain(|area, level| {
+        crate::logging::is_area(area) && crate::overrides::LOG_LEVELS.contains(&level.as_str())
+    });
+    settings.redaction.patterns.retain(|pattern| {
+        let valid = crate::redact::compile_pattern(pattern).is_ok();
+        if !valid {
+            warnings.push(t_args(
+                "settings-invalid-redaction-pattern",
+                &[("pattern", pattern.as_str().into())],
+            ));
+        }
+        valid
+    });
+    let proxy = &mut settings.proxy;
+    if proxy.mode == ProxyMode::Manual && (proxy.host.trim().is_empty() || proxy.port == 0) {
+        warnings.push(t("settings-invalid-proxy"));
+        proxy.mode = ProxyMode::System;
+    }
+    let update = &mut settings.update;
+    if update
+        .snoozed_until
+        .as_deref()
+        .is_some_and(|s| DateTime::parse_from_rfc3339(s).is_err())
+    {
+        update.snoozed_until = None;
+    }
+    for version in [&mut update.previous_version, &mut update.skipped_version] {
+        if version
+            .as_deref()
+            .is_some_and(|v| semver::Version::parse(v).is_err())
+        {
+            *version = None;
+        }
+    }
+}
+
+fn parse(raw: &str) -> Result<Settings, String> {
+    from_value(serde_json::from_str(raw).map_err(|e| e.to_string())?)
+}
+
+// Versioned JSON → Settings, migrating older shapes (also used for imports).
+pub fn from_value(mut value: Value) -> Result<Settings, String> {
+    let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+    // A newer file (e.g. after rolling back the app) is read as-is: unknown
+    // keys are ignored and missing ones take their defaults.
+    if version < SCHEMA_VERSION {
+        migrate(&mut value, version)?;
+    }
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+// Moves an unreadable file aside so it can be inspected or restored by hand.
+fn quarantine(path: &Path) -> Result<PathBuf, String> {
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let target = path.with_file_name(format!("settings.corrupt-{stamp}.json"));
+    fs::rename(path, &target).map_err(|e| e.to_string())?;
+    Ok(target)
+}
+
+impl SettingsState {
+    // A missing file means defaults; a corrupt one is quarantined and reported
+    // instead of being silently overwritten.
+    pub fn load(app: &AppHandle) -> Self {
+        let mut warnings = Vec::new();
+        let path = match settings_path(app) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!("settings path unavailable: {e}");
+                return Self::default();
+            }
+        };
+        let mut settings = match fs::read_to_string(&path) {
+            Err(_) => Settings::default(),
+            Ok(raw) => match parse(&raw) {
+                Ok(s) => s,
+                Err(e) => {
+                    error!("settings file is corrupt: {e}");
+                    match quarantine(&path) {
+                        Ok(moved) => warnings.push(t_args(
+                            "settings-corrupt",
+                            &[("path", moved.display().to_string().into())],
+                        )),
+                        Err(e) => error!("settings quarantine failed: {e}"),
+                    }
+                    Settings::default()
+                }
+            },
+        };
+        validate(&mut settings, &mut warnings);
+        if crate::vault::seal_plaintext(&mut settings.secrets) {
+            if let Err(e) = save(app, &settings) {
+                error!("settings save failed: {e}");
+            }
+        }
+        Self {
+            settings: Mutex::new(settings),
+            warnings: Mutex::new(warnings),
+        }
+    }
+}
+
+pub fn to_value(settings: &Settings) -> Result<Value, String> {
+    let mut value = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    value["version"] = SCHEMA_VERSION.into();
+    Ok(value)
+}
+
+// Drops what only makes sense on this machine (update bookkeeping, sealed
+// secrets, sync setup), for settings leaving it as an export or sync snapshot.
+pub fn portable(settings: &Settings) -> Settings {
+    let mut portable = settings.clone();
+    portable.digest.last_sent_on = None;
+    portable.update.previous_version = None;
+    portable.update.previous_backend = None;
+    portable.update.skipped_version = None;
+    portable.update.snoozed_until = None;
+    portable.onboarding = Default::default();
+    portable.secrets.clear();
+    portable.sync = Default::default();
+    // Another machine may have no Touch ID / Windows Hello to unlock with
+    portable.lock = Default::default();
+    // Consent is given per machine
+    portable.telemetry = Default::default();
+    portable.error_reporting.enabled = false;
+    portable.debug_mode = false;
+    // The key is in this machine's keychain
+    portable.encrypt_history = false;
+    // Depends on this machine's desktop
+    portable.background_without_tray = false;
+    // Depends on this machine's OS version and management
+    portable.login_item = Default::default();
+    // The password is in this machine's keychain
+    portable.mail.enabled = false;
+    // Deleting is agreed to on each machine
+    portable.retention.enabled = false;
+    portable.retention.last_run_at = None;
+    // This machine's engine
+    portable.working_hours.override_until = None;
+    portable.working_hours.holding = false;
+    portable
+}
+
+// The reverse: settings arriving from elsewhere keep this machine's local state.
+pub fn with_local(mut incoming: Settings, local: &Settings) -> Settings {
+    incoming.digest.last_sent_on = local.digest.last_sent_on.clone();
+    incoming.update.previous_version = local.update.previous_version.clone();
+    incoming.update.previous_backend = local.update.previous_backend.clone();
+    incoming.update.skipped_version = local.update.skipped_version.clone();
+    incoming.update.snoozed_until = local.update.snoozed_until.clone();
+    incoming.onboarding = local.onboarding.clone();
+    incoming.secrets = local.secrets.clone();
+    incoming.sync = local.sync.clone();
+    incoming.lock = local.lock.clone();
+    incoming.telemetry = local.telemetry.clone();
+    incoming.error_reporting.enabled = local.error_reporting.enabled;
+    incoming.debug_mode = local.debug_mode;
+    incoming.encrypt_history = local.encrypt_history;
+    incoming.background_without_tray = local.background_without_tray;
+    incoming.login_item = local.login_item;
+    incoming.mail.enabled = local.mail.enabled;
+    incoming.retention.enabled = local.retention.enabled;
+    incoming.retention.last_run_at = local.retention.last_run_at.clone();
+    incoming.working_hours.override_until = local.working_hours.override_until;
+    incoming.working_hours.holding = local.working_hours.holding;
+    incoming
+}
+
+// Same checks as at startup, for settings arriving from an import.
+pub fn validated(mut settings: Settings) -> (Settings, Vec<String>) {
+    let mut warnings = Vec::new();
+    validate(&mut settings, &mut warnings);
+    (settings, warnings)
+}
+
+fn save(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = settings_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&to_value(settings)?).map_err(|e| e.to_string())?;
+    // Write-then-rename so a crash mid-write never leaves a truncated file
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+// Re-reads the file after the config dir changed (profile switch).
+pub fn reload(app: &AppHandle) {
+    let fresh = SettingsState::load(app);
+    let state = app.state::<SettingsState>();
+    *state.settings.lock().unwrap() = fresh.settings.into_inner().unwrap();
+    state
+        .warnings
+        .lock()
+        .unwrap()
+        .extend(fresh.warnings.into_inner().unwrap());
+    crate::i18n::apply(app);
+    crate::redact::refresh(app);
+    crate::logging::refresh_levels(app);
+    report_warnings(app);
+    let _ = app.emit(SETTINGS_CHANGED, get(app));
+    crate::flags::notify_changed(app);
+}
+
+// Shows load-time problems once; later calls find the list empty.
+pub fn report_warnings(app: &AppHandle) {
+    let warnings = std::mem::take(&mut *app.state::<SettingsState>().warnings.lock().unwrap());
+    for warning in warnings {
+        crate::notifications::notify(app, &t("settings-title"), &warning);
+    }
+}
+
+pub fn get(app: &AppHandle) -> Settings {
+    app.state::<SettingsState>()
+        .settings
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+// Applies `f` to the in-memory settings and persists the result.
+pub fn update(app: &AppHandle, f: impl FnOnce(&mut Settings)) -> Result<Settings, String> {
+    let (previous, updated) = {
+        let state = app.state::<SettingsState>();
+        let mut settings = state.settings.lock().unwrap();
+        let previous = settings.clone();
+        f(&mut settings);
+        save(app, &settings)?;
+        (previous, settings.clone())
+    };
+    audit_changes(app, &previous, &updated, "app");
+    let _ = app.emit(SETTINGS_CHANGED, &updated);
+    Ok(updated)
+}
+
+// Bookkeeping the app writes for itself; not a change anyone made.
+const UNAUDITED: &[&str] = &[
+    "digest.last_sent_on",
+    "backup.last_backup_at",
+    "update.previous_version",
+    "update.previous_backend",
+    "update.skipped_version",
+    "update.snoozed_until",
+    "onboarding",
+    "working_hours.holding",
+];
+
+// Dotted paths of the leaves that differ.
+fn changed_paths(before: &Value, after: &Value, path: &str, out: &mut Vec<String>) {
+    if before == after {
+        return;
+    }
+    if let (Some(b), Some(a)) = (before.as_object(), after.as_object()) {
+        for key in b.keys().chain(a.keys().filter(|k| !b.contains_key(*k))) {
+            let child = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{path}.{key}")
+            };
+            changed_paths(
+                b.get(key).unwrap_or(&Value::Null),
+                a.get(key).unwrap_or(&Value::Null),
+                &child,
+                out,
+            );
+        }
+    } else {
+        out.push(path.to_string());
+    }
+}
+
+// Only which fields changed is recorded, never their values.
+fn audit_changes(app: &AppHandle, previous: &Settings, current: &Settings, source: &str) {
+    let (Ok(before), Ok(after)) = (
+        serde_json::to_value(previous),
+        serde_json::to_value(current),
+    ) else {
+        return;
+    };
+    let mut fields = Vec::new();
+    changed_paths(&before, &after, "", &mut fields);
+    fields.retain(|f| {
+        !UNAUDITED
+            .iter()
+            .any(|u| f == u || f.starts_with(&format!("{u}.")))
+    });
+    if !fields.is_empty() {
+        let detail = json!({ "fields": fields, "source": source });
+        audit::record(app, Action::SettingsChanged, detail);
+    }
+}
+
+fn modified(app: &AppHandle) -> Option<SystemTime> {
+    fs::metadata(settings_path(app).ok()?).ok()?.modified().ok()
+}
+
+// Re-applies the parts of the settings that are read once rather than on use.
+// Swaps in settings that came from elsewhere (sync) and reacts like an edit
+// on disk would.
+pub fn replace(app: &AppHandle, fresh: Settings) -> Result<(), String> {
+    let previous = get(app);
+    let current = update(app, |s| *s = fresh)?;
+    apply(app, &previous, &current);
+    Ok(())
+}
+
+fn apply(app: &AppHandle, previous: &Settings, current: &Settings) {
+    let proxy_changed =
+        serde_json::to_value(&previous.proxy).ok() != serde_json::to_value(&current.proxy).ok();
+    if proxy_changed {
+        crate::proxy::refresh(app);
+    }
+    // The engine reads secrets and redaction rules from its environment
+    if proxy_changed
+        || previous.secrets != current.secrets
+        || previous.redaction != current.redaction
+    {
+        if let Err(e) = crate::restart_backend(app) {
+            error!("backend restart failed: {e}");
+        }
+    }
+    if previous.redaction != current.redaction {
+        crate::redact::refresh(app);
+    }
+    if previous.telemetry != current.telemetry {
+        if let Err(e) = crate::telemetry::refresh(app) {
+            warn!("pending usage counts not removed: {e}");
+        }
+    }
+    if previous.logs != current.logs {
+        crate::logging::refresh_levels(app);
+        crate::logretention::enforce(app);
+    }
+    if previous.language != current.language {
+        crate::i18n::apply(app);
+    }
+    if previous.flags != current.flags {
+        crate::flags::notify_changed(app);
+    }
+    if previous.debug_mode != current.debug_mode {
+        crate::debugmode::refresh(app);
+    }
+    if previous.power != current.power {
+        crate::keepawake::refresh(app);
+    }
+    if previous.menu_bar_only != current.menu_bar_only {
+        crate::menubar::refresh(app);
+    }
+    crate::refresh_tray_menu(app);
+}
+
+fn reload_external(app: &AppHandle) -> Result<(), String> {
+    // A deleted file keeps the in-memory settings until the next save
+    let Ok(raw) = fs::read_to_string(settings_path(app)?) else {
+        return Ok(());
+    };
+    // Half-written files fail here and are picked up on the next change
+    let (mut fresh, warnings) = validated(parse(&raw)?);
+    // Secrets pasted in as plain text are encrypted straight away
+    if crate::vault::seal_plaintext(&mut fresh.secrets) {
+        save(app, &fresh)?;
+    }
+    let previous = {
+        let state = app.state::<SettingsState>();
+        let mut current = state.settings.lock().unwrap();
+        // Our own saves land here too and change nothing
+        if to_value(&current)? == to_value(&fresh)? {
+            return Ok(());
+        }
+        std::mem::replace(&mut *current, fresh.clone())
+    };
+    for warning in warnings {
+        crate::notifications::notify(app, &t("settings-title"), &warning);
+    }
+    audit_changes(app, &previous, &fresh, "file");
+    apply(app, &previous, &fresh);
+    let _ = app.emit(SETTINGS_CHANGED, &fresh);
+    Ok(())
+}
+
+// Picks up edits made outside the app (text editor, sync tools) without a
+// restart. Polls the mtime like the other background loops.
+pub fn watch(app: AppHandle) {
+    thread::spawn(move || {
+        let mut last_seen = modified(&app);
+        loop {
+            thread::sleep(WATCH_INTERVAL);
+            let seen = modified(&app);
+            if seen == last_seen {
+                continue;
+            }
+            last_seen = seen;
+            if let Err(e) = reload_external(&app) {
+                warn!("settings reload skipped: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_unversioned_files() {
+        let settings = from_value(json!({ "digest": { "time": "07:30" } })).unwrap();
+        assert_eq!(settings.digest.time, "07:30");
+        // Anyone with a settings file from before onboarding is past it
+        assert_eq!(settings.onboarding.step, OnboardingStep::Done);
+        assert_eq!(to_value(&settings).unwrap()["version"], SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn reads_newer_files_as_they_are() {
+        let settings = from_value(json!({
+            "version": SCHEMA_VERSION + 1,
+            "digest": { "enabled": false },
+            "from_the_future": true,
+        }))
+        .unwrap();
+        assert!(!settings.digest.enabled);
+        assert_eq!(settings.digest.time, DigestSettings::default().time);
+        assert!(from_value(json!({ "digest": { "enabled": "yes" } })).is_err());
+    }
+
+    #[test]
+    fn validation_resets_unusable_fields() {
+        let mut settings = Settings::default();
+        settings.digest.time = "25:99".into();
+        settings.lock.idle_minutes = 0;
+        settings.working_hours.from_hour = 20;
+        settings.working_hours.to_hour = 8;
+        settings.working_hours.days = vec![0, 9];
+        settings.update.skipped_version = Some("not a version".into());
+        settings.update.previous_version = Some("1.2.3".into());
+        let (settings, warnings) = validated(settings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(settings.digest.time, DigestSettings::default().time);
+        assert_eq!(
+            settings.lock.idle_minutes,
+            LockSettings::default().idle_minutes
+        );
+        assert_eq!(
+            settings.working_hours.days,
+            WorkingHoursSettings::default().days
+        );
+        assert_eq!(settings.working_hours.from_hour, 8);
+        assert_eq!(settings.update.skipped_version, None);
+        assert_eq!(settings.update.previous_version.as_deref(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn local_state_stays_on_the_machine() {
+        let mut local = Settings::default();
+        local.digest.last_sent_on = Some("2026-10-15".into());
+        local.debug_mode = true;
+        local.encrypt_history = true;
+        local.working_hours.holding = true;
+        local.digest.time = "07:00".into();
+        let leaving = portable(&local);
+        assert_eq!(leaving.digest.last_sent_on, None);
+        assert!(!leaving.debug_mode);
+        assert!(!leaving.encrypt_history);
+        assert!(!leaving.working_hours.holding);
+        // What's shared goes with it
+        assert_eq!(leaving.digest.time, "07:00");
+
+        let mut incoming = leaving.clone();
+        incoming.digest.time = "21:00".into();
+        let merged = with_local(incoming, &local);
+        assert_eq!(merged.digest.time, "21:00");
+        assert_eq!(merged.digest.last_sent_on.as_deref(), Some("2026-10-15"));
+        assert!(merged.debug_mode && merged.encrypt_history && merged.working_hours.holding);
+    }
+
+    #[test]
+    fn portable_then_local_is_unchanged() {
+        let mut local = Settings::default();
+        local.update.snoozed_until = Some("2026-10-20T00:00:00Z".into());
+        local.retention.enabled = true;
+        local.mail.enabled = true;
+        let round = with_local(portable(&local), &local);
+        assert_eq!(to_value(&round).unwrap(), to_value(&local).unwrap());
+    }
+
+    #[test]
+    fn changed_leaves() {
+        let before = json!({ "a": { "b": 1, "c": [1] }, "d": true });
+        let after = json!({ "a": { "b": 2, "c": [1] }, "e": "new" });
+        let mut fields = Vec::new();
+        changed_paths(&before, &after, "", &mut fields);
+        fields.sort();
+        assert_eq!(fields, ["a.b", "d", "e"]);
+    }
+}
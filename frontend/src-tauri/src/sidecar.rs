@@ -0,0 +1,468 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+use tracing::{error, warn};
+
+use crate::{
+    backend, i18n, notifications, quarantine,
+    settings::{self, UpdateChannel},
+    signing,
+};
+
+// Engine fixes ship far more often than shell changes, so the backend sidecar
+// can be updated on its own. Downloaded builds live in
+// <app data>/backend/<version>/ and state.json says which one runs; with no
+// active override the binary bundled with the app is used.
+const STABLE_MANIFEST: &str =
+    "https://github.com/IFAKA/currobot/releases/download/backend-latest/backend.json";
+const BETA_MANIFEST: &str =
+    "https://github.com/IFAKA/currobot/releases/download/backend-beta/backend.json";
+
+// SHA-256 of binaries/jobbot-backend-<target> at build time (build.rs).
+#[cfg(not(target_os = "macos"))]
+const BUNDLED_SHA256: Option<&str> = option_env!("JOBBOT_SIDECAR_SHA256");
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(45);
+// The PyInstaller build with its browser driver is a few hundred MB
+const MAX_DOWNLOAD_BYTES: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    version: String,
+    // Oldest shell that can drive this backend
+    #[serde(default)]
+    min_shell_version: Option<String>,
+    platforms: HashMap<String, Artifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    url: String,
+    sha256: String,
+    signature: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SidecarState {
+    active: Option<String>,
+    previous: Option<String>,
+    pinned: Option<String>,
+    // SHA-256 of each downloaded build, from its (signed-off) manifest entry
+    checksums: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackendVersionInfo {
+    pub bundled: String,
+    pub active: Option<String>,
+    pub previous: Option<String>,
+    pub pinned: Option<String>,
+    pub installed: Vec<String>,
+}
+
+fn root(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::paths::base_data_dir(app)?.join("backend"))
+}
+
+fn binary_path(app: &AppHandle, version: &str) -> Result<PathBuf, String> {
+    Ok(root(app)?
+        .join(version)
+        .join(format!("jobbot-backend{}", std::env::consts::EXE_SUFFIX)))
+}
+
+fn load_state(app: &AppHandle) -> SidecarState {
+    root(app)
+        .ok()
+        .and_then(|r| fs::read_to_string(r.join("state.json")).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(app: &AppHandle, state: &SidecarState) -> Result<(), String> {
+    let dir = root(app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(dir.join("state.json"), json).map_err(|e| e.to_string())
+}
+
+fn bundled_version(app: &AppHandle) -> semver::Version {
+    app.package_info().version.clone()
+}
+
+fn effective_version(app: &AppHandle, state: &SidecarState) -> semver::Version {
+    state
+        .active
+        .as_deref()
+        .and_then(|v| semver::Version::parse(v).ok())
+        .unwrap_or_else(|| bundled_version(app))
+}
+
+fn file_sha256(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(signing::sha256_hex(&bytes))
+}
+
+// Where the bundler puts the sidecar: next to the app executable.
+fn bundled_binary() -> Result<PathBuf, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let dir = exe.parent().ok_or("app executable has no parent dir")?;
+    Ok(dir.join(format!("jobbot-backend{}", std::env::consts::EXE_SUFFIX)))
+}
+
+// macOS bundles re-sign the sidecar (ad hoc), which changes its bytes, so the
+// build-time hash can't match there; the code signature covers the same
+// ground and `codesign --verify` catches edits and truncation.
+#[cfg(target_os = "macos")]
+fn verify_bundled() -> Result<(), String> {
+    if cfg!(debug_assertions) {
+        return Ok(());
+    }
+    let path = bundled_binary()?;
+    let status = std::process::Command::new("/usr/bin/codesign")
+        .args(["--verify", "--strict"])
+        .arg(&path)
+        .status()
+        .map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err(format!("{} has an invalid code signature", path.display()));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn verify_bundled() -> Result<(), String> {
+    // Source checkouts without a built backend have nothing to compare
+    let Some(expected) = BUNDLED_SHA256 else {
+        return Ok(());
+    };
+    let path = bundled_binary()?;
+    let actual = file_sha256(&path)?;
+    if actual != expected {
+        return Err(format!(
+            "{} does not match this build (sha256 {actual}, expected {expected})",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+// Downloads made before checksums were recorded are trusted once and pinned
+// to their current hash.
+fn verify_downloaded(app: &AppHandle, version: &str, path: &Path) -> Result<(), String> {
+    let actual = file_sha256(path)?;
+    let mut state = load_state(app);
+    match state.checksums.get(version) {
+        Some(expected) if *expected == actual => Ok(()),
+        Some(expected) => Err(format!(
+            "{} does not match its download (sha256 {actual}, expected {expected})",
+            path.display()
+        )),
+        None => {
+            state.checksums.insert(version.to_string(), actual);
+            save_state(app, &state)
+        }
+    }
+}
+
+// The binary to launch, checked right before every spawn: Some(path) for a
+// downloaded backend, None for the bundled sidecar. A downloaded build that
+// fails falls back to the bundled one; if that fails too nothing is started.
+pub fn verified_binary(app: &AppHandle) -> Result<Option<PathBuf>, String> {
+    let active = load_state(app).active;
+    if let Some(version) = active {
+        let path = binary_path(app, &version)?;
+        if path.exists() {
+            match verify_downloaded(app, &version, &path) {
+                Ok(()) => return Ok(Some(path)),
+                Err(e) => {
+                    error!("downloaded backend rejected: {e}");
+                    notifications::notify(
+                        app,
+                        &i18n::t("engine-integrity-title"),
+                        &i18n::t_args(
+                            "engine-update-integrity-body",
+                            &[("version", version.as_str().into())],
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    verify_bundled().map_err(|e| {
+        notifications::notify(
+            app,
+            &i18n::t("engine-integrity-title"),
+            &i18n::t("engine-integrity-body"),
+        );
+        format!("backend integrity check failed, not starting it: {e}")
+    })?;
+    Ok(None)
+}
+
+// Version of the downloaded backend currently selected, None = bundled.
+pub fn active_version(app: &AppHandle) -> Option<String> {
+    load_state(app).active
+}
+
+// Selects a backend for the next launch without restarting anything; used when
+// the whole app is about to be replaced and relaunched.
+pub fn select_for_next_launch(app: &AppHandle, version: Option<String>) -> Result<(), String> {
+    let mut state = load_state(app);
+    if state.active == version {
+        return Ok(());
+    }
+    state.previous = state.active.take();
+    state.active = version;
+    save_state(app, &state)
+}
+
+fn wait_healthy() -> bool {
+    let started = Instant::now();
+    while started.elapsed() < HEALTH_TIMEOUT {
+        if backend::get_json("/api/health").is_ok() {
+            return true;
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+    false
+}
+
+// Switches to `version` (None = bundled), restarts the backend and rolls back
+// automatically if the new one never becomes healthy.
+fn activate(app: &AppHandle, version: Option<String>) -> Result<(), String> {
+    let mut state = load_state(app);
+    let before = state.clone();
+    state.previous = state.active.take();
+    state.active = version;
+    save_state(app, &state)?;
+    crate::restart_backend(app)?;
+    if wait_healthy() {
+        return Ok(());
+    }
+    save_state(app, &before)?;
+    crate::restart_backend(app)?;
+    Err(format!(
+        "backend {} did not become healthy; rolled back",
+        state.active.as_deref().unwrap_or("bundled")
+    ))
+}
+
+fn download(app: &AppHandle, manifest: &Manifest) -> Result<(), String> {
+    let target = env!("TAURI_ENV_TARGET_TRIPLE");
+    let artifact = manifest
+        .platforms
+        .get(target)
+        .ok_or_else(|| format!("no backend build for {target}"))?;
+    let file = quarantine::fetch(
+        app,
+        &artifact.url,
+        Duration::from_secs(600),
+        &quarantine::Expected {
+            name: &format!("engine {}", manifest.version),
+            max_bytes: MAX_DOWNLOAD_BYTES,
+            file_type: quarantine::FileType::Executable,
+            sha256: &artifact.sha256,
+            signature: &artifact.signature,
+        },
+    )?;
+    file.release(&binary_path(app, &manifest.version)?)?;
+    let mut state = load_state(app);
+    state
+        .checksums
+        .insert(manifest.version.clone(), artifact.sha256.to_lowercase());
+    save_state(app, &state)
+}
+
+fn fetch_manifest(app: &AppHandle) -> Result<Manifest, String> {
+    let url = match settings::get(app).update.channel {
+        UpdateChannel::Stable => STABLE_MANIFEST,
+        UpdateChannel::Beta => BETA_MANIFEST,
+    };
+    crate::net::client(Duration::from_secs(30))?
+        .get(url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.json())
+        .map_err(|e| e.to_string())
+}
+
+// Whether the manifest's build is newer than the one `running`, and one this
+// shell can drive.
+fn is_upgrade(
+    manifest: &Manifest,
+    running: &semver::Version,
+    shell: &semver::Version,
+) -> Result<bool, String> {
+    let remote = semver::Version::parse(&manifest.version).map_err(|e| e.to_string())?;
+    if remote <= *running {
+        return Ok(false);
+    }
+    match &manifest.min_shell_version {
+        Some(min) => Ok(*shell >= semver::Version::parse(min).map_err(|e| e.to_string())?),
+        None => Ok(true),
+    }
+}
+
+// Returns the version that was installed, if any.
+fn check_and_apply(app: &AppHandle) -> Result<Option<String>, String> {
+    let state = load_state(app);
+    if state.pinned.is_some() {
+        return Ok(None);
+    }
+    let manifest = fetch_manifest(app)?;
+    if !is_upgrade(
+        &manifest,
+        &effective_version(app, &state),
+        &bundled_version(app),
+    )? {
+        return Ok(None);
+    }
+    if !binary_path(app, &manifest.version)?.exists() {
+        download(app, &manifest)?;
+    }
+    activate(app, Some(manifest.version.clone()))?;
+    notifications::notify(
+        app,
+        &i18n::t("engine-updated-title"),
+        &i18n::t_args(
+            "engine-updated-body",
+            &[("version", manifest.version.as_str().into())],
+        ),
+    );
+    Ok(Some(manifest.version))
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+        if let Err(e) = check_and_apply(&app) {
+            warn!("backend update failed: {e}");
+        }
+    });
+}
+
+// Downloads and health waits block, so commands run them off the IPC thread.
+async fn run_blocking<T: Send + 'static>(
+    app: AppHandle,
+    f: impl FnOnce(&AppHandle) -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(move || f(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_backend_version_info(app: AppHandle) -> BackendVersionInfo {
+    let state = load_state(&app);
+    let mut installed: Vec<String> = root(&app)
+        .ok()
+        .and_then(|r| fs::read_dir(r).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect();
+    installed.sort();
+    BackendVersionInfo {
+        bundled: bundled_version(&app).to_string(),
+        active: state.active,
+        previous: state.previous,
+        pinned: state.pinned,
+        installed,
+    }
+}
+
+#[tauri::command]
+pub async fn check_backend_update(app: AppHandle) -> Result<Option<String>, String> {
+    crate::paths::updates_allowed()?;
+    run_blocking(app, check_and_apply).await
+}
+
+#[tauri::command]
+pub async fn rollback_backend(app: AppHandle) -> Result<(), String> {
+    run_blocking(app, |app| {
+        let previous = load_state(app).previous;
+        activate(app, previous)
+    })
+    .await
+}
+
+// Pinning to `None` resumes automatic updates; pinning to a version that is
+// installed (or "bundled") switches to it immediately.
+#[tauri::command]
+pub async fn pin_backend_version(app: AppHandle, version: Option<String>) -> Result<(), String> {
+    run_blocking(app, move |app| {
+        if let Some(v) = version.as_deref().filter(|v| *v != "bundled") {
+            semver::Version::parse(v).map_err(|e| format!("invalid version {v}: {e}"))?;
+        }
+        let mut state = load_state(app);
+        state.pinned = version.clone();
+        save_state(app, &state)?;
+        match version.as_deref() {
+            None => Ok(()),
+            Some("bundled") => activate(app, None),
+            Some(v) if state.active.as_deref() == Some(v) => Ok(()),
+            Some(v) if binary_path(app, v)?.exists() => activate(app, Some(v.to_string())),
+            Some(v) => Err(format!("backend {v} is not installed")),
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn manifest(version: &str, min_shell_version: Option<&str>) -> Manifest {
+        serde_json::from_value(json!({
+            "version": version,
+            "min_shell_version": min_shell_version,
+            "platforms": {
+                "x86_64-unknown-linux-gnu": {
+                    "url": "https://example.com/jobbot-backend",
+                    "sha256": "00",
+                    "signature": "c2ln",
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn version(v: &str) -> semver::Version {
+        semver::Version::parse(v).unwrap()
+    }
+
+    #[test]
+    fn only_newer_builds() {
+        let shell = version("1.0.0");
+        assert!(is_upgrade(&manifest("1.2.0", None), &version("1.1.0"), &shell).unwrap());
+        assert!(!is_upgrade(&manifest("1.1.0", None), &version("1.1.0"), &shell).unwrap());
+        assert!(!is_upgrade(&manifest("1.0.9", None), &version("1.1.0"), &shell).unwrap());
+        assert!(is_upgrade(&manifest("soon", None), &version("1.1.0"), &shell).is_err());
+    }
+
+    #[test]
+    fn only_for_shells_that_can_drive_them() {
+        let running = version("1.0.0");
+        let needs = manifest("1.5.0", Some("1.4.0"));
+        assert!(!is_upgrade(&needs, &running, &version("1.3.9")).unwrap());
+        assert!(is_upgrade(&needs, &running, &version("1.4.0")).unwrap());
+    }
+
+    #[test]
+    fn state_from_older_files() {
+        let state: SidecarState = serde_json::from_value(json!({ "active": "1.2.0" })).unwrap();
+        assert_eq!(state.active.as_deref(), Some("1.2.0"));
+        assert!(state.pinned.is_none() && state.checksums.is_empty());
+    }
+}
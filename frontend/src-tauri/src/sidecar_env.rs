@@ -0,0 +1,170 @@
+use std::{env, ffi::OsString, path::PathBuf};
+use tracing::warn;
+
+// The engine starts from an empty environment plus the variables below, so
+// loader tricks (LD_PRELOAD, DYLD_*), a stray PYTHONPATH or whatever else the
+// user's shell exports can't change how it behaves. Everything it needs from
+// the shell is set explicitly in `spawn_backend`.
+const INHERITED: &[&str] = &[
+    // Basics any process expects
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "LANG",
+    "LANGUAGE",
+    "TZ",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    // Headed browser windows for human review (X11 / Wayland / D-Bus)
+    "DISPLAY",
+    "WAYLAND_DISPLAY",
+    "XAUTHORITY",
+    "XDG_RUNTIME_DIR",
+    "XDG_CONFIG_HOME",
+    "XDG_CACHE_HOME",
+    "XDG_DATA_HOME",
+    "DBUS_SESSION_BUS_ADDRESS",
+    // macOS text encoding for the process
+    "__CF_USER_TEXT_ENCODING",
+    // Windows can't start much without these
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+    "USERNAME",
+    "USERPROFILE",
+    "HOMEDRIVE",
+    "HOMEPATH",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PROGRAMDATA",
+    "PROGRAMFILES",
+    "PROGRAMFILES(X86)",
+    "PROGRAMW6432",
+    "COMMONPROGRAMFILES",
+    "NUMBER_OF_PROCESSORS",
+    "PROCESSOR_ARCHITECTURE",
+    // Corporate CA bundles
+    "SSL_CERT_FILE",
+    "SSL_CERT_DIR",
+    "REQUESTS_CA_BUNDLE",
+    // proxy::Resolved::Auto leaves these to the environment
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "NO_PROXY",
+    // Read by the engine's own settings and Playwright
+    "OLLAMA_HOST",
+    "PLAYWRIGHT_BROWSERS_PATH",
+];
+
+const INHERITED_PREFIXES: &[&str] = &["LC_"];
+
+// Dropped anyway; named in the log because someone set them on purpose.
+const REPORTED: &[&str] = &["LD_", "DYLD_", "PYTHON"];
+
+fn allowed(name: &str) -> bool {
+    // Windows names are case-insensitive, and proxy variables come in both cases
+    let upper = name.to_ascii_uppercase();
+    INHERITED.contains(&upper.as_str()) || INHERITED_PREFIXES.iter().any(|p| upper.starts_with(p))
+}
+
+// Absolute directories that exist, each once. Relative entries (".", "bin")
+// resolve against whatever the working directory is and are dropped.
+fn sanitized_path(path: &OsString) -> Option<OsString> {
+    let mut kept: Vec<PathBuf> = Vec::new();
+    for dir in env::split_paths(path) {
+        if dir.is_absolute() && dir.is_dir() && !kept.contains(&dir) {
+            kept.push(dir);
+        }
+    }
+    env::join_paths(kept).ok()
+}
+
+// Pairs to pass after clearing the child's environment.
+pub fn inherited() -> Vec<(OsString, OsString)> {
+    let mut vars = Vec::new();
+    let mut dropped = Vec::new();
+    for (name, value) in env::vars_os() {
+        let Some(key) = name.to_str() else {
+            continue;
+        };
+        if key.eq_ignore_ascii_case("PATH") {
+            if let Some(path) = sanitized_path(&value) {
+                vars.push((name, path));
+            }
+        } else if allowed(key) {
+            vars.push((name, value));
+        } else if REPORTED
+            .iter()
+            .any(|p| key.to_ascii_uppercase().starts_with(p))
+        {
+            dropped.push(key.to_string());
+        }
+    }
+    if !dropped.is_empty() {
+        warn!("not passing {} to the engine", dropped.join(", "));
+    }
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn names_match_in_any_case() {
+        assert!(allowed("HOME"));
+        assert!(allowed("SystemRoot"));
+        assert!(allowed("ProgramFiles(x86)"));
+        assert!(allowed("https_proxy"));
+        assert!(!allowed("HOMER"));
+        assert!(!allowed("PATH_EXTRA"));
+    }
+
+    #[test]
+    fn locale_prefixes_are_kept() {
+        assert!(allowed("LC_ALL"));
+        assert!(allowed("LC_TIME"));
+        assert!(allowed("lc_ctype"));
+        assert!(!allowed("LCX"));
+    }
+
+    #[test]
+    fn loader_and_python_variables_are_dropped() {
+        for name in [
+            "LD_PRELOAD",
+            "LD_LIBRARY_PATH",
+            "DYLD_INSERT_LIBRARIES",
+            "DYLD_LIBRARY_PATH",
+            "PYTHONPATH",
+            "PYTHONHOME",
+            "PYTHONSTARTUP",
+        ] {
+            assert!(!allowed(name), "{name}");
+            assert!(REPORTED.iter().any(|p| name.starts_with(p)), "{name}");
+        }
+    }
+
+    #[test]
+    fn path_keeps_existing_absolute_directories_once() {
+        let root = env::temp_dir().join(format!("jobbot-sidecar-env-{}", std::process::id()));
+        let (a, b) = (root.join("a"), root.join("b"));
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        let path = env::join_paths([
+            a.clone(),
+            PathBuf::from("bin"),
+            PathBuf::from("."),
+            root.join("missing"),
+            b.clone(),
+            a.clone(),
+        ])
+        .unwrap();
+        let kept: Vec<PathBuf> = env::split_paths(&sanitized_path(&path).unwrap()).collect();
+        assert_eq!(kept, [a, b]);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,75 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use minisign_verify::{PublicKey, Signature};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+
+// Artifacts the shell fetches outside the Tauri updater are signed with the
+// same key as app releases (`tauri signer sign`), so one public key — the one
+// in plugins.updater.pubkey — covers everything.
+fn updater_pubkey(app: &AppHandle) -> Result<String, String> {
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|u| u["pubkey"].as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "updater pubkey is not configured".into())
+}
+
+fn decode_b64(s: &str) -> Result<String, String> {
+    let bytes = STANDARD.decode(s.trim()).map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+// `signature` is the base64 `.sig` file content produced by `tauri signer sign`.
+pub fn verify(app: &AppHandle, data: &[u8], signature: &str) -> Result<(), String> {
+    verify_with(&updater_pubkey(app)?, data, signature)
+}
+
+// The same with `pubkey` in the config's form, base64 of the key file.
+fn verify_with(pubkey: &str, data: &[u8], signature: &str) -> Result<(), String> {
+    let key = PublicKey::decode(&decode_b64(pubkey)?).map_err(|e| e.to_string())?;
+    let sig = Signature::decode(&decode_b64(signature)?).map_err(|e| e.to_string())?;
+    key.verify(data, &sig, true)
+        .map_err(|e| format!("signature mismatch: {e}"))
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // minisign's own test key and a prehashed signature of "test", as
+    // `tauri signer sign` makes them
+    const PUBKEY: &str = "untrusted comment: minisign public key E7620F1842B4E81F
+RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1556193335\tfile:test
+y/rUw2y8/hOUYjZU71eHp/Wo1KZ40fGy2VJEDl34XMJM+TX48Ss/17u3IvIfbVR1FkZZSNCisQbuQY+bHwhEBg==";
+
+    #[test]
+    fn verifies_signed_files() {
+        let (key, sig) = (STANDARD.encode(PUBKEY), STANDARD.encode(SIGNATURE));
+        verify_with(&key, b"test", &sig).unwrap();
+        assert!(verify_with(&key, b"Test", &sig)
+            .unwrap_err()
+            .starts_with("signature mismatch"));
+        assert!(verify_with(&key, b"test", "not base64!").is_err());
+        assert!(verify_with(&key, b"test", &STANDARD.encode("not a signature")).is_err());
+    }
+
+    #[test]
+    fn sha256() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
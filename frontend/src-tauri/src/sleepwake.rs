@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use std::{
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{applock, backend, events};
+
+// System sleep and wake. Across a sleep the event stream can be left open
+// but dead and the pages' own connections dropped, so on wake the shell
+// checks the engine answers (restarting it if not), opens a fresh event
+// stream and tells the windows to reconnect theirs. The heartbeat isn't
+// expected while asleep, and timers on the monotonic clock, which stops
+// during sleep on some platforms, are moved on by the time slept.
+pub const SYSTEM_RESUMED: &str = "system-resumed";
+const ENGINE_WAIT: Duration = Duration::from_secs(30);
+
+struct Asleep {
+    wall: SystemTime,
+    monotonic: Instant,
+}
+
+static ASLEEP: Mutex<Option<Asleep>> = Mutex::new(None);
+static WOKE_AT: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+pub fn is_asleep() -> bool {
+    ASLEEP.lock().unwrap().is_some()
+}
+
+// When the system last woke, for the health report's heartbeat check.
+pub fn woke_at() -> Option<DateTime<Utc>> {
+    *WOKE_AT.lock().unwrap()
+}
+
+pub fn start(app: AppHandle) {
+    platform::watch(app);
+}
+
+fn on_suspend() {
+    info!("system going to sleep");
+    *ASLEEP.lock().unwrap() = Some(Asleep {
+        wall: SystemTime::now(),
+        monotonic: Instant::now(),
+    });
+}
+
+fn on_resume(app: &AppHandle) {
+    let asleep = ASLEEP.lock().unwrap().take();
+    *WOKE_AT.lock().unwrap() = Some(Utc::now());
+    // Time the wall clock saw that the monotonic one didn't
+    let missed = asleep
+        .and_then(|a| {
+            let wall = a.wall.elapsed().ok()?;
+            Some(wall.saturating_sub(a.monotonic.elapsed()))
+        })
+        .unwrap_or_default();
+    info!(missed_secs = missed.as_secs(), "system woke");
+    applock::on_wake(app, missed);
+    let app = app.clone();
+    thread::spawn(move || {
+        if !wait_responding() {
+            warn!("engine not answering after wake; restarting it");
+            if let Err(e) = crate::restart_backend(&app) {
+                warn!("engine restart failed: {e}");
+            }
+        }
+        events::reconnect(&app);
+        let _ = app.emit(SYSTEM_RESUMED, ());
+    });
+}
+
+// The network can take a moment to come back after wake.
+fn wait_responding() -> bool {
+    let started = Instant::now();
+    while started.elapsed() < ENGINE_WAIT {
+        if backend::get_json("/api/health").is_ok() {
+            return true;
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+    false
+}
+
+// NSWorkspace's sleep and wake notifications.
+#[cfg(target_os = "macos")]
+mod platform {
+    use block2::RcBlock;
+    use objc2_app_kit::{
+        NSWorkspace, NSWorkspaceDidWakeNotification, NSWorkspaceWillSleepNotification,
+    };
+    use objc2_foundation::NSNotification;
+    use std::ptr::NonNull;
+    use tauri::AppHandle;
+
+    pub fn watch(app: AppHandle) {
+        let center = NSWorkspace::sharedWorkspace().notificationCenter();
+        let will_sleep = RcBlock::new(|_: NonNull<NSNotification>| super::on_suspend());
+        let did_wake = RcBlock::new(move |_: NonNull<NSNotification>| super::on_resume(&app));
+        unsafe {
+            // Observers live as long as the app
+            std::mem::forget(center.addObserverForName_object_queue_usingBlock(
+                Some(NSWorkspaceWillSleepNotification),
+                None,
+                None,
+                &will_sleep,
+            ));
+            std::mem::forget(center.addObserverForName_object_queue_usingBlock(
+                Some(NSWorkspaceDidWakeNotification),
+                None,
+                None,
+                &did_wake,
+            ));
+        }
+    }
+}
+
+// Suspend and resume callbacks from the power manager, without a window.
+#[cfg(windows)]
+mod platform {
+    use std::{ffi::c_void, sync::OnceLock};
+    use tauri::AppHandle;
+    use tracing::warn;
+    use windows::Win32::{
+        Foundation::{ERROR_SUCCESS, HANDLE},
+        System::Power::{
+            PowerRegisterSuspendResumeNotification, DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS,
+        },
+        UI::WindowsAndMessaging::{DEVICE_NOTIFY_CALLBACK, PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND},
+    };
+
+    static APP: OnceLock<AppHandle> = OnceLock::new();
+
+    unsafe extern "system" fn on_power(_: *const c_void, kind: u32, _: *const c_void) -> u32 {
+        match (kind, APP.get()) {
+            (PBT_APMSUSPEND, _) => super::on_suspend(),
+            // Sent on every wake, whether or not someone is at the keyboard
+            (PBT_APMRESUMEAUTOMATIC, Some(app)) => super::on_resume(app),
+            _ => {}
+        }
+        0
+    }
+
+    pub fn watch(app: AppHandle) {
+        if APP.set(app).is_err() {
+            return;
+        }
+        // Read by the system for as long as the registration lasts
+        let parameters = Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+            Callback: Some(on_power),
+            Context: std::ptr::null_mut(),
+        }));
+        let mut registration = std::ptr::null_mut();
+        let result = unsafe {
+            PowerRegisterSuspendResumeNotification(
+                DEVICE_NOTIFY_CALLBACK,
+                HANDLE(parameters as *mut DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS as *mut c_void),
+                &mut registration,
+            )
+        };
+        if result != ERROR_SUCCESS {
+            warn!("sleep and wake not watched: {result:?}");
+        }
+    }
+}
+
+// logind's PrepareForSleep signal: true before sleeping, false after waking.
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::thread;
+    use tauri::AppHandle;
+    use tracing::warn;
+    use zbus::blocking::{Connection, Proxy};
+
+    fn listen(app: &AppHandle) -> zbus::Result<()> {
+        let connection = Connection::system()?;
+        let proxy = Proxy::new(
+            &connection,
+            "org.freedesktop.login1",
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+        )?;
+        for signal in proxy.receive_signal("PrepareForSleep")? {
+            if signal.body().deserialize::<bool>()? {
+                super::on_suspend();
+            } else {
+                super::on_resume(app);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn watch(app: AppHandle) {
+        thread::spawn(move || {
+            if let Err(e) = listen(&app) {
+                warn!("sleep and wake not watched: {e}");
+            }
+        });
+    }
+}
@@ -0,0 +1,257 @@
+use chrono::{Local, NaiveDateTime};
+use rust_xlsxwriter::{Format, Workbook};
+use serde::Deserialize;
+use std::{fs, path::Path};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+use tracing::info;
+
+use crate::{
+    history::{self, HistoryEntry, HistoryQuery},
+    i18n::t,
+};
+
+// The application history (history.rs) as a spreadsheet, for a careers
+// adviser or the user's own tracking: CSV or Excel, the columns chosen in
+// the order given, applications created within an optional date range. The
+// file goes where the save dialog says.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileType {
+    Csv,
+    Xlsx,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Id,
+    Company,
+    Role,
+    Url,
+    Status,
+    CvProfile,
+    CreatedAt,
+    UpdatedAt,
+    AuthorizedAt,
+    SubmittedAt,
+}
+
+// A cell: text, or a time shown in local time.
+enum Cell {
+    Text(String),
+    Time(Option<NaiveDateTime>),
+}
+
+impl Column {
+    fn title(self) -> String {
+        t(match self {
+            Column::Id => "export-column-id",
+            Column::Company => "export-column-company",
+            Column::Role => "export-column-role",
+            Column::Url => "export-column-url",
+            Column::Status => "export-column-status",
+            Column::CvProfile => "export-column-cv-profile",
+            Column::CreatedAt => "export-column-created-at",
+            Column::UpdatedAt => "export-column-updated-at",
+            Column::AuthorizedAt => "export-column-authorized-at",
+            Column::SubmittedAt => "export-column-submitted-at",
+        })
+    }
+
+    fn cell(self, e: &HistoryEntry) -> Cell {
+        let text = |v: &Option<String>| Cell::Text(v.clone().unwrap_or_default());
+        match self {
+            Column::Id => Cell::Text(e.id.to_string()),
+            Column::Company => Cell::Text(e.company.clone()),
+            Column::Role => text(&e.role),
+            Column::Url => text(&e.url),
+            Column::Status => Cell::Text(e.status.replace('_', " ")),
+            Column::CvProfile => text(&e.cv_profile),
+            Column::CreatedAt => Cell::Time(history::local(&e.created_at)),
+            Column::UpdatedAt => Cell::Time(history::local(&e.updated_at)),
+            Column::AuthorizedAt => Cell::Time(history::local(&e.authorized_at)),
+            Column::SubmittedAt => Cell::Time(history::local(&e.submitted_at)),
+        }
+    }
+}
+
+// Scraped text starting like a formula is kept as text rather than run.
+fn csv_field(text: &str) -> String {
+    if text.starts_with(['=', '+', '-', '@']) {
+        return csv_field(&format!("'{text}"));
+    }
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn write_csv(path: &Path, columns: &[Column], entries: &[HistoryEntry]) -> Result<(), String> {
+    // The byte-order mark makes Excel read it as UTF-8
+    let mut out = String::from("\u{feff}");
+    let header: Vec<String> = columns.iter().map(|c| csv_field(&c.title())).collect();
+    out.push_str(&header.join(","));
+    out.push_str("\r\n");
+    for entry in entries {
+        let row: Vec<String> = columns
+            .iter()
+            .map(|c| match c.cell(entry) {
+                Cell::Text(text) => csv_field(&text),
+                Cell::Time(time) => time
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    fs::write(path, out).map_err(|e| e.to_string())
+}
+
+fn write_xlsx(path: &Path, columns: &[Column], entries: &[HistoryEntry]) -> Result<(), String> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let date = Format::new().set_num_format("yyyy-mm-dd hh:mm");
+    let sheet = workbook.add_worksheet();
+    sheet
+        .set_name(t("export-sheet-name"))
+        .map_err(|e| e.to_string())?;
+    for (col, column) in columns.iter().enumerate() {
+        sheet
+            .write_string_with_format(0, col as u16, column.title(), &bold)
+            .map_err(|e| e.to_string())?;
+    }
+    for (row, entry) in entries.iter().enumerate() {
+        let row = row as u32 + 1;
+        for (col, column) in columns.iter().enumerate() {
+            let col = col as u16;
+            match column.cell(entry) {
+                Cell::Text(text) => sheet.write_string(row, col, text),
+                Cell::Time(Some(time)) => sheet.write_datetime_with_format(row, col, time, &date),
+                Cell::Time(None) => continue,
+            }
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    sheet.set_freeze_panes(1, 0).map_err(|e| e.to_string())?;
+    sheet.autofit();
+    workbook.save(path).map_err(|e| e.to_string())
+}
+
+// The path written, or None when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_applications(
+    app: AppHandle,
+    format: FileType,
+    columns: Vec<Column>,
+    since: Option<String>,
+    until: Option<String>,
+) -> Result<Option<String>, String> {
+    if columns.is_empty() {
+        return Err("choose at least one column".into());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let query = HistoryQuery {
+            since,
+            until,
+            ..Default::default()
+        };
+        let entries = history::entries(&app, &query)?;
+        let (name, extension) = match format {
+            FileType::Csv => ("CSV", "csv"),
+            FileType::Xlsx => ("Excel", "xlsx"),
+        };
+        let Some(chosen) = app
+            .dialog()
+            .file()
+            .set_title(t("export-applications-title"))
+            .set_file_name(format!(
+                "applications-{}.{extension}",
+                Local::now().format("%Y-%m-%d")
+            ))
+            .add_filter(name, &[extension])
+            .blocking_save_file()
+        else {
+            return Ok(None);
+        };
+        let path = chosen.into_path().map_err(|e| e.to_string())?;
+        match format {
+            FileType::Csv => write_csv(&path, &columns, &entries)?,
+            FileType::Xlsx => write_xlsx(&path, &columns, &entries)?,
+        }
+        info!(count = entries.len(), "applications exported");
+        Ok(Some(path.display().to_string()))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: i64, company: &str, role: Option<&str>) -> HistoryEntry {
+        HistoryEntry {
+            id,
+            company: company.into(),
+            role: role.map(str::to_string),
+            url: None,
+            status: "pending_review".into(),
+            cv_profile: None,
+            created_at: None,
+            updated_at: None,
+            authorized_at: None,
+            submitted_at: None,
+            recorded_at: String::new(),
+            site: None,
+            imported_from: None,
+            location: None,
+            salary_raw: None,
+            salary: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn csv_fields() {
+        assert_eq!(csv_field("Acme"), "Acme");
+        assert_eq!(csv_field("Acme, Inc."), "\"Acme, Inc.\"");
+        assert_eq!(csv_field("The \"best\" job"), "\"The \"\"best\"\" job\"");
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("-5"), "'-5");
+    }
+
+    #[test]
+    fn csv_rows() {
+        let path = std::env::temp_dir().join(format!("jobbot-export-{}.csv", std::process::id()));
+        let columns = [
+            Column::Id,
+            Column::Company,
+            Column::Role,
+            Column::Status,
+            Column::SubmittedAt,
+        ];
+        let entries = [
+            entry(1, "Acme, Inc.", Some("Engineer")),
+            entry(2, "Initech", None),
+        ];
+        write_csv(&path, &columns, &entries).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with('\u{feff}'));
+        let lines: Vec<&str> = text.split_terminator("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "1,\"Acme, Inc.\",Engineer,pending review,");
+        assert_eq!(lines[2], "2,Initech,,pending review,");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn xlsx_is_written() {
+        let path = std::env::temp_dir().join(format!("jobbot-export-{}.xlsx", std::process::id()));
+        write_xlsx(&path, &[Column::Company], &[entry(1, "Acme", None)]).unwrap();
+        assert!(fs::read(&path).unwrap().starts_with(b"PK\x03\x04"));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,453 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+use crate::history;
+
+// Rollups for the dashboard charts, over the shell's application history
+// (history.rs) so they work with the engine down. An application counts from
+// when it was sent: its submission, else its first change to applied, else,
+// for one the engine already had further along, when it was created. Its
+// response is the first acknowledgement, interview, offer or rejection after
+// that; interviews and offers are what a board "succeeds" with. Days are
+// local days.
+const SENT: &[&str] = &["applied", "submitted_ambiguous"];
+const RESPONSES: &[&str] = &[
+    "acknowledged",
+    "interview_scheduled",
+    "interviewed",
+    "offered",
+    "rejected",
+];
+const SUCCESSES: &[&str] = &["interview_scheduled", "interviewed", "offered"];
+// Applications without a known board
+const UNKNOWN_BOARD: &str = "unknown";
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsRange {
+    Week,
+    Month,
+    Quarter,
+    Year,
+    All,
+}
+
+impl StatsRange {
+    fn days(self) -> Option<i64> {
+        match self {
+            StatsRange::Week => Some(7),
+            StatsRange::Month => Some(30),
+            StatsRange::Quarter => Some(90),
+            StatsRange::Year => Some(365),
+            StatsRange::All => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    Day,
+    Week,
+    Month,
+}
+
+impl GroupBy {
+    // The first day of the bucket `day` falls in; weeks start on Monday.
+    fn start(self, day: NaiveDate) -> NaiveDate {
+        match self {
+            GroupBy::Day => day,
+            GroupBy::Week => day - Duration::days(i64::from(day.weekday().num_days_from_monday())),
+            GroupBy::Month => day.with_day(1).unwrap_or(day),
+        }
+    }
+
+    fn next(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            GroupBy::Day => start + Duration::days(1),
+            GroupBy::Week => start + Duration::days(7),
+            GroupBy::Month => start
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap_or(start),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Bucket {
+    // Its first day, YYYY-MM-DD
+    pub start: String,
+    pub applications: u32,
+    // Responses that arrived in the bucket, whenever their application was sent
+    pub responses: u32,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Totals {
+    pub applications: u32,
+    pub responded: u32,
+    pub successes: u32,
+    pub offers: u32,
+    pub rejections: u32,
+    // Of the applications sent in the range
+    pub response_rate: f64,
+    pub success_rate: f64,
+    // Between sending and the first response
+    pub median_response_hours: Option<f64>,
+    pub average_response_hours: Option<f64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BoardStats {
+    pub site: String,
+    pub applications: u32,
+    pub responded: u32,
+    pub successes: u32,
+    pub response_rate: f64,
+    pub success_rate: f64,
+    pub median_response_hours: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub range: StatsRange,
+    pub group_by: GroupBy,
+    // First day covered, or None for everything
+    pub since: Option<String>,
+    pub buckets: Vec<Bucket>,
+    pub totals: Totals,
+    // Most applications first
+    pub boards: Vec<BoardStats>,
+}
+
+struct Sent {
+    site: String,
+    at: NaiveDateTime,
+    // The first response, and when if it was seen happen
+    response: Option<Option<NaiveDateTime>>,
+    success: bool,
+    offered: bool,
+    rejected: bool,
+}
+
+fn placeholders(values: &[&str]) -> String {
+    values
+        .iter()
+        .map(|v| format!("'{v}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn sent_applications(db: &Connection) -> Result<Vec<Sent>, String> {
+    let responses = placeholders(RESPONSES);
+    let sql = format!(
+        "SELECT a.site, a.status,
+            COALESCE(
+                a.submitted_at,
+                (SELECT MIN(at) FROM status_changes
+                 WHERE application_id = a.id AND status IN ({sent})),
+                CASE WHEN a.status IN ({responses}) AND a.status != 'rejected'
+                     THEN a.created_at END
+            ) AS sent_at,
+            a.id
+         FROM applications a",
+        sent = placeholders(SENT),
+    );
+    let mut statement = db.prepare(&sql).map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map([], |r| {
+            Ok((
+                r.get::<_, Option<String>>(0)?,
+                r.get::<_, String>(1)?,
+                r.get::<_, Option<String>>(2)?,
+                r.get::<_, i64>(3)?,
+            ))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    let mut changes = db
+        .prepare(&format!(
+            "SELECT status, at FROM status_changes
+             WHERE application_id = ?1 AND status IN ({responses}) ORDER BY rowid"
+        ))
+        .map_err(|e| e.to_string())?;
+    let mut sent = Vec::new();
+    for (site, status, sent_at, id) in rows {
+        let Some(at) = history::local(&sent_at) else {
+            continue;
+        };
+        let seen: Vec<(String, Option<NaiveDateTime>)> = changes
+            .query_map([id], |r| Ok((r.get::<_, String>(0)?, r.get(1)?)))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|(status, when)| (status, history::local(&Some(when))))
+            .filter(|(_, when)| when.is_none_or(|w| w >= at))
+            .collect();
+        // Known only from the engine's list, without the moment it changed
+        let response = match seen.first() {
+            Some((_, when)) => Some(*when),
+            None => RESPONSES.contains(&status.as_str()).then_some(None),
+        };
+        let reached = |statuses: &[&str]| {
+            statuses.contains(&status.as_str())
+                || seen.iter().any(|(s, _)| statuses.contains(&s.as_str()))
+        };
+        sent.push(Sent {
+            site: site.unwrap_or_else(|| UNKNOWN_BOARD.to_string()),
+            at,
+            response,
+            success: reached(SUCCESSES),
+            offered: reached(&["offered"]),
+            rejected: status == "rejected",
+        });
+    }
+    Ok(sent)
+}
+
+fn rate(part: u32, whole: u32) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        f64::from(part) / f64::from(whole)
+    }
+}
+
+fn response_hours(items: &[&Sent]) -> Vec<f64> {
+    let mut hours: Vec<f64> = items
+        .iter()
+        .filter_map(|s| Some(s.response??.signed_duration_since(s.at)))
+        .map(|d| d.num_minutes() as f64 / 60.0)
+        .collect();
+    hours.sort_by(f64::total_cmp);
+    hours
+}
+
+fn median(sorted: &[f64]) -> Option<f64> {
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(sorted[mid]),
+        _ => Some((sorted[mid - 1] + sorted[mid]) / 2.0),
+    }
+}
+
+pub fn compute(app: &AppHandle, range: StatsRange, group_by: GroupBy) -> Result<Stats, String> {
+    let all = sent_applications(&history::open(app)?)?;
+    Ok(summarize(&all, Local::now().date_naive(), range, group_by))
+}
+
+fn summarize(all: &[Sent], today: NaiveDate, range: StatsRange, group_by: GroupBy) -> Stats {
+    let since = range.days().map(|days| today - Duration::days(days - 1));
+    let in_range = |at: NaiveDateTime| since.is_none_or(|since| at.date() >= since);
+    let sent: Vec<&Sent> = all.iter().filter(|s| in_range(s.at)).collect();
+
+    // Every bucket from the first day to today, empty ones included
+    let first = since
+        .or_else(|| sent.iter().map(|s| s.at.date()).min())
+        .unwrap_or(today);
+    let mut buckets: BTreeMap<NaiveDate, Bucket> = BTreeMap::new();
+    let mut start = group_by.start(first);
+    while start <= today {
+        buckets.insert(
+            start,
+            Bucket {
+                start: start.to_string(),
+                ..Default::default()
+            },
+        );
+        start = group_by.next(start);
+    }
+    for s in &sent {
+        if let Some(bucket) = buckets.get_mut(&group_by.start(s.at.date())) {
+            bucket.applications += 1;
+        }
+    }
+    for s in all {
+        let Some(Some(when)) = s.response else {
+            continue;
+        };
+        if in_range(when) {
+            if let Some(bucket) = buckets.get_mut(&group_by.start(when.date())) {
+                bucket.responses += 1;
+            }
+        }
+    }
+
+    let count =
+        |items: &[&Sent], f: &dyn Fn(&Sent) -> bool| items.iter().filter(|s| f(s)).count() as u32;
+    let applications = sent.len() as u32;
+    let responded = count(&sent, &|s| s.response.is_some());
+    let successes = count(&sent, &|s| s.success);
+    let hours = response_hours(&sent);
+    let totals = Totals {
+        applications,
+        responded,
+        successes,
+        offers: count(&sent, &|s| s.offered),
+        rejections: count(&sent, &|s| s.rejected),
+        response_rate: rate(responded, applications),
+        success_rate: rate(successes, applications),
+        median_response_hours: median(&hours),
+        average_response_hours: (!hours.is_empty())
+            .then(|| hours.iter().sum::<f64>() / hours.len() as f64),
+    };
+
+    let mut by_site: BTreeMap<&str, Vec<&Sent>> = BTreeMap::new();
+    for s in &sent {
+        by_site.entry(s.site.as_str()).or_default().push(s);
+    }
+    let mut boards: Vec<BoardStats> = by_site
+        .into_iter()
+        .map(|(site, items)| {
+            let applications = items.len() as u32;
+            let responded = count(&items, &|s| s.response.is_some());
+            let successes = count(&items, &|s| s.success);
+            BoardStats {
+                site: site.to_string(),
+                applications,
+                responded,
+                successes,
+                response_rate: rate(responded, applications),
+                success_rate: rate(successes, applications),
+                median_response_hours: median(&response_hours(&items)),
+            }
+        })
+        .collect();
+    boards.sort_by_key(|b| std::cmp::Reverse(b.applications));
+
+    Stats {
+        range,
+        group_by,
+        since: since.map(|d| d.to_string()),
+        buckets: buckets.into_values().collect(),
+        totals,
+        boards,
+    }
+}
+
+#[tauri::command]
+pub async fn get_stats(
+    app: AppHandle,
+    range: StatsRange,
+    group_by: GroupBy,
+) -> Result<Stats, String> {
+    tauri::async_runtime::spawn_blocking(move || compute(&app, range, group_by))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn at(d: u32, hour: u32) -> NaiveDateTime {
+        day(d).and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn sent(site: &str, d: u32, response: Option<Option<NaiveDateTime>>) -> Sent {
+        Sent {
+            site: site.into(),
+            at: at(d, 9),
+            response,
+            success: false,
+            offered: false,
+            rejected: false,
+        }
+    }
+
+    #[test]
+    fn buckets_start_on_mondays_and_months() {
+        assert_eq!(GroupBy::Week.start(day(15)), day(12));
+        assert_eq!(GroupBy::Week.start(day(12)), day(12));
+        assert_eq!(GroupBy::Month.start(day(15)), day(1));
+        assert_eq!(
+            GroupBy::Month.next(day(1)),
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn medians() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[1.0, 5.0, 9.0]), Some(5.0));
+        assert_eq!(median(&[1.0, 3.0]), Some(2.0));
+        assert_eq!(rate(1, 0), 0.0);
+    }
+
+    #[test]
+    fn totals_buckets_and_boards() {
+        let mut offered = sent("linkedin", 13, Some(Some(at(14, 9))));
+        offered.success = true;
+        offered.offered = true;
+        let mut rejected = sent("indeed", 5, Some(Some(at(13, 21))));
+        rejected.rejected = true;
+        let all = [
+            offered,
+            sent("linkedin", 14, Some(None)),
+            sent("linkedin", 15, None),
+            rejected,
+            // Before the week; its response isn't counted either
+            sent("indeed", 1, Some(Some(at(2, 9)))),
+        ];
+        let stats = summarize(&all, day(15), StatsRange::Week, GroupBy::Day);
+        assert_eq!(stats.since.as_deref(), Some("2026-10-09"));
+        assert_eq!(stats.buckets.len(), 7);
+        let bucket = |d: u32| &stats.buckets[(d - 9) as usize];
+        assert_eq!((bucket(13).applications, bucket(13).responses), (1, 1));
+        assert_eq!((bucket(14).applications, bucket(14).responses), (1, 1));
+
+        let totals = &stats.totals;
+        assert_eq!(totals.applications, 3);
+        assert_eq!(totals.responded, 2);
+        assert_eq!(
+            (totals.successes, totals.offers, totals.rejections),
+            (1, 1, 0)
+        );
+        assert_eq!(totals.median_response_hours, Some(24.0));
+
+        assert_eq!(stats.boards[0].site, "linkedin");
+        assert_eq!(stats.boards[0].applications, 3);
+    }
+
+    #[test]
+    fn everything_from_the_first_application() {
+        let all = [sent("linkedin", 1, None), sent("linkedin", 14, None)];
+        let stats = summarize(&all, day(15), StatsRange::All, GroupBy::Week);
+        assert_eq!(stats.since, None);
+        let starts: Vec<&str> = stats.buckets.iter().map(|b| b.start.as_str()).collect();
+        assert_eq!(starts, ["2026-09-28", "2026-10-05", "2026-10-12"]);
+        assert_eq!(stats.totals.applications, 2);
+    }
+
+    #[test]
+    fn sent_and_answered_from_the_history() {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        db.execute_batch(
+            "INSERT INTO applications (id, company, status, site, submitted_at, recorded_at)
+               VALUES (1, 'Acme', 'interviewed', 'linkedin', '2026-10-01T09:00:00Z', ''),
+                      (2, 'Initech', 'applied', NULL, NULL, ''),
+                      (3, 'Globex', 'pending_review', 'indeed', NULL, '');
+             INSERT INTO status_changes (application_id, status, at)
+               VALUES (1, 'interview_scheduled', '2026-10-03T09:00:00Z'),
+                      (2, 'applied', '2026-10-02T09:00:00Z');",
+        )
+        .unwrap();
+        let sent = sent_applications(&db).unwrap();
+        assert_eq!(sent.len(), 2);
+        let acme = &sent[0];
+        assert!(acme.success && !acme.offered);
+        let hours = acme.response.unwrap().unwrap() - acme.at;
+        assert_eq!(hours.num_hours(), 48);
+        assert_eq!(sent[1].site, UNKNOWN_BOARD);
+        assert!(sent[1].response.is_none());
+    }
+}
@@ -0,0 +1,465 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime},
+};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+
+use crate::{
+    export::{self, Sealed},
+    i18n::{t, t_args},
+    notifications, paths, profiles, settings,
+    telemetry::{self, Counter},
+};
+
+// Optional settings sync through a folder the user already syncs (Dropbox,
+// OneDrive, a NAS share). Each machine reads and writes one passphrase-sealed
+// snapshot there and keeps its own copy of the last agreed snapshot (the
+// base), so a change on either side can be told apart from a conflict:
+//   only local changed  → write it out
+//   only remote changed → apply it here
+//   both changed        → merge field by field; fields changed differently
+//                         on both sides stop the sync until the user picks
+// Machine-local state never leaves (see `settings::portable`).
+const SYNC_FILE: &str = "jobbot-settings.sync";
+const FORMAT: &str = "jobbot-sync";
+const FORMAT_VERSION: u32 = 1;
+pub const KEYCHAIN_SERVICE: &str = "com.currobot.app.sync";
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+pub const SYNC_STATUS_EVENT: &str = "sync-status";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SyncFile {
+    format: String,
+    format_version: u32,
+    written_at: String,
+    app_version: String,
+    payload: Sealed,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SyncStatus {
+    #[default]
+    Off,
+    Synced {
+        at: String,
+    },
+    // Dotted setting paths changed differently on both sides
+    Conflict {
+        fields: Vec<String>,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Keep {
+    Local,
+    Remote,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncInfo {
+    pub folder: Option<String>,
+    pub status: SyncStatus,
+}
+
+#[derive(Default)]
+pub struct SyncState {
+    status: Mutex<SyncStatus>,
+    // Snapshot file and mtime last read, so an idle poll skips the decrypt
+    seen: Mutex<Option<(PathBuf, SystemTime)>>,
+}
+
+fn set_status(app: &AppHandle, status: SyncStatus) {
+    let state = app.state::<SyncState>();
+    let mut current = state.status.lock().unwrap();
+    if *current == status {
+        return;
+    }
+    if let SyncStatus::Conflict { fields } = &status {
+        notifications::notify(
+            app,
+            &t("sync-conflict-title"),
+            &t_args("sync-conflict-body", &[("count", fields.len().into())]),
+        );
+    }
+    *current = status.clone();
+    drop(current);
+    let _ = app.emit(SYNC_STATUS_EVENT, status);
+}
+
+fn passphrase_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &profiles::active()).map_err(|e| e.to_string())
+}
+
+fn base_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::config_dir(app)?.join("sync-base.json"))
+}
+
+fn load_base(app: &AppHandle) -> Option<Value> {
+    let raw = fs::read_to_string(base_path(app).ok()?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn save_base(app: &AppHandle, base: &Value) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(base).map_err(|e| e.to_string())?;
+    fs::write(base_path(app)?, json).map_err(|e| e.to_string())
+}
+
+fn clear_base(app: &AppHandle) {
+    if let Ok(path) = base_path(app) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// Snapshot as it would be written from `settings`, normalized through the
+// current schema so files from older app versions compare cleanly.
+fn snapshot(settings: &settings::Settings) -> Result<Value, String> {
+    settings::to_value(&settings::portable(settings))
+}
+
+fn read_remote(path: &Path, passphrase: &str) -> Result<Value, String> {
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let file: SyncFile =
+        serde_json::from_str(&raw).map_err(|e| format!("unreadable sync file: {e}"))?;
+    if file.format != FORMAT {
+        return Err("unreadable sync file".into());
+    }
+    if file.format_version > FORMAT_VERSION {
+        return Err(format!(
+            "the sync file was written by a newer JobBot ({}); update this one",
+            file.app_version
+        ));
+    }
+    let plain = export::open(&file.payload, passphrase)?;
+    let value = serde_json::from_slice(&plain).map_err(|e| e.to_string())?;
+    snapshot(&settings::from_value(value)?)
+}
+
+fn write_remote(
+    app: &AppHandle,
+    path: &Path,
+    snapshot: &Value,
+    passphrase: &str,
+) -> Result<(), String> {
+    let plain = serde_json::to_vec(snapshot).map_err(|e| e.to_string())?;
+    let file = SyncFile {
+        format: FORMAT.into(),
+        format_version: FORMAT_VERSION,
+        written_at: chrono::Utc::now().to_rfc3339(),
+        app_version: app.package_info().version.to_string(),
+        payload: export::seal(&plain, passphrase)?,
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    // Sync clients pick up a rename as one change, not a half-written file
+    let tmp = path.with_extension("sync.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+// Three-way merge of JSON trees. Leaves changed on both sides to different
+// values are conflicts, settled by `keep` when given.
+fn merge(
+    base: &Value,
+    local: &Value,
+    remote: &Value,
+    path: &str,
+    keep: Option<Keep>,
+    conflicts: &mut Vec<String>,
+) -> Value {
+    if local == remote || remote == base {
+        return local.clone();
+    }
+    if local == base {
+        return remote.clone();
+    }
+    if let (Some(l), Some(r)) = (local.as_object(), remote.as_object()) {
+        let empty = Map::new();
+        let b = base.as_object().unwrap_or(&empty);
+        let keys: BTreeSet<&String> = l.keys().chain(r.keys()).collect();
+        let merged = keys
+            .into_iter()
+            .filter_map(|key| {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                let value = merge(
+                    b.get(key).unwrap_or(&Value::Null),
+                    l.get(key).unwrap_or(&Value::Null),
+                    r.get(key).unwrap_or(&Value::Null),
+                    &child,
+                    keep,
+                    conflicts,
+                );
+                // Absent and null both mean "default" to every settings field
+                (!value.is_null()).then(|| (key.clone(), value))
+            })
+            .collect();
+        return Value::Object(merged);
+    }
+    match keep {
+        Some(Keep::Local) => local.clone(),
+        Some(Keep::Remote) => remote.clone(),
+        None => {
+            conflicts.push(path.to_string());
+            local.clone()
+        }
+    }
+}
+
+// `manual` runs (Sync now, resolving) always read the folder; polls skip it
+// when nothing moved, or when the last run is waiting on the user.
+fn sync_once(app: &AppHandle, manual: bool, keep: Option<Keep>) -> Result<(), String> {
+    let current = settings::get(app);
+    let Some(folder) = current.sync.folder.clone() else {
+        set_status(app, SyncStatus::Off);
+        return Ok(());
+    };
+    let path = Path::new(&folder).join(SYNC_FILE);
+    let local = snapshot(&current)?;
+    let base = load_base(app);
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+    let state = app.state::<SyncState>();
+    let unchanged_remote =
+        modified.is_some_and(|m| state.seen.lock().unwrap().as_ref() == Some(&(path.clone(), m)));
+    let stalled = matches!(
+        *state.status.lock().unwrap(),
+        SyncStatus::Conflict { .. } | SyncStatus::Failed { .. }
+    );
+    if !manual && unchanged_remote && (stalled || base.as_ref() == Some(&local)) {
+        return Ok(());
+    }
+
+    let passphrase = passphrase_entry()?
+        .get_password()
+        .map_err(|_| "the sync passphrase is missing from the keychain".to_string())?;
+    let remote = match modified {
+        Some(m) => {
+            *state.seen.lock().unwrap() = Some((path.clone(), m));
+            Some(read_remote(&path, &passphrase)?)
+        }
+        None => None,
+    };
+    let merged = match &remote {
+        None => local.clone(),
+        Some(remote) => {
+            // Joining an existing sync: whatever is in the folder wins
+            let base = base.unwrap_or_else(|| local.clone());
+            let mut conflicts = Vec::new();
+            let merged = merge(&base, &local, remote, "", keep, &mut conflicts);
+            if !conflicts.is_empty() {
+                set_status(app, SyncStatus::Conflict { fields: conflicts });
+                return Ok(());
+            }
+            snapshot(&settings::from_value(merged)?)?
+        }
+    };
+
+    if merged != local {
+        let (incoming, warnings) = settings::validated(settings::from_value(merged.clone())?);
+        for warning in warnings {
+            warn!("synced settings adjusted: {warning}");
+        }
+        settings::replace(app, settings::with_local(incoming, &settings::get(app)))?;
+    }
+    if remote.as_ref() != Some(&merged) {
+        write_remote(app, &path, &merged, &passphrase)?;
+    }
+    save_base(app, &merged)?;
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+    *state.seen.lock().unwrap() = modified.map(|m| (path, m));
+    set_status(
+        app,
+        SyncStatus::Synced {
+            at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    Ok(())
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        if let Err(error) = sync_once(&app, false, None) {
+            warn!("settings sync failed: {error}");
+            set_status(&app, SyncStatus::Failed { error });
+        }
+        thread::sleep(POLL_INTERVAL);
+    });
+}
+
+fn run(app: &AppHandle, keep: Option<Keep>) -> Result<SyncInfo, String> {
+    if let Err(error) = sync_once(app, true, keep) {
+        set_status(
+            app,
+            SyncStatus::Failed {
+                error: error.clone(),
+            },
+        );
+        return Err(error);
+    }
+    Ok(info(app))
+}
+
+fn info(app: &AppHandle) -> SyncInfo {
+    SyncInfo {
+        folder: settings::get(app).sync.folder,
+        status: app.state::<SyncState>().status.lock().unwrap().clone(),
+    }
+}
+
+#[tauri::command]
+pub fn get_sync_status(app: AppHandle) -> SyncInfo {
+    info(&app)
+}
+
+// Checks the passphrase against an existing snapshot before saving anything,
+// so a typo can't fork the folder into two unreadable copies.
+#[tauri::command]
+pub async fn enable_sync(
+    app: AppHandle,
+    folder: String,
+    passphrase: String,
+) -> Result<SyncInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if passphrase.len() < 8 {
+            return Err("use a passphrase of at least 8 characters".into());
+        }
+        if !Path::new(&folder).is_dir() {
+            return Err(format!("{folder} is not a folder"));
+        }
+        let existing = Path::new(&folder).join(SYNC_FILE);
+        if existing.exists() {
+            read_remote(&existing, &passphrase)?;
+        }
+        passphrase_entry()?
+            .set_password(&passphrase)
+            .map_err(|e| e.to_string())?;
+        clear_base(&app);
+        *app.state::<SyncState>().seen.lock().unwrap() = None;
+        settings::update(&app, |s| s.sync.folder = Some(folder))?;
+        run(&app, None)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Leaves the snapshot in the folder for the other machines.
+#[tauri::command]
+pub fn disable_sync(app: AppHandle) -> Result<(), String> {
+    settings::update(&app, |s| s.sync.folder = None)?;
+    if let Ok(entry) = passphrase_entry() {
+        let _ = entry.delete_credential();
+    }
+    clear_base(&app);
+    *app.state::<SyncState>().seen.lock().unwrap() = None;
+    set_status(&app, SyncStatus::Off);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn sync_now(app: AppHandle) -> Result<SyncInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        telemetry::record(&app, Counter::SyncNow);
+        run(&app, None)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Settles every conflicting field in favour of one side; fields that only
+// one side changed still merge as usual.
+#[tauri::command]
+pub async fn resolve_sync_conflict(app: AppHandle, keep: Keep) -> Result<SyncInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || run(&app, Some(keep)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(base: Value, local: Value, remote: Value, keep: Option<Keep>) -> (Value, Vec<String>) {
+        let mut conflicts = Vec::new();
+        let merged = merge(&base, &local, &remote, "", keep, &mut conflicts);
+        (merged, conflicts)
+    }
+
+    #[test]
+    fn changes_on_either_side_are_kept() {
+        let base = json!({ "theme": "system", "digest": { "hour": 9, "enabled": true } });
+        let local = json!({ "theme": "dark", "digest": { "hour": 9, "enabled": true } });
+        let remote = json!({ "theme": "system", "digest": { "hour": 18, "enabled": true } });
+        let (merged, conflicts) = run(base, local, remote, None);
+        assert_eq!(
+            merged,
+            json!({ "theme": "dark", "digest": { "hour": 18, "enabled": true } })
+        );
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn added_and_removed_keys() {
+        let base = json!({ "a": 1, "b": 2 });
+        let local = json!({ "a": 1, "b": 2, "c": 3 });
+        let remote = json!({ "a": 1 });
+        let (merged, conflicts) = run(base, local, remote, None);
+        assert_eq!(merged, json!({ "a": 1, "c": 3 }));
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn conflicts_are_reported_with_their_path() {
+        let base = json!({ "digest": { "hour": 9 }, "theme": "system" });
+        let local = json!({ "digest": { "hour": 8 }, "theme": "system" });
+        let remote = json!({ "digest": { "hour": 10 }, "theme": "system" });
+        let (merged, conflicts) = run(base, local, remote, None);
+        assert_eq!(
+            merged,
+            json!({ "digest": { "hour": 8 }, "theme": "system" })
+        );
+        assert_eq!(conflicts, ["digest.hour"]);
+    }
+
+    #[test]
+    fn keep_settles_conflicts() {
+        let (base, local, remote) = (json!({ "x": 1 }), json!({ "x": 2 }), json!({ "x": 3 }));
+        let (merged, conflicts) = run(
+            base.clone(),
+            local.clone(),
+            remote.clone(),
+            Some(Keep::Remote),
+        );
+        assert_eq!(merged, json!({ "x": 3 }));
+        assert!(conflicts.is_empty());
+        let (merged, _) = run(base, local, remote, Some(Keep::Local));
+        assert_eq!(merged, json!({ "x": 2 }));
+    }
+
+    #[test]
+    fn first_sync_without_a_base() {
+        let (merged, conflicts) = run(
+            Value::Null,
+            json!({ "theme": "dark", "lang": "es" }),
+            json!({ "theme": "light", "lang": "es" }),
+            None,
+        );
+        assert_eq!(merged, json!({ "theme": "dark", "lang": "es" }));
+        assert_eq!(conflicts, ["theme"]);
+    }
+}
@@ -0,0 +1,312 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::paths;
+
+// What the machine looks like, for the onboarding checks, the diagnostics
+// page and support. Anything that can't be read on a platform is None rather
+// than an error; the data directory itself is left out, as its path usually
+// holds the user's name.
+#[derive(Debug, Serialize)]
+pub struct SystemInfo {
+    // std::env::consts::OS, e.g. "macos"
+    pub os: &'static str,
+    // e.g. "macOS 15.1", "Windows 11 24H2 (build 26100)", "Ubuntu 24.04 LTS (6.8.0)"
+    pub os_version: Option<String>,
+    pub arch: &'static str,
+    pub cpu: Option<String>,
+    pub cpu_cores: usize,
+    pub memory_total_bytes: Option<u64>,
+    pub memory_available_bytes: Option<u64>,
+    // Of the volume holding the data directory
+    pub disk_free_bytes: Option<u64>,
+    pub disk_total_bytes: Option<u64>,
+    pub webview_version: Option<String>,
+    pub displays: Vec<Display>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Display {
+    pub name: Option<String>,
+    // Physical pixels
+    pub width: u32,
+    pub height: u32,
+    // 1.0 = 100 %
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
+// The data directory may not exist yet on a first launch; its nearest
+// existing parent is on the same volume.
+fn existing(mut dir: PathBuf) -> Option<PathBuf> {
+    while !dir.exists() {
+        dir = dir.parent()?.to_path_buf();
+    }
+    Some(dir)
+}
+
+fn displays(app: &AppHandle) -> Vec<Display> {
+    let primary = app.primary_monitor().ok().flatten();
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|m| Display {
+            name: m.name().cloned(),
+            width: m.size().width,
+            height: m.size().height,
+            scale_factor: m.scale_factor(),
+            primary: primary
+                .as_ref()
+                .is_some_and(|p| p.name() == m.name() && p.position() == m.position()),
+        })
+        .collect()
+}
+
+// Free and total bytes of the volume holding the data directory.
+pub fn data_volume(app: &AppHandle) -> Option<(u64, u64)> {
+    paths::data_dir(app)
+        .ok()
+        .and_then(existing)
+        .and_then(|dir| platform::disk(&dir))
+}
+
+pub fn collect(app: &AppHandle) -> SystemInfo {
+    let (memory_total_bytes, memory_available_bytes) = platform::memory();
+    let disk = data_volume(app);
+    SystemInfo {
+        os: std::env::consts::OS,
+        os_version: platform::os_version(),
+        arch: std::env::consts::ARCH,
+        cpu: platform::cpu(),
+        cpu_cores: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        memory_total_bytes,
+        memory_available_bytes,
+        disk_free_bytes: disk.map(|(free, _)| free),
+        disk_total_bytes: disk.map(|(_, total)| total),
+        webview_version: tauri::webview_version().ok(),
+        displays: displays(app),
+    }
+}
+
+#[tauri::command]
+pub async fn get_system_info(app: AppHandle) -> Result<SystemInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || collect(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Free and total bytes for the volume holding `dir`. The field types differ
+// between platforms, hence the conversions.
+#[cfg(unix)]
+#[allow(clippy::useless_conversion)]
+fn statvfs(dir: &std::path::Path) -> Option<(u64, u64)> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+    let path = CString::new(dir.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let block = u64::from(stat.f_frsize);
+    Some((
+        u64::from(stat.f_bavail) * block,
+        u64::from(stat.f_blocks) * block,
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::{ffi::CStr, fs, path::Path};
+
+    fn kernel() -> Option<String> {
+        let mut uts: libc::utsname = unsafe { std::mem::zeroed() };
+        if unsafe { libc::uname(&mut uts) } != 0 {
+            return None;
+        }
+        let release = unsafe { CStr::from_ptr(uts.release.as_ptr()) };
+        Some(release.to_string_lossy().into_owned())
+    }
+
+    pub fn os_version() -> Option<String> {
+        let release = fs::read_to_string("/etc/os-release").ok();
+        let name = release.as_deref().and_then(|r| {
+            r.lines()
+                .find_map(|l| l.strip_prefix("PRETTY_NAME="))
+                .map(|v| v.trim_matches('"').to_string())
+        });
+        match (name, kernel()) {
+            (Some(name), Some(kernel)) => Some(format!("{name} ({kernel})")),
+            (name, kernel) => name.or(kernel.map(|k| format!("Linux {k}"))),
+        }
+    }
+
+    pub fn cpu() -> Option<String> {
+        fs::read_to_string("/proc/cpuinfo")
+            .ok()?
+            .lines()
+            .find(|l| l.starts_with("model name"))
+            .and_then(|l| l.split_once(':'))
+            .map(|(_, v)| v.trim().to_string())
+    }
+
+    // From /proc/meminfo, which counts in kB.
+    pub fn memory() -> (Option<u64>, Option<u64>) {
+        let Ok(meminfo) = fs::read_to_string("/proc/meminfo") else {
+            return (None, None);
+        };
+        let field = |name: &str| {
+            meminfo
+                .lines()
+                .find_map(|l| l.strip_prefix(name))
+                .and_then(|v| v.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+                .map(|kb| kb * 1024)
+        };
+        (field("MemTotal:"), field("MemAvailable:"))
+    }
+
+    pub fn disk(dir: &Path) -> Option<(u64, u64)> {
+        super::statvfs(dir)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::{ffi::CStr, path::Path, ptr};
+
+    fn sysctl_string(name: &CStr) -> Option<String> {
+        let mut len = 0;
+        let found = unsafe {
+            libc::sysctlbyname(name.as_ptr(), ptr::null_mut(), &mut len, ptr::null_mut(), 0)
+        };
+        if found != 0 {
+            return None;
+        }
+        let mut buf = vec![0u8; len];
+        let read = unsafe {
+            libc::sysctlbyname(
+                name.as_ptr(),
+                buf.as_mut_ptr().cast(),
+                &mut len,
+                ptr::null_mut(),
+                0,
+            )
+        };
+        if read != 0 {
+            return None;
+        }
+        let value = CStr::from_bytes_until_nul(&buf).ok()?;
+        Some(value.to_string_lossy().into_owned())
+    }
+
+    pub fn os_version() -> Option<String> {
+        sysctl_string(c"kern.osproductversion").map(|v| format!("macOS {v}"))
+    }
+
+    pub fn cpu() -> Option<String> {
+        sysctl_string(c"machdep.cpu.brand_string")
+    }
+
+    // Available memory would need the Mach host statistics; total only.
+    pub fn memory() -> (Option<u64>, Option<u64>) {
+        let mut total: u64 = 0;
+        let mut len = std::mem::size_of::<u64>();
+        let read = unsafe {
+            libc::sysctlbyname(
+                c"hw.memsize".as_ptr(),
+                (&mut total as *mut u64).cast(),
+                &mut len,
+                ptr::null_mut(),
+                0,
+            )
+        };
+        ((read == 0).then_some(total), None)
+    }
+
+    pub fn disk(dir: &Path) -> Option<(u64, u64)> {
+        super::statvfs(dir)
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{iter, os::windows::ffi::OsStrExt, path::Path};
+    use windows::{
+        core::PCWSTR,
+        Win32::{
+            Storage::FileSystem::GetDiskFreeSpaceExW,
+            System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX},
+        },
+    };
+
+    pub fn os_version() -> Option<String> {
+        let key = windows_registry::LOCAL_MACHINE
+            .open(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion")
+            .ok()?;
+        let build: u32 = key.get_string("CurrentBuildNumber").ok()?.parse().ok()?;
+        // ProductName still says Windows 10 on 11
+        let name = if build >= 22000 {
+            "Windows 11"
+        } else {
+            "Windows 10"
+        };
+        Some(match key.get_string("DisplayVersion") {
+            Ok(release) => format!("{name} {release} (build {build})"),
+            Err(_) => format!("{name} (build {build})"),
+        })
+    }
+
+    pub fn cpu() -> Option<String> {
+        windows_registry::LOCAL_MACHINE
+            .open(r"HARDWARE\DESCRIPTION\System\CentralProcessor\0")
+            .ok()?
+            .get_string("ProcessorNameString")
+            .ok()
+            .map(|name| name.trim().to_string())
+    }
+
+    pub fn memory() -> (Option<u64>, Option<u64>) {
+        let mut status = MEMORYSTATUSEX {
+            dwLength: std::mem::size_of::<MEMORYSTATUSEX>() as u32,
+            ..Default::default()
+        };
+        match unsafe { GlobalMemoryStatusEx(&mut status) } {
+            Ok(()) => (Some(status.ullTotalPhys), Some(status.ullAvailPhys)),
+            Err(_) => (None, None),
+        }
+    }
+
+    pub fn disk(dir: &Path) -> Option<(u64, u64)> {
+        let wide: Vec<u16> = dir.as_os_str().encode_wide().chain(iter::once(0)).collect();
+        let (mut free, mut total) = (0u64, 0u64);
+        unsafe {
+            GetDiskFreeSpaceExW(
+                PCWSTR(wide.as_ptr()),
+                Some(&mut free),
+                Some(&mut total),
+                None,
+            )
+            .ok()?;
+        }
+        Some((free, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_existing_parent() {
+        let tmp = std::env::temp_dir();
+        assert_eq!(existing(tmp.join("jobbot-missing/data")), Some(tmp.clone()));
+        assert_eq!(existing(tmp.clone()), Some(tmp));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn volume_sizes() {
+        let (free, total) = statvfs(&std::env::temp_dir()).unwrap();
+        assert!(total > 0 && free <= total);
+        assert!(statvfs(std::path::Path::new("/jobbot-missing")).is_none());
+    }
+}
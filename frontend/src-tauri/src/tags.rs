@@ -0,0 +1,333 @@
+use chrono::Utc;
+use regex::Regex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::{collections::HashMap, sync::LazyLock};
+use tauri::{AppHandle, Emitter};
+use tracing::info;
+
+use crate::history::{self, HISTORY_CHANGED};
+
+// The user's own labels for applications ("remote", "dream job", "agency"),
+// beside the statuses the engine keeps: in the history database, so they
+// stay on this machine and work with the engine down. A tag has a name, told
+// apart without regard to case, and a color; an application has any number
+// of them, and the history can be narrowed to one (HistoryQuery::tag).
+pub const TAGS_CHANGED: &str = "tags-changed";
+const MAX_NAME: usize = 40;
+const MAX_TAGS: usize = 200;
+// Handed out in turn when a tag is made without a color
+const PALETTE: &[&str] = &[
+    "#007aff", "#34c759", "#ff9500", "#ff3b30", "#af52de", "#5ac8fa", "#ffcc00", "#ff2d55",
+];
+
+static COLOR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#[0-9a-f]{6}$").unwrap());
+
+#[derive(Debug, Serialize)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub color: String,
+    // Applications with it
+    pub count: u32,
+}
+
+fn changed(app: &AppHandle) {
+    let _ = app.emit(TAGS_CHANGED, ());
+}
+
+fn name(name: &str) -> Result<String, String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err("a tag needs a name".into());
+    }
+    if name.chars().count() > MAX_NAME {
+        return Err(format!("tag names are limited to {MAX_NAME} characters"));
+    }
+    Ok(name)
+}
+
+fn color(color: &str) -> Result<String, String> {
+    let color = color.trim().to_lowercase();
+    if COLOR.is_match(&color) {
+        Ok(color)
+    } else {
+        Err(format!("{color} is not a color like #34c759"))
+    }
+}
+
+// Another tag called that, whatever the case.
+fn taken(db: &Connection, name: &str, except: Option<i64>) -> Result<bool, String> {
+    db.query_row(
+        "SELECT 1 FROM tags WHERE name = ?1 COLLATE NOCASE AND id IS NOT ?2",
+        params![name, except],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|found| found.is_some())
+    .map_err(|e| e.to_string())
+}
+
+pub fn list(db: &Connection) -> Result<Vec<Tag>, String> {
+    let mut statement = db
+        .prepare(
+            "SELECT t.id, t.name, t.color, COUNT(a.application_id)
+             FROM tags t LEFT JOIN application_tags a ON a.tag_id = t.id
+             GROUP BY t.id ORDER BY t.name COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+    let tags = statement
+        .query_map([], |r| {
+            Ok(Tag {
+                id: r.get(0)?,
+                name: r.get(1)?,
+                color: r.get(2)?,
+                count: r.get(3)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string());
+    tags
+}
+
+fn get(db: &Connection, id: i64) -> Result<Tag, String> {
+    list(db)?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("no tag {id}"))
+}
+
+// Each application's tag ids, for the ones asked about.
+pub fn of(db: &Connection, application_ids: &[i64]) -> Result<HashMap<i64, Vec<i64>>, String> {
+    let mut tags: HashMap<i64, Vec<i64>> = HashMap::new();
+    if application_ids.is_empty() {
+        return Ok(tags);
+    }
+    let marks = vec!["?"; application_ids.len()].join(", ");
+    let mut statement = db
+        .prepare(&format!(
+            "SELECT a.application_id, a.tag_id FROM application_tags a JOIN tags t ON t.id = a.tag_id
+             WHERE a.application_id IN ({marks}) ORDER BY t.name COLLATE NOCASE"
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = statement
+        .query_map(rusqlite::params_from_iter(application_ids), |r| {
+            Ok((r.get::<_, i64>(0)?, r.get::<_, i64>(1)?))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| e.to_string())?;
+    for (application_id, tag_id) in rows {
+        tags.entry(application_id).or_default().push(tag_id);
+    }
+    Ok(tags)
+}
+
+#[tauri::command]
+pub async fn list_tags(app: AppHandle) -> Result<Vec<Tag>, String> {
+    tauri::async_runtime::spawn_blocking(move || list(&history::open(&app)?))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Without a color, the next in the palette.
+#[tauri::command]
+pub async fn create_tag(
+    app: AppHandle,
+    name: String,
+    color: Option<String>,
+) -> Result<Tag, String> {
+    let name = self::name(&name)?;
+    let color = color.as_deref().map(self::color).transpose()?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = history::open(&app)?;
+        if taken(&db, &name, None)? {
+            return Err(format!("there is already a tag called {name}"));
+        }
+        let count: usize = db
+            .query_row("SELECT COUNT(*) FROM tags", [], |r| r.get(0))
+            .map_err(|e| e.to_string())?;
+        if count >= MAX_TAGS {
+            return Err(format!("there can be at most {MAX_TAGS} tags"));
+        }
+        let color = color.unwrap_or_else(|| PALETTE[count % PALETTE.len()].to_string());
+        db.execute(
+            "INSERT INTO tags (name, color, created_at) VALUES (?1, ?2, ?3)",
+            params![name, color, Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| e.to_string())?;
+        let tag = get(&db, db.last_insert_rowid())?;
+        info!(id = tag.id, "tag created");
+        changed(&app);
+        Ok(tag)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Renames or recolors it, or both.
+#[tauri::command]
+pub async fn update_tag(
+    app: AppHandle,
+    id: i64,
+    name: Option<String>,
+    color: Option<String>,
+) -> Result<Tag, String> {
+    let name = name.as_deref().map(self::name).transpose()?;
+    let color = color.as_deref().map(self::color).transpose()?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = history::open(&app)?;
+        if let Some(name) = &name {
+            if taken(&db, name, Some(id))? {
+                return Err(format!("there is already a tag called {name}"));
+            }
+        }
+        let updated = db
+            .execute(
+                "UPDATE tags SET name = COALESCE(?2, name), color = COALESCE(?3, color)
+                 WHERE id = ?1",
+                params![id, name, color],
+            )
+            .map_err(|e| e.to_string())?;
+        if updated == 0 {
+            return Err(format!("no tag {id}"));
+        }
+        changed(&app);
+        get(&db, id)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Takes it off every application too.
+#[tauri::command]
+pub async fn delete_tag(app: AppHandle, id: i64) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut db = history::open(&app)?;
+        let tx = db.transaction().map_err(|e| e.to_string())?;
+        tx.execute("DELETE FROM application_tags WHERE tag_id = ?1", [id])
+            .map_err(|e| e.to_string())?;
+        let deleted = tx
+            .execute("DELETE FROM tags WHERE id = ?1", [id])
+            .map_err(|e| e.to_string())?;
+        if deleted == 0 {
+            return Err(format!("no tag {id}"));
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        changed(&app);
+        let _ = app.emit(HISTORY_CHANGED, ());
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// The application's tags become exactly these.
+#[tauri::command]
+pub async fn set_application_tags(
+    app: AppHandle,
+    application_id: i64,
+    tag_ids: Vec<i64>,
+) -> Result<Vec<i64>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut db = history::open(&app)?;
+        let known: Vec<i64> = list(&db)?.into_iter().map(|t| t.id).collect();
+        if let Some(unknown) = tag_ids.iter().find(|id| !known.contains(id)) {
+            return Err(format!("no tag {unknown}"));
+        }
+        let tx = db.transaction().map_err(|e| e.to_string())?;
+        tx.execute(
+            "DELETE FROM application_tags WHERE application_id = ?1",
+            [application_id],
+        )
+        .map_err(|e| e.to_string())?;
+        for tag_id in &tag_ids {
+            tx.execute(
+                "INSERT OR IGNORE INTO application_tags (application_id, tag_id) VALUES (?1, ?2)",
+                params![application_id, tag_id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        changed(&app);
+        let _ = app.emit(HISTORY_CHANGED, application_id);
+        Ok(of(&db, &[application_id])?
+            .remove(&application_id)
+            .unwrap_or_default())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        history::migrate(&db).unwrap();
+        for (id, name) in [(1, "remote"), (2, "Agency"), (3, "dream job")] {
+            db.execute(
+                "INSERT INTO tags (id, name, color, created_at) VALUES (?1, ?2, '#007aff', '')",
+                params![id, name],
+            )
+            .unwrap();
+        }
+        for (application_id, tag_id) in [(10, 1), (10, 2), (11, 1)] {
+            db.execute(
+                "INSERT INTO application_tags (application_id, tag_id) VALUES (?1, ?2)",
+                params![application_id, tag_id],
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn names_and_colors() {
+        assert_eq!(name("  dream \n job ").as_deref(), Ok("dream job"));
+        assert!(name(" ").is_err());
+        assert!(name(&"a".repeat(MAX_NAME)).is_ok());
+        assert!(name(&"á".repeat(MAX_NAME + 1)).is_err());
+        assert_eq!(color(" #34C759 ").as_deref(), Ok("#34c759"));
+        assert!(color("#34c75").is_err());
+        assert!(color("red").is_err());
+    }
+
+    #[test]
+    fn names_taken_whatever_the_case() {
+        let db = db();
+        assert!(taken(&db, "REMOTE", None).unwrap());
+        assert!(!taken(&db, "remote", Some(1)).unwrap());
+        assert!(!taken(&db, "onsite", None).unwrap());
+    }
+
+    #[test]
+    fn listed_by_name_with_counts() {
+        let db = db();
+        let tags: Vec<(String, u32)> = list(&db)
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.name, t.count))
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                ("Agency".to_string(), 1),
+                ("dream job".to_string(), 0),
+                ("remote".to_string(), 2)
+            ]
+        );
+        assert_eq!(get(&db, 3).unwrap().name, "dream job");
+        assert!(get(&db, 9).is_err());
+    }
+
+    #[test]
+    fn tags_of_applications() {
+        let db = db();
+        let tags = of(&db, &[10, 11, 12]).unwrap();
+        assert_eq!(tags[&10], [2, 1]);
+        assert_eq!(tags[&11], [1]);
+        assert!(!tags.contains_key(&12));
+        assert!(of(&db, &[]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use serde_json::Value;
+use std::{collections::HashMap, sync::Mutex};
+use tauri::AppHandle;
+
+// The engine's runs (`progress`) on the main window's taskbar button on
+// Windows, so they can be followed with the window minimized: the share of
+// all running items done, yellow while automation is paused and red once a
+// run has ended with failures, until the window is next focused.
+#[derive(Default)]
+struct Taskbar {
+    // current, total per run
+    runs: HashMap<String, (u32, u32)>,
+    paused: bool,
+    failed: bool,
+}
+
+static TASKBAR: Mutex<Option<Taskbar>> = Mutex::new(None);
+
+fn with(app: &AppHandle, change: impl FnOnce(&mut Taskbar)) {
+    let mut guard = TASKBAR.lock().unwrap();
+    let taskbar = guard.get_or_insert_with(Taskbar::default);
+    change(taskbar);
+    platform::show(app, state(taskbar));
+}
+
+// What the button shows: None for nothing, or the status and percentage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum State {
+    None,
+    Normal(u64),
+    Paused(u64),
+    Error(u64),
+}
+
+fn state(taskbar: &Taskbar) -> State {
+    let (current, total) = taskbar
+        .runs
+        .values()
+        .fold((0u64, 0u64), |(c, t), (current, total)| {
+            (c + (*current).min(*total) as u64, t + *total as u64)
+        });
+    let percent = (current * 100).checked_div(total).unwrap_or(0);
+    if taskbar.runs.is_empty() {
+        if taskbar.failed {
+            State::Error(100)
+        } else {
+            State::None
+        }
+    } else if taskbar.failed {
+        State::Error(percent)
+    } else if taskbar.paused {
+        State::Paused(percent)
+    } else {
+        State::Normal(percent)
+    }
+}
+
+pub fn on_progress(app: &AppHandle, run_id: &str, current: u32, total: u32) {
+    with(app, |t| {
+        // A new batch starts over
+        if t.runs.is_empty() {
+            t.failed = false;
+        }
+        t.runs.insert(run_id.to_string(), (current, total));
+    });
+}
+
+pub fn on_finished(app: &AppHandle, run_id: &str, failed: bool) {
+    with(app, |t| {
+        t.runs.remove(run_id);
+        t.failed |= failed;
+    });
+}
+
+// `automation_changed` { "paused": true }
+pub fn on_automation_changed(app: &AppHandle, data: &Value) {
+    let paused = data["paused"].as_bool().unwrap_or(false);
+    with(app, |t| t.paused = paused);
+}
+
+// When the event stream (re)connects, as changes while it was down are missed.
+pub fn on_connected(app: &AppHandle) {
+    if let Some(paused) = platform::paused() {
+        with(app, |t| t.paused = paused);
+    }
+}
+
+// The main window was focused: failures have been seen.
+pub fn on_focused(app: &AppHandle) {
+    if TASKBAR.lock().unwrap().as_ref().is_some_and(|t| t.failed) {
+        with(app, |t| t.failed = false);
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use tauri::{
+        window::{ProgressBarState, ProgressBarStatus},
+        AppHandle, Manager,
+    };
+    use tracing::debug;
+
+    use super::State;
+
+    pub fn paused() -> Option<bool> {
+        crate::automation::paused()
+    }
+
+    pub fn show(app: &AppHandle, state: State) {
+        let Some(window) = app.get_webview_window(crate::scope::MAIN_WINDOW) else {
+            return;
+        };
+        let (status, progress) = match state {
+            State::None => (ProgressBarStatus::None, None),
+            State::Normal(p) => (ProgressBarStatus::Normal, Some(p)),
+            State::Paused(p) => (ProgressBarStatus::Paused, Some(p)),
+            State::Error(p) => (ProgressBarStatus::Error, Some(p)),
+        };
+        if let Err(e) = window.set_progress_bar(ProgressBarState {
+            status: Some(status),
+            progress,
+        }) {
+            debug!("taskbar progress not set: {e}");
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use tauri::AppHandle;
+
+    use super::State;
+
+    pub fn paused() -> Option<bool> {
+        None
+    }
+
+    pub fn show(_app: &AppHandle, _state: State) {}
+}
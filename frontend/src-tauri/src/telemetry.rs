@@ -0,0 +1,286 @@
+use chrono::{Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::{flags, paths, settings};
+
+// Anonymous usage counts, off unless the user turns them on in Settings.
+// Nothing is counted while it's off. When on, each day's totals of the
+// counters below are kept in telemetry.json and sent once the day is over,
+// with the app version, OS and update channel and nothing else: no install
+// id, no profile, no job data. `get_telemetry` returns the pending reports
+// exactly as they would be posted. Turning it off deletes what's pending;
+// the `telemetry` feature flag stops sending for everyone at once.
+//
+// The endpoint is set at build time (JOBBOT_TELEMETRY_URL); builds without
+// one count for the preview and never send.
+const ENDPOINT: Option<&str> = option_env!("JOBBOT_TELEMETRY_URL");
+const FILE: &str = "telemetry.json";
+const SCHEMA: u32 = 1;
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// Days kept when they can't be sent
+const MAX_DAYS: usize = 7;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Counter {
+    Launch,
+    Crash,
+    SettingsExport,
+    SettingsImport,
+    SyncNow,
+    CredentialCopied,
+    ProfileSwitch,
+    DigestSentNow,
+    UpdateRollback,
+}
+
+impl Counter {
+    fn key(self) -> &'static str {
+        match self {
+            Counter::Launch => "launches",
+            Counter::Crash => "crashes",
+            Counter::SettingsExport => "feature.settings_export",
+            Counter::SettingsImport => "feature.settings_import",
+            Counter::SyncNow => "feature.sync_now",
+            Counter::CredentialCopied => "feature.credential_copied",
+            Counter::ProfileSwitch => "feature.profile_switch",
+            Counter::DigestSentNow => "feature.digest_sent_now",
+            Counter::UpdateRollback => "feature.update_rollback",
+        }
+    }
+}
+
+// One day's report, as posted.
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    pub schema: u32,
+    // Local date, YYYY-MM-DD
+    pub day: String,
+    pub app_version: String,
+    pub os: &'static str,
+    pub channel: settings::UpdateChannel,
+    pub counters: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Store {
+    // Local date → counter → count
+    days: BTreeMap<String, BTreeMap<String, u64>>,
+    // RFC 3339
+    last_sent_at: Option<String>,
+}
+
+impl Store {
+    // Days past the last MAX_DAYS are dropped unsent.
+    fn add(&mut self, day: String, counter: Counter, n: u64) {
+        *self
+            .days
+            .entry(day)
+            .or_default()
+            .entry(counter.key().to_string())
+            .or_default() += n;
+        while self.days.len() > MAX_DAYS {
+            self.days.pop_first();
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TelemetryStatus {
+    pub enabled: bool,
+    // False when the build has no endpoint or the flag is off
+    pub sending: bool,
+    pub last_sent_at: Option<String>,
+    // Oldest first, today's (still counting) last
+    pub pending: Vec<Report>,
+}
+
+// Mirrors `telemetry.enabled` so counting doesn't read the settings each time
+static ENABLED: AtomicBool = AtomicBool::new(false);
+// telemetry.json is read-modify-written from several threads
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+// Per install, like the flags cache.
+fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::base_config_dir(app)?.join(FILE))
+}
+
+fn load(app: &AppHandle) -> Store {
+    path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, store: &Store) -> Result<(), String> {
+    let path = path(app)?;
+    let json = serde_json::to_vec_pretty(store).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+fn today() -> String {
+    Local::now().format("%Y-%m-%d").to_string()
+}
+
+// After `telemetry.enabled` changed, or at startup. Off also throws away
+// whatever hasn't been sent.
+pub fn refresh(app: &AppHandle) -> Result<(), String> {
+    let enabled = settings::get(app).telemetry.enabled;
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if enabled {
+        return Ok(());
+    }
+    let _guard = FILE_LOCK.lock().unwrap();
+    match fs::remove_file(path(app)?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+pub fn record(app: &AppHandle, counter: Counter) {
+    record_n(app, counter, 1);
+}
+
+pub fn record_n(app: &AppHandle, counter: Counter, n: u64) {
+    if !ENABLED.load(Ordering::Relaxed) || n == 0 {
+        return;
+    }
+    let _guard = FILE_LOCK.lock().unwrap();
+    let mut store = load(app);
+    store.add(today(), counter, n);
+    if let Err(e) = save(app, &store) {
+        warn!("telemetry count not saved: {e}");
+    }
+}
+
+fn report(app: &AppHandle, day: &str, counters: &BTreeMap<String, u64>) -> Report {
+    Report {
+        schema: SCHEMA,
+        day: day.to_string(),
+        app_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS,
+        channel: settings::get(app).update.channel,
+        counters: counters.clone(),
+    }
+}
+
+fn sending(app: &AppHandle) -> bool {
+    ENDPOINT.is_some()
+        && flags::all(app)
+            .get("telemetry")
+            .is_some_and(|flag| flag.enabled)
+}
+
+// Sends finished days one at a time; a day is dropped only once accepted.
+fn flush(app: &AppHandle) -> Result<(), String> {
+    let Some(endpoint) = ENDPOINT else {
+        return Ok(());
+    };
+    if !ENABLED.load(Ordering::Relaxed) || !sending(app) {
+        return Ok(());
+    }
+    let today = today();
+    let finished: Vec<(String, BTreeMap<String, u64>)> = {
+        let _guard = FILE_LOCK.lock().unwrap();
+        load(app)
+            .days
+            .into_iter()
+            .filter(|(day, _)| *day < today)
+            .collect()
+    };
+    let client = crate::net::client(Duration::from_secs(30))?;
+    for (day, counters) in finished {
+        client
+            .post(endpoint)
+            .json(&report(app, &day, &counters))
+            .send()
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string())?;
+        let _guard = FILE_LOCK.lock().unwrap();
+        let mut store = load(app);
+        store.days.remove(&day);
+        store.last_sent_at = Some(Utc::now().to_rfc3339());
+        save(app, &store)?;
+        info!(day, "usage report sent");
+    }
+    Ok(())
+}
+
+pub fn start(app: AppHandle) {
+    if let Err(e) = refresh(&app) {
+        warn!("pending usage counts not removed: {e}");
+    }
+    record(&app, Counter::Launch);
+    thread::spawn(move || loop {
+        if let Err(e) = flush(&app) {
+            warn!("usage report not sent: {e}");
+        }
+        thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn get_telemetry(app: AppHandle) -> TelemetryStatus {
+    let store = {
+        let _guard = FILE_LOCK.lock().unwrap();
+        load(&app)
+    };
+    TelemetryStatus {
+        enabled: settings::get(&app).telemetry.enabled,
+        sending: sending(&app),
+        last_sent_at: store.last_sent_at,
+        pending: store
+            .days
+            .iter()
+            .map(|(day, counters)| report(&app, day, counters))
+            .collect(),
+    }
+}
+
+#[tauri::command]
+pub fn set_telemetry(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update(&app, |s| s.telemetry.enabled = enabled)?;
+    refresh(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_add_up_per_day() {
+        let mut store = Store::default();
+        store.add("2026-10-14".into(), Counter::Launch, 1);
+        store.add("2026-10-15".into(), Counter::Launch, 1);
+        store.add("2026-10-15".into(), Counter::Launch, 2);
+        store.add("2026-10-15".into(), Counter::SyncNow, 1);
+        assert_eq!(store.days["2026-10-14"]["launches"], 1);
+        assert_eq!(store.days["2026-10-15"]["launches"], 3);
+        assert_eq!(store.days["2026-10-15"]["feature.sync_now"], 1);
+    }
+
+    #[test]
+    fn only_the_last_week_is_kept() {
+        let mut store = Store::default();
+        for day in 1..=10 {
+            store.add(format!("2026-10-{day:02}"), Counter::Launch, 1);
+        }
+        assert_eq!(store.days.len(), MAX_DAYS);
+        assert_eq!(store.days.keys().next().unwrap(), "2026-10-04");
+    }
+}
@@ -0,0 +1,529 @@
+use chrono::{DateTime, Local, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{LazyLock, Mutex},
+};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_dialog::DialogExt;
+use tracing::info;
+
+use crate::{
+    history::{self, HistoryEntry},
+    i18n::t,
+    paths, pdf,
+};
+
+// Cover letter templates, kept by the shell in `templates.json` in the
+// profile's data folder rather than in the webview's storage, so a cleared
+// webview or a reinstall doesn't lose them and backups carry them. A
+// template keeps every wording saved to it, and an older one can be brought
+// back as the newest. Placeholders like {company} are filled in from an
+// application (fill), for a preview or the PDF (pdf.rs); the letter's sender
+// is kept here too. Templates go to and come from other machines as one
+// JSON file, merged on import by id.
+pub const TEMPLATES_CHANGED: &str = "templates-changed";
+const FILE: &str = "templates.json";
+const FORMAT: &str = "currobot-templates";
+const FORMAT_VERSION: u32 = 1;
+const MAX_VERSIONS: usize = 50;
+
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{([a-z_]+)\}").unwrap());
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemplateVersion {
+    // From 1
+    pub version: u32,
+    pub body: String,
+    pub saved_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Template {
+    pub id: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    // Oldest first; the last is current
+    pub versions: Vec<TemplateVersion>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sender {
+    pub name: String,
+    // Address, email, phone: one per line
+    pub details: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TemplateStore {
+    pub templates: Vec<Template>,
+    pub sender: Sender,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Exported {
+    format: String,
+    format_version: u32,
+    templates: Vec<Template>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Rendered {
+    pub text: String,
+    // Placeholders with nothing to fill them with, left blank
+    pub missing: Vec<String>,
+    // Not placeholders we know, left as written
+    pub unknown: Vec<String>,
+}
+
+fn path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join(FILE))
+}
+
+fn load(path: &Path) -> TemplateStore {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+// Written beside and renamed over, so a crash can't leave half a file.
+fn save(path: &Path, store: &TemplateStore) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, json).map_err(|e| e.to_string())?;
+    fs::rename(temp, path).map_err(|e| e.to_string())
+}
+
+fn change<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut TemplateStore) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = LOCK.lock().unwrap();
+    let path = path(app)?;
+    let mut store = load(&path);
+    let result = f(&mut store)?;
+    save(&path, &store)?;
+    let _ = app.emit(TEMPLATES_CHANGED, ());
+    Ok(result)
+}
+
+fn new_id() -> String {
+    format!("tpl-{}", Local::now().format("%Y%m%d-%H%M%S%3f"))
+}
+
+fn find<'a>(store: &'a mut TemplateStore, id: &str) -> Result<&'a mut Template, String> {
+    store
+        .templates
+        .iter_mut()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("no template {id}"))
+}
+
+// A new version unless the wording is the current one; the oldest go past
+// MAX_VERSIONS.
+fn push_version(template: &mut Template, body: String) {
+    if template.versions.last().is_some_and(|v| v.body == body) {
+        return;
+    }
+    let version = template.versions.last().map_or(1, |v| v.version + 1);
+    template.versions.push(TemplateVersion {
+        version,
+        body,
+        saved_at: Utc::now(),
+    });
+    let excess = template.versions.len().saturating_sub(MAX_VERSIONS);
+    template.versions.drain(..excess);
+}
+
+// What the placeholders stand for: the application's, where there is one.
+pub fn values(
+    entry: Option<&HistoryEntry>,
+    company: &str,
+    role: &str,
+    name: &str,
+    recipient: Option<&str>,
+) -> HashMap<&'static str, String> {
+    let field =
+        |f: fn(&HistoryEntry) -> Option<&String>| entry.and_then(f).cloned().unwrap_or_default();
+    HashMap::from([
+        ("company", company.trim().to_string()),
+        ("role", role.trim().to_string()),
+        ("name", name.trim().to_string()),
+        (
+            "date",
+            pdf::date(Local::now().date_naive(), "pdf-date-long"),
+        ),
+        (
+            "recipient",
+            recipient.unwrap_or_default().trim().to_string(),
+        ),
+        ("location", field(|e| e.location.as_ref())),
+        ("url", field(|e| e.url.as_ref())),
+    ])
+}
+
+pub fn fill(body: &str, values: &HashMap<&'static str, String>) -> Rendered {
+    let (mut missing, mut unknown) = (Vec::new(), Vec::new());
+    let text = PLACEHOLDER.replace_all(body, |c: &regex::Captures| {
+        let key = &c[1];
+        match values.get(key) {
+            Some(value) if !value.is_empty() => value.clone(),
+            Some(_) => {
+                missing.push(key.to_string());
+                String::new()
+            }
+            None => {
+                unknown.push(key.to_string());
+                c[0].to_string()
+            }
+        }
+    });
+    for list in [&mut missing, &mut unknown] {
+        list.sort();
+        list.dedup();
+    }
+    Rendered {
+        text: text.into_owned(),
+        missing,
+        unknown,
+    }
+}
+
+#[tauri::command]
+pub async fn list_templates(app: AppHandle) -> Result<TemplateStore, String> {
+    tauri::async_runtime::spawn_blocking(move || Ok(load(&path(&app)?)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Adds a template, or with `id` renames it and saves the wording as its
+// newest version.
+#[tauri::command]
+pub fn save_template(
+    app: AppHandle,
+    id: Option<String>,
+    name: String,
+    body: String,
+) -> Result<Template, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || body.trim().is_empty() {
+        return Err("a template needs a name and some text".into());
+    }
+    change(&app, |store| {
+        let template = match id {
+            Some(id) => {
+                let template = find(store, &id)?;
+                template.name = name;
+                template
+            }
+            None => {
+                store.templates.push(Template {
+                    id: new_id(),
+                    name,
+                    created_at: Utc::now(),
+                    versions: Vec::new(),
+                });
+                store.templates.last_mut().unwrap()
+            }
+        };
+        push_version(template, body);
+        Ok(template.clone())
+    })
+}
+
+// Saves an older wording again as the newest.
+#[tauri::command]
+pub fn restore_template_version(
+    app: AppHandle,
+    id: String,
+    version: u32,
+) -> Result<Template, String> {
+    change(&app, |store| {
+        let template = find(store, &id)?;
+        let body = template
+            .versions
+            .iter()
+            .find(|v| v.version == version)
+            .map(|v| v.body.clone())
+            .ok_or_else(|| format!("no version {version} of {id}"))?;
+        push_version(template, body);
+        Ok(template.clone())
+    })
+}
+
+#[tauri::command]
+pub fn delete_template(app: AppHandle, id: String) -> Result<(), String> {
+    change(&app, |store| {
+        let before = store.templates.len();
+        store.templates.retain(|t| t.id != id);
+        if store.templates.len() == before {
+            return Err(format!("no template {id}"));
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn save_letter_sender(app: AppHandle, sender: Sender) -> Result<(), String> {
+    change(&app, |store| {
+        store.sender = Sender {
+            name: sender.name.trim().to_string(),
+            details: sender.details.trim().to_string(),
+        };
+        Ok(())
+    })
+}
+
+// `body` filled in for an application, or with only the sender and date
+// without one.
+#[tauri::command]
+pub async fn render_template(
+    app: AppHandle,
+    body: String,
+    application_id: Option<i64>,
+    recipient: Option<String>,
+) -> Result<Rendered, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let entry = match application_id {
+            Some(id) => history::detail(&app, id)?.map(|d| d.entry),
+            None => None,
+        };
+        let sender = load(&path(&app)?).sender;
+        let values = values(
+            entry.as_ref(),
+            entry.as_ref().map_or("", |e| e.company.as_str()),
+            entry
+                .as_ref()
+                .and_then(|e| e.role.as_deref())
+                .unwrap_or_default(),
+            &sender.name,
+            recipient.as_deref(),
+        );
+        Ok(fill(&body, &values))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// The path written, or None when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_templates(app: AppHandle) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = load(&path(&app)?);
+        let Some(chosen) = app
+            .dialog()
+            .file()
+            .set_title(t("templates-export-title"))
+            .set_file_name("currobot-templates.json")
+            .add_filter("JSON", &["json"])
+            .blocking_save_file()
+        else {
+            return Ok(None);
+        };
+        let target = chosen.into_path().map_err(|e| e.to_string())?;
+        let exported = Exported {
+            format: FORMAT.into(),
+            format_version: FORMAT_VERSION,
+            templates: store.templates,
+        };
+        let json = serde_json::to_string_pretty(&exported).map_err(|e| e.to_string())?;
+        fs::write(&target, json).map_err(|e| e.to_string())?;
+        info!(count = exported.templates.len(), "templates exported");
+        Ok(Some(target.display().to_string()))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// The templates in an exported file, if it's one this version can read.
+fn read_export(json: &str) -> Result<Vec<Template>, String> {
+    let imported: Exported =
+        serde_json::from_str(json).map_err(|_| "not a currobot templates file")?;
+    if imported.format != FORMAT {
+        return Err("not a currobot templates file".into());
+    }
+    if imported.format_version > FORMAT_VERSION {
+        return Err("these templates are from a newer version of the app; update it first".into());
+    }
+    Ok(imported.templates)
+}
+
+// Templates new here are added; of ones already here, the wordings this
+// machine hasn't got come after its own. How many were added or changed.
+fn merge(store: &mut TemplateStore, incoming: Vec<Template>) -> usize {
+    let mut changed = 0;
+    for incoming in incoming {
+        if incoming.name.trim().is_empty() || incoming.versions.is_empty() {
+            continue;
+        }
+        match store.templates.iter_mut().find(|t| t.id == incoming.id) {
+            Some(template) => {
+                let mut added = false;
+                for version in incoming.versions {
+                    if !template.versions.iter().any(|v| v.body == version.body) {
+                        push_version(template, version.body);
+                        added = true;
+                    }
+                }
+                changed += usize::from(added);
+            }
+            None => {
+                store.templates.push(incoming);
+                changed += 1;
+            }
+        }
+    }
+    changed
+}
+
+// How many templates were added or changed, or None when the dialog was
+// cancelled.
+#[tauri::command]
+pub async fn import_templates(app: AppHandle) -> Result<Option<usize>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(chosen) = app
+            .dialog()
+            .file()
+            .set_title(t("templates-import-title"))
+            .add_filter("JSON", &["json"])
+            .blocking_pick_file()
+        else {
+            return Ok(None);
+        };
+        let source = chosen.into_path().map_err(|e| e.to_string())?;
+        let json = fs::read_to_string(source).map_err(|e| e.to_string())?;
+        let imported = read_export(&json)?;
+        let changed = change(&app, |store| Ok(merge(store, imported)))?;
+        info!(count = changed, "templates imported");
+        Ok(Some(changed))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: &str, bodies: &[&str]) -> Template {
+        let mut template = Template {
+            id: id.into(),
+            name: id.to_uppercase(),
+            created_at: Utc::now(),
+            versions: Vec::new(),
+        };
+        for body in bodies {
+            push_version(&mut template, body.to_string());
+        }
+        template
+    }
+
+    fn bodies(template: &Template) -> Vec<&str> {
+        template.versions.iter().map(|v| v.body.as_str()).collect()
+    }
+
+    #[test]
+    fn placeholders_filled_missing_and_unknown() {
+        let values = values(None, " Acme ", "Cajero", "", None);
+        assert_eq!(values["company"], "Acme");
+        assert!(!values["date"].is_empty());
+        let rendered = fill(
+            "Dear {recipient}, about {role} at {company} ({company}). {salary} {name}",
+            &values,
+        );
+        assert_eq!(
+            rendered.text,
+            "Dear , about Cajero at Acme (Acme). {salary} "
+        );
+        assert_eq!(rendered.missing, ["name", "recipient"]);
+        assert_eq!(rendered.unknown, ["salary"]);
+    }
+
+    #[test]
+    fn versions_only_for_new_wording() {
+        let mut template = template("tpl-1", &["one", "one", "two"]);
+        assert_eq!(bodies(&template), ["one", "two"]);
+        assert_eq!(template.versions[1].version, 2);
+        for i in 0..MAX_VERSIONS {
+            push_version(&mut template, format!("v{i}"));
+        }
+        assert_eq!(template.versions.len(), MAX_VERSIONS);
+        assert_eq!(template.versions[0].version, 3);
+        assert_eq!(
+            template.versions.last().unwrap().version,
+            MAX_VERSIONS as u32 + 2
+        );
+    }
+
+    #[test]
+    fn imports_merge_by_id() {
+        let mut store = TemplateStore {
+            templates: vec![template("tpl-1", &["one", "two"])],
+            ..Default::default()
+        };
+        let mut nameless = template("tpl-3", &["x"]);
+        nameless.name = " ".into();
+        let changed = merge(
+            &mut store,
+            vec![
+                template("tpl-1", &["one", "three"]),
+                template("tpl-2", &["other"]),
+                nameless,
+                template("tpl-4", &[]),
+            ],
+        );
+        assert_eq!(changed, 2);
+        assert_eq!(bodies(&store.templates[0]), ["one", "two", "three"]);
+        assert_eq!(store.templates[1].id, "tpl-2");
+        assert_eq!(store.templates.len(), 2);
+        assert_eq!(merge(&mut store, vec![template("tpl-2", &["other"])]), 0);
+    }
+
+    #[test]
+    fn export_files() {
+        let exported = |format: &str, version: u32| {
+            serde_json::to_string(&Exported {
+                format: format.into(),
+                format_version: version,
+                templates: vec![template("tpl-1", &["one"])],
+            })
+            .unwrap()
+        };
+        assert_eq!(read_export(&exported(FORMAT, 1)).unwrap().len(), 1);
+        assert!(read_export(&exported("other", 1)).is_err());
+        assert!(read_export(&exported(FORMAT, FORMAT_VERSION + 1))
+            .unwrap_err()
+            .contains("newer version"));
+        assert!(read_export("[]").is_err());
+    }
+
+    #[test]
+    fn saved_and_loaded() {
+        let dir = std::env::temp_dir().join(format!("jobbot-templates-{}", std::process::id()));
+        let path = dir.join(FILE);
+        assert!(load(&path).templates.is_empty());
+        let store = TemplateStore {
+            templates: vec![template("tpl-1", &["one"])],
+            sender: Sender {
+                name: "Ana".into(),
+                details: "ana@example.com".into(),
+            },
+        };
+        save(&path, &store).unwrap();
+        let loaded = load(&path);
+        assert_eq!(bodies(&loaded.templates[0]), ["one"]);
+        assert_eq!(loaded.sender.name, "Ana");
+        assert!(!path.with_extension("json.tmp").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use tauri::AppHandle;
+use tracing::debug;
+
+use crate::automation;
+
+// Clicking a notification on Windows. Toasts are shown with the page they
+// are about as their launch argument, and a COM activator registered for
+// the app id receives the click: in the running app, or, once it has quit,
+// in a new instance Windows starts with ACTIVATED_ARG for the purpose. The
+// activator is registered per user at every start, beside the installer's
+// shortcut, and removed on uninstall. Elsewhere the plugin's notifications
+// just bring the app up.
+
+pub fn register(app: AppHandle) {
+    platform::register(app);
+}
+
+pub fn unregister() {
+    platform::unregister();
+}
+
+// Whether the notification was shown here, with `route` behind a click.
+pub fn show(app: &AppHandle, title: &str, body: &str, route: &str) -> bool {
+    platform::show(app, title, body, route)
+}
+
+// Toasts shown by the plugin (progress, say) come back without a route.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn on_activated(app: &AppHandle, args: &str) {
+    debug!("notification clicked");
+    if args.starts_with('/') {
+        automation::open_route(app, args);
+    } else {
+        crate::show_window(app);
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{ffi::c_void, sync::OnceLock, thread};
+    use tauri::AppHandle;
+    use tracing::warn;
+    use windows::{
+        core::{implement, Interface, Ref, BOOL, GUID, HSTRING, PCWSTR},
+        Data::Xml::Dom::XmlDocument,
+        Win32::{
+            Foundation::CLASS_E_NOAGGREGATION,
+            System::Com::{
+                CoInitializeEx, CoRegisterClassObject, IClassFactory, IClassFactory_Impl,
+                CLSCTX_LOCAL_SERVER, COINIT_MULTITHREADED, REGCLS_MULTIPLEUSE,
+            },
+            UI::Notifications::{
+                INotificationActivationCallback, INotificationActivationCallback_Impl,
+                NOTIFICATION_USER_INPUT_DATA,
+            },
+        },
+        UI::Notifications::{ToastNotification, ToastNotificationManager},
+    };
+    use windows_registry::CURRENT_USER;
+
+    const ACTIVATED_ARG: &str = "--toast-activated";
+    const CLSID: GUID = GUID::from_u128(0xc08a8598_c179_4283_9daf_d28e755bb59d);
+
+    static APP: OnceLock<AppHandle> = OnceLock::new();
+
+    #[implement(INotificationActivationCallback)]
+    struct Activator;
+
+    impl INotificationActivationCallback_Impl for Activator_Impl {
+        fn Activate(
+            &self,
+            _app_id: &PCWSTR,
+            args: &PCWSTR,
+            _data: *const NOTIFICATION_USER_INPUT_DATA,
+            _count: u32,
+        ) -> windows::core::Result<()> {
+            let args = if args.is_null() {
+                String::new()
+            } else {
+                unsafe { args.to_string() }.unwrap_or_default()
+            };
+            if let Some(app) = APP.get() {
+                super::on_activated(app, &args);
+            }
+            Ok(())
+        }
+    }
+
+    #[implement(IClassFactory)]
+    struct Factory;
+
+    impl IClassFactory_Impl for Factory_Impl {
+        fn CreateInstance(
+            &self,
+            outer: Ref<'_, windows::core::IUnknown>,
+            iid: *const GUID,
+            object: *mut *mut c_void,
+        ) -> windows::core::Result<()> {
+            if !outer.is_null() {
+                return Err(CLASS_E_NOAGGREGATION.into());
+            }
+            let callback: INotificationActivationCallback = Activator.into();
+            unsafe { callback.query(iid, object).ok() }
+        }
+
+        fn LockServer(&self, _lock: BOOL) -> windows::core::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn clsid() -> String {
+        format!("{{{CLSID:?}}}")
+    }
+
+    // What Windows runs for a click while the app isn't, and which class it
+    // asks for.
+    fn write_registry(app_id: &str) -> Result<(), String> {
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        CURRENT_USER
+            .create(format!(r"Software\Classes\CLSID\{}\LocalServer32", clsid()))
+            .and_then(|k| k.set_string("", format!("\"{}\" {ACTIVATED_ARG}", exe.display())))
+            .map_err(|e| e.to_string())?;
+        CURRENT_USER
+            .create(format!(r"Software\Classes\AppUserModelId\{app_id}"))
+            .and_then(|k| k.set_string("CustomActivator", clsid()))
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn register(app: AppHandle) {
+        if let Err(e) = write_registry(&app.config().identifier) {
+            warn!("notification activator not registered: {e}");
+        }
+        if APP.set(app).is_err() {
+            return;
+        }
+        thread::spawn(|| {
+            // Clicks arrive on COM's own threads; this one only keeps the
+            // apartment, and with it the registration, alive.
+            let _ = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+            let factory: IClassFactory = Factory.into();
+            match unsafe {
+                CoRegisterClassObject(&CLSID, &factory, CLSCTX_LOCAL_SERVER, REGCLS_MULTIPLEUSE)
+            } {
+                Ok(_) => loop {
+                    thread::park();
+                },
+                Err(e) => warn!("notification activator not started: {e}"),
+            }
+        });
+    }
+
+    pub fn unregister() {
+        let _ = CURRENT_USER.remove_tree(format!(r"Software\Classes\CLSID\{}", clsid()));
+        if let Some(app) = APP.get() {
+            let key = format!(
+                r"Software\Classes\AppUserModelId\{}",
+                app.config().identifier
+            );
+            if let Ok(key) = CURRENT_USER.open(key) {
+                let _ = key.remove_value("CustomActivator");
+            }
+        }
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn toast(app_id: &str, title: &str, body: &str, route: &str) -> windows::core::Result<()> {
+        let xml = XmlDocument::new()?;
+        xml.LoadXml(&HSTRING::from(format!(
+            r#"<toast launch="{}" activationType="foreground"><visual><binding template="ToastGeneric"><text>{}</text><text>{}</text></binding></visual></toast>"#,
+            escape(route),
+            escape(title),
+            escape(body),
+        )))?;
+        let toast = ToastNotification::CreateToastNotification(&xml)?;
+        ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(app_id))?.Show(&toast)
+    }
+
+    pub fn show(app: &AppHandle, title: &str, body: &str, route: &str) -> bool {
+        match toast(&app.config().identifier, title, body, route) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("toast not shown: {e}");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use tauri::AppHandle;
+
+    pub fn register(_app: AppHandle) {}
+
+    pub fn unregister() {}
+
+    pub fn show(_app: &AppHandle, _title: &str, _body: &str, _route: &str) -> bool {
+        false
+    }
+}
@@ -0,0 +1,70 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::settings;
+
+// Whether the desktop shows the tray icon. Linux draws it through a
+// StatusNotifierItem host (KDE and most other desktops; GNOME only with the
+// AppIndicator extension) and without one the icon silently doesn't appear,
+// so a hidden window could never be brought back. Then the window opens even
+// on login and closing it quits, unless `background_without_tray` is set:
+// the app keeps running hidden and launching it again shows the window,
+// over D-Bus (see `instance`).
+#[derive(Debug, Serialize)]
+pub struct TrayStatus {
+    pub available: bool,
+    pub background_without_tray: bool,
+}
+
+pub fn available() -> bool {
+    platform::available()
+}
+
+// Whether the main window can be hidden rather than closed.
+pub fn can_hide(app: &AppHandle) -> bool {
+    available() || settings::get(app).background_without_tray
+}
+
+#[tauri::command]
+pub fn get_tray_status(app: AppHandle) -> TrayStatus {
+    TrayStatus {
+        available: available(),
+        background_without_tray: settings::get(&app).background_without_tray,
+    }
+}
+
+#[tauri::command]
+pub fn set_background_without_tray(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update(&app, |s| s.background_without_tray = enabled)?;
+    Ok(())
+}
+
+// Hosts register as the watcher's name on the session bus. Asked each time,
+// as the extension can be turned on while the app runs.
+#[cfg(target_os = "linux")]
+mod platform {
+    use zbus::{blocking::fdo::DBusProxy, blocking::Connection, names::BusName};
+
+    const WATCHER: &str = "org.kde.StatusNotifierWatcher";
+
+    pub fn available() -> bool {
+        let Ok(connection) = Connection::session() else {
+            return false;
+        };
+        let Ok(name) = BusName::try_from(WATCHER) else {
+            return false;
+        };
+        DBusProxy::new(&connection)
+            .ok()
+            .and_then(|bus| bus.name_has_owner(name).ok())
+            .unwrap_or(false)
+    }
+}
+
+// The menu bar and the notification area are always there.
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    pub fn available() -> bool {
+        true
+    }
+}
@@ -0,0 +1,223 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+use tauri::{image::Image, AppHandle, Manager};
+use tracing::debug;
+
+use crate::{scope, TRAY_ID};
+
+// The tray icon drawn at the size the display shows it, rather than one
+// bitmap the system stretches and blurs at 150% or 200%: scaled down from
+// the 256 px app icon, with the unread badge (`notifications`) composited on
+// at the same size. Redrawn when the badge changes or the scale does. On
+// Windows the badge also goes on the taskbar button as its overlay icon.
+static SOURCE: Image<'static> = tauri::include_image!("icons/128x128@2x.png");
+// Notification red
+const BADGE: [u8; 3] = [0xFF, 0x3B, 0x30];
+
+static UNREAD: AtomicBool = AtomicBool::new(false);
+// Pixel size and badge of the icon the tray has
+static DRAWN: Mutex<Option<(u32, bool)>> = Mutex::new(None);
+
+// Logical size of a tray icon: the notification area's small icon, the menu
+// bar's glyph height, a panel's usual indicator size.
+fn tray_points() -> f64 {
+    if cfg!(windows) {
+        16.0
+    } else if cfg!(target_os = "macos") {
+        18.0
+    } else {
+        22.0
+    }
+}
+
+// The tray sits on the primary display; the main window stands in when
+// that can't be read.
+fn scale(app: &AppHandle) -> f64 {
+    app.primary_monitor()
+        .ok()
+        .flatten()
+        .map(|m| m.scale_factor())
+        .or_else(|| {
+            app.get_webview_window(scope::MAIN_WINDOW)
+                .and_then(|w| w.scale_factor().ok())
+        })
+        .unwrap_or(1.0)
+}
+
+fn pixels(points: f64, scale: f64) -> u32 {
+    ((points * scale).round() as u32).clamp(16, SOURCE.width())
+}
+
+// Area averaging, in premultiplied alpha so the transparent surround
+// doesn't darken the edges.
+fn scaled(size: u32) -> Vec<u8> {
+    let (width, height) = (SOURCE.width(), SOURCE.height());
+    let source = SOURCE.rgba();
+    let mut out = vec![0u8; (size * size * 4) as usize];
+    for y in 0..size {
+        let (y0, y1) = (
+            y * height / size,
+            ((y + 1) * height / size).max(y * height / size + 1),
+        );
+        for x in 0..size {
+            let (x0, x1) = (
+                x * width / size,
+                ((x + 1) * width / size).max(x * width / size + 1),
+            );
+            let mut sum = [0u64; 4];
+            for sy in y0..y1 {
+                for sx in x0..x1 {
+                    let p = &source[((sy * width + sx) * 4) as usize..][..4];
+                    let alpha = p[3] as u64;
+                    for c in 0..3 {
+                        sum[c] += p[c] as u64 * alpha;
+                    }
+                    sum[3] += alpha;
+                }
+            }
+            let o = ((y * size + x) * 4) as usize;
+            for c in 0..3 {
+                out[o + c] = sum[c].checked_div(sum[3]).unwrap_or(0) as u8;
+            }
+            out[o + 3] = (sum[3] / ((y1 - y0) * (x1 - x0)) as u64) as u8;
+        }
+    }
+    out
+}
+
+// A dot of radius `r` at (cx, cy) over the image, edges antialiased, with a
+// transparent ring `gap` wide cut around it so it stands apart from the icon.
+fn dot(rgba: &mut [u8], size: u32, (cx, cy): (f64, f64), r: f64, gap: f64) {
+    for y in 0..size {
+        for x in 0..size {
+            let d = ((x as f64 + 0.5 - cx).powi(2) + (y as f64 + 0.5 - cy).powi(2)).sqrt();
+            let cover = (r - d + 0.5).clamp(0.0, 1.0);
+            let cut = (r + gap - d + 0.5).clamp(0.0, 1.0);
+            let o = ((y * size + x) * 4) as usize;
+            let below = rgba[o + 3] as f64 / 255.0 * (1.0 - cut);
+            let alpha = cover + below * (1.0 - cover);
+            for c in 0..3 {
+                rgba[o + c] = if alpha > 0.0 {
+                    ((BADGE[c] as f64 * cover + rgba[o + c] as f64 * below * (1.0 - cover)) / alpha)
+                        .round() as u8
+                } else {
+                    0
+                };
+            }
+            rgba[o + 3] = (alpha * 255.0).round() as u8;
+        }
+    }
+}
+
+fn draw(size: u32, badge: bool) -> Image<'static> {
+    let mut rgba = scaled(size);
+    if badge {
+        let s = size as f64;
+        let r = s * 0.22;
+        dot(&mut rgba, size, (s - r, r), r, s * 0.06);
+    }
+    Image::new_owned(rgba, size, size)
+}
+
+// The badge alone, filling the overlay's square.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn overlay(size: u32) -> Image<'static> {
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+    let s = size as f64;
+    dot(&mut rgba, size, (s / 2.0, s / 2.0), s * 0.4, 0.0);
+    Image::new_owned(rgba, size, size)
+}
+
+// For `build_tray`.
+pub fn icon(app: &AppHandle) -> Image<'static> {
+    let size = pixels(tray_points(), scale(app));
+    let badge = UNREAD.load(Ordering::Relaxed);
+    *DRAWN.lock().unwrap() = Some((size, badge));
+    draw(size, badge)
+}
+
+// After a scale change; cheap when nothing changed.
+pub fn refresh(app: &AppHandle) {
+    let scale = scale(app);
+    let size = pixels(tray_points(), scale);
+    let badge = UNREAD.load(Ordering::Relaxed);
+    {
+        let mut drawn = DRAWN.lock().unwrap();
+        if *drawn == Some((size, badge)) {
+            return;
+        }
+        *drawn = Some((size, badge));
+    }
+    debug!(size, badge, "tray icon redrawn");
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_icon(Some(draw(size, badge)));
+    }
+    platform::show_overlay(app, badge, scale);
+}
+
+pub fn set_unread(app: &AppHandle, unread: bool) {
+    UNREAD.store(unread, Ordering::Relaxed);
+    refresh(app);
+}
+
+#[cfg(windows)]
+mod platform {
+    use tauri::{AppHandle, Manager};
+
+    use crate::scope;
+
+    pub fn show_overlay(app: &AppHandle, badge: bool, scale: f64) {
+        let Some(window) = app.get_webview_window(scope::MAIN_WINDOW) else {
+            return;
+        };
+        let icon = badge.then(|| super::overlay(super::pixels(16.0, scale)));
+        let _ = window.set_overlay_icon(icon);
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use tauri::AppHandle;
+
+    pub fn show_overlay(_app: &AppHandle, _badge: bool, _scale: f64) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(image: &Image, x: u32, y: u32) -> [u8; 4] {
+        let o = ((y * image.width() + x) * 4) as usize;
+        image.rgba()[o..o + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn sizes_for_the_display() {
+        assert_eq!(pixels(16.0, 1.0), 16);
+        assert_eq!(pixels(22.0, 1.5), 33);
+        assert_eq!(pixels(8.0, 1.0), 16);
+        assert_eq!(pixels(200.0, 2.0), SOURCE.width());
+    }
+
+    #[test]
+    fn badge_in_the_corner() {
+        let size = 32;
+        let plain = draw(size, false);
+        let badged = draw(size, true);
+        assert_eq!(plain.rgba().len(), (size * size * 4) as usize);
+        let centre = (size as f64 - size as f64 * 0.22) as u32;
+        let corner = pixel(&badged, centre, size - centre);
+        assert_eq!(corner, [BADGE[0], BADGE[1], BADGE[2], 255]);
+        assert_ne!(pixel(&plain, centre, size - centre), corner);
+        assert_eq!(pixel(&plain, 0, size - 1), pixel(&badged, 0, size - 1));
+    }
+
+    #[test]
+    fn overlay_is_the_badge_alone() {
+        let image = overlay(16);
+        assert_eq!(pixel(&image, 8, 8), [BADGE[0], BADGE[1], BADGE[2], 255]);
+        assert_eq!(pixel(&image, 0, 0)[3], 0);
+    }
+}
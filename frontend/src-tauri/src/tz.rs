@@ -0,0 +1,679 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use serde::Serialize;
+
+// Time zones by IANA name ("Europe/London"), for interviews set in the
+// recruiter's time rather than ours (calendar.rs). Linux and macOS read the
+// system's tz database; Windows asks the ICU that ships with it. A time is
+// kept as the wall-clock time and its zone, and turned into an instant only
+// when needed, so a change of offset (DST, or new rules in a tz update)
+// moves the instant and not the time agreed on.
+const DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Serialize)]
+pub struct ZonedTime {
+    pub timezone: String,
+    pub local_time: NaiveDateTime,
+    // "+01:00"
+    pub utc_offset: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Conversion {
+    pub at: DateTime<Utc>,
+    pub from: ZonedTime,
+    pub to: ZonedTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimeZones {
+    pub local: String,
+    pub zones: Vec<String>,
+}
+
+// Where the machine is now; follows the system setting as the user travels.
+pub fn local_zone() -> String {
+    iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".into())
+}
+
+// Names only, never paths.
+fn check(zone: &str) -> Result<(), String> {
+    let ok = !zone.is_empty()
+        && zone.split('/').all(|part| {
+            !part.is_empty()
+                && !part.starts_with('.')
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "_+-".contains(c))
+        });
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("unknown time zone {zone}"))
+    }
+}
+
+// Seconds east of UTC in `zone` at `at`.
+pub fn offset(zone: &str, at: DateTime<Utc>) -> Result<FixedOffset, String> {
+    check(zone)?;
+    let seconds = platform::offset(zone, at.timestamp())?;
+    FixedOffset::east_opt(seconds).ok_or_else(|| format!("bad offset for {zone}"))
+}
+
+pub fn known(zone: &str) -> bool {
+    offset(zone, Utc::now()).is_ok()
+}
+
+// The instant `wall` is in `zone`. Of a time that happens twice, as clocks go
+// back, the first; one skipped as they go forward counts from before the
+// change, so 02:30 on a spring-forward night is 03:30.
+pub fn resolve(zone: &str, wall: NaiveDateTime) -> Result<DateTime<Utc>, String> {
+    check(zone)?;
+    let naive = wall.and_utc().timestamp();
+    let before = platform::offset(zone, naive - DAY)?;
+    let after = platform::offset(zone, naive + DAY)?;
+    let mut valid = Vec::new();
+    for o in [before, after] {
+        let t = naive - i64::from(o);
+        if platform::offset(zone, t)? == o {
+            valid.push(t);
+        }
+    }
+    let t = valid.into_iter().min().unwrap_or(naive - i64::from(before));
+    DateTime::from_timestamp(t, 0).ok_or_else(|| "time out of range".into())
+}
+
+pub fn zoned(zone: &str, at: DateTime<Utc>) -> Result<ZonedTime, String> {
+    let offset = offset(zone, at)?;
+    Ok(ZonedTime {
+        timezone: zone.to_string(),
+        local_time: at.with_timezone(&offset).naive_local(),
+        utc_offset: offset.to_string(),
+    })
+}
+
+// Every zone there is a name for, and the one we're in.
+#[tauri::command]
+pub async fn list_timezones() -> Result<TimeZones, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut zones = platform::zones();
+        if !zones.iter().any(|z| z == "UTC") {
+            zones.push("UTC".into());
+        }
+        zones.sort();
+        zones.dedup();
+        Ok(TimeZones {
+            local: local_zone(),
+            zones,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// `local_time` in zone `from` as it reads in `to`, or here.
+#[tauri::command]
+pub async fn convert_time(
+    local_time: NaiveDateTime,
+    from: String,
+    to: Option<String>,
+) -> Result<Conversion, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let to = to.unwrap_or_else(local_zone);
+        let at = resolve(&from, local_time)?;
+        Ok(Conversion {
+            at,
+            from: zoned(&from, at)?,
+            to: zoned(&to, at)?,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(unix)]
+mod platform {
+    use chrono::{DateTime, Datelike, Duration, NaiveDate};
+    use std::{
+        collections::HashMap,
+        fs,
+        path::PathBuf,
+        sync::{Arc, LazyLock, Mutex},
+    };
+
+    // TZif files (RFC 8536), parsed once each.
+    static ZONES: LazyLock<Mutex<HashMap<String, Arc<Zone>>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+
+    fn dirs() -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = std::env::var_os("TZDIR")
+            .map(PathBuf::from)
+            .into_iter()
+            .collect();
+        dirs.extend(
+            [
+                "/usr/share/zoneinfo",
+                "/var/db/timezone/zoneinfo",
+                "/usr/lib/zoneinfo",
+            ]
+            .map(PathBuf::from),
+        );
+        dirs
+    }
+
+    struct Zone {
+        transitions: Vec<i64>,
+        // Per transition, an index into `offsets`
+        kinds: Vec<usize>,
+        offsets: Vec<i32>,
+        // After the last transition
+        rule: Option<Rule>,
+    }
+
+    impl Zone {
+        fn offset(&self, t: i64) -> i32 {
+            if let Some(rule) = &self.rule {
+                if self.transitions.last().is_none_or(|&last| t >= last) {
+                    return rule.offset(t);
+                }
+            }
+            match self.transitions.partition_point(|&x| x <= t) {
+                0 => self.offsets.first().copied().unwrap_or(0),
+                i => self.offsets[self.kinds[i - 1]],
+            }
+        }
+    }
+
+    fn load(name: &str) -> Result<Arc<Zone>, String> {
+        if let Some(zone) = ZONES.lock().unwrap().get(name) {
+            return Ok(zone.clone());
+        }
+        let data = dirs()
+            .into_iter()
+            .find_map(|dir| fs::read(dir.join(name)).ok())
+            .ok_or_else(|| format!("unknown time zone {name}"))?;
+        let zone = Arc::new(parse(&data).ok_or_else(|| format!("unreadable time zone {name}"))?);
+        ZONES.lock().unwrap().insert(name.to_string(), zone.clone());
+        Ok(zone)
+    }
+
+    pub fn offset(name: &str, t: i64) -> Result<i32, String> {
+        Ok(load(name)?.offset(t))
+    }
+
+    // zone1970.tab lists one zone per region, the rest being links.
+    pub fn zones() -> Vec<String> {
+        for dir in dirs() {
+            for table in ["zone1970.tab", "zone.tab"] {
+                let Ok(text) = fs::read_to_string(dir.join(table)) else {
+                    continue;
+                };
+                return text
+                    .lines()
+                    .filter(|l| !l.starts_with('#'))
+                    .filter_map(|l| l.split('\t').nth(2))
+                    .map(str::to_string)
+                    .collect();
+            }
+        }
+        Vec::new()
+    }
+
+    struct Reader<'a> {
+        data: &'a [u8],
+        at: usize,
+    }
+
+    impl Reader<'_> {
+        fn take(&mut self, n: usize) -> Option<&[u8]> {
+            let bytes = self.data.get(self.at..self.at.checked_add(n)?)?;
+            self.at += n;
+            Some(bytes)
+        }
+
+        fn u32(&mut self) -> Option<u32> {
+            Some(u32::from_be_bytes(self.take(4)?.try_into().ok()?))
+        }
+
+        fn time(&mut self, wide: bool) -> Option<i64> {
+            if wide {
+                Some(i64::from_be_bytes(self.take(8)?.try_into().ok()?))
+            } else {
+                Some(i64::from(i32::from_be_bytes(
+                    self.take(4)?.try_into().ok()?,
+                )))
+            }
+        }
+    }
+
+    fn parse(data: &[u8]) -> Option<Zone> {
+        let mut r = Reader { data, at: 0 };
+        let mut zone = block(&mut r, false)?;
+        // Version 2 on repeats it all with 64-bit times, then the rule
+        if data.get(4).is_some_and(|&v| v >= b'2') {
+            zone = block(&mut r, true)?;
+            let rest = std::str::from_utf8(&data[r.at..]).ok()?;
+            zone.rule = rest
+                .trim_start_matches('\n')
+                .split('\n')
+                .next()
+                .filter(|s| !s.is_empty())
+                .and_then(Rule::parse);
+        }
+        Some(zone)
+    }
+
+    fn block(r: &mut Reader, wide: bool) -> Option<Zone> {
+        if r.take(4)? != b"TZif" {
+            return None;
+        }
+        r.take(16)?;
+        let counts: Vec<usize> = (0..6)
+            .map(|_| r.u32().map(|n| n as usize))
+            .collect::<Option<_>>()?;
+        let [isut, isstd, leap, time, kind, chars] = counts[..] else {
+            return None;
+        };
+        let transitions = (0..time)
+            .map(|_| r.time(wide))
+            .collect::<Option<Vec<_>>>()?;
+        let kinds: Vec<usize> = r.take(time)?.iter().map(|&k| k as usize).collect();
+        let mut offsets = Vec::with_capacity(kind);
+        for _ in 0..kind {
+            let record = r.take(6)?;
+            offsets.push(i32::from_be_bytes(record[..4].try_into().ok()?));
+        }
+        if kinds.iter().any(|&k| k >= offsets.len()) {
+            return None;
+        }
+        r.take(chars + leap * if wide { 12 } else { 8 } + isstd + isut)?;
+        Some(Zone {
+            transitions,
+            kinds,
+            offsets,
+            rule: None,
+        })
+    }
+
+    // A POSIX TZ string, like "CET-1CEST,M3.5.0,M10.5.0/3".
+    struct Rule {
+        standard: i32,
+        summer: Option<Summer>,
+    }
+
+    struct Summer {
+        offset: i32,
+        start: (When, i64),
+        end: (When, i64),
+    }
+
+    enum When {
+        // 1 to 365, never counting 29 February
+        Julian(i64),
+        // 0 to 365
+        Day(i64),
+        // Month, week (5 being the last) and weekday from Sunday
+        Weekday(u32, u32, u32),
+    }
+
+    impl When {
+        fn date(&self, year: i32) -> Option<NaiveDate> {
+            let january = NaiveDate::from_ymd_opt(year, 1, 1)?;
+            match *self {
+                When::Julian(n) => {
+                    let leap = january.leap_year() && n >= 60;
+                    Some(january + Duration::days(n - 1 + i64::from(leap)))
+                }
+                When::Day(n) => Some(january + Duration::days(n)),
+                When::Weekday(month, week, weekday) => {
+                    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                    let skip = (weekday + 7 - first.weekday().num_days_from_sunday()) % 7;
+                    let mut day = first + Duration::days(i64::from(skip + (week - 1) * 7));
+                    while day.month() != month {
+                        day -= Duration::days(7);
+                    }
+                    Some(day)
+                }
+            }
+        }
+    }
+
+    struct Cursor<'a> {
+        s: &'a [u8],
+        at: usize,
+    }
+
+    impl Cursor<'_> {
+        fn peek(&self) -> Option<u8> {
+            self.s.get(self.at).copied()
+        }
+
+        fn eat(&mut self, c: u8) -> bool {
+            let found = self.peek() == Some(c);
+            self.at += usize::from(found);
+            found
+        }
+
+        fn number(&mut self) -> Option<i64> {
+            let start = self.at;
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.at += 1;
+            }
+            std::str::from_utf8(&self.s[start..self.at])
+                .ok()?
+                .parse()
+                .ok()
+        }
+
+        fn name(&mut self) -> Option<()> {
+            if self.eat(b'<') {
+                while !self.eat(b'>') {
+                    self.peek()?;
+                    self.at += 1;
+                }
+            } else {
+                let start = self.at;
+                while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                    self.at += 1;
+                }
+                if self.at - start < 3 {
+                    return None;
+                }
+            }
+            Some(())
+        }
+
+        // [+-]hh[:mm[:ss]], in seconds
+        fn time(&mut self) -> Option<i64> {
+            let sign = if self.eat(b'-') {
+                -1
+            } else {
+                self.eat(b'+');
+                1
+            };
+            let mut seconds = self.number()? * 3600;
+            for unit in [60, 1] {
+                if !self.eat(b':') {
+                    break;
+                }
+                seconds += self.number()? * unit;
+            }
+            Some(sign * seconds)
+        }
+
+        fn when(&mut self) -> Option<(When, i64)> {
+            let when = if self.eat(b'J') {
+                When::Julian(self.number()?)
+            } else if self.eat(b'M') {
+                let month = self.number()?;
+                self.eat(b'.').then_some(())?;
+                let week = self.number()?;
+                self.eat(b'.').then_some(())?;
+                let weekday = self.number()?;
+                if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+                    return None;
+                }
+                When::Weekday(month as u32, week as u32, weekday as u32)
+            } else {
+                When::Day(self.number()?)
+            };
+            let at = if self.eat(b'/') {
+                self.time()?
+            } else {
+                2 * 3600
+            };
+            Some((when, at))
+        }
+    }
+
+    impl Rule {
+        fn parse(text: &str) -> Option<Rule> {
+            let mut c = Cursor {
+                s: text.as_bytes(),
+                at: 0,
+            };
+            c.name()?;
+            // POSIX counts west of UTC
+            let standard = -c.time()?;
+            if c.peek().is_none() {
+                return Some(Rule {
+                    standard: standard as i32,
+                    summer: None,
+                });
+            }
+            c.name()?;
+            let summer = match c.peek() {
+                Some(b',') | None => standard + 3600,
+                _ => -c.time()?,
+            };
+            // Without dates, the US ones
+            let (start, end) = if c.eat(b',') {
+                let start = c.when()?;
+                c.eat(b',').then_some(())?;
+                (start, c.when()?)
+            } else {
+                (
+                    (When::Weekday(3, 2, 0), 7200),
+                    (When::Weekday(11, 1, 0), 7200),
+                )
+            };
+            Some(Rule {
+                standard: standard as i32,
+                summer: Some(Summer {
+                    offset: summer as i32,
+                    start,
+                    end,
+                }),
+            })
+        }
+
+        fn offset(&self, t: i64) -> i32 {
+            let Some(summer) = &self.summer else {
+                return self.standard;
+            };
+            let year = DateTime::from_timestamp(t + i64::from(self.standard), 0)
+                .map(|d| d.year())
+                .unwrap_or(1970);
+            let instant = |(when, at): &(When, i64), offset: i32| {
+                when.date(year).map(|d| {
+                    d.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp() + at - i64::from(offset)
+                })
+            };
+            let (Some(start), Some(end)) = (
+                instant(&summer.start, self.standard),
+                instant(&summer.end, summer.offset),
+            ) else {
+                return self.standard;
+            };
+            // Southern summers span the new year
+            let in_summer = if start < end {
+                start <= t && t < end
+            } else {
+                !(end <= t && t < start)
+            };
+            if in_summer {
+                summer.offset
+            } else {
+                self.standard
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn rule_dates() {
+            let date = |when: When, year| when.date(year).unwrap().to_string();
+            assert_eq!(date(When::Weekday(3, 5, 0), 2026), "2026-03-29");
+            assert_eq!(date(When::Weekday(10, 1, 0), 2026), "2026-10-04");
+            assert_eq!(date(When::Julian(60), 2024), "2024-03-01");
+            assert_eq!(date(When::Day(59), 2024), "2024-02-29");
+        }
+
+        #[test]
+        fn rules_north_and_south() {
+            let at = |date: &str| {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap()
+                    .and_utc()
+                    .timestamp()
+            };
+            let madrid = Rule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+            assert_eq!(madrid.offset(at("2040-01-15")), 3600);
+            assert_eq!(madrid.offset(at("2040-07-15")), 7200);
+            let sydney = Rule::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+            assert_eq!(sydney.offset(at("2040-01-15")), 39600);
+            assert_eq!(sydney.offset(at("2040-07-15")), 36000);
+            let fixed = Rule::parse("<-03>3").unwrap();
+            assert_eq!(fixed.offset(at("2040-07-15")), -10800);
+            assert!(Rule::parse("X1").is_none());
+            assert!(Rule::parse("CET-1CEST,M13.5.0,M10.5.0").is_none());
+        }
+
+        #[test]
+        fn rejects_what_isnt_tzif() {
+            assert!(parse(b"TZif").is_none());
+            assert!(parse(b"not a zone file at all, not even close").is_none());
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use windows::{
+        core::PCSTR,
+        Win32::Globalization::{
+            ucal_close, ucal_get, ucal_getCanonicalTimeZoneID, ucal_open,
+            ucal_openTimeZoneIDEnumeration, ucal_setMillis, uenum_close, uenum_unext, UErrorCode,
+            UCAL_DST_OFFSET, UCAL_GREGORIAN, UCAL_ZONE_OFFSET, UCAL_ZONE_TYPE_CANONICAL_LOCATION,
+            U_ZERO_ERROR,
+        },
+    };
+
+    // ICU puts warnings below zero and errors above.
+    fn failed(status: UErrorCode) -> bool {
+        status.0 > 0
+    }
+
+    pub fn offset(name: &str, t: i64) -> Result<i32, String> {
+        let id: Vec<u16> = name.encode_utf16().collect();
+        let unknown = || format!("unknown time zone {name}");
+        unsafe {
+            // ICU quietly takes an unknown zone for UTC; ask first
+            let mut status = U_ZERO_ERROR;
+            let mut canonical = [0u16; 64];
+            let mut system = 0i8;
+            ucal_getCanonicalTimeZoneID(
+                id.as_ptr(),
+                id.len() as i32,
+                canonical.as_mut_ptr(),
+                canonical.len() as i32,
+                &mut system,
+                &mut status,
+            );
+            if failed(status) || system == 0 {
+                return Err(unknown());
+            }
+            let calendar = ucal_open(
+                id.as_ptr(),
+                id.len() as i32,
+                PCSTR::null(),
+                UCAL_GREGORIAN,
+                &mut status,
+            );
+            if failed(status) || calendar.is_null() {
+                return Err(unknown());
+            }
+            ucal_setMillis(calendar, t as f64 * 1000.0, &mut status);
+            let raw = ucal_get(calendar as _, UCAL_ZONE_OFFSET, &mut status);
+            let summer = ucal_get(calendar as _, UCAL_DST_OFFSET, &mut status);
+            ucal_close(calendar);
+            if failed(status) {
+                return Err(unknown());
+            }
+            Ok((raw + summer) / 1000)
+        }
+    }
+
+    pub fn zones() -> Vec<String> {
+        let mut zones = Vec::new();
+        unsafe {
+            let mut status = U_ZERO_ERROR;
+            let list = ucal_openTimeZoneIDEnumeration(
+                UCAL_ZONE_TYPE_CANONICAL_LOCATION,
+                PCSTR::null(),
+                std::ptr::null(),
+                &mut status,
+            );
+            if failed(status) || list.is_null() {
+                return zones;
+            }
+            loop {
+                let mut len = 0;
+                let id = uenum_unext(list, &mut len, &mut status);
+                if failed(status) || id.is_null() {
+                    break;
+                }
+                zones.push(String::from_utf16_lossy(std::slice::from_raw_parts(
+                    id,
+                    len as usize,
+                )));
+            }
+            uenum_close(list);
+        }
+        zones
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall(text: &str) -> NaiveDateTime {
+        text.parse().unwrap()
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn names_not_paths() {
+        assert!(check("America/Argentina/Buenos_Aires").is_ok());
+        assert!(check("Etc/GMT+3").is_ok());
+        for bad in [
+            "",
+            "../etc/passwd",
+            "Europe//Madrid",
+            "Europe/.hidden",
+            "Europe\\Madrid",
+        ] {
+            assert!(check(bad).is_err(), "{bad}");
+        }
+        assert!(!known("Europe/Atlantis"));
+    }
+
+    #[test]
+    fn offsets_follow_the_season() {
+        let madrid = |at: &str| offset("Europe/Madrid", utc(at)).unwrap().to_string();
+        assert_eq!(madrid("2026-01-15T12:00:00Z"), "+01:00");
+        assert_eq!(madrid("2026-07-15T12:00:00Z"), "+02:00");
+        let zoned = zoned("America/New_York", utc("2026-07-15T12:00:00Z")).unwrap();
+        assert_eq!(zoned.local_time, wall("2026-07-15T08:00:00"));
+        assert_eq!(zoned.utc_offset, "-04:00");
+    }
+
+    #[test]
+    fn wall_times_across_changes() {
+        let madrid = |text: &str| resolve("Europe/Madrid", wall(text)).unwrap();
+        assert_eq!(madrid("2026-07-01T09:00:00"), utc("2026-07-01T07:00:00Z"));
+        // Skipped as clocks go forward: from before the change
+        assert_eq!(madrid("2026-03-29T02:30:00"), utc("2026-03-29T01:30:00Z"));
+        // Twice as they go back: the first
+        assert_eq!(madrid("2026-10-25T02:30:00"), utc("2026-10-25T00:30:00Z"));
+        assert!(resolve("Nowhere/City", wall("2026-07-01T09:00:00")).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::{fs, path::PathBuf};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tracing::warn;
+
+use crate::{
+    applock, audit, datadir, export, historycrypt, i18n, overrides, paths, profiles, proxy, reset,
+    sync, vault,
+};
+
+// The tray's "Uninstall JobBot..." opens this window (frontend route
+// /uninstall) instead of quitting straight away, so the user can opt into
+// wiping everything the app left on the machine. The window lists exactly
+// what `wipe` deletes: every profile, not just the active one.
+pub const UNINSTALL_WINDOW: &str = "uninstall";
+
+#[derive(Debug, Serialize)]
+pub struct WipePlan {
+    // And the files of DATA_ENTRIES in a data folder the app doesn't own
+    pub folders: Vec<PathBuf>,
+    // "service / account" of keychain entries that exist
+    pub credentials: Vec<String>,
+}
+
+// What the app keeps in a data folder, the default profile's and (under
+// `profiles`) every other one's. A folder from JOBBOT_DATA_DIR or chosen in
+// Settings (datadir.rs) is the user's, so only these go from it.
+const DATA_ENTRIES: &[&str] = &[
+    "profiles",
+    "engine",
+    "backend",
+    "backups",
+    "browser-profiles",
+    "datapacks",
+    "documents",
+    "drivers",
+    "evidence",
+    "quarantine",
+    "history.db",
+    "history.db-journal",
+    "history.db.converting",
+    "job-alerts.json",
+    "offline-queue.json",
+    "templates.json",
+    "webhook-deliveries.json",
+    "mail-watch.json",
+    "exchange-rates.json",
+];
+
+// Config, data (custom and default location), webview data, cache and logs.
+// Anything containing the home dir is left alone in case JOBBOT_DATA_DIR or
+// a custom location points somewhere broad; of those only what the app put
+// there goes, never the folder itself.
+fn folders(app: &AppHandle) -> Vec<PathBuf> {
+    let path = app.path();
+    let home = path.home_dir().ok();
+    let supplied = overrides::get()
+        .data_dir
+        .clone()
+        .or_else(datadir::custom)
+        .filter(|d| d.exists())
+        .filter(|d| home.as_ref().is_none_or(|h| !h.starts_with(d)));
+    let mut dirs: Vec<PathBuf> = [
+        paths::base_config_dir(app).ok(),
+        paths::default_data_dir(app).ok(),
+        path.app_local_data_dir().ok(),
+        path.app_cache_dir().ok(),
+        path.app_log_dir().ok(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|d| d.exists())
+    .filter(|d| home.as_ref().is_none_or(|h| !h.starts_with(d)))
+    .filter(|d| supplied.as_ref().is_none_or(|s| !s.starts_with(d)))
+    .chain(supplied.iter().flat_map(|root| {
+        DATA_ENTRIES
+            .iter()
+            .map(|entry| root.join(entry))
+            .filter(|p| p.exists())
+    }))
+    .collect();
+    dirs.sort();
+    dirs.dedup();
+    // Removing a parent takes its children with it
+    let nested: Vec<PathBuf> = dirs
+        .iter()
+        .filter(|d| dirs.iter().any(|o| o != *d && d.starts_with(o)))
+        .cloned()
+        .collect();
+    dirs.retain(|d| !nested.contains(d));
+    dirs
+}
+
+fn proxy_username(settings_file: PathBuf) -> Option<String> {
+    let value: Value = serde_json::from_str(&fs::read_to_string(settings_file).ok()?).ok()?;
+    value["proxy"]["username"].as_str().map(str::to_string)
+}
+
+// Needs the config of every profile, so must run before the folders go.
+fn credentials(app: &AppHandle) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = export::BACKEND_SECRETS
+        .iter()
+        .map(|(s, a)| (s.to_string(), a.to_string()))
+        .collect();
+    entries.push((
+        vault::KEYCHAIN_SERVICE.into(),
+        vault::KEYCHAIN_ACCOUNT.into(),
+    ));
+    entries.push((
+        audit::KEYCHAIN_SERVICE.into(),
+        audit::KEYCHAIN_ACCOUNT.into(),
+    ));
+    entries.push((
+        historycrypt::KEYCHAIN_SERVICE.into(),
+        historycrypt::KEYCHAIN_ACCOUNT.into(),
+    ));
+    let base = paths::base_config_dir(app).ok();
+    for profile in profiles::list(app).profiles {
+        if let Some(user) = base
+            .clone()
+            .and_then(|b| proxy_username(profiles::dir_for(b, &profile.id).join("settings.json")))
+        {
+            entries.push((proxy::KEYCHAIN_SERVICE.into(), user));
+        }
+        entries.push((sync::KEYCHAIN_SERVICE.into(), profile.id));
+    }
+    entries.retain(|(service, account)| {
+        keyring::Entry::new(service, account).is_ok_and(|e| e.get_password().is_ok())
+    });
+    entries
+}
+
+pub fn plan(app: &AppHandle) -> WipePlan {
+    WipePlan {
+        folders: folders(app),
+        credentials: credentials(app)
+            .into_iter()
+            .map(|(service, account)| format!("{service} / {account}"))
+            .collect(),
+    }
+}
+
+// Stops the engine so nothing holds the database open, then deletes the
+// keychain entries followed by the folders. Carries on past failures and
+// reports them all.
+pub fn wipe(app: &AppHandle) -> Result<(), String> {
+    crate::kill_backend(app);
+    let mut errors = Vec::new();
+    for (service, account) in credentials(app) {
+        let deleted = keyring::Entry::new(&service, &account).and_then(|e| e.delete_credential());
+        if let Err(e) = deleted {
+            errors.push(format!("{service} / {account}: {e}"));
+        }
+    }
+    // Forget the cached key too
+    vault::wipe(app);
+    for path in folders(app) {
+        let removed = if path.is_dir() {
+            reset::remove_dir(&path)
+        } else {
+            reset::remove_file(&path)
+        };
+        if let Err(e) = removed {
+            errors.push(e);
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+pub fn open_window(app: &AppHandle) {
+    if !applock::gate(app, open_window) {
+        return;
+    }
+    if let Some(w) = app.get_webview_window(UNINSTALL_WINDOW) {
+        let _ = w.show();
+        let _ = w.set_focus();
+        return;
+    }
+    let built =
+        WebviewWindowBuilder::new(app, UNINSTALL_WINDOW, WebviewUrl::App("uninstall".into()))
+            .title(i18n::t("window-uninstall"))
+            .inner_size(520.0, 560.0)
+            .resizable(true)
+            .center()
+            .build();
+    if let Err(e) = built {
+        warn!("uninstall window failed: {e}");
+    }
+}
+
+// Keychain lookups can block, so run them off the IPC thread.
+#[tauri::command]
+pub async fn get_wipe_plan(app: AppHandle) -> Result<WipePlan, String> {
+    tauri::async_runtime::spawn_blocking(move || plan(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,584 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{sync::Mutex, thread, time::Duration};
+use tauri::{AppHandle, Emitter, Manager, Url, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_updater::{Update, UpdaterBuilder, UpdaterExt};
+use tracing::warn;
+
+use crate::{
+    i18n, notifications,
+    settings::{self, UpdateChannel},
+    sidecar,
+    telemetry::{self, Counter},
+};
+
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+// Stable uses the endpoints in tauri.conf.json (latest published release).
+// Beta builds are published under a rolling `beta` release tag.
+const BETA_ENDPOINT: &str = "https://github.com/IFAKA/currobot/releases/download/beta/latest.json";
+
+// Every release also carries its own latest.json, which is how a rollback
+// finds (and signature-checks) the previous installer.
+const RELEASE_ENDPOINT: &str =
+    "https://github.com/IFAKA/currobot/releases/download/v{version}/latest.json";
+
+pub const RELEASE_NOTES_WINDOW: &str = "release-notes";
+
+// A downloaded, signature-verified update waiting for the user to restart.
+struct Pending {
+    update: Update,
+    bytes: Vec<u8>,
+    // Whether the user has been told about it (notification or window)
+    prompted: bool,
+}
+
+// Lifecycle events for the Settings page progress bar; the latest one is
+// also kept so a page opened mid-download can catch up.
+pub const UPDATE_STATUS_EVENT: &str = "update-status";
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum UpdateStatus {
+    #[default]
+    Idle,
+    Checking,
+    UpToDate,
+    Downloading {
+        downloaded: u64,
+        total: Option<u64>,
+        percent: Option<u8>,
+    },
+    Verifying,
+    Ready {
+        version: String,
+    },
+    Installing,
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Default)]
+pub struct UpdaterState {
+    pending: Mutex<Option<Pending>>,
+    status: Mutex<UpdateStatus>,
+}
+
+fn set_status(app: &AppHandle, status: UpdateStatus) {
+    *app.state::<UpdaterState>().status.lock().unwrap() = status.clone();
+    let _ = app.emit(UPDATE_STATUS_EVENT, status);
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub notes: Option<String>,
+    pub critical: bool,
+}
+
+impl UpdateInfo {
+    fn from_update(update: &Update) -> Self {
+        Self {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            notes: update.body.clone(),
+            critical: is_critical(update),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnoozeFor {
+    Day,
+    Week,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub current_version: String,
+    pub date: Option<String>,
+    pub notes: Option<String>,
+    pub critical: bool,
+}
+
+// Set as `"critical": true` in latest.json for security or data-loss fixes.
+fn is_critical(update: &Update) -> bool {
+    update.raw_json["critical"].as_bool().unwrap_or(false)
+}
+
+fn is_snoozed(app: &AppHandle) -> bool {
+    settings::get(app)
+        .update
+        .snoozed_until
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .is_some_and(|until| until > Utc::now())
+}
+
+// Tells the user about a downloaded update once, unless they snoozed prompts.
+// Critical updates ignore the snooze and open the release notes straight away.
+fn prompt_if_due(app: &AppHandle) {
+    let due = {
+        let state = app.state::<UpdaterState>();
+        let mut pending = state.pending.lock().unwrap();
+        match pending.as_mut() {
+            Some(p) if !p.prompted && (is_critical(&p.update) || !is_snoozed(app)) => {
+                p.prompted = true;
+                Some((p.update.version.clone(), is_critical(&p.update)))
+            }
+            _ => None,
+        }
+    };
+    let Some((version, critical)) = due else {
+        return;
+    };
+    if critical {
+        notifications::notify(
+            app,
+            &i18n::t("update-critical-title"),
+            &i18n::t_args(
+                "update-critical-body",
+                &[("version", version.as_str().into())],
+            ),
+        );
+        show_release_notes(app);
+    } else {
+        notifications::notify(
+            app,
+            &i18n::t("update-ready-title"),
+            &i18n::t_args("update-ready-body", &[("version", version.as_str().into())]),
+        );
+    }
+}
+
+pub fn is_ready(app: &AppHandle) -> bool {
+    app.state::<UpdaterState>()
+        .pending
+        .lock()
+        .unwrap()
+        .is_some()
+}
+
+fn pending_info(app: &AppHandle) -> Option<UpdateInfo> {
+    app.state::<UpdaterState>()
+        .pending
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|p| UpdateInfo::from_update(&p.update))
+}
+
+fn base_builder(app: &AppHandle) -> Result<UpdaterBuilder, String> {
+    let handle = app.clone();
+    let mut builder = app
+        .updater_builder()
+        // Windows exits the process itself once the installer launches
+        .on_before_exit(move || crate::kill_backend(&handle));
+    if let Some(proxy) = crate::net::proxy_url() {
+        builder = builder.proxy(Url::parse(&proxy).map_err(|e| e.to_string())?);
+    }
+    Ok(builder)
+}
+
+// `download` checks the minisign signature against the pubkey in
+// tauri.conf.json and errors out on mismatch, so only verified bytes are kept.
+async fn check_and_download(app: &AppHandle) -> Result<Option<UpdateInfo>, String> {
+    let result = fetch_update(app).await;
+    match &result {
+        Ok(Some(_)) => prompt_if_due(app),
+        Ok(None) => {}
+        Err(e) => set_status(app, UpdateStatus::Failed { error: e.clone() }),
+    }
+    result
+}
+
+async fn fetch_update(app: &AppHandle) -> Result<Option<UpdateInfo>, String> {
+    if let Some(info) = pending_info(app) {
+        set_status(
+            app,
+            UpdateStatus::Ready {
+                version: info.version.clone(),
+            },
+        );
+        return Ok(Some(info));
+    }
+    set_status(app, UpdateStatus::Checking);
+    let mut builder = base_builder(app)?;
+    let update_settings = settings::get(app).update;
+    if update_settings.channel == UpdateChannel::Beta {
+        let url = Url::parse(BETA_ENDPOINT).map_err(|e| e.to_string())?;
+        builder = builder.endpoints(vec![url]).map_err(|e| e.to_string())?;
+    }
+    // A beta build switching back to stable has a higher (pre-release)
+    // version than the latest stable, so accept any differing version.
+    let any_version = update_settings.channel == UpdateChannel::Stable
+        && !app.package_info().version.pre.is_empty();
+    let skipped = update_settings.skipped_version;
+    builder = builder.version_comparator(move |current, remote| {
+        if skipped.as_deref() == Some(remote.version.to_string().as_str()) {
+            return false;
+        }
+        if any_version {
+            remote.version != current
+        } else {
+            remote.version > current
+        }
+    });
+    let updater = builder.build().map_err(|e| e.to_string())?;
+    let Some(update) = updater.check().await.map_err(|e| e.to_string())? else {
+        set_status(app, UpdateStatus::UpToDate);
+        return Ok(None);
+    };
+
+    let mut downloaded = 0u64;
+    let mut last_percent = None;
+    let bytes = update
+        .download(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                let percent = total
+                    .filter(|t| *t > 0)
+                    .map(|t| (downloaded * 100 / t).min(100) as u8);
+                // One event per percent step, not per network chunk
+                if percent.is_none() || percent != last_percent {
+                    last_percent = percent;
+                    set_status(
+                        app,
+                        UpdateStatus::Downloading {
+                            downloaded,
+                            total,
+                            percent,
+                        },
+                    );
+                }
+            },
+            || set_status(app, UpdateStatus::Verifying),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let info = UpdateInfo::from_update(&update);
+    *app.state::<UpdaterState>().pending.lock().unwrap() = Some(Pending {
+        update,
+        bytes,
+        prompted: false,
+    });
+    set_status(
+        app,
+        UpdateStatus::Ready {
+            version: info.version.clone(),
+        },
+    );
+    crate::refresh_tray_menu(app);
+    Ok(Some(info))
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || {
+        thread::sleep(FIRST_CHECK_DELAY);
+        loop {
+            if let Err(e) = tauri::async_runtime::block_on(check_and_download(&app)) {
+                warn!("update check failed: {e}");
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+// latest.json may carry the notes inline or point at them: either a
+// `notes_url` field next to `notes`, or `notes` that is itself just a URL.
+fn notes_source(body: Option<&str>, raw_json: &Value) -> (Option<String>, Option<String>) {
+    let url = raw_json["notes_url"].as_str().map(str::to_string);
+    match body.map(str::trim) {
+        Some(b) if url.is_none() && (b.starts_with("https://") || b.starts_with("http://")) => {
+            (None, Some(b.to_string()))
+        }
+        _ => (body.map(str::to_string), url),
+    }
+}
+
+fn fetch_notes(url: &str) -> Result<String, String> {
+    crate::net::client(Duration::from_secs(15))?
+        .get(url)
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.text())
+        .map_err(|e| e.to_string())
+}
+
+// Small window rendering the notes (frontend route /release-notes) with the
+// restart button, so the user sees what changes before confirming.
+pub fn show_release_notes(app: &AppHandle) {
+    if let Some(w) = app.get_webview_window(RELEASE_NOTES_WINDOW) {
+        let _ = w.show();
+        let _ = w.set_focus();
+        return;
+    }
+    let built = WebviewWindowBuilder::new(
+        app,
+        RELEASE_NOTES_WINDOW,
+        WebviewUrl::App("release-notes".into()),
+    )
+    .title(i18n::t("window-release-notes"))
+    .inner_size(520.0, 600.0)
+    .resizable(true)
+    .build();
+    if let Err(e) = built {
+        warn!("release notes window failed: {e}");
+    }
+}
+
+fn install(app: &AppHandle) -> Result<(), String> {
+    let pending = app
+        .state::<UpdaterState>()
+        .pending
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("no update has been downloaded")?;
+    set_status(app, UpdateStatus::Installing);
+    // Recorded before installing, since the Windows installer exits the
+    // process; put back if the install fails, so a rollback doesn't target
+    // the version still running
+    let before = settings::get(app).update;
+    let current = pending.update.current_version.clone();
+    let backend = sidecar::active_version(app);
+    if let Err(e) = settings::update(app, |s| {
+        s.update.previous_version = Some(current);
+        s.update.previous_backend = backend;
+        s.update.snoozed_until = None;
+    }) {
+        warn!("could not record rollback point: {e}");
+    }
+    if let Err(e) = pending.update.install(&pending.bytes) {
+        if let Err(e) = settings::update(app, |s| {
+            s.update.previous_version = before.previous_version;
+            s.update.previous_backend = before.previous_backend;
+        }) {
+            warn!("could not restore rollback point: {e}");
+        }
+        // Kept so the user can retry from the tray; the next check shows it
+        // as ready again
+        *app.state::<UpdaterState>().pending.lock().unwrap() = Some(pending);
+        set_status(
+            app,
+            UpdateStatus::Failed {
+                error: e.to_string(),
+            },
+        );
+        return Err(e.to_string());
+    }
+    // RunEvent::Exit stops the backend before the relaunch
+    app.restart();
+}
+
+// Reinstalls the release that was running before the last update and puts the
+// backend back the way it was. The version rolled back from is skipped by
+// future checks until a newer one ships.
+async fn rollback(app: &AppHandle) -> Result<(), String> {
+    let update_settings = settings::get(app).update;
+    let target = update_settings
+        .previous_version
+        .ok_or("no previous version to roll back to")?;
+    let url =
+        Url::parse(&RELEASE_ENDPOINT.replace("{version}", &target)).map_err(|e| e.to_string())?;
+    set_status(app, UpdateStatus::Checking);
+    let wanted = target.clone();
+    let update = base_builder(app)?
+        .endpoints(vec![url])
+        .map_err(|e| e.to_string())?
+        .version_comparator(move |_, remote| remote.version.to_string() == wanted)
+        .build()
+        .map_err(|e| e.to_string())?
+        .check()
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("release {target} is not available for this platform"))?;
+
+    let bytes = update
+        .download(|_, _| {}, || set_status(app, UpdateStatus::Verifying))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let current = app.package_info().version.to_string();
+    sidecar::select_for_next_launch(app, update_settings.previous_backend)?;
+    settings::update(app, |s| {
+        s.update.previous_version = None;
+        s.update.previous_backend = None;
+        s.update.skipped_version = Some(current);
+    })?;
+    app.state::<UpdaterState>().pending.lock().unwrap().take();
+    set_status(app, UpdateStatus::Installing);
+    update.install(&bytes).map_err(|e| e.to_string())?;
+    app.restart();
+}
+
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    crate::paths::updates_allowed()?;
+    check_and_download(&app).await
+}
+
+#[tauri::command]
+pub async fn get_release_notes(app: AppHandle) -> Result<ReleaseNotes, String> {
+    let (notes, url, mut release) = {
+        let state = app.state::<UpdaterState>();
+        let pending = state.pending.lock().unwrap();
+        let update = &pending
+            .as_ref()
+            .ok_or("no update has been downloaded")?
+            .update;
+        let (notes, url) = notes_source(update.body.as_deref(), &update.raw_json);
+        let release = ReleaseNotes {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            date: update.date.map(|d| d.to_string()),
+            notes: None,
+            critical: is_critical(update),
+        };
+        (notes, url, release)
+    };
+    release.notes = match url {
+        Some(url) => {
+            match tauri::async_runtime::spawn_blocking(move || fetch_notes(&url))
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                Ok(text) => Some(text),
+                // Fall back to whatever was inline rather than showing nothing
+                Err(e) => {
+                    warn!("release notes fetch failed: {e}");
+                    notes
+                }
+            }
+        }
+        None => notes,
+    };
+    Ok(release)
+}
+
+#[tauri::command]
+pub fn open_release_notes(app: AppHandle) {
+    show_release_notes(&app);
+}
+
+#[tauri::command]
+pub fn get_update_status(app: AppHandle) -> UpdateStatus {
+    app.state::<UpdaterState>().status.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn install_update(app: AppHandle) -> Result<(), String> {
+    install(&app)
+}
+
+#[tauri::command]
+pub fn get_previous_version(app: AppHandle) -> Option<String> {
+    settings::get(&app).update.previous_version
+}
+
+#[tauri::command]
+pub async fn rollback_update(app: AppHandle) -> Result<(), String> {
+    crate::paths::updates_allowed()?;
+    telemetry::record(&app, Counter::UpdateRollback);
+    let result = rollback(&app).await;
+    if let Err(e) = &result {
+        set_status(&app, UpdateStatus::Failed { error: e.clone() });
+    }
+    result
+}
+
+// Postpones the "update ready" prompt; the update stays downloaded and can
+// still be installed from the tray or Settings in the meantime.
+#[tauri::command]
+pub fn snooze_update(app: AppHandle, duration: SnoozeFor) -> Result<String, String> {
+    let until = Utc::now()
+        + match duration {
+            SnoozeFor::Day => chrono::Duration::days(1),
+            SnoozeFor::Week => chrono::Duration::weeks(1),
+        };
+    let until = until.to_rfc3339();
+    let stored = until.clone();
+    settings::update(&app, |s| s.update.snoozed_until = Some(stored))?;
+    // Prompt again once the snooze runs out
+    if let Some(p) = app.state::<UpdaterState>().pending.lock().unwrap().as_mut() {
+        p.prompted = false;
+    }
+    Ok(until)
+}
+
+#[tauri::command]
+pub fn get_update_channel(app: AppHandle) -> UpdateChannel {
+    settings::get(&app).update.channel
+}
+
+// Drops any update already downloaded from the other channel, then checks the
+// new one in the background.
+#[tauri::command]
+pub fn set_update_channel(app: AppHandle, channel: UpdateChannel) -> Result<(), String> {
+    let previous = settings::get(&app).update.channel;
+    settings::update(&app, |s| s.update.channel = channel)?;
+    if previous == channel {
+        return Ok(());
+    }
+    app.state::<UpdaterState>().pending.lock().unwrap().take();
+    set_status(&app, UpdateStatus::Idle);
+    crate::refresh_tray_menu(&app);
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = check_and_download(&app).await {
+            warn!("update check failed: {e}");
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn notes_inline_or_linked() {
+        assert_eq!(
+            notes_source(Some("Fixes"), &json!({})),
+            (Some("Fixes".into()), None)
+        );
+        assert_eq!(
+            notes_source(Some(" https://example.com/notes.md\n"), &json!({})),
+            (None, Some("https://example.com/notes.md".into()))
+        );
+        // An explicit notes_url wins, and the body stays the notes
+        assert_eq!(
+            notes_source(
+                Some("https://example.com/a"),
+                &json!({ "notes_url": "https://example.com/b" })
+            ),
+            (
+                Some("https://example.com/a".into()),
+                Some("https://example.com/b".into())
+            )
+        );
+        assert_eq!(notes_source(None, &json!({})), (None, None));
+    }
+
+    #[test]
+    fn status_for_the_settings_page() {
+        let downloading = UpdateStatus::Downloading {
+            downloaded: 10,
+            total: Some(40),
+            percent: Some(25),
+        };
+        assert_eq!(
+            serde_json::to_value(downloading).unwrap(),
+            json!({ "state": "downloading", "downloaded": 10, "total": 40, "percent": 25 })
+        );
+        assert_eq!(
+            serde_json::to_value(UpdateStatus::UpToDate).unwrap(),
+            json!({ "state": "up_to_date" })
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::json;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::{
+    audit::{self, Action},
+    settings,
+};
+
+// Secrets the engine needs in its config (API keys, session cookies) live in
+// settings.json sealed with AES-256-GCM. The key is generated on first use and
+// kept in the OS keychain, so the file alone is useless; values are opened
+// only when handed to the backend as environment variables at spawn.
+pub const KEYCHAIN_SERVICE: &str = "com.currobot.app.vault";
+pub const KEYCHAIN_ACCOUNT: &str = "settings-key";
+const PREFIX: &str = "enc:v1:";
+
+static KEY: Mutex<Option<Key<Aes256Gcm>>> = Mutex::new(None);
+
+fn entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT).map_err(|e| e.to_string())
+}
+
+fn key(create: bool) -> Result<Key<Aes256Gcm>, String> {
+    let mut cached = KEY.lock().unwrap();
+    if let Some(key) = *cached {
+        return Ok(key);
+    }
+    let entry = entry()?;
+    let key = match entry.get_password() {
+        Ok(encoded) => {
+            let bytes = STANDARD.decode(encoded).map_err(|e| e.to_string())?;
+            if bytes.len() != 32 {
+                return Err("the config key in the keychain is corrupt".into());
+            }
+            *Key::<Aes256Gcm>::from_slice(&bytes)
+        }
+        Err(keyring::Error::NoEntry) if create => {
+            let key = Aes256Gcm::generate_key(&mut OsRng);
+            entry
+                .set_password(&STANDARD.encode(key))
+                .map_err(|e| e.to_string())?;
+            key
+        }
+        Err(keyring::Error::NoEntry) => {
+            return Err("the config key is missing from the keychain".into())
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    *cached = Some(key);
+    Ok(key)
+}
+
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+pub fn seal(plain: &str) -> Result<String, String> {
+    let cipher = Aes256Gcm::new(&key(true)?);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plain.as_bytes())
+        .map_err(|e| e.to_string())?;
+    let mut blob = nonce.to_vec();
+    blob.extend(ciphertext);
+    Ok(format!("{PREFIX}{}", STANDARD.encode(blob)))
+}
+
+pub fn open(sealed: &str) -> Result<String, String> {
+    let blob = sealed
+        .strip_prefix(PREFIX)
+        .ok_or("value is not encrypted")
+        .and_then(|b| STANDARD.decode(b).map_err(|_| "corrupt encrypted value"))?;
+    if blob.len() < 12 {
+        return Err("corrupt encrypted value".into());
+    }
+    let (nonce, ciphertext) = blob.split_at(12);
+    let plain = Aes256Gcm::new(&key(false)?)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        // Typically settings.json copied from another machine or user
+        .map_err(|_| "encrypted with a different key".to_string())?;
+    String::from_utf8(plain).map_err(|e| e.to_string())
+}
+
+// Seals values that were typed into settings.json by hand. Returns whether
+// anything changed so the caller can write the file back.
+pub fn seal_plaintext(secrets: &mut settings::Secrets) -> bool {
+    let mut changed = false;
+    for (name, value) in secrets.iter_mut().filter(|(_, v)| !is_sealed(v)) {
+        match seal(value) {
+            Ok(sealed) => {
+                *value = sealed;
+                changed = true;
+            }
+            Err(e) => warn!("could not encrypt config secret {name}: {e}"),
+        }
+    }
+    changed
+}
+
+// Opened secrets as environment variables for the backend. pydantic-settings
+// matches them to fields case-insensitively, so `ollama_api_key` reaches
+// `settings.ollama_api_key`. Unreadable entries are skipped, not fatal.
+pub fn backend_env(app: &AppHandle) -> Vec<(String, String)> {
+    let env: Vec<(String, String)> = settings::get(app)
+        .secrets
+        .iter()
+        .filter_map(|(name, sealed)| match open(sealed) {
+            Ok(plain) => Some((name.to_uppercase(), plain)),
+            Err(e) => {
+                warn!("config secret {name} not passed to the engine: {e}");
+                None
+            }
+        })
+        .collect();
+    if !env.is_empty() {
+        let names: Vec<&str> = env.iter().map(|(name, _)| name.as_str()).collect();
+        audit::record(
+            app,
+            Action::CredentialUsed,
+            json!({ "secrets": names, "for": "engine" }),
+        );
+    }
+    env
+}
+
+// Drops the key and every sealed value with it (Reset → saved passwords).
+pub fn wipe(app: &AppHandle) {
+    let _ = settings::update(app, |s| s.secrets.clear());
+    if let Ok(entry) = entry() {
+        let _ = entry.delete_credential();
+    }
+    *KEY.lock().unwrap() = None;
+}
+
+fn valid_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// Names only; values are opened for the backend and nothing else.
+#[tauri::command]
+pub fn list_config_secrets(app: AppHandle) -> Vec<String> {
+    settings::get(&app).secrets.keys().cloned().collect()
+}
+
+// Stores (or with `None`, removes) a secret and restarts the backend so it
+// picks the change up.
+#[tauri::command]
+pub fn set_config_secret(
+    app: AppHandle,
+    name: String,
+    value: Option<String>,
+) -> Result<(), String> {
+    if !valid_name(&name) {
+        return Err(format!(
+            "\"{name}\" is not a valid setting name (lowercase letters, digits and _)"
+        ));
+    }
+    let sealed = value
+        .filter(|v| !v.is_empty())
+        .map(|v| seal(&v))
+        .transpose()?;
+    settings::update(&app, |s| match sealed {
+        Some(sealed) => {
+            s.secrets.insert(name, sealed);
+        }
+        None => {
+            s.secrets.remove(&name);
+        }
+    })?;
+    crate::restart_backend(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert!(valid_name("ollama_api_key"));
+        assert!(valid_name("key2"));
+        assert!(!valid_name(""));
+        assert!(!valid_name("2fa"));
+        assert!(!valid_name("_key"));
+        assert!(!valid_name("Api_Key"));
+        assert!(!valid_name("api-key"));
+    }
+
+    #[test]
+    fn sealed_values_are_marked() {
+        assert!(is_sealed("enc:v1:AAAA"));
+        assert!(!is_sealed("sk-plain"));
+    }
+
+    // Refused before the keychain is touched
+    #[test]
+    fn open_refuses_what_it_cannot_have_sealed() {
+        assert_eq!(open("sk-plain").unwrap_err(), "value is not encrypted");
+        assert_eq!(open("enc:v1:***").unwrap_err(), "corrupt encrypted value");
+        assert_eq!(open("enc:v1:AAAA").unwrap_err(), "corrupt encrypted value");
+    }
+}
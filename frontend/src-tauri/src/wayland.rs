@@ -0,0 +1,178 @@
+use serde::Serialize;
+
+use crate::trayhost;
+
+// Wayland sessions on Linux. There the tray is drawn only by a
+// StatusNotifierItem host, windows can't place themselves (the compositor
+// centers or cascades them) and keys can be grabbed system-wide only through
+// the GlobalShortcuts portal, where the desktop has one; each fails without a
+// word. Compatibility mode runs the app through XWayland instead, so windows
+// behave as on X11. It's kept machine-wide in `wayland.json` beside the
+// profile registry and applied at the next start, before the toolkit
+// connects to the display. The health report carries what was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub enum Session {
+    Wayland,
+    X11,
+    Other,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DisplayCompat {
+    pub session: Session,
+    // XDG_CURRENT_DESKTOP
+    pub desktop: Option<String>,
+    // Compatibility mode is set; `xwayland` is whether this run uses it
+    pub compat_mode: bool,
+    pub xwayland: bool,
+    pub tray_host: bool,
+    pub global_shortcuts: bool,
+    pub window_positioning: bool,
+}
+
+// First thing in `run`, while the environment can still be changed safely.
+pub fn init() {
+    platform::init();
+}
+
+// None off Linux. Blocking: asks the session bus.
+pub fn report() -> Option<DisplayCompat> {
+    let session = platform::session()?;
+    let xwayland = platform::xwayland();
+    Some(DisplayCompat {
+        session,
+        desktop: std::env::var("XDG_CURRENT_DESKTOP").ok(),
+        compat_mode: platform::compat_mode(),
+        xwayland,
+        tray_host: trayhost::available(),
+        global_shortcuts: session != Session::Wayland || platform::shortcuts_portal(),
+        window_positioning: session != Session::Wayland || xwayland,
+    })
+}
+
+#[tauri::command]
+pub async fn get_display_compat() -> Result<Option<DisplayCompat>, String> {
+    tauri::async_runtime::spawn_blocking(report)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_wayland_compat(enabled: bool) -> Result<(), String> {
+    platform::set_compat_mode(enabled)
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use serde::{Deserialize, Serialize};
+    use std::{env, fs, path::PathBuf};
+    use zbus::blocking::{Connection, Proxy};
+
+    use super::Session;
+    use crate::paths;
+
+    const CONFIG_FILE: &str = "wayland.json";
+
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Config {
+        compat: bool,
+    }
+
+    fn config_path() -> Option<PathBuf> {
+        Some(paths::early_base_config_dir()?.join(CONFIG_FILE))
+    }
+
+    fn load() -> Config {
+        config_path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn session() -> Option<Session> {
+        let kind = env::var("XDG_SESSION_TYPE").unwrap_or_default();
+        Some(
+            if kind == "wayland" || env::var_os("WAYLAND_DISPLAY").is_some() {
+                Session::Wayland
+            } else if kind == "x11" || env::var_os("DISPLAY").is_some() {
+                Session::X11
+            } else {
+                Session::Other
+            },
+        )
+    }
+
+    pub fn xwayland() -> bool {
+        session() == Some(Session::Wayland)
+            && env::var("GDK_BACKEND").is_ok_and(|b| b.starts_with("x11"))
+    }
+
+    pub fn init() {
+        if session() != Some(Session::Wayland) || !load().compat {
+            return;
+        }
+        // Set by hand, or no XWayland to run on
+        if env::var_os("GDK_BACKEND").is_some() || env::var_os("DISPLAY").is_none() {
+            return;
+        }
+        env::set_var("GDK_BACKEND", "x11");
+    }
+
+    pub fn compat_mode() -> bool {
+        load().compat
+    }
+
+    pub fn set_compat_mode(enabled: bool) -> Result<(), String> {
+        let path = config_path().ok_or("no config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let json =
+            serde_json::to_string_pretty(&Config { compat: enabled }).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    pub fn shortcuts_portal() -> bool {
+        let Ok(connection) = Connection::session() else {
+            return false;
+        };
+        Proxy::new(
+            &connection,
+            "org.freedesktop.portal.Desktop",
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.GlobalShortcuts",
+        )
+        .and_then(|proxy| proxy.get_property::<u32>("version"))
+        .is_ok()
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    use super::Session;
+
+    pub fn session() -> Option<Session> {
+        None
+    }
+
+    pub fn xwayland() -> bool {
+        false
+    }
+
+    pub fn init() {}
+
+    pub fn compat_mode() -> bool {
+        false
+    }
+
+    pub fn set_compat_mode(_enabled: bool) -> Result<(), String> {
+        Err("only on Linux".into())
+    }
+
+    pub fn shortcuts_portal() -> bool {
+        false
+    }
+}
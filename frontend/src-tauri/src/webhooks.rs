@@ -0,0 +1,692 @@
+use chrono::{DateTime, Duration, Local, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration as StdDuration,
+};
+use tauri::{AppHandle, Emitter, Url};
+use tracing::{info, warn};
+
+use crate::{events::BackendEvent, history, net, paths};
+
+// Sends what happens to applications to URLs the user sets up (Zapier, Make,
+// a Notion integration, their own server): each engine event a webhook
+// subscribes to is POSTed to it as JSON. With a secret, the body is signed
+// the way Stripe and GitHub do it, HMAC-SHA256 over "<timestamp>.<body>" in
+// X-Currobot-Signature, so the receiver can tell it came from here. A
+// delivery that fails is tried again after each of RETRY_AFTER, then given
+// up; every one is kept in a log the Settings page shows, and can be sent
+// again from there. Webhooks live in `webhooks.json` in the profile's config
+// folder, their secrets in the OS keychain, and the log in
+// `webhook-deliveries.json` in its data folder, so pending retries survive a
+// restart.
+pub const WEBHOOKS_CHANGED: &str = "webhooks-changed";
+const KEYCHAIN_SERVICE: &str = "com.currobot.app.webhooks";
+const FILE: &str = "webhooks.json";
+const LOG_FILE: &str = "webhook-deliveries.json";
+const TIMEOUT: StdDuration = StdDuration::from_secs(15);
+const POLL_INTERVAL: StdDuration = StdDuration::from_secs(15);
+// Between attempts; a delivery gets one more attempt than there are waits
+const RETRY_AFTER: &[i64] = &[30, 2 * 60, 10 * 60, 60 * 60];
+const MAX_LOG: usize = 200;
+// What a webhook can subscribe to
+pub const EVENTS: &[&str] = &[
+    "application.submitted",
+    "application.status_changed",
+    "run.finished",
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    // Of EVENTS
+    pub events: Vec<String>,
+    pub enabled: bool,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookStatus {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub has_secret: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryState {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Delivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub event: String,
+    pub payload: Value,
+    pub created_at: DateTime<Utc>,
+    pub state: DeliveryState,
+    #[serde(default)]
+    pub attempts: u32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    // The receiver's answer to the last attempt
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Hooks {
+    webhooks: Vec<Webhook>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Log {
+    deliveries: Vec<Delivery>,
+}
+
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+static SENDING: AtomicBool = AtomicBool::new(false);
+
+fn hooks_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::config_dir(app)?.join(FILE))
+}
+
+fn log_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join(LOG_FILE))
+}
+
+fn read<T: Default + for<'de> Deserialize<'de>>(path: Result<PathBuf, String>) -> T {
+    path.ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn write<T: Serialize>(path: PathBuf, value: &T) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn load(app: &AppHandle) -> Hooks {
+    read(hooks_path(app))
+}
+
+fn load_log(app: &AppHandle) -> Log {
+    read(log_path(app))
+}
+
+fn change<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut Hooks) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = FILE_LOCK.lock().unwrap();
+    let mut hooks = load(app);
+    let result = f(&mut hooks)?;
+    write(hooks_path(app)?, &hooks)?;
+    let _ = app.emit(WEBHOOKS_CHANGED, ());
+    Ok(result)
+}
+
+fn change_log<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut Log) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = FILE_LOCK.lock().unwrap();
+    let mut log = load_log(app);
+    let result = f(&mut log)?;
+    trim(&mut log);
+    write(log_path(app)?, &log)?;
+    let _ = app.emit(WEBHOOKS_CHANGED, ());
+    Ok(result)
+}
+
+// Oldest finished ones go first; pending ones are never dropped.
+fn trim(log: &mut Log) {
+    while log.deliveries.len() > MAX_LOG {
+        match log
+            .deliveries
+            .iter()
+            .position(|d| d.state != DeliveryState::Pending)
+        {
+            Some(i) => log.deliveries.remove(i),
+            None => break,
+        };
+    }
+}
+
+fn keychain_entry(id: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, id).map_err(|e| e.to_string())
+}
+
+fn secret(id: &str) -> Option<String> {
+    keychain_entry(id).ok()?.get_password().ok()
+}
+
+fn new_id(prefix: &str) -> String {
+    format!("{prefix}-{}", Local::now().format("%Y%m%d-%H%M%S%3f"))
+}
+
+// Receivers are on the internet, so HTTPS, except for one on this machine.
+fn check_url(url: &str) -> Result<String, String> {
+    let parsed = Url::parse(url.trim()).map_err(|e| format!("invalid URL: {e}"))?;
+    let local = matches!(parsed.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    match parsed.scheme() {
+        "https" => Ok(parsed.to_string()),
+        "http" if local => Ok(parsed.to_string()),
+        _ => Err("a webhook URL must start with https://".into()),
+    }
+}
+
+fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(body);
+    let hex: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    format!("sha256={hex}")
+}
+
+// One attempt: the receiver's status, or why there was none.
+fn post(hook: &Webhook, delivery: &Delivery) -> Result<u16, (Option<u16>, String)> {
+    let body = serde_json::to_vec(&delivery.payload).map_err(|e| (None, e.to_string()))?;
+    let timestamp = Utc::now().timestamp();
+    let mut request = net::client(TIMEOUT)
+        .map_err(|e| (None, e))?
+        .post(&hook.url)
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "currobot-webhooks")
+        .header("X-Currobot-Event", &delivery.event)
+        .header("X-Currobot-Delivery", &delivery.id)
+        .header("X-Currobot-Timestamp", timestamp.to_string());
+    if let Some(secret) = secret(&hook.id) {
+        request = request.header("X-Currobot-Signature", signature(&secret, timestamp, &body));
+    }
+    let response = request
+        .body(body)
+        .send()
+        .map_err(|e| (None, e.to_string()))?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16())
+    } else {
+        Err((Some(status.as_u16()), format!("HTTP {status}")))
+    }
+}
+
+// What an attempt came to: delivered, due again later, or given up.
+fn settle(d: &mut Delivery, outcome: Result<u16, (Option<u16>, String)>, now: DateTime<Utc>) {
+    d.attempts += 1;
+    match outcome {
+        Ok(status) => {
+            d.state = DeliveryState::Delivered;
+            d.response_status = Some(status);
+            d.last_error = None;
+            d.next_attempt_at = None;
+            d.finished_at = Some(now);
+        }
+        Err((status, error)) => {
+            // A 4xx other than a timeout or rate limit won't change on retrying
+            let hopeless = status.is_some_and(|s| (400..500).contains(&s) && s != 408 && s != 429);
+            let wait = RETRY_AFTER.get(d.attempts as usize - 1).copied();
+            d.response_status = status;
+            d.last_error = Some(error);
+            match wait.filter(|_| !hopeless) {
+                Some(seconds) => d.next_attempt_at = Some(now + Duration::seconds(seconds)),
+                None => {
+                    d.state = DeliveryState::Failed;
+                    d.next_attempt_at = None;
+                    d.finished_at = Some(now);
+                    warn!(
+                        webhook = d.webhook_id,
+                        event = d.event,
+                        attempts = d.attempts,
+                        "webhook delivery failed"
+                    );
+                }
+            }
+        }
+    }
+}
+
+// Sends one delivery and records how it went.
+fn attempt(app: &AppHandle, hook: &Webhook, delivery: &Delivery) -> Result<Delivery, String> {
+    let outcome = post(hook, delivery);
+    change_log(app, |log| {
+        let d = log
+            .deliveries
+            .iter_mut()
+            .find(|d| d.id == delivery.id)
+            .ok_or_else(|| format!("no delivery {}", delivery.id))?;
+        settle(d, outcome, Utc::now());
+        Ok(d.clone())
+    })
+}
+
+// Every pending delivery that's due, oldest first.
+fn send_due(app: &AppHandle) {
+    if SENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let hooks = load(app).webhooks;
+    let now = Utc::now();
+    let due: Vec<Delivery> = load_log(app)
+        .deliveries
+        .into_iter()
+        .filter(|d| d.state == DeliveryState::Pending && d.next_attempt_at.is_none_or(|t| t <= now))
+        .collect();
+    for delivery in due {
+        let Some(hook) = hooks.iter().find(|h| h.id == delivery.webhook_id) else {
+            continue;
+        };
+        if let Err(e) = attempt(app, hook, &delivery) {
+            warn!("webhook delivery not recorded: {e}");
+        }
+    }
+    SENDING.store(false, Ordering::SeqCst);
+}
+
+fn send_in_background(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || send_due(&app));
+}
+
+fn queue(app: &AppHandle, hook: &Webhook, event: &str, data: Value) -> Result<Delivery, String> {
+    let id = new_id("delivery");
+    let created_at = Utc::now();
+    let delivery = Delivery {
+        payload: json!({
+            "id": id,
+            "event": event,
+            "created_at": created_at,
+            "data": data,
+        }),
+        id,
+        webhook_id: hook.id.clone(),
+        event: event.to_string(),
+        created_at,
+        state: DeliveryState::Pending,
+        attempts: 0,
+        next_attempt_at: None,
+        response_status: None,
+        last_error: None,
+        finished_at: None,
+    };
+    change_log(app, |log| {
+        log.deliveries.push(delivery.clone());
+        Ok(())
+    })?;
+    Ok(delivery)
+}
+
+// The engine's event under the name subscribers know it by.
+fn subscribed_name(event: &str) -> Option<&'static str> {
+    match event {
+        "application_submitted" => Some("application.submitted"),
+        "application_status_updated" | "application_authorized" | "application_rejected" => {
+            Some("application.status_changed")
+        }
+        "run_finished" => Some("run.finished"),
+        _ => None,
+    }
+}
+
+// What the event says, with the application it's about from the history.
+fn event_data(app: &AppHandle, event: &BackendEvent) -> Value {
+    let mut data = event.data.clone();
+    let status = match event.event.as_str() {
+        "application_authorized" => Some("authorized"),
+        "application_rejected" => Some("rejected"),
+        _ => None,
+    };
+    if let (Some(status), Some(map)) = (status, data.as_object_mut()) {
+        map.insert("status".into(), status.into());
+    }
+    let Some(id) = event.data["application_id"].as_i64() else {
+        return data;
+    };
+    let found = history::open(app).and_then(|db| {
+        db.query_row(
+            "SELECT company, role, url, site, location FROM applications WHERE id = ?1",
+            [id],
+            |r| {
+                Ok(json!({
+                    "id": id,
+                    "company": r.get::<_, String>(0)?,
+                    "role": r.get::<_, Option<String>>(1)?,
+                    "url": r.get::<_, Option<String>>(2)?,
+                    "site": r.get::<_, Option<String>>(3)?,
+                    "location": r.get::<_, Option<String>>(4)?,
+                }))
+            },
+        )
+        .map_err(|e| e.to_string())
+    });
+    if let (Ok(application), Some(map)) = (found, data.as_object_mut()) {
+        map.insert("application".into(), application);
+    }
+    data
+}
+
+// From the event bridge.
+pub fn on_event(app: &AppHandle, event: &BackendEvent) {
+    let Some(name) = subscribed_name(&event.event) else {
+        return;
+    };
+    let hooks: Vec<Webhook> = load(app)
+        .webhooks
+        .into_iter()
+        .filter(|h| h.enabled && h.events.iter().any(|e| e == name))
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    let event = event.clone();
+    thread::spawn(move || {
+        let data = event_data(&app, &event);
+        for hook in &hooks {
+            if let Err(e) = queue(&app, hook, name, data.clone()) {
+                warn!(webhook = hook.id, "webhook delivery not queued: {e}");
+            }
+        }
+        send_due(&app);
+    });
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+        send_due(&app);
+    });
+}
+
+pub fn forget_secrets(app: &AppHandle) {
+    for hook in load(app).webhooks {
+        if let Ok(entry) = keychain_entry(&hook.id) {
+            let _ = entry.delete_credential();
+        }
+    }
+}
+
+#[tauri::command]
+pub fn list_webhooks(app: AppHandle) -> Vec<WebhookStatus> {
+    load(&app)
+        .webhooks
+        .into_iter()
+        .map(|webhook| WebhookStatus {
+            has_secret: secret(&webhook.id).is_some(),
+            webhook,
+        })
+        .collect()
+}
+
+// Adds a webhook, or with `id` changes it. A `secret` of None keeps the one
+// saved; an empty one removes it.
+#[tauri::command]
+pub fn save_webhook(
+    app: AppHandle,
+    id: Option<String>,
+    url: String,
+    events: Vec<String>,
+    enabled: bool,
+    secret: Option<String>,
+) -> Result<Webhook, String> {
+    let url = check_url(&url)?;
+    if let Some(unknown) = events.iter().find(|e| !EVENTS.contains(&e.as_str())) {
+        return Err(format!("unknown event {unknown}"));
+    }
+    if events.is_empty() {
+        return Err("choose at least one event".into());
+    }
+    let saved = change(&app, |hooks| match &id {
+        Some(id) => {
+            let hook = hooks
+                .webhooks
+                .iter_mut()
+                .find(|h| h.id == *id)
+                .ok_or_else(|| format!("no webhook {id}"))?;
+            hook.url = url;
+            hook.events = events;
+            hook.enabled = enabled;
+            Ok(hook.clone())
+        }
+        None => {
+            let hook = Webhook {
+                id: new_id("webhook"),
+                url,
+                events,
+                enabled,
+                added_at: Utc::now(),
+            };
+            hooks.webhooks.push(hook.clone());
+            Ok(hook)
+        }
+    })?;
+    match secret.as_deref().map(str::trim) {
+        Some("") => {
+            let _ = keychain_entry(&saved.id)?.delete_credential();
+        }
+        Some(secret) => keychain_entry(&saved.id)?
+            .set_password(secret)
+            .map_err(|e| e.to_string())?,
+        None => {}
+    }
+    info!(id = saved.id, "webhook saved");
+    Ok(saved)
+}
+
+// Its pending deliveries are dropped; the log of the others stays.
+#[tauri::command]
+pub fn remove_webhook(app: AppHandle, id: String) -> Result<(), String> {
+    change(&app, |hooks| {
+        hooks.webhooks.retain(|h| h.id != id);
+        Ok(())
+    })?;
+    change_log(&app, |log| {
+        log.deliveries
+            .retain(|d| d.webhook_id != id || d.state != DeliveryState::Pending);
+        Ok(())
+    })?;
+    if let Ok(entry) = keychain_entry(&id) {
+        let _ = entry.delete_credential();
+    }
+    Ok(())
+}
+
+// Newest first, of one webhook or all of them.
+#[tauri::command]
+pub fn list_webhook_deliveries(app: AppHandle, webhook_id: Option<String>) -> Vec<Delivery> {
+    let mut deliveries: Vec<Delivery> = load_log(&app)
+        .deliveries
+        .into_iter()
+        .filter(|d| webhook_id.as_ref().is_none_or(|id| *id == d.webhook_id))
+        .collect();
+    deliveries.reverse();
+    deliveries
+}
+
+// A "ping" the receiver can be set up with, sent once right away.
+#[tauri::command]
+pub async fn test_webhook(app: AppHandle, id: String) -> Result<Delivery, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let hook = load(&app)
+            .webhooks
+            .into_iter()
+            .find(|h| h.id == id)
+            .ok_or_else(|| format!("no webhook {id}"))?;
+        let delivery = queue(&app, &hook, "ping", json!({ "webhook_id": hook.id }))?;
+        let result = attempt(&app, &hook, &delivery)?;
+        // A ping isn't worth retrying
+        if result.state == DeliveryState::Pending {
+            return change_log(&app, |log| {
+                let d = log
+                    .deliveries
+                    .iter_mut()
+                    .find(|d| d.id == result.id)
+                    .ok_or_else(|| format!("no delivery {}", result.id))?;
+                d.state = DeliveryState::Failed;
+                d.next_attempt_at = None;
+                d.finished_at = Some(Utc::now());
+                Ok(d.clone())
+            });
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Sends a delivery again from the start, with its original payload.
+#[tauri::command]
+pub fn redeliver_webhook(app: AppHandle, delivery_id: String) -> Result<(), String> {
+    let hooks = load(&app).webhooks;
+    change_log(&app, |log| {
+        let d = log
+            .deliveries
+            .iter_mut()
+            .find(|d| d.id == delivery_id)
+            .ok_or_else(|| format!("no delivery {delivery_id}"))?;
+        if !hooks.iter().any(|h| h.id == d.webhook_id) {
+            return Err("its webhook was removed".into());
+        }
+        d.state = DeliveryState::Pending;
+        d.attempts = 0;
+        d.next_attempt_at = None;
+        d.finished_at = None;
+        Ok(())
+    })?;
+    send_in_background(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery(id: &str, state: DeliveryState) -> Delivery {
+        Delivery {
+            id: id.into(),
+            webhook_id: "webhook-1".into(),
+            event: "run.finished".into(),
+            payload: json!({}),
+            created_at: Utc::now(),
+            state,
+            attempts: 0,
+            next_attempt_at: None,
+            response_status: None,
+            last_error: None,
+            finished_at: None,
+        }
+    }
+
+    #[test]
+    fn https_or_this_machine() {
+        assert_eq!(
+            check_url(" https://hooks.zapier.com/hooks/catch/1/ ").as_deref(),
+            Ok("https://hooks.zapier.com/hooks/catch/1/")
+        );
+        assert!(check_url("http://localhost:8080/hook").is_ok());
+        assert!(check_url("http://127.0.0.1/hook").is_ok());
+        assert!(check_url("http://example.com/hook").is_err());
+        assert!(check_url("ftp://localhost/hook").is_err());
+        assert!(check_url("not a url").is_err());
+    }
+
+    #[test]
+    fn signed_over_timestamp_and_body() {
+        assert_eq!(
+            signature("whsec_test", 1760536800, br#"{"event":"run.finished"}"#),
+            "sha256=61d8dbee9df06266d354cd87d74d6c08468aaa0aca160d95b8f0c93326705ed6"
+        );
+    }
+
+    #[test]
+    fn engine_events_by_subscriber_name() {
+        assert_eq!(
+            subscribed_name("application_rejected"),
+            Some("application.status_changed")
+        );
+        assert_eq!(subscribed_name("run_finished"), Some("run.finished"));
+        assert_eq!(subscribed_name("log"), None);
+        for event in [
+            "application_submitted",
+            "application_status_updated",
+            "run_finished",
+        ] {
+            assert!(EVENTS.contains(&subscribed_name(event).unwrap()));
+        }
+    }
+
+    #[test]
+    fn retried_then_given_up() {
+        let now = Utc::now();
+        let mut d = delivery("delivery-1", DeliveryState::Pending);
+        for (i, wait) in RETRY_AFTER.iter().enumerate() {
+            settle(&mut d, Err((Some(503), "HTTP 503".into())), now);
+            assert_eq!(d.attempts, i as u32 + 1);
+            assert_eq!(d.state, DeliveryState::Pending);
+            assert_eq!(d.next_attempt_at, Some(now + Duration::seconds(*wait)));
+        }
+        settle(&mut d, Err((None, "timed out".into())), now);
+        assert_eq!(d.state, DeliveryState::Failed);
+        assert_eq!(d.next_attempt_at, None);
+        assert_eq!(d.finished_at, Some(now));
+        assert_eq!(d.last_error.as_deref(), Some("timed out"));
+    }
+
+    #[test]
+    fn refusals_are_final_but_rate_limits_are_not() {
+        let now = Utc::now();
+        let mut d = delivery("delivery-1", DeliveryState::Pending);
+        settle(&mut d, Err((Some(429), "HTTP 429".into())), now);
+        assert_eq!(d.state, DeliveryState::Pending);
+        settle(&mut d, Err((Some(410), "HTTP 410".into())), now);
+        assert_eq!(d.state, DeliveryState::Failed);
+        assert_eq!(d.response_status, Some(410));
+
+        let mut d = delivery("delivery-2", DeliveryState::Pending);
+        settle(&mut d, Err((Some(500), "HTTP 500".into())), now);
+        settle(&mut d, Ok(204), now);
+        assert_eq!(d.state, DeliveryState::Delivered);
+        assert_eq!((d.attempts, d.response_status), (2, Some(204)));
+        assert_eq!(d.last_error, None);
+    }
+
+    #[test]
+    fn log_keeps_pending_deliveries() {
+        let mut log = Log::default();
+        log.deliveries
+            .push(delivery("delivery-pending", DeliveryState::Pending));
+        for i in 0..MAX_LOG + 5 {
+            log.deliveries
+                .push(delivery(&format!("delivery-{i}"), DeliveryState::Delivered));
+        }
+        trim(&mut log);
+        assert_eq!(log.deliveries.len(), MAX_LOG);
+        assert_eq!(log.deliveries[0].id, "delivery-pending");
+        assert_eq!(log.deliveries[1].id, "delivery-6");
+    }
+}
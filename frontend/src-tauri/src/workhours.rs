@@ -0,0 +1,376 @@
+use chrono::{DateTime, Datelike, Days, Local, Locale, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+use std::{fmt::Write, sync::Mutex, thread, time::Duration};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::{
+    automation,
+    i18n::{self, t, t_args},
+    notifications,
+    settings::{self, WorkingHoursSettings},
+    sleepwake,
+};
+
+// Automation only in the hours the user sets, say 8:00 to 20:00 local time
+// and never on Sunday. Outside them the shell pauses the engine (its own
+// scheduler stops) and turns down the runs it would start itself: the
+// schedules, the Dock's and jump list's Run now. The engine is resumed when
+// the hours start again, unless it was paused by hand before they ended.
+// The tray says so, and offers to run anyway until the hours next start;
+// resuming by hand outside them counts as that too.
+pub const WORKING_HOURS_CHANGED: &str = "working-hours-changed";
+const TICK: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum State {
+    // Within the hours, or none set
+    Open,
+    Closed,
+    // Outside the hours, running anyway
+    Overridden,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkingHoursStatus {
+    pub settings: WorkingHoursSettings,
+    pub state: State,
+    // When the hours next start or end, whichever comes first
+    pub next_change: Option<DateTime<Utc>>,
+}
+
+// As of the last check, for the tray
+static STATE: Mutex<State> = Mutex::new(State::Open);
+// The ticker and the commands both pause and resume
+static ENFORCING: Mutex<()> = Mutex::new(());
+
+// The hours on `date`, if it's one of the days.
+fn window(
+    cfg: &WorkingHoursSettings,
+    date: NaiveDate,
+) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    if !cfg.days.contains(&date.weekday().number_from_monday()) {
+        return None;
+    }
+    let at = |hour: u32| {
+        let (date, hour) = if hour >= 24 {
+            (date + Days::new(1), 0)
+        } else {
+            (date, hour)
+        };
+        Local
+            .from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+            .earliest()
+    };
+    Some((at(cfg.from_hour)?, at(cfg.to_hour)?))
+}
+
+fn within(cfg: &WorkingHoursSettings, now: DateTime<Local>) -> bool {
+    window(cfg, now.date_naive()).is_some_and(|(from, to)| from <= now && now < to)
+}
+
+fn next_start(cfg: &WorkingHoursSettings, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    (0..8)
+        .filter_map(|offset| window(cfg, now.date_naive() + Days::new(offset)))
+        .map(|(from, _)| from)
+        .find(|from| *from > now)
+}
+
+fn next_change(cfg: &WorkingHoursSettings, now: DateTime<Local>) -> Option<DateTime<Utc>> {
+    if !cfg.enabled {
+        return None;
+    }
+    let next = match window(cfg, now.date_naive()) {
+        Some((from, to)) if from <= now && now < to => Some(to),
+        _ => next_start(cfg, now),
+    };
+    next.map(|at| at.with_timezone(&Utc))
+}
+
+fn state(cfg: &WorkingHoursSettings, now: DateTime<Local>) -> State {
+    if !cfg.enabled || within(cfg, now) {
+        State::Open
+    } else if cfg.override_until.is_some_and(|until| until > now) {
+        State::Overridden
+    } else {
+        State::Closed
+    }
+}
+
+// `at` with a strftime format in `locale`'s words; a broken format in a
+// translation falls back to ISO rather than failing.
+fn localized(at: DateTime<Local>, format: &str, locale: Locale) -> String {
+    let mut text = String::new();
+    match write!(text, "{}", at.format_localized(format, locale)) {
+        Ok(()) => text,
+        Err(_) => at.format("%Y-%m-%d %H:%M").to_string(),
+    }
+}
+
+// Why the shell won't start a run now, if it won't.
+pub fn refusal(app: &AppHandle) -> Option<String> {
+    let cfg = settings::get(app).working_hours;
+    let now = Local::now();
+    (state(&cfg, now) == State::Closed).then(|| match next_start(&cfg, now) {
+        Some(start) => {
+            let start = localized(start, &t("working-hours-start"), i18n::chrono_locale());
+            t_args("working-hours-refusal-until", &[("start", start.into())])
+        }
+        None => t("working-hours-refusal"),
+    })
+}
+
+// The tray tooltip's line, outside the hours.
+pub fn tooltip_line() -> Option<String> {
+    match *STATE.lock().unwrap() {
+        State::Open => None,
+        State::Closed => Some(t("working-hours-tooltip")),
+        State::Overridden => Some(t("working-hours-tooltip-override")),
+    }
+}
+
+// The tray's item, outside the hours: its id and label.
+pub fn tray_item() -> Option<(&'static str, String)> {
+    match *STATE.lock().unwrap() {
+        State::Open => None,
+        State::Closed => Some(("workhours:override", t("tray-working-hours-override"))),
+        State::Overridden => Some(("workhours:end", t("tray-working-hours-end"))),
+    }
+}
+
+// Pauses or resumes the engine as the hours say. Blocking; an engine that
+// doesn't answer is dealt with on the next tick.
+fn enforce(app: &AppHandle) {
+    let _guard = ENFORCING.lock().unwrap();
+    let mut cfg = settings::get(app).working_hours;
+    let now = Local::now();
+    let mut state = state(&cfg, now);
+    let change = |f: &dyn Fn(&mut WorkingHoursSettings)| {
+        if let Err(e) = settings::update(app, |s| f(&mut s.working_hours)) {
+            warn!("working hours not saved: {e}");
+        }
+    };
+    if state != State::Overridden && cfg.override_until.is_some() {
+        cfg.override_until = None;
+        change(&|h| h.override_until = None);
+    }
+    match (state, cfg.holding, automation::paused()) {
+        // Paused by hand is left as it is
+        (State::Closed, false, Some(false)) => match automation::pause(true) {
+            Ok(()) => {
+                info!("automation paused: outside working hours");
+                change(&|h| h.holding = true);
+            }
+            Err(e) => warn!("automation not paused: {e}"),
+        },
+        (State::Closed, true, Some(false)) => {
+            info!("automation resumed by hand outside working hours");
+            let until = next_start(&cfg, now).map(|at| at.with_timezone(&Utc));
+            change(&|h| {
+                h.holding = false;
+                h.override_until = until;
+            });
+            state = State::Overridden;
+        }
+        (State::Open | State::Overridden, true, Some(true)) => match automation::pause(false) {
+            Ok(()) => {
+                info!(?state, "automation resumed");
+                change(&|h| h.holding = false);
+            }
+            Err(e) => warn!("automation not resumed: {e}"),
+        },
+        // Resumed by hand in the meantime
+        (State::Open | State::Overridden, true, Some(false)) => change(&|h| h.holding = false),
+        _ => {}
+    }
+    let changed = {
+        let mut current = STATE.lock().unwrap();
+        let changed = *current != state;
+        *current = state;
+        changed
+    };
+    if changed {
+        notifications::refresh_tray(app);
+        crate::refresh_tray_menu(app);
+        let _ = app.emit(WORKING_HOURS_CHANGED, ());
+    }
+}
+
+fn enforce_soon(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || enforce(&app));
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        if !sleepwake::is_asleep() {
+            enforce(&app);
+        }
+        thread::sleep(TICK);
+    });
+}
+
+// Running anyway until the hours next start, or back to keeping them.
+fn set_override(app: &AppHandle, active: bool) -> Result<(), String> {
+    let cfg = settings::get(app).working_hours;
+    let until = if active {
+        Some(next_start(&cfg, Local::now()).ok_or("no working hours are set")?)
+    } else {
+        None
+    };
+    settings::update(app, |s| {
+        s.working_hours.override_until = until.map(|at| at.with_timezone(&Utc))
+    })?;
+    info!(active, "working hours override");
+    let _ = app.emit(WORKING_HOURS_CHANGED, ());
+    enforce_soon(app);
+    Ok(())
+}
+
+// From the tray, on the main thread.
+pub fn on_menu_event(app: &AppHandle, id: &str) {
+    let active = id == "workhours:override";
+    if let Err(e) = set_override(app, active) {
+        warn!("working hours override not changed: {e}");
+    }
+}
+
+#[tauri::command]
+pub fn get_working_hours(app: AppHandle) -> WorkingHoursStatus {
+    let cfg = settings::get(&app).working_hours;
+    let now = Local::now();
+    WorkingHoursStatus {
+        state: state(&cfg, now),
+        next_change: next_change(&cfg, now),
+        settings: cfg,
+    }
+}
+
+#[tauri::command]
+pub fn set_working_hours(
+    app: AppHandle,
+    enabled: bool,
+    from_hour: u32,
+    to_hour: u32,
+    days: Vec<u32>,
+) -> Result<WorkingHoursStatus, String> {
+    if from_hour >= to_hour || to_hour > 24 {
+        return Err("the hours must be in order, from 0 to 24".into());
+    }
+    if days.is_empty() || days.iter().any(|d| !(1..=7).contains(d)) {
+        return Err("choose at least one day".into());
+    }
+    // New hours start afresh, without the override
+    settings::update(&app, |s| {
+        let hours = &mut s.working_hours;
+        hours.override_until = None;
+        hours.enabled = enabled;
+        hours.from_hour = from_hour;
+        hours.to_hour = to_hour;
+        hours.days = days;
+    })?;
+    let _ = app.emit(WORKING_HOURS_CHANGED, ());
+    enforce_soon(&app);
+    Ok(get_working_hours(app))
+}
+
+#[tauri::command]
+pub fn override_working_hours(app: AppHandle, active: bool) -> Result<(), String> {
+    set_override(&app, active)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Monday to Friday, 9:00 to 18:00
+    fn weekdays() -> WorkingHoursSettings {
+        WorkingHoursSettings {
+            enabled: true,
+            from_hour: 9,
+            to_hour: 18,
+            days: (1..=5).collect(),
+            ..Default::default()
+        }
+    }
+
+    // 2026-10-12 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn window_on_the_days() {
+        let cfg = weekdays();
+        assert_eq!(window(&cfg, date(14)), Some((at(14, 9, 0), at(14, 18, 0))));
+        assert_eq!(window(&cfg, date(17)), None);
+        // Until midnight is the start of the next day
+        let late = WorkingHoursSettings {
+            to_hour: 24,
+            ..weekdays()
+        };
+        assert_eq!(window(&late, date(16)).unwrap().1, at(17, 0, 0));
+    }
+
+    #[test]
+    fn state_at_the_edges() {
+        let cfg = weekdays();
+        assert_eq!(state(&cfg, at(14, 8, 59)), State::Closed);
+        assert_eq!(state(&cfg, at(14, 9, 0)), State::Open);
+        assert_eq!(state(&cfg, at(14, 17, 59)), State::Open);
+        assert_eq!(state(&cfg, at(14, 18, 0)), State::Closed);
+        assert_eq!(state(&cfg, at(17, 12, 0)), State::Closed);
+        let off = WorkingHoursSettings {
+            enabled: false,
+            ..weekdays()
+        };
+        assert_eq!(state(&off, at(17, 12, 0)), State::Open);
+    }
+
+    #[test]
+    fn override_until_the_next_start() {
+        let cfg = WorkingHoursSettings {
+            override_until: Some(at(19, 9, 0).with_timezone(&Utc)),
+            ..weekdays()
+        };
+        assert_eq!(state(&cfg, at(17, 12, 0)), State::Overridden);
+        assert_eq!(state(&cfg, at(19, 9, 0)), State::Open);
+        assert_eq!(state(&cfg, at(19, 18, 30)), State::Closed);
+    }
+
+    #[test]
+    fn next_start_and_change() {
+        let cfg = weekdays();
+        // Friday evening: Monday morning
+        assert_eq!(next_start(&cfg, at(16, 18, 0)), Some(at(19, 9, 0)));
+        assert_eq!(next_start(&cfg, at(14, 7, 0)), Some(at(14, 9, 0)));
+        // Within the hours the next change is their end
+        assert_eq!(
+            next_change(&cfg, at(14, 10, 0)),
+            Some(at(14, 18, 0).with_timezone(&Utc))
+        );
+        let never = WorkingHoursSettings {
+            days: Vec::new(),
+            ..weekdays()
+        };
+        assert_eq!(next_start(&never, at(14, 10, 0)), None);
+    }
+
+    #[test]
+    fn start_in_the_language() {
+        let thursday = at(15, 9, 0);
+        assert_eq!(localized(thursday, "%a %H:%M", Locale::en_US), "Thu 09:00");
+        assert_eq!(
+            localized(thursday, "%A a las %H:%M", Locale::es_ES),
+            "jueves a las 09:00"
+        );
+        assert_eq!(localized(thursday, "%Q", Locale::en_US), "2026-10-15 09:00");
+    }
+}
@@ -0,0 +1,114 @@
+"""
+Tests for backend/security/redaction.py
+
+Log lines must not carry personal data or secrets: built-in patterns,
+user-supplied patterns and names all end up as placeholders, and ordinary
+text (dates, ids, URLs without secrets) is left alone.
+"""
+import sys
+import os
+
+# Allow running from project root without installing the package
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import pytest
+
+from backend.security.redaction import Redactor, PATTERNS_ENV, NAMES_ENV
+
+
+@pytest.fixture
+def redactor():
+    return Redactor()
+
+
+# ---------------------------------------------------------------------------
+# Built-in patterns
+# ---------------------------------------------------------------------------
+
+class TestBuiltin:
+    def test_email(self, redactor):
+        assert redactor.redact("sent to ana.garcia+jobs@example.es ok") == "sent to [email] ok"
+
+    @pytest.mark.parametrize("phone", [
+        "phone 612345678", "612 345 678", "+34 612-345-678", "+34612345678", "Teléfono: 612345678",
+    ])
+    def test_phone(self, redactor, phone):
+        assert "[phone]" in redactor.redact(f"call {phone} now")
+        assert "345" not in redactor.redact(f"call {phone} now")
+
+    @pytest.mark.parametrize("line", [
+        "engine started pid=612345678",
+        "downloaded 734003200 bytes",
+        "at 1760515200 took 123456789ms",
+    ])
+    def test_bare_numbers_untouched(self, redactor, line):
+        assert redactor.redact(line) == line
+
+    def test_bearer(self, redactor):
+        assert redactor.redact("Authorization: Bearer abc.def-123") == "Authorization: Bearer [token]"
+
+    @pytest.mark.parametrize("line,expected", [
+        ("password=hunter2", "password=[token]"),
+        ('{"api_key": "sk-123"}', '{"api_key": "[token]"}'),
+        ("GET /api/events?token=deadbeef&x=1", "GET /api/events?token=[token]&x=1"),
+    ])
+    def test_key_value_secrets(self, redactor, line, expected):
+        assert redactor.redact(line) == expected
+
+    def test_long_hex(self, redactor):
+        assert redactor.redact("key " + "ab12" * 16) == "key [token]"
+        assert redactor.redact("session_token=" + "ab12" * 16) == "session_token=[token]"
+
+    @pytest.mark.parametrize("line", [
+        f"backend does not match this build (sha256 {'ab12' * 16}, expected {'cd34' * 16})",
+        f"stored {'ab12' * 16}.pdf",
+    ])
+    def test_hashes_untouched(self, redactor, line):
+        assert redactor.redact(line) == line
+
+    def test_ordinary_text_untouched(self, redactor):
+        line = "scraper.done site=infojobs jobs=42 at 2026-10-15T08:00:00 application_id=1234"
+        assert redactor.redact(line) == line
+
+
+# ---------------------------------------------------------------------------
+# Names and custom patterns
+# ---------------------------------------------------------------------------
+
+class TestConfigured:
+    def test_full_name_and_parts(self):
+        r = Redactor(names=["Ana García López"])
+        assert r.redact("CV for Ana García López") == "CV for [name]"
+        assert r.redact("signed: garcía") == "signed: [name]"
+
+    def test_short_parts_ignored(self):
+        r = Redactor(names=["Li Wu"])
+        assert r.redact("Li Wu applied") == "[name] applied"
+        assert r.redact("a list of wu-tang") == "a list of wu-tang"
+
+    def test_add_names_later(self, redactor):
+        redactor.add_names(["Marta", None, ""])
+        assert redactor.redact("hello Marta") == "hello [name]"
+
+    def test_custom_patterns(self):
+        r = Redactor(patterns=[r"NIE-\w+", "("])  # invalid pattern is skipped
+        assert r.redact("doc NIE-X1234 ok") == "doc [redacted] ok"
+
+    def test_from_env(self, monkeypatch):
+        monkeypatch.setenv(PATTERNS_ENV, '["ref-\\\\d+"]')
+        monkeypatch.setenv(NAMES_ENV, '["Pablo"]')
+        r = Redactor.from_env()
+        assert r.redact("Pablo ref-99") == "[name] [redacted]"
+
+    def test_from_env_garbage(self, monkeypatch):
+        monkeypatch.setenv(PATTERNS_ENV, "not json")
+        monkeypatch.setenv(NAMES_ENV, '{"a": 1}')
+        r = Redactor.from_env()
+        assert r.custom == [] and r.names == []
+
+
+def test_redact_value_nested(redactor):
+    event = {"event": "login", "user": "a@b.com", "tries": 2, "extra": ["612345678"]}
+    assert redactor.redact_value(event) == {
+        "event": "login", "user": "[email]", "tries": 2, "extra": ["[phone]"],
+    }
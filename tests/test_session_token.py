@@ -0,0 +1,151 @@
+"""
+Tests for backend/security/session.py
+
+With a session token set, only requests carrying it (header or ?token=)
+reach the app: others get 401, or a 4401 close for WebSockets. CORS
+preflights pass, and without a token the API stays open.
+"""
+import sys
+import os
+import asyncio
+
+# Allow running from project root without installing the package
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import pytest
+
+from backend.security.session import SessionTokenMiddleware, TOKEN_ENV
+
+TOKEN = "s3cret-launch-token"
+
+
+class App:
+    """Records whether a request got through."""
+
+    def __init__(self):
+        self.called = False
+
+    async def __call__(self, scope, receive, send):
+        self.called = True
+        if scope["type"] == "http":
+            await send({"type": "http.response.start", "status": 200, "headers": []})
+            await send({"type": "http.response.body", "body": b"ok"})
+        else:
+            await send({"type": "websocket.accept"})
+
+
+def call(scope, token=TOKEN):
+    """Runs one request through the middleware: (app reached, messages sent)."""
+    app = App()
+    sent = []
+
+    async def receive():
+        return {"type": "http.request", "body": b"", "more_body": False}
+
+    async def send(message):
+        sent.append(message)
+
+    asyncio.run(SessionTokenMiddleware(app, token=token)(scope, receive, send))
+    return app.called, sent
+
+
+def http(method="GET", headers=(), query=b""):
+    return {
+        "type": "http",
+        "method": method,
+        "path": "/api/jobs",
+        "headers": list(headers),
+        "query_string": query,
+    }
+
+
+def websocket(headers=(), query=b""):
+    return {
+        "type": "websocket",
+        "path": "/ws",
+        "headers": list(headers),
+        "query_string": query,
+    }
+
+
+def status(sent):
+    return next(m["status"] for m in sent if m["type"] == "http.response.start")
+
+
+# ---------------------------------------------------------------------------
+# HTTP
+# ---------------------------------------------------------------------------
+
+class TestHttp:
+    def test_header_token_accepted(self):
+        called, sent = call(http(headers=[(b"x-jobbot-token", TOKEN.encode())]))
+        assert called
+        assert status(sent) == 200
+
+    def test_query_token_accepted(self):
+        called, sent = call(http(query=f"since=1&token={TOKEN}".encode()))
+        assert called
+        assert status(sent) == 200
+
+    def test_missing_token_refused(self):
+        called, sent = call(http())
+        assert not called
+        assert status(sent) == 401
+
+    @pytest.mark.parametrize("scope", [
+        http(headers=[(b"x-jobbot-token", b"wrong")]),
+        http(query=b"token=wrong"),
+        http(query=b"token="),
+        http(headers=[(b"authorization", TOKEN.encode())]),
+        http(query=f"tokens={TOKEN}".encode()),
+    ])
+    def test_wrong_token_refused(self, scope):
+        called, sent = call(scope)
+        assert not called
+        assert status(sent) == 401
+
+    def test_preflight_passes(self):
+        called, sent = call(http(method="OPTIONS"))
+        assert called
+        assert status(sent) == 200
+
+
+# ---------------------------------------------------------------------------
+# WebSocket
+# ---------------------------------------------------------------------------
+
+class TestWebSocket:
+    def test_token_accepted(self):
+        called, sent = call(websocket(query=f"token={TOKEN}".encode()))
+        assert called
+        assert sent == [{"type": "websocket.accept"}]
+
+    def test_missing_token_closes_4401(self):
+        called, sent = call(websocket())
+        assert not called
+        assert sent == [{"type": "websocket.close", "code": 4401}]
+
+    def test_wrong_token_closes_4401(self):
+        called, sent = call(websocket(headers=[(b"x-jobbot-token", b"wrong")]))
+        assert not called
+        assert sent == [{"type": "websocket.close", "code": 4401}]
+
+
+# ---------------------------------------------------------------------------
+# Without a token
+# ---------------------------------------------------------------------------
+
+class TestUnset:
+    def test_open_without_token(self, monkeypatch):
+        monkeypatch.delenv(TOKEN_ENV, raising=False)
+        called, _ = call(http(), token=None)
+        assert called
+
+    def test_token_from_environment(self, monkeypatch):
+        monkeypatch.setenv(TOKEN_ENV, TOKEN)
+        assert not call(http(), token=None)[0]
+        assert call(http(query=f"token={TOKEN}".encode()), token=None)[0]
+
+    def test_lifespan_passes(self):
+        called, _ = call({"type": "lifespan"})
+        assert called